@@ -3,10 +3,10 @@ pub mod embedded;
 use anyhow::{Context, anyhow, ensure};
 use migration::Migrator;
 use migration::data::Runner;
-use postgresql_commands::{CommandBuilder, psql::PsqlBuilder};
+use postgresql_commands::{CommandBuilder, pg_dump::PgDumpBuilder, psql::PsqlBuilder};
 use sea_orm::{ConnectionTrait, Statement};
 use sea_orm_migration::prelude::{MigrationTrait, MigratorTrait};
-use std::process::Stdio;
+use std::{path::Path, process::Stdio};
 use tokio::io::{self, AsyncRead, AsyncWriteExt};
 use tracing::instrument;
 use trustify_common::{config, db};
@@ -106,6 +106,9 @@ impl<'a> Database<'a> {
     }
 
     /// Import a database from a provided DB dump.
+    ///
+    /// The dump must be a plain SQL dump, like the decompressed output of [`Self::export`]. The
+    /// database is dropped and re-created before importing.
     #[instrument(skip(r), err(level=tracing::Level::INFO))]
     pub async fn import<R>(
         database: &config::Database,
@@ -149,6 +152,52 @@ impl<'a> Database<'a> {
         Ok(db)
     }
 
+    /// Export a database as a gzip compressed, plain SQL dump.
+    ///
+    /// The dump can be restored using [`Self::import`], after decompressing it. If no output file
+    /// is provided, the dump is written to stdout.
+    #[instrument(err(level=tracing::Level::INFO))]
+    pub async fn export(
+        database: &config::Database,
+        output: Option<&Path>,
+    ) -> Result<(), anyhow::Error> {
+        let builder = match &database.url {
+            // a connection string is accepted in place of the database name
+            Some(url) => PgDumpBuilder::new().dbname(url),
+            None => PgDumpBuilder::new()
+                .dbname(&database.name)
+                .host(&database.host)
+                .port(database.port)
+                .username(&database.username)
+                .pg_password(&database.password.0),
+        };
+
+        // psql can't restore the custom format, so stick to plain SQL for the import
+        let builder = builder.format("plain").compress("6");
+        let builder = match output {
+            Some(output) => builder.file(output),
+            None => builder,
+        };
+
+        let result = builder
+            .build_tokio()
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("failed to run pg_dump")?
+            .wait_with_output()
+            .await?;
+
+        ensure!(
+            result.status.success(),
+            "pg_dump failed ({}): {}",
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        );
+
+        Ok(())
+    }
+
     pub async fn data_migrate(&self, runner: Runner) -> Result<(), anyhow::Error> {
         runner.run::<Migrator>().await
     }
@@ -627,7 +627,7 @@ impl HttpServerBuilder {
 
             let metrics = match self.metrics {
                 Metrics::Disabled => None,
-                Metrics::Enabled => {
+                Metrics::Enabled | Metrics::Prometheus => {
                     let request_metrics = RequestMetrics::builder()
                         .with_route_formatter(DefaultRootRouteFormatter)
                         .build();
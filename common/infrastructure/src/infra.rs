@@ -1,6 +1,6 @@
 use crate::{
     health::{Checks, HealthChecks},
-    otel::{Metrics as OtelMetrics, Tracing, init_metrics, init_tracing},
+    otel::{Metrics as OtelMetrics, PrometheusExporter, Tracing, init_metrics, init_tracing},
 };
 use actix_web::{
     App, HttpRequest, HttpResponse, HttpServer, Responder, http::uri::Builder, middleware::Logger,
@@ -17,6 +17,7 @@ use tokio::signal;
 use tokio::signal::unix::{SignalKind, signal};
 
 const DEFAULT_BIND_ADDR: &str = "localhost:9010";
+const DEFAULT_METRICS_BIND_ADDR: &str = "localhost:9090";
 
 /// Infrastructure
 #[derive(Clone, Debug, clap::Parser)]
@@ -41,6 +42,9 @@ pub struct InfrastructureConfig {
     /// Enable metrics
     #[arg(long, env, default_value_t = OtelMetrics::Disabled)]
     pub metrics: OtelMetrics,
+    /// Bind address of the Prometheus metrics endpoint, when metrics are set to `prometheus`
+    #[arg(long, env, default_value_t = DEFAULT_METRICS_BIND_ADDR.into())]
+    pub metrics_bind: String,
 }
 
 impl Default for InfrastructureConfig {
@@ -51,6 +55,7 @@ impl Default for InfrastructureConfig {
             infrastructure_workers: 1,
             tracing: Tracing::Disabled,
             metrics: OtelMetrics::Disabled,
+            metrics_bind: DEFAULT_METRICS_BIND_ADDR.into(),
         }
     }
 }
@@ -98,6 +103,12 @@ async fn readiness(health: web::Data<HealthChecks>) -> impl Responder + use<> {
     run_checks(&health.readiness).await
 }
 
+async fn metrics(exporter: web::Data<PrometheusExporter>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(exporter.render())
+}
+
 async fn run_checks(checks: &Checks) -> impl Responder + use<> {
     let checks = checks.run().await;
 
@@ -186,6 +197,32 @@ impl Infrastructure {
         }))
     }
 
+    /// Serve the metrics collected by the exporter, for being scraped by Prometheus
+    fn start_metrics(
+        bind: String,
+        exporter: PrometheusExporter,
+    ) -> anyhow::Result<Pin<Box<dyn Future<Output = anyhow::Result<()>>>>> {
+        let exporter = web::Data::new(exporter);
+
+        let http = HttpServer::new(move || {
+            App::new()
+                .app_data(exporter.clone())
+                .service(web::resource("/metrics").to(metrics))
+        })
+        .workers(1)
+        .bind(bind)
+        .context("Failed to bind metrics endpoint")?;
+
+        Ok(Box::pin(async move {
+            log::info!("Running metrics endpoint on:");
+            for (addr, scheme) in http.addrs_with_scheme() {
+                log::info!("   {scheme}://{addr}/metrics");
+            }
+            http.run().await.context("Failed to run metrics endpoint")?;
+            Ok::<_, anyhow::Error>(())
+        }))
+    }
+
     pub async fn run_with_config<I, IFut, M, MFut, D>(
         self,
         id: &'static str,
@@ -206,7 +243,10 @@ impl Infrastructure {
         );
 
         init_tracing(id, self.config.tracing);
-        init_metrics(id, self.config.metrics);
+        let exporter = init_metrics(id, self.config.metrics);
+        let metrics = exporter
+            .map(|exporter| Self::start_metrics(self.config.metrics_bind.clone(), exporter))
+            .transpose()?;
 
         let init_data = init(InitContext {
             health: self.health.clone(),
@@ -220,9 +260,12 @@ impl Infrastructure {
         let runner = Box::pin(self.start_internal(configurator).await?);
         let sigint = Box::pin(async { signal::ctrl_c().await.context("termination failed") });
 
-        #[allow(unused_mut)]
         let mut tasks = vec![runner, main, sigint];
 
+        if let Some(metrics) = metrics {
+            tasks.push(metrics);
+        }
+
         #[cfg(unix)]
         {
             let sigterm = Box::pin(async {
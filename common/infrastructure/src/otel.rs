@@ -1,3 +1,7 @@
+mod prometheus;
+
+pub use prometheus::PrometheusExporter;
+
 use core::fmt;
 use opentelemetry::{
     Context,
@@ -15,12 +19,15 @@ use opentelemetry_sdk::{
     trace::{Sampler, Sampler::ParentBased, SdkTracerProvider},
 };
 use reqwest::RequestBuilder;
-use std::sync::Once;
+use std::{sync::Once, time::Duration};
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::{
     EnvFilter, field::MakeExt, layer::SubscriberExt, util::SubscriberInitExt,
 };
 
+/// How often metrics are collected for being scraped by Prometheus
+const PROMETHEUS_COLLECT_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Default)]
 pub enum Metrics {
     #[clap(name = "disabled")]
@@ -28,6 +35,9 @@ pub enum Metrics {
     Disabled,
     #[clap(name = "enabled")]
     Enabled,
+    /// Expose metrics to be scraped by Prometheus, instead of exporting them
+    #[clap(name = "prometheus")]
+    Prometheus,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Default)]
@@ -44,6 +54,7 @@ impl fmt::Display for Metrics {
         match self {
             Metrics::Disabled => write!(f, "disabled"),
             Metrics::Enabled => write!(f, "enabled"),
+            Metrics::Prometheus => write!(f, "prometheus"),
         }
     }
 }
@@ -133,9 +144,15 @@ pub fn init_tracing(name: &str, tracing: Tracing) {
     }
 }
 
-pub fn init_metrics(name: &'static str, metrics: Metrics) {
-    if let Metrics::Enabled = metrics {
-        init_otlp_metrics(name);
+/// Initialize metrics, returning the exporter to scrape if Prometheus is used
+pub fn init_metrics(name: &'static str, metrics: Metrics) -> Option<PrometheusExporter> {
+    match metrics {
+        Metrics::Disabled => None,
+        Metrics::Enabled => {
+            init_otlp_metrics(name);
+            None
+        }
+        Metrics::Prometheus => Some(init_prometheus_metrics(name)),
     }
 }
 
@@ -163,6 +180,29 @@ fn init_otlp_metrics(name: &str) {
     set_meter_provider(provider);
 }
 
+fn init_prometheus_metrics(name: &str) -> PrometheusExporter {
+    let exporter = PrometheusExporter::default();
+
+    let reader = PeriodicReader::builder(exporter.clone())
+        .with_interval(PROMETHEUS_COLLECT_INTERVAL)
+        .build();
+
+    let resource = Resource::builder()
+        .with_service_name(name.to_string())
+        .build();
+
+    let provider = SdkMeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(resource)
+        .build();
+
+    println!("Exposing metrics for Prometheus.");
+
+    set_meter_provider(provider);
+
+    exporter
+}
+
 fn init_otlp_tracing(name: &str) {
     set_text_map_propagator(TraceContextPropagator::new());
 
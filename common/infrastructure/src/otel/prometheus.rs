@@ -0,0 +1,286 @@
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::{
+    error::OTelSdkResult,
+    metrics::{
+        Temporality,
+        data::{AggregatedMetrics, MetricData, ResourceMetrics},
+        exporter::PushMetricExporter,
+    },
+};
+use parking_lot::RwLock;
+use std::{collections::BTreeMap, fmt::Display, sync::Arc, time::Duration};
+
+/// Keeps the most recently collected metrics, rendered in the Prometheus text exposition format
+#[derive(Clone, Debug, Default)]
+pub struct PrometheusExporter {
+    rendered: Arc<RwLock<String>>,
+}
+
+impl PrometheusExporter {
+    /// Get the most recently collected metrics
+    pub fn render(&self) -> String {
+        self.rendered.read().clone()
+    }
+}
+
+impl PushMetricExporter for PrometheusExporter {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        *self.rendered.write() = render(metrics);
+        Ok(())
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn temporality(&self) -> Temporality {
+        Temporality::Cumulative
+    }
+}
+
+/// All samples of a metric, which Prometheus expects to be grouped by the metric name
+struct Family {
+    kind: &'static str,
+    help: String,
+    samples: Vec<String>,
+}
+
+#[derive(Default)]
+struct Families(BTreeMap<String, Family>);
+
+impl Families {
+    fn family(&mut self, name: &str, kind: &'static str, help: &str) -> &mut Family {
+        self.0.entry(name.to_string()).or_insert_with(|| Family {
+            kind,
+            help: help.to_string(),
+            samples: vec![],
+        })
+    }
+
+    fn add<T: Display + Copy>(
+        &mut self,
+        name: &str,
+        help: &str,
+        scope: &KeyValue,
+        data: &MetricData<T>,
+    ) {
+        match data {
+            MetricData::Gauge(gauge) => {
+                let family = self.family(name, "gauge", help);
+                for point in gauge.data_points() {
+                    let labels = labels(scope, point.attributes());
+                    family.samples.push(sample(name, &labels, point.value()));
+                }
+            }
+            MetricData::Sum(sum) => {
+                let (kind, sample_name) = match sum.is_monotonic() {
+                    true => ("counter", format!("{name}_total")),
+                    false => ("gauge", name.to_string()),
+                };
+                let family = self.family(name, kind, help);
+                for point in sum.data_points() {
+                    let labels = labels(scope, point.attributes());
+                    family
+                        .samples
+                        .push(sample(&sample_name, &labels, point.value()));
+                }
+            }
+            MetricData::Histogram(histogram) => {
+                let family = self.family(name, "histogram", help);
+                for point in histogram.data_points() {
+                    let labels = labels(scope, point.attributes());
+
+                    let mut cumulative = 0;
+                    for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                        cumulative += count;
+                        let labels = with_label(&labels, "le", bound.to_string());
+                        family
+                            .samples
+                            .push(sample(&format!("{name}_bucket"), &labels, cumulative));
+                    }
+                    let labels_inf = with_label(&labels, "le", "+Inf".to_string());
+                    family.samples.push(sample(
+                        &format!("{name}_bucket"),
+                        &labels_inf,
+                        point.count(),
+                    ));
+
+                    family
+                        .samples
+                        .push(sample(&format!("{name}_sum"), &labels, point.sum()));
+                    family
+                        .samples
+                        .push(sample(&format!("{name}_count"), &labels, point.count()));
+                }
+            }
+            MetricData::ExponentialHistogram(_) => {
+                // not created by the default aggregations, and not supported by the text format
+            }
+        }
+    }
+}
+
+/// Render metrics in the Prometheus text exposition format
+fn render(metrics: &ResourceMetrics) -> String {
+    let mut families = Families::default();
+
+    for scope_metrics in metrics.scope_metrics() {
+        // the same metric name may be used by different meters
+        let scope = KeyValue::new("otel_scope_name", scope_metrics.scope().name().to_string());
+
+        for metric in scope_metrics.metrics() {
+            let name = metric_name(metric.name(), metric.unit());
+            let help = metric.description();
+
+            match metric.data() {
+                AggregatedMetrics::F64(data) => families.add(&name, help, &scope, data),
+                AggregatedMetrics::U64(data) => families.add(&name, help, &scope, data),
+                AggregatedMetrics::I64(data) => families.add(&name, help, &scope, data),
+            }
+        }
+    }
+
+    let mut result = String::new();
+
+    for (name, family) in families.0 {
+        if !family.help.is_empty() {
+            result.push_str(&format!("# HELP {name} {}\n", escape(&family.help, false)));
+        }
+        result.push_str(&format!("# TYPE {name} {}\n", family.kind));
+        for sample in family.samples {
+            result.push_str(&sample);
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// Build the Prometheus name of a metric, replacing unsupported characters and adding the unit
+fn metric_name(name: &str, unit: &str) -> String {
+    let mut name = sanitize(name);
+
+    let suffix = match unit {
+        "s" => Some("seconds"),
+        "b" | "By" => Some("bytes"),
+        _ => None,
+    };
+
+    if let Some(suffix) = suffix
+        && !name.ends_with(suffix)
+    {
+        name.push('_');
+        name.push_str(suffix);
+    }
+
+    name
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn labels<'a>(
+    scope: &'a KeyValue,
+    attributes: impl Iterator<Item = &'a KeyValue>,
+) -> Vec<(String, String)> {
+    std::iter::once(scope)
+        .chain(attributes)
+        .map(|kv| (sanitize(kv.key.as_str()), kv.value.as_str().into_owned()))
+        .collect()
+}
+
+fn with_label(labels: &[(String, String)], key: &str, value: String) -> Vec<(String, String)> {
+    let mut labels = labels.to_vec();
+    labels.push((key.to_string(), value));
+    labels
+}
+
+fn sample(name: &str, labels: &[(String, String)], value: impl Display) -> String {
+    let labels = labels
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{}\"", escape(value, true)))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{name}{{{labels}}} {value}")
+}
+
+/// Escape a label value or help text, only label values need quotes to be escaped
+fn escape(value: &str, quotes: bool) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '"' if quotes => result.push_str("\\\""),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+
+    #[test]
+    fn render_metrics() -> anyhow::Result<()> {
+        let exporter = PrometheusExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+
+        let meter = provider.meter("test");
+        meter
+            .u64_counter("cache.hits")
+            .with_description("Number of cache hits")
+            .build()
+            .add(3, &[KeyValue::new("cache", "graph")]);
+        meter
+            .f64_histogram("query_duration")
+            .with_unit("s")
+            .with_boundaries(vec![0.1, 1.0])
+            .build()
+            .record(0.5, &[]);
+
+        provider.force_flush()?;
+
+        let rendered = exporter.render();
+        assert!(rendered.contains("# HELP cache_hits Number of cache hits\n"));
+        assert!(rendered.contains("# TYPE cache_hits counter\n"));
+        assert!(rendered.contains(r#"cache_hits_total{otel_scope_name="test",cache="graph"} 3"#));
+        assert!(rendered.contains("# TYPE query_duration_seconds histogram\n"));
+        assert!(
+            rendered
+                .contains(r#"query_duration_seconds_bucket{otel_scope_name="test",le="0.1"} 0"#)
+        );
+        assert!(
+            rendered.contains(r#"query_duration_seconds_bucket{otel_scope_name="test",le="1"} 1"#)
+        );
+        assert!(
+            rendered
+                .contains(r#"query_duration_seconds_bucket{otel_scope_name="test",le="+Inf"} 1"#)
+        );
+        assert!(rendered.contains(r#"query_duration_seconds_count{otel_scope_name="test"} 1"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape("a\"b\\c\nd", true), r#"a\"b\\c\nd"#);
+        assert_eq!(escape("a\"b", false), "a\"b");
+    }
+}
@@ -44,6 +44,7 @@ const ENV_DB_RO_ACQUIRE_TIMEOUT: &str = "TRUSTD_DB_RO_ACQUIRE_TIMEOUT";
 const ENV_DB_RO_MAX_LIFETIME: &str = "TRUSTD_DB_RO_MAX_LIFETIME";
 const ENV_DB_RO_IDLE_TIMEOUT: &str = "TRUSTD_DB_RO_IDLE_TIMEOUT";
 const ENV_DB_RO_SSLMODE: &str = "TRUSTD_DB_RO_SSLMODE";
+const ENV_DB_READ_REPLICA_URL: &str = "TRUSTD_DB_READ_REPLICA_URL";
 
 /// PostgreSQL SSL mode
 #[derive(Copy, Clone, Debug, Default, clap::ValueEnum, Eq, PartialEq, strum::Display)]
@@ -213,6 +214,15 @@ pub struct DatabaseReadOnly {
     pub max_lifetime: Option<u64>,
     #[arg(id = "db-ro-idle-timeout", long, env = ENV_DB_RO_IDLE_TIMEOUT)]
     pub idle_timeout: Option<u64>,
+    /// Complete URLs of read replicas, comma separated. If set, read-only transactions are
+    /// distributed across the replicas, round-robin, instead of using the read-only database.
+    #[arg(
+        id = "db-read-replica-url",
+        long,
+        env = ENV_DB_READ_REPLICA_URL,
+        value_delimiter = ','
+    )]
+    pub replica_urls: Vec<String>,
 }
 
 impl DatabaseReadOnly {
@@ -240,6 +250,19 @@ impl DatabaseReadOnly {
             idle_timeout: self.idle_timeout.unwrap_or(fallback.idle_timeout),
         }
     }
+
+    /// Builds a `Database` config for each read replica, using the R/O config for everything
+    /// but the URL.
+    pub fn to_replica_configs(&self, fallback: &Database) -> Vec<Database> {
+        let config = self.to_database_config(fallback);
+        self.replica_urls
+            .iter()
+            .map(|url| Database {
+                url: Some(url.clone()),
+                ..config.clone()
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -400,4 +423,39 @@ mod test {
         assert_eq!(result.host, rw.host);
         assert_eq!(result.port, rw.port);
     }
+
+    /// Verify that each replica URL results in a config, inheriting all other R/O values.
+    #[test]
+    fn replica_configs() {
+        // given: an R/O config with a username and two replicas
+        let rw = rw_default();
+        let ro = DatabaseReadOnly {
+            username: Some("readonly_user".into()),
+            replica_urls: vec![
+                "postgres://replica-1:5432/trustify".into(),
+                "postgres://replica-2:5432/trustify".into(),
+            ],
+            ..Default::default()
+        };
+
+        // when: building the replica configs
+        let result = ro.to_replica_configs(&rw);
+
+        // then: there is one config per replica, inheriting the R/O values
+        assert_eq!(
+            result
+                .iter()
+                .map(|config| config.url.as_deref())
+                .collect::<Vec<_>>(),
+            [
+                Some("postgres://replica-1:5432/trustify"),
+                Some("postgres://replica-2:5432/trustify")
+            ]
+        );
+        assert!(
+            result
+                .iter()
+                .all(|config| config.username == "readonly_user")
+        );
+    }
 }
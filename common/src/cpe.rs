@@ -450,7 +450,7 @@ fn unescape_cpe23(s: &str) -> String {
 
 /// Percent-encode a decoded component value using CPE 2.2 URI syntax,
 /// mapping the `?`/`*` wildcards to their `%01`/`%02` special encodings.
-fn encode_uri_component(value: &str) -> String {
+pub fn encode_uri_component(value: &str) -> String {
     let mut out = String::with_capacity(value.len());
     for c in value.chars() {
         match c {
@@ -0,0 +1,284 @@
+//! Keyset pagination, continuing after the last item of the previous page instead of skipping
+//! over an offset.
+
+use crate::db::pagination_cache::{LimitError, PaginationCache};
+use base64::{Engine, prelude::BASE64_URL_SAFE_NO_PAD};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, Iterable, ModelTrait, PrimaryKeyToColumn,
+    PrimaryKeyTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Select, SelectThree,
+    SelectTwo,
+};
+use sea_query::{OrderedStatement, SelectStatement};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+use uuid::Uuid;
+
+/// The position in a list, which is paginated by the ID of its items.
+///
+/// Clients must treat the cursor as opaque. An empty cursor starts at the beginning of the list.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    /// The ID of the last item of the previous page
+    pub last: Option<Uuid>,
+}
+
+/// The content of an encoded cursor
+#[derive(Serialize, Deserialize)]
+struct Encoded {
+    last: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid cursor")]
+pub struct CursorError;
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some(last) = self.last else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_vec(&Encoded { last }).map_err(|_| fmt::Error)?;
+        f.write_str(&BASE64_URL_SAFE_NO_PAD.encode(json))
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = CursorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let json = BASE64_URL_SAFE_NO_PAD.decode(s).map_err(|_| CursorError)?;
+        let Encoded { last } = serde_json::from_slice(&json).map_err(|_| CursorError)?;
+
+        Ok(Self { last: Some(last) })
+    }
+}
+
+impl Serialize for Cursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A page of items, along with the cursor to the next page, if there is one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<Cursor>,
+}
+
+/// A query which can be paginated by the primary key of its (first) entity.
+pub trait CursorQuery: Clone {
+    type Item;
+
+    /// Limit the query to the items following the cursor, ordered by their key.
+    ///
+    /// This replaces any other order of the query, as that would skip items.
+    fn after(self, cursor: Cursor) -> Self;
+
+    /// Fetch up to `limit` items.
+    fn fetch<C: ConnectionTrait>(
+        self,
+        db: &C,
+        limit: u64,
+    ) -> impl Future<Output = Result<Vec<Self::Item>, DbErr>>;
+
+    /// The cursor pointing to the items following this one.
+    fn cursor(item: &Self::Item) -> Cursor;
+
+    /// Paginate the query using a cursor, rather than an offset.
+    fn cursor_limiting<'a, C: ConnectionTrait>(
+        self,
+        db: &'a C,
+        limit: u64,
+        cache: &PaginationCache,
+    ) -> Result<CursorLimiter<'a, C, Self>, LimitError> {
+        Ok(CursorLimiter {
+            db,
+            query: self,
+            limit: cache.check_limit(limit)?,
+        })
+    }
+}
+
+/// Fetches pages of a query, continuing after the last item of the previous page.
+pub struct CursorLimiter<'a, C, Q> {
+    db: &'a C,
+    query: Q,
+    limit: u64,
+}
+
+pub trait CursorLimiterTrait {
+    type Item;
+
+    /// Fetch the page following the cursor, or the first page if there is no cursor.
+    fn next_page(
+        &self,
+        cursor: Option<Cursor>,
+    ) -> impl Future<Output = Result<CursorPage<Self::Item>, DbErr>>;
+}
+
+impl<C, Q> CursorLimiterTrait for CursorLimiter<'_, C, Q>
+where
+    C: ConnectionTrait,
+    Q: CursorQuery,
+{
+    type Item = Q::Item;
+
+    async fn next_page(&self, cursor: Option<Cursor>) -> Result<CursorPage<Q::Item>, DbErr> {
+        if self.limit == 0 {
+            return Ok(CursorPage {
+                items: vec![],
+                next_cursor: None,
+            });
+        }
+
+        // fetch one more item than requested, to find out if there is another page
+        let mut items = self
+            .query
+            .clone()
+            .after(cursor.unwrap_or_default())
+            .fetch(self.db, self.limit.saturating_add(1))
+            .await?;
+
+        let next_cursor = match items.len() as u64 > self.limit {
+            true => {
+                items.truncate(self.limit as usize);
+                items.last().map(Q::cursor)
+            }
+            false => None,
+        };
+
+        Ok(CursorPage { items, next_cursor })
+    }
+}
+
+fn order_after<E, Q>(mut query: Q, cursor: Cursor) -> Q
+where
+    E: EntityTrait,
+    Q: QueryFilter + QueryOrder + QueryTrait<QueryStatement = SelectStatement>,
+{
+    QueryTrait::query(&mut query).clear_order_by();
+
+    for column in E::PrimaryKey::iter().map(PrimaryKeyToColumn::into_column) {
+        if let Some(last) = cursor.last {
+            query = query.filter(column.gt(last));
+        }
+        query = query.order_by_asc(column);
+    }
+
+    query
+}
+
+fn cursor_of<E: EntityTrait>(model: &E::Model) -> Cursor {
+    Cursor {
+        last: E::PrimaryKey::iter().next().and_then(|key| {
+            <Uuid as sea_query::ValueType>::try_from(model.get(key.into_column())).ok()
+        }),
+    }
+}
+
+impl<E> CursorQuery for Select<E>
+where
+    E: EntityTrait,
+    E::PrimaryKey: PrimaryKeyTrait<ValueType = Uuid>,
+{
+    type Item = E::Model;
+
+    fn after(self, cursor: Cursor) -> Self {
+        order_after::<E, _>(self, cursor)
+    }
+
+    async fn fetch<C: ConnectionTrait>(self, db: &C, limit: u64) -> Result<Vec<E::Model>, DbErr> {
+        self.limit(limit).all(db).await
+    }
+
+    fn cursor(item: &Self::Item) -> Cursor {
+        cursor_of::<E>(item)
+    }
+}
+
+impl<E1, E2> CursorQuery for SelectTwo<E1, E2>
+where
+    E1: EntityTrait,
+    E2: EntityTrait,
+    E1::PrimaryKey: PrimaryKeyTrait<ValueType = Uuid>,
+{
+    type Item = (E1::Model, Option<E2::Model>);
+
+    fn after(self, cursor: Cursor) -> Self {
+        order_after::<E1, _>(self, cursor)
+    }
+
+    async fn fetch<C: ConnectionTrait>(self, db: &C, limit: u64) -> Result<Vec<Self::Item>, DbErr> {
+        self.limit(limit).all(db).await
+    }
+
+    fn cursor(item: &Self::Item) -> Cursor {
+        cursor_of::<E1>(&item.0)
+    }
+}
+
+impl<E1, E2, E3> CursorQuery for SelectThree<E1, E2, E3>
+where
+    E1: EntityTrait,
+    E2: EntityTrait,
+    E3: EntityTrait,
+    E1::PrimaryKey: PrimaryKeyTrait<ValueType = Uuid>,
+{
+    type Item = (E1::Model, Option<E2::Model>, Option<E3::Model>);
+
+    fn after(self, cursor: Cursor) -> Self {
+        order_after::<E1, _>(self, cursor)
+    }
+
+    async fn fetch<C: ConnectionTrait>(self, db: &C, limit: u64) -> Result<Vec<Self::Item>, DbErr> {
+        self.limit(limit).all(db).await
+    }
+
+    fn cursor(item: &Self::Item) -> Cursor {
+        cursor_of::<E1>(&item.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encoding() {
+        let cursor = Cursor {
+            last: Some(Uuid::from_u128(0x1234)),
+        };
+
+        let encoded = cursor.to_string();
+        assert_eq!(Cursor::from_str(&encoded), Ok(cursor));
+
+        assert_eq!(Cursor::default().to_string(), "");
+        assert_eq!(Cursor::from_str(""), Ok(Cursor::default()));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Cursor::from_str("not a cursor"), Err(CursorError));
+        assert_eq!(
+            Cursor::from_str(&BASE64_URL_SAFE_NO_PAD.encode(r#"{"last":42}"#)),
+            Err(CursorError)
+        );
+    }
+}
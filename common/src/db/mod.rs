@@ -1,4 +1,5 @@
 pub mod chunk;
+pub mod cursor;
 pub mod limiter;
 pub mod multi_model;
 pub mod pagination_cache;
@@ -6,12 +7,15 @@ pub mod query;
 
 mod create;
 mod func;
+mod timeout;
 
 pub use create::*;
 pub use func::*;
+pub use timeout::*;
 
 use actix_web::{HttpResponse, ResponseError};
 use anyhow::Context;
+use opentelemetry::{KeyValue, global};
 use reqwest::Url;
 use sea_orm::{
     AccessMode, ConnectOptions, ConnectionTrait, DatabaseConnection, DatabaseTransaction,
@@ -24,6 +28,10 @@ use std::{
     ops::{Deref, DerefMut},
     pin::Pin,
     str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
     time::Duration,
 };
 use tracing::instrument;
@@ -38,6 +46,12 @@ use tracing::instrument;
 #[async_trait::async_trait]
 pub trait DatabaseExt {
     async fn begin_read(&self) -> Result<DatabaseTransaction, DbErr>;
+
+    /// Begin a read transaction, cancelling statements which take longer than the timeout.
+    async fn begin_read_with_timeout(
+        &self,
+        timeout: Option<QueryTimeout>,
+    ) -> Result<DatabaseTransaction, DbErr>;
 }
 
 #[async_trait::async_trait]
@@ -46,11 +60,23 @@ where
     T: TransactionTrait + Sync,
 {
     async fn begin_read(&self) -> Result<DatabaseTransaction, DbErr> {
-        self.begin_with_config(
-            Some(IsolationLevel::RepeatableRead),
-            Some(AccessMode::ReadOnly),
-        )
-        .await
+        self.begin_read_with_timeout(None).await
+    }
+
+    async fn begin_read_with_timeout(
+        &self,
+        timeout: Option<QueryTimeout>,
+    ) -> Result<DatabaseTransaction, DbErr> {
+        let tx = self
+            .begin_with_config(
+                Some(IsolationLevel::RepeatableRead),
+                Some(AccessMode::ReadOnly),
+            )
+            .await?;
+        if let Some(timeout) = timeout {
+            timeout.apply(&tx).await?;
+        }
+        Ok(tx)
     }
 }
 
@@ -62,6 +88,8 @@ pub trait DatabaseErrors {
     fn is_read_only(&self) -> bool;
     /// return `true` if the error is a foreign key constraint violation
     fn is_foreign_key_violation(&self) -> bool;
+    /// return `true` if the statement got cancelled, e.g. by exceeding the statement timeout
+    fn is_query_canceled(&self) -> bool;
 }
 
 impl DatabaseErrors for DbErr {
@@ -94,6 +122,16 @@ impl DatabaseErrors for DbErr {
             _ => false,
         }
     }
+
+    fn is_query_canceled(&self) -> bool {
+        match self {
+            DbErr::Query(RuntimeErr::SqlxError(sqlx::error::Error::Database(err)))
+            | DbErr::Exec(RuntimeErr::SqlxError(sqlx::error::Error::Database(err))) => {
+                err.code().as_deref() == Some("57014")
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -131,9 +169,21 @@ impl Database {
         opt.max_lifetime(Duration::from_secs(database.max_lifetime));
         opt.idle_timeout(Duration::from_secs(database.idle_timeout));
 
-        let db = sea_orm::Database::connect(opt).await?;
+        let mut db = sea_orm::Database::connect(opt).await?;
         let name = database.name.clone();
 
+        let query_duration = crate::metrics::duration_histogram(
+            &global::meter("Database"),
+            "db_query_duration",
+            "Duration of database statements",
+        );
+        db.set_metric_callback(move |info| {
+            query_duration.record(
+                info.elapsed.as_secs_f64(),
+                &[KeyValue::new("failed", info.failed)],
+            );
+        });
+
         Ok(Self { db, name })
     }
 
@@ -566,12 +616,16 @@ pub enum DbError {
     Unavailable,
     #[error("cannot open a read-write transaction on a read-only connection")]
     ReadOnly,
+    #[error("the query took too long")]
+    Timeout,
 }
 
 impl From<DbErr> for DbError {
     fn from(err: DbErr) -> Self {
         if err.is_read_only() {
             Self::Unavailable
+        } else if err.is_query_canceled() {
+            Self::Timeout
         } else {
             Self::Database(err)
         }
@@ -585,6 +639,8 @@ impl ResponseError for DbError {
                 .json(crate::error::ErrorInformation::new("Unavailable", self)),
             Self::ReadOnly => HttpResponse::Forbidden()
                 .json(crate::error::ErrorInformation::new("ReadOnly", self)),
+            Self::Timeout => HttpResponse::GatewayTimeout()
+                .json(crate::error::ErrorInformation::new("Timeout", self)),
             Self::Database(err) => {
                 log::warn!("{err}");
                 HttpResponse::InternalServerError()
@@ -599,37 +655,97 @@ impl ResponseError for DbError {
 /// Does not implement `ConnectionTrait` directly — callers must use `begin()` to obtain
 /// a `DatabaseTransaction` opened with `AccessMode::ReadOnly`. All operations then go
 /// through that transaction, which PostgreSQL enforces as read-only.
+///
+/// If a query timeout is configured, statements of those transactions taking longer get
+/// cancelled.
+///
+/// If read replicas are configured, transactions are distributed across them, round-robin,
+/// instead of using the wrapped database.
 #[derive(Clone, Debug)]
-pub struct ReadOnly(Database);
+pub struct ReadOnly {
+    db: Database,
+    replicas: Arc<[Database]>,
+    next: Arc<AtomicUsize>,
+    timeout: Option<QueryTimeout>,
+}
 
 impl ReadOnly {
     /// Wraps an existing database connection for read-only access.
     pub fn new(db: Database) -> Self {
-        Self(db)
+        Self {
+            db,
+            replicas: Arc::new([]),
+            next: Default::default(),
+            timeout: None,
+        }
+    }
+
+    /// Set the read replicas to use for read-only transactions.
+    pub fn with_replicas(mut self, replicas: Vec<Database>) -> Self {
+        self.replicas = replicas.into();
+        self
+    }
+
+    /// Set the timeout of the statements of read-only transactions.
+    pub fn with_query_timeout(mut self, timeout: Option<QueryTimeout>) -> Self {
+        self.timeout = timeout;
+        self
     }
 
     /// Get the name of the database.
     pub fn name(&self) -> &str {
-        self.0.name()
+        self.db.name()
     }
 
-    /// Ping the database for health checks.
+    /// Ping the database, and all replicas, for health checks.
     pub async fn ping(&self) -> anyhow::Result<()> {
-        self.0.ping().await
+        self.db.ping().await?;
+        for replica in self.replicas.iter() {
+            replica.ping().await?;
+        }
+        Ok(())
     }
 
     /// Close the connection.
+    ///
+    /// The connections of replicas get closed once the last clone of this wrapper is dropped.
     pub async fn close(self) -> anyhow::Result<()> {
-        self.0.close().await
+        self.db.close().await
+    }
+
+    /// The database to use for the next transaction.
+    fn connection(&self) -> &Database {
+        if self.replicas.is_empty() {
+            return &self.db;
+        }
+
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        &self.replicas[next % self.replicas.len()]
     }
 
     /// Begins a read-only transaction.
     #[instrument(skip(self), err(level=tracing::Level::INFO))]
     pub async fn begin(&self) -> Result<DatabaseTransaction, DbError> {
-        Ok(self
-            .0
+        self.begin_with_config(None, None).await
+    }
+
+    /// Begins a read-only transaction, using a different timeout than the configured one.
+    ///
+    /// Use this for operations which are known to take longer than regular queries. `None`
+    /// falls back to the configured timeout.
+    #[instrument(skip(self), err(level=tracing::Level::INFO))]
+    pub async fn begin_with_timeout(
+        &self,
+        timeout: Option<QueryTimeout>,
+    ) -> Result<DatabaseTransaction, DbError> {
+        let tx = self
+            .connection()
             .begin_with_config(None, Some(AccessMode::ReadOnly))
-            .await?)
+            .await?;
+        if let Some(timeout) = timeout.or(self.timeout) {
+            timeout.apply(&tx).await?;
+        }
+        Ok(tx)
     }
 
     /// Begins a read-only transaction with the given isolation level.
@@ -642,7 +758,14 @@ impl ReadOnly {
         access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbError> {
         let mode = Self::validate_access_mode(access_mode)?;
-        Ok(self.0.begin_with_config(isolation_level, mode).await?)
+        let tx = self
+            .connection()
+            .begin_with_config(isolation_level, mode)
+            .await?;
+        if let Some(timeout) = self.timeout {
+            timeout.apply(&tx).await?;
+        }
+        Ok(tx)
     }
 
     /// Validates the access mode, rejecting explicit read-write requests.
@@ -655,7 +778,7 @@ impl ReadOnly {
 
     /// Extracts the inner Database, consuming this wrapper.
     pub fn into_inner(self) -> Database {
-        self.0
+        self.db
     }
 }
 
@@ -723,4 +846,22 @@ mod test {
         let result = ReadOnly::validate_access_mode(Some(AccessMode::ReadOnly));
         assert_eq!(result.unwrap(), Some(AccessMode::ReadOnly));
     }
+
+    #[test]
+    fn read_only_replicas_round_robin() {
+        let database = |name: &str| Database {
+            db: DatabaseConnection::Disconnected,
+            name: name.to_string(),
+        };
+
+        let ro = ReadOnly::new(database("primary"));
+        assert_eq!(ro.connection().name(), "primary");
+        assert_eq!(ro.connection().name(), "primary");
+
+        let ro = ro.with_replicas(vec![database("replica-1"), database("replica-2")]);
+        let names = (0..4)
+            .map(|_| ro.connection().name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["replica-1", "replica-2", "replica-1", "replica-2"]);
+    }
 }
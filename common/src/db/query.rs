@@ -55,39 +55,50 @@ impl Query {
     /// values.
     pub fn apply(&self, context: impl Context) -> bool {
         use Operator::*;
-        self.parse().iter().all(|c| match c {
-            Constraint {
-                field: Some(f),
-                op: Some(o),
-                value: vs,
-            } => context.get(f).is_some_and(|field| match o {
-                Equal => vs.iter().any(|v| field.eq(v)),
-                NotEqual => vs.iter().all(|v| field.ne(v)),
-                Like => vs.iter().any(|v| field.like(v)),
-                NotLike => vs.iter().all(|v| !field.like(v)),
-                GreaterThan => vs.iter().all(|v| field.gt(v)),
-                GreaterThanOrEqual => vs.iter().all(|v| field.ge(v)),
-                LessThan => vs.iter().all(|v| field.lt(v)),
-                LessThanOrEqual => vs.iter().all(|v| field.le(v)),
-                _ => false,
-            }),
-            Constraint {
-                field: None,
-                value: vs,
-                ..
-            } => context
-                .values()
-                .any(|field| vs.iter().any(|v| field.like(v))),
-            _ => false,
-        })
+        let groups = self.parse();
+        groups.is_empty()
+            || groups.iter().any(|group| {
+                group.iter().all(|c| match c {
+                    Constraint {
+                        field: Some(f),
+                        op: Some(o),
+                        value: vs,
+                    } => context.get(f).is_some_and(|field| match o {
+                        Equal => vs.iter().any(|v| field.eq(v)),
+                        NotEqual => vs.iter().all(|v| field.ne(v)),
+                        Like => vs.iter().any(|v| field.like(v)),
+                        NotLike => vs.iter().all(|v| !field.like(v)),
+                        GreaterThan => vs.iter().all(|v| field.gt(v)),
+                        GreaterThanOrEqual => vs.iter().all(|v| field.ge(v)),
+                        LessThan => vs.iter().all(|v| field.lt(v)),
+                        LessThanOrEqual => vs.iter().all(|v| field.le(v)),
+                        _ => false,
+                    }),
+                    Constraint {
+                        field: None,
+                        value: vs,
+                        ..
+                    } => context
+                        .values()
+                        .any(|field| vs.iter().any(|v| field.like(v))),
+                    _ => false,
+                })
+            })
     }
 
-    fn parse(&self) -> Vec<Constraint> {
+    /// Parse the query into groups of constraints, which are OR-ed. The constraints of a group
+    /// are AND-ed, so '&' binds tighter than '|'.
+    fn parse(&self) -> Vec<Vec<Constraint>> {
         // regex for filters: {field}{op}{value}
         const RE: &str = r"^(?<field>[^\\]+?)(?<op>=|!=|~|!~|>=|>|<=|<)(?<value>.*)$";
-        static LOCK: OnceLock<Regex> = OnceLock::new();
+        // regex for filters following a '|', restricting the field name, so that values like
+        // "pkg:maven/foo?type=jar" continue to be values of the previous filter
+        const OR_RE: &str =
+            r"^(?<field>[a-zA-Z_][\w:.\-]*?)(?<op>=|!=|~|!~|>=|>|<=|<)(?<value>.*)$";
+        static LOCK: OnceLock<(Regex, Regex)> = OnceLock::new();
         #[allow(clippy::unwrap_used)]
-        let regex = LOCK.get_or_init(|| Regex::new(RE).unwrap());
+        let (regex, or_regex) =
+            LOCK.get_or_init(|| (Regex::new(RE).unwrap(), Regex::new(OR_RE).unwrap()));
 
         fn encode(s: &str) -> String {
             s.replace(r"\&", "\x07").replace(r"\|", "\x08")
@@ -99,45 +110,100 @@ impl Query {
                 .replace('\\', "")
                 .replace('\x08', r"\")
         }
-        encode(&self.q)
-            .split_terminator('&')
-            .map(|s| {
-                if let Some(capture) = regex.captures(s) {
-                    // We have a filter: {field}{op}{value}
-                    let field = Some(capture["field"].into());
-                    #[allow(clippy::unwrap_used)] // regex ensures we won't panic
-                    let op = Some(Operator::from_str(&capture["op"]).unwrap());
-                    let value = capture["value"].split('|').map(decode).collect();
-                    Constraint { field, op, value }
-                } else {
-                    // We have a full-text search
-                    Constraint {
-                        field: None,
-                        op: None,
-                        value: s.split('|').map(decode).collect(),
+        fn filter(capture: regex::Captures<'_>) -> Constraint {
+            let field = Some(capture["field"].into());
+            #[allow(clippy::unwrap_used)] // regex ensures we won't panic
+            let op = Some(Operator::from_str(&capture["op"]).unwrap());
+            let value = vec![decode(&capture["value"])];
+            Constraint { field, op, value }
+        }
+
+        let mut groups: Vec<Vec<Constraint>> = vec![];
+        for s in encode(&self.q).split_terminator('&') {
+            let mut terms = s.split('|');
+            let first = terms.next().unwrap_or_default();
+
+            // We have a filter: {field}{op}{value}, or a full-text search
+            let constraint = match regex.captures(first) {
+                Some(capture) => filter(capture),
+                None => Constraint {
+                    field: None,
+                    op: None,
+                    value: vec![decode(first)],
+                },
+            };
+            match groups.last_mut() {
+                Some(group) => group.push(constraint),
+                None => groups.push(vec![constraint]),
+            }
+
+            for term in terms {
+                match or_regex.captures(term) {
+                    // Another filter, starting a new group
+                    Some(capture) => groups.push(vec![filter(capture)]),
+                    // Another value of the previous filter or full-text search
+                    None => {
+                        if let Some(last) = groups.last_mut().and_then(|group| group.last_mut()) {
+                            last.value.push(decode(term));
+                        }
                     }
                 }
-            })
-            .collect()
+            }
+        }
+        groups
     }
 
     fn filter_for(&self, columns: &Columns) -> Result<Filter, Error> {
-        let constraints = self.parse();
-        Ok(match constraints.len() {
-            1 => constraints[0].filter_for(columns)?,
-            _ => Filter::all(
-                constraints
+        let mut groups = self
+            .parse()
+            .iter()
+            .map(|group| {
+                let mut filters = group
                     .iter()
                     .map(|constraint| constraint.filter_for(columns))
-                    .collect::<Result<Vec<_>, _>>()?,
-            ),
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(match filters.len() {
+                    1 => filters.remove(0),
+                    _ => Filter::all(filters),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(match groups.len() {
+            0 => Filter::all(groups),
+            1 => groups.remove(0),
+            _ => Filter::any(groups),
         })
     }
 
-    pub fn get_constraint_for_field(&self, field: &str) -> Option<Constraint> {
-        self.parse()
-            .into_iter()
-            .find(|constraint| constraint.has_field(field))
+    /// Get the constraints on a field, as a query of their own.
+    ///
+    /// This is used for fields which are filtered by a separate query. So the field must not be
+    /// OR-ed with other fields, as that can't be split up.
+    pub fn get_query_for_field(&self, field: &str) -> Result<Option<Query>, Error> {
+        let groups = self.parse();
+        // without any OR, or only OR-ing constraints of the field
+        let separable = groups.len() == 1 || groups.iter().flatten().all(|c| c.has_field(field));
+
+        let groups = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .filter(|c| c.has_field(field))
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("&")
+            })
+            .filter(|group| !group.is_empty())
+            .collect::<Vec<_>>();
+
+        match (groups.is_empty(), separable) {
+            (true, _) => Ok(None),
+            (false, true) => Ok(Some(q(&groups.join("|")))),
+            (false, false) => Err(Error::SearchSyntax(format!(
+                "'{field}' can't be combined with other fields using '|'"
+            ))),
+        }
     }
 }
 
@@ -148,7 +214,8 @@ impl Query {
 pub struct Query {
     /// EBNF grammar for the _q_ parameter:
     /// ```text
-    ///     q = ( values | filter ) { '&' q }
+    ///     q = conjunction { '|' conjunction }
+    ///     conjunction = ( values | filter ) { '&' ( values | filter ) }
     ///     values = value { '|', values }
     ///     filter = field, operator, values
     ///     operator = "=" | "!=" | "~" | "!~" | ">=" | ">" | "<=" | "<"
@@ -188,6 +255,12 @@ pub struct Query {
     ///
     /// - `red hat|fedora&labels:type=cve|osv&published>last wednesday 17:00`
     ///
+    /// Filters may also be '|'-delimited -- they are logically OR'd
+    /// together. A '&' binds tighter than a '|'.
+    ///
+    /// - `name~openssl|name~libssl` - entity's _name_ contains either 'openssl' OR 'libssl'
+    /// - `name=foo|version=1&name=bar` - entity's _name_ is 'foo', OR its _version_ is '1' AND its _name_ is 'bar'
+    ///
     /// Fields corresponding to JSON objects in the database may use a
     /// ':' to delimit the column name and the object key,
     /// e.g. `purl:qualifiers:type=pom`
@@ -281,15 +354,55 @@ pub(crate) mod tests {
     }
 
     #[test_log::test(rstest::rstest)]
-    #[case("f=x", vec!["f=x"])]
-    #[case("f=x|y", vec!["f=x|y"])]
-    #[case("x", vec!["x"])]
-    #[case("x|y", vec!["x|y"])]
-    #[case("x|y&f>x", vec!["x|y", "f>x"])]
-    #[case("x!=\0&foo", vec!["x!=\0", "foo"])]
-    fn parsing(#[case] input: &str, #[case] expected: Vec<&str>) {
-        let constraints: Vec<_> = q(input).parse().iter().map(ToString::to_string).collect();
-        assert_eq!(expected, constraints)
+    #[case("f=x", vec![vec!["f=x"]])]
+    #[case("f=x|y", vec![vec!["f=x|y"]])]
+    #[case("x", vec![vec!["x"]])]
+    #[case("x|y", vec![vec!["x|y"]])]
+    #[case("x|y&f>x", vec![vec!["x|y", "f>x"]])]
+    #[case("x!=\0&foo", vec![vec!["x!=\0", "foo"]])]
+    #[case("f~x|g~y", vec![vec!["f~x"], vec!["g~y"]])]
+    #[case("f~x|y|g=z", vec![vec!["f~x|y"], vec!["g=z"]])]
+    #[case("x|f=y", vec![vec!["x"], vec!["f=y"]])]
+    #[case("f=x|g\\=y", vec![vec!["f=x|g=y"]])]
+    #[case("f~x|g~y&h>z", vec![vec!["f~x"], vec!["g~y", "h>z"]])]
+    #[case("h>z&f~x|g~y", vec![vec!["h>z", "f~x"], vec!["g~y"]])]
+    #[case(
+        "purl=pkg:maven/a@1?type=jar|pkg:maven/b@1?type=pom",
+        vec![vec!["purl=pkg:maven/a@1?type=jar|pkg:maven/b@1?type=pom"]]
+    )]
+    fn parsing(#[case] input: &str, #[case] expected: Vec<Vec<&str>>) {
+        let groups: Vec<Vec<_>> = q(input)
+            .parse()
+            .iter()
+            .map(|group| group.iter().map(ToString::to_string).collect())
+            .collect();
+        assert_eq!(expected, groups)
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[case("license=MIT", Some("license=MIT"))]
+    #[case("license=MIT|GPL", Some("license=MIT|GPL"))]
+    #[case("license~MIT|license~GPL", Some("license~MIT|license~GPL"))]
+    #[case("name=foo&license~MIT&license!~GPL", Some("license~MIT&license!~GPL"))]
+    #[case(
+        "license~MIT|license~GPL&license!~BSD",
+        Some("license~MIT|license~GPL&license!~BSD")
+    )]
+    #[case("name=foo|bar", None)]
+    #[case("name=foo|version=1", None)]
+    fn query_for_field(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(q(input).get_query_for_field("license"), Ok(expected.map(q)));
+    }
+
+    #[test_log::test(rstest::rstest)]
+    #[case("license=MIT|name=foo")]
+    #[case("name=foo|license=MIT")]
+    #[case("name=foo&license=MIT|license=GPL")]
+    fn query_for_field_or_other_field(#[case] input: &str) {
+        assert!(matches!(
+            q(input).get_query_for_field("license"),
+            Err(Error::SearchSyntax(_))
+        ));
     }
 
     /////////////////////////////////////////////////////////////////////////
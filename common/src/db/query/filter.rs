@@ -37,6 +37,16 @@ impl Filter {
             ),
         }
     }
+
+    pub(crate) fn any(filters: Vec<Filter>) -> Self {
+        Self {
+            clause: ConditionExpression::Condition(
+                filters
+                    .into_iter()
+                    .fold(Condition::any(), |or, f| or.add(f)),
+            ),
+        }
+    }
 }
 
 // From a filter string of the form {field}{op}{values} where multiple
@@ -353,6 +363,51 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn or_conditions() -> Result<(), anyhow::Error> {
+        // Filters on other fields must resolve as well
+        match where_clause("location=a|foo=b") {
+            Ok(_) => panic!("invalid field"),
+            Err(e) => log::error!("{e}"),
+        }
+        assert_eq!(
+            where_clause("location=a|title=b")?,
+            r#""advisory"."location" = 'a' OR "advisory"."title" = 'b'"#
+        );
+        assert_eq!(
+            where_clause("location=a|b|title=c")?,
+            r#"("advisory"."location" = 'a' OR "advisory"."location" = 'b') OR "advisory"."title" = 'c'"#
+        );
+        // AND binds tighter than OR
+        assert_eq!(
+            where_clause("location=a|title=b&published>2023-11-03")?,
+            r#""advisory"."location" = 'a' OR ("advisory"."title" = 'b' AND "advisory"."published" > '2023-11-03')"#
+        );
+        assert_eq!(
+            where_clause("published>2023-11-03&location=a|title=b")?,
+            r#"("advisory"."published" > '2023-11-03' AND "advisory"."location" = 'a') OR "advisory"."title" = 'b'"#
+        );
+        assert_eq!(
+            where_clause("location=a|b&published>2023-11-03")?,
+            r#"("advisory"."location" = 'a' OR "advisory"."location" = 'b') AND "advisory"."published" > '2023-11-03'"#
+        );
+        assert_eq!(
+            where_clause("foo|location=bar")?,
+            r#"(("advisory"."location" ILIKE '%foo%') OR ("advisory"."title" ILIKE '%foo%') OR (array_to_string("advisory"."authors", '|') ILIKE '%foo%')) OR "advisory"."location" = 'bar'"#
+        );
+        // Values looking like filters, but with an invalid field name, remain values
+        assert_eq!(
+            where_clause("location=pkg:maven/a?type=jar|pkg:maven/b?type=pom")?,
+            r#""advisory"."location" = 'pkg:maven/a?type=jar' OR "advisory"."location" = 'pkg:maven/b?type=pom'"#
+        );
+        assert_eq!(
+            where_clause(r"location=a|title\=b")?,
+            r#""advisory"."location" = 'a' OR "advisory"."location" = 'title=b'"#
+        );
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn empty_values() -> Result<(), anyhow::Error> {
         assert_eq!(where_clause("location=")?, r#""advisory"."location" = ''"#);
@@ -315,6 +315,27 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn filter_or_values() -> Result<(), anyhow::Error> {
+        let mut context = ValueContext::from([("id", "foo")]);
+        context.put("count", 42);
+
+        assert!(q("id=bar|count=42").apply(&context));
+        assert!(q("id=foo|count=0").apply(&context));
+        assert!(!q("id=bar|count=0").apply(&context));
+        assert!(q("id=bar|baz|count>40").apply(&context));
+        assert!(q("id=bar|count>40&count<50").apply(&context));
+        assert!(!q("id=bar|count>40&count>50").apply(&context));
+        // AND binds tighter than OR
+        assert!(q("count>50&id=bar|id~oo").apply(&context));
+        assert!(!q("count>50&id=bar|count=0").apply(&context));
+        assert!(q("count<50&id=bar|count=0|id~oo").apply(&context));
+        assert!(q("bar|count=42").apply(&context));
+        assert!(!q("id=bar|missing=foo").apply(&context));
+
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn filter_array_custom_values() -> Result<(), anyhow::Error> {
         use crate::purl::Purl;
@@ -0,0 +1,23 @@
+use sea_orm::{ConnectionTrait, DatabaseTransaction, DbErr};
+use std::time::Duration;
+
+/// The time a single statement of a transaction may take, before PostgreSQL cancels it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct QueryTimeout(pub Duration);
+
+impl QueryTimeout {
+    /// Create a timeout, a duration of zero disables the timeout.
+    pub fn new(duration: Duration) -> Option<Self> {
+        (!duration.is_zero()).then_some(Self(duration))
+    }
+
+    /// Apply the timeout to all following statements of the transaction.
+    pub async fn apply(&self, tx: &DatabaseTransaction) -> Result<(), DbErr> {
+        tx.execute_unprepared(&format!(
+            "SET LOCAL statement_timeout = {}",
+            self.0.as_millis()
+        ))
+        .await?;
+        Ok(())
+    }
+}
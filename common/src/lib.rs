@@ -9,6 +9,7 @@ pub mod fmt;
 pub mod hashing;
 pub mod id;
 pub mod memo;
+pub mod metrics;
 pub mod middleware;
 pub mod model;
 pub mod package;
@@ -0,0 +1,20 @@
+use opentelemetry::metrics::{Histogram, Meter};
+
+/// Bucket boundaries, in seconds, for histograms recording the duration of an operation
+pub const DURATION_BOUNDARIES: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Create a histogram recording the duration of an operation, in seconds
+pub fn duration_histogram(
+    meter: &Meter,
+    name: &'static str,
+    description: &'static str,
+) -> Histogram<f64> {
+    meter
+        .f64_histogram(name)
+        .with_description(description)
+        .with_unit("s")
+        .with_boundaries(DURATION_BOUNDARIES.to_vec())
+        .build()
+}
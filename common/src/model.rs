@@ -1,7 +1,10 @@
 mod bytesize;
 pub use bytesize::*;
 
-use crate::db::limiter::{LimitedResult, Limiter};
+use crate::db::{
+    cursor::Cursor,
+    limiter::{LimitedResult, Limiter},
+};
 use sea_orm::{ConnectionTrait, DbErr, SelectorTrait};
 use std::{cmp::min, fmt::Debug, marker::PhantomData};
 use utoipa::{IntoParams, ToSchema};
@@ -22,7 +25,7 @@ pub struct Revisioned<T> {
 }
 
 #[derive(IntoParams, Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", try_from = "PaginatedParams")]
 pub struct Paginated {
     /// The first item to return, skipping all that come before it.
     ///
@@ -39,12 +42,83 @@ pub struct Paginated {
     pub total: bool,
 }
 
+/// The query parameters of [`Paginated`], which rejects a cursor instead of ignoring it.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaginatedParams {
+    #[serde(default)]
+    offset: u64,
+    #[serde(default = "default::limit")]
+    limit: u64,
+    #[serde(default)]
+    total: bool,
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+impl TryFrom<PaginatedParams> for Paginated {
+    type Error = &'static str;
+
+    fn try_from(value: PaginatedParams) -> Result<Self, Self::Error> {
+        let PaginatedParams {
+            offset,
+            limit,
+            total,
+            cursor,
+        } = value;
+
+        if cursor.is_some() {
+            return Err("this list does not support cursor based pagination");
+        }
+
+        Ok(Self {
+            offset,
+            limit,
+            total,
+        })
+    }
+}
+
+/// Pagination of lists which also support continuing from a cursor.
+///
+/// Only the SBOM lists support cursors for now. All other lists use [`Paginated`], which rejects
+/// a cursor.
+#[derive(IntoParams, Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPaginated {
+    /// The first item to return, skipping all that come before it.
+    ///
+    /// NOTE: The order of items is defined by the API being called.
+    #[serde(default)]
+    pub offset: u64,
+    /// The maximum number of entries to return.
+    ///
+    /// Zero means: return no items (the total count is still computed if requested).
+    #[serde(default = "default::limit")]
+    pub limit: u64,
+    /// Whether to compute and return the total count of matching items.
+    #[serde(default)]
+    pub total: bool,
+    /// Continue after the last item of the previous page, rather than skipping `offset` items.
+    ///
+    /// An empty cursor starts at the beginning. The cursor to the next page is then returned as
+    /// `nextCursor`, and items are ordered by their ID.
+    #[serde(default)]
+    #[param(value_type = Option<String>)]
+    pub cursor: Option<Cursor>,
+}
+
 /// Trait for types that carry pagination parameters.
 pub trait Pagination: Copy + Debug {
     fn offset(&self) -> u64;
     fn limit(&self) -> u64;
     fn total(&self) -> bool;
 
+    /// The cursor to continue from, if cursor based pagination is requested.
+    fn cursor(&self) -> Option<Cursor> {
+        None
+    }
+
     /// Paginate an in-memory slice, optionally including the total count.
     fn paginate_array<T: Clone>(&self, vec: &[T]) -> PaginatedResults<T> {
         let total = if self.total() {
@@ -57,6 +131,7 @@ pub trait Pagination: Copy + Debug {
             return PaginatedResults {
                 items: vec![],
                 total,
+                next_cursor: None,
             };
         }
 
@@ -64,6 +139,7 @@ pub trait Pagination: Copy + Debug {
             return PaginatedResults {
                 items: vec![],
                 total,
+                next_cursor: None,
             };
         }
 
@@ -72,6 +148,7 @@ pub trait Pagination: Copy + Debug {
         PaginatedResults {
             items: Vec::from(&vec[self.offset() as usize..end]),
             total,
+            next_cursor: None,
         }
     }
 }
@@ -88,6 +165,21 @@ impl Pagination for Paginated {
     }
 }
 
+impl Pagination for CursorPaginated {
+    fn offset(&self) -> u64 {
+        self.offset
+    }
+    fn limit(&self) -> u64 {
+        self.limit
+    }
+    fn total(&self) -> bool {
+        self.total
+    }
+    fn cursor(&self) -> Option<Cursor> {
+        self.cursor
+    }
+}
+
 /// A pagination limit, convertible into a full `Paginated` with default offset and no total.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Limit(pub u64);
@@ -123,6 +215,17 @@ impl Default for Paginated {
     }
 }
 
+impl Default for CursorPaginated {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: default::limit(),
+            total: false,
+            cursor: None,
+        }
+    }
+}
+
 mod default {
     pub(super) const fn limit() -> u64 {
         25
@@ -134,6 +237,12 @@ mod default {
 pub struct PaginatedResults<R> {
     pub items: Vec<R>,
     pub total: Option<u64>,
+    /// The cursor to the next page, if there is one and the list was paginated using a cursor
+    ///
+    /// Only lists accepting a cursor return one, which currently are the SBOM lists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub next_cursor: Option<Cursor>,
 }
 
 impl<T> Default for PaginatedResults<T> {
@@ -141,6 +250,7 @@ impl<T> Default for PaginatedResults<T> {
         Self {
             items: vec![],
             total: None,
+            next_cursor: None,
         }
     }
 }
@@ -159,13 +269,18 @@ impl<R> PaginatedResults<R> {
         let LimitedResult { items, total } = limiter.fetch().await?;
         let total = total.requested(paginated.total()).await?;
 
-        Ok(PaginatedResults { items, total })
+        Ok(PaginatedResults {
+            items,
+            total,
+            next_cursor: None,
+        })
     }
 
     pub fn map<O, F: FnMut(R) -> O>(self, f: F) -> PaginatedResults<O> {
         PaginatedResults {
             items: self.items.into_iter().map(f).collect(),
             total: self.total,
+            next_cursor: self.next_cursor,
         }
     }
 }
@@ -176,7 +291,8 @@ pub struct BinaryData(PhantomData<Vec<u8>>);
 
 #[cfg(test)]
 mod test {
-    use crate::model::{Paginated, PaginatedResults, Pagination};
+    use crate::model::{CursorPaginated, Paginated, PaginatedResults, Pagination};
+    use serde_json::json;
 
     #[test_log::test(test)]
     fn paginated_vec() {
@@ -245,14 +361,28 @@ mod test {
         let input = PaginatedResults {
             items: vec![1, 2, 3],
             total: Some(10),
+            next_cursor: None,
         };
 
         assert_eq!(
             input.map(|n| n.to_string()),
             PaginatedResults {
                 items: vec!["1".to_string(), "2".to_string(), "3".to_string()],
-                total: Some(10)
+                total: Some(10),
+                next_cursor: None,
             }
         )
     }
+
+    #[test]
+    fn reject_cursor() {
+        let paginated: Result<Paginated, _> = serde_json::from_value(json!({"limit": 5}));
+        assert!(paginated.is_ok_and(|paginated| paginated.limit == 5));
+
+        let paginated: Result<Paginated, _> = serde_json::from_value(json!({"cursor": ""}));
+        assert!(paginated.is_err());
+
+        let paginated: Result<CursorPaginated, _> = serde_json::from_value(json!({"cursor": ""}));
+        assert!(paginated.is_ok_and(|paginated| paginated.cursor == Some(Default::default())));
+    }
 }
@@ -289,9 +289,28 @@ impl From<PackageUrl<'_>> for Purl {
     }
 }
 
+/// The identity of a package across SBOMs
+///
+/// This is the smallest of its versionless PURLs, so that it doesn't depend on the order of the
+/// PURLs. Without any PURL, it is its (group and) name.
+pub fn package_key<'a>(
+    purls: impl IntoIterator<Item = &'a Purl>,
+    group: Option<&str>,
+    name: &str,
+) -> String {
+    purls
+        .into_iter()
+        .map(|purl| purl.to_base().to_string())
+        .min()
+        .unwrap_or_else(|| match group {
+            Some(group) => format!("{group}/{name}"),
+            None => name.to_string(),
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::purl::Purl;
+    use crate::purl::{Purl, package_key};
     use std::str::FromStr;
     use test_log::test;
 
@@ -447,4 +466,23 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn package_key_ignores_order() -> Result<(), anyhow::Error> {
+        let a = Purl::from_str("pkg:maven/org.example/a@1.0")?;
+        let b = Purl::from_str("pkg:npm/b@2.0")?;
+
+        assert_eq!(
+            package_key([&a, &b], None, "name"),
+            "pkg:maven/org.example/a"
+        );
+        assert_eq!(
+            package_key([&b, &a], None, "name"),
+            "pkg:maven/org.example/a"
+        );
+        assert_eq!(package_key([], None, "name"), "name");
+        assert_eq!(package_key([], Some("group"), "name"), "group/name");
+
+        Ok(())
+    }
 }
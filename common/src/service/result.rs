@@ -44,7 +44,11 @@ impl Resulting for Paginated {
         let LimitedResult { items, total } = limiter.fetch().await?;
         let total = total.requested(self.total).await?;
 
-        Ok(PaginatedResults { items, total })
+        Ok(PaginatedResults {
+            items,
+            total,
+            next_cursor: None,
+        })
     }
 }
 
@@ -111,6 +115,7 @@ impl<In> Mappable<In> for PaginatedResults<In> {
         PaginatedResults {
             total,
             items: items.collect(),
+            next_cursor: None,
         }
     }
 }
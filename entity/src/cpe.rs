@@ -1,7 +1,10 @@
 use cpe::{error::CpeError, uri::Uri};
 use sea_orm::{Set, entity::prelude::*};
-use std::fmt::{Debug, Display, Formatter};
-use trustify_common::cpe::{Component, Cpe, CpeType, Language};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    str::FromStr,
+};
+use trustify_common::cpe::{Component, Cpe, CpeType, Language, encode_uri_component};
 use trustify_common::impl_try_into_cpe;
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -180,6 +183,163 @@ pub struct CpeDto {
     pub other: Option<String>,
 }
 
+impl CpeDto {
+    /// Render the CPE using the CPE 2.2 URI binding (`cpe:/<part>:<vendor>:<product>:...`).
+    ///
+    /// ANY (`*`) binds to an empty component and NA (no value) to `-`. The extended 2.3
+    /// attributes are packed into the edition component, and trailing empty components are
+    /// dropped, as described by NISTIR 7695, section 6.1.2.
+    pub fn to_uri_binding(&self) -> String {
+        fn bind(value: &Option<String>) -> String {
+            match value.as_deref() {
+                None => "-".to_string(),
+                Some("*") => String::new(),
+                Some(value) => encode_uri_component(value),
+            }
+        }
+
+        let part = match self.part.as_deref() {
+            None | Some("*") => String::new(),
+            Some(part) => part.to_string(),
+        };
+
+        let extended = [
+            &self.sw_edition,
+            &self.target_sw,
+            &self.target_hw,
+            &self.other,
+        ];
+        let edition = if extended
+            .iter()
+            .all(|value| matches!(value.as_deref(), Some("*")))
+        {
+            bind(&self.edition)
+        } else {
+            format!(
+                "~{}~{}~{}~{}~{}",
+                bind(&self.edition),
+                bind(&self.sw_edition),
+                bind(&self.target_sw),
+                bind(&self.target_hw),
+                bind(&self.other),
+            )
+        };
+
+        let language = match self.language.as_deref() {
+            None | Some("*") => String::new(),
+            Some(language) => language.to_string(),
+        };
+
+        let mut components = vec![
+            part,
+            bind(&self.vendor),
+            bind(&self.product),
+            bind(&self.version),
+            bind(&self.update),
+            edition,
+            language,
+        ];
+        while components.last().is_some_and(|c| c.is_empty()) {
+            components.pop();
+        }
+
+        format!("cpe:/{}", components.join(":"))
+    }
+
+    /// Render the CPE using the CPE 2.3 formatted string binding (`cpe:2.3:<part>:<vendor>:...`).
+    ///
+    /// All eleven attributes are bound: ANY stays `*`, NA (no value) binds to `-`, and
+    /// punctuation other than `.`, `-` and `_` is quoted with a `\`, as described by
+    /// NISTIR 7695, section 6.2.2.
+    pub fn to_formatted_string(&self) -> String {
+        fn bind(value: &Option<String>) -> String {
+            match value.as_deref() {
+                None => "-".to_string(),
+                Some("*") => "*".to_string(),
+                Some(value) => {
+                    let mut out = String::with_capacity(value.len());
+                    for c in value.chars() {
+                        match c {
+                            // wildcards keep their special meaning
+                            '?' | '*' => out.push(c),
+                            c if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') => out.push(c),
+                            c => {
+                                out.push('\\');
+                                out.push(c);
+                            }
+                        }
+                    }
+                    out
+                }
+            }
+        }
+
+        let part = match self.part.as_deref() {
+            None => "*",
+            Some(part) => part,
+        };
+
+        let components = [
+            &self.vendor,
+            &self.product,
+            &self.version,
+            &self.update,
+            &self.edition,
+            &self.language,
+            &self.sw_edition,
+            &self.target_sw,
+            &self.target_hw,
+            &self.other,
+        ]
+        .map(bind);
+
+        format!("cpe:2.3:{part}:{}", components.join(":"))
+    }
+
+    /// Parse a CPE 2.2 URI (`cpe:/a:vendor:product:version`) into its structured form.
+    pub fn from_uri_binding(s: &str) -> Result<CpeDto, CpeError> {
+        if !s.starts_with("cpe:/") {
+            return Err(CpeError::InvalidPrefix {
+                value: s.to_string(),
+            });
+        }
+
+        Ok(Cpe::from_str(s)?.into())
+    }
+}
+
+impl From<Cpe> for CpeDto {
+    fn from(value: Cpe) -> Self {
+        fn component(value: Component) -> Option<String> {
+            match value {
+                Component::Any => Some("*".to_string()),
+                Component::NotApplicable => None,
+                Component::Value(inner) => Some(inner),
+            }
+        }
+
+        Self {
+            part: match value.part() {
+                CpeType::Empty => None,
+                ct => Some(ct.to_string()),
+            },
+            vendor: component(value.vendor()),
+            product: component(value.product()),
+            version: component(value.version()),
+            update: component(value.update()),
+            edition: component(value.edition()),
+            language: match value.language() {
+                Language::Any => Some("*".to_string()),
+                Language::Language(inner) => Some(inner),
+            },
+            sw_edition: component(value.sw_edition()),
+            target_sw: component(value.target_sw()),
+            target_hw: component(value.target_hw()),
+            other: component(value.other()),
+        }
+    }
+}
+
 impl From<Model> for CpeDto {
     fn from(value: Model) -> Self {
         // turn into a model and destructure to ensure we don't miss any new fields
@@ -367,7 +527,6 @@ fn pack_edition(
 mod test {
     use super::*;
     use sea_orm::TryIntoModel;
-    use std::str::FromStr;
 
     use test_log::test;
     use trustify_common::cpe::Cpe;
@@ -440,4 +599,75 @@ mod test {
             "cpe:/o:microsoft:windows_10:1607:*~*~*~*~x64~*:en-US"
         );
     }
+
+    #[test]
+    fn uri_binding_roundtrip() {
+        for uri in [
+            "cpe:/a:redhat:openshift_container_storage:4.8::el8",
+            "cpe:/o:redhat:enterprise_linux:9",
+            "cpe:/a:apache:tomcat:-",
+            "cpe:/a:vendor:product:1.0:beta:enterprise:en-US",
+            "cpe:/a:foo%21bar:product",
+            "cpe:/o:microsoft:windows_10:1607::~~~~x64~:en-US",
+        ] {
+            let dto = CpeDto::from_uri_binding(uri).expect("must parse");
+            assert_eq!(dto.to_uri_binding(), uri);
+        }
+    }
+
+    #[test]
+    fn uri_binding_components() {
+        let dto = CpeDto::from_uri_binding("cpe:/a:apache:tomcat:-:beta").expect("must parse");
+
+        assert_eq!(dto.part.as_deref(), Some("a"));
+        assert_eq!(dto.vendor.as_deref(), Some("apache"));
+        assert_eq!(dto.product.as_deref(), Some("tomcat"));
+        // NA has no value
+        assert_eq!(dto.version, None);
+        assert_eq!(dto.update.as_deref(), Some("beta"));
+        assert_eq!(dto.edition.as_deref(), Some("*"));
+        assert_eq!(dto.language.as_deref(), Some("*"));
+    }
+
+    /// A CPE 2.3 formatted string gets bound to the CPE 2.2 URI
+    #[test]
+    fn uri_binding_from_cpe23() {
+        let cpe =
+            Cpe::from_str("cpe:2.3:a:redhat:openshift:4.15:*:*:*:*:*:*:*").expect("must parse");
+        let dto = CpeDto::from(cpe);
+        assert_eq!(dto.to_uri_binding(), "cpe:/a:redhat:openshift:4.15");
+    }
+
+    #[test]
+    fn formatted_string_binding() {
+        for (input, expected) in [
+            (
+                "cpe:/a:redhat:openshift_container_storage:4.8::el8",
+                "cpe:2.3:a:redhat:openshift_container_storage:4.8:*:el8:*:*:*:*:*",
+            ),
+            (
+                "cpe:/a:apache:tomcat:-",
+                "cpe:2.3:a:apache:tomcat:-:*:*:*:*:*:*:*",
+            ),
+            (
+                "cpe:/a:foo%21bar:product",
+                r"cpe:2.3:a:foo\!bar:product:*:*:*:*:*:*:*:*",
+            ),
+            (
+                "cpe:2.3:o:microsoft:windows_10:1607:*:*:en-US:*:*:x64:*",
+                "cpe:2.3:o:microsoft:windows_10:1607:*:*:en-US:*:*:x64:*",
+            ),
+        ] {
+            let dto = CpeDto::from(Cpe::from_str(input).expect("must parse"));
+            assert_eq!(dto.to_formatted_string(), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn uri_binding_invalid_prefix() {
+        assert!(matches!(
+            CpeDto::from_uri_binding("cpe:2.3:a:redhat:openshift:4.15:*:*:*:*:*:*:*"),
+            Err(CpeError::InvalidPrefix { .. })
+        ));
+    }
 }
@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+use time::Date;
+
+/// The EPSS score of a vulnerability, as published by FIRST
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "epss_score")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub vulnerability_id: String,
+    pub score: f64,
+    pub percentile: f64,
+    pub scored_date: Date,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+
+/// The persisted analysis graph of an SBOM
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "graph_cache")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub sbom_id: Uuid,
+    /// The version of the encoding of the graph
+    pub version: i32,
+    pub serialized_graph: Vec<u8>,
+    pub loaded_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::sbom::Entity",
+        from = "Column::SbomId",
+        to = "super::sbom::Column::SbomId"
+    )]
+    Sbom,
+}
+
+impl Related<super::sbom::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sbom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -4,11 +4,15 @@ pub mod advisory_vulnerability_score;
 pub mod base_purl;
 pub mod cpe;
 pub mod cpe_status;
+pub mod epss_score;
 pub mod expanded_license;
+pub mod graph_cache;
 pub mod importer;
 pub mod importer_report;
 pub mod labels;
 pub mod license;
+pub mod license_compatibility;
+pub mod license_obligation;
 pub mod licensing_infos;
 pub mod organization;
 pub mod package_relates_to_package;
@@ -26,10 +30,12 @@ pub mod remediation_purl_status;
 pub mod sbom;
 pub mod sbom_ai;
 pub mod sbom_ancestor;
+pub mod sbom_attestation;
 pub mod sbom_crypto;
 pub mod sbom_describing_cpe;
 pub mod sbom_external_node;
 pub mod sbom_file;
+pub mod sbom_formulation;
 pub mod sbom_group;
 pub mod sbom_group_assignment;
 pub mod sbom_license_expanded;
@@ -38,7 +44,9 @@ pub mod sbom_node_checksum;
 pub mod sbom_node_cpe_ref;
 pub mod sbom_node_purl_ref;
 pub mod sbom_package;
+pub mod sbom_package_external_ref;
 pub mod sbom_package_license;
+pub mod sbom_snippet;
 pub mod source_document;
 pub mod status;
 pub mod user_preferences;
@@ -47,4 +55,7 @@ pub mod version_scheme;
 pub mod versioned_purl;
 pub mod vulnerability;
 pub mod vulnerability_description;
+pub mod vulnerability_suppression;
 pub mod weakness;
+pub mod webhook_config;
+pub mod webhook_delivery_log;
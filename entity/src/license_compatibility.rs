@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+
+/// The compatibility of two SPDX licenses, when combined in a single product.
+///
+/// Each pair is stored once, in either order.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "license_compatibility")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub license_a: String,
+    #[sea_orm(primary_key)]
+    pub license_b: String,
+    pub compatible: bool,
+    pub explanation: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
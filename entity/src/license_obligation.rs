@@ -0,0 +1,50 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The obligations triggered by distributing software under an SPDX license.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "license_obligation")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub license_id: String,
+    pub requires_attribution: bool,
+    pub requires_source_disclosure: bool,
+    pub patent_grant: bool,
+    pub copyleft: Option<CopyleftStrength>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// The strength of a copyleft license, ordered from weakest to strongest
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "copyleft_strength")]
+#[serde(rename_all = "snake_case")]
+pub enum CopyleftStrength {
+    /// Modifications of the licensed files must be shared, like with the LGPL or MPL
+    #[sea_orm(string_value = "weak")]
+    Weak,
+    /// Works including the licensed code must be shared under the same license, like with the GPL
+    #[sea_orm(string_value = "strong")]
+    Strong,
+    /// Like strong copyleft, but also triggered by providing access over a network, like with the
+    /// AGPL
+    #[sea_orm(string_value = "network")]
+    Network,
+}
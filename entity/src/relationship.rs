@@ -55,6 +55,9 @@ pub enum Relationship {
     Package,
     #[sea_orm(num_value = 15)]
     Undefined,
+    /// A link to a node of another document, which has no more specific relationship
+    #[sea_orm(num_value = 16)]
+    ExternalReference,
 }
 
 impl fmt::Display for Relationship {
@@ -62,3 +65,193 @@ impl fmt::Display for Relationship {
         write!(f, "{self:?}")
     }
 }
+
+impl Relationship {
+    /// The canonical name of this relationship.
+    ///
+    /// This is the name used when serializing the relationship, and parsing it from a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Contains => "contains",
+            Self::Dependency => "dependency",
+            Self::DevDependency => "dev_dependency",
+            Self::OptionalDependency => "optional_dependency",
+            Self::ProvidedDependency => "provided_dependency",
+            Self::TestDependency => "test_dependency",
+            Self::RuntimeDependency => "runtime_dependency",
+            Self::Example => "example",
+            Self::Generates => "generates",
+            Self::AncestorOf => "ancestor_of",
+            Self::Variant => "variant",
+            Self::BuildTool => "build_tool",
+            Self::DevTool => "dev_tool",
+            Self::Describes => "describes",
+            Self::Package => "package",
+            Self::Undefined => "undefined",
+            Self::ExternalReference => "external_reference",
+        }
+    }
+
+    /// A human-readable name of this relationship, suitable for showing it to users.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Contains => "Contains",
+            Self::Dependency => "Depends On",
+            Self::DevDependency => "Dev Dependency",
+            Self::OptionalDependency => "Optional Dependency",
+            Self::ProvidedDependency => "Provided Dependency",
+            Self::TestDependency => "Test Dependency",
+            Self::RuntimeDependency => "Runtime Dependency",
+            Self::Example => "Example",
+            Self::Generates => "Generates",
+            Self::AncestorOf => "Ancestor Of",
+            Self::Variant => "Variant",
+            Self::BuildTool => "Build Tool",
+            Self::DevTool => "Dev Tool",
+            Self::Describes => "Describes",
+            Self::Package => "Package",
+            Self::Undefined => "Undefined",
+            Self::ExternalReference => "External Reference",
+        }
+    }
+
+    /// The SPDX relationship type name of this relationship.
+    ///
+    /// Some of the SPDX relationship types (all ending in `_OF`) point in the opposite
+    /// direction of ours. For example, `DEV_DEPENDENCY_OF` is stored with left and right swapped.
+    pub fn as_spdx_str(&self) -> &'static str {
+        match self {
+            Self::Contains => "CONTAINS",
+            Self::Dependency => "DEPENDS_ON",
+            Self::DevDependency => "DEV_DEPENDENCY_OF",
+            Self::OptionalDependency => "OPTIONAL_DEPENDENCY_OF",
+            Self::ProvidedDependency => "PROVIDED_DEPENDENCY_OF",
+            Self::TestDependency => "TEST_DEPENDENCY_OF",
+            Self::RuntimeDependency => "RUNTIME_DEPENDENCY_OF",
+            Self::Example => "EXAMPLE_OF",
+            Self::Generates => "GENERATES",
+            Self::AncestorOf => "ANCESTOR_OF",
+            Self::Variant => "VARIANT_OF",
+            Self::BuildTool => "BUILD_TOOL_OF",
+            Self::DevTool => "DEV_TOOL_OF",
+            Self::Describes => "DESCRIBES",
+            Self::Package => "PACKAGE_OF",
+            // SPDX has no relationship type for this, external documents are referenced
+            // through the node ID instead
+            Self::Undefined | Self::ExternalReference => "OTHER",
+        }
+    }
+
+    /// Parse an SPDX relationship type name, the reverse of [`Self::as_spdx_str`].
+    ///
+    /// As `OTHER` is ambiguous, it is always parsed as [`Self::Undefined`].
+    pub fn from_spdx_str(s: &str) -> Option<Relationship> {
+        Some(match s {
+            "CONTAINS" => Self::Contains,
+            "DEPENDS_ON" => Self::Dependency,
+            "DEV_DEPENDENCY_OF" => Self::DevDependency,
+            "OPTIONAL_DEPENDENCY_OF" => Self::OptionalDependency,
+            "PROVIDED_DEPENDENCY_OF" => Self::ProvidedDependency,
+            "TEST_DEPENDENCY_OF" => Self::TestDependency,
+            "RUNTIME_DEPENDENCY_OF" => Self::RuntimeDependency,
+            "EXAMPLE_OF" => Self::Example,
+            "GENERATES" => Self::Generates,
+            "ANCESTOR_OF" => Self::AncestorOf,
+            "VARIANT_OF" => Self::Variant,
+            "BUILD_TOOL_OF" => Self::BuildTool,
+            "DEV_TOOL_OF" => Self::DevTool,
+            "DESCRIBES" => Self::Describes,
+            "PACKAGE_OF" => Self::Package,
+            "OTHER" => Self::Undefined,
+            _ => return None,
+        })
+    }
+
+    /// The name of the CycloneDX dependency type this relationship originates from, if any.
+    ///
+    /// CycloneDX only knows `dependsOn` and `provides`, all other relationships have no
+    /// CycloneDX counterpart.
+    pub fn as_cyclonedx_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Dependency => Some("dependsOn"),
+            Self::Generates => Some("provides"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{collections::HashSet, str::FromStr};
+    use strum::VariantArray;
+
+    #[test]
+    fn canonical_names() {
+        for rel in Relationship::VARIANTS {
+            assert_eq!(
+                Relationship::from_str(rel.as_str()).ok(),
+                Some(*rel),
+                "{rel}"
+            );
+            assert_eq!(
+                serde_json::to_value(rel).unwrap(),
+                serde_json::Value::from(rel.as_str()),
+                "{rel}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_names() {
+        assert_eq!(Relationship::Contains.display_name(), "Contains");
+        assert_eq!(Relationship::Dependency.display_name(), "Depends On");
+        assert_eq!(Relationship::AncestorOf.display_name(), "Ancestor Of");
+
+        let names: HashSet<_> = Relationship::VARIANTS
+            .iter()
+            .map(|rel| rel.display_name())
+            .collect();
+        assert_eq!(names.len(), Relationship::VARIANTS.len());
+    }
+
+    #[test]
+    fn spdx_roundtrip() {
+        for rel in Relationship::VARIANTS {
+            if *rel == Relationship::ExternalReference {
+                continue;
+            }
+            assert_eq!(
+                Relationship::from_spdx_str(rel.as_spdx_str()),
+                Some(*rel),
+                "{rel}"
+            );
+        }
+    }
+
+    #[test]
+    fn spdx_names() {
+        assert_eq!(Relationship::Contains.as_spdx_str(), "CONTAINS");
+        assert_eq!(Relationship::Dependency.as_spdx_str(), "DEPENDS_ON");
+        assert_eq!(Relationship::Undefined.as_spdx_str(), "OTHER");
+        assert_eq!(Relationship::ExternalReference.as_spdx_str(), "OTHER");
+        assert_eq!(
+            Relationship::from_spdx_str("OTHER"),
+            Some(Relationship::Undefined)
+        );
+        assert_eq!(Relationship::from_spdx_str("DEPENDENCY_OF"), None);
+        assert_eq!(Relationship::from_spdx_str("contains"), None);
+    }
+
+    #[test]
+    fn cyclonedx_names() {
+        for rel in Relationship::VARIANTS {
+            let expected = match rel {
+                Relationship::Dependency => Some("dependsOn"),
+                Relationship::Generates => Some("provides"),
+                _ => None,
+            };
+            assert_eq!(rel.as_cyclonedx_str(), expected, "{rel}");
+        }
+    }
+}
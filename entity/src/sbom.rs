@@ -25,6 +25,11 @@ pub struct Model {
 
     /// properties from the SBOM document
     pub properties: serde_json::Value,
+
+    /// When the SBOM was deleted, if it was
+    ///
+    /// Deleted SBOMs are hidden, until they are either restored or purged.
+    pub deleted_at: Option<OffsetDateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -39,6 +44,8 @@ pub enum Relation {
     Packages,
     #[sea_orm(has_many = "super::sbom_file::Entity")]
     Files,
+    #[sea_orm(has_many = "super::sbom_snippet::Entity")]
+    Snippets,
     #[sea_orm(has_one = "super::sbom_node::Entity")]
     Node,
     #[sea_orm(has_many = "super::package_relates_to_package::Entity")]
@@ -117,6 +124,12 @@ impl Related<super::sbom_file::Entity> for Entity {
     }
 }
 
+impl Related<super::sbom_snippet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Snippets.def()
+    }
+}
+
 impl Related<super::sbom_node::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Node.def()
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "sbom_formulation")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub sbom_id: Uuid,
+    #[sea_orm(primary_key)]
+    pub bom_ref: String,
+    pub description: Option<String>,
+    /// The transient components used by the formula, as provided by the document
+    pub components: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::sbom::Entity",
+        from = "Column::SbomId",
+        to = "super::sbom::Column::SbomId"
+    )]
+    Sbom,
+}
+
+impl Related<super::sbom::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sbom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
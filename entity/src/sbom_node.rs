@@ -55,6 +55,12 @@ pub enum Relation {
         to = "(super::sbom_crypto::Column::SbomId, super::sbom_crypto::Column::NodeId)"
     )]
     Crypto,
+    #[sea_orm(
+        belongs_to = "super::sbom_snippet::Entity",
+        from = "(Column::SbomId, Column::NodeId)",
+        to = "(super::sbom_snippet::Column::SbomId, super::sbom_snippet::Column::NodeId)"
+    )]
+    Snippet,
     #[sea_orm(
         belongs_to = "super::sbom_node_checksum::Entity",
         from = "(Column::SbomId, Column::NodeId)",
@@ -130,6 +136,12 @@ impl Related<super::sbom_crypto::Entity> for Entity {
     }
 }
 
+impl Related<super::sbom_snippet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Snippet.def()
+    }
+}
+
 impl Related<super::sbom_node_checksum::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Checksum.def()
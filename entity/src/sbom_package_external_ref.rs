@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+
+/// External references of a package, like its website or VCS
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "sbom_package_external_ref")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub sbom_id: Uuid,
+    #[sea_orm(primary_key)]
+    pub node_id: String,
+    /// The type of reference, like `vcs` or `website`
+    #[sea_orm(primary_key)]
+    pub ref_type: String,
+    #[sea_orm(primary_key)]
+    pub url: String,
+    /// The hashes of the referenced content, as provided by the document
+    pub hashes: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::sbom::Entity",
+        from = "Column::SbomId",
+        to = "super::sbom::Column::SbomId"
+    )]
+    Sbom,
+    #[sea_orm(
+        belongs_to = "super::sbom_package::Entity",
+        from = "(Column::SbomId, Column::NodeId)",
+        to = "(super::sbom_package::Column::SbomId, super::sbom_package::Column::NodeId)"
+    )]
+    Package,
+}
+
+impl Related<super::sbom::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sbom.def()
+    }
+}
+
+impl Related<super::sbom_package::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Package.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+
+/// An SPDX snippet, covering a range of a file
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "sbom_snippet")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub sbom_id: Uuid,
+    #[sea_orm(primary_key)]
+    pub node_id: String,
+    /// The node ID of the file the snippet is part of
+    pub file_node_id: String,
+    pub byte_range_start: Option<i64>,
+    pub byte_range_end: Option<i64>,
+    pub line_range_start: Option<i32>,
+    pub line_range_end: Option<i32>,
+    pub license_concluded: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_one = "super::sbom_node::Entity")]
+    Node,
+    #[sea_orm(
+        belongs_to = "super::sbom::Entity",
+        from = "Column::SbomId",
+        to = "super::sbom::Column::SbomId"
+    )]
+    Sbom,
+}
+
+impl Related<super::sbom_node::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Node.def()
+    }
+}
+
+impl Related<super::sbom::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sbom.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
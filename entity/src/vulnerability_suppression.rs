@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+
+/// An accepted risk of a vulnerability, for a package
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "vulnerability_suppression")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub vulnerability_id: String,
+    pub purl: String,
+    pub justification: String,
+    pub expires_at: Option<OffsetDateTime>,
+    pub created_by: String,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::vulnerability::Entity",
+        from = "Column::VulnerabilityId",
+        to = "super::vulnerability::Column::Id"
+    )]
+    Vulnerability,
+}
+
+impl Related<super::vulnerability::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vulnerability.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+
+/// A subscription of an external endpoint to events
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "webhook_config")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub url: String,
+    /// The secret the payload gets signed with
+    pub secret: String,
+    pub events: Vec<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::webhook_delivery_log::Entity")]
+    Delivery,
+}
+
+impl Related<super::webhook_delivery_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Delivery.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
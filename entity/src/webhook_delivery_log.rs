@@ -0,0 +1,49 @@
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+
+/// The delivery of an event to a webhook, along with the result of the last attempt
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "webhook_delivery_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event: String,
+    /// The body of the request
+    pub payload: serde_json::Value,
+    pub state: State,
+    pub attempts: i32,
+    pub created: OffsetDateTime,
+    /// When the next attempt is due, while pending
+    pub next_attempt: OffsetDateTime,
+    pub last_attempt: Option<OffsetDateTime>,
+    /// The HTTP status code of the last attempt, if a response was received
+    pub last_status: Option<i32>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum State {
+    Pending = 0,
+    Delivered = 1,
+    Failed = 2,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::webhook_config::Entity",
+        from = "Column::WebhookId",
+        to = "super::webhook_config::Column::Id"
+    )]
+    Webhook,
+}
+
+impl Related<super::webhook_config::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
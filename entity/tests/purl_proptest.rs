@@ -0,0 +1,55 @@
+//! Round-trip PURLs through their database representation.
+
+use proptest::prelude::*;
+use std::str::FromStr;
+use trustify_common::purl::Purl;
+use trustify_entity::qualified_purl::CanonicalPurl;
+
+/// Arbitrary strings, shaped like a PURL. Not all of them are valid PURLs.
+fn purl_like() -> impl Strategy<Value = String> {
+    proptest::string::string_regex(r"pkg:[a-z]+(/[^/]+)?/[^@]+(@[^?]+)?(\?[^#]+)?")
+        .expect("regex must be valid")
+}
+
+/// Strings shaped like a PURL with qualifiers, which are rarely generated by [`purl_like`].
+fn purl_with_qualifiers() -> impl Strategy<Value = String> {
+    proptest::string::string_regex(
+        r"pkg:[a-z]+/[^/@?#]+(@[^?#]+)?\?[a-z][a-z0-9._-]{0,8}=[^&#]+(&[a-z][a-z0-9._-]{0,8}=[^&#]+){0,3}",
+    )
+    .expect("regex must be valid")
+}
+
+/// Parse the input, convert it to its database representation and back, and check that the
+/// string form of the result parses to the same PURL.
+fn assert_round_trip(input: &str) -> Result<(), TestCaseError> {
+    // only valid PURLs can be round-tripped
+    let Ok(purl) = Purl::from_str(input) else {
+        return Ok(());
+    };
+
+    let canonical = CanonicalPurl::from(purl.clone());
+    let output = Purl::from(canonical).to_string();
+
+    let reparsed = Purl::from_str(&output)
+        .map_err(|err| TestCaseError::fail(format!("failed to re-parse {output}: {err}")))?;
+
+    prop_assert_eq!(&reparsed.ty, &purl.ty);
+    prop_assert_eq!(&reparsed.namespace, &purl.namespace);
+    prop_assert_eq!(&reparsed.name, &purl.name);
+    prop_assert_eq!(&reparsed.version, &purl.version);
+    prop_assert_eq!(&reparsed.qualifiers, &purl.qualifiers);
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn canonical_round_trip(input in purl_like()) {
+        assert_round_trip(&input)?;
+    }
+
+    #[test]
+    fn canonical_round_trip_qualifiers(input in purl_with_qualifiers()) {
+        assert_round_trip(&input)?;
+    }
+}
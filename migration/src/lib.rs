@@ -68,6 +68,20 @@ mod m0002230_sle_license_id_index;
 mod m0002240_product_version_sbom_index;
 mod m0002250_create_cpe_status;
 mod m0002260_cpe_part_vendor_product_index;
+mod m0002270_create_sbom_formulation_attestation;
+mod m0002280_create_sbom_package_external_ref;
+mod m0002290_create_sbom_snippet;
+mod m0002300_sbom_advisory_summary_view;
+mod m0002310_gover_cmp;
+mod m0002320_relationship_external_reference;
+mod m0002330_graph_cache;
+mod m0002340_sbom_deleted_at;
+mod m0002350_epss_score;
+mod m0002360_license_compatibility;
+mod m0002370_license_obligation;
+mod m0002380_vulnerability_suppression;
+mod m0002390_webhook;
+mod m0002400_sbom_advisory_summary_deleted;
 
 pub trait MigratorExt: Send {
     fn build_migrations() -> Migrations;
@@ -151,6 +165,20 @@ impl MigratorExt for Migrator {
             .normal(m0002240_product_version_sbom_index::Migration)
             .normal(m0002250_create_cpe_status::Migration)
             .normal(m0002260_cpe_part_vendor_product_index::Migration)
+            .normal(m0002270_create_sbom_formulation_attestation::Migration)
+            .normal(m0002280_create_sbom_package_external_ref::Migration)
+            .normal(m0002290_create_sbom_snippet::Migration)
+            .normal(m0002300_sbom_advisory_summary_view::Migration)
+            .normal(m0002310_gover_cmp::Migration)
+            .normal(m0002320_relationship_external_reference::Migration)
+            .normal(m0002330_graph_cache::Migration)
+            .normal(m0002340_sbom_deleted_at::Migration)
+            .normal(m0002350_epss_score::Migration)
+            .normal(m0002360_license_compatibility::Migration)
+            .normal(m0002370_license_obligation::Migration)
+            .normal(m0002380_vulnerability_suppression::Migration)
+            .normal(m0002390_webhook::Migration)
+            .normal(m0002400_sbom_advisory_summary_deleted::Migration)
     }
 }
 
@@ -0,0 +1,129 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // CycloneDX 1.6 formulation entries, describing how the content was built
+        manager
+            .create_table(
+                Table::create()
+                    .table(SbomFormulation::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(SbomFormulation::SbomId).uuid().not_null())
+                    .col(ColumnDef::new(SbomFormulation::BomRef).text().not_null())
+                    .col(ColumnDef::new(SbomFormulation::Description).text())
+                    .col(
+                        ColumnDef::new(SbomFormulation::Components)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SbomFormulation::SbomId)
+                            .col(SbomFormulation::BomRef)
+                            .primary(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(SbomFormulation::SbomId)
+                            .to(Sbom::Table, Sbom::SbomId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // CycloneDX 1.6 attestations, part of the document's declarations
+        manager
+            .create_table(
+                Table::create()
+                    .table(SbomAttestation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SbomAttestation::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SbomAttestation::SbomId).uuid().not_null())
+                    .col(ColumnDef::new(SbomAttestation::Summary).text())
+                    .col(ColumnDef::new(SbomAttestation::AssessorRef).text())
+                    .col(
+                        ColumnDef::new(SbomAttestation::Evidence)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(SbomAttestation::SbomId)
+                            .to(Sbom::Table, Sbom::SbomId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .table(SbomAttestation::Table)
+                    .name("sbom_attestation_sbom_id_idx")
+                    .col(SbomAttestation::SbomId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(SbomAttestation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(SbomFormulation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SbomFormulation {
+    Table,
+    SbomId,
+    BomRef,
+    Description,
+    Components,
+}
+
+#[derive(DeriveIden)]
+pub enum SbomAttestation {
+    Table,
+    Id,
+    SbomId,
+    Summary,
+    AssessorRef,
+    Evidence,
+}
+
+#[derive(DeriveIden)]
+pub enum Sbom {
+    Table,
+    SbomId,
+}
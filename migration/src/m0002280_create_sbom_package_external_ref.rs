@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // CycloneDX external references of packages, like website, VCS, or distribution
+        manager
+            .create_table(
+                Table::create()
+                    .table(SbomPackageExternalRef::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SbomPackageExternalRef::SbomId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SbomPackageExternalRef::NodeId)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SbomPackageExternalRef::RefType)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SbomPackageExternalRef::Url)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SbomPackageExternalRef::Hashes)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SbomPackageExternalRef::SbomId)
+                            .col(SbomPackageExternalRef::NodeId)
+                            .col(SbomPackageExternalRef::RefType)
+                            .col(SbomPackageExternalRef::Url)
+                            .primary(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(SbomPackageExternalRef::SbomId)
+                            .to(Sbom::Table, Sbom::SbomId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // for finding all references of a type, like all VCS URLs of an SBOM
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .table(SbomPackageExternalRef::Table)
+                    .name("sbom_package_external_ref_sbom_id_ref_type_idx")
+                    .col(SbomPackageExternalRef::SbomId)
+                    .col(SbomPackageExternalRef::RefType)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(SbomPackageExternalRef::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SbomPackageExternalRef {
+    Table,
+    SbomId,
+    NodeId,
+    RefType,
+    Url,
+    Hashes,
+}
+
+#[derive(DeriveIden)]
+pub enum Sbom {
+    Table,
+    SbomId,
+}
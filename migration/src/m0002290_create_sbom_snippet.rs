@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SPDX snippets, a node type covering a range of a file
+        manager
+            .create_table(
+                Table::create()
+                    .table(SbomSnippet::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(SbomSnippet::SbomId).uuid().not_null())
+                    .col(ColumnDef::new(SbomSnippet::NodeId).text().not_null())
+                    .col(ColumnDef::new(SbomSnippet::FileNodeId).text().not_null())
+                    .col(ColumnDef::new(SbomSnippet::ByteRangeStart).big_integer())
+                    .col(ColumnDef::new(SbomSnippet::ByteRangeEnd).big_integer())
+                    .col(ColumnDef::new(SbomSnippet::LineRangeStart).integer())
+                    .col(ColumnDef::new(SbomSnippet::LineRangeEnd).integer())
+                    .col(ColumnDef::new(SbomSnippet::LicenseConcluded).text())
+                    .primary_key(
+                        Index::create()
+                            .col(SbomSnippet::SbomId)
+                            .col(SbomSnippet::NodeId)
+                            .primary(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(SbomSnippet::SbomId)
+                            .to(Sbom::Table, Sbom::SbomId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(SbomSnippet::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SbomSnippet {
+    Table,
+    SbomId,
+    NodeId,
+    FileNodeId,
+    ByteRangeStart,
+    ByteRangeEnd,
+    LineRangeStart,
+    LineRangeEnd,
+    LicenseConcluded,
+}
+
+#[derive(DeriveIden)]
+pub enum Sbom {
+    Table,
+    SbomId,
+}
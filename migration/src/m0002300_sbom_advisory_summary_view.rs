@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Materialized view of the advisories affecting SBOMs, refreshed periodically
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("m0002300_sbom_advisory_summary_view/up.sql"))
+            .await
+            .map(|_| ())?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP MATERIALIZED VIEW IF EXISTS mv_sbom_advisory_summary;")
+            .await
+            .map(|_| ())?;
+
+        Ok(())
+    }
+}
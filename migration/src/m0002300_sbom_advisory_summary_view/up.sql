@@ -0,0 +1,184 @@
+-- Pre-computed matches between SBOMs and advisories, backing the advisory
+-- summary of the SBOM list. Combines the same matching paths as the details
+-- endpoint: PURL-based matching (via `purl_status` + `version_matches()`),
+-- name-keyed CPE matching (via `product_status` + package name matching), and
+-- package-level CPE-identity matching (via `cpe_status` + `version_matches()`).
+--
+-- There is one row per (sbom, advisory, vulnerability, status). The severity is
+-- the highest severity the advisory scores the vulnerability with, or 'unknown'
+-- if it has no score. Deprecated advisories are excluded.
+CREATE MATERIALIZED VIEW IF NOT EXISTS mv_sbom_advisory_summary AS
+WITH
+-- SBOM package info including version, base_purl_id, and name/namespace.
+-- Referenced 3x so PostgreSQL auto-materializes it.
+sbom_purl_info AS (
+    SELECT
+        spr.sbom_id,
+        vp.version,
+        vp.base_purl_id,
+        bp.name,
+        bp.namespace
+    FROM sbom_node_purl_ref spr
+    JOIN qualified_purl qp ON spr.qualified_purl_id = qp.id
+    JOIN versioned_purl vp ON qp.versioned_purl_id = vp.id
+    JOIN base_purl bp ON vp.base_purl_id = bp.id
+),
+
+-- CPE-based matching: per-SBOM allowed CPE IDs with generalized matching
+sbom_cpes AS (
+    SELECT sdc.sbom_id, cpe.*
+    FROM sbom_describing_cpe sdc
+    JOIN cpe ON sdc.cpe_id = cpe.id
+),
+sbom_generalized_cpes AS (
+    SELECT sc.sbom_id, c.*
+    FROM sbom_cpes sc
+    JOIN cpe c ON c.vendor = sc.vendor
+        AND c.product = sc.product
+        AND c.version = split_part(sc.version, '.', 1)
+),
+sbom_allowed_cpes AS (
+    SELECT sbom_id, id AS cpe_id FROM sbom_cpes
+    UNION
+    SELECT sbom_id, id AS cpe_id FROM sbom_generalized_cpes
+),
+sbom_has_cpes AS (
+    SELECT DISTINCT sbom_id FROM sbom_cpes
+),
+
+-- PURL-based matching
+purl_matches AS (
+    SELECT DISTINCT
+        sp.sbom_id,
+        pst.advisory_id,
+        pst.vulnerability_id,
+        status.slug AS status
+    FROM sbom_purl_info sp
+    JOIN purl_status pst ON pst.base_purl_id = sp.base_purl_id
+    JOIN version_range vr ON pst.version_range_id = vr.id
+    JOIN status ON pst.status_id = status.id
+    JOIN advisory ON pst.advisory_id = advisory.id
+    WHERE advisory.deprecated = false
+      AND version_matches(sp.version, vr.*)
+      AND (
+          pst.context_cpe_id IS NULL
+          OR pst.context_cpe_id IN (SELECT cpe_id FROM sbom_allowed_cpes sac WHERE sac.sbom_id = sp.sbom_id)
+          OR sp.sbom_id NOT IN (SELECT sbom_id FROM sbom_has_cpes)
+      )
+),
+
+-- CPE product_status matches by name
+cpe_matches_name AS (
+    SELECT DISTINCT
+        sp.sbom_id,
+        ps.advisory_id,
+        ps.vulnerability_id,
+        status.slug AS status
+    FROM product_status ps
+    JOIN sbom_purl_info sp ON ps.package = sp.name
+    JOIN status ON ps.status_id = status.id
+    JOIN advisory ON ps.advisory_id = advisory.id
+    WHERE advisory.deprecated = false
+      AND (
+          ps.context_cpe_id IS NULL
+          OR ps.context_cpe_id IN (SELECT cpe_id FROM sbom_allowed_cpes sac WHERE sac.sbom_id = sp.sbom_id)
+          OR sp.sbom_id NOT IN (SELECT sbom_id FROM sbom_has_cpes)
+      )
+),
+
+-- CPE product_status matches by namespace/name
+cpe_matches_ns AS (
+    SELECT DISTINCT
+        sp.sbom_id,
+        ps.advisory_id,
+        ps.vulnerability_id,
+        status.slug AS status
+    FROM product_status ps
+    JOIN sbom_purl_info sp ON ps.package = CONCAT(sp.namespace, '/', sp.name)
+    JOIN status ON ps.status_id = status.id
+    JOIN advisory ON ps.advisory_id = advisory.id
+    WHERE sp.namespace IS NOT NULL
+      AND advisory.deprecated = false
+      AND (
+          ps.context_cpe_id IS NULL
+          OR ps.context_cpe_id IN (SELECT cpe_id FROM sbom_allowed_cpes sac WHERE sac.sbom_id = sp.sbom_id)
+          OR sp.sbom_id NOT IN (SELECT sbom_id FROM sbom_has_cpes)
+      )
+),
+
+-- Package-level CPEs harvested from SBOMs, joined back to the owning package
+-- for its (fallback) version
+sbom_cpe_pkgs AS (
+    SELECT
+        scr.sbom_id,
+        c.vendor,
+        c.product,
+        c.part,
+        COALESCE(NULLIF(c.version, '*'), sp.version) AS version
+    FROM sbom_node_cpe_ref scr
+    JOIN cpe c ON scr.cpe_id = c.id
+    JOIN sbom_package sp ON sp.sbom_id = scr.sbom_id AND sp.node_id = scr.node_id
+),
+
+-- CPE-based matching via cpe_status, without the sbom_describing_cpe context
+-- filter
+cpe_version_matches AS (
+    SELECT DISTINCT
+        p.sbom_id,
+        cs.advisory_id,
+        cs.vulnerability_id,
+        status.slug AS status
+    FROM sbom_cpe_pkgs p
+    JOIN cpe sc ON sc.vendor = p.vendor AND sc.product = p.product AND sc.part = 'a'
+    JOIN cpe_status cs ON cs.cpe_id = sc.id
+    JOIN version_range vr ON cs.version_range_id = vr.id
+    JOIN status ON cs.status_id = status.id
+    JOIN advisory ON cs.advisory_id = advisory.id
+    WHERE p.part = 'a'
+      AND advisory.deprecated = false
+      AND version_matches(p.version, vr.*)
+),
+
+all_matches AS (
+    SELECT * FROM purl_matches
+    UNION
+    SELECT * FROM cpe_matches_name
+    UNION
+    SELECT * FROM cpe_matches_ns
+    UNION
+    SELECT * FROM cpe_version_matches
+),
+
+-- The highest severity per (advisory, vulnerability), across CVSS versions
+scores AS (
+    SELECT DISTINCT ON (advisory_id, vulnerability_id)
+        advisory_id,
+        vulnerability_id,
+        severity::text AS severity
+    FROM advisory_vulnerability_score
+    ORDER BY advisory_id, vulnerability_id,
+        CASE severity::text
+            WHEN 'critical' THEN 5
+            WHEN 'high' THEN 4
+            WHEN 'medium' THEN 3
+            WHEN 'low' THEN 2
+            WHEN 'none' THEN 1
+            ELSE 0
+        END DESC
+)
+
+SELECT
+    m.sbom_id,
+    m.advisory_id,
+    m.vulnerability_id,
+    m.status,
+    COALESCE(s.severity, 'unknown') AS severity
+FROM all_matches m
+LEFT JOIN scores s
+    ON s.advisory_id = m.advisory_id
+    AND s.vulnerability_id = m.vulnerability_id
+WITH DATA;
+
+-- Required for refreshing the view concurrently
+CREATE UNIQUE INDEX IF NOT EXISTS mv_sbom_advisory_summary_idx
+    ON mv_sbom_advisory_summary (sbom_id, advisory_id, vulnerability_id, status);
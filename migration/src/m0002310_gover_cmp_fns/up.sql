@@ -0,0 +1,16 @@
+CREATE OR REPLACE FUNCTION public.gover_cmp(left_p text, right_p text) RETURNS integer
+    LANGUAGE plpgsql IMMUTABLE PARALLEL SAFE
+    AS $$
+BEGIN
+    -- Go versions are semantic versions, prefixed with a 'v'
+    IF left_p LIKE 'v%' THEN
+        left_p := substring(left_p FROM 2);
+    END IF;
+
+    IF right_p LIKE 'v%' THEN
+        right_p := substring(right_p FROM 2);
+    END IF;
+
+    RETURN semver_cmp(left_p, right_p);
+END
+$$;
@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Persist the analysis graphs of SBOMs, so that they don't need to be re-constructed
+        // after a restart.
+        manager
+            .create_table(
+                Table::create()
+                    .table(GraphCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GraphCache::SbomId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GraphCache::Version).integer().not_null())
+                    .col(
+                        ColumnDef::new(GraphCache::SerializedGraph)
+                            .binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GraphCache::LoadedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GraphCache::Table, GraphCache::SbomId)
+                            .to(Sbom::Table, Sbom::SbomId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(GraphCache::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum GraphCache {
+    Table,
+    SbomId,
+    Version,
+    SerializedGraph,
+    LoadedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum Sbom {
+    Table,
+    SbomId,
+}
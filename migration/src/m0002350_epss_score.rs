@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // EPSS scores are published for all CVEs, most of which are not known to the vulnerability
+        // table. So there's no foreign key to it.
+        manager
+            .create_table(
+                Table::create()
+                    .table(EpssScore::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EpssScore::VulnerabilityId)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EpssScore::Score).double().not_null())
+                    .col(ColumnDef::new(EpssScore::Percentile).double().not_null())
+                    .col(ColumnDef::new(EpssScore::ScoredDate).date().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(EpssScore::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum EpssScore {
+    Table,
+    VulnerabilityId,
+    Score,
+    Percentile,
+    ScoredDate,
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LicenseCompatibility::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LicenseCompatibility::LicenseA)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LicenseCompatibility::LicenseB)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LicenseCompatibility::Compatible)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LicenseCompatibility::Explanation)
+                            .text()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(LicenseCompatibility::LicenseA)
+                            .col(LicenseCompatibility::LicenseB),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("m0002360_license_compatibility/up.sql"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(LicenseCompatibility::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum LicenseCompatibility {
+    Table,
+    LicenseA,
+    LicenseB,
+    Compatible,
+    Explanation,
+}
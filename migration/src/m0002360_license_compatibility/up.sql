@@ -0,0 +1,149 @@
+-- A curated set of commonly cited compatibility relations between SPDX licenses, regarding the
+-- combination of code under both licenses in a single distributed product.
+--
+-- Each pair is stored once, the lookup checks both orders.
+INSERT INTO license_compatibility (license_a, license_b, compatible, explanation) VALUES
+    ('AGPL-3.0-only', 'AGPL-3.0-or-later', TRUE, 'AGPL-3.0-only and AGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'Apache-2.0', TRUE, 'Apache-2.0 is permissive, and can be combined with AGPL-3.0-only, as long as the combined work is distributed under the terms of AGPL-3.0-only.'),
+    ('AGPL-3.0-only', 'BSD-2-Clause', TRUE, 'BSD-2-Clause is permissive, and can be combined with AGPL-3.0-only, as long as the combined work is distributed under the terms of AGPL-3.0-only.'),
+    ('AGPL-3.0-only', 'BSD-3-Clause', TRUE, 'BSD-3-Clause is permissive, and can be combined with AGPL-3.0-only, as long as the combined work is distributed under the terms of AGPL-3.0-only.'),
+    ('AGPL-3.0-only', 'CDDL-1.0', FALSE, 'CDDL-1.0 and the GNU General Public Licenses impose conflicting copyleft requirements on the combined work.'),
+    ('AGPL-3.0-only', 'EPL-2.0', FALSE, 'EPL-2.0 is incompatible with the GNU General Public Licenses, unless the GPL is designated as a secondary license of the EPL-2.0 licensed code.'),
+    ('AGPL-3.0-only', 'GPL-2.0-only', FALSE, 'GPL-2.0-only does not permit distribution under version 3 of the GNU licenses, which AGPL-3.0-only requires.'),
+    ('AGPL-3.0-only', 'GPL-2.0-or-later', TRUE, 'AGPL-3.0-only and GPL-2.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'GPL-3.0-only', TRUE, 'AGPL-3.0-only and GPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'GPL-3.0-or-later', TRUE, 'AGPL-3.0-only and GPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'ISC', TRUE, 'ISC is permissive, and can be combined with AGPL-3.0-only, as long as the combined work is distributed under the terms of AGPL-3.0-only.'),
+    ('AGPL-3.0-only', 'LGPL-2.1-only', TRUE, 'AGPL-3.0-only and LGPL-2.1-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'LGPL-2.1-or-later', TRUE, 'AGPL-3.0-only and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'LGPL-3.0-only', TRUE, 'AGPL-3.0-only and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'LGPL-3.0-or-later', TRUE, 'AGPL-3.0-only and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-only', 'MIT', TRUE, 'MIT is permissive, and can be combined with AGPL-3.0-only, as long as the combined work is distributed under the terms of AGPL-3.0-only.'),
+    ('AGPL-3.0-only', 'MPL-2.0', TRUE, 'AGPL-3.0-only is a secondary license of MPL-2.0, which allows distributing the combined work under AGPL-3.0-only.'),
+    ('AGPL-3.0-or-later', 'Apache-2.0', TRUE, 'Apache-2.0 is permissive, and can be combined with AGPL-3.0-or-later, as long as the combined work is distributed under the terms of AGPL-3.0-or-later.'),
+    ('AGPL-3.0-or-later', 'BSD-2-Clause', TRUE, 'BSD-2-Clause is permissive, and can be combined with AGPL-3.0-or-later, as long as the combined work is distributed under the terms of AGPL-3.0-or-later.'),
+    ('AGPL-3.0-or-later', 'BSD-3-Clause', TRUE, 'BSD-3-Clause is permissive, and can be combined with AGPL-3.0-or-later, as long as the combined work is distributed under the terms of AGPL-3.0-or-later.'),
+    ('AGPL-3.0-or-later', 'CDDL-1.0', FALSE, 'CDDL-1.0 and the GNU General Public Licenses impose conflicting copyleft requirements on the combined work.'),
+    ('AGPL-3.0-or-later', 'EPL-2.0', FALSE, 'EPL-2.0 is incompatible with the GNU General Public Licenses, unless the GPL is designated as a secondary license of the EPL-2.0 licensed code.'),
+    ('AGPL-3.0-or-later', 'GPL-2.0-only', FALSE, 'GPL-2.0-only does not permit distribution under version 3 of the GNU licenses, which AGPL-3.0-or-later requires.'),
+    ('AGPL-3.0-or-later', 'GPL-2.0-or-later', TRUE, 'AGPL-3.0-or-later and GPL-2.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'GPL-3.0-only', TRUE, 'AGPL-3.0-or-later and GPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'GPL-3.0-or-later', TRUE, 'AGPL-3.0-or-later and GPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'ISC', TRUE, 'ISC is permissive, and can be combined with AGPL-3.0-or-later, as long as the combined work is distributed under the terms of AGPL-3.0-or-later.'),
+    ('AGPL-3.0-or-later', 'LGPL-2.1-only', TRUE, 'AGPL-3.0-or-later and LGPL-2.1-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'LGPL-2.1-or-later', TRUE, 'AGPL-3.0-or-later and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'LGPL-3.0-only', TRUE, 'AGPL-3.0-or-later and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'LGPL-3.0-or-later', TRUE, 'AGPL-3.0-or-later and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('AGPL-3.0-or-later', 'MIT', TRUE, 'MIT is permissive, and can be combined with AGPL-3.0-or-later, as long as the combined work is distributed under the terms of AGPL-3.0-or-later.'),
+    ('AGPL-3.0-or-later', 'MPL-2.0', TRUE, 'AGPL-3.0-or-later is a secondary license of MPL-2.0, which allows distributing the combined work under AGPL-3.0-or-later.'),
+    ('Apache-2.0', 'BSD-2-Clause', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('Apache-2.0', 'BSD-3-Clause', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('Apache-2.0', 'CDDL-1.0', TRUE, 'Apache-2.0 is permissive, and can be combined with CDDL-1.0, as long as the combined work is distributed under the terms of CDDL-1.0.'),
+    ('Apache-2.0', 'EPL-2.0', TRUE, 'Apache-2.0 is permissive, and can be combined with EPL-2.0, as long as the combined work is distributed under the terms of EPL-2.0.'),
+    ('Apache-2.0', 'GPL-2.0-only', FALSE, 'The patent termination and indemnification terms of Apache-2.0 are additional restrictions not permitted by GPL-2.0-only.'),
+    ('Apache-2.0', 'GPL-2.0-or-later', TRUE, 'Apache-2.0 is permissive, and can be combined with GPL-2.0-or-later, as long as the combined work is distributed under the terms of GPL-2.0-or-later.'),
+    ('Apache-2.0', 'GPL-3.0-only', TRUE, 'Apache-2.0 is permissive, and can be combined with GPL-3.0-only, as long as the combined work is distributed under the terms of GPL-3.0-only.'),
+    ('Apache-2.0', 'GPL-3.0-or-later', TRUE, 'Apache-2.0 is permissive, and can be combined with GPL-3.0-or-later, as long as the combined work is distributed under the terms of GPL-3.0-or-later.'),
+    ('Apache-2.0', 'ISC', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('Apache-2.0', 'LGPL-2.1-or-later', TRUE, 'Apache-2.0 is permissive, and can be combined with LGPL-2.1-or-later, as long as the combined work is distributed under the terms of LGPL-2.1-or-later.'),
+    ('Apache-2.0', 'LGPL-3.0-only', TRUE, 'Apache-2.0 is permissive, and can be combined with LGPL-3.0-only, as long as the combined work is distributed under the terms of LGPL-3.0-only.'),
+    ('Apache-2.0', 'LGPL-3.0-or-later', TRUE, 'Apache-2.0 is permissive, and can be combined with LGPL-3.0-or-later, as long as the combined work is distributed under the terms of LGPL-3.0-or-later.'),
+    ('Apache-2.0', 'MIT', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('Apache-2.0', 'MPL-2.0', TRUE, 'Apache-2.0 is permissive, and can be combined with MPL-2.0, as long as the combined work is distributed under the terms of MPL-2.0.'),
+    ('BSD-2-Clause', 'BSD-3-Clause', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('BSD-2-Clause', 'CDDL-1.0', TRUE, 'BSD-2-Clause is permissive, and can be combined with CDDL-1.0, as long as the combined work is distributed under the terms of CDDL-1.0.'),
+    ('BSD-2-Clause', 'EPL-2.0', TRUE, 'BSD-2-Clause is permissive, and can be combined with EPL-2.0, as long as the combined work is distributed under the terms of EPL-2.0.'),
+    ('BSD-2-Clause', 'GPL-2.0-only', TRUE, 'BSD-2-Clause is permissive, and can be combined with GPL-2.0-only, as long as the combined work is distributed under the terms of GPL-2.0-only.'),
+    ('BSD-2-Clause', 'GPL-2.0-or-later', TRUE, 'BSD-2-Clause is permissive, and can be combined with GPL-2.0-or-later, as long as the combined work is distributed under the terms of GPL-2.0-or-later.'),
+    ('BSD-2-Clause', 'GPL-3.0-only', TRUE, 'BSD-2-Clause is permissive, and can be combined with GPL-3.0-only, as long as the combined work is distributed under the terms of GPL-3.0-only.'),
+    ('BSD-2-Clause', 'GPL-3.0-or-later', TRUE, 'BSD-2-Clause is permissive, and can be combined with GPL-3.0-or-later, as long as the combined work is distributed under the terms of GPL-3.0-or-later.'),
+    ('BSD-2-Clause', 'ISC', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('BSD-2-Clause', 'LGPL-2.1-only', TRUE, 'BSD-2-Clause is permissive, and can be combined with LGPL-2.1-only, as long as the combined work is distributed under the terms of LGPL-2.1-only.'),
+    ('BSD-2-Clause', 'LGPL-2.1-or-later', TRUE, 'BSD-2-Clause is permissive, and can be combined with LGPL-2.1-or-later, as long as the combined work is distributed under the terms of LGPL-2.1-or-later.'),
+    ('BSD-2-Clause', 'LGPL-3.0-only', TRUE, 'BSD-2-Clause is permissive, and can be combined with LGPL-3.0-only, as long as the combined work is distributed under the terms of LGPL-3.0-only.'),
+    ('BSD-2-Clause', 'LGPL-3.0-or-later', TRUE, 'BSD-2-Clause is permissive, and can be combined with LGPL-3.0-or-later, as long as the combined work is distributed under the terms of LGPL-3.0-or-later.'),
+    ('BSD-2-Clause', 'MIT', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('BSD-2-Clause', 'MPL-2.0', TRUE, 'BSD-2-Clause is permissive, and can be combined with MPL-2.0, as long as the combined work is distributed under the terms of MPL-2.0.'),
+    ('BSD-3-Clause', 'CDDL-1.0', TRUE, 'BSD-3-Clause is permissive, and can be combined with CDDL-1.0, as long as the combined work is distributed under the terms of CDDL-1.0.'),
+    ('BSD-3-Clause', 'EPL-2.0', TRUE, 'BSD-3-Clause is permissive, and can be combined with EPL-2.0, as long as the combined work is distributed under the terms of EPL-2.0.'),
+    ('BSD-3-Clause', 'GPL-2.0-only', TRUE, 'BSD-3-Clause is permissive, and can be combined with GPL-2.0-only, as long as the combined work is distributed under the terms of GPL-2.0-only.'),
+    ('BSD-3-Clause', 'GPL-2.0-or-later', TRUE, 'BSD-3-Clause is permissive, and can be combined with GPL-2.0-or-later, as long as the combined work is distributed under the terms of GPL-2.0-or-later.'),
+    ('BSD-3-Clause', 'GPL-3.0-only', TRUE, 'BSD-3-Clause is permissive, and can be combined with GPL-3.0-only, as long as the combined work is distributed under the terms of GPL-3.0-only.'),
+    ('BSD-3-Clause', 'GPL-3.0-or-later', TRUE, 'BSD-3-Clause is permissive, and can be combined with GPL-3.0-or-later, as long as the combined work is distributed under the terms of GPL-3.0-or-later.'),
+    ('BSD-3-Clause', 'ISC', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('BSD-3-Clause', 'LGPL-2.1-only', TRUE, 'BSD-3-Clause is permissive, and can be combined with LGPL-2.1-only, as long as the combined work is distributed under the terms of LGPL-2.1-only.'),
+    ('BSD-3-Clause', 'LGPL-2.1-or-later', TRUE, 'BSD-3-Clause is permissive, and can be combined with LGPL-2.1-or-later, as long as the combined work is distributed under the terms of LGPL-2.1-or-later.'),
+    ('BSD-3-Clause', 'LGPL-3.0-only', TRUE, 'BSD-3-Clause is permissive, and can be combined with LGPL-3.0-only, as long as the combined work is distributed under the terms of LGPL-3.0-only.'),
+    ('BSD-3-Clause', 'LGPL-3.0-or-later', TRUE, 'BSD-3-Clause is permissive, and can be combined with LGPL-3.0-or-later, as long as the combined work is distributed under the terms of LGPL-3.0-or-later.'),
+    ('BSD-3-Clause', 'MIT', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('BSD-3-Clause', 'MPL-2.0', TRUE, 'BSD-3-Clause is permissive, and can be combined with MPL-2.0, as long as the combined work is distributed under the terms of MPL-2.0.'),
+    ('CDDL-1.0', 'GPL-2.0-only', FALSE, 'CDDL-1.0 and the GNU General Public Licenses impose conflicting copyleft requirements on the combined work.'),
+    ('CDDL-1.0', 'GPL-2.0-or-later', FALSE, 'CDDL-1.0 and the GNU General Public Licenses impose conflicting copyleft requirements on the combined work.'),
+    ('CDDL-1.0', 'GPL-3.0-only', FALSE, 'CDDL-1.0 and the GNU General Public Licenses impose conflicting copyleft requirements on the combined work.'),
+    ('CDDL-1.0', 'GPL-3.0-or-later', FALSE, 'CDDL-1.0 and the GNU General Public Licenses impose conflicting copyleft requirements on the combined work.'),
+    ('CDDL-1.0', 'ISC', TRUE, 'ISC is permissive, and can be combined with CDDL-1.0, as long as the combined work is distributed under the terms of CDDL-1.0.'),
+    ('CDDL-1.0', 'MIT', TRUE, 'MIT is permissive, and can be combined with CDDL-1.0, as long as the combined work is distributed under the terms of CDDL-1.0.'),
+    ('CDDL-1.0', 'MPL-2.0', TRUE, 'Both MPL-2.0 and CDDL-1.0 are file based copyleft licenses, which allows combining files under both licenses in a larger work.'),
+    ('EPL-2.0', 'GPL-2.0-only', FALSE, 'EPL-2.0 is incompatible with the GNU General Public Licenses, unless the GPL is designated as a secondary license of the EPL-2.0 licensed code.'),
+    ('EPL-2.0', 'GPL-2.0-or-later', FALSE, 'EPL-2.0 is incompatible with the GNU General Public Licenses, unless the GPL is designated as a secondary license of the EPL-2.0 licensed code.'),
+    ('EPL-2.0', 'GPL-3.0-only', FALSE, 'EPL-2.0 is incompatible with the GNU General Public Licenses, unless the GPL is designated as a secondary license of the EPL-2.0 licensed code.'),
+    ('EPL-2.0', 'GPL-3.0-or-later', FALSE, 'EPL-2.0 is incompatible with the GNU General Public Licenses, unless the GPL is designated as a secondary license of the EPL-2.0 licensed code.'),
+    ('EPL-2.0', 'ISC', TRUE, 'ISC is permissive, and can be combined with EPL-2.0, as long as the combined work is distributed under the terms of EPL-2.0.'),
+    ('EPL-2.0', 'MIT', TRUE, 'MIT is permissive, and can be combined with EPL-2.0, as long as the combined work is distributed under the terms of EPL-2.0.'),
+    ('EPL-2.0', 'MPL-2.0', TRUE, 'Both MPL-2.0 and EPL-2.0 are file based copyleft licenses, which allows combining files under both licenses in a larger work.'),
+    ('GPL-2.0-only', 'GPL-2.0-or-later', TRUE, 'GPL-2.0-only and GPL-2.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-only', 'GPL-3.0-only', FALSE, 'GPL-2.0-only does not permit distribution under version 3 of the GNU licenses, which GPL-3.0-only requires.'),
+    ('GPL-2.0-only', 'GPL-3.0-or-later', FALSE, 'GPL-2.0-only does not permit distribution under version 3 of the GNU licenses, which GPL-3.0-or-later requires.'),
+    ('GPL-2.0-only', 'ISC', TRUE, 'ISC is permissive, and can be combined with GPL-2.0-only, as long as the combined work is distributed under the terms of GPL-2.0-only.'),
+    ('GPL-2.0-only', 'LGPL-2.1-only', TRUE, 'GPL-2.0-only and LGPL-2.1-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-only', 'LGPL-2.1-or-later', TRUE, 'GPL-2.0-only and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-only', 'LGPL-3.0-only', FALSE, 'GPL-2.0-only does not permit distribution under version 3 of the GNU licenses, which LGPL-3.0-only requires.'),
+    ('GPL-2.0-only', 'LGPL-3.0-or-later', FALSE, 'GPL-2.0-only does not permit distribution under version 3 of the GNU licenses, which LGPL-3.0-or-later requires.'),
+    ('GPL-2.0-only', 'MIT', TRUE, 'MIT is permissive, and can be combined with GPL-2.0-only, as long as the combined work is distributed under the terms of GPL-2.0-only.'),
+    ('GPL-2.0-only', 'MPL-2.0', TRUE, 'GPL-2.0-only is a secondary license of MPL-2.0, which allows distributing the combined work under GPL-2.0-only.'),
+    ('GPL-2.0-or-later', 'GPL-3.0-only', TRUE, 'GPL-2.0-or-later and GPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-or-later', 'GPL-3.0-or-later', TRUE, 'GPL-2.0-or-later and GPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-or-later', 'ISC', TRUE, 'ISC is permissive, and can be combined with GPL-2.0-or-later, as long as the combined work is distributed under the terms of GPL-2.0-or-later.'),
+    ('GPL-2.0-or-later', 'LGPL-2.1-only', TRUE, 'GPL-2.0-or-later and LGPL-2.1-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-or-later', 'LGPL-2.1-or-later', TRUE, 'GPL-2.0-or-later and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-or-later', 'LGPL-3.0-only', TRUE, 'GPL-2.0-or-later and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-or-later', 'LGPL-3.0-or-later', TRUE, 'GPL-2.0-or-later and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-2.0-or-later', 'MIT', TRUE, 'MIT is permissive, and can be combined with GPL-2.0-or-later, as long as the combined work is distributed under the terms of GPL-2.0-or-later.'),
+    ('GPL-2.0-or-later', 'MPL-2.0', TRUE, 'GPL-2.0-or-later is a secondary license of MPL-2.0, which allows distributing the combined work under GPL-2.0-or-later.'),
+    ('GPL-3.0-only', 'GPL-3.0-or-later', TRUE, 'GPL-3.0-only and GPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-only', 'ISC', TRUE, 'ISC is permissive, and can be combined with GPL-3.0-only, as long as the combined work is distributed under the terms of GPL-3.0-only.'),
+    ('GPL-3.0-only', 'LGPL-2.1-only', TRUE, 'GPL-3.0-only and LGPL-2.1-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-only', 'LGPL-2.1-or-later', TRUE, 'GPL-3.0-only and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-only', 'LGPL-3.0-only', TRUE, 'GPL-3.0-only and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-only', 'LGPL-3.0-or-later', TRUE, 'GPL-3.0-only and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-only', 'MIT', TRUE, 'MIT is permissive, and can be combined with GPL-3.0-only, as long as the combined work is distributed under the terms of GPL-3.0-only.'),
+    ('GPL-3.0-only', 'MPL-2.0', TRUE, 'GPL-3.0-only is a secondary license of MPL-2.0, which allows distributing the combined work under GPL-3.0-only.'),
+    ('GPL-3.0-or-later', 'ISC', TRUE, 'ISC is permissive, and can be combined with GPL-3.0-or-later, as long as the combined work is distributed under the terms of GPL-3.0-or-later.'),
+    ('GPL-3.0-or-later', 'LGPL-2.1-only', TRUE, 'GPL-3.0-or-later and LGPL-2.1-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-or-later', 'LGPL-2.1-or-later', TRUE, 'GPL-3.0-or-later and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-or-later', 'LGPL-3.0-only', TRUE, 'GPL-3.0-or-later and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-or-later', 'LGPL-3.0-or-later', TRUE, 'GPL-3.0-or-later and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('GPL-3.0-or-later', 'MIT', TRUE, 'MIT is permissive, and can be combined with GPL-3.0-or-later, as long as the combined work is distributed under the terms of GPL-3.0-or-later.'),
+    ('GPL-3.0-or-later', 'MPL-2.0', TRUE, 'GPL-3.0-or-later is a secondary license of MPL-2.0, which allows distributing the combined work under GPL-3.0-or-later.'),
+    ('ISC', 'LGPL-2.1-only', TRUE, 'ISC is permissive, and can be combined with LGPL-2.1-only, as long as the combined work is distributed under the terms of LGPL-2.1-only.'),
+    ('ISC', 'LGPL-2.1-or-later', TRUE, 'ISC is permissive, and can be combined with LGPL-2.1-or-later, as long as the combined work is distributed under the terms of LGPL-2.1-or-later.'),
+    ('ISC', 'LGPL-3.0-only', TRUE, 'ISC is permissive, and can be combined with LGPL-3.0-only, as long as the combined work is distributed under the terms of LGPL-3.0-only.'),
+    ('ISC', 'LGPL-3.0-or-later', TRUE, 'ISC is permissive, and can be combined with LGPL-3.0-or-later, as long as the combined work is distributed under the terms of LGPL-3.0-or-later.'),
+    ('ISC', 'MIT', TRUE, 'Both licenses are permissive and only require preserving notices.'),
+    ('ISC', 'MPL-2.0', TRUE, 'ISC is permissive, and can be combined with MPL-2.0, as long as the combined work is distributed under the terms of MPL-2.0.'),
+    ('LGPL-2.1-only', 'LGPL-2.1-or-later', TRUE, 'LGPL-2.1-only and LGPL-2.1-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('LGPL-2.1-only', 'LGPL-3.0-only', TRUE, 'LGPL-2.1-only and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('LGPL-2.1-only', 'LGPL-3.0-or-later', TRUE, 'LGPL-2.1-only and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('LGPL-2.1-only', 'MIT', TRUE, 'MIT is permissive, and can be combined with LGPL-2.1-only, as long as the combined work is distributed under the terms of LGPL-2.1-only.'),
+    ('LGPL-2.1-only', 'MPL-2.0', TRUE, 'LGPL-2.1-only is a secondary license of MPL-2.0, which allows distributing the combined work under LGPL-2.1-only.'),
+    ('LGPL-2.1-or-later', 'LGPL-3.0-only', TRUE, 'LGPL-2.1-or-later and LGPL-3.0-only can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('LGPL-2.1-or-later', 'LGPL-3.0-or-later', TRUE, 'LGPL-2.1-or-later and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('LGPL-2.1-or-later', 'MIT', TRUE, 'MIT is permissive, and can be combined with LGPL-2.1-or-later, as long as the combined work is distributed under the terms of LGPL-2.1-or-later.'),
+    ('LGPL-2.1-or-later', 'MPL-2.0', TRUE, 'LGPL-2.1-or-later is a secondary license of MPL-2.0, which allows distributing the combined work under LGPL-2.1-or-later.'),
+    ('LGPL-3.0-only', 'LGPL-3.0-or-later', TRUE, 'LGPL-3.0-only and LGPL-3.0-or-later can be combined, distributing the combined work under the terms of the stricter of the GNU licenses.'),
+    ('LGPL-3.0-only', 'MIT', TRUE, 'MIT is permissive, and can be combined with LGPL-3.0-only, as long as the combined work is distributed under the terms of LGPL-3.0-only.'),
+    ('LGPL-3.0-only', 'MPL-2.0', TRUE, 'LGPL-3.0-only is a secondary license of MPL-2.0, which allows distributing the combined work under LGPL-3.0-only.'),
+    ('LGPL-3.0-or-later', 'MIT', TRUE, 'MIT is permissive, and can be combined with LGPL-3.0-or-later, as long as the combined work is distributed under the terms of LGPL-3.0-or-later.'),
+    ('LGPL-3.0-or-later', 'MPL-2.0', TRUE, 'LGPL-3.0-or-later is a secondary license of MPL-2.0, which allows distributing the combined work under LGPL-3.0-or-later.'),
+    ('MIT', 'MPL-2.0', TRUE, 'MIT is permissive, and can be combined with MPL-2.0, as long as the combined work is distributed under the terms of MPL-2.0.')
+ON CONFLICT DO NOTHING;
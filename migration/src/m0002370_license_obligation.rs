@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::{extension::postgres::Type, *};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(CopyleftStrength::Type)
+                    .values([
+                        CopyleftStrength::Weak,
+                        CopyleftStrength::Strong,
+                        CopyleftStrength::Network,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LicenseObligation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LicenseObligation::LicenseId)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LicenseObligation::RequiresAttribution)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LicenseObligation::RequiresSourceDisclosure)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LicenseObligation::PatentGrant)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(LicenseObligation::Copyleft).enumeration(
+                        CopyleftStrength::Type,
+                        [
+                            CopyleftStrength::Weak,
+                            CopyleftStrength::Strong,
+                            CopyleftStrength::Network,
+                        ],
+                    ))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(include_str!("m0002370_license_obligation/up.sql"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(LicenseObligation::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(
+                Type::drop()
+                    .if_exists()
+                    .name(CopyleftStrength::Type)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum LicenseObligation {
+    Table,
+    LicenseId,
+    RequiresAttribution,
+    RequiresSourceDisclosure,
+    PatentGrant,
+    Copyleft,
+}
+
+#[derive(DeriveIden)]
+pub enum CopyleftStrength {
+    #[sea_orm(iden = "copyleft_strength")]
+    Type,
+    Weak,
+    Strong,
+    Network,
+}
@@ -0,0 +1,43 @@
+-- The obligations triggered by distributing software under common SPDX licenses.
+INSERT INTO license_obligation (license_id, requires_attribution, requires_source_disclosure, patent_grant, copyleft) VALUES
+    ('0BSD', FALSE, FALSE, FALSE, NULL),
+    ('AFL-3.0', TRUE, FALSE, TRUE, NULL),
+    ('AGPL-3.0-only', TRUE, TRUE, TRUE, 'network'),
+    ('AGPL-3.0-or-later', TRUE, TRUE, TRUE, 'network'),
+    ('Apache-1.1', TRUE, FALSE, FALSE, NULL),
+    ('Apache-2.0', TRUE, FALSE, TRUE, NULL),
+    ('Artistic-2.0', TRUE, FALSE, TRUE, NULL),
+    ('BSD-1-Clause', TRUE, FALSE, FALSE, NULL),
+    ('BSD-2-Clause', TRUE, FALSE, FALSE, NULL),
+    ('BSD-3-Clause', TRUE, FALSE, FALSE, NULL),
+    ('BSL-1.0', TRUE, FALSE, FALSE, NULL),
+    ('CC-BY-4.0', TRUE, FALSE, FALSE, NULL),
+    ('CC-BY-SA-4.0', TRUE, TRUE, FALSE, 'strong'),
+    ('CC0-1.0', FALSE, FALSE, FALSE, NULL),
+    ('CDDL-1.0', TRUE, TRUE, TRUE, 'weak'),
+    ('CDDL-1.1', TRUE, TRUE, TRUE, 'weak'),
+    ('EPL-1.0', TRUE, TRUE, TRUE, 'weak'),
+    ('EPL-2.0', TRUE, TRUE, TRUE, 'weak'),
+    ('EUPL-1.2', TRUE, TRUE, TRUE, 'network'),
+    ('GPL-2.0-only', TRUE, TRUE, FALSE, 'strong'),
+    ('GPL-2.0-or-later', TRUE, TRUE, FALSE, 'strong'),
+    ('GPL-3.0-only', TRUE, TRUE, TRUE, 'strong'),
+    ('GPL-3.0-or-later', TRUE, TRUE, TRUE, 'strong'),
+    ('ISC', TRUE, FALSE, FALSE, NULL),
+    ('LGPL-2.0-only', TRUE, TRUE, FALSE, 'weak'),
+    ('LGPL-2.0-or-later', TRUE, TRUE, FALSE, 'weak'),
+    ('LGPL-2.1-only', TRUE, TRUE, FALSE, 'weak'),
+    ('LGPL-2.1-or-later', TRUE, TRUE, FALSE, 'weak'),
+    ('LGPL-3.0-only', TRUE, TRUE, TRUE, 'weak'),
+    ('LGPL-3.0-or-later', TRUE, TRUE, TRUE, 'weak'),
+    ('MIT', TRUE, FALSE, FALSE, NULL),
+    ('MIT-0', FALSE, FALSE, FALSE, NULL),
+    ('MPL-1.1', TRUE, TRUE, TRUE, 'weak'),
+    ('MPL-2.0', TRUE, TRUE, TRUE, 'weak'),
+    ('OSL-3.0', TRUE, TRUE, TRUE, 'network'),
+    ('PostgreSQL', TRUE, FALSE, FALSE, NULL),
+    ('Python-2.0', TRUE, FALSE, FALSE, NULL),
+    ('Unlicense', FALSE, FALSE, FALSE, NULL),
+    ('UPL-1.0', TRUE, FALSE, TRUE, NULL),
+    ('Zlib', TRUE, FALSE, FALSE, NULL)
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,116 @@
+use crate::UuidV4;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VulnerabilitySuppression::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::Id)
+                            .uuid()
+                            .not_null()
+                            .default(Func::cust(UuidV4))
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::VulnerabilityId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::Purl)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::Justification)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::CreatedBy)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VulnerabilitySuppression::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                VulnerabilitySuppression::Table,
+                                VulnerabilitySuppression::VulnerabilityId,
+                            )
+                            .to(Vulnerability::Table, Vulnerability::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Suppressions are looked up by the vulnerabilities found during an analysis
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .table(VulnerabilitySuppression::Table)
+                    .name(Indexes::IdxVulnerabilitySuppressionVulnerabilityId.to_string())
+                    .col(VulnerabilitySuppression::VulnerabilityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(VulnerabilitySuppression::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Indexes {
+    IdxVulnerabilitySuppressionVulnerabilityId,
+}
+
+#[derive(DeriveIden)]
+enum VulnerabilitySuppression {
+    Table,
+    Id,
+    VulnerabilityId,
+    Purl,
+    Justification,
+    ExpiresAt,
+    CreatedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Vulnerability {
+    Table,
+    Id,
+}
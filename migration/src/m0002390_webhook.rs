@@ -0,0 +1,163 @@
+use crate::UuidV4;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookConfig::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookConfig::Id)
+                            .uuid()
+                            .not_null()
+                            .default(Func::cust(UuidV4))
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebhookConfig::Url).string().not_null())
+                    .col(ColumnDef::new(WebhookConfig::Secret).string().not_null())
+                    .col(
+                        ColumnDef::new(WebhookConfig::Events)
+                            .array(ColumnType::Text)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDeliveryLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::Id)
+                            .uuid()
+                            .not_null()
+                            .default(Func::cust(UuidV4))
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::WebhookId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::Event)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::Payload)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::State)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::Created)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDeliveryLog::NextAttempt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(WebhookDeliveryLog::LastAttempt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(WebhookDeliveryLog::LastStatus).integer())
+                    .col(ColumnDef::new(WebhookDeliveryLog::LastError).string())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(WebhookDeliveryLog::Table, WebhookDeliveryLog::WebhookId)
+                            .to(WebhookConfig::Table, WebhookConfig::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Pending deliveries are picked up once they are due
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .table(WebhookDeliveryLog::Table)
+                    .name(Indexes::IdxWebhookDeliveryLogStateNextAttempt.to_string())
+                    .col(WebhookDeliveryLog::State)
+                    .col(WebhookDeliveryLog::NextAttempt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(WebhookDeliveryLog::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .if_exists()
+                    .table(WebhookConfig::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Indexes {
+    IdxWebhookDeliveryLogStateNextAttempt,
+}
+
+#[derive(DeriveIden)]
+enum WebhookConfig {
+    Table,
+    Id,
+    Url,
+    Secret,
+    Events,
+}
+
+#[derive(DeriveIden)]
+enum WebhookDeliveryLog {
+    Table,
+    Id,
+    WebhookId,
+    Event,
+    Payload,
+    State,
+    Attempts,
+    Created,
+    NextAttempt,
+    LastAttempt,
+    LastStatus,
+    LastError,
+}
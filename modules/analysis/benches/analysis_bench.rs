@@ -0,0 +1,207 @@
+#![recursion_limit = "512"]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+#[path = "fixtures/gen.rs"]
+mod fixtures;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::{fmt::Debug, hint::black_box, time::Duration};
+use test_context::AsyncTestContext;
+use tokio::runtime::Runtime;
+use trustify_common::{
+    db::{ReadOnly, query::Query},
+    model::Paginated,
+};
+use trustify_module_analysis::{
+    config::AnalysisConfig,
+    service::{AnalysisService, ComponentReference, GraphQuery, QueryOptions},
+};
+use trustify_test_context::TrustifyContext;
+use uuid::Uuid;
+
+/// The number of loaded graphs to query
+const GRAPHS: [usize; 3] = [1, 10, 50];
+
+/// The number of nodes of each queried graph
+const QUERY_NODES: usize = 1_000;
+
+struct Setup {
+    runtime: Runtime,
+    ctx: TrustifyContext,
+    service: AnalysisService,
+    /// SBOMs of 1k and 10k nodes
+    sizes: Vec<(usize, Uuid)>,
+}
+
+fn setup() -> Setup {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let ctx = runtime.block_on(TrustifyContext::setup());
+    // the service spawns background tasks, so it must be created inside the runtime
+    let service = {
+        let _guard = runtime.enter();
+        AnalysisService::new(AnalysisConfig::default(), ReadOnly::new(ctx.db.clone()))
+    };
+
+    let sizes = runtime.block_on(async {
+        let mut sizes = vec![];
+        for nodes in [1_000, 10_000] {
+            let ids = fixtures::generate_sboms(&ctx.db, &format!("size{nodes}"), nodes, 1)
+                .await
+                .expect("generating SBOM");
+            sizes.push((nodes, ids[0]));
+        }
+
+        for graphs in GRAPHS {
+            fixtures::generate_sboms(&ctx.db, &prefix(graphs), QUERY_NODES, graphs)
+                .await
+                .expect("generating SBOMs");
+        }
+
+        sizes
+    });
+
+    Setup {
+        runtime,
+        ctx,
+        service,
+        sizes,
+    }
+}
+
+/// The prefix of the SBOMs used for querying `graphs` graphs at once
+fn prefix(graphs: usize) -> String {
+    format!("graphs{graphs}")
+}
+
+fn load_graph(c: &mut Criterion, setup: &Setup) {
+    let mut group = c.benchmark_group("load_graph");
+
+    for (nodes, sbom_id) in &setup.sizes {
+        group.bench_with_input(BenchmarkId::from_parameter(nodes), sbom_id, |b, sbom_id| {
+            b.iter(|| {
+                // measure loading from the database, not the cache
+                setup.service.clear_all_graphs().unwrap();
+                setup.runtime.block_on(async {
+                    black_box(
+                        setup
+                            .service
+                            .load_graph(&setup.ctx.db, *sbom_id)
+                            .await
+                            .unwrap(),
+                    )
+                })
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn retrieve_root_components(c: &mut Criterion, setup: &Setup) {
+    let mut group = c.benchmark_group("retrieve_root_components");
+
+    for graphs in GRAPHS {
+        let prefix = prefix(graphs);
+        // a leaf of the tree, having the most ancestors
+        let leaf = QUERY_NODES - 1;
+        let name = fixtures::package_name(&prefix, leaf);
+        let purl = fixtures::package_purl(&prefix, leaf);
+        let query = Query::q(&name);
+
+        // warm up the cache, so that only the query is measured
+        retrieve(setup, ComponentReference::Name(&name), graphs);
+
+        group.bench_with_input(BenchmarkId::new("name", graphs), &name, |b, name| {
+            b.iter(|| retrieve(setup, ComponentReference::Name(name), graphs))
+        });
+        group.bench_with_input(BenchmarkId::new("purl", graphs), &purl, |b, purl| {
+            b.iter(|| retrieve(setup, ComponentReference::Purl(purl), graphs))
+        });
+        group.bench_with_input(BenchmarkId::new("query", graphs), &query, |b, query| {
+            b.iter(|| retrieve(setup, query, graphs))
+        });
+    }
+
+    group.finish();
+
+    fn retrieve<'a>(setup: &Setup, reference: impl Into<GraphQuery<'a>> + Debug, graphs: usize) {
+        let result = setup.runtime.block_on(async {
+            setup
+                .service
+                .retrieve(
+                    reference,
+                    QueryOptions::ancestors(),
+                    Paginated {
+                        offset: 0,
+                        limit: graphs as u64,
+                        total: false,
+                    },
+                    &setup.ctx.db,
+                )
+                .await
+                .unwrap()
+        });
+        assert_eq!(result.items.len(), graphs);
+        black_box(result);
+    }
+}
+
+fn dep_nodes(c: &mut Criterion, setup: &Setup) {
+    let mut group = c.benchmark_group("dep_nodes");
+
+    let (nodes, _) = setup.sizes.last().unwrap();
+    let root = fixtures::package_name(&format!("size{nodes}"), 0);
+
+    for depth in [3u64, 5, 10] {
+        let options = QueryOptions {
+            descendants: depth,
+            ..Default::default()
+        };
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(depth),
+            &options,
+            |b, options| {
+                b.iter(|| {
+                    setup.runtime.block_on(async {
+                        black_box(
+                            setup
+                                .service
+                                .retrieve(
+                                    ComponentReference::Id(&root),
+                                    options.clone(),
+                                    Paginated::default(),
+                                    &setup.ctx.db,
+                                )
+                                .await
+                                .unwrap(),
+                        )
+                    })
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    let setup = setup();
+
+    load_graph(c, &setup);
+    retrieve_root_components(c, &setup);
+    dep_nodes(c, &setup);
+}
+
+criterion_group! {
+  name = analysis;
+  config = Criterion::default()
+    .measurement_time(Duration::from_secs(10))
+    .sample_size(10);
+  targets = benches
+}
+criterion_main!(analysis);
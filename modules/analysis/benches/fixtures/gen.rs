@@ -0,0 +1,181 @@
+//! Generate synthetic SBOMs, by inserting their rows directly into the database.
+//!
+//! Each SBOM is a binary tree of packages: the package `n` depends on the packages `2n + 1` and
+//! `2n + 2`. The document node describes the root package `0`. A tree of 1k nodes has a depth
+//! of 10, one of 10k nodes a depth of 13.
+
+use sea_orm::{ActiveValue::Set, ConnectionTrait, DbErr, EntityTrait};
+use std::str::FromStr;
+use time::OffsetDateTime;
+use trustify_common::purl::Purl;
+use trustify_entity::{
+    base_purl, labels::Labels, package_relates_to_package, qualified_purl,
+    relationship::Relationship, sbom, sbom_node, sbom_node_purl_ref, sbom_package, source_document,
+    versioned_purl,
+};
+use uuid::Uuid;
+
+/// Number of rows inserted with a single statement
+const BATCH: usize = 1000;
+
+/// The name of the package `n` of SBOMs generated with `prefix`.
+///
+/// SBOMs generated with the same prefix share their package names and PURLs.
+pub fn package_name(prefix: &str, n: usize) -> String {
+    format!("{prefix}-pkg-{n}")
+}
+
+/// The PURL of the package `n` of SBOMs generated with `prefix`.
+pub fn package_purl(prefix: &str, n: usize) -> Purl {
+    Purl::from_str(&format!("pkg:generic/{}@1.0.0", package_name(prefix, n)))
+        .expect("generated PURLs must be valid")
+}
+
+/// Insert `count` SBOMs of `nodes` packages each, returning their IDs.
+pub async fn generate_sboms(
+    db: &impl ConnectionTrait,
+    prefix: &str,
+    nodes: usize,
+    count: usize,
+) -> Result<Vec<Uuid>, DbErr> {
+    create_purls(db, prefix, nodes).await?;
+
+    let mut result = Vec::with_capacity(count);
+    for _ in 0..count {
+        result.push(create_sbom(db, prefix, nodes).await?);
+    }
+
+    Ok(result)
+}
+
+async fn create_purls(db: &impl ConnectionTrait, prefix: &str, nodes: usize) -> Result<(), DbErr> {
+    let purls: Vec<_> = (0..nodes).map(|n| package_purl(prefix, n)).collect();
+
+    for batch in purls.chunks(BATCH) {
+        base_purl::Entity::insert_many(batch.iter().map(|purl| base_purl::ActiveModel {
+            id: Set(purl.package_uuid()),
+            r#type: Set(purl.ty.clone()),
+            namespace: Set(purl.namespace.clone()),
+            name: Set(purl.name.clone()),
+        }))
+        .on_conflict_do_nothing()
+        .exec_without_returning(db)
+        .await?;
+
+        versioned_purl::Entity::insert_many(batch.iter().map(|purl| versioned_purl::ActiveModel {
+            id: Set(purl.version_uuid()),
+            base_purl_id: Set(purl.package_uuid()),
+            version: Set(purl.version.clone().unwrap_or_default()),
+        }))
+        .on_conflict_do_nothing()
+        .exec_without_returning(db)
+        .await?;
+
+        qualified_purl::Entity::insert_many(batch.iter().map(|purl| qualified_purl::ActiveModel {
+            id: Set(purl.qualifier_uuid()),
+            versioned_purl_id: Set(purl.version_uuid()),
+            qualifiers: Set(qualified_purl::Qualifiers(purl.qualifiers.clone())),
+            purl: Set(purl.clone().into()),
+        }))
+        .on_conflict_do_nothing()
+        .exec_without_returning(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn create_sbom(db: &impl ConnectionTrait, prefix: &str, nodes: usize) -> Result<Uuid, DbErr> {
+    let sbom_id = Uuid::new_v4();
+    let document_node = format!("{prefix}-document");
+
+    // the storage isn't used, the digests only need to be unique
+    let digest = sbom_id.simple().to_string();
+    let source_document_id = Uuid::new_v4();
+    source_document::Entity::insert(source_document::ActiveModel {
+        id: Set(source_document_id),
+        sha256: Set(digest.clone()),
+        sha384: Set(digest.clone()),
+        sha512: Set(digest),
+        size: Set(0),
+        ingested: Set(OffsetDateTime::now_utc()),
+    })
+    .exec_without_returning(db)
+    .await?;
+
+    sbom::Entity::insert(sbom::ActiveModel {
+        sbom_id: Set(sbom_id),
+        node_id: Set(document_node.clone()),
+        document_id: Set(Some(format!("urn:uuid:{sbom_id}"))),
+        published: Set(Some(OffsetDateTime::now_utc())),
+        authors: Set(vec![]),
+        suppliers: Set(vec![]),
+        data_licenses: Set(vec![]),
+        source_document_id: Set(source_document_id),
+        revision: Set(Uuid::new_v4()),
+        labels: Set(Labels::new()),
+        properties: Set(serde_json::Value::Object(Default::default())),
+        deleted_at: Set(None),
+    })
+    .exec_without_returning(db)
+    .await?;
+
+    sbom_node::Entity::insert(sbom_node::ActiveModel {
+        sbom_id: Set(sbom_id),
+        node_id: Set(document_node.clone()),
+        name: Set(format!("{prefix}-sbom")),
+    })
+    .exec_without_returning(db)
+    .await?;
+
+    let ids: Vec<usize> = (0..nodes).collect();
+    for batch in ids.chunks(BATCH) {
+        sbom_node::Entity::insert_many(batch.iter().map(|&n| sbom_node::ActiveModel {
+            sbom_id: Set(sbom_id),
+            node_id: Set(package_name(prefix, n)),
+            name: Set(package_name(prefix, n)),
+        }))
+        .exec_without_returning(db)
+        .await?;
+
+        sbom_package::Entity::insert_many(batch.iter().map(|&n| sbom_package::ActiveModel {
+            sbom_id: Set(sbom_id),
+            node_id: Set(package_name(prefix, n)),
+            group: Set(None),
+            version: Set(Some("1.0.0".into())),
+        }))
+        .exec_without_returning(db)
+        .await?;
+
+        sbom_node_purl_ref::Entity::insert_many(batch.iter().map(|&n| {
+            sbom_node_purl_ref::ActiveModel {
+                sbom_id: Set(sbom_id),
+                node_id: Set(package_name(prefix, n)),
+                qualified_purl_id: Set(package_purl(prefix, n).qualifier_uuid()),
+            }
+        }))
+        .exec_without_returning(db)
+        .await?;
+    }
+
+    let mut relationships = vec![package_relates_to_package::ActiveModel {
+        sbom_id: Set(sbom_id),
+        left_node_id: Set(document_node),
+        relationship: Set(Relationship::Describes),
+        right_node_id: Set(package_name(prefix, 0)),
+    }];
+    relationships.extend((1..nodes).map(|n| package_relates_to_package::ActiveModel {
+        sbom_id: Set(sbom_id),
+        left_node_id: Set(package_name(prefix, (n - 1) / 2)),
+        relationship: Set(Relationship::Dependency),
+        right_node_id: Set(package_name(prefix, n)),
+    }));
+
+    for batch in relationships.chunks(BATCH) {
+        package_relates_to_package::Entity::insert_many(batch.to_vec())
+            .exec_without_returning(db)
+            .await?;
+    }
+
+    Ok(sbom_id)
+}
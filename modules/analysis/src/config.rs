@@ -1,5 +1,10 @@
 use bytesize::ByteSize;
-use std::num::{NonZeroU64, NonZeroUsize};
+use std::{
+    fmt,
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    time::Duration,
+};
 use trustify_common::model::BinaryByteSize;
 
 fn parse_concurrency(s: &str) -> Result<NonZeroUsize, String> {
@@ -17,9 +22,10 @@ pub struct AnalysisConfig {
     #[arg(
         id = "max-cache-size",
         long,
+        alias = "analysis-cache-max-bytes",
         env = "TRUSTD_MAX_CACHE_SIZE",
         default_value = "200 MiB",
-        help = "Maximum size of the graph cache."
+        help = "Maximum size of the graph cache. Once exceeded, the least recently used graphs get evicted."
     )]
     pub max_cache_size: BinaryByteSize,
 
@@ -38,6 +44,59 @@ pub struct AnalysisConfig {
         help = "Only consider SBOMs published within this many days. Disabled by default."
     )]
     pub sbom_published_cutoff_days: Option<NonZeroU64>,
+
+    #[arg(
+        id = "analysis-warm-on-startup",
+        long,
+        env = "TRUSTD_ANALYSIS_WARM_ON_STARTUP",
+        default_value_t = WarmStrategy::None,
+        help = "Which SBOMs to load into the graph cache on startup."
+    )]
+    pub warm_on_startup: WarmStrategy,
+
+    #[arg(
+        id = "analysis-warm-recent-count",
+        long,
+        env = "TRUSTD_ANALYSIS_WARM_RECENT_COUNT",
+        default_value_t = 50,
+        help = "The number of most recently published SBOMs to load, when warming up the recent ones."
+    )]
+    pub warm_recent_count: u64,
+
+    #[arg(
+        id = "analysis-warm-timeout",
+        long,
+        env = "TRUSTD_ANALYSIS_WARM_TIMEOUT",
+        default_value = "10m",
+        help = "The maximum time spent warming up the graph cache."
+    )]
+    pub warm_timeout: humantime::Duration,
+
+    #[arg(
+        id = "analysis-snapshot-dir",
+        long,
+        env = "TRUSTD_ANALYSIS_SNAPSHOT_DIR",
+        help = "Directory to save graph cache snapshots to and restore them from. Disabled by default."
+    )]
+    pub snapshot_dir: Option<PathBuf>,
+
+    #[arg(
+        id = "analysis-persist-graphs",
+        long,
+        env = "TRUSTD_ANALYSIS_PERSIST_GRAPHS",
+        default_value_t = false,
+        help = "Persist constructed graphs to the database, so that they survive restarts."
+    )]
+    pub persist_graphs: bool,
+
+    #[arg(
+        id = "analysis-export-max-nodes",
+        long,
+        env = "TRUSTD_ANALYSIS_EXPORT_MAX_NODES",
+        default_value_t = 100_000,
+        help = "The maximum number of nodes of a graph which can be exported. Zero disables the limit."
+    )]
+    pub export_max_nodes: u64,
 }
 
 impl Default for AnalysisConfig {
@@ -46,6 +105,37 @@ impl Default for AnalysisConfig {
             max_cache_size: BinaryByteSize(ByteSize::mib(200)),
             concurrency: DEFAULT_CONCURRENCY,
             sbom_published_cutoff_days: None,
+            warm_on_startup: WarmStrategy::None,
+            warm_recent_count: 50,
+            warm_timeout: Duration::from_secs(10 * 60).into(),
+            snapshot_dir: None,
+            persist_graphs: false,
+            export_max_nodes: 100_000,
+        }
+    }
+}
+
+/// Strategy for loading graphs into the cache when the service starts.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum WarmStrategy {
+    /// Start with an empty cache
+    #[clap(name = "none")]
+    #[default]
+    None,
+    /// Load the most recently published SBOMs
+    #[clap(name = "recent")]
+    Recent,
+    /// Load all SBOMs
+    #[clap(name = "all")]
+    All,
+}
+
+impl fmt::Display for WarmStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Recent => write!(f, "recent"),
+            Self::All => write!(f, "all"),
         }
     }
 }
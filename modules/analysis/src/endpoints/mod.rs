@@ -7,14 +7,18 @@ use super::service::{AnalysisService, QueryOptions};
 use crate::{
     endpoints::query::OwnedComponentReference,
     error::Error,
-    model::{AnalysisStatus, Node},
+    model::{
+        AnalysisStatus, BaseSummary, GraphDiff, GraphExport, ImpactEntry, Node, PathNode,
+        graph::{GraphStats, GraphValidation},
+    },
     parse_sbom_id,
     service::render::Renderer,
 };
-use actix_web::{HttpResponse, Responder, get, web};
+use actix_web::{HttpResponse, Responder, get, post, web};
 use serde_json::json;
+use std::str::FromStr;
 use trustify_auth::{
-    Permission, ReadSbom, ReadSystemInformation,
+    DeleteSbom, Permission, ReadSbom, ReadSystemInformation,
     authenticator::user::UserInformation,
     authorizer::{Authorizer, Require},
     utoipa::AuthResponse,
@@ -22,6 +26,7 @@ use trustify_auth::{
 use trustify_common::{
     db::{self, query::Query},
     model::{Paginated, PaginatedResults},
+    purl::Purl,
 };
 use utoipa_actix_web::service_config::ServiceConfig;
 
@@ -33,8 +38,19 @@ pub fn configure(config: &mut ServiceConfig, db: db::ReadOnly, analysis: Analysi
         .service(search_component)
         .service(analysis_status)
         .service(render_sbom_graph)
+        .service(get_sbom_graph)
+        .service(render_loaded_graphs)
+        .service(validate_sbom_graph)
+        .service(get_sbom_graph_stats)
+        .service(get_sbom_roots)
+        .service(get_roots_by_purl)
+        .service(get_shortest_path)
+        .service(diff_sbom_graphs)
+        .service(get_impact)
         .service(search_latest_component)
-        .service(get_latest_component);
+        .service(get_latest_component)
+        .service(save_snapshot)
+        .service(restore_snapshot);
 }
 
 #[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
@@ -165,6 +181,329 @@ pub async fn render_sbom_graph(
     }
 }
 
+/// The format to export a graph in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum GraphFormat {
+    /// Nodes and edges as JSON
+    #[default]
+    Json,
+    /// Graphviz
+    Dot,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, utoipa::IntoParams)]
+struct GraphQuery {
+    /// The format of the exported graph
+    #[serde(default)]
+    #[param(inline)]
+    format: GraphFormat,
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "getSbomGraph",
+    params(
+        ("sbom" = String, Path, description = "ID of the SBOM"),
+        GraphQuery,
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "All nodes and edges of the SBOM graph", body = GraphExport),
+        (status = 404, description = "The SBOM could not be found"),
+        (status = 413, description = "The graph has more nodes than may be exported"),
+    ),
+)]
+#[get("/v3/analysis/sbom/{sbom}/graph")]
+/// Export the full graph of an SBOM
+pub async fn get_sbom_graph(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    sbom: web::Path<String>,
+    web::Query(GraphQuery { format }): web::Query<GraphQuery>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let sbom = parse_sbom_id(&sbom)?;
+    let tx = db.begin().await?;
+
+    let graph = service.load_graph(&tx, sbom).await?;
+
+    match format {
+        GraphFormat::Json => Ok(HttpResponse::Ok().json(service.export(graph.as_ref())?)),
+        GraphFormat::Dot => {
+            service.ensure_exportable(graph.as_ref())?;
+            match service.render(graph.as_ref(), Renderer::Graphviz) {
+                Some((data, content_type)) => {
+                    Ok(HttpResponse::Ok().content_type(content_type).body(data))
+                }
+                None => Ok(HttpResponse::NotFound().finish()),
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "renderLoadedGraphs",
+    params(
+        ("ext" = inline(Renderer), Path, description = "Renderer to use")
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "A rendered version of all graphs in the graph cache, in the format requested", body = String),
+        (status = 415, description = "Unsupported rendering format"),
+    ),
+)]
+#[get("/v3/analysis/render.{ext}")]
+/// Render all SBOM graphs currently loaded into the graph cache as a single graph
+pub async fn render_loaded_graphs(
+    service: web::Data<AnalysisService>,
+    ext: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let Ok(ext) = serde_json::from_value::<Renderer>(json!(ext.into_inner())) else {
+        return Ok(HttpResponse::UnsupportedMediaType().finish());
+    };
+
+    let (data, content_type) = service.render_all(ext);
+    Ok(HttpResponse::Ok().content_type(content_type).body(data))
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "validateSbomGraph",
+    params(
+        ("sbom" = String, Path, description = "ID of the SBOM"),
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Structural issues of the SBOM graph", body = GraphValidation),
+        (status = 400, description = "The SBOM ID could not be parsed"),
+    ),
+)]
+#[get("/v3/analysis/sbom/{sbom}/validate")]
+/// Check an SBOM graph for structural issues
+pub async fn validate_sbom_graph(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    sbom: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let sbom = parse_sbom_id(&sbom)?;
+    let tx = db.begin().await?;
+
+    Ok(HttpResponse::Ok().json(service.validate(&tx, sbom).await?))
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "getSbomGraphStats",
+    params(
+        ("sbom" = String, Path, description = "ID of the SBOM"),
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Statistics of the structure of the SBOM graph", body = GraphStats),
+        (status = 400, description = "The SBOM ID could not be parsed"),
+    ),
+)]
+#[get("/v3/analysis/sbom/{sbom}/stats")]
+/// Get statistics of the structure of an SBOM graph
+pub async fn get_sbom_graph_stats(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    sbom: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let sbom = parse_sbom_id(&sbom)?;
+    let tx = db.begin().await?;
+
+    Ok(HttpResponse::Ok().json(service.graph_stats(&tx, sbom).await?))
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "getSbomRoots",
+    params(
+        ("sbom" = String, Path, description = "ID of the SBOM"),
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Top level components of the SBOM", body = Vec<BaseSummary>),
+        (status = 400, description = "The SBOM ID could not be parsed"),
+    ),
+)]
+#[get("/v3/analysis/sbom/{sbom}/roots")]
+/// Retrieve the top level components of an SBOM, which are not part of any other component
+pub async fn get_sbom_roots(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    sbom: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let sbom = parse_sbom_id(&sbom)?;
+    let tx = db.begin().await?;
+
+    Ok(HttpResponse::Ok().json(service.root_nodes(&tx, sbom).await?))
+}
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+struct RootsByPurlQuery {
+    /// ID of the SBOM
+    pub sbom_id: String,
+    /// The pURL of the component
+    pub purl: String,
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "getRootsByPurl",
+    params(
+        RootsByPurlQuery
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Root components of the SBOM, containing the component", body = Vec<Node>),
+        (status = 400, description = "The SBOM ID or the pURL could not be parsed"),
+    ),
+)]
+#[get("/v3/analysis/roots-by-purl")]
+/// Retrieve the root components of an SBOM, which contain a component identified by its Package URL.
+pub async fn get_roots_by_purl(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(RootsByPurlQuery { sbom_id, purl }): web::Query<RootsByPurlQuery>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let sbom_id = parse_sbom_id(&sbom_id)?;
+    let purl = Purl::from_str(&purl)?;
+    let tx = db.begin().await?;
+
+    Ok(HttpResponse::Ok().json(
+        service
+            .retrieve_all_sbom_roots_by_purl(sbom_id, &purl, &tx)
+            .await?,
+    ))
+}
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+struct ShortestPathQuery {
+    /// ID of the SBOM
+    pub sbom_id: String,
+    /// The component to start from, by name, pURL, or CPE
+    pub from: String,
+    /// The component to reach, by name, pURL, or CPE
+    pub to: String,
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "getShortestPath",
+    params(
+        ShortestPathQuery
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Components on the shortest path, starting with the 'from' component", body = Vec<PathNode>),
+        (status = 400, description = "The SBOM ID or a component reference could not be parsed"),
+        (status = 404, description = "One of the components was not found, or there is no path between them"),
+    ),
+)]
+#[get("/v3/analysis/path")]
+/// Retrieve the shortest chain of relationships from one component of an SBOM to another.
+pub async fn get_shortest_path(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(ShortestPathQuery { sbom_id, from, to }): web::Query<ShortestPathQuery>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let sbom_id = parse_sbom_id(&sbom_id)?;
+    let from = OwnedComponentReference::try_from(from.as_str())?;
+    let to = OwnedComponentReference::try_from(to.as_str())?;
+    let tx = db.begin().await?;
+
+    match service
+        .shortest_path(sbom_id, (&from).into(), (&to).into(), &tx)
+        .await?
+    {
+        Some(path) => Ok(HttpResponse::Ok().json(path)),
+        None => Err(Error::BadRequest {
+            msg: "There is no path between the components".into(),
+            status: actix_http::StatusCode::NOT_FOUND,
+        }),
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+struct DiffQuery {
+    /// ID of the old SBOM
+    pub old: String,
+    /// ID of the new SBOM
+    pub new: String,
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "diffSbomGraphs",
+    params(
+        DiffQuery
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Components added, removed, or changed between the SBOM graphs", body = GraphDiff),
+        (status = 400, description = "An SBOM ID could not be parsed"),
+        (status = 404, description = "An SBOM could not be found"),
+    ),
+)]
+#[get("/v3/analysis/diff")]
+/// Compare the graphs of two SBOMs, e.g. two versions of the same document.
+pub async fn diff_sbom_graphs(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(DiffQuery { old, new }): web::Query<DiffQuery>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let old = parse_sbom_id(&old)?;
+    let new = parse_sbom_id(&new)?;
+    let tx = db.begin().await?;
+
+    Ok(HttpResponse::Ok().json(service.diff_graphs(old, new, &tx).await?))
+}
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+struct ImpactQuery {
+    /// The pURL of the component
+    pub purl: String,
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "getImpact",
+    params(
+        ImpactQuery,
+        Paginated,
+    ),
+    responses(
+        AuthResponse,
+        (status = 200, description = "Root components of all SBOMs, transitively depending on the component", body = PaginatedResults<ImpactEntry>),
+        (status = 400, description = "The pURL could not be parsed"),
+    ),
+)]
+#[get("/v3/analysis/impact")]
+/// Retrieve the root components of all SBOMs, which transitively depend on a component identified by its Package URL.
+pub async fn get_impact(
+    service: web::Data<AnalysisService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(ImpactQuery { purl }): web::Query<ImpactQuery>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let purl = Purl::from_str(&purl)?;
+    let tx = db.begin().await?;
+
+    Ok(HttpResponse::Ok().json(service.impact_analysis(&purl, paginated, &tx).await?))
+}
+
 #[utoipa::path(
     tag = "analysis",
     operation_id = "searchLatestComponent",
@@ -228,3 +567,64 @@ pub async fn get_latest_component(
             .await?,
     ))
 }
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+struct SnapshotQuery {
+    /// Path of the snapshot file, relative to the configured snapshot directory
+    pub path: String,
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "saveAnalysisSnapshot",
+    params(
+        SnapshotQuery
+    ),
+    responses(
+        AuthResponse,
+        (status = 204, description = "The graph cache was saved to the snapshot"),
+        (status = 404, description = "Graph snapshots are not enabled"),
+    ),
+)]
+#[post("/v3/admin/analysis/snapshot/save")]
+/// Save the content of the graph cache to a snapshot file.
+pub async fn save_snapshot(
+    service: web::Data<AnalysisService>,
+    web::Query(SnapshotQuery { path }): web::Query<SnapshotQuery>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let path = service.snapshot_path(&path)?;
+    web::block(move || service.save_snapshot(&path))
+        .await
+        .map_err(actix_web::Error::from)??;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    tag = "analysis",
+    operation_id = "restoreAnalysisSnapshot",
+    params(
+        SnapshotQuery
+    ),
+    responses(
+        AuthResponse,
+        (status = 204, description = "The graph cache was restored from the snapshot"),
+        (status = 400, description = "The file is not a snapshot, or has an incompatible version"),
+        (status = 404, description = "The snapshot was not found, or graph snapshots are not enabled"),
+    ),
+)]
+#[post("/v3/admin/analysis/snapshot/restore")]
+/// Replace the content of the graph cache with a snapshot file.
+pub async fn restore_snapshot(
+    service: web::Data<AnalysisService>,
+    web::Query(SnapshotQuery { path }): web::Query<SnapshotQuery>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let path = service.snapshot_path(&path)?;
+    web::block(move || service.restore_snapshot(&path))
+        .await
+        .map_err(actix_web::Error::from)??;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,114 @@
+use crate::test::caller;
+use actix_web::test::TestRequest;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_test_context::{TrustifyContext, call::CallService, document};
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn diff(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let old = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    // a new version of the document: BB got a new version and depends on CC, FF got replaced by GG,
+    // neither of which has a PURL
+    let (mut spdx, _) = document::<Value>("spdx/simple.json").await?;
+    let packages = spdx["packages"].as_array_mut().expect("must be an array");
+    for package in packages.iter_mut() {
+        if package["SPDXID"] == "SPDXRef-BB" {
+            package["versionInfo"] = json!("2");
+        }
+    }
+    let mut gg = packages
+        .iter()
+        .find(|package| package["SPDXID"] == "SPDXRef-FF")
+        .cloned()
+        .expect("must have FF");
+    packages.retain(|package| package["SPDXID"] != "SPDXRef-FF");
+    gg["SPDXID"] = json!("SPDXRef-GG");
+    gg["name"] = json!("GG");
+    gg["externalRefs"] = json!([]);
+    packages.push(gg);
+
+    let relationships = spdx["relationships"]
+        .as_array_mut()
+        .expect("must be an array");
+    for relationship in relationships.iter_mut() {
+        if relationship["spdxElementId"] == "SPDXRef-CC" {
+            relationship["relationshipType"] = json!("DEPENDENCY_OF");
+        }
+        if relationship["spdxElementId"] == "SPDXRef-FF" {
+            relationship["spdxElementId"] = json!("SPDXRef-GG");
+        }
+    }
+
+    let new = ctx.ingest_json(spdx).await?.id;
+
+    let uri = format!("/api/v3/analysis/diff?old={old}&new={new}");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    let names = |nodes: &Value| {
+        nodes
+            .as_array()
+            .expect("must be an array")
+            .iter()
+            .map(|node| node["name"].clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&response["added"]), [json!("GG")]);
+    assert_eq!(names(&response["removed"]), [json!("FF")]);
+
+    let changed = response["changed"].as_array().expect("must be an array");
+    assert_eq!(changed.len(), 2);
+
+    assert_eq!(changed[0]["old"]["name"], "BB");
+    assert_eq!(changed[0]["old"]["version"], "1");
+    assert_eq!(changed[0]["new"]["version"], "2");
+    assert_eq!(
+        changed[0]["relationships"],
+        json!([
+            { "target": "pkg:rpm/redhat/CC", "old": "contains", "new": "dependency" },
+        ])
+    );
+
+    assert_eq!(changed[1]["old"]["name"], "DD");
+    assert_eq!(changed[1]["new"]["version"], "1");
+    assert_eq!(
+        changed[1]["relationships"],
+        json!([
+            { "target": "FF", "old": "contains", "new": null },
+            { "target": "GG", "old": null, "new": "contains" },
+        ])
+    );
+
+    // comparing an SBOM with itself shows no difference
+    let uri = format!("/api/v3/analysis/diff?old={old}&new={old}");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(
+        response,
+        json!({ "added": [], "removed": [], "changed": [] })
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn diff_unknown_sbom(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let old = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/diff?old={old}&new=0190b7bd-d9a0-7fd0-b5f5-b8bc03207120");
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), 404);
+
+    Ok(())
+}
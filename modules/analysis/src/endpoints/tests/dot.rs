@@ -1,9 +1,12 @@
 use crate::test::caller;
 use actix_http::Request;
 use actix_web::test::TestRequest;
+use sea_orm::{ActiveModelTrait, Set};
 use test_context::test_context;
 use test_log::test;
+use trustify_entity::{sbom_external_node, sbom_node};
 use trustify_test_context::{TrustifyContext, call::CallService};
+use uuid::Uuid;
 
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
@@ -47,6 +50,53 @@ digraph {
     Ok(())
 }
 
+/// A reference to another document, which is not connected by any relationship.
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn render_dot_unconnected_external(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let sbom = ctx.ingest_document("spdx/simple.json").await?;
+    let sbom_id: Uuid = sbom.id.parse()?;
+
+    let node_id = "DocumentRef-other:SPDXRef-X";
+    sbom_node::ActiveModel {
+        sbom_id: Set(sbom_id),
+        node_id: Set(node_id.to_string()),
+        name: Set(node_id.to_string()),
+    }
+    .insert(&ctx.db)
+    .await?;
+    sbom_external_node::ActiveModel {
+        sbom_id: Set(sbom_id),
+        node_id: Set(node_id.to_string()),
+        external_doc_ref: Set("DocumentRef-other".to_string()),
+        external_node_ref: Set("SPDXRef-X".to_string()),
+        external_type: Set(sbom_external_node::ExternalType::SPDX),
+        target_sbom_id: Set(None),
+        discriminator_type: Set(None),
+        discriminator_value: Set(None),
+    }
+    .insert(&ctx.db)
+    .await?;
+
+    let uri = format!("/api/v3/analysis/sbom/{}/render.dot", sbom.id);
+    let request: Request = TestRequest::get().uri(&uri).to_request();
+    let response: String = String::from_utf8(app.call_and_read_body(request).await.into())?;
+    log::debug!("{response}");
+
+    assert!(response.contains(
+        r#""DocumentRef-other:SPDXRef-X" [label="DocumentRef-other # SPDXRef-X: DocumentRef-other:SPDXRef-X"]"#
+    ));
+    assert!(response.contains(
+        r#""SPDXRef-DOCUMENT" -> "DocumentRef-other:SPDXRef-X" [label="External Reference"]"#
+    ));
+    // other unconnected nodes are still undefined
+    assert!(response.contains(r#""SPDXRef-DOCUMENT" -> "SPDXRef-EE" [label="Undefined"]"#));
+
+    Ok(())
+}
+
 /// A test for an existing SBOM, but an unknown renderer.
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
@@ -65,3 +115,47 @@ async fn render_unsupported_ext(ctx: &TrustifyContext) -> Result<(), anyhow::Err
 
     Ok(())
 }
+
+/// Render all loaded graphs as one, keeping node IDs of different SBOMs apart
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn render_loaded_dot(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let spdx = ctx.ingest_document("spdx/simple.json").await?.id;
+    let cyclonedx = ctx.ingest_document("cyclonedx/simple.json").await?.id;
+
+    // nothing loaded yet
+    let request: Request = TestRequest::get()
+        .uri("/api/v3/analysis/render.dot")
+        .to_request();
+    let response: String = String::from_utf8(app.call_and_read_body(request).await.into())?;
+    assert_eq!(response, "\ndigraph {\n\n}\n");
+
+    // load the graph of both SBOMs
+    for sbom in [&spdx, &cyclonedx] {
+        let uri = format!("/api/v3/analysis/sbom/{sbom}/render.dot");
+        let request: Request = TestRequest::get().uri(&uri).to_request();
+        app.call_and_read_body(request).await;
+    }
+
+    let request: Request = TestRequest::get()
+        .uri("/api/v3/analysis/render.dot")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(
+        response.headers().get("content-type").map(|v| v.as_bytes()),
+        Some(&b"text/vnd.graphviz"[..])
+    );
+    let response = String::from_utf8(actix_web::test::read_body(response).await.into())?;
+    log::debug!("{response}");
+
+    assert!(response.contains(&format!(r#""{spdx}/SPDXRef-A" [label="A / 1: SPDXRef-A"]"#)));
+    assert!(response.contains(&format!(
+        r#""{spdx}/SPDXRef-A" -> "{spdx}/SPDXRef-B" [label="Contains"]"#
+    )));
+    assert!(response.contains(&format!(r#""{cyclonedx}/"#)));
+    assert!(!response.contains(r#""SPDXRef-A""#));
+
+    Ok(())
+}
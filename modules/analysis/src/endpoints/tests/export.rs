@@ -0,0 +1,103 @@
+use crate::{config::AnalysisConfig, endpoints::configure, service::AnalysisService, test::caller};
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_common::db;
+use trustify_test_context::{
+    TrustifyContext,
+    call::{self, CallService},
+};
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn export_json(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/graph");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#?}");
+
+    let mut nodes = response["nodes"]
+        .as_array()
+        .expect("must be an array")
+        .iter()
+        .map(|node| node["node_id"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    nodes.sort();
+    assert_eq!(
+        nodes,
+        [
+            "SPDXRef-A",
+            "SPDXRef-AA",
+            "SPDXRef-B",
+            "SPDXRef-BB",
+            "SPDXRef-CC",
+            "SPDXRef-DD",
+            "SPDXRef-DOCUMENT",
+            "SPDXRef-EE",
+            "SPDXRef-FF",
+        ]
+    );
+
+    let edges = response["edges"].as_array().expect("must be an array");
+    assert_eq!(edges.len(), 7);
+    assert!(edges.contains(&json!({
+        "source_node_id": "SPDXRef-DOCUMENT",
+        "target_node_id": "SPDXRef-A",
+        "relationship": "describes",
+    })));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn export_dot(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/graph?format=dot");
+    let response = String::from_utf8(
+        app.call_and_read_body(TestRequest::get().uri(&uri).to_request())
+            .await
+            .into(),
+    )?;
+    log::debug!("{response}");
+
+    assert!(response.contains(r#""SPDXRef-A" -> "SPDXRef-B" [label="Contains"]"#));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn export_too_large(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let db = db::ReadOnly::new(ctx.db.clone());
+    let analysis = AnalysisService::new(
+        AnalysisConfig {
+            export_max_nodes: 5,
+            ..Default::default()
+        },
+        db.clone(),
+    );
+    let app = call::caller(|svc| configure(svc, db, analysis)).await?;
+
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    for format in ["json", "dot"] {
+        let uri = format!("/api/v3/analysis/sbom/{sbom}/graph?format={format}");
+        let response = app
+            .call_service(TestRequest::get().uri(&uri).to_request())
+            .await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{format}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::test::caller;
+use actix_web::test::TestRequest;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_test_context::{TrustifyContext, call::CallService, document};
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn impact(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let first = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    // another SBOM, in which the AA subtree is part of A
+    let (mut spdx, _) = document::<Value>("spdx/simple.json").await?;
+    spdx["documentNamespace"] = json!("uri:just-another-example");
+    spdx["relationships"]
+        .as_array_mut()
+        .expect("must be an array")
+        .push(json!({
+            "spdxElementId": "SPDXRef-AA",
+            "relationshipType": "CONTAINED_BY",
+            "relatedSpdxElement": "SPDXRef-A",
+        }));
+    let second = ctx.ingest_json(spdx).await?.id;
+
+    let purl = urlencoding::encode("pkg:rpm/redhat/DD@0.0.0");
+    let uri = format!("/api/v3/analysis/impact?purl={purl}&total=true");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    assert_eq!(response["total"], 2);
+    let items = response["items"].as_array().expect("must be an array");
+    assert_eq!(items[0]["sbom_id"], json!(first));
+    assert_eq!(items[0]["name"], "AA");
+    assert_eq!(items[0]["path_length"], 2);
+    assert_eq!(items[1]["sbom_id"], json!(second));
+    assert_eq!(items[1]["name"], "A");
+    assert_eq!(items[1]["path_length"], 3);
+
+    // paginated
+    let uri = format!("/api/v3/analysis/impact?purl={purl}&offset=1&limit=1&total=true");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response["total"], 2);
+    assert_eq!(response["items"][0]["name"], "A");
+
+    // a root component impacts only itself
+    let purl = urlencoding::encode("pkg:rpm/redhat/AA@0.0.0?arch=src");
+    let uri = format!("/api/v3/analysis/impact?purl={purl}&total=true");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response["total"], 2);
+    assert_eq!(response["items"][0]["name"], "AA");
+    assert_eq!(response["items"][0]["path_length"], 0);
+    assert_eq!(response["items"][1]["name"], "A");
+    assert_eq!(response["items"][1]["path_length"], 1);
+
+    // a component not part of any SBOM
+    let purl = urlencoding::encode("pkg:rpm/redhat/ZZ@0.0.0");
+    let uri = format!("/api/v3/analysis/impact?purl={purl}&total=true");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response["total"], 0);
+    assert_eq!(response["items"], json!([]));
+
+    Ok(())
+}
@@ -1,20 +1,32 @@
 mod cyclonedx;
+mod diff;
 mod dot;
+mod export;
+mod impact;
 mod latest_filters;
+mod path;
 pub mod req;
 mod rh_variant;
+mod roots;
 mod spdx;
+mod stats;
 mod temporal;
+mod validate;
 
-use crate::test::caller;
-use actix_http::Request;
+use crate::{config::AnalysisConfig, endpoints::configure, service::AnalysisService, test::caller};
+use actix_http::{Request, StatusCode};
 use actix_web::test::TestRequest;
 use req::*;
 use rstest::rstest;
 use serde_json::{Value, json};
 use test_context::test_context;
 use test_log::test;
-use trustify_test_context::{LazyPool, TrustifyContext, call::CallService, subset::ContainsSubset};
+use trustify_common::db;
+use trustify_test_context::{
+    LazyPool, TrustifyContext,
+    call::{self, CallService},
+    subset::ContainsSubset,
+};
 
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
@@ -124,6 +136,36 @@ async fn test_simple_retrieve_by_purl_analysis_endpoint(
     Ok(())
 }
 
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn test_simple_roots_by_purl_analysis_endpoint(
+    ctx: &TrustifyContext,
+) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/simple.json").await?;
+    let sbom = sbom.id;
+
+    let purl = urlencoding::encode("pkg:rpm/redhat/BB@0.0.0");
+    let uri = format!("/api/v3/analysis/roots-by-purl?sbom_id={sbom}&purl={purl}");
+    let request: Request = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    tracing::debug!(test = "", "{response:#?}");
+
+    assert!(response.contains_subset(json!([{
+        "purl": [ "pkg:rpm/redhat/AA@0.0.0?arch=src" ]
+    }])));
+    assert_eq!(response.as_array().map(Vec::len), Some(1));
+
+    // a component which is not part of the SBOM has no roots
+    let purl = urlencoding::encode("pkg:rpm/redhat/ZZ@0.0.0");
+    let uri = format!("/api/v3/analysis/roots-by-purl?sbom_id={sbom}&purl={purl}");
+    let request: Request = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    assert_eq!(response, json!([]));
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn test_quarkus_retrieve_analysis_endpoint(
@@ -190,6 +232,7 @@ async fn test_status_endpoint(ctx: &TrustifyContext) -> Result<(), anyhow::Error
 
     assert_eq!(response["sbom_count"], 1);
     assert_eq!(response["graph_count"], 1);
+    assert_eq!(response["cache_warming"], false);
 
     // ingest duplicate sbom which has different date
     ctx.ingest_documents(["spdx/simple-dup.json"]).await?;
@@ -649,3 +692,75 @@ async fn deep_descendants_lazy_pool(ctx: &LazyPool<TrustifyContext>) -> Result<(
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn test_snapshot_endpoints(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let dir = tempfile::tempdir()?;
+    let db = db::ReadOnly::new(ctx.db.clone());
+    let analysis = AnalysisService::new(
+        AnalysisConfig {
+            snapshot_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        },
+        db.clone(),
+    );
+    let app = call::caller(|svc| configure(svc, db, analysis)).await?;
+    ctx.ingest_documents(["spdx/simple.json"]).await?;
+
+    // prime the graph hashmap
+    let _response: Value = app
+        .req(Req {
+            what: What::Q("BB"),
+            ..Req::default()
+        })
+        .await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/analysis/snapshot/save?path=graphs.snapshot")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(dir.path().join("graphs.snapshot").is_file());
+
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/analysis/snapshot/restore?path=graphs.snapshot")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = TestRequest::get()
+        .uri("/api/v3/analysis/status")
+        .to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    assert_eq!(response["graph_count"], 1);
+
+    // must not leave the snapshot directory
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/analysis/snapshot/save?path=../graphs.snapshot")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/analysis/snapshot/restore?path=unknown.snapshot")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn test_snapshot_disabled(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/analysis/snapshot/save?path=graphs.snapshot")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::test::caller;
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use rstest::rstest;
+use serde_json::{Value, json};
+use test_context::test_context;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn shortest_path(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/path?sbom_id={sbom}&from=AA&to=FF");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    let path = response.as_array().expect("must be an array");
+    assert_eq!(
+        path.iter().map(|node| &node["name"]).collect::<Vec<_>>(),
+        [&json!("AA"), &json!("BB"), &json!("DD"), &json!("FF")]
+    );
+    assert_eq!(path[0].get("relationship"), None);
+    assert_eq!(path[1]["relationship"], "contains");
+    assert_eq!(path[3]["relationship"], "contains");
+
+    // a component is its own shortest path
+    let uri = format!("/api/v3/analysis/path?sbom_id={sbom}&from=BB&to=BB");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.as_array().map(Vec::len), Some(1));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::unknown_from("ZZ", "FF", "The 'from' component was not found in the SBOM")]
+#[case::unknown_to("AA", "ZZ", "The 'to' component was not found in the SBOM")]
+#[case::against_relationships("FF", "AA", "There is no path between the components")]
+#[case::other_subtree("A", "FF", "There is no path between the components")]
+#[test_log::test(actix_web::test)]
+async fn shortest_path_not_found(
+    ctx: &TrustifyContext,
+    #[case] from: &str,
+    #[case] to: &str,
+    #[case] message: &str,
+) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/path?sbom_id={sbom}&from={from}&to={to}");
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let body: Value = actix_web::test::read_body_json(response).await;
+    assert_eq!(body["message"], message);
+
+    Ok(())
+}
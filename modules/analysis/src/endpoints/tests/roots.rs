@@ -0,0 +1,32 @@
+use crate::test::caller;
+use actix_web::test::TestRequest;
+use serde_json::Value;
+use test_context::test_context;
+use test_log::test;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+/// `EE` is only related to the document by an undefined relationship, and `AA` by none at all
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn sbom_roots(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/roots");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    let mut roots = response
+        .as_array()
+        .expect("must be an array")
+        .iter()
+        .map(|node| node["node_id"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    roots.sort();
+
+    assert_eq!(roots, ["SPDXRef-AA", "SPDXRef-DOCUMENT", "SPDXRef-EE"]);
+
+    Ok(())
+}
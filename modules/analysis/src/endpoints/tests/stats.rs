@@ -0,0 +1,49 @@
+use crate::test::caller;
+use actix_web::test::TestRequest;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn graph_stats(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/stats");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    // the longest path is AA -> BB -> DD -> FF
+    assert_eq!(
+        response,
+        json!({
+            "node_count": 9,
+            "edge_count": 7,
+            "max_depth": 4,
+            "relationship_counts": {
+                "contains": 5,
+                "describes": 1,
+                "undefined": 1,
+            },
+            "isolated_nodes": 0,
+        })
+    );
+
+    // the loaded graph is part of the totals of the status
+
+    let response: Value = app
+        .call_and_read_body_json(
+            TestRequest::get()
+                .uri("/api/v3/analysis/status")
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response["graph_nodes"], 9);
+    assert_eq!(response["graph_edges"], 7);
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::test::caller;
+use actix_web::test::TestRequest;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+/// The `AA` subtree of the SBOM isn't connected to the described package
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn validate_unreachable(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/validate");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    assert_eq!(response["valid"], true);
+    let issues = response["issues"].as_array().expect("must be an array");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["severity"], "warning");
+    assert_eq!(issues[0]["code"], "unreachable_node");
+    assert_eq!(
+        issues[0]["node_ids"],
+        json!([
+            "SPDXRef-AA",
+            "SPDXRef-BB",
+            "SPDXRef-CC",
+            "SPDXRef-DD",
+            "SPDXRef-FF"
+        ])
+    );
+
+    Ok(())
+}
+
+/// A package describing itself results in a self-loop, which is only a warning
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn validate_self_loop(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx.ingest_document("spdx/self-package.json").await?.id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/validate");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    assert_eq!(response["valid"], true);
+    let issues = response["issues"].as_array().expect("must be an array");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["severity"], "warning");
+    assert_eq!(issues[0]["code"], "self_loop");
+    assert_eq!(issues[0]["node_ids"], json!(["SPDXRef-A"]));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn validate_invalid_id(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::get()
+        .uri("/api/v3/analysis/sbom/not-an-id/validate")
+        .to_request();
+    let response = app.call_service(request).await;
+
+    assert_eq!(response.status(), actix_http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// Circular dependencies are reported as a cycle
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn validate_cycle(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let sbom = ctx
+        .ingest_document("cyclonedx/cyclonedx-circular.json")
+        .await?
+        .id;
+
+    let uri = format!("/api/v3/analysis/sbom/{sbom}/validate");
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    assert_eq!(response["valid"], true);
+    let issues = response["issues"].as_array().expect("must be an array");
+    let cycles: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue["code"] == "cycle")
+        .collect();
+    assert!(!cycles.is_empty());
+    for cycle in cycles {
+        assert_eq!(cycle["severity"], "warning");
+        assert!(cycle["node_ids"].as_array().map(Vec::len) > Some(1));
+    }
+
+    Ok(())
+}
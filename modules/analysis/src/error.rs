@@ -38,6 +38,12 @@ pub enum Error {
     Internal(String),
     #[error("unavailable")]
     Unavailable,
+    #[error("the query took too long")]
+    Timeout,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid snapshot: {0}")]
+    Snapshot(String),
 }
 
 unsafe impl Send for Error {}
@@ -48,6 +54,8 @@ impl From<DbErr> for Error {
     fn from(value: DbErr) -> Self {
         if value.is_read_only() {
             Self::Unavailable
+        } else if value.is_query_canceled() {
+            Self::Timeout
         } else {
             Self::Database(value)
         }
@@ -60,6 +68,7 @@ impl From<DbError> for Error {
             DbError::Database(err) => Self::Database(err),
             DbError::Unavailable => Self::Unavailable,
             DbError::ReadOnly => Self::Internal(value.to_string()),
+            DbError::Timeout => Self::Timeout,
         }
     }
 }
@@ -83,6 +92,15 @@ impl ResponseError for Error {
             Self::Unavailable => {
                 HttpResponse::ServiceUnavailable().json(ErrorInformation::new("Unavailable", self))
             }
+            Self::Timeout => {
+                HttpResponse::GatewayTimeout().json(ErrorInformation::new("Timeout", self))
+            }
+            Self::Io(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                HttpResponse::NotFound().json(ErrorInformation::new("NotFound", err))
+            }
+            Self::Snapshot(msg) => {
+                HttpResponse::BadRequest().json(ErrorInformation::new("InvalidSnapshot", msg))
+            }
 
             // All other cases are internal system errors that are not expected to occur.
             // They are logged and a generic error response is returned to avoid leaking
@@ -1,11 +1,15 @@
 pub mod graph;
 
+mod diff;
+mod export;
 mod roots;
+pub use diff::*;
+pub use export::*;
 pub use roots::*;
 
 use bytesize::ByteSize;
 use deepsize::{Context, DeepSizeOf};
-use moka::{notification::RemovalCause, sync::Cache};
+use moka::{notification::RemovalCause, policy::EvictionPolicy, sync::Cache};
 use opentelemetry::{KeyValue, Value, metrics::Counter};
 use petgraph::Graph;
 use serde::Serialize;
@@ -25,12 +29,18 @@ pub struct AnalysisStatus {
     pub sbom_count: u32,
     /// The number of graphs loaded in memory
     pub graph_count: u32,
+    /// The number of nodes of all graphs loaded in memory
+    pub graph_nodes: u64,
+    /// The number of edges of all graphs loaded in memory
+    pub graph_edges: u64,
     /// The number of bytes consumed by entries in the graph
     pub graph_memory: u64,
     /// The maximum number of bytes the cache will hold
     pub graph_max_memory: u64,
     /// The number of ongoing loading operations
     pub loading_operations: u32,
+    /// Whether the graph cache is still being warmed up after startup
+    pub cache_warming: bool,
     /// More details
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<AnalysisStatusDetails>,
@@ -129,6 +139,29 @@ impl Node {
     }
 }
 
+/// A component on a path through the graph of an SBOM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PathNode {
+    #[serde(flatten)]
+    pub base: BaseSummary,
+
+    /// The relationship of the previous component on the path to this one. [`None`] for the
+    /// first component.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<Relationship>,
+}
+
+/// A root component of an SBOM, transitively depending on a component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ImpactEntry {
+    #[serde(flatten)]
+    pub base: BaseSummary,
+
+    /// The number of relationships of the shortest chain from the root to the component. Zero if
+    /// the component is the root itself.
+    pub path_length: usize,
+}
+
 impl Deref for Node {
     type Target = BaseSummary;
 
@@ -199,6 +232,10 @@ impl From<RemovalCauseAttributeValue> for Value {
 
 impl GraphMap {
     /// Create a new instance of GraphMap
+    ///
+    /// Once the estimated size of all graphs exceeds the capacity, the least recently used graphs
+    /// get evicted. Unlike the default policy of the cache, a newly loaded graph is always
+    /// admitted, as it is about to be used.
     pub fn new(cap: u64, evictions: Counter<u64>, eviction_size: Counter<u64>) -> Self {
         log::info!("Setting graph cache size to {cap} bytes");
 
@@ -207,6 +244,7 @@ impl GraphMap {
             map: Cache::builder()
                 .weigher(size_of_graph_entry)
                 .max_capacity(cap)
+                .eviction_policy(EvictionPolicy::lru())
                 .eviction_listener(move |k, v, cause| {
                     let attrs = [KeyValue::new("cause", RemovalCauseAttributeValue(cause))];
                     let size = size_of_graph_entry(&k, &v);
@@ -254,6 +292,17 @@ impl GraphMap {
         self.map.get(&key)
     }
 
+    /// Remove the graph with the given key (write access)
+    pub fn remove(&self, key: Uuid) {
+        self.map.invalidate(&key);
+        self.map.run_pending_tasks();
+    }
+
+    /// Get all graphs currently in the map
+    pub fn entries(&self) -> Vec<(Uuid, Arc<PackageGraph>)> {
+        self.map.iter().map(|(k, v)| (*k, v)).collect()
+    }
+
     /// Clear all graphs from the map
     pub fn clear(&self) {
         self.map.invalidate_all();
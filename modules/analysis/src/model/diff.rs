@@ -0,0 +1,38 @@
+use crate::model::BaseSummary;
+use serde::Serialize;
+use trustify_entity::relationship::Relationship;
+use utoipa::ToSchema;
+
+/// The difference between the graphs of two SBOMs
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, ToSchema)]
+pub struct GraphDiff {
+    /// Components only present in the new SBOM
+    pub added: Vec<BaseSummary>,
+    /// Components only present in the old SBOM
+    pub removed: Vec<BaseSummary>,
+    /// Components present in both SBOMs, but with a different version or relationships
+    pub changed: Vec<ChangedNode>,
+}
+
+/// A component present in both SBOMs, which changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ChangedNode {
+    /// The component in the old SBOM
+    pub old: BaseSummary,
+    /// The component in the new SBOM
+    pub new: BaseSummary,
+    /// The changes of relationships from this component to others
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relationships: Vec<RelationshipChange>,
+}
+
+/// A changed relationship of a component to another one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RelationshipChange {
+    /// The identity of the related component: its versionless PURL, or its name
+    pub target: String,
+    /// The relationship in the old SBOM, [`None`] if it was added
+    pub old: Option<Relationship>,
+    /// The relationship in the new SBOM, [`None`] if it was removed
+    pub new: Option<Relationship>,
+}
@@ -0,0 +1,21 @@
+use crate::model::BaseSummary;
+use serde::Serialize;
+use trustify_entity::relationship::Relationship;
+use utoipa::ToSchema;
+
+/// All nodes and edges of an SBOM graph
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, ToSchema)]
+pub struct GraphExport {
+    pub nodes: Vec<BaseSummary>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A relationship between two nodes of an SBOM graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct GraphEdge {
+    /// The ID of the node the relationship originates from
+    pub source_node_id: String,
+    /// The ID of the node the relationship points to
+    pub target_node_id: String,
+    pub relationship: Relationship,
+}
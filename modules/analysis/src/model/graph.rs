@@ -1,4 +1,6 @@
 use super::*;
+use petgraph::{Direction, algo::tarjan_scc, graph::NodeIndex};
+use std::collections::{HashMap, HashSet, VecDeque};
 use time::{OffsetDateTime, macros::format_description};
 use uuid::Uuid;
 
@@ -163,3 +165,301 @@ impl From<&PackageNode> for BaseSummary {
         }
     }
 }
+
+/// The severity of a structural issue of a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSchema, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// The graph is broken
+    Error,
+    /// The graph is usable, but likely not what the SBOM intended
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSchema, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCode {
+    /// An edge references a node which does not exist
+    DanglingEdge,
+    /// More than one node has the same node ID
+    DuplicateNodeId,
+    /// A node cannot be reached from any of the nodes describing the SBOM
+    UnreachableNode,
+    /// An edge starts and ends at the same node
+    SelfLoop,
+    /// Relationships lead from a node back to itself, through other nodes
+    Cycle,
+}
+
+/// A structural issue of a graph
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, serde::Serialize)]
+pub struct GraphIssue {
+    pub severity: IssueSeverity,
+    pub code: IssueCode,
+    pub description: String,
+    /// The IDs of the nodes involved
+    pub node_ids: Vec<String>,
+}
+
+/// The result of validating a graph
+#[derive(Debug, Clone, PartialEq, Eq, ToSchema, serde::Serialize)]
+pub struct GraphValidation {
+    /// If the graph has no issues of severity [`IssueSeverity::Error`]
+    pub valid: bool,
+    pub issues: Vec<GraphIssue>,
+}
+
+impl From<Vec<GraphIssue>> for GraphValidation {
+    fn from(issues: Vec<GraphIssue>) -> Self {
+        Self {
+            valid: !issues
+                .iter()
+                .any(|issue| issue.severity == IssueSeverity::Error),
+            issues,
+        }
+    }
+}
+
+/// Statistics of the structure of a graph
+#[derive(Debug, Clone, PartialEq, Eq, Default, ToSchema, serde::Serialize)]
+pub struct GraphStats {
+    pub node_count: u64,
+    pub edge_count: u64,
+    /// The number of nodes of the longest path from a node without incoming relationships
+    pub max_depth: u64,
+    /// The number of edges, by their relationship
+    pub relationship_counts: HashMap<Relationship, u64>,
+    /// The number of nodes without any relationships
+    pub isolated_nodes: u64,
+}
+
+/// Collect statistics of the structure of a graph
+///
+/// The depth is determined by a depth-first search, starting at all nodes without incoming
+/// relationships. Relationships leading back to a node of the current path are not followed, so
+/// that cycles don't add to the depth.
+pub fn graph_stats(graph: &PackageGraph) -> GraphStats {
+    let mut relationship_counts = HashMap::<Relationship, u64>::new();
+    for edge in graph.raw_edges() {
+        *relationship_counts.entry(edge.weight).or_default() += 1;
+    }
+
+    let isolated_nodes = graph
+        .node_indices()
+        .filter(|&index| graph.neighbors_undirected(index).next().is_none())
+        .count() as u64;
+
+    // the depth of each node, once all of its children have been visited
+
+    let mut depths = vec![None::<u64>; graph.node_count()];
+    let mut on_path = vec![false; graph.node_count()];
+
+    let roots = graph.node_indices().filter(|&index| {
+        graph
+            .neighbors_directed(index, Direction::Incoming)
+            .next()
+            .is_none()
+    });
+
+    for root in roots {
+        let mut stack = vec![(root, false)];
+        while let Some((index, children_visited)) = stack.pop() {
+            if children_visited {
+                on_path[index.index()] = false;
+                let depth = graph
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .filter_map(|next| depths[next.index()])
+                    .max()
+                    .unwrap_or_default();
+                depths[index.index()] = Some(depth + 1);
+                continue;
+            }
+
+            if depths[index.index()].is_some() || on_path[index.index()] {
+                continue;
+            }
+
+            on_path[index.index()] = true;
+            stack.push((index, true));
+            for next in graph.neighbors_directed(index, Direction::Outgoing) {
+                if depths[next.index()].is_none() && !on_path[next.index()] {
+                    stack.push((next, false));
+                }
+            }
+        }
+    }
+
+    GraphStats {
+        node_count: graph.node_count() as u64,
+        edge_count: graph.edge_count() as u64,
+        max_depth: depths.into_iter().flatten().max().unwrap_or_default(),
+        relationship_counts,
+        isolated_nodes,
+    }
+}
+
+/// Check a graph for structural issues
+pub fn validate_graph(graph: &PackageGraph) -> Vec<GraphIssue> {
+    let mut issues = vec![];
+
+    let node_id = |index: NodeIndex| graph.node_weight(index).map(|node| node.node_id.clone());
+
+    // all edges must point to existing nodes
+
+    for edge in graph.raw_edges() {
+        let (source, target) = (edge.source(), edge.target());
+        if node_id(source).is_none() || node_id(target).is_none() {
+            issues.push(GraphIssue {
+                severity: IssueSeverity::Error,
+                code: IssueCode::DanglingEdge,
+                description: format!(
+                    "Edge {:?} between node indices {} and {} references a missing node",
+                    edge.weight,
+                    source.index(),
+                    target.index()
+                ),
+                node_ids: [node_id(source), node_id(target)]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            });
+        }
+    }
+
+    // node IDs must be unique
+
+    let mut seen = HashMap::<&str, usize>::new();
+    for node in graph.node_weights() {
+        *seen.entry(node.node_id.as_str()).or_default() += 1;
+    }
+    let mut duplicates: Vec<_> = seen.into_iter().filter(|(_, count)| *count > 1).collect();
+    duplicates.sort_unstable();
+    for (id, count) in duplicates {
+        issues.push(GraphIssue {
+            severity: IssueSeverity::Error,
+            code: IssueCode::DuplicateNodeId,
+            description: format!("Node ID {id} is used by {count} nodes"),
+            node_ids: vec![id.to_string()],
+        });
+    }
+
+    // all nodes must be reachable from the nodes describing the SBOM
+
+    let describing: HashSet<_> = graph
+        .raw_edges()
+        .iter()
+        .filter(|edge| edge.weight == Relationship::Describes)
+        .map(|edge| edge.source())
+        .filter(|index| index.index() < graph.node_count())
+        .collect();
+
+    if !describing.is_empty() {
+        let mut reached: HashSet<NodeIndex> = describing.clone();
+        let mut queue: VecDeque<_> = describing.into_iter().collect();
+        while let Some(index) = queue.pop_front() {
+            for next in graph.neighbors_directed(index, Direction::Outgoing) {
+                if reached.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let unreachable: Vec<_> = graph
+            .node_indices()
+            .filter(|index| !reached.contains(index))
+            .filter_map(node_id)
+            .collect();
+        if !unreachable.is_empty() {
+            issues.push(GraphIssue {
+                severity: IssueSeverity::Warning,
+                code: IssueCode::UnreachableNode,
+                description: format!(
+                    "{} node(s) cannot be reached from the nodes describing the SBOM",
+                    unreachable.len()
+                ),
+                node_ids: unreachable,
+            });
+        }
+    }
+
+    // no edge must start and end at the same node
+
+    for edge in graph.raw_edges() {
+        if edge.source() == edge.target() {
+            let node_ids: Vec<_> = node_id(edge.source()).into_iter().collect();
+            issues.push(GraphIssue {
+                severity: IssueSeverity::Warning,
+                code: IssueCode::SelfLoop,
+                description: format!(
+                    "Node {} has a {:?} relationship to itself",
+                    node_ids.first().map(String::as_str).unwrap_or_default(),
+                    edge.weight
+                ),
+                node_ids,
+            });
+        }
+    }
+
+    // relationships should not lead back to where they started from
+
+    for cycle in find_cycles(graph) {
+        let node_ids: Vec<_> = cycle.into_iter().filter_map(node_id).collect();
+        issues.push(GraphIssue {
+            severity: IssueSeverity::Warning,
+            code: IssueCode::Cycle,
+            description: format!("{} node(s) form a cycle", node_ids.len()),
+            node_ids,
+        });
+    }
+
+    issues
+}
+
+/// Find cycles of a graph, spanning more than a single node
+///
+/// Returns one representative cycle for each group of nodes which can all reach each other,
+/// starting with the lowest node index of the group. Self-loops are not considered.
+pub fn find_cycles(graph: &PackageGraph) -> Vec<Vec<NodeIndex>> {
+    let mut cycles: Vec<_> = tarjan_scc(graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .filter_map(|component| find_cycle_path(graph, &component))
+        .collect();
+    cycles.sort_unstable();
+    cycles
+}
+
+/// Find the shortest cycle through the first node of a group of nodes which can all reach each
+/// other, only passing nodes of that group
+fn find_cycle_path(graph: &PackageGraph, component: &[NodeIndex]) -> Option<Vec<NodeIndex>> {
+    let start = *component.iter().min()?;
+    let component: HashSet<_> = component.iter().copied().collect();
+
+    let mut previous = HashMap::<NodeIndex, NodeIndex>::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(index) = queue.pop_front() {
+        for next in graph.neighbors_directed(index, Direction::Outgoing) {
+            if next == start {
+                if index == start {
+                    // a self-loop
+                    continue;
+                }
+                // walk back to the start, and reverse
+                let mut path = vec![index];
+                let mut current = index;
+                while let Some(prev) = previous.get(&current) {
+                    path.push(*prev);
+                    current = *prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if component.contains(&next) && !previous.contains_key(&next) {
+                previous.insert(next, index);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
@@ -12,7 +12,11 @@ impl Roots for PaginatedResults<Node> {
     fn roots(self) -> PaginatedResults<Node> {
         let items = self.items.roots();
         let total = Some(items.len() as u64);
-        Self { items, total }
+        Self {
+            items,
+            total,
+            next_cursor: None,
+        }
     }
 }
 
@@ -51,7 +55,11 @@ impl<'a> RootTraces for &'a PaginatedResults<Node> {
     fn root_traces(self) -> Self::Result {
         let items = self.items.root_traces();
         let total = Some(items.len() as u64);
-        Self::Result { items, total }
+        Self::Result {
+            items,
+            total,
+            next_cursor: None,
+        }
     }
 }
 
@@ -0,0 +1,144 @@
+use super::AnalysisService;
+use crate::{
+    Error,
+    model::{BaseSummary, ChangedNode, GraphDiff, PackageGraph, RelationshipChange, graph},
+};
+use petgraph::{Direction, graph::NodeIndex, visit::EdgeRef};
+use sea_orm::{ConnectionTrait, EntityTrait, PaginatorTrait};
+use std::collections::BTreeMap;
+use tracing::instrument;
+use trustify_common::purl::package_key;
+use trustify_entity::{relationship::Relationship, sbom};
+use uuid::Uuid;
+
+impl AnalysisService {
+    /// Compare the graphs of two SBOMs, e.g. two versions of the same document
+    ///
+    /// Components are matched by their versionless PURL, falling back to their name if they
+    /// don't have a PURL. Relationships are matched by the component they point to.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn diff_graphs<C: ConnectionTrait>(
+        &self,
+        old_sbom_id: Uuid,
+        new_sbom_id: Uuid,
+        connection: &C,
+    ) -> Result<GraphDiff, Error> {
+        for sbom_id in [old_sbom_id, new_sbom_id] {
+            if sbom::Entity::find_by_id(sbom_id).count(connection).await? == 0 {
+                return Err(Error::BadRequest {
+                    msg: format!("SBOM {sbom_id} not found"),
+                    status: actix_http::StatusCode::NOT_FOUND,
+                });
+            }
+        }
+
+        let old = self.load_graph(connection, old_sbom_id).await?;
+        let new = self.load_graph(connection, new_sbom_id).await?;
+
+        let mut new_components = components(&new);
+        let mut diff = GraphDiff::default();
+
+        for (key, old_index) in components(&old) {
+            let old_node = BaseSummary::from(&old[old_index]);
+            let Some(new_index) = new_components.remove(&key) else {
+                diff.removed.push(old_node);
+                continue;
+            };
+            let new_node = BaseSummary::from(&new[new_index]);
+
+            let relationships = relationship_changes(&old, old_index, &new, new_index);
+            if old_node.version != new_node.version || !relationships.is_empty() {
+                diff.changed.push(ChangedNode {
+                    old: old_node,
+                    new: new_node,
+                    relationships,
+                });
+            }
+        }
+
+        diff.added = new_components
+            .into_values()
+            .map(|index| BaseSummary::from(&new[index]))
+            .collect();
+
+        Ok(diff)
+    }
+}
+
+/// The identity of a component across SBOMs: its versionless PURL, or its name.
+fn component_key(node: &graph::Node) -> String {
+    match node {
+        graph::Node::Package(package) => package_key(package.purl.iter(), None, &package.name),
+        _ => node.name.clone(),
+    }
+}
+
+/// The components of a graph, by their identity. Of components sharing an identity, only the
+/// first one is used.
+fn components(graph: &PackageGraph) -> BTreeMap<String, NodeIndex> {
+    let mut result = BTreeMap::new();
+    for index in graph.node_indices() {
+        result.entry(component_key(&graph[index])).or_insert(index);
+    }
+    result
+}
+
+/// The outgoing relationships of a component, by the identity of the component they point to.
+fn outgoing(graph: &PackageGraph, index: NodeIndex) -> BTreeMap<String, Vec<Relationship>> {
+    let mut result = BTreeMap::<_, Vec<_>>::new();
+    for edge in graph.edges_directed(index, Direction::Outgoing) {
+        result
+            .entry(component_key(&graph[edge.target()]))
+            .or_default()
+            .push(*edge.weight());
+    }
+    result
+}
+
+fn relationship_changes(
+    old: &PackageGraph,
+    old_index: NodeIndex,
+    new: &PackageGraph,
+    new_index: NodeIndex,
+) -> Vec<RelationshipChange> {
+    let mut old = outgoing(old, old_index);
+    let mut result = vec![];
+
+    for (target, mut added) in outgoing(new, new_index) {
+        let mut removed = old.remove(&target).unwrap_or_default();
+        // drop relationships present in both graphs
+        added.retain(
+            |relationship| match removed.iter().position(|r| r == relationship) {
+                Some(position) => {
+                    removed.swap_remove(position);
+                    false
+                }
+                None => true,
+            },
+        );
+        push_changes(&mut result, target, removed, added);
+    }
+
+    for (target, removed) in old {
+        push_changes(&mut result, target, removed, vec![]);
+    }
+
+    result.sort_by(|a, b| a.target.cmp(&b.target));
+    result
+}
+
+/// Pair up removed and added relationships to the same component
+fn push_changes(
+    result: &mut Vec<RelationshipChange>,
+    target: String,
+    removed: Vec<Relationship>,
+    added: Vec<Relationship>,
+) {
+    for i in 0..removed.len().max(added.len()) {
+        result.push(RelationshipChange {
+            target: target.clone(),
+            old: removed.get(i).copied(),
+            new: added.get(i).copied(),
+        });
+    }
+}
@@ -0,0 +1,95 @@
+use super::{AnalysisService, ComponentReference, GraphQuery};
+use crate::{
+    Error,
+    model::{BaseSummary, ImpactEntry, PackageGraph},
+};
+use petgraph::{Direction, graph::NodeIndex, visit::EdgeRef};
+use sea_orm::ConnectionTrait;
+use std::collections::{HashMap, VecDeque, hash_map::Entry};
+use tracing::instrument;
+use trustify_common::{
+    model::{PaginatedResults, Pagination},
+    purl::Purl,
+};
+use trustify_entity::relationship::Relationship;
+
+impl AnalysisService {
+    /// Find the root components of all SBOMs, transitively depending on a package
+    ///
+    /// Only the SBOMs containing the package are loaded. Roots are the components without any
+    /// ancestor, other than the document describing them. Results are ordered by the length of
+    /// the shortest chain from the root to the package.
+    ///
+    /// This is a slow operation, as the graphs of all matching SBOMs get loaded, which may exceed
+    /// a query timeout meant for regular queries.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn impact_analysis<C: ConnectionTrait>(
+        &self,
+        purl: &Purl,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<ImpactEntry>, Error> {
+        let query = GraphQuery::Component(ComponentReference::Purl(purl));
+        let graphs = self.load_graphs_query(connection, query).await?;
+
+        let mut entries: Vec<_> = graphs
+            .iter()
+            .flat_map(|(_, graph)| {
+                let sources = graph
+                    .node_indices()
+                    .filter(|index| Self::filter(graph, &query, *index));
+                roots(graph, sources)
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            (a.path_length, &a.base.sbom_id, &a.base.node_id).cmp(&(
+                b.path_length,
+                &b.base.sbom_id,
+                &b.base.node_id,
+            ))
+        });
+
+        Ok(paginated.paginate_array(&entries))
+    }
+}
+
+/// Walk up the ancestors of some nodes, returning the roots with their distance
+fn roots(graph: &PackageGraph, sources: impl IntoIterator<Item = NodeIndex>) -> Vec<ImpactEntry> {
+    let ancestors = |index: NodeIndex| {
+        graph
+            .edges_directed(index, Direction::Incoming)
+            .filter(|edge| *edge.weight() != Relationship::Describes)
+            .map(|edge| edge.source())
+    };
+
+    let mut distances = HashMap::<NodeIndex, usize>::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if distances.insert(source, 0).is_none() {
+            queue.push_back(source);
+        }
+    }
+
+    let mut result = vec![];
+    while let Some(index) = queue.pop_front() {
+        let distance = distances[&index];
+        let mut is_root = true;
+        for ancestor in ancestors(index) {
+            is_root = false;
+            if let Entry::Vacant(entry) = distances.entry(ancestor) {
+                entry.insert(distance + 1);
+                queue.push_back(ancestor);
+            }
+        }
+
+        if is_root {
+            result.push(ImpactEntry {
+                base: BaseSummary::from(&graph[index]),
+                path_length: distance,
+            });
+        }
+    }
+
+    result
+}
@@ -72,7 +72,7 @@ pub struct Node {
 }
 
 #[derive(Debug, Default)]
-struct Context {
+pub(super) struct Context {
     strings: HashMap<String, Arc<String>>,
 }
 
@@ -394,6 +394,7 @@ impl InnerService {
                     .column(sbom_node::Column::Name)
                     .column(sbom::Column::Published)
                     .left_join(sbom::Entity)
+                    .filter(sbom::Column::DeletedAt.is_null())
                     // required for purl and cpe refs
                     .join(JoinType::InnerJoin, sbom_node::Relation::Package.def())
                     // required for querying purls
@@ -456,6 +457,7 @@ impl InnerService {
     ) -> Result<Vec<(Uuid, Arc<PackageGraph>)>, Error> {
         let distinct_sbom_ids = sbom::Entity::find()
             .filter(sbom::Column::SbomId.in_subquery(subquery))
+            .filter(sbom::Column::DeletedAt.is_null())
             .select()
             .all(connection)
             .await?
@@ -532,16 +534,25 @@ impl InnerService {
             }
         };
 
-        // full cache miss, perform the work
+        // check for a persisted graph first, only then perform the work
 
-        let g = match Self::perform_load_graph(connection, distinct_sbom_id).await {
-            Ok(g) => g,
-            Err(err) => {
-                // failed to load, remove and notify
-                self.loading_ops.lock().remove(&distinct_sbom_id);
-                let _ = tx.send(Err(err.to_string()));
-                return Err(err);
+        let (g, persisted) = match self
+            .load_persisted_graph(connection, distinct_sbom_id)
+            .await
+        {
+            Some(g) => {
+                log::debug!("Loaded persisted graph");
+                (g, true)
             }
+            None => match Self::perform_load_graph(connection, distinct_sbom_id).await {
+                Ok(g) => (g, false),
+                Err(err) => {
+                    // failed to load, remove and notify
+                    self.loading_ops.lock().remove(&distinct_sbom_id);
+                    let _ = tx.send(Err(err.to_string()));
+                    return Err(err);
+                }
+            },
         };
         let g = Arc::new(g);
 
@@ -552,6 +563,12 @@ impl InnerService {
         self.loading_ops.lock().remove(&distinct_sbom_id);
         let _ = tx.send(Ok(g.clone()));
 
+        // store a newly constructed graph, after the waiting tasks got it
+
+        if !persisted {
+            self.persist_graph(distinct_sbom_id, &g).await;
+        }
+
         // done
 
         Ok(g)
@@ -571,6 +588,7 @@ impl InnerService {
         let mut g: PackageGraph = Graph::new();
         let mut nodes = HashMap::new();
         let mut detected_nodes = HashSet::new();
+        let mut external_nodes = HashSet::new();
 
         // populate packages/components
 
@@ -585,6 +603,9 @@ impl InnerService {
 
         for node in loaded_nodes {
             detected_nodes.insert(node.node_id.clone());
+            if node.ext_node_id.is_some() {
+                external_nodes.insert(node.node_id.clone());
+            }
 
             match nodes.entry(node.node_id.clone()) {
                 Entry::Vacant(entry) => {
@@ -634,12 +655,20 @@ impl InnerService {
         if !describedby_node_id.is_empty() {
             // search of unconnected nodes and create undefined relationships
             // all nodes not removed are unconnected
-            for id in detected_nodes {
-                let Some(id) = nodes.get(&id) else { continue };
-                // add "undefined" relationship
+            for node_id in detected_nodes {
+                let Some(id) = nodes.get(&node_id) else {
+                    continue;
+                };
+                // references to other documents are at least known to be such
+                let relationship = match external_nodes.contains(&node_id) {
+                    true => Relationship::ExternalReference,
+                    false => Relationship::Undefined,
+                };
                 for from in &describedby_node_id {
-                    log::debug!("Creating undefined relationship - left: {from:?}, right: {id:?}");
-                    g.add_edge(*from, *id, Relationship::Undefined);
+                    log::debug!(
+                        "Creating {relationship} relationship - left: {from:?}, right: {id:?}"
+                    );
+                    g.add_edge(*from, *id, relationship);
                 }
             }
         }
@@ -46,6 +46,7 @@ pub fn select() -> Select<sbom_node::Entity> {
         .column(sbom_node::Column::Name)
         .column(sbom::Column::Published)
         .left_join(sbom::Entity)
+        .filter(sbom::Column::DeletedAt.is_null())
 }
 
 // ─── Phase 1: batch direct CPE matches ──────────────────────────────
@@ -1,5 +1,10 @@
+mod diff;
+mod impact;
 mod load;
+mod path;
+mod persist;
 mod query;
+mod snapshot;
 mod walk;
 
 pub use collector::*;
@@ -15,12 +20,18 @@ mod test;
 use crate::model::AnalysisStatusDetails;
 use crate::{
     Error,
-    config::AnalysisConfig,
-    model::{AnalysisStatus, BaseSummary, GraphMap, Node, PackageGraph, graph},
+    config::{AnalysisConfig, WarmStrategy},
+    model::{
+        AnalysisStatus, BaseSummary, GraphMap, Node, PackageGraph, Roots,
+        graph::{self, GraphStats, GraphValidation},
+    },
 };
 use fixedbitset::FixedBitSet;
 use futures::{StreamExt, TryStreamExt, future::Shared, stream};
-use opentelemetry::{global, metrics::Counter};
+use opentelemetry::{
+    global,
+    metrics::{Counter, Histogram},
+};
 use parking_lot::Mutex;
 use petgraph::{
     Direction,
@@ -29,17 +40,23 @@ use petgraph::{
     visit::{VisitMap, Visitable},
 };
 use sea_orm::{
-    ColumnTrait, DatabaseBackend, EntityOrSelect, EntityTrait, FromQueryResult, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, RelationTrait, Statement, prelude::ConnectionTrait,
+    ColumnTrait, DatabaseBackend, EntityOrSelect, EntityTrait, FromQueryResult, Order,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Statement,
+    prelude::ConnectionTrait,
 };
-use sea_query::JoinType;
+use sea_query::{JoinType, NullOrdering};
 use std::{
     collections::{HashMap, HashSet},
     fmt::Debug,
     num::NonZeroU64,
+    path::PathBuf,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::{mpsc, oneshot, oneshot::error::RecvError},
@@ -48,10 +65,12 @@ use tokio::{
 use tracing::instrument;
 use trustify_common::{
     db::{
-        ReadOnly,
+        ReadOnly, ReadWrite,
         query::{Value, ValueContext},
     },
+    metrics::duration_histogram,
     model::{PaginatedResults, Pagination},
+    purl::Purl,
 };
 use trustify_entity::{
     relationship::Relationship,
@@ -92,6 +111,8 @@ pub struct AnalysisService {
     _loader: Arc<JoinHandle<()>>,
     tx: mpsc::UnboundedSender<QueueEntry>,
     concurrency: usize,
+    snapshot_dir: Option<PathBuf>,
+    export_max_nodes: u64,
 }
 
 /// A cross-SBOM link resolved by checksum or document-reference matching.
@@ -164,8 +185,9 @@ async fn resolve_external_sbom<C: ConnectionTrait>(
                 return Ok(None);
             }
 
-            let mut query =
-                sbom::Entity::find().join(JoinType::Join, sbom::Relation::SourceDocument.def());
+            let mut query = sbom::Entity::find()
+                .join(JoinType::Join, sbom::Relation::SourceDocument.def())
+                .filter(sbom::Column::DeletedAt.is_null());
 
             match discriminator_type {
                 DiscriminatorType::Sha256 => {
@@ -204,6 +226,7 @@ async fn resolve_external_sbom<C: ConnectionTrait>(
 
             Ok(sbom::Entity::find()
                 .filter(sbom::Column::DocumentId.eq(external_doc_id))
+                .filter(sbom::Column::DeletedAt.is_null())
                 .order_by_desc(sbom::Column::Published)
                 .one(connection)
                 .await?
@@ -521,6 +544,19 @@ impl AnalysisService {
     /// Also, we do not implement default because of this. As a new instance has the implication
     /// of having its own cache. So creating a new instance should be a deliberate choice.
     pub fn new(config: AnalysisConfig, connection: ReadOnly) -> Self {
+        Self::new_with_store(config, connection, None)
+    }
+
+    /// Create a new analysis service instance, persisting constructed graphs to the database
+    ///
+    /// If a store is provided, graphs which are not in the cache are first looked up from the
+    /// `graph_cache` table, and newly constructed graphs are written to it. As the service
+    /// otherwise only reads, this requires a separate read-write connection.
+    pub fn new_with_store(
+        config: AnalysisConfig,
+        connection: ReadOnly,
+        graph_store: Option<ReadWrite>,
+    ) -> Self {
         let meter = global::meter("AnalysisService");
 
         let graph_cache = Arc::new(GraphMap::new(
@@ -564,23 +600,104 @@ impl AnalysisService {
             loading_ops,
             cache_hit: meter.u64_counter("cache_hits").build(),
             cache_miss: meter.u64_counter("cache_miss").build(),
+            query_duration: duration_histogram(
+                &meter,
+                "query_duration",
+                "Duration of running a query on the loaded graphs",
+            ),
             concurrency: config.concurrency.get(),
             sbom_published_cutoff_days: config.sbom_published_cutoff_days,
+            cache_warming: Arc::new(AtomicBool::new(false)),
+            graph_store,
         };
 
         let loader = {
             let inner = inner.clone();
+            let connection = connection.clone();
             Arc::new(tokio::spawn(async move {
                 Self::loader(rx, inner, connection).await;
             }))
         };
 
-        Self {
+        let service = Self {
             inner,
             _loader: loader,
             tx,
             concurrency: config.concurrency.get(),
+            snapshot_dir: config.snapshot_dir,
+            export_max_nodes: config.export_max_nodes,
+        };
+
+        if config.warm_on_startup != WarmStrategy::None {
+            service.inner.cache_warming.store(true, Ordering::Relaxed);
+
+            let warm = service.clone();
+            tokio::spawn(async move {
+                warm.warm_up(
+                    config.warm_on_startup,
+                    config.warm_recent_count,
+                    *config.warm_timeout,
+                    connection,
+                )
+                .await;
+            });
+        }
+
+        service
+    }
+
+    /// Background task that loads graphs into the cache on startup, according to the strategy.
+    ///
+    /// Gives up once `timeout` has passed, keeping the graphs loaded so far.
+    async fn warm_up(
+        &self,
+        strategy: WarmStrategy,
+        recent_count: u64,
+        timeout: Duration,
+        connection: ReadOnly,
+    ) {
+        log::info!("Warming up graph cache: {strategy}");
+        let start = Instant::now();
+
+        let result = tokio::time::timeout(timeout, async {
+            let tx = connection.begin().await?;
+            match strategy {
+                WarmStrategy::None => Ok(vec![]),
+                WarmStrategy::Recent => {
+                    let ids = sbom::Entity::find()
+                        .select_only()
+                        .column(sbom::Column::SbomId)
+                        .filter(sbom::Column::DeletedAt.is_null())
+                        .order_by_with_nulls(
+                            sbom::Column::Published,
+                            Order::Desc,
+                            NullOrdering::Last,
+                        )
+                        .limit(recent_count)
+                        .into_tuple::<Uuid>()
+                        .all(&tx)
+                        .await?;
+                    self.load_graphs(&tx, ids).await
+                }
+                WarmStrategy::All => self.load_all_graphs(&tx).await,
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(graphs)) => log::info!(
+                "Warmed up graph cache with {} graphs, took {}",
+                graphs.len(),
+                humantime::Duration::from(start.elapsed())
+            ),
+            Ok(Err(err)) => log::warn!("Failed to warm up graph cache: {err}"),
+            Err(_) => log::warn!(
+                "Warming up graph cache timed out after {}",
+                humantime::Duration::from(timeout)
+            ),
         }
+
+        self.inner.cache_warming.store(false, Ordering::Relaxed);
     }
 
     /// Background task that loads graphs into cache using a read-only connection.
@@ -654,6 +771,7 @@ impl AnalysisService {
         // retrieve all sboms in trustify
 
         let distinct_sbom_ids = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
             .select()
             .all(connection)
             .await?
@@ -664,6 +782,62 @@ impl AnalysisService {
         self.load_graphs(connection, distinct_sbom_ids).await
     }
 
+    /// Check the graph of an SBOM for structural issues
+    ///
+    /// The graph is loaded through the cache, but not modified.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn validate<C: ConnectionTrait>(
+        &self,
+        connection: &C,
+        sbom_id: Uuid,
+    ) -> Result<GraphValidation, Error> {
+        let graph = self.load_graph(connection, sbom_id).await?;
+        Ok(graph::validate_graph(&graph).into())
+    }
+
+    /// Collect statistics of the structure of the graph of an SBOM
+    ///
+    /// The graph is loaded through the cache, but not modified.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn graph_stats<C: ConnectionTrait>(
+        &self,
+        connection: &C,
+        sbom_id: Uuid,
+    ) -> Result<GraphStats, Error> {
+        let graph = self.load_graph(connection, sbom_id).await?;
+        Ok(graph::graph_stats(&graph))
+    }
+
+    /// Get the top level nodes of the graph of an SBOM
+    ///
+    /// These are all nodes without incoming relationships, ignoring relationships of an undefined
+    /// type. Unlike querying for components, this only works on the graph, which is loaded
+    /// through the cache.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn root_nodes<C: ConnectionTrait>(
+        &self,
+        connection: &C,
+        sbom_id: Uuid,
+    ) -> Result<Vec<BaseSummary>, Error> {
+        let graph = self.load_graph(connection, sbom_id).await?;
+
+        Ok(graph
+            .node_indices()
+            .filter(|&index| {
+                graph
+                    .edges_directed(index, Direction::Incoming)
+                    .all(|edge| *edge.weight() == Relationship::Undefined)
+            })
+            .filter_map(|index| graph.node_weight(index))
+            .map(BaseSummary::from)
+            .collect())
+    }
+
+    /// Remove the graph of an SBOM from the cache, so that it gets loaded again on next access
+    pub fn clear_graph(&self, sbom_id: Uuid) {
+        self.inner.graph_cache.remove(sbom_id);
+    }
+
     pub fn clear_all_graphs(&self) -> Result<(), Error> {
         self.inner.graph_cache.clear();
         Ok(())
@@ -674,14 +848,28 @@ impl AnalysisService {
         connection: &C,
         details: bool,
     ) -> Result<AnalysisStatus, Error> {
-        let distinct_sbom_ids = sbom::Entity::find().count(connection).await?;
+        let distinct_sbom_ids = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
+            .count(connection)
+            .await?;
+
+        let graphs = self.inner.graph_cache.entries();
 
         Ok(AnalysisStatus {
             sbom_count: distinct_sbom_ids as u32,
             graph_count: self.inner.graph_cache.len() as u32,
+            graph_nodes: graphs
+                .iter()
+                .map(|(_, graph)| graph.node_count() as u64)
+                .sum(),
+            graph_edges: graphs
+                .iter()
+                .map(|(_, graph)| graph.edge_count() as u64)
+                .sum(),
             graph_memory: self.inner.graph_cache.size_used(),
             graph_max_memory: self.inner.graph_cache.capacity(),
             loading_operations: self.inner.loading_ops.lock().len() as u32,
+            cache_warming: self.inner.cache_warming.load(Ordering::Relaxed),
             details: details.then(|| self.inner.status_details()),
         })
     }
@@ -725,6 +913,8 @@ impl AnalysisService {
         graphs: &[(Uuid, Arc<PackageGraph>)],
         connection: &C,
     ) -> Result<Vec<Node>, Error> {
+        let start = Instant::now();
+
         let relationships = options.relationships;
         log::debug!("relations: {:?}", relationships);
 
@@ -740,77 +930,84 @@ impl AnalysisService {
             ancestor_cache.prefetch(*sbom_id, graph, connection).await?;
         }
 
-        self.collect_graph(
-            query,
-            graphs,
-            self.concurrency,
-            |graph, node_index, node| {
-                let graph_cache = self.inner.graph_cache.clone();
-                let relationships = relationships.clone();
-                let ancestor_cache = ancestor_cache.clone();
-                async move {
-                    log::trace!(
-                        "Discovered node - sbom: {}, node: {}",
-                        node.sbom_id,
-                        node.node_id
-                    );
-
-                    let ancestors = Collector::new(
-                        &graph_cache,
-                        graphs,
-                        node.sbom_id,
-                        graph,
-                        node_index,
-                        Direction::Incoming,
-                        options.ancestors,
-                        &relationships,
-                        connection,
-                        self.concurrency,
-                        loader,
-                        ancestor_cache.clone(),
-                    )
-                    .collect();
-
-                    let descendants = Collector::new(
-                        &graph_cache,
-                        graphs,
-                        node.sbom_id,
-                        graph,
-                        node_index,
-                        Direction::Outgoing,
-                        options.descendants,
-                        &relationships,
-                        connection,
-                        self.concurrency,
-                        loader,
-                        ancestor_cache,
-                    )
-                    .collect();
-
-                    // join and eval error
-
-                    let (ancestors, descendants) = futures::join!(ancestors, descendants);
-                    let ancestors = ancestors?;
-                    let descendants = descendants?;
-
-                    // collect warnings
-
-                    let mut warnings = ancestors.1;
-                    warnings.extend(descendants.1);
-
-                    // return result
-
-                    Ok(Node {
-                        base: node.into(),
-                        relationship: None,
-                        ancestors: ancestors.0,
-                        descendants: descendants.0,
-                        warnings,
-                    })
-                }
-            },
-        )
-        .await
+        let result = self
+            .collect_graph(
+                query,
+                graphs,
+                self.concurrency,
+                |graph, node_index, node| {
+                    let graph_cache = self.inner.graph_cache.clone();
+                    let relationships = relationships.clone();
+                    let ancestor_cache = ancestor_cache.clone();
+                    async move {
+                        log::trace!(
+                            "Discovered node - sbom: {}, node: {}",
+                            node.sbom_id,
+                            node.node_id
+                        );
+
+                        let ancestors = Collector::new(
+                            &graph_cache,
+                            graphs,
+                            node.sbom_id,
+                            graph,
+                            node_index,
+                            Direction::Incoming,
+                            options.ancestors,
+                            &relationships,
+                            connection,
+                            self.concurrency,
+                            loader,
+                            ancestor_cache.clone(),
+                        )
+                        .collect();
+
+                        let descendants = Collector::new(
+                            &graph_cache,
+                            graphs,
+                            node.sbom_id,
+                            graph,
+                            node_index,
+                            Direction::Outgoing,
+                            options.descendants,
+                            &relationships,
+                            connection,
+                            self.concurrency,
+                            loader,
+                            ancestor_cache,
+                        )
+                        .collect();
+
+                        // join and eval error
+
+                        let (ancestors, descendants) = futures::join!(ancestors, descendants);
+                        let ancestors = ancestors?;
+                        let descendants = descendants?;
+
+                        // collect warnings
+
+                        let mut warnings = ancestors.1;
+                        warnings.extend(descendants.1);
+
+                        // return result
+
+                        Ok(Node {
+                            base: node.into(),
+                            relationship: None,
+                            ancestors: ancestors.0,
+                            descendants: descendants.0,
+                            warnings,
+                        })
+                    }
+                },
+            )
+            .await;
+
+        self.inner
+            .query_duration
+            .record(start.elapsed().as_secs_f64(), &[]);
+
+        result
     }
 
     /// locate components, retrieve dependency information, from a single SBOM
@@ -837,6 +1034,28 @@ impl AnalysisService {
         Ok(paginated.paginate_array(&components))
     }
 
+    /// locate all root components of a single SBOM, which (transitively) contain a component
+    /// matching the PURL
+    #[instrument(skip(self, connection), err)]
+    pub async fn retrieve_all_sbom_roots_by_purl<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        purl: &Purl,
+        connection: &C,
+    ) -> Result<Vec<Node>, Error> {
+        let graphs = self.load_graphs(connection, vec![sbom_id]).await?;
+        let components = self
+            .run_graph_query(
+                GraphQuery::from(purl),
+                QueryOptions::ancestors(),
+                &graphs,
+                connection,
+            )
+            .await?;
+
+        Ok(components.roots())
+    }
+
     /// locate components, retrieve dependency information
     #[instrument(skip(self, connection), err)]
     pub async fn retrieve<C: ConnectionTrait>(
@@ -985,8 +1204,13 @@ struct InnerService {
     loading_ops: Arc<Mutex<HashMap<Uuid, LoadingOp>>>,
     cache_hit: Counter<u64>,
     cache_miss: Counter<u64>,
+    query_duration: Histogram<f64>,
     concurrency: usize,
     sbom_published_cutoff_days: Option<NonZeroU64>,
+    /// Whether the graph cache is being warmed up on startup
+    cache_warming: Arc<AtomicBool>,
+    /// The connection to persist graphs with, if enabled
+    graph_store: Option<ReadWrite>,
 }
 
 impl InnerService {
@@ -0,0 +1,84 @@
+use super::{AnalysisService, ComponentReference, GraphQuery};
+use crate::{
+    Error,
+    model::{BaseSummary, PackageGraph, PathNode},
+};
+use petgraph::{algo::astar, graph::NodeIndex};
+use sea_orm::ConnectionTrait;
+use std::collections::HashSet;
+use tracing::instrument;
+use uuid::Uuid;
+
+impl AnalysisService {
+    /// Find the shortest path from one component of an SBOM to another
+    ///
+    /// The path follows the direction of the relationships, the same way descendants are
+    /// discovered. If a reference matches more than one component, the shortest path of all
+    /// of them is returned.
+    ///
+    /// Returns `None` if there is no such path, and an error if either component was not found.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn shortest_path<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        from: ComponentReference<'_>,
+        to: ComponentReference<'_>,
+        connection: &C,
+    ) -> Result<Option<Vec<PathNode>>, Error> {
+        let graph = self.load_graph(connection, sbom_id).await?;
+
+        let sources = find_nodes(&graph, from);
+        if sources.is_empty() {
+            return Err(not_found("from"));
+        }
+        let targets = find_nodes(&graph, to).into_iter().collect::<HashSet<_>>();
+        if targets.is_empty() {
+            return Err(not_found("to"));
+        }
+
+        let path = sources
+            .into_iter()
+            .filter_map(|source| {
+                astar(
+                    &*graph,
+                    source,
+                    |node| targets.contains(&node),
+                    |_| 1u32,
+                    |_| 0,
+                )
+            })
+            .min_by_key(|(cost, _)| *cost);
+
+        Ok(path.map(|(_, path)| {
+            let mut previous = None;
+            path.into_iter()
+                .map(|index| {
+                    let relationship = previous
+                        .and_then(|previous| graph.find_edge(previous, index))
+                        .map(|edge| graph[edge]);
+                    previous = Some(index);
+                    PathNode {
+                        base: BaseSummary::from(&graph[index]),
+                        relationship,
+                    }
+                })
+                .collect()
+        }))
+    }
+}
+
+/// Find all nodes of a graph matching the reference
+fn find_nodes(graph: &PackageGraph, reference: ComponentReference<'_>) -> Vec<NodeIndex> {
+    let query = GraphQuery::Component(reference);
+    graph
+        .node_indices()
+        .filter(|index| AnalysisService::filter(graph, &query, *index))
+        .collect()
+}
+
+fn not_found(side: &str) -> Error {
+    Error::BadRequest {
+        msg: format!("The '{side}' component was not found in the SBOM"),
+        status: actix_http::StatusCode::NOT_FOUND,
+    }
+}
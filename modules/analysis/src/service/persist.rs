@@ -0,0 +1,89 @@
+use super::{
+    InnerService,
+    snapshot::{SNAPSHOT_VERSION, decode_graph, encode_graph},
+};
+use crate::model::PackageGraph;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect,
+};
+use sea_query::OnConflict;
+use time::OffsetDateTime;
+use trustify_entity::{graph_cache, sbom};
+use uuid::Uuid;
+
+impl InnerService {
+    /// Load the persisted graph of an SBOM
+    ///
+    /// Returns `None` if persisting graphs is disabled, or if there is no usable graph. A graph
+    /// which can't be read is treated as missing, so that it gets re-constructed. Graphs of deleted
+    /// SBOMs are ignored, but kept in case the SBOM gets restored.
+    pub(super) async fn load_persisted_graph<C: ConnectionTrait>(
+        &self,
+        connection: &C,
+        sbom_id: Uuid,
+    ) -> Option<PackageGraph> {
+        self.graph_store.as_ref()?;
+
+        let result = graph_cache::Entity::find_by_id(sbom_id)
+            .inner_join(sbom::Entity)
+            .filter(graph_cache::Column::Version.eq(SNAPSHOT_VERSION as i32))
+            .filter(sbom::Column::DeletedAt.is_null())
+            .one(connection)
+            .await;
+
+        let entry = match result {
+            Ok(entry) => entry?,
+            Err(err) => {
+                tracing::warn!("Failed to load persisted graph of SBOM {sbom_id}: {err}");
+                return None;
+            }
+        };
+
+        match decode_graph(&entry.serialized_graph) {
+            Ok(graph) => Some(graph),
+            Err(err) => {
+                tracing::warn!("Ignoring persisted graph of SBOM {sbom_id}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Persist the graph of an SBOM, if enabled
+    ///
+    /// Failing to do so only gets logged, as the graph can always be re-constructed.
+    pub(super) async fn persist_graph(&self, sbom_id: Uuid, graph: &PackageGraph) {
+        let Some(store) = &self.graph_store else {
+            return;
+        };
+
+        let serialized_graph = match encode_graph(sbom_id, graph) {
+            Ok(data) => data,
+            Err(err) => {
+                tracing::warn!("Failed to encode graph of SBOM {sbom_id}: {err}");
+                return;
+            }
+        };
+
+        let result = graph_cache::Entity::insert(graph_cache::ActiveModel {
+            sbom_id: Set(sbom_id),
+            version: Set(SNAPSHOT_VERSION as i32),
+            serialized_graph: Set(serialized_graph),
+            loaded_at: Set(OffsetDateTime::now_utc()),
+        })
+        .on_conflict(
+            OnConflict::column(graph_cache::Column::SbomId)
+                .update_columns([
+                    graph_cache::Column::Version,
+                    graph_cache::Column::SerializedGraph,
+                    graph_cache::Column::LoadedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(store)
+        .await;
+
+        if let Err(err) = result {
+            tracing::warn!("Failed to persist graph of SBOM {sbom_id}: {err}");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    model::{BaseSummary, GraphEdge, GraphExport, graph},
+    service::Visitor,
+};
+use trustify_entity::relationship::Relationship;
+
+/// Collects the nodes and edges of a graph, for serializing them
+#[derive(Default)]
+pub struct Exporter {
+    export: GraphExport,
+}
+
+impl Visitor for Exporter {
+    type Output = GraphExport;
+
+    fn node(&mut self, node: &graph::Node) {
+        self.export.nodes.push(BaseSummary::from(node));
+    }
+
+    fn edge(&mut self, source: &graph::Node, relationship: Relationship, target: &graph::Node) {
+        self.export.edges.push(GraphEdge {
+            source_node_id: source.node_id.clone(),
+            target_node_id: target.node_id.clone(),
+            relationship,
+        });
+    }
+
+    fn complete(self) -> Self::Output {
+        self.export
+    }
+}
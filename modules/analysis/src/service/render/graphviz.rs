@@ -36,6 +36,8 @@ fn escape(id: &str) -> String {
 
 pub struct Renderer {
     data: String,
+    /// Whether to prefix node IDs with the SBOM ID, to keep them unique across SBOMs
+    qualified: bool,
 }
 
 impl Renderer {
@@ -45,6 +47,23 @@ impl Renderer {
 digraph {
 "#
             .to_string(),
+            qualified: false,
+        }
+    }
+
+    /// Create a renderer for nodes of multiple SBOMs
+    pub fn qualified() -> Self {
+        Self {
+            qualified: true,
+            ..Self::new()
+        }
+    }
+
+    /// The escaped ID of a node in the rendered graph
+    fn id(&self, node: &graph::BaseNode) -> String {
+        match self.qualified {
+            true => escape(&format!("{}/{}", node.sbom_id, node.node_id)),
+            false => escape(&node.node_id),
         }
     }
 }
@@ -58,7 +77,7 @@ impl Visitor for Renderer {
                 let _ = writeln!(
                     self.data,
                     r#""{id}" [label="{label}"]"#,
-                    id = escape(&package.node_id),
+                    id = self.id(package),
                     label = escape(&format!(
                         "{name} / {version}: {id}",
                         name = package.name,
@@ -71,7 +90,7 @@ impl Visitor for Renderer {
                 let _ = writeln!(
                     self.data,
                     r#""{id}" [label="{label}"]"#,
-                    id = escape(&external.node_id),
+                    id = self.id(external),
                     label = escape(&format!(
                         "{doc} # {node}: {id}",
                         doc = external.external_document_reference,
@@ -84,7 +103,7 @@ impl Visitor for Renderer {
                 let _ = writeln!(
                     self.data,
                     r#""{id}" [label="{label}"]"#,
-                    id = escape(&base.node_id),
+                    id = self.id(base),
                     label = escape(&base.node_id.to_string())
                 );
             }
@@ -95,9 +114,9 @@ impl Visitor for Renderer {
         let _ = writeln!(
             self.data,
             r#""{source}" -> "{target}" [label="{label}"]"#,
-            source = escape(&source.node_id),
-            target = escape(&target.node_id),
-            label = escape(&relationship.to_string())
+            source = self.id(source),
+            target = self.id(target),
+            label = escape(relationship.display_name())
         );
     }
 
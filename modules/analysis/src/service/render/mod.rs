@@ -1,6 +1,8 @@
+mod export;
 mod graphviz;
 
 use super::*;
+use crate::model::GraphExport;
 use serde::Deserialize;
 use utoipa::ToSchema;
 
@@ -18,4 +20,44 @@ impl AnalysisService {
             Renderer::Graphviz => self.walk(graph, graphviz::Renderer::new()),
         }
     }
+
+    /// Export all nodes and edges of a graph.
+    ///
+    /// Fails with `413 Payload Too Large` if the graph has more nodes than the configured
+    /// maximum.
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub fn export(&self, graph: &PackageGraph) -> Result<GraphExport, Error> {
+        self.ensure_exportable(graph)?;
+        Ok(self.walk_all([graph], export::Exporter::default()))
+    }
+
+    /// Ensure the graph doesn't have more nodes than may be exported.
+    pub fn ensure_exportable(&self, graph: &PackageGraph) -> Result<(), Error> {
+        let nodes = graph.node_count() as u64;
+        if self.export_max_nodes > 0 && nodes > self.export_max_nodes {
+            return Err(Error::BadRequest {
+                msg: format!(
+                    "The graph has {nodes} nodes, exceeding the limit of {}",
+                    self.export_max_nodes
+                ),
+                status: actix_http::StatusCode::PAYLOAD_TOO_LARGE,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Render all graphs currently in the cache as a single graph.
+    ///
+    /// As node IDs are only unique within an SBOM, they are qualified with the ID of the SBOM.
+    #[instrument(skip_all)]
+    pub fn render_all(&self, renderer: Renderer) -> (String, String) {
+        let mut graphs = self.inner.graph_cache.entries();
+        graphs.sort_unstable_by_key(|(sbom_id, _)| *sbom_id);
+        let graphs = graphs.iter().map(|(_, graph)| graph.as_ref());
+
+        match renderer {
+            Renderer::Graphviz => self.walk_all(graphs, graphviz::Renderer::qualified()),
+        }
+    }
 }
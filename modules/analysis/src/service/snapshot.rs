@@ -0,0 +1,316 @@
+use super::{AnalysisService, load::Context};
+use crate::{
+    Error,
+    model::{PackageGraph, graph},
+};
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Write,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use time::{OffsetDateTime, UtcOffset};
+use trustify_common::{cpe::Cpe, purl::Purl};
+use trustify_entity::{cpe::CpeDto, relationship::Relationship};
+use uuid::Uuid;
+
+/// Marker at the start of each snapshot file
+const MAGIC: &[u8; 8] = b"TRUSTGRF";
+
+/// The version of the snapshot format
+///
+/// This must be increased whenever the encoding of the snapshot changes in an incompatible way.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    graphs: Vec<SnapshotGraph>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotGraph {
+    sbom_id: Uuid,
+    nodes: Vec<SnapshotNode>,
+    /// Edges, as source index, target index and relationship
+    edges: Vec<(u32, u32, Relationship)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotBase {
+    sbom_id: Uuid,
+    node_id: String,
+    /// Unix timestamp, in nanoseconds
+    published: i128,
+    /// UTC offset, in seconds
+    published_offset: i32,
+    name: String,
+    document_id: Option<String>,
+    product_name: Option<String>,
+    product_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum SnapshotNode {
+    Package {
+        base: SnapshotBase,
+        purl: Vec<Purl>,
+        /// CPEs in their decomposed form, as their string form doesn't round-trip
+        cpe: Vec<CpeDto>,
+        version: String,
+    },
+    External {
+        base: SnapshotBase,
+        external_document_reference: String,
+        external_node_id: String,
+    },
+    Unknown(SnapshotBase),
+}
+
+impl From<&graph::BaseNode> for SnapshotBase {
+    fn from(value: &graph::BaseNode) -> Self {
+        Self {
+            sbom_id: value.sbom_id,
+            node_id: value.node_id.clone(),
+            published: value.published.unix_timestamp_nanos(),
+            published_offset: value.published.offset().whole_seconds(),
+            name: value.name.clone(),
+            document_id: value.document_id.as_deref().cloned(),
+            product_name: value.product_name.as_deref().cloned(),
+            product_version: value.product_version.as_deref().cloned(),
+        }
+    }
+}
+
+impl From<&graph::Node> for SnapshotNode {
+    fn from(value: &graph::Node) -> Self {
+        match value {
+            graph::Node::Package(package) => Self::Package {
+                base: (&package.base).into(),
+                purl: package.purl.to_vec(),
+                cpe: package.cpe.iter().cloned().map(CpeDto::from).collect(),
+                version: package.version.clone(),
+            },
+            graph::Node::External(external) => Self::External {
+                base: (&external.base).into(),
+                external_document_reference: external.external_document_reference.clone(),
+                external_node_id: external.external_node_id.clone(),
+            },
+            graph::Node::Unknown(base) => Self::Unknown(base.into()),
+        }
+    }
+}
+
+impl SnapshotBase {
+    fn into_base_node(self, ctx: &mut Context) -> Result<graph::BaseNode, Error> {
+        let published = OffsetDateTime::from_unix_timestamp_nanos(self.published)
+            .and_then(|published| {
+                Ok(published.to_offset(UtcOffset::from_whole_seconds(self.published_offset)?))
+            })
+            .map_err(|err| Error::Snapshot(format!("invalid published date: {err}")))?;
+
+        Ok(graph::BaseNode {
+            sbom_id: self.sbom_id,
+            node_id: self.node_id,
+            published,
+            name: self.name,
+            document_id: self.document_id.map(|s| ctx.intern(s)),
+            product_name: self.product_name.map(|s| ctx.intern(s)),
+            product_version: self.product_version.map(|s| ctx.intern(s)),
+        })
+    }
+}
+
+impl SnapshotNode {
+    fn into_graph_node(self, ctx: &mut Context) -> Result<graph::Node, Error> {
+        Ok(match self {
+            Self::Package {
+                base,
+                purl,
+                cpe,
+                version,
+            } => graph::Node::Package(graph::PackageNode {
+                base: base.into_base_node(ctx)?,
+                purl: purl.into(),
+                cpe: cpe
+                    .into_iter()
+                    .map(Cpe::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| Error::Snapshot(format!("invalid CPE: {err}")))?
+                    .into(),
+                version,
+            }),
+            Self::External {
+                base,
+                external_document_reference,
+                external_node_id,
+            } => graph::Node::External(graph::ExternalNode {
+                base: base.into_base_node(ctx)?,
+                external_document_reference,
+                external_node_id,
+            }),
+            Self::Unknown(base) => graph::Node::Unknown(base.into_base_node(ctx)?),
+        })
+    }
+}
+
+impl SnapshotGraph {
+    fn new(sbom_id: Uuid, graph: &PackageGraph) -> Self {
+        Self {
+            sbom_id,
+            nodes: graph
+                .raw_nodes()
+                .iter()
+                .map(|node| (&node.weight).into())
+                .collect(),
+            edges: graph
+                .raw_edges()
+                .iter()
+                .map(|edge| {
+                    (
+                        edge.source().index() as u32,
+                        edge.target().index() as u32,
+                        edge.weight,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn into_graph(self) -> Result<(Uuid, PackageGraph), Error> {
+        let mut ctx = Context::new();
+        let mut graph = PackageGraph::with_capacity(self.nodes.len(), self.edges.len());
+
+        for node in self.nodes {
+            graph.add_node(node.into_graph_node(&mut ctx)?);
+        }
+
+        for (source, target, relationship) in self.edges {
+            let (source, target) = (NodeIndex::new(source as _), NodeIndex::new(target as _));
+            if graph.node_weight(source).is_none() || graph.node_weight(target).is_none() {
+                return Err(Error::Snapshot(format!(
+                    "edge of graph {} references an unknown node",
+                    self.sbom_id
+                )));
+            }
+            graph.add_edge(source, target, relationship);
+        }
+
+        Ok((self.sbom_id, graph))
+    }
+}
+
+/// Encode a single graph, using the format of the snapshots
+pub(super) fn encode_graph(sbom_id: Uuid, graph: &PackageGraph) -> Result<Vec<u8>, Error> {
+    postcard::to_stdvec(&SnapshotGraph::new(sbom_id, graph))
+        .map_err(|err| Error::Internal(format!("failed to encode graph: {err}")))
+}
+
+/// Decode a single graph, encoded by [`encode_graph`]
+pub(super) fn decode_graph(data: &[u8]) -> Result<PackageGraph, Error> {
+    let graph: SnapshotGraph = postcard::from_bytes(data)
+        .map_err(|err| Error::Snapshot(format!("failed to decode graph: {err}")))?;
+    Ok(graph.into_graph()?.1)
+}
+
+impl AnalysisService {
+    /// Write all graphs currently in the cache to a snapshot file.
+    pub fn save_snapshot(&self, path: &Path) -> Result<(), Error> {
+        let snapshot = Snapshot {
+            graphs: self
+                .inner
+                .graph_cache
+                .entries()
+                .into_iter()
+                .map(|(sbom_id, graph)| SnapshotGraph::new(sbom_id, &graph))
+                .collect(),
+        };
+
+        let data = postcard::to_stdvec(&snapshot)
+            .map_err(|err| Error::Internal(format!("failed to encode snapshot: {err}")))?;
+
+        let mut file = fs::File::create(path)?;
+        file.write_all(MAGIC)?;
+        file.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+
+        tracing::info!(
+            "Saved {} graphs to snapshot {}",
+            snapshot.graphs.len(),
+            path.display()
+        );
+
+        Ok(())
+    }
+
+    /// Replace the content of the cache with the graphs of a snapshot file.
+    ///
+    /// Fails if the snapshot was written using a different version of the format.
+    pub fn restore_snapshot(&self, path: &Path) -> Result<(), Error> {
+        let data = fs::read(path)?;
+
+        let Some((version, data)) = data
+            .strip_prefix(MAGIC)
+            .and_then(|data| data.split_first_chunk::<4>())
+        else {
+            return Err(Error::Snapshot("not a graph snapshot".into()));
+        };
+
+        let version = u32::from_le_bytes(*version);
+        if version != SNAPSHOT_VERSION {
+            return Err(Error::Snapshot(format!(
+                "unsupported snapshot version {version}, expected {SNAPSHOT_VERSION}"
+            )));
+        }
+
+        let snapshot: Snapshot = postcard::from_bytes(data)
+            .map_err(|err| Error::Snapshot(format!("failed to decode snapshot: {err}")))?;
+
+        let graphs = snapshot
+            .graphs
+            .into_iter()
+            .map(SnapshotGraph::into_graph)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        tracing::info!(
+            "Restoring {} graphs from snapshot {}",
+            graphs.len(),
+            path.display()
+        );
+
+        self.inner.graph_cache.clear();
+        for (sbom_id, graph) in graphs {
+            self.inner.graph_cache.insert(sbom_id, Arc::new(graph));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the name of a snapshot to a file in the configured snapshot directory.
+    ///
+    /// The name must be a relative path, not leaving the snapshot directory.
+    pub fn snapshot_path(&self, name: &str) -> Result<PathBuf, Error> {
+        let Some(dir) = &self.snapshot_dir else {
+            return Err(Error::BadRequest {
+                msg: "Graph snapshots are not enabled".into(),
+                status: actix_http::StatusCode::NOT_FOUND,
+            });
+        };
+
+        let name = Path::new(name);
+        if name.as_os_str().is_empty()
+            || !name
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(Error::BadRequest {
+                msg: format!("Invalid snapshot path: {}", name.display()),
+                status: actix_http::StatusCode::BAD_REQUEST,
+            });
+        }
+
+        Ok(dir.join(name))
+    }
+}
@@ -1,16 +1,23 @@
 use crate::{
     config::AnalysisConfig,
+    model::{
+        GraphMap, PackageGraph,
+        graph::{BaseNode, Node},
+    },
     service::{
         AnalysisService, ComponentReference, QueryOptions, test::warnings::collect_warnings,
     },
 };
-use std::collections::BTreeMap;
+use opentelemetry::global;
+use std::{collections::BTreeMap, sync::Arc};
 use test_context::test_context;
+use time::OffsetDateTime;
 use trustify_common::{
     db::ReadOnly,
     model::{BinaryByteSize, Paginated, PaginatedResults},
 };
 use trustify_test_context::TrustifyContext;
+use uuid::Uuid;
 
 /// Given a set of ingested documents and a component query,
 /// when the same query is run against a large cache and a tiny (1-byte) cache,
@@ -176,3 +183,48 @@ async fn fan_out_same_target(ctx: &TrustifyContext) -> Result<(), anyhow::Error>
 
     Ok(())
 }
+
+/// A graph of a single node, all of the same size
+fn single_node_graph(sbom_id: Uuid) -> Arc<PackageGraph> {
+    let mut graph = PackageGraph::new();
+    graph.add_node(Node::Unknown(BaseNode {
+        sbom_id,
+        node_id: "node".to_string(),
+        published: OffsetDateTime::UNIX_EPOCH,
+        name: "node".to_string(),
+        document_id: None,
+        product_name: None,
+        product_version: None,
+    }));
+    Arc::new(graph)
+}
+
+/// Given a cache with room for two graphs, when a third one gets added,
+/// then the least recently used graph is evicted, and the new one is kept.
+#[test]
+fn evict_least_recently_used() {
+    let meter = global::meter("test");
+    let [a, b, c] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+    let probe = GraphMap::new(
+        u64::MAX,
+        meter.u64_counter("evictions").build(),
+        meter.u64_counter("eviction_size").build(),
+    );
+    probe.insert(a, single_node_graph(a));
+    let size = probe.size_used();
+
+    let map = GraphMap::new(
+        size * 2,
+        meter.u64_counter("evictions").build(),
+        meter.u64_counter("eviction_size").build(),
+    );
+    map.insert(a, single_node_graph(a));
+    map.insert(b, single_node_graph(b));
+    assert!(map.get(a).is_some());
+    map.insert(c, single_node_graph(c));
+
+    assert!(map.contains_key(a));
+    assert!(!map.contains_key(b));
+    assert!(map.contains_key(c));
+}
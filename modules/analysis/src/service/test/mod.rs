@@ -1,7 +1,11 @@
 mod cache_eviction;
 mod external_depth;
+mod persist;
 mod query;
 mod recursive;
+mod snapshot;
+mod stats;
+mod validate;
 mod warnings;
 
 use super::*;
@@ -365,6 +369,37 @@ async fn test_status_service(ctx: &TrustifyContext) -> Result<(), anyhow::Error>
     Ok(())
 }
 
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn test_warm_up_recent(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_documents(["spdx/simple.json", "spdx/simple-dup.json"])
+        .await?;
+
+    let service = AnalysisService::new(
+        AnalysisConfig {
+            warm_on_startup: WarmStrategy::Recent,
+            warm_recent_count: 1,
+            ..Default::default()
+        },
+        ReadOnly::new(ctx.db.clone()),
+    );
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while service.status(&ctx.db, false).await?.cache_warming {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        Ok::<_, Error>(())
+    })
+    .await??;
+
+    let analysis_status = service.status(&ctx.db, false).await?;
+
+    assert_eq!(analysis_status.sbom_count, 2);
+    assert_eq!(analysis_status.graph_count, 1);
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(tokio::test)]
 async fn test_cache_size_used(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -0,0 +1,123 @@
+use crate::{config::AnalysisConfig, service::AnalysisService};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Statement};
+use test_context::test_context;
+use test_log::test;
+use trustify_common::db::{ReadOnly, ReadWrite};
+use trustify_entity::{graph_cache, package_relates_to_package};
+use trustify_test_context::TrustifyContext;
+use uuid::Uuid;
+
+fn service(ctx: &TrustifyContext) -> AnalysisService {
+    AnalysisService::new_with_store(
+        AnalysisConfig::default(),
+        ReadOnly::new(ctx.db.clone()),
+        Some(ReadWrite::new(ctx.db.clone())),
+    )
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn persist_and_reuse(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom_id = ctx
+        .ingest_document("spdx/simple.json")
+        .await?
+        .id
+        .parse::<Uuid>()?;
+
+    let graph = service(ctx).load_graph(&ctx.db, sbom_id).await?;
+    assert_ne!(graph.edge_count(), 0);
+
+    let entry = graph_cache::Entity::find_by_id(sbom_id)
+        .one(&ctx.db)
+        .await?
+        .expect("graph must be persisted");
+    assert!(!entry.serialized_graph.is_empty());
+
+    // drop the relationships, so a re-constructed graph would be different
+    package_relates_to_package::Entity::delete_many()
+        .filter(package_relates_to_package::Column::SbomId.eq(sbom_id))
+        .exec(&ctx.db)
+        .await?;
+
+    // a new instance, with an empty cache, must use the persisted graph
+    let persisted = service(ctx).load_graph(&ctx.db, sbom_id).await?;
+    assert_eq!(persisted.node_count(), graph.node_count());
+    assert_eq!(persisted.edge_count(), graph.edge_count());
+
+    // a persisted graph of another version of the format must be ignored and replaced
+    ctx.db
+        .execute(Statement::from_sql_and_values(
+            ctx.db.get_database_backend(),
+            "UPDATE graph_cache SET version = version + 1 WHERE sbom_id = $1",
+            [sbom_id.into()],
+        ))
+        .await?;
+
+    let reconstructed = service(ctx).load_graph(&ctx.db, sbom_id).await?;
+    assert_eq!(reconstructed.node_count(), graph.node_count());
+    assert!(reconstructed.edge_count() < graph.edge_count());
+
+    let replaced = graph_cache::Entity::find_by_id(sbom_id)
+        .one(&ctx.db)
+        .await?
+        .expect("graph must be persisted");
+    assert_eq!(replaced.version, entry.version);
+    assert!(replaced.loaded_at >= entry.loaded_at);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn not_persisted_without_store(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom_id = ctx
+        .ingest_document("spdx/simple.json")
+        .await?
+        .id
+        .parse::<Uuid>()?;
+
+    let service = AnalysisService::new(AnalysisConfig::default(), ReadOnly::new(ctx.db.clone()));
+    service.load_graph(&ctx.db, sbom_id).await?;
+
+    assert!(
+        graph_cache::Entity::find_by_id(sbom_id)
+            .one(&ctx.db)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn ignore_persisted_of_deleted(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom_id = ctx
+        .ingest_document("spdx/simple.json")
+        .await?
+        .id
+        .parse::<Uuid>()?;
+
+    let graph = service(ctx).load_graph(&ctx.db, sbom_id).await?;
+    assert_ne!(graph.edge_count(), 0);
+
+    // drop the relationships, so a re-constructed graph would be different
+    package_relates_to_package::Entity::delete_many()
+        .filter(package_relates_to_package::Column::SbomId.eq(sbom_id))
+        .exec(&ctx.db)
+        .await?;
+
+    ctx.db
+        .execute(Statement::from_sql_and_values(
+            ctx.db.get_database_backend(),
+            "UPDATE sbom SET deleted_at = now() WHERE sbom_id = $1",
+            [sbom_id.into()],
+        ))
+        .await?;
+
+    // the persisted graph of a deleted SBOM must not be used
+    let reconstructed = service(ctx).load_graph(&ctx.db, sbom_id).await?;
+    assert!(reconstructed.edge_count() < graph.edge_count());
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::{
+    Error,
+    config::AnalysisConfig,
+    model::PackageGraph,
+    service::{AnalysisService, snapshot::SNAPSHOT_VERSION},
+};
+use std::fs;
+use test_context::test_context;
+use test_log::test;
+use trustify_common::db::ReadOnly;
+use trustify_entity::relationship::Relationship;
+use trustify_test_context::TrustifyContext;
+
+fn edges(graph: &PackageGraph) -> Vec<(usize, usize, Relationship)> {
+    graph
+        .raw_edges()
+        .iter()
+        .map(|edge| (edge.source().index(), edge.target().index(), edge.weight))
+        .collect()
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn save_and_restore(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_documents(["spdx/simple.json", "cyclonedx/simple.json"])
+        .await?;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("graphs.snapshot");
+
+    let service = AnalysisService::new(AnalysisConfig::default(), ReadOnly::new(ctx.db.clone()));
+    let graphs = service.load_all_graphs(&ctx.db).await?;
+    assert_eq!(graphs.len(), 2);
+    service.save_snapshot(&path)?;
+
+    let restored = AnalysisService::new(AnalysisConfig::default(), ReadOnly::new(ctx.db.clone()));
+    assert_eq!(restored.cache_len(), 0);
+    restored.restore_snapshot(&path)?;
+    assert_eq!(restored.cache_len(), 2);
+
+    for (sbom_id, graph) in graphs {
+        let restored = restored
+            .inner
+            .graph_cache
+            .get(sbom_id)
+            .expect("graph must be restored");
+
+        assert_eq!(
+            graph
+                .raw_nodes()
+                .iter()
+                .map(|n| &n.weight)
+                .collect::<Vec<_>>(),
+            restored
+                .raw_nodes()
+                .iter()
+                .map(|n| &n.weight)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(edges(&graph), edges(&restored));
+    }
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn restore_incompatible(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("graphs.snapshot");
+
+    let service = AnalysisService::new(AnalysisConfig::default(), ReadOnly::new(ctx.db.clone()));
+
+    // a newer version of the format
+    let mut data = b"TRUSTGRF".to_vec();
+    data.extend_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+    fs::write(&path, data)?;
+
+    let err = service.restore_snapshot(&path).unwrap_err();
+    assert!(
+        matches!(&err, Error::Snapshot(msg) if msg.contains("unsupported snapshot version")),
+        "unexpected error: {err}"
+    );
+
+    // not a snapshot at all
+    fs::write(&path, b"{}")?;
+
+    let err = service.restore_snapshot(&path).unwrap_err();
+    assert!(
+        matches!(&err, Error::Snapshot(msg) if msg == "not a graph snapshot"),
+        "unexpected error: {err}"
+    );
+
+    Ok(())
+}
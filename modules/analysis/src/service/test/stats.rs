@@ -0,0 +1,67 @@
+use crate::model::{
+    PackageGraph,
+    graph::{BaseNode, Node, graph_stats},
+};
+use time::OffsetDateTime;
+use trustify_entity::relationship::Relationship;
+use uuid::Uuid;
+
+fn node(node_id: &str) -> Node {
+    Node::Unknown(BaseNode {
+        sbom_id: Uuid::nil(),
+        node_id: node_id.to_string(),
+        published: OffsetDateTime::UNIX_EPOCH,
+        name: node_id.to_string(),
+        document_id: None,
+        product_name: None,
+        product_version: None,
+    })
+}
+
+#[test]
+fn empty() {
+    let stats = graph_stats(&PackageGraph::new());
+    assert_eq!(stats.node_count, 0);
+    assert_eq!(stats.edge_count, 0);
+    assert_eq!(stats.max_depth, 0);
+    assert_eq!(stats.isolated_nodes, 0);
+}
+
+#[test]
+fn depth_and_relationships() {
+    let mut graph = PackageGraph::new();
+    let doc = graph.add_node(node("doc"));
+    let a = graph.add_node(node("a"));
+    let b = graph.add_node(node("b"));
+    let c = graph.add_node(node("c"));
+    graph.add_node(node("isolated"));
+    graph.add_edge(doc, a, Relationship::Describes);
+    graph.add_edge(a, b, Relationship::Contains);
+    graph.add_edge(b, c, Relationship::Contains);
+    // a shortcut must not reduce the depth
+    graph.add_edge(a, c, Relationship::Dependency);
+
+    let stats = graph_stats(&graph);
+    assert_eq!(stats.node_count, 5);
+    assert_eq!(stats.edge_count, 4);
+    assert_eq!(stats.max_depth, 4);
+    assert_eq!(stats.isolated_nodes, 1);
+    assert_eq!(stats.relationship_counts.len(), 3);
+    assert_eq!(stats.relationship_counts[&Relationship::Contains], 2);
+    assert_eq!(stats.relationship_counts[&Relationship::Describes], 1);
+    assert_eq!(stats.relationship_counts[&Relationship::Dependency], 1);
+}
+
+#[test]
+fn cycle() {
+    let mut graph = PackageGraph::new();
+    let doc = graph.add_node(node("doc"));
+    let a = graph.add_node(node("a"));
+    let b = graph.add_node(node("b"));
+    graph.add_edge(doc, a, Relationship::Describes);
+    graph.add_edge(a, b, Relationship::Contains);
+    graph.add_edge(b, a, Relationship::Contains);
+
+    // the cycle is only followed once
+    assert_eq!(graph_stats(&graph).max_depth, 3);
+}
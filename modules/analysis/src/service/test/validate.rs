@@ -0,0 +1,104 @@
+use crate::model::{
+    PackageGraph,
+    graph::{BaseNode, IssueCode, IssueSeverity, Node, validate_graph},
+};
+use time::OffsetDateTime;
+use trustify_entity::relationship::Relationship;
+use uuid::Uuid;
+
+fn node(node_id: &str) -> Node {
+    Node::Unknown(BaseNode {
+        sbom_id: Uuid::nil(),
+        node_id: node_id.to_string(),
+        published: OffsetDateTime::UNIX_EPOCH,
+        name: node_id.to_string(),
+        document_id: None,
+        product_name: None,
+        product_version: None,
+    })
+}
+
+#[test]
+fn valid() {
+    let mut graph = PackageGraph::new();
+    let doc = graph.add_node(node("doc"));
+    let a = graph.add_node(node("a"));
+    let b = graph.add_node(node("b"));
+    graph.add_edge(doc, a, Relationship::Describes);
+    graph.add_edge(a, b, Relationship::Contains);
+
+    assert!(validate_graph(&graph).is_empty());
+}
+
+#[test]
+fn duplicate_node_id() {
+    let mut graph = PackageGraph::new();
+    graph.add_node(node("a"));
+    graph.add_node(node("a"));
+    graph.add_node(node("b"));
+
+    let issues = validate_graph(&graph);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, IssueSeverity::Error);
+    assert_eq!(issues[0].code, IssueCode::DuplicateNodeId);
+    assert_eq!(issues[0].node_ids, vec!["a"]);
+}
+
+#[test]
+fn unreachable_node() {
+    let mut graph = PackageGraph::new();
+    let doc = graph.add_node(node("doc"));
+    let a = graph.add_node(node("a"));
+    let b = graph.add_node(node("b"));
+    let c = graph.add_node(node("c"));
+    graph.add_edge(doc, a, Relationship::Describes);
+    // the edge points the wrong way, b can't be reached
+    graph.add_edge(b, a, Relationship::Contains);
+    graph.add_edge(b, c, Relationship::Contains);
+
+    let issues = validate_graph(&graph);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, IssueSeverity::Warning);
+    assert_eq!(issues[0].code, IssueCode::UnreachableNode);
+    assert_eq!(issues[0].node_ids, vec!["b", "c"]);
+}
+
+#[test]
+fn self_loop() {
+    let mut graph = PackageGraph::new();
+    let doc = graph.add_node(node("doc"));
+    let a = graph.add_node(node("a"));
+    graph.add_edge(doc, a, Relationship::Describes);
+    graph.add_edge(a, a, Relationship::Dependency);
+
+    let issues = validate_graph(&graph);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, IssueSeverity::Warning);
+    assert_eq!(issues[0].code, IssueCode::SelfLoop);
+    assert_eq!(issues[0].node_ids, vec!["a"]);
+}
+
+#[test]
+fn cycle() {
+    let mut graph = PackageGraph::new();
+    let doc = graph.add_node(node("doc"));
+    let a = graph.add_node(node("a"));
+    let b = graph.add_node(node("b"));
+    let c = graph.add_node(node("c"));
+    let d = graph.add_node(node("d"));
+    graph.add_edge(doc, a, Relationship::Describes);
+    graph.add_edge(a, a, Relationship::Dependency);
+    graph.add_edge(a, b, Relationship::Dependency);
+    graph.add_edge(b, c, Relationship::Dependency);
+    graph.add_edge(c, a, Relationship::Dependency);
+    // a shortcut, making the shortest cycle skip c
+    graph.add_edge(b, a, Relationship::Dependency);
+    graph.add_edge(c, d, Relationship::Dependency);
+
+    let issues = validate_graph(&graph);
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].code, IssueCode::SelfLoop);
+    assert_eq!(issues[1].severity, IssueSeverity::Warning);
+    assert_eq!(issues[1].code, IssueCode::Cycle);
+    assert_eq!(issues[1].node_ids, vec!["a", "b"]);
+}
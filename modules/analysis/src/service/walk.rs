@@ -30,19 +30,40 @@ impl AnalysisService {
     where
         V: Visitor,
     {
-        for node in graph.node_weights() {
-            v.node(node);
+        visit(graph, &mut v);
+        Some(v.complete())
+    }
+
+    /// Walk the graphs of multiple SBOMs, as if they were a single graph.
+    ///
+    /// The visitor will be called for all nodes and edges of each graph, before the next graph
+    /// is visited.
+    pub fn walk_all<'a, V>(
+        &self,
+        graphs: impl IntoIterator<Item = &'a PackageGraph>,
+        mut v: V,
+    ) -> V::Output
+    where
+        V: Visitor,
+    {
+        for graph in graphs {
+            visit(graph, &mut v);
         }
+        v.complete()
+    }
+}
+
+fn visit<V: Visitor>(graph: &PackageGraph, v: &mut V) {
+    for node in graph.node_weights() {
+        v.node(node);
+    }
 
-        for edge in graph.raw_edges() {
-            let source = graph.node_weight(edge.source());
-            let target = graph.node_weight(edge.target());
+    for edge in graph.raw_edges() {
+        let source = graph.node_weight(edge.source());
+        let target = graph.node_weight(edge.target());
 
-            if let (Some(source), Some(target)) = (source, target) {
-                v.edge(source, edge.weight, target);
-            }
+        if let (Some(source), Some(target)) = (source, target) {
+            v.edge(source, edge.weight, target);
         }
-
-        Some(v.complete())
     }
 }
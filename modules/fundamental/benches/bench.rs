@@ -14,8 +14,12 @@ pub(crate) mod trustify_benches {
 
     use crate::common;
     use criterion::Criterion;
-    use trustify_entity::labels::Labels;
+    use sea_orm::{EntityTrait, QuerySelect};
+    use trustify_common::{db::pagination_cache::PaginationCache, purl::Purl};
+    use trustify_entity::{labels::Labels, qualified_purl};
+    use trustify_module_fundamental::vulnerability::service::VulnerabilityService;
     use trustify_module_ingestor::service::{Cache, Format};
+    use trustify_test_context::Dataset;
 
     pub fn ingestion(c: &mut Criterion) {
         let (runtime, ctx) = common::setup_runtime_and_ctx();
@@ -59,6 +63,39 @@ pub(crate) mod trustify_benches {
             });
         });
     }
+
+    /// Analyze a realistic number of PURLs in a single request
+    pub fn analyze_purls(c: &mut Criterion) {
+        let (runtime, ctx) = common::setup_runtime_and_ctx();
+
+        let purls = runtime.block_on(async {
+            ctx.ingest_dataset(Dataset::DS3).await.expect("ingest ok");
+
+            qualified_purl::Entity::find()
+                .limit(500)
+                .all(&ctx.db)
+                .await
+                .expect("query ok")
+                .into_iter()
+                .map(|purl| Purl::from(purl.purl).to_string())
+                .collect::<Vec<_>>()
+        });
+
+        let service = VulnerabilityService::new(PaginationCache::for_test());
+
+        let (service, purls, db) = (&service, &purls, &ctx.db);
+
+        c.bench_function("analyze_purls", |b| {
+            b.to_async(&runtime).iter(move || async move {
+                black_box(
+                    service
+                        .analyze_purls_v3(purls, db)
+                        .await
+                        .expect("analyze ok"),
+                )
+            });
+        });
+    }
 }
 
 criterion_group! {
@@ -71,4 +108,11 @@ criterion_group! {
     .sample_size(10);
   targets = trustify_benches::ingestion
 }
-criterion_main!(benches);
+criterion_group! {
+  name = analysis;
+  config = Criterion::default()
+    .measurement_time(Duration::from_secs(15))
+    .sample_size(10);
+  targets = trustify_benches::analyze_purls
+}
+criterion_main!(benches, analysis);
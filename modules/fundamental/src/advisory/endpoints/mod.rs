@@ -11,10 +11,11 @@ use crate::{
     },
     common::service::delete_doc,
     endpoints::Deprecation,
+    license::get_sanitize_filename,
 };
 use actix_web::{HttpResponse, Responder, delete, get, http::header, post, web};
 use config::Config;
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt, stream};
 use sea_orm::TransactionTrait;
 use std::str::FromStr;
 use time::OffsetDateTime;
@@ -72,6 +73,10 @@ struct AdvisoryQuery {
     title: Option<String>,
     ingested: OffsetDateTime,
     label: String,
+    published_after: OffsetDateTime,
+    published_before: OffsetDateTime,
+    modified_after: OffsetDateTime,
+    modified_before: OffsetDateTime,
 }
 
 #[utoipa::path(
@@ -246,6 +251,8 @@ pub async fn upload(
 )]
 #[get("/v3/advisory/{key}/download")]
 /// Download an advisory document
+///
+/// The document is returned in its original format, as an attachment named after the advisory.
 pub async fn download(
     db: web::Data<db::ReadOnly>,
     ingestor: web::Data<IngestorService>,
@@ -257,19 +264,46 @@ pub async fn download(
     let tx = db.begin().await?;
 
     // look up document by id
-    let Some(advisory) = advisory.fetch_advisory(id, &tx).await? else {
+    let Some(details) = advisory.fetch_advisory(id, &tx).await? else {
         return Ok(HttpResponse::NotFound().finish());
     };
 
-    let stream = ingestor
+    let Some(stream) = ingestor
         .storage()
-        .retrieve(advisory.source_document.try_into()?)
+        .retrieve(details.source_document.try_into()?)
         .await
         .map_err(Error::Storage)?
-        .map(|stream| stream.map_err(Error::Storage));
+    else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+    let mut stream = Box::pin(stream.map_err(Error::Storage));
+
+    // OSV documents may be YAML, everything else we ingest is JSON. Only read as far as needed
+    // to tell them apart, and put back what was read in front of the rest of the stream.
+    let mut head = vec![];
+    let first = loop {
+        let Some(chunk) = stream.try_next().await? else {
+            break None;
+        };
+        let first = chunk.trim_ascii_start().first().copied();
+        head.push(chunk);
+        if first.is_some() {
+            break first;
+        }
+    };
+    let (content_type, extension) = match first {
+        Some(b'{' | b'[') | None => ("application/json", "json"),
+        Some(_) => ("application/x-yaml", "yaml"),
+    };
 
-    Ok(match stream {
-        Some(s) => HttpResponse::Ok().streaming(s),
-        None => HttpResponse::NotFound().finish(),
-    })
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .append_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}.{extension}\"",
+                get_sanitize_filename(details.head.document_id)
+            ),
+        ))
+        .streaming(stream::iter(head.into_iter().map(Ok)).chain(stream)))
 }
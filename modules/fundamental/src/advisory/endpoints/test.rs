@@ -3,20 +3,21 @@ use crate::{
     test::{caller, caller_with, label::Api},
 };
 use actix_http::StatusCode;
-use actix_web::{body::MessageBody, test::TestRequest};
+use actix_web::{body::MessageBody, http::header, test::TestRequest};
 use hex::ToHex;
 use jsonpath_rust::JsonPath;
+use sea_orm::EntityTrait;
 use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use std::time::Duration;
 use test_context::test_context;
 use test_log::test;
-use time::OffsetDateTime;
+use time::{OffsetDateTime, macros::datetime};
 use trustify_common::{
     db::pagination_cache::PaginationCache, error::ErrorInformation, hashing::Digests,
     model::PaginatedResults,
 };
-use trustify_entity::{advisory_vulnerability_score, labels::Labels};
+use trustify_entity::{advisory, advisory_vulnerability_score, labels::Labels, source_document};
 use trustify_module_ingestor::{
     graph::{advisory::AdvisoryInformation, cvss::ScoreCreator},
     model::IngestResult,
@@ -25,6 +26,7 @@ use trustify_module_ingestor::{
 use trustify_module_storage::service::{StorageBackend, StorageKey};
 use trustify_test_context::{TrustifyContext, call::CallService, document_bytes};
 use urlencoding::encode;
+use uuid::Uuid;
 
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
@@ -378,6 +380,87 @@ async fn search_advisories(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn search_advisories_by_date(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    for (id, published, modified) in [
+        (
+            "RHSA-1",
+            datetime!(2023-06-01 0:00 UTC),
+            datetime!(2024-02-01 0:00 UTC),
+        ),
+        (
+            "RHSA-2",
+            datetime!(2024-01-01 0:00 UTC),
+            datetime!(2024-01-01 0:00 UTC),
+        ),
+        (
+            "RHSA-3",
+            datetime!(2024-07-01 0:00 UTC),
+            datetime!(2024-09-01 0:00 UTC),
+        ),
+    ] {
+        ctx.graph
+            .ingest_advisory(
+                id,
+                ("source", "http://redhat.com/"),
+                &Digests::digest(id),
+                AdvisoryInformation {
+                    id: id.to_string(),
+                    title: Some(id.to_string()),
+                    version: None,
+                    issuer: None,
+                    published: Some(published),
+                    modified: Some(modified),
+                    withdrawn: None,
+                },
+                &ctx.db,
+            )
+            .await?;
+    }
+
+    let query = async |q: &str| {
+        let uri = format!(
+            "/api/v3/advisory?q={}&sort={}",
+            encode(q),
+            encode("identifier:asc")
+        );
+        let req = TestRequest::get().uri(&uri).to_request();
+        let response: PaginatedResults<AdvisorySummary> = app.call_and_read_body_json(req).await;
+        response
+            .items
+            .into_iter()
+            .map(|item| item.head.identifier)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        query("published_after=2024-01-01").await,
+        ["RHSA-2", "RHSA-3"]
+    );
+    assert_eq!(
+        query("published_before=2024-01-01").await,
+        ["RHSA-1", "RHSA-2"]
+    );
+    assert_eq!(
+        query("published_after=2023-07-01&published_before=2024-06-30").await,
+        ["RHSA-2"]
+    );
+    assert_eq!(
+        query("modified_after=2024-02-01").await,
+        ["RHSA-1", "RHSA-3"]
+    );
+    assert_eq!(query("modified_before=2024-01-31").await, ["RHSA-2"]);
+    assert_eq!(
+        query("published_before=2024-01-01&modified_after=2024-01-15").await,
+        ["RHSA-1"]
+    );
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn rejected_cve(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -591,6 +674,67 @@ async fn download_advisory_by_id(ctx: &TrustifyContext) -> Result<(), anyhow::Er
     Ok(())
 }
 
+/// Download a document and check it is returned unchanged, with the expected headers
+async fn assert_download(
+    ctx: &TrustifyContext,
+    doc: &str,
+    content_type: &str,
+    filename: &str,
+) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let result = ctx.ingest_document(doc).await?;
+
+    let (_, source) = advisory::Entity::find_by_id(Uuid::parse_str(&result.id)?)
+        .find_also_related(source_document::Entity)
+        .one(&ctx.db)
+        .await?
+        .expect("must exist");
+    let source = source.expect("must have a source document");
+
+    let uri = format!("/api/v3/advisory/urn:uuid:{}/download", result.id);
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response = app.call_service(request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(
+        headers.get(header::CONTENT_TYPE).map(|v| v.as_bytes()),
+        Some(content_type.as_bytes())
+    );
+    assert_eq!(
+        headers
+            .get(header::CONTENT_DISPOSITION)
+            .map(|v| v.as_bytes()),
+        Some(format!("attachment; filename=\"{filename}\"").as_bytes())
+    );
+
+    let body = actix_web::test::read_body(response).await;
+    let digest: String = Sha256::digest(&body).encode_hex();
+    assert_eq!(digest, source.sha256);
+
+    Ok(())
+}
+
+/// Test downloading a CSAF document, along with its headers
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn download_advisory_csaf(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    assert_download(ctx, DOC, "application/json", "CVE-2023-33201.json").await
+}
+
+/// Test downloading an OSV document in YAML format, along with its headers
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn download_advisory_osv_yaml(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    assert_download(
+        ctx,
+        "osv/RSEC-2023-6.yaml",
+        "application/x-yaml",
+        "RSEC-2023-6.yaml",
+    )
+    .await
+}
+
 /// Test updating labels
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
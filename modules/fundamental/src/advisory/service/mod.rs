@@ -56,8 +56,13 @@ impl AdvisoryService {
                         source_document::Column::Ingested,
                         ColumnType::TimestampWithTimeZone,
                     )
-                    .translator(|f, op, v| match f.split_once(':') {
-                        Some(("label", key)) => Some(format!("labels:{key}{op}{v}")),
+                    .translator(|f, op, v| match (f.split_once(':'), f, op) {
+                        (Some(("label", key)), _, _) => Some(format!("labels:{key}{op}{v}")),
+                        // date range shortcuts, inclusive on both ends
+                        (None, "published_after", "=") => Some(format!("published>={v}")),
+                        (None, "published_before", "=") => Some(format!("published<={v}")),
+                        (None, "modified_after", "=") => Some(format!("modified>={v}")),
+                        (None, "modified_before", "=") => Some(format!("modified<={v}")),
                         _ => None,
                     }),
             )?
@@ -69,6 +74,7 @@ impl AdvisoryService {
         Ok(PaginatedResults {
             total,
             items: AdvisorySummary::from_entities(&items, connection).await?,
+            next_cursor: None,
         })
     }
 
@@ -1,8 +1,16 @@
 use crate::{Error, common::LicenseRefMapping, source_document::model::SourceDocument};
-use sea_orm::{ConnectionTrait, DbBackend, FromQueryResult, PaginatorTrait, Statement};
+use bytes::{Bytes, BytesMut};
+use futures_util::TryStreamExt;
+use hex::ToHex;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, PaginatorTrait,
+    QueryFilter, Statement,
+};
 use spdx_expression;
 use std::collections::BTreeMap;
 use tracing::instrument;
+use trustify_common::hashing::Digests;
+use trustify_entity::source_document;
 use trustify_module_storage::service::{StorageBackend, StorageKey, dispatch::DispatchBackend};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -73,6 +81,55 @@ pub async fn delete_doc(doc: &SourceDocument, storage: impl DocumentDelete) -> R
     storage.delete(key).await
 }
 
+/// Delete a stored document whose ingestion got rolled back.
+///
+/// Storing a document which was ingested before stores the same blob again. So the blob is only
+/// deleted if no source document refers to it.
+pub async fn delete_orphaned_doc(
+    bytes: &[u8],
+    storage: &DispatchBackend,
+    connection: &impl ConnectionTrait,
+) -> Result<(), Error> {
+    let sha256: String = Digests::digest(bytes).sha256.encode_hex();
+
+    let references = source_document::Entity::find()
+        .filter(source_document::Column::Sha256.eq(&sha256))
+        .count(connection)
+        .await?;
+
+    if references > 0 {
+        return Ok(());
+    }
+
+    storage
+        .delete(StorageKey::from_sha256(&sha256))
+        .await
+        .map_err(Error::Storage)
+}
+
+/// Retrieve the original raw doc from storage, by its SHA-256 digest.
+pub async fn retrieve_doc(sha256: &str, storage: &DispatchBackend) -> Result<Bytes, Error> {
+    let Some(stream) = storage
+        .retrieve(StorageKey::from_sha256(sha256))
+        .await
+        .map_err(Error::Storage)?
+    else {
+        return Err(Error::NotFound(format!(
+            "Source document {sha256} is missing from storage"
+        )));
+    };
+
+    let bytes = stream
+        .try_fold(BytesMut::new(), |mut acc, chunk| async move {
+            acc.extend_from_slice(&chunk);
+            Ok(acc)
+        })
+        .await
+        .map_err(Error::Storage)?;
+
+    Ok(bytes.freeze())
+}
+
 pub trait DocumentDelete {
     fn delete(&self, key: StorageKey) -> impl Future<Output = Result<(), Error>>;
 }
@@ -1,16 +1,28 @@
+use crate::{
+    license::model::policy::LicensePolicy, purl::service::federated::FederatedPurlService,
+    sbom::model::quality::QualityWeights,
+};
 use actix_web::web;
 use trustify_common::db::{self, pagination_cache::PaginationCache};
 use trustify_module_analysis::service::AnalysisService;
 use trustify_module_ingestor::graph::Graph;
-use trustify_module_ingestor::service::IngestorService;
+use trustify_module_ingestor::service::{IngestorService, sbom::spdx::SbomExternalRefResolver};
 use trustify_module_storage::service::dispatch::DispatchBackend;
 use utoipa::{IntoParams, ToSchema};
 
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Config {
     pub sbom_upload_limit: usize,
     pub advisory_upload_limit: usize,
     pub max_group_name_length: usize,
+    /// The weights used when scoring the quality of an SBOM
+    pub sbom_quality_weights: QualityWeights,
+    /// The license policy uploaded SBOMs must comply with, if any
+    pub sbom_license_policy: Option<LicensePolicy>,
+    /// Resolves the external documents referenced by uploaded SPDX SBOMs, if enabled
+    pub sbom_external_ref_resolver: Option<SbomExternalRefResolver>,
+    /// Looks up PURLs not known locally from external registries, if enabled
+    pub federated_purl_service: Option<FederatedPurlService>,
 }
 
 pub fn configure(
@@ -22,7 +34,10 @@ pub fn configure(
     analysis: AnalysisService,
     cache: PaginationCache,
 ) {
-    let ingestor_service = IngestorService::new(Graph::new(), storage, Some(analysis));
+    let mut ingestor_service = IngestorService::new(Graph::new(), storage, Some(analysis));
+    if let Some(resolver) = config.sbom_external_ref_resolver {
+        ingestor_service = ingestor_service.with_external_ref_resolver(resolver);
+    }
     svc.app_data(web::Data::new(ingestor_service));
 
     crate::advisory::endpoints::configure(
@@ -34,16 +49,25 @@ pub fn configure(
     );
     crate::license::endpoints::configure(svc, db_ro.clone());
     crate::organization::endpoints::configure(svc, db_ro.clone(), cache.clone());
-    crate::purl::endpoints::configure(svc, db_ro.clone(), cache.clone());
+    crate::purl::endpoints::configure(
+        svc,
+        db_rw.clone(),
+        db_ro.clone(),
+        config.federated_purl_service,
+        cache.clone(),
+    );
     crate::product::endpoints::configure(svc, db_rw.clone(), db_ro.clone(), cache.clone());
     crate::sbom::endpoints::configure(
         svc,
         db_rw.clone(),
         db_ro.clone(),
         config.sbom_upload_limit,
+        config.sbom_quality_weights,
+        config.sbom_license_policy,
         cache.clone(),
     );
-    crate::vulnerability::endpoints::configure(svc, db_ro.clone(), cache.clone());
+    crate::source_document::endpoints::configure(svc, db_rw.clone(), db_ro.clone(), cache.clone());
+    crate::vulnerability::endpoints::configure(svc, db_rw.clone(), db_ro.clone(), cache.clone());
     crate::weakness::endpoints::configure(svc, db_ro.clone(), cache.clone());
     crate::sbom_group::endpoints::configure(svc, db_rw, db_ro, config.max_group_name_length, cache);
 }
@@ -65,6 +65,10 @@ pub enum Error {
     RevisionNotFound,
     #[error("unavailable")]
     Unavailable,
+    #[error("the query took too long")]
+    Timeout,
+    #[error("external registry: {0}")]
+    Registry(#[from] reqwest::Error),
 }
 
 impl Error {
@@ -86,6 +90,8 @@ impl From<DbErr> for Error {
     fn from(value: DbErr) -> Self {
         if value.is_read_only() {
             Self::Unavailable
+        } else if value.is_query_canceled() {
+            Self::Timeout
         } else {
             Self::Database(value)
         }
@@ -107,6 +113,7 @@ impl From<DbError> for Error {
             DbError::Database(err) => Self::Database(err),
             DbError::Unavailable => Self::Unavailable,
             DbError::ReadOnly => Self::Internal(value.to_string()),
+            DbError::Timeout => Self::Timeout,
         }
     }
 }
@@ -159,6 +166,12 @@ impl ResponseError for Error {
             Self::Unavailable => {
                 HttpResponse::ServiceUnavailable().json(ErrorInformation::new("Unavailable", self))
             }
+            Self::Timeout => {
+                HttpResponse::GatewayTimeout().json(ErrorInformation::new("Timeout", self))
+            }
+            Self::Registry(err) => {
+                HttpResponse::BadGateway().json(ErrorInformation::new("Registry", err))
+            }
 
             // All other cases are internal system errors that are not expected to occur.
             // They are logged and a generic error response is returned to avoid leaking
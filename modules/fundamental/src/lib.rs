@@ -17,6 +17,6 @@ pub mod weakness;
 pub use endpoints::{Config, configure};
 pub use error::Error;
 
-mod sbom_group;
+pub mod sbom_group;
 #[cfg(test)]
 pub mod test;
@@ -2,6 +2,7 @@ use crate::{
     Error,
     license::{
         endpoints::spdx::{get_spdx_license, list_spdx_licenses},
+        model::{LicenseCompatibilityDetails, UnlicensedPackage},
         service::{LicenseService, LicenseText},
     },
 };
@@ -13,6 +14,8 @@ use trustify_common::{
 };
 use trustify_query::TrustifyQuery;
 use trustify_query_derive::Query;
+use utoipa::IntoParams;
+use uuid::Uuid;
 
 pub mod spdx;
 
@@ -24,7 +27,9 @@ pub fn configure(config: &mut utoipa_actix_web::service_config::ServiceConfig, d
         .app_data(web::Data::new(license_service))
         .service(list_spdx_licenses)
         .service(get_spdx_license)
-        .service(list_licenses);
+        .service(list_licenses)
+        .service(list_packages_without_license)
+        .service(check_compatibility);
 }
 
 #[allow(dead_code)]
@@ -56,5 +61,72 @@ pub async fn list_licenses(
     Ok(HttpResponse::Ok().json(service.licenses(search, paginated, &tx).await?))
 }
 
+#[derive(IntoParams, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+struct MissingLicenseParams {
+    /// Only consider packages of the SBOM with this ID
+    #[serde(default)]
+    sbom_id: Option<Uuid>,
+}
+
+/// List packages of SBOMs which declare no license
+#[utoipa::path(
+    operation_id = "listPackagesWithoutLicense",
+    tag = "license",
+    params(
+        MissingLicenseParams,
+        Query,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Packages without a license", body = PaginatedResults<UnlicensedPackage>),
+    ),
+)]
+#[get("/v3/license/missing")]
+pub async fn list_packages_without_license(
+    service: web::Data<LicenseService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(MissingLicenseParams { sbom_id }): web::Query<MissingLicenseParams>,
+    web::Query(search): web::Query<Query>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(
+        service
+            .packages_without_license(sbom_id, search, paginated, &tx)
+            .await?,
+    ))
+}
+
+#[derive(IntoParams, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+struct CompatibilityParams {
+    /// The SPDX identifier of the first license
+    a: String,
+    /// The SPDX identifier of the second license
+    b: String,
+}
+
+/// Check if two licenses can be combined in a single product
+#[utoipa::path(
+    operation_id = "checkLicenseCompatibility",
+    tag = "license",
+    params(
+        CompatibilityParams,
+    ),
+    responses(
+        (status = 200, description = "The compatibility of the licenses", body = LicenseCompatibilityDetails),
+    ),
+)]
+#[get("/v3/license/compatible")]
+pub async fn check_compatibility(
+    service: web::Data<LicenseService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(CompatibilityParams { a, b }): web::Query<CompatibilityParams>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(service.compatibility(&a, &b, &tx).await?))
+}
+
 #[cfg(test)]
 mod test;
@@ -1,4 +1,7 @@
-use crate::license::model::{SpdxLicenseDetails, SpdxLicenseSummary};
+use crate::license::model::{
+    LicenseCompatibility, LicenseCompatibilityDetails, SpdxLicenseDetails, SpdxLicenseSummary,
+    UnlicensedPackage,
+};
 use crate::license::service::LicenseText;
 use crate::test::caller;
 use actix_web::test::TestRequest;
@@ -6,6 +9,7 @@ use test_context::test_context;
 use test_log::test;
 use trustify_common::model::PaginatedResults;
 use trustify_test_context::{TrustifyContext, call::CallService};
+use uuid::Uuid;
 
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
@@ -415,3 +419,85 @@ async fn list_licenses_sorting(ctx: &TrustifyContext) -> Result<(), anyhow::Erro
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn list_packages_without_license(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    // all but the upstream source and one component declare a license
+    let cdx = ctx
+        .ingest_document("cyclonedx/openssl-3.0.7-18.el9_2.cdx_1.6.sbom.json")
+        .await?;
+    let other = ctx
+        .ingest_document("cyclonedx/openssl-3.0.7-18.el9_2.cdx_1.6_aliases.sbom.json")
+        .await?;
+    let (cdx_id, other_id): (Uuid, Uuid) = (cdx.id.parse()?, other.id.parse()?);
+
+    let uri = format!("/api/v3/license/missing?sbom_id={cdx_id}&total=true");
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response: PaginatedResults<UnlicensedPackage> = app.call_and_read_body_json(request).await;
+
+    assert_eq!(response.total, Some(2));
+    for package in &response.items {
+        assert_eq!(package.sbom_id, cdx_id);
+        assert_eq!(
+            package.document_id.as_deref(),
+            Some("urn:uuid:a4f16b62-fea9-42c1-8365-d72d3cef37d1/1")
+        );
+        assert_eq!(package.package.name, "openssl");
+        assert_eq!(package.package.version.as_deref(), Some("3.0.7"));
+        assert!(package.package.licenses.is_empty());
+    }
+    assert!(response.items.iter().any(
+        |package| package.package.id == "pkg:generic/openssl@3.0.7?package-id=d4557c28c5c2e817"
+    ));
+
+    // without an SBOM, the other SBOM's packages show up too
+
+    let uri = "/api/v3/license/missing?total=true";
+    let request = TestRequest::get().uri(uri).to_request();
+    let response: PaginatedResults<UnlicensedPackage> = app.call_and_read_body_json(request).await;
+
+    assert_eq!(response.total, Some(4));
+    assert_eq!(
+        response
+            .items
+            .iter()
+            .filter(|package| package.sbom_id == other_id)
+            .count(),
+        2
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn check_license_compatibility(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let check = async |a: &str, b: &str| -> LicenseCompatibilityDetails {
+        let uri = format!("/api/v3/license/compatible?a={a}&b={b}");
+        let request = TestRequest::get().uri(&uri).to_request();
+        app.call_and_read_body_json(request).await
+    };
+
+    let result = check("MIT", "GPL-2.0-only").await;
+    assert_eq!(result.compatibility, LicenseCompatibility::Compatible);
+
+    // the order of the licenses doesn't matter, neither does the case
+    let result = check("gpl-2.0-only", "apache-2.0").await;
+    assert_eq!(result.license_a, "GPL-2.0-only");
+    assert_eq!(result.license_b, "Apache-2.0");
+    assert_eq!(result.compatibility, LicenseCompatibility::Incompatible);
+    assert!(!result.explanation.is_empty());
+
+    let result = check("GPL-3.0-only", "GPL-3.0-only").await;
+    assert_eq!(result.compatibility, LicenseCompatibility::Compatible);
+
+    let result = check("MIT", "LicenseRef-foo").await;
+    assert_eq!(result.compatibility, LicenseCompatibility::Unknown);
+
+    Ok(())
+}
@@ -1,9 +1,17 @@
+pub mod policy;
 pub mod sbom_license;
 
-use crate::{Error, purl::model::VersionedPurlHead, sbom::model::SbomHead};
+use crate::{
+    Error,
+    purl::model::VersionedPurlHead,
+    sbom::model::{SbomHead, SbomPackage},
+};
 use serde::{Deserialize, Serialize};
 use spdx::License;
-use trustify_entity::license;
+use trustify_entity::{
+    license,
+    license_obligation::{self, CopyleftStrength},
+};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -34,6 +42,19 @@ impl LicenseSummary {
     }
 }
 
+/// A package of an SBOM which declares no license at all
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct UnlicensedPackage {
+    /// The ID of the SBOM the package belongs to
+    #[serde(with = "uuid::serde::urn")]
+    #[schema(value_type=String)]
+    pub sbom_id: Uuid,
+    /// The document ID of the SBOM the package belongs to
+    pub document_id: Option<String>,
+    #[serde(flatten)]
+    pub package: SbomPackage,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LicenseDetailsPurlSummary {
     pub purl: VersionedPurlHead,
@@ -69,3 +90,86 @@ pub struct SpdxLicenseDetails {
     pub summary: SpdxLicenseSummary,
     pub text: String,
 }
+
+/// The compatibility of two licenses, when combined in a single product
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseCompatibility {
+    /// The licenses can be combined
+    Compatible,
+    /// The licenses cannot be combined
+    Incompatible,
+    /// There is no information on the compatibility of the licenses
+    Unknown,
+}
+
+/// The result of checking the compatibility of two licenses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LicenseCompatibilityDetails {
+    /// The SPDX identifier of the first license
+    pub license_a: String,
+    /// The SPDX identifier of the second license
+    pub license_b: String,
+    pub compatibility: LicenseCompatibility,
+    /// A human-readable explanation of the result
+    pub explanation: String,
+}
+
+/// Two incompatible licenses, found together in an SBOM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LicenseConflict {
+    /// The SPDX identifier of the first license
+    pub license_a: String,
+    /// The SPDX identifier of the second license
+    pub license_b: String,
+    /// A human-readable explanation of the conflict
+    pub explanation: String,
+}
+
+/// The obligations triggered by distributing software under a license
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LicenseObligation {
+    /// The SPDX identifier of the license
+    pub license_id: String,
+    /// Copyright and license notices must be retained
+    pub requires_attribution: bool,
+    /// The source code must be made available
+    pub requires_source_disclosure: bool,
+    /// The license grants a license to the contributors' patents
+    pub patent_grant: bool,
+    /// The strength of the copyleft, if the license is a copyleft license
+    pub copyleft: Option<CopyleftStrength>,
+}
+
+impl From<license_obligation::Model> for LicenseObligation {
+    fn from(value: license_obligation::Model) -> Self {
+        Self {
+            license_id: value.license_id,
+            requires_attribution: value.requires_attribution,
+            requires_source_disclosure: value.requires_source_disclosure,
+            patent_grant: value.patent_grant,
+            copyleft: value.copyleft,
+        }
+    }
+}
+
+/// The license obligations of an SBOM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SbomLicenseObligations {
+    /// The strongest copyleft of all licenses, if any of them is a copyleft license
+    pub strongest_copyleft: Option<CopyleftStrength>,
+    /// The obligations of each license used by packages of the SBOM
+    pub obligations: Vec<LicenseObligation>,
+}
+
+impl From<Vec<LicenseObligation>> for SbomLicenseObligations {
+    fn from(obligations: Vec<LicenseObligation>) -> Self {
+        Self {
+            strongest_copyleft: obligations
+                .iter()
+                .filter_map(|obligation| obligation.copyleft)
+                .max(),
+            obligations,
+        }
+    }
+}
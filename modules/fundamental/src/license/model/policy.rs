@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A policy of the SPDX licenses SBOMs may use
+///
+/// License identifiers are compared ignoring their case.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct LicensePolicy {
+    /// Licenses which may be used. If not empty, all other licenses, which are not on the
+    /// warn list, are violations.
+    pub allow: Vec<String>,
+    /// Licenses which must not be used
+    pub deny: Vec<String>,
+    /// Licenses which may be used, but raise a warning
+    pub warn: Vec<String>,
+}
+
+impl LicensePolicy {
+    /// Check if the policy has any rules at all
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty() && self.warn.is_empty()
+    }
+
+    /// Evaluate the policy for a set of licenses
+    pub fn evaluate<'a>(&self, licenses: impl IntoIterator<Item = &'a str>) -> PolicyEvaluation {
+        let contains = |list: &[String], license: &str| {
+            list.iter().any(|entry| entry.eq_ignore_ascii_case(license))
+        };
+
+        let mut result = PolicyEvaluation::default();
+
+        for license in licenses {
+            let warn = contains(&self.warn, license);
+
+            if contains(&self.deny, license) {
+                result.violations.push(LicenseViolation {
+                    license_id: license.to_string(),
+                    reason: LicenseViolationReason::Denied,
+                });
+            } else if !self.allow.is_empty() && !warn && !contains(&self.allow, license) {
+                result.violations.push(LicenseViolation {
+                    license_id: license.to_string(),
+                    reason: LicenseViolationReason::NotAllowed,
+                });
+            } else if warn {
+                result.warnings.push(LicenseWarning {
+                    license_id: license.to_string(),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+/// The result of evaluating a [`LicensePolicy`] for an SBOM
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+pub struct PolicyEvaluation {
+    /// Licenses violating the policy
+    pub violations: Vec<LicenseViolation>,
+    /// Licenses which are permitted, but flagged by the policy
+    pub warnings: Vec<LicenseWarning>,
+}
+
+impl PolicyEvaluation {
+    /// Check if the policy got violated
+    pub fn is_violated(&self) -> bool {
+        !self.violations.is_empty()
+    }
+}
+
+/// A license violating the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LicenseViolation {
+    /// The SPDX identifier of the license
+    pub license_id: String,
+    pub reason: LicenseViolationReason,
+}
+
+/// The reason of a license violating the policy
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseViolationReason {
+    /// The license is on the deny list
+    Denied,
+    /// The license is not on the allow list
+    NotAllowed,
+}
+
+/// A license which is flagged by the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LicenseWarning {
+    /// The SPDX identifier of the license
+    pub license_id: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn list(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn deny_and_warn() {
+        let policy = LicensePolicy {
+            allow: vec![],
+            deny: list(&["AGPL-3.0-only"]),
+            warn: list(&["GPL-3.0-only"]),
+        };
+
+        let result = policy.evaluate(["MIT", "agpl-3.0-only", "GPL-3.0-only"]);
+
+        assert_eq!(
+            result.violations,
+            vec![LicenseViolation {
+                license_id: "agpl-3.0-only".into(),
+                reason: LicenseViolationReason::Denied,
+            }]
+        );
+        assert_eq!(
+            result.warnings,
+            vec![LicenseWarning {
+                license_id: "GPL-3.0-only".into(),
+            }]
+        );
+        assert!(result.is_violated());
+    }
+
+    #[test]
+    fn allow() {
+        let policy = LicensePolicy {
+            allow: list(&["MIT", "Apache-2.0"]),
+            deny: vec![],
+            warn: list(&["LGPL-2.1-only"]),
+        };
+
+        let result = policy.evaluate(["MIT", "LGPL-2.1-only", "GPL-2.0-only"]);
+
+        assert_eq!(
+            result.violations,
+            vec![LicenseViolation {
+                license_id: "GPL-2.0-only".into(),
+                reason: LicenseViolationReason::NotAllowed,
+            }]
+        );
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = policy.evaluate(["MIT", "Apache-2.0"]);
+        assert!(!result.is_violated());
+        assert!(result.warnings.is_empty());
+    }
+}
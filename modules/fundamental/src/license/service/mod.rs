@@ -5,21 +5,28 @@ use crate::{
         license_filtering::{LICENSE, license_text_coalesce},
     },
     license::model::{
-        SpdxLicenseDetails, SpdxLicenseSummary,
+        LicenseCompatibility, LicenseCompatibilityDetails, LicenseConflict, LicenseObligation,
+        SpdxLicenseDetails, SpdxLicenseSummary, UnlicensedPackage,
+        policy::{LicensePolicy, PolicyEvaluation},
         sbom_license::{
             ExtractedLicensingInfos, Purl, SbomNameId, SbomPackageLicense, SbomPackageLicenseBase,
         },
     },
+    sbom::{
+        model::SbomPackage,
+        service::sbom::{IntoPackage, PackageCatcher, join_purls_and_cpes},
+    },
 };
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult, QueryFilter,
-    QueryOrder, QuerySelect, QueryTrait, RelationTrait, Statement,
+    ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Statement,
 };
 use sea_query::{
     Asterisk, Condition, Expr, Func, JoinType, PostgresQueryBuilder, SimpleExpr, UnionType,
 };
 use serde::{Deserialize, Serialize};
 use spdx::License;
+use std::{collections::BTreeSet, num::NonZeroU64};
 use tracing::instrument;
 use trustify_common::{
     db::query::{Columns, Filtering, IntoColumns, Query, q},
@@ -27,10 +34,12 @@ use trustify_common::{
     model::{PaginatedResults, Pagination},
 };
 use trustify_entity::{
-    expanded_license, license, licensing_infos, qualified_purl, sbom, sbom_license_expanded,
-    sbom_node, sbom_node_cpe_ref, sbom_node_purl_ref, sbom_package, sbom_package_license,
+    expanded_license, license, license_compatibility, license_obligation, licensing_infos,
+    qualified_purl, sbom, sbom_license_expanded, sbom_node, sbom_node_cpe_ref, sbom_node_purl_ref,
+    sbom_package, sbom_package_license,
 };
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 pub mod license_export;
 
@@ -70,6 +79,7 @@ impl LicenseService {
     ) -> Result<LicenseExportResult, Error> {
         let name_version_group: Option<SbomNameId> = sbom::Entity::find()
             .try_filter(id.clone())?
+            .filter(sbom::Column::DeletedAt.is_null())
             .join(JoinType::Join, sbom::Relation::SbomNode.def())
             .select_only()
             .column_as(sbom::Column::DocumentId, "sbom_id")
@@ -80,6 +90,7 @@ impl LicenseService {
 
         let package_license: Vec<SbomPackageLicenseBase> = sbom::Entity::find()
             .try_filter(id.clone())?
+            .filter(sbom::Column::DeletedAt.is_null())
             .join(JoinType::LeftJoin, sbom::Relation::Packages.def())
             .join(JoinType::InnerJoin, sbom_package::Relation::Node.def())
             .join(
@@ -198,6 +209,7 @@ impl LicenseService {
             return Ok(PaginatedResults {
                 items: vec![],
                 total,
+                next_cursor: None,
             });
         }
 
@@ -207,11 +219,13 @@ impl LicenseService {
             Ok(PaginatedResults {
                 items: SpdxLicenseSummary::from_details(&matching[..paginated.limit() as usize]),
                 total,
+                next_cursor: None,
             })
         } else {
             Ok(PaginatedResults {
                 items: SpdxLicenseSummary::from_details(matching),
                 total,
+                next_cursor: None,
             })
         }
     }
@@ -257,6 +271,7 @@ impl LicenseService {
         let sbom = sbom::Entity::find()
             .join(JoinType::LeftJoin, sbom::Relation::SourceDocument.def())
             .try_filter(id)?
+            .filter(sbom::Column::DeletedAt.is_null())
             .one(connection)
             .await?;
 
@@ -413,6 +428,304 @@ impl LicenseService {
         .all(connection)
         .await?;
 
-        Ok(PaginatedResults { total, items })
+        Ok(PaginatedResults {
+            total,
+            items,
+            next_cursor: None,
+        })
+    }
+
+    /// Find packages which declare no license, optionally limited to a single SBOM
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn packages_without_license<C: ConnectionTrait>(
+        &self,
+        sbom_id: Option<Uuid>,
+        search: Query,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<UnlicensedPackage>, Error> {
+        #[derive(FromQueryResult)]
+        struct Row {
+            sbom_id: Uuid,
+            document_id: Option<String>,
+            #[sea_orm(nested)]
+            package: PackageCatcher,
+        }
+
+        let license_subquery = sea_query::Query::select()
+            .expr(Expr::val(1))
+            .from(sbom_package_license::Entity)
+            .and_where(
+                Expr::col((
+                    sbom_package_license::Entity,
+                    sbom_package_license::Column::SbomId,
+                ))
+                .equals((sbom_package::Entity, sbom_package::Column::SbomId)),
+            )
+            .and_where(
+                Expr::col((
+                    sbom_package_license::Entity,
+                    sbom_package_license::Column::NodeId,
+                ))
+                .equals((sbom_package::Entity, sbom_package::Column::NodeId)),
+            )
+            .to_owned();
+
+        let mut query = sbom_package::Entity::find()
+            .filter(Expr::exists(license_subquery).not())
+            .join(JoinType::Join, sbom_package::Relation::Node.def())
+            .join(JoinType::Join, sbom_package::Relation::Sbom.def())
+            .select_only()
+            .column(sbom_package::Column::SbomId)
+            .group_by(sbom_package::Column::SbomId)
+            .column(sbom::Column::DocumentId)
+            .group_by(sbom::Column::DocumentId)
+            .column_as(sbom_package::Column::NodeId, "id")
+            .group_by(sbom_package::Column::NodeId)
+            .column_as(sbom_node::Column::Name, "name")
+            .group_by(sbom_node::Column::Name)
+            .column_as(sbom_package::Column::Group, "group")
+            .group_by(sbom_package::Column::Group)
+            .column_as(sbom_package::Column::Version, "version")
+            .group_by(sbom_package::Column::Version)
+            // there are no licenses to aggregate
+            .column_as(Expr::cust("'[]'::json"), "licenses")
+            .join(JoinType::LeftJoin, sbom_node::Relation::Purl.def())
+            .join(JoinType::LeftJoin, sbom_node::Relation::Cpe.def());
+
+        if let Some(sbom_id) = sbom_id {
+            query = query.filter(sbom_package::Column::SbomId.eq(sbom_id));
+        }
+
+        let query = join_purls_and_cpes(query)
+            .filtering_with(
+                search,
+                sbom_package::Entity
+                    .columns()
+                    .add_columns(sbom_node::Entity)
+                    .add_columns(sbom::Entity),
+            )?
+            .order_by_asc(sbom::Column::DocumentId)
+            .order_by_asc(sbom_node::Column::Name)
+            .order_by_asc(sbom_package::Column::Version)
+            .order_by_asc(sbom_package::Column::NodeId);
+
+        let total = match paginated.total() {
+            true => Some(query.clone().count(connection).await?),
+            false => None,
+        };
+
+        let items = query
+            .offset(NonZeroU64::new(paginated.offset()).map(NonZeroU64::get))
+            .limit(NonZeroU64::new(paginated.limit()).map(NonZeroU64::get))
+            .into_model::<Row>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|row| UnlicensedPackage {
+                sbom_id: row.sbom_id,
+                document_id: row.document_id,
+                package: SbomPackage::from_row(row.package),
+            })
+            .collect();
+
+        Ok(PaginatedResults {
+            total,
+            items,
+            next_cursor: None,
+        })
     }
+
+    /// Check if two licenses can be combined in a single product
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn compatible<C: ConnectionTrait>(
+        &self,
+        license_a: &str,
+        license_b: &str,
+        connection: &C,
+    ) -> Result<LicenseCompatibility, Error> {
+        Ok(self
+            .compatibility(license_a, license_b, connection)
+            .await?
+            .compatibility)
+    }
+
+    /// Check if two licenses can be combined in a single product, explaining the result
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn compatibility<C: ConnectionTrait>(
+        &self,
+        license_a: &str,
+        license_b: &str,
+        connection: &C,
+    ) -> Result<LicenseCompatibilityDetails, Error> {
+        let license_a = canonical_spdx_id(license_a);
+        let license_b = canonical_spdx_id(license_b);
+
+        if license_a == license_b {
+            return Ok(LicenseCompatibilityDetails {
+                explanation: format!("{license_a} is always compatible with itself."),
+                license_a,
+                license_b,
+                compatibility: LicenseCompatibility::Compatible,
+            });
+        }
+
+        let entry = license_compatibility::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        license_compatibility::Column::LicenseA
+                            .eq(&license_a)
+                            .and(license_compatibility::Column::LicenseB.eq(&license_b)),
+                    )
+                    .add(
+                        license_compatibility::Column::LicenseA
+                            .eq(&license_b)
+                            .and(license_compatibility::Column::LicenseB.eq(&license_a)),
+                    ),
+            )
+            .one(connection)
+            .await?;
+
+        Ok(match entry {
+            Some(entry) => LicenseCompatibilityDetails {
+                license_a,
+                license_b,
+                compatibility: match entry.compatible {
+                    true => LicenseCompatibility::Compatible,
+                    false => LicenseCompatibility::Incompatible,
+                },
+                explanation: entry.explanation,
+            },
+            None => LicenseCompatibilityDetails {
+                explanation: format!(
+                    "There is no information on the compatibility of {license_a} and {license_b}."
+                ),
+                license_a,
+                license_b,
+                compatibility: LicenseCompatibility::Unknown,
+            },
+        })
+    }
+
+    /// Find pairs of incompatible licenses, used together by packages of an SBOM
+    ///
+    /// Returns `None` if the SBOM could not be found.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn license_conflicts<C: ConnectionTrait>(
+        &self,
+        id: Id,
+        connection: &C,
+    ) -> Result<Option<Vec<LicenseConflict>>, Error> {
+        let Some(sbom) = sbom::Entity::find()
+            .join(JoinType::LeftJoin, sbom::Relation::SourceDocument.def())
+            .try_filter(id)?
+            .filter(sbom::Column::DeletedAt.is_null())
+            .one(connection)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let licenses = spdx_licenses_of_sbom(sbom.sbom_id, connection).await?;
+
+        if licenses.len() < 2 {
+            return Ok(Some(vec![]));
+        }
+
+        let conflicts = license_compatibility::Entity::find()
+            .filter(license_compatibility::Column::Compatible.eq(false))
+            .filter(
+                license_compatibility::Column::LicenseA.is_in(licenses.iter().map(String::as_str)),
+            )
+            .filter(
+                license_compatibility::Column::LicenseB.is_in(licenses.iter().map(String::as_str)),
+            )
+            .order_by_asc(license_compatibility::Column::LicenseA)
+            .order_by_asc(license_compatibility::Column::LicenseB)
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|entry| LicenseConflict {
+                license_a: entry.license_a,
+                license_b: entry.license_b,
+                explanation: entry.explanation,
+            })
+            .collect();
+
+        Ok(Some(conflicts))
+    }
+
+    /// Get the obligations triggered by the licenses of the packages of an SBOM
+    ///
+    /// Licenses without known obligations are not part of the result.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn obligations_for_sbom<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        connection: &C,
+    ) -> Result<Vec<LicenseObligation>, Error> {
+        let licenses = spdx_licenses_of_sbom(sbom_id, connection).await?;
+
+        if licenses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(license_obligation::Entity::find()
+            .filter(
+                license_obligation::Column::LicenseId.is_in(licenses.iter().map(String::as_str)),
+            )
+            .order_by_asc(license_obligation::Column::LicenseId)
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(LicenseObligation::from)
+            .collect())
+    }
+
+    /// Evaluate a license policy for the licenses of the packages of an SBOM
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn evaluate_policy<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        policy: &LicensePolicy,
+        connection: &C,
+    ) -> Result<PolicyEvaluation, Error> {
+        let licenses = spdx_licenses_of_sbom(sbom_id, connection).await?;
+        Ok(policy.evaluate(licenses.iter().map(String::as_str)))
+    }
+}
+
+/// Collect the SPDX license identifiers used by packages of an SBOM
+async fn spdx_licenses_of_sbom<C: ConnectionTrait>(
+    sbom_id: Uuid,
+    connection: &C,
+) -> Result<BTreeSet<String>, Error> {
+    Ok(sbom_package_license::Entity::find()
+        .select_only()
+        .distinct()
+        .column(license::Column::SpdxLicenses)
+        .filter(sbom_package_license::Column::SbomId.eq(sbom_id))
+        .join(
+            JoinType::Join,
+            sbom_package_license::Relation::License.def(),
+        )
+        .into_tuple::<Option<Vec<String>>>()
+        .all(connection)
+        .await?
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect())
+}
+
+/// Get the canonical form of an SPDX license identifier, falling back to the value itself if it's
+/// not a known identifier.
+fn canonical_spdx_id(id: &str) -> String {
+    let id = id.trim();
+    spdx::identifiers::LICENSES
+        .iter()
+        .find(|License { name, .. }| name.eq_ignore_ascii_case(id))
+        .map(|License { name, .. }| name.to_string())
+        .unwrap_or_else(|| id.to_string())
 }
@@ -1,8 +1,12 @@
-use crate::license::service::LicenseService;
-use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use crate::{license::service::LicenseService, sbom::service::SbomService};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, TransactionTrait};
 use test_context::test_context;
 use test_log::test;
-use trustify_common::{db::query::Query, id::Id, model::Paginated};
+use trustify_common::{
+    db::{pagination_cache::PaginationCache, query::Query},
+    id::Id,
+    model::Paginated,
+};
 use trustify_entity::{expanded_license, sbom_license_expanded};
 use trustify_test_context::TrustifyContext;
 use uuid::Uuid;
@@ -554,3 +558,39 @@ async fn test_preloaded_licenses_visible(ctx: &TrustifyContext) -> Result<(), an
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn deleted_sbom_licenses(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let service = LicenseService::new();
+
+    let result = ctx
+        .ingest_document("spdx/OCP-TOOLS-4.11-RHEL-8.json")
+        .await?;
+    let sbom_id = Uuid::parse_str(&result.id)?;
+
+    let tx = ctx.db.begin().await?;
+    SbomService::new(PaginationCache::for_test())
+        .soft_delete_sboms(vec![sbom_id], &tx)
+        .await?;
+    tx.commit().await?;
+
+    let export = service.license_export(Id::Uuid(sbom_id), &ctx.db).await?;
+    assert!(export.sbom_name_group_version.is_none());
+    assert!(export.sbom_package_license.is_empty());
+
+    assert!(
+        service
+            .get_all_license_info(Id::Uuid(sbom_id), &ctx.db)
+            .await?
+            .is_none()
+    );
+    assert!(
+        service
+            .license_conflicts(Id::Uuid(sbom_id), &ctx.db)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
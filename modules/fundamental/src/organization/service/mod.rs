@@ -41,6 +41,7 @@ impl OrganizationService {
         Ok(PaginatedResults {
             total,
             items: OrganizationSummary::from_entities(&items),
+            next_cursor: None,
         })
     }
     pub async fn fetch_organization<C: ConnectionTrait>(
@@ -4,7 +4,7 @@ mod test;
 use crate::{
     Error,
     product::{
-        model::{details::ProductDetails, summary::ProductSummary},
+        model::{details::ProductDetails, diff::ProductVersionDiff, summary::ProductSummary},
         service::ProductService,
     },
 };
@@ -15,6 +15,7 @@ use trustify_common::{
     db::{self, pagination_cache::PaginationCache, query::Query},
     model::{Paginated, PaginatedResults},
 };
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 pub fn configure(
@@ -29,6 +30,7 @@ pub fn configure(
         .app_data(web::Data::new(db_ro))
         .app_data(web::Data::new(service))
         .service(all)
+        .service(compare)
         .service(delete)
         .service(get);
 }
@@ -56,6 +58,42 @@ pub async fn all(
     Ok(HttpResponse::Ok().json(state.fetch_products(search, paginated, &tx).await?))
 }
 
+#[derive(IntoParams, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+struct CompareParams {
+    /// ID of the product version to compare from
+    left_version_id: Uuid,
+    /// ID of the product version to compare to
+    right_version_id: Uuid,
+}
+
+#[utoipa::path(
+    tag = "product",
+    operation_id = "compareProductVersions",
+    params(
+        CompareParams,
+    ),
+    responses(
+        (status = 200, description = "The differences between the SBOMs of the two product versions", body = ProductVersionDiff),
+        (status = 404, description = "A product version or its SBOM could not be found"),
+    ),
+)]
+#[get("/v3/product/compare")]
+pub async fn compare(
+    state: web::Data<ProductService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(CompareParams {
+        left_version_id,
+        right_version_id,
+    }): web::Query<CompareParams>,
+    _: Require<ReadMetadata>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let diff = state
+        .compare_versions(left_version_id, right_version_id, &tx)
+        .await?;
+    Ok(HttpResponse::Ok().json(diff))
+}
+
 #[utoipa::path(
     tag = "product",
     operation_id = "getProduct",
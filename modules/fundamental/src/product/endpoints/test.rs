@@ -2,14 +2,17 @@ use crate::test::caller;
 use actix_http::StatusCode;
 use actix_web::{body::MessageBody, test::TestRequest};
 use jsonpath_rust::JsonPath;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde_json::{Value, json};
 use test_context::test_context;
 use test_log::test;
 use trustify_common::db::pagination_cache::PaginationCache;
 use trustify_common::db::query::Query;
 use trustify_common::model::Paginated;
+use trustify_entity::product_version;
 use trustify_module_ingestor::graph::product::ProductInformation;
 use trustify_test_context::{TrustifyContext, call::CallService};
+use uuid::Uuid;
 
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
@@ -167,3 +170,84 @@ async fn delete_product(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn compare_product_versions(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let mut versions = vec![];
+    for file in [
+        "spdx/quarkus-bom-3.2.11.Final-redhat-00001.json",
+        "spdx/quarkus-bom-3.2.12.Final-redhat-00002.json",
+    ] {
+        let sbom_id = Uuid::parse_str(&ctx.ingest_document(file).await?.id)?;
+        let version = product_version::Entity::find()
+            .filter(product_version::Column::SbomId.eq(sbom_id))
+            .one(&ctx.db)
+            .await?
+            .expect("product version must be created from the SBOM");
+        versions.push(version.id);
+    }
+
+    let uri = format!(
+        "/api/v3/product/compare?left_version_id={}&right_version_id={}",
+        versions[0], versions[1]
+    );
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+
+    let added = response.query("$.added_packages[*].name")?;
+    assert_eq!(
+        added,
+        [
+            &json!("bcpkix-jdk18on"),
+            &json!("bcprov-jdk18on"),
+            &json!("bcutil-jdk18on")
+        ]
+    );
+    let removed = response.query("$.removed_packages[*].name")?;
+    assert_eq!(
+        removed,
+        [
+            &json!("bcpkix-jdk15on"),
+            &json!("bcprov-jdk15on"),
+            &json!("bcutil-jdk15on")
+        ]
+    );
+
+    let changes = response["version_changes"]
+        .as_array()
+        .expect("must be an array");
+    assert!(changes.contains(&json!({
+        "purl": "pkg:maven/io.netty/netty-codec",
+        "from": ["4.1.100.Final-redhat-00001"],
+        "to": ["4.1.108.Final-redhat-00001"],
+    })));
+    // spymemcached didn't change between the two releases
+    assert!(
+        !changes
+            .iter()
+            .any(|change| change["purl"] == "pkg:maven/net.spy/spymemcached")
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn compare_unknown_product_versions(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let uri = format!(
+        "/api/v3/product/compare?left_version_id={}&right_version_id={}",
+        Uuid::new_v4(),
+        Uuid::new_v4()
+    );
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response = app.call_service(request).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::sbom::model::SbomPackage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+/// The difference between the SBOMs of two product versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProductVersionDiff {
+    /// Packages only present in the right product version
+    pub added_packages: Vec<SbomPackage>,
+    /// Packages only present in the left product version
+    pub removed_packages: Vec<SbomPackage>,
+    /// Packages present in both product versions, but with a different version
+    pub version_changes: Vec<PackageVersionChange>,
+}
+
+/// A package which changed its versions between two product versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({
+    "purl": "pkg:maven/io.netty/netty-codec",
+    "from": ["4.1.100.Final-redhat-00001"],
+    "to": ["4.1.108.Final-redhat-00001"]
+}))]
+pub struct PackageVersionChange {
+    /// The versionless PURL of the package, or its name if it has no PURL
+    pub purl: String,
+    /// The versions in the left product version
+    pub from: BTreeSet<String>,
+    /// The versions in the right product version
+    pub to: BTreeSet<String>,
+}
@@ -3,6 +3,7 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 
 pub mod details;
+pub mod diff;
 pub mod summary;
 
 use crate::Error;
@@ -1,17 +1,28 @@
 use super::model::summary::ProductSummary;
-use crate::{Error, product::model::details::ProductDetails};
+use crate::{
+    Error,
+    product::model::{
+        details::ProductDetails,
+        diff::{PackageVersionChange, ProductVersionDiff},
+    },
+    sbom::{model::SbomPackage, service::SbomService},
+};
 use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use std::collections::{BTreeMap, BTreeSet};
 use trustify_common::{
     db::{
         limiter::{LimitedResult, LimiterTrait},
         pagination_cache::PaginationCache,
         query::{Filtering, Query},
     },
-    model::{PaginatedResults, Pagination},
+    model::{Paginated, PaginatedResults, Pagination},
 };
-use trustify_entity::product;
+use trustify_entity::{product, product_version};
 use uuid::Uuid;
 
+/// The number of SBOM packages fetched per query when comparing product versions.
+const PACKAGE_PAGE_SIZE: u64 = 1000;
+
 pub struct ProductService {
     cache: PaginationCache,
 }
@@ -39,6 +50,7 @@ impl ProductService {
         Ok(PaginatedResults {
             total,
             items: ProductSummary::from_entities(&items, connection).await?,
+            next_cursor: None,
         })
     }
 
@@ -61,6 +73,98 @@ impl ProductService {
         }
     }
 
+    /// Compare the SBOMs of two product versions.
+    ///
+    /// Packages are matched by their versionless PURL, falling back to their name if they don't
+    /// have a PURL.
+    pub async fn compare_versions<C: ConnectionTrait + Sync + Send>(
+        &self,
+        left: Uuid,
+        right: Uuid,
+        connection: &C,
+    ) -> Result<ProductVersionDiff, Error> {
+        let left = self.version_packages(left, connection).await?;
+        let mut right = self.version_packages(right, connection).await?;
+
+        let mut removed_packages = vec![];
+        let mut version_changes = vec![];
+
+        for (key, packages) in left {
+            match right.remove(&key) {
+                None => removed_packages.extend(packages.packages),
+                Some(other) if other.versions != packages.versions => {
+                    version_changes.push(PackageVersionChange {
+                        purl: key,
+                        from: packages.versions,
+                        to: other.versions,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(ProductVersionDiff {
+            added_packages: right
+                .into_values()
+                .flat_map(|packages| packages.packages)
+                .collect(),
+            removed_packages,
+            version_changes,
+        })
+    }
+
+    /// Fetch all packages of the SBOM of a product version, grouped by the package's identity.
+    async fn version_packages<C: ConnectionTrait + Sync + Send>(
+        &self,
+        id: Uuid,
+        connection: &C,
+    ) -> Result<BTreeMap<String, PackageVersions>, Error> {
+        let version = product_version::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Product version {id} not found")))?;
+        let sbom_id = version
+            .sbom_id
+            .ok_or_else(|| Error::NotFound(format!("Product version {id} has no SBOM")))?;
+
+        let service = SbomService::new(self.cache.clone());
+        let limit = match self.cache.max_limit() {
+            0 => PACKAGE_PAGE_SIZE,
+            max => max.min(PACKAGE_PAGE_SIZE),
+        };
+
+        let mut result = BTreeMap::new();
+        let mut offset = 0;
+        loop {
+            let page = service
+                .fetch_sbom_packages(
+                    sbom_id,
+                    Query::default(),
+                    Paginated {
+                        offset,
+                        limit,
+                        total: false,
+                    },
+                    connection,
+                )
+                .await?;
+
+            let len = page.items.len() as u64;
+            for package in page.items {
+                let entry: &mut PackageVersions = result.entry(package.key()).or_default();
+                entry.versions.extend(package.version.clone());
+                entry.packages.push(package);
+            }
+
+            if len < limit {
+                break;
+            }
+            offset += len;
+        }
+
+        Ok(result)
+    }
+
     pub async fn delete_product<C: ConnectionTrait + Sync + Send>(
         &self,
         id: Uuid,
@@ -74,5 +178,12 @@ impl ProductService {
     }
 }
 
+/// The packages of a product version sharing the same identity, along with all their versions
+#[derive(Default)]
+struct PackageVersions {
+    packages: Vec<SbomPackage>,
+    versions: BTreeSet<String>,
+}
+
 #[cfg(test)]
 mod test;
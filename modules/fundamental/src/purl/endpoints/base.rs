@@ -1,7 +1,14 @@
 use crate::{
     Error,
     purl::{
-        model::{details::base_purl::BasePurlDetails, summary::base_purl::BasePurlSummary},
+        model::{
+            details::{
+                base_purl::{BasePurlDetails, VersionSort},
+                versioned_purl::VersionedPurlDetails,
+            },
+            summary::base_purl::BasePurlSummary,
+            version_constraint::{VersionConstraint, parse_version_scheme},
+        },
         service::PurlService,
     },
 };
@@ -16,11 +23,21 @@ use trustify_common::{
     purl::Purl,
 };
 
+#[derive(Clone, Debug, Default, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct BasePurlQuery {
+    /// The order of the versions
+    #[serde(default)]
+    #[param(inline)]
+    sort: VersionSort,
+}
+
 #[utoipa::path(
     operation_id = "getBasePurl",
     tag = "purl",
     params(
-        ("key" = String, Path, description = "opaque identifier for a base PURL, or a URL-encoded full pURL starting with `pkg:` (e.g. `pkg:golang/k8s.io%2Fapiserver`)")
+        ("key" = String, Path, description = "opaque identifier for a base PURL, or a URL-encoded full pURL starting with `pkg:` (e.g. `pkg:golang/k8s.io%2Fapiserver`)"),
+        BasePurlQuery,
     ),
     responses(
         (status = 200, description = "Details for the versionless base PURL", body = BasePurlDetails),
@@ -32,15 +49,16 @@ pub async fn get_base_purl(
     service: web::Data<PurlService>,
     db: web::Data<db::ReadOnly>,
     key: web::Path<String>,
+    web::Query(BasePurlQuery { sort }): web::Query<BasePurlQuery>,
     _: Require<ReadSbom>,
 ) -> Result<impl Responder, Error> {
     let tx = db.begin().await?;
     if key.starts_with("pkg:") {
         let purl = Purl::from_str(&key).map_err(|e| Error::IdKey(IdError::Purl(e)))?;
-        Ok(HttpResponse::Ok().json(service.base_purl_by_purl(&purl, &tx).await?))
+        Ok(HttpResponse::Ok().json(service.base_purl_by_purl(&purl, sort, &tx).await?))
     } else {
         let uuid = Uuid::from_str(&key).map_err(|e| Error::IdKey(IdError::InvalidUuid(e)))?;
-        Ok(HttpResponse::Ok().json(service.base_purl_by_uuid(&uuid, &tx).await?))
+        Ok(HttpResponse::Ok().json(service.base_purl_by_uuid(&uuid, sort, &tx).await?))
     }
 }
 
@@ -66,3 +84,48 @@ pub async fn all_base_purls(
     let tx = db.begin().await?;
     Ok(HttpResponse::Ok().json(service.base_purls(search, paginated, &tx).await?))
 }
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct VersionRangeQuery {
+    /// The version constraint, a comma separated list of comparisons, e.g. `>=2.14.0,<2.15.0`
+    range: String,
+    /// The version scheme to compare versions with, e.g. `semver`, `rpm` or `maven`. Derived
+    /// from the PURL type if not provided.
+    scheme: Option<String>,
+}
+
+#[utoipa::path(
+    operation_id = "listBasePurlVersionsInRange",
+    tag = "purl",
+    params(
+        ("uuid" = Uuid, Path, description = "opaque identifier for a base PURL"),
+        VersionRangeQuery,
+    ),
+    responses(
+        (status = 200, description = "The versions of the base PURL matching the version constraint", body = Vec<VersionedPurlDetails>),
+        (status = 400, description = "The version constraint or scheme is invalid"),
+        (status = 404, description = "The base PURL could not be found"),
+    ),
+)]
+#[get("/v3/purl/base/{uuid}/versions")]
+/// List the versions of a base versionless pURL matching a version constraint
+pub async fn base_purl_versions_in_range(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadOnly>,
+    uuid: web::Path<Uuid>,
+    web::Query(VersionRangeQuery { range, scheme }): web::Query<VersionRangeQuery>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let constraint = VersionConstraint::from_str(&range)?;
+    let scheme = scheme.as_deref().map(parse_version_scheme).transpose()?;
+
+    let tx = db.begin().await?;
+    match service
+        .versioned_purls_in_range(&uuid, constraint, scheme, &tx)
+        .await?
+    {
+        Some(versions) => Ok(HttpResponse::Ok().json(versions)),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
@@ -3,16 +3,20 @@ use crate::{
     endpoints::Deprecation,
     purl::{
         model::{
-            RecommendRequest, RecommendResponse, details::purl::PurlDetails,
-            summary::purl::PurlSummary,
+            GcReport, PurlBatchRequest, PurlBatchResponse, RecommendRequest, RecommendResponse,
+            details::purl::PurlDetails,
+            federated::FederatedPurlInfo,
+            stats::PurlStats,
+            summary::{purl::PurlSummary, r#type::TypeSummary},
         },
-        service::PurlService,
+        service::{PurlService, federated::FederatedPurlService},
     },
+    sbom::model::SbomSummary,
 };
 use actix_web::{HttpResponse, Responder, get, post, web};
-use sea_orm::prelude::Uuid;
+use sea_orm::{TransactionTrait, prelude::Uuid};
 use std::str::FromStr;
-use trustify_auth::{ReadAdvisory, ReadSbom, authorizer::Require};
+use trustify_auth::{DeleteSbom, ReadAdvisory, ReadSbom, authorizer::Require};
 use trustify_common::{
     db::{self, pagination_cache::PaginationCache, query::Query},
     id::IdError,
@@ -27,20 +31,55 @@ mod test;
 
 pub fn configure(
     config: &mut utoipa_actix_web::service_config::ServiceConfig,
-    db: db::ReadOnly,
+    db_rw: db::ReadWrite,
+    db_ro: db::ReadOnly,
+    federated: Option<FederatedPurlService>,
     cache: PaginationCache,
 ) {
     let purl_service = PurlService::new(cache);
 
+    if let Some(federated) = federated {
+        config.app_data(web::Data::new(federated));
+    }
+
     config
-        .app_data(web::Data::new(db))
+        .app_data(web::Data::new(db_rw))
+        .app_data(web::Data::new(db_ro))
         .app_data(web::Data::new(purl_service))
         .service(base::get_base_purl)
         .service(base::all_base_purls)
+        .service(base::base_purl_versions_in_range)
         .service(v2::recommend) // Must be before `get` to avoid {key} matching "recommend"
         .service(v3::recommend) // Must be before `get` to avoid {key} matching "recommend"
+        .service(batch)
+        .service(types) // Must be before `get` to avoid {key} matching "type"
+        .service(stats) // Must be before `get` to avoid {key} matching "stats"
         .service(all)
-        .service(get);
+        .service(get)
+        .service(sboms)
+        .service(gc_duplicate_purls)
+        .service(gc_purls);
+}
+
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StatsQuery {
+    /// The number of most referenced base PURLs to report
+    #[serde(default = "default_top")]
+    #[param(default = 10)]
+    top: u64,
+}
+
+const fn default_top() -> u64 {
+    10
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+struct FederatedQuery {
+    /// Look up PURLs not known locally from the external registry of their type, if enabled
+    #[serde(default)]
+    federated: bool,
 }
 
 #[utoipa::path(
@@ -48,31 +87,152 @@ pub fn configure(
     tag = "purl",
     params(
         Deprecation,
+        FederatedQuery,
         ("key" = String, Path, description = "opaque identifier for a fully-qualified PURL, or URL-encoded pURL itself")
     ),
     responses(
         (status = 200, description = "Details for the qualified PURL", body = PurlDetails),
+        (status = 203, description = "Metadata from the external registry, for a PURL not known locally", body = FederatedPurlInfo),
+        (status = 502, description = "The external registry could not be queried"),
     ),
 )]
 #[get("/v3/purl/{key}")]
 /// Retrieve details of a fully-qualified pURL
 pub async fn get(
     service: web::Data<PurlService>,
+    federated_service: Option<web::Data<FederatedPurlService>>,
     db: web::Data<db::ReadOnly>,
     key: web::Path<String>,
     web::Query(Deprecation { deprecated }): web::Query<Deprecation>,
+    web::Query(FederatedQuery { federated }): web::Query<FederatedQuery>,
     _: Require<ReadSbom>,
 ) -> Result<impl Responder, Error> {
-    let tx = db.begin().await?;
     if key.starts_with("pkg") {
         let purl = Purl::from_str(&key).map_err(Error::Purl)?;
-        Ok(HttpResponse::Ok().json(service.purl_by_purl(&purl, deprecated, &tx).await?))
+        let details = {
+            let tx = db.begin().await?;
+            service.purl_by_purl(&purl, deprecated, &tx).await?
+        };
+        // don't keep the transaction open while waiting for the external registry
+        if details.is_none()
+            && federated
+            && let Some(federated_service) = federated_service
+            && let Some(info) = federated_service.lookup(&purl).await?
+        {
+            return Ok(HttpResponse::NonAuthoritativeInformation().json(info));
+        }
+        Ok(HttpResponse::Ok().json(details))
     } else {
         let id = Uuid::from_str(&key).map_err(|e| Error::IdKey(IdError::InvalidUuid(e)))?;
+        let tx = db.begin().await?;
         Ok(HttpResponse::Ok().json(service.purl_by_uuid(&id, deprecated, &tx).await?))
     }
 }
 
+#[utoipa::path(
+    operation_id = "listPurlSboms",
+    tag = "purl",
+    params(
+        ("key" = String, Path, description = "opaque identifier for a fully-qualified PURL, or URL-encoded pURL itself"),
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "SBOMs containing the PURL", body = PaginatedResults<SbomSummary>),
+    ),
+)]
+#[get("/v3/purl/{key}/sboms")]
+/// List the SBOMs containing a fully-qualified pURL
+pub async fn sboms(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadOnly>,
+    key: web::Path<String>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let id = if key.starts_with("pkg") {
+        Purl::from_str(&key).map_err(Error::Purl)?.qualifier_uuid()
+    } else {
+        Uuid::from_str(&key).map_err(|e| Error::IdKey(IdError::InvalidUuid(e)))?
+    };
+
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(service.sboms_for_purl(id, paginated, &tx).await?))
+}
+
+#[utoipa::path(
+    operation_id = "listPurlTypes",
+    tag = "purl",
+    responses(
+        (status = 200, description = "All PURL types, with the number of PURLs of each type", body = Vec<TypeSummary>),
+    ),
+)]
+#[get("/v3/purl/type")]
+/// List the pURL types
+pub async fn types(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadOnly>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(service.purl_types(&tx).await?))
+}
+
+#[utoipa::path(
+    operation_id = "getPurlStats",
+    tag = "purl",
+    params(StatsQuery),
+    responses(
+        (status = 200, description = "Statistics about the PURLs", body = PurlStats),
+    ),
+)]
+#[get("/v3/purl/stats")]
+/// Get statistics about the pURLs
+pub async fn stats(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(StatsQuery { top }): web::Query<StatsQuery>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(service.purl_stats(top, &tx).await?))
+}
+
+/// The maximum number of PURLs which can be requested with a single batch request
+const MAX_BATCH_PURLS: usize = 100;
+
+#[utoipa::path(
+    operation_id = "getPurlBatch",
+    tag = "purl",
+    params(Deprecation),
+    request_body = PurlBatchRequest,
+    responses(
+        (status = 200, description = "Details for each of the requested PURLs", body = PurlBatchResponse),
+        (status = 400, description = "More than 100 PURLs were requested"),
+    ),
+)]
+#[post("/v3/purl/batch")]
+/// Retrieve details of a set of fully-qualified pURLs
+pub async fn batch(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(Deprecation { deprecated }): web::Query<Deprecation>,
+    request: web::Json<PurlBatchRequest>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    if request.purls.len() > MAX_BATCH_PURLS {
+        return Err(Error::BadRequest(
+            format!("At most {MAX_BATCH_PURLS} PURLs can be requested at once").into(),
+            None,
+        ));
+    }
+
+    let tx = db.begin().await?;
+    let results = service
+        .purls_by_purls(&request.purls, deprecated, &tx)
+        .await?;
+    Ok(HttpResponse::Ok().json(PurlBatchResponse { results }))
+}
+
 #[utoipa::path(
     operation_id = "listPurl",
     tag = "purl",
@@ -97,6 +257,26 @@ pub async fn all(
     Ok(HttpResponse::Ok().json(service.purls(search, paginated, &tx).await?))
 }
 
+#[utoipa::path(
+    operation_id = "gcDuplicatePurls",
+    tag = "purl",
+    responses(
+        (status = 200, description = "Number of removed duplicate qualified PURLs", body = u64, content_type = "application/json"),
+    ),
+)]
+#[post("/v3/admin/gc-duplicate-purls")]
+/// Merge duplicate fully-qualified pURLs into the oldest one
+pub async fn gc_duplicate_purls(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadWrite>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let deleted = service.gc_duplicate_purls(&tx).await?;
+    tx.commit().await?;
+    Ok(HttpResponse::Ok().json(deleted))
+}
+
 mod v2 {
     #![allow(deprecated)]
     use super::*;
@@ -152,3 +332,26 @@ mod v3 {
         Ok(HttpResponse::Ok().json(response))
     }
 }
+
+/// The number of deleted PURLs reported back by the garbage collection
+const GC_SAMPLE_SIZE: u64 = 100;
+
+#[utoipa::path(
+    operation_id = "gcPurls",
+    tag = "purl",
+    responses(
+        (status = 200, description = "The removed PURLs", body = GcReport),
+    ),
+)]
+#[post("/v3/admin/gc-purls")]
+/// Delete all pURLs neither referenced by an SBOM nor by an advisory
+pub async fn gc_purls(
+    service: web::Data<PurlService>,
+    db: web::Data<db::ReadWrite>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let report = service.gc_purls(GC_SAMPLE_SIZE, &tx).await?;
+    tx.commit().await?;
+    Ok(HttpResponse::Ok().json(report))
+}
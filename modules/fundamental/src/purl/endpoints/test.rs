@@ -1,10 +1,12 @@
 use crate::{
     purl::model::{
+        GcReport,
         details::base_purl::BasePurlDetails,
         summary::{base_purl::BasePurlSummary, purl::PurlSummary},
     },
     test::caller,
 };
+use actix_http::StatusCode;
 use actix_web::test::TestRequest;
 use rstest::rstest;
 use serde_json::{Value, json};
@@ -99,6 +101,177 @@ async fn base_purls(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Test sorting the versions of a base PURL, following the version scheme of its type
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::maven("pkg:maven/org.example/sort", &["1.10", "1.9", "1.2"], "version_asc", &["1.2", "1.9", "1.10"])]
+#[case::maven_desc("pkg:maven/org.example/sort", &["1.9", "1.10", "1.2"], "version_desc", &["1.10", "1.9", "1.2"])]
+#[case::maven_pre_release("pkg:maven/org.example/sort", &["1.0.0", "1.0.0-rc2", "1.0.0-rc1"], "version_asc", &["1.0.0-rc1", "1.0.0-rc2", "1.0.0"])]
+#[case::rpm("pkg:rpm/redhat/sort", &["1.10-1.el9", "1.9-12.el9", "1.9-2.el9"], "version_asc", &["1.9-2.el9", "1.9-12.el9", "1.10-1.el9"])]
+#[case::pypi("pkg:pypi/sort", &["1.10.0", "1.9.0", "1.10.0rc1"], "version_asc", &["1.9.0", "1.10.0rc1", "1.10.0"])]
+#[case::golang("pkg:golang/example.com/sort", &["v1.10.0", "v1.9.0"], "version_asc", &["v1.9.0", "v1.10.0"])]
+#[case::cargo("pkg:cargo/sort", &["1.10.0", "1.9.0", "1.10.0-alpha"], "version_asc", &["1.9.0", "1.10.0-alpha", "1.10.0"])]
+#[case::npm_desc("pkg:npm/sort", &["1.0.0-rc1", "1.10.0", "1.9.0"], "version_desc", &["1.10.0", "1.9.0", "1.0.0-rc1"])]
+#[case::generic("pkg:generic/sort", &["1.9", "1.10"], "version_asc", &["1.10", "1.9"])]
+#[test_log::test(actix_web::test)]
+async fn base_purl_version_sort(
+    ctx: &TrustifyContext,
+    #[case] base: &str,
+    #[case] versions: &[&str],
+    #[case] sort: &str,
+    #[case] expected: &[&str],
+) -> Result<(), anyhow::Error> {
+    for version in versions {
+        ctx.graph
+            .ingest_qualified_package(&Purl::from_str(&format!("{base}@{version}"))?, &ctx.db)
+            .await?;
+    }
+
+    let app = caller(ctx).await?;
+    let uri = format!("/api/v3/purl/base/{}?sort={sort}", encode(base));
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response: BasePurlDetails = app.call_and_read_body_json(request).await;
+
+    let versions: Vec<_> = response
+        .versions
+        .iter()
+        .map(|version| version.head.version.as_str())
+        .collect();
+    assert_eq!(versions, expected);
+
+    Ok(())
+}
+
+/// Test finding the versions of a base PURL matching a version constraint
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::maven(
+    "pkg:maven/org.apache.logging.log4j/log4j-core",
+    &["2.2", "2.13.0", "2.14.0", "2.14.1", "2.15.0"],
+    ">=2.14.0,<2.15.0",
+    None,
+    &["2.14.0", "2.14.1"]
+)]
+#[case::rpm(
+    "pkg:rpm/redhat/openssl",
+    &["3.0.7-1.el9", "3.0.7-2.el9", "3.0.10-1.el9"],
+    ">3.0.7-1.el9",
+    None,
+    &["3.0.10-1.el9", "3.0.7-2.el9"]
+)]
+#[case::exact(
+    "pkg:generic/range",
+    &["1.0", "1.0.0", "2.0"],
+    "1.0",
+    Some("generic"),
+    &["1.0"]
+)]
+#[case::scheme(
+    "pkg:generic/range",
+    &["1.2.0", "1.10.0", "2.0.0"],
+    "<2.0.0",
+    Some("semver"),
+    &["1.10.0", "1.2.0"]
+)]
+#[test_log::test(actix_web::test)]
+async fn base_purl_versions_in_range(
+    ctx: &TrustifyContext,
+    #[case] base: &str,
+    #[case] versions: &[&str],
+    #[case] range: &str,
+    #[case] scheme: Option<&str>,
+    #[case] expected: &[&str],
+) -> Result<(), anyhow::Error> {
+    for version in versions {
+        ctx.graph
+            .ingest_qualified_package(&Purl::from_str(&format!("{base}@{version}"))?, &ctx.db)
+            .await?;
+    }
+
+    let app = caller(ctx).await?;
+    let request = TestRequest::get()
+        .uri(&format!("/api/v3/purl/base/{}", encode(base)))
+        .to_request();
+    let base: BasePurlDetails = app.call_and_read_body_json(request).await;
+
+    let mut uri = format!(
+        "/api/v3/purl/base/{}/versions?range={}",
+        base.head.uuid,
+        encode(range)
+    );
+    if let Some(scheme) = scheme {
+        uri.push_str(&format!("&scheme={scheme}"));
+    }
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+
+    let versions: Vec<_> = response
+        .as_array()
+        .expect("must be an array")
+        .iter()
+        .map(|version| version["version"].as_str().expect("must be a string"))
+        .collect();
+    assert_eq!(versions, expected);
+
+    Ok(())
+}
+
+/// Test invalid version constraints and unknown base PURLs
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn base_purl_versions_in_range_invalid(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    setup(&ctx.db, &ctx.graph).await?;
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::get()
+        .uri(&format!(
+            "/api/v3/purl/base/{}",
+            encode("pkg:maven/org.apache/log4j")
+        ))
+        .to_request();
+    let base: BasePurlDetails = app.call_and_read_body_json(request).await;
+
+    for (query, status) in [
+        ("range=%3E", StatusCode::BAD_REQUEST),
+        ("range=1.0&scheme=unknown", StatusCode::BAD_REQUEST),
+        ("range=%3E%3D1.0", StatusCode::OK),
+    ] {
+        let uri = format!("/api/v3/purl/base/{}/versions?{query}", base.head.uuid);
+        let response = app
+            .call_service(TestRequest::get().uri(&uri).to_request())
+            .await;
+        assert_eq!(response.status(), status, "{query}");
+    }
+
+    let uri = format!("/api/v3/purl/base/{}/versions?range=1.0", Uuid::now_v7());
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+/// Test unknown PURLs are not looked up from external registries unless requested
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn unknown_purl_not_federated(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let uri = format!(
+        "/api/v3/purl/{}?federated=false",
+        encode("pkg:cargo/does-not-exist@1.0.0")
+    );
+    let request = TestRequest::get().uri(&uri).to_request();
+    let response = app.call_service(request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(&actix_web::test::read_body(response).await)?;
+    assert_eq!(body, Value::Null);
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn qualified_packages(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -114,6 +287,98 @@ async fn qualified_packages(ctx: &TrustifyContext) -> Result<(), anyhow::Error>
     Ok(())
 }
 
+/// Test resolving known, unknown and duplicate PURLs in one request
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn qualified_packages_batch(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    setup(&ctx.db, &ctx.graph).await?;
+    let app = caller(ctx).await?;
+
+    let jdk11 = "pkg:maven/org.apache/log4j@1.2.3?jdk=11";
+    let jdk17 = "pkg:maven/org.apache/log4j@1.2.3?jdk=17";
+    let unknown = "pkg:maven/org.apache/log4j@1.2.3?jdk=21";
+
+    let request = TestRequest::post()
+        .uri("/api/v3/purl/batch")
+        .set_json(json!({ "purls": [jdk11, jdk17, unknown, jdk11] }))
+        .to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+
+    let results = response["results"].as_object().expect("must be an object");
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[jdk11]["purl"], jdk11);
+    assert_eq!(results[jdk11]["version"]["version"], "1.2.3");
+    assert_eq!(results[jdk17]["purl"], jdk17);
+    assert_ne!(results[jdk11]["uuid"], results[jdk17]["uuid"]);
+    assert_eq!(results[unknown], Value::Null);
+
+    // too many PURLs at once
+    let purls: Vec<_> = (0..101)
+        .map(|n| format!("pkg:maven/org.apache/log4j@1.2.{n}"))
+        .collect();
+    let request = TestRequest::post()
+        .uri("/api/v3/purl/batch")
+        .set_json(json!({ "purls": purls }))
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// Test the counts per PURL type, and the most referenced base PURLs
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn purl_stats(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    // two SBOMs, sharing all packages but the top level one
+    ctx.ingest_document("spdx/simple.json").await?;
+    let (spdx, _) = trustify_test_context::document::<Value>("spdx/simple.json").await?;
+    let mut spdx = serde_json::to_string(&spdx)?
+        .replace("pkg:rpm/redhat/A@0.0.0?arch=src", "pkg:rpm/redhat/Z@0.0.0");
+    spdx = spdx.replace("uri:just-an-example", "uri:just-another-example");
+    ctx.ingest_json(serde_json::from_str::<Value>(&spdx)?)
+        .await?;
+    // and a package not part of any SBOM
+    ctx.graph
+        .ingest_qualified_package(
+            &Purl::from_str("pkg:maven/org.apache/log4j@1.2.3")?,
+            &ctx.db,
+        )
+        .await?;
+
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::get().uri("/api/v3/purl/type").to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    assert_eq!(
+        response,
+        json!([
+            { "name": "maven", "counts": { "base": 1, "version": 1, "package": 1 } },
+            { "name": "rpm", "counts": { "base": 8, "version": 8, "package": 8 } },
+        ])
+    );
+
+    let request = TestRequest::get()
+        .uri("/api/v3/purl/stats?top=2")
+        .to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    assert_eq!(
+        response["totals"],
+        json!({ "base": 9, "version": 9, "package": 9 })
+    );
+    assert_eq!(response["types"].as_array().map(Vec::len), Some(2));
+    assert!(response["most_referenced"].contains_subset(json!([
+        { "purl": "pkg:rpm/redhat/AA", "sboms": 2 },
+        { "purl": "pkg:rpm/redhat/B", "sboms": 2 },
+    ])));
+    assert_eq!(
+        response["most_referenced"].as_array().map(Vec::len),
+        Some(2)
+    );
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn qualified_packages_filtering(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -695,3 +960,173 @@ async fn get_recommendations_fixed_status(ctx: &TrustifyContext) -> Result<(), a
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn gc_duplicate_purls(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+    use trustify_entity::{qualified_purl, sbom_node_purl_ref};
+
+    ctx.ingest_document("zookeeper-3.9.2-cyclonedx.json")
+        .await?;
+    let app = caller(ctx).await?;
+
+    // pick a referenced qualified PURL and create a newer duplicate of it
+    let canonical = sbom_node_purl_ref::Entity::find()
+        .one(&ctx.db)
+        .await?
+        .expect("must have a reference")
+        .qualified_purl_id;
+    let duplicate = Uuid::now_v7();
+
+    ctx.db
+        .execute_unprepared(&format!(
+            r#"
+INSERT INTO qualified_purl (id, "timestamp", versioned_purl_id, qualifiers, purl)
+SELECT '{duplicate}', now() + interval '1 day', versioned_purl_id, qualifiers, purl
+FROM qualified_purl WHERE id = '{canonical}'
+"#
+        ))
+        .await?;
+    ctx.db
+        .execute_unprepared(&format!(
+            "UPDATE sbom_node_purl_ref SET qualified_purl_id = '{duplicate}' WHERE qualified_purl_id = '{canonical}'"
+        ))
+        .await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/gc-duplicate-purls")
+        .to_request();
+    let response: u64 = app.call_and_read_body_json(request).await;
+    assert_eq!(1, response);
+
+    // the duplicate is gone, and its references moved over to the canonical one
+    assert!(
+        qualified_purl::Entity::find_by_id(duplicate)
+            .one(&ctx.db)
+            .await?
+            .is_none()
+    );
+    let refs = sbom_node_purl_ref::Entity::find()
+        .filter(sbom_node_purl_ref::Column::QualifiedPurlId.eq(canonical))
+        .all(&ctx.db)
+        .await?;
+    assert!(!refs.is_empty());
+
+    // running it again finds nothing to do
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/gc-duplicate-purls")
+        .to_request();
+    let response: u64 = app.call_and_read_body_json(request).await;
+    assert_eq!(0, response);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn gc_purls(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_document("spdx/simple.json").await?;
+
+    // unreferenced PURLs: a whole package, and another qualifier of a referenced version
+    let orphan = "pkg:maven/org.example/orphan@1.0?type=jar";
+    let qualifier = "pkg:rpm/redhat/B@0.0.0?arch=noarch";
+    ctx.graph
+        .ingest_qualified_package(&Purl::from_str(orphan)?, &ctx.db)
+        .await?
+        .package_version
+        .package
+        .ingest_package_version(
+            &Purl::from_str("pkg:maven/org.example/orphan@2.0")?,
+            &ctx.db,
+        )
+        .await?;
+    ctx.graph
+        .ingest_qualified_package(&Purl::from_str(qualifier)?, &ctx.db)
+        .await?;
+
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/gc-purls")
+        .to_request();
+    let mut response: GcReport = app.call_and_read_body_json(request).await;
+    response.sample_deleted_purls.sort();
+    assert_eq!(
+        response,
+        GcReport {
+            base_purls_deleted: 1,
+            versioned_purls_deleted: 2,
+            qualified_purls_deleted: 2,
+            sample_deleted_purls: vec![orphan.to_string(), qualifier.to_string()],
+        }
+    );
+
+    // referenced PURLs are kept
+    let uri = format!("/api/v3/purl/{}", encode("pkg:rpm/redhat/B@0.0.0"));
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response["purl"], "pkg:rpm/redhat/B@0.0.0");
+
+    // running it again finds nothing to do
+    let request = TestRequest::post()
+        .uri("/api/v3/admin/gc-purls")
+        .to_request();
+    let response: GcReport = app.call_and_read_body_json(request).await;
+    assert_eq!(response, GcReport::default());
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn sboms_containing_purl(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    // both SBOMs depend on the same PURL
+    ctx.ingest_documents(["spdx/simple-ext-a.json", "spdx/simple-ext-b.json"])
+        .await?;
+
+    let app = caller(ctx).await?;
+    let purl = Purl::from_str("pkg:rpm/redhat/A@0.0.0?arch=src")?;
+
+    let names = async |key: String| {
+        let uri = format!("/api/v3/purl/{key}/sboms?total=true");
+        let response: Value = app
+            .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+            .await;
+        assert_eq!(response["total"], 2);
+
+        let mut names = response["items"]
+            .as_array()
+            .expect("must be an array")
+            .iter()
+            .map(|sbom| sbom["name"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    // by PURL, and by its ID
+
+    assert_eq!(
+        names(encode(&purl.to_string()).to_string()).await,
+        ["simple-a", "simple-b"]
+    );
+    assert_eq!(
+        names(purl.qualifier_uuid().to_string()).await,
+        ["simple-a", "simple-b"]
+    );
+
+    // an unknown PURL isn't contained by any SBOM
+
+    let uri = format!(
+        "/api/v3/purl/{}/sboms?total=true",
+        encode("pkg:rpm/redhat/B@9.9.9")
+    );
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response["total"], 0);
+
+    Ok(())
+}
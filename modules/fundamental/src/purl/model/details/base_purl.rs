@@ -13,12 +13,109 @@ pub struct BasePurlDetails {
     pub versions: Vec<VersionedPurlSummary>,
 }
 
+/// The order of the versions of a base PURL
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionSort {
+    /// Ascending, following the version scheme of the PURL type
+    VersionAsc,
+    /// Descending, following the version scheme of the PURL type
+    VersionDesc,
+    /// No particular order
+    #[default]
+    None,
+}
+
+impl VersionSort {
+    /// Sort the versions, computing the sort key of each version only once
+    fn apply(&self, r#type: &str, versions: &mut [versioned_purl::Model]) {
+        let reverse = match self {
+            Self::VersionAsc => false,
+            Self::VersionDesc => true,
+            Self::None => return,
+        };
+
+        versions.sort_by_cached_key(|version| {
+            (
+                VersionKey::new(r#type, &version.version),
+                version.version.clone(),
+            )
+        });
+
+        if reverse {
+            versions.reverse();
+        }
+    }
+}
+
+/// A key ordering versions following the version scheme of the PURL type
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum VersionKey {
+    /// Types without a known scheme are ordered by the version string
+    Plain,
+    /// Semantic versions, unparsable versions come first
+    Semver(Option<semver::Version>),
+    /// Segments of dotted versions with qualifiers, like Maven, PyPI and RPM use them
+    Segments(Vec<Segment>),
+}
+
+/// A segment of a version, a qualifier ranks below the end of a version, a number above
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    Qualifier(String),
+    End,
+    /// The number of digits and the digits, without leading zeros
+    Number(usize, String),
+}
+
+impl VersionKey {
+    fn new(r#type: &str, version: &str) -> Self {
+        match r#type {
+            "npm" | "cargo" | "golang" => Self::Semver(lenient_semver::parse(version).ok()),
+            "maven" | "pypi" | "rpm" => Self::Segments(segments(version)),
+            _ => Self::Plain,
+        }
+    }
+}
+
+/// Split a version into numbers and qualifiers, so that `1.9` < `1.10` and `1.0rc1` < `1.0`
+fn segments(version: &str) -> Vec<Segment> {
+    let mut result = Vec::new();
+    let mut chars = version.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !c.is_ascii_alphanumeric() {
+            continue;
+        }
+
+        let digit = c.is_ascii_digit();
+        let mut segment = String::from(c);
+        while let Some(next) =
+            chars.next_if(|next| next.is_ascii_alphanumeric() && next.is_ascii_digit() == digit)
+        {
+            segment.push(next);
+        }
+
+        if digit {
+            let digits = segment.trim_start_matches('0').to_string();
+            result.push(Segment::Number(digits.len(), digits));
+        } else {
+            result.push(Segment::Qualifier(segment.to_lowercase()));
+        }
+    }
+
+    result.push(Segment::End);
+    result
+}
+
 impl BasePurlDetails {
     pub async fn from_entity<C: ConnectionTrait>(
         package: &base_purl::Model,
+        sort: VersionSort,
         tx: &C,
     ) -> Result<Self, Error> {
-        let package_versions = package.find_related(versioned_purl::Entity).all(tx).await?;
+        let mut package_versions = package.find_related(versioned_purl::Entity).all(tx).await?;
+        sort.apply(&package.r#type, &mut package_versions);
 
         Ok(Self {
             head: BasePurlHead::from_entity(package),
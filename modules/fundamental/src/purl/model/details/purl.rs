@@ -12,32 +12,31 @@ use crate::{
     vulnerability::model::VulnerabilityHead,
 };
 use sea_orm::{
-    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, LoaderTrait,
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, IntoSimpleExpr,
     ModelTrait, QueryFilter, QueryOrder, QueryResult, QuerySelect, QueryTrait, RelationTrait,
     Select, SelectColumns,
 };
-use sea_query::{
-    Alias, Asterisk, ColumnRef, Expr, Func, IntoIden, JoinType, SimpleExpr, UnionType,
-};
+use sea_query::{Alias, Asterisk, Expr, Func, JoinType, SelectStatement, SimpleExpr, UnionType};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, hash_map::Entry};
+use std::collections::{HashMap, HashSet, hash_map::Entry};
 use trustify_common::{
-    db::VersionMatches,
-    db::multi_model::{FromQueryResultMultiModel, SelectIntoMultiModel},
-    memo::Memo,
-    purl::Purl,
+    db::VersionMatches, db::multi_model::FromQueryResultMultiModel, memo::Memo, purl::Purl,
 };
 use trustify_entity::{
     advisory, advisory_vulnerability_score, base_purl, cpe, license, organization, product,
     product_status, product_version, product_version_range, purl_status, qualified_purl, sbom,
     sbom_describing_cpe, sbom_license_expanded, sbom_node, sbom_node_purl_ref,
     sbom_package_license, status, version_range, versioned_purl, vulnerability,
+    vulnerability_description,
+};
+use trustify_module_ingestor::{
+    common::{Deprecation, DeprecationForExt},
+    service::advisory::user_override::is_user_override,
 };
-use trustify_module_ingestor::common::{Deprecation, DeprecationForExt};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct PurlDetails {
     #[serde(flatten)]
     pub head: PurlHead,
@@ -51,12 +50,12 @@ pub struct PurlDetails {
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, FromQueryResult)]
 pub struct PurlLicenseResult {
+    pub qualified_purl_id: Uuid,
     pub license_name: String,
     pub license_type: i32,
 }
 
 impl PurlDetails {
-    #[allow(deprecated)]
     pub async fn from_entity<C: ConnectionTrait>(
         package: Option<base_purl::Model>,
         package_version: Option<versioned_purl::Model>,
@@ -86,154 +85,248 @@ impl PurlDetails {
                 .ok_or(Error::Data("underlying package missing".to_string()))?
         };
 
-        let sbom_ids_for_purl = sbom_node_purl_ref::Entity::find()
-            .select_only()
-            .column(sbom_node_purl_ref::Column::SbomId)
-            .filter(sbom_node_purl_ref::Column::QualifiedPurlId.eq(qualified_package.id))
-            .into_query();
-
-        let mut allowed_cpe_ids = sbom_describing_cpe::Entity::find()
-            .select_only()
-            .column(sbom_describing_cpe::Column::CpeId)
-            .filter(sbom_describing_cpe::Column::SbomId.in_subquery(sbom_ids_for_purl.clone()))
-            .into_query();
-
-        let c = Alias::new("c");
-        let sc = Alias::new("sc");
-        let sdc = Alias::new("sdc");
-        let generalized_cpe_ids = sea_query::Query::select()
-            .expr(Expr::col((c.clone(), cpe::Column::Id)))
-            .from_as(cpe::Entity, c.clone())
-            .join_as(
-                JoinType::InnerJoin,
-                cpe::Entity,
-                sc.clone(),
-                Condition::all()
-                    .add(
-                        Expr::col((c.clone(), cpe::Column::Vendor))
-                            .equals((sc.clone(), cpe::Column::Vendor)),
-                    )
-                    .add(
-                        Expr::col((c.clone(), cpe::Column::Product))
-                            .equals((sc.clone(), cpe::Column::Product)),
-                    )
-                    .add(
-                        Expr::col((c.clone(), cpe::Column::Version)).eq(SimpleExpr::FunctionCall(
-                            Func::cust(Alias::new("split_part"))
-                                .arg(Expr::col((sc.clone(), cpe::Column::Version)))
-                                .arg(Expr::value("."))
-                                .arg(Expr::value(1i32)),
-                        )),
-                    ),
-            )
-            .join_as(
-                JoinType::InnerJoin,
-                sbom_describing_cpe::Entity,
-                sdc.clone(),
-                Expr::col((sdc.clone(), sbom_describing_cpe::Column::CpeId))
-                    .equals((sc.clone(), cpe::Column::Id)),
-            )
-            .and_where(
-                Expr::col((sdc.clone(), sbom_describing_cpe::Column::SbomId))
-                    .in_subquery(sbom_ids_for_purl.clone()),
-            )
-            .to_owned();
-        allowed_cpe_ids.union(UnionType::Distinct, generalized_cpe_ids);
-
-        let sbom_has_cpes = sea_query::Query::select()
-            .expr(Expr::value(1i32))
-            .from(sbom_describing_cpe::Entity)
-            .and_where(sbom_describing_cpe::Column::SbomId.in_subquery(sbom_ids_for_purl))
-            .to_owned();
-
-        let purl_statuses = purl_status::Entity::find()
-            .filter(purl_status::Column::BasePurlId.eq(package.id))
-            .left_join(version_range::Entity)
-            .left_join(base_purl::Entity)
-            .filter(SimpleExpr::FunctionCall(
-                Func::cust(VersionMatches)
-                    .arg(Expr::value(package_version.version.clone()))
-                    .arg(Expr::col((version_range::Entity, Asterisk))),
-            ))
-            .filter(
-                Condition::any()
-                    .add(purl_status::Column::ContextCpeId.is_null())
-                    .add(purl_status::Column::ContextCpeId.in_subquery(allowed_cpe_ids))
-                    .add(Expr::exists(sbom_has_cpes).not()),
-            )
-            .distinct_on([ColumnRef::TableColumn(
-                purl_status::Entity.into_iden(),
-                purl_status::Column::Id.into_iden(),
-            )])
-            .with_deprecation_related(deprecation)
-            .all(tx)
-            .await?;
+        Self::from_entities(
+            vec![(package, package_version, qualified_package.clone())],
+            deprecation,
+            tx,
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(Error::Data("missing details of package".to_string()))
+    }
+
+    /// Build the details of multiple qualified PURLs, along with their base and versioned PURLs
+    ///
+    /// The advisories and licenses of all PURLs are fetched together, so that the number of
+    /// queries doesn't depend on the number of PURLs. The result has the same order as the
+    /// provided entities.
+    #[allow(deprecated)]
+    pub async fn from_entities<C: ConnectionTrait>(
+        entities: Vec<(
+            base_purl::Model,
+            versioned_purl::Model,
+            qualified_purl::Model,
+        )>,
+        deprecation: Deprecation,
+        tx: &C,
+    ) -> Result<Vec<Self>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ids = entities
+            .iter()
+            .map(|(_, _, qualified_package)| qualified_package.id)
+            .collect::<Vec<_>>();
+
+        let mut purl_statuses = HashMap::<_, Vec<_>>::new();
+        for status in get_purl_statuses(&ids, deprecation, tx).await? {
+            purl_statuses
+                .entry(status.qualified_purl_id)
+                .or_default()
+                .push(status.purl_status);
+        }
+
+        let mut product_statuses = HashMap::<_, Vec<_>>::new();
+        for status in get_product_statuses(&ids, tx).await? {
+            product_statuses
+                .entry(status.qualified_purl_id)
+                .or_default()
+                .push(status);
+        }
+
+        let mut licenses = HashMap::<_, Vec<_>>::new();
+        for license in get_licenses(&ids, tx).await? {
+            licenses
+                .entry(license.qualified_purl_id)
+                .or_default()
+                .push(LicenseInfo::from(LicenseBasicInfo {
+                    license_name: license.license_name,
+                    license_type: license.license_type,
+                }));
+        }
 
-        let product_statuses = get_product_statuses_for_purl(
+        let references = StatusReferences::load(
+            purl_statuses.values().flatten(),
+            product_statuses.values().flatten(),
             tx,
-            qualified_package.id,
-            &package.name,
-            package.namespace.as_deref(),
-            &package_version.version,
         )
         .await?;
 
-        let licenses: Vec<LicenseInfo> = sbom_node_purl_ref::Entity::find()
-            .distinct()
-            .select_only()
-            .column_as(license_text_coalesce(), "license_name")
-            .select_column(sbom_package_license::Column::LicenseType)
-            .filter(sbom_node_purl_ref::Column::QualifiedPurlId.eq(qualified_package.id))
-            .join(JoinType::Join, sbom_node_purl_ref::Relation::Node.def())
-            .join(JoinType::Join, sbom_node::Relation::PackageLicense.def())
-            .join(
-                JoinType::LeftJoin,
-                sbom_package_license::Relation::SbomLicenseExpanded.def(),
-            )
-            .join(
-                JoinType::LeftJoin,
-                sbom_license_expanded::Relation::ExpandedLicense.def(),
-            )
-            .join(
-                JoinType::LeftJoin,
-                sbom_package_license::Relation::License.def(),
-            )
-            .into_model::<PurlLicenseResult>()
-            .all(tx)
-            .await?
-            .iter()
-            .map(|purl_license_result| {
-                LicenseInfo::from(LicenseBasicInfo {
-                    license_name: purl_license_result.license_name.clone(),
-                    license_type: purl_license_result.license_type,
-                })
-            })
-            .collect();
+        let mut result = Vec::with_capacity(entities.len());
+        for (package, package_version, qualified_package) in entities {
+            let id = qualified_package.id;
+            result.push(PurlDetails {
+                head: PurlHead::from_entity(&package, &package_version, &qualified_package),
+                version: VersionedPurlHead::from_entity(&package, &package_version),
+                base: BasePurlHead::from_entity(&package),
+                advisories: PurlAdvisory::from_references(
+                    purl_statuses
+                        .get(&id)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                    product_statuses
+                        .get(&id)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                    &references,
+                    tx,
+                )
+                .await?,
+                licenses: licenses.get(&id).cloned().unwrap_or_default(),
+                licenses_ref_mapping: vec![],
+            });
+        }
+
+        Ok(result)
+    }
+}
+
+/// Subquery of the SBOMs containing the qualified PURL of the outer query
+fn sbom_ids_for_purl() -> SelectStatement {
+    sbom_node_purl_ref::Entity::find()
+        .select_only()
+        .column(sbom_node_purl_ref::Column::SbomId)
+        .filter(
+            Expr::col((
+                sbom_node_purl_ref::Entity,
+                sbom_node_purl_ref::Column::QualifiedPurlId,
+            ))
+            .equals((qualified_purl::Entity, qualified_purl::Column::Id)),
+        )
+        .into_query()
+}
 
-        Ok(PurlDetails {
-            head: PurlHead::from_entity(&package, &package_version, qualified_package),
-            version: VersionedPurlHead::from_entity(&package, &package_version),
-            base: BasePurlHead::from_entity(&package),
-            advisories: PurlAdvisory::from_entities(purl_statuses, product_statuses, tx).await?,
-            licenses,
-            licenses_ref_mapping: vec![],
+/// A PURL status, along with the qualified PURL it applies to
+struct QualifiedPurlStatus {
+    qualified_purl_id: Uuid,
+    purl_status: purl_status::Model,
+}
+
+impl FromQueryResult for QualifiedPurlStatus {
+    fn from_query_result(res: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+        Ok(Self {
+            qualified_purl_id: res.try_get(pre, "qualified_purl_id")?,
+            purl_status: purl_status::Model::from_query_result(res, pre)?,
         })
     }
 }
 
-async fn get_product_statuses_for_purl<C: ConnectionTrait>(
+/// Get the PURL statuses of a set of qualified PURLs
+async fn get_purl_statuses<C: ConnectionTrait>(
+    qualified_package_ids: &[Uuid],
+    deprecation: Deprecation,
+    tx: &C,
+) -> Result<Vec<QualifiedPurlStatus>, Error> {
+    let mut allowed_cpe_ids = sbom_describing_cpe::Entity::find()
+        .select_only()
+        .column(sbom_describing_cpe::Column::CpeId)
+        .filter(sbom_describing_cpe::Column::SbomId.in_subquery(sbom_ids_for_purl()))
+        .into_query();
+
+    let c = Alias::new("c");
+    let sc = Alias::new("sc");
+    let sdc = Alias::new("sdc");
+    let generalized_cpe_ids = sea_query::Query::select()
+        .expr(Expr::col((c.clone(), cpe::Column::Id)))
+        .from_as(cpe::Entity, c.clone())
+        .join_as(
+            JoinType::InnerJoin,
+            cpe::Entity,
+            sc.clone(),
+            Condition::all()
+                .add(
+                    Expr::col((c.clone(), cpe::Column::Vendor))
+                        .equals((sc.clone(), cpe::Column::Vendor)),
+                )
+                .add(
+                    Expr::col((c.clone(), cpe::Column::Product))
+                        .equals((sc.clone(), cpe::Column::Product)),
+                )
+                .add(
+                    Expr::col((c.clone(), cpe::Column::Version)).eq(SimpleExpr::FunctionCall(
+                        Func::cust(Alias::new("split_part"))
+                            .arg(Expr::col((sc.clone(), cpe::Column::Version)))
+                            .arg(Expr::value("."))
+                            .arg(Expr::value(1i32)),
+                    )),
+                ),
+        )
+        .join_as(
+            JoinType::InnerJoin,
+            sbom_describing_cpe::Entity,
+            sdc.clone(),
+            Expr::col((sdc.clone(), sbom_describing_cpe::Column::CpeId))
+                .equals((sc.clone(), cpe::Column::Id)),
+        )
+        .and_where(
+            Expr::col((sdc.clone(), sbom_describing_cpe::Column::SbomId))
+                .in_subquery(sbom_ids_for_purl()),
+        )
+        .to_owned();
+    allowed_cpe_ids.union(UnionType::Distinct, generalized_cpe_ids);
+
+    let sbom_has_cpes = sea_query::Query::select()
+        .expr(Expr::value(1i32))
+        .from(sbom_describing_cpe::Entity)
+        .and_where(sbom_describing_cpe::Column::SbomId.in_subquery(sbom_ids_for_purl()))
+        .to_owned();
+
+    Ok(purl_status::Entity::find()
+        .left_join(version_range::Entity)
+        .left_join(base_purl::Entity)
+        .join(JoinType::Join, base_purl::Relation::VersionedPurls.def())
+        .join(
+            JoinType::Join,
+            versioned_purl::Relation::QualifiedPurl.def(),
+        )
+        .filter(qualified_purl::Column::Id.is_in(qualified_package_ids.iter().copied()))
+        .filter(SimpleExpr::FunctionCall(
+            Func::cust(VersionMatches)
+                .arg(Expr::col((
+                    versioned_purl::Entity,
+                    versioned_purl::Column::Version,
+                )))
+                .arg(Expr::col((version_range::Entity, Asterisk))),
+        ))
+        .filter(
+            Condition::any()
+                .add(purl_status::Column::ContextCpeId.is_null())
+                .add(purl_status::Column::ContextCpeId.in_subquery(allowed_cpe_ids))
+                .add(Expr::exists(sbom_has_cpes).not()),
+        )
+        .column_as(qualified_purl::Column::Id, "qualified_purl_id")
+        .distinct_on([
+            (qualified_purl::Entity, qualified_purl::Column::Id),
+            (purl_status::Entity, purl_status::Column::Id),
+        ])
+        .order_by_asc(qualified_purl::Column::Id)
+        .order_by_asc(purl_status::Column::Id)
+        .with_deprecation_related(deprecation)
+        .into_model::<QualifiedPurlStatus>()
+        .all(tx)
+        .await?)
+}
+
+/// Get the product statuses of a set of qualified PURLs
+async fn get_product_statuses<C: ConnectionTrait>(
+    qualified_package_ids: &[Uuid],
     tx: &C,
-    qualified_package_id: Uuid,
-    purl_name: &str,
-    namespace_name: Option<&str>,
-    version: &str,
 ) -> Result<Vec<ProductStatusCatcher>, Error> {
-    // Subquery to get all SBOM IDs for the given purl
+    // Subquery to get all SBOM IDs for the purl
     let sbom_ids_query = sbom::Entity::find()
         .join(JoinType::Join, sbom::Relation::Node.def())
         .join(JoinType::Join, sbom::Relation::Packages.def())
         .join(JoinType::Join, sbom_node::Relation::Purl.def())
-        .filter(sbom_node_purl_ref::Column::QualifiedPurlId.eq(qualified_package_id))
+        .filter(
+            Expr::col((
+                sbom_node_purl_ref::Entity,
+                sbom_node_purl_ref::Column::QualifiedPurlId,
+            ))
+            .equals((qualified_purl::Entity, qualified_purl::Column::Id)),
+        )
+        .filter(sbom::Column::DeletedAt.is_null())
         .select_only()
         .column(sbom::Column::SbomId)
         .into_query();
@@ -258,17 +351,30 @@ async fn get_product_statuses_for_purl<C: ConnectionTrait>(
             product_status::Relation::Vulnerability.def(),
         )
         .filter(product_version::Column::SbomId.in_subquery(sbom_ids_query))
-        .filter(Expr::col(product_status::Column::Package).eq(purl_name).or(
-            namespace_name.map_or(Expr::value(false), |ns| {
-                Expr::col(product_status::Column::Package).eq(format!("{ns}/{purl_name}"))
-            }),
-        ))
+        .filter(
+            Expr::col(product_status::Column::Package)
+                .equals((base_purl::Entity, base_purl::Column::Name))
+                .or(
+                    Expr::col(product_status::Column::Package).eq(Expr::cust_with_exprs(
+                        "$1 || '/' || $2",
+                        [
+                            base_purl::Column::Namespace.into_simple_expr(),
+                            base_purl::Column::Name.into_simple_expr(),
+                        ],
+                    )),
+                ),
+        )
         .filter(SimpleExpr::FunctionCall(
             Func::cust(VersionMatches)
-                .arg(Expr::value(version.to_string()))
+                .arg(Expr::col((
+                    versioned_purl::Entity,
+                    versioned_purl::Column::Version,
+                )))
                 .arg(Expr::col((version_range::Entity, Asterisk))),
         ))
+        .column_as(qualified_purl::Column::Id, "qualified_purl_id")
         .distinct_on([
+            (qualified_purl::Entity, qualified_purl::Column::Id),
             (product_status::Entity, product_status::Column::ContextCpeId),
             (product_status::Entity, product_status::Column::StatusId),
             (product_status::Entity, product_status::Column::Package),
@@ -277,20 +383,254 @@ async fn get_product_statuses_for_purl<C: ConnectionTrait>(
                 product_status::Column::VulnerabilityId,
             ),
         ])
+        .order_by_asc(qualified_purl::Column::Id)
         .order_by_asc(product_status::Column::ContextCpeId)
         .order_by_asc(product_status::Column::StatusId)
         .order_by_asc(product_status::Column::Package)
         .order_by_asc(product_status::Column::VulnerabilityId);
 
-    let product_statuses = product_statuses_query
-        .try_into_multi_model::<ProductStatusCatcher>()?
+    // there is no relation from a product status to a PURL, so join the PURLs to evaluate
+    let mut query =
+        ProductStatusCatcher::try_into_multi_model(product_statuses_query)?.into_query();
+    query
+        .join(
+            JoinType::InnerJoin,
+            qualified_purl::Entity,
+            qualified_purl::Column::Id.is_in(qualified_package_ids.iter().copied()),
+        )
+        .join(
+            JoinType::InnerJoin,
+            versioned_purl::Entity,
+            Expr::col((versioned_purl::Entity, versioned_purl::Column::Id)).equals((
+                qualified_purl::Entity,
+                qualified_purl::Column::VersionedPurlId,
+            )),
+        )
+        .join(
+            JoinType::InnerJoin,
+            base_purl::Entity,
+            Expr::col((base_purl::Entity, base_purl::Column::Id))
+                .equals((versioned_purl::Entity, versioned_purl::Column::BasePurlId)),
+        );
+
+    Ok(
+        ProductStatusCatcher::find_by_statement(tx.get_database_backend().build(&query))
+            .all(tx)
+            .await?,
+    )
+}
+
+/// Get the licenses of a set of qualified PURLs
+async fn get_licenses<C: ConnectionTrait>(
+    qualified_package_ids: &[Uuid],
+    tx: &C,
+) -> Result<Vec<PurlLicenseResult>, Error> {
+    Ok(sbom_node_purl_ref::Entity::find()
+        .distinct()
+        .select_only()
+        .column(sbom_node_purl_ref::Column::QualifiedPurlId)
+        .column_as(license_text_coalesce(), "license_name")
+        .select_column(sbom_package_license::Column::LicenseType)
+        .filter(
+            sbom_node_purl_ref::Column::QualifiedPurlId
+                .is_in(qualified_package_ids.iter().copied()),
+        )
+        .join(JoinType::Join, sbom_node_purl_ref::Relation::Node.def())
+        .join(JoinType::Join, sbom_node::Relation::PackageLicense.def())
+        .join(
+            JoinType::LeftJoin,
+            sbom_package_license::Relation::SbomLicenseExpanded.def(),
+        )
+        .join(
+            JoinType::LeftJoin,
+            sbom_license_expanded::Relation::ExpandedLicense.def(),
+        )
+        .join(
+            JoinType::LeftJoin,
+            sbom_package_license::Relation::License.def(),
+        )
+        .into_model::<PurlLicenseResult>()
         .all(tx)
-        .await?;
+        .await?)
+}
+
+/// The entities referenced by PURL and product statuses, fetched once for all statuses
+struct StatusReferences {
+    vulnerabilities: HashMap<String, vulnerability::Model>,
+    descriptions: HashMap<String, String>,
+    scores: HashMap<String, Vec<advisory_vulnerability_score::Model>>,
+    advisories: HashMap<Uuid, advisory::Model>,
+    issuers: HashMap<Uuid, organization::Model>,
+    statuses: HashMap<Uuid, String>,
+    cpes: HashMap<Uuid, String>,
+    version_ranges: HashMap<Uuid, version_range::Model>,
+}
+
+impl StatusReferences {
+    async fn load<'a, C: ConnectionTrait>(
+        purl_statuses: impl Iterator<Item = &'a purl_status::Model> + Clone,
+        product_statuses: impl Iterator<Item = &'a ProductStatusCatcher> + Clone,
+        tx: &C,
+    ) -> Result<Self, Error> {
+        let vulnerability_ids = purl_statuses
+            .clone()
+            .map(|status| status.vulnerability_id.clone())
+            .chain(
+                product_statuses
+                    .clone()
+                    .map(|status| status.vulnerability.id.clone()),
+            )
+            .collect::<HashSet<_>>();
+
+        let vulnerabilities = vulnerability::Entity::find()
+            .filter(vulnerability::Column::Id.is_in(vulnerability_ids.clone()))
+            .all(tx)
+            .await?
+            .into_iter()
+            .map(|vulnerability| (vulnerability.id.clone(), vulnerability))
+            .collect();
 
-    Ok(product_statuses)
+        let mut descriptions = HashMap::new();
+        for description in vulnerability_description::Entity::find()
+            .filter(
+                vulnerability_description::Column::VulnerabilityId.is_in(vulnerability_ids.clone()),
+            )
+            .filter(vulnerability_description::Column::Lang.eq("en"))
+            .all(tx)
+            .await?
+        {
+            descriptions
+                .entry(description.vulnerability_id)
+                .or_insert(description.description);
+        }
+
+        let mut scores = HashMap::<_, Vec<_>>::new();
+        for score in advisory_vulnerability_score::Entity::find()
+            .filter(advisory_vulnerability_score::Column::VulnerabilityId.is_in(vulnerability_ids))
+            .all(tx)
+            .await?
+        {
+            scores
+                .entry(score.vulnerability_id.clone())
+                .or_default()
+                .push(score);
+        }
+
+        let advisory_ids = purl_statuses
+            .clone()
+            .map(|status| status.advisory_id)
+            .collect::<HashSet<_>>();
+        let mut advisories: HashMap<_, _> = advisory::Entity::find()
+            .filter(advisory::Column::Id.is_in(advisory_ids))
+            .all(tx)
+            .await?
+            .into_iter()
+            .map(|advisory| (advisory.id, advisory))
+            .collect();
+        for status in product_statuses {
+            advisories
+                .entry(status.advisory.id)
+                .or_insert_with(|| status.advisory.clone());
+        }
+
+        let issuer_ids = advisories
+            .values()
+            .filter_map(|advisory| advisory.issuer_id)
+            .collect::<HashSet<_>>();
+        let issuers = organization::Entity::find()
+            .filter(organization::Column::Id.is_in(issuer_ids))
+            .all(tx)
+            .await?
+            .into_iter()
+            .map(|issuer| (issuer.id, issuer))
+            .collect();
+
+        let status_ids = purl_statuses
+            .clone()
+            .map(|status| status.status_id)
+            .collect::<HashSet<_>>();
+        let statuses = status::Entity::find()
+            .filter(status::Column::Id.is_in(status_ids))
+            .all(tx)
+            .await?
+            .into_iter()
+            .map(|status| (status.id, status.slug))
+            .collect();
+
+        let cpe_ids = purl_statuses
+            .clone()
+            .filter_map(|status| status.context_cpe_id)
+            .collect::<HashSet<_>>();
+        let cpes = cpe::Entity::find()
+            .filter(cpe::Column::Id.is_in(cpe_ids))
+            .all(tx)
+            .await?
+            .into_iter()
+            .map(|cpe| (cpe.id, cpe.to_string()))
+            .collect();
+
+        let version_range_ids = purl_statuses
+            .map(|status| status.version_range_id)
+            .collect::<HashSet<_>>();
+        let version_ranges = version_range::Entity::find()
+            .filter(version_range::Column::Id.is_in(version_range_ids))
+            .all(tx)
+            .await?
+            .into_iter()
+            .map(|version_range| (version_range.id, version_range))
+            .collect();
+
+        Ok(Self {
+            vulnerabilities,
+            descriptions,
+            scores,
+            advisories,
+            issuers,
+            statuses,
+            cpes,
+            version_ranges,
+        })
+    }
+
+    async fn advisory_head<C: ConnectionTrait>(
+        &self,
+        advisory: &advisory::Model,
+        tx: &C,
+    ) -> Result<AdvisoryHead, Error> {
+        let issuer = advisory
+            .issuer_id
+            .and_then(|id| self.issuers.get(&id))
+            .cloned();
+        AdvisoryHead::from_advisory(advisory, Memo::Provided(issuer), tx).await
+    }
+
+    async fn purl_status<C: ConnectionTrait>(
+        &self,
+        vulnerability: &vulnerability::Model,
+        advisory: &advisory::Model,
+        status: String,
+        version_range: Option<VersionRange>,
+        cpe: Option<String>,
+        tx: &C,
+    ) -> Result<PurlStatus, Error> {
+        PurlStatus::from_head(
+            VulnerabilityHead::from_vulnerability_entity_and_description(
+                vulnerability,
+                self.descriptions.get(&vulnerability.id).cloned(),
+            ),
+            self.advisory_head(advisory, tx).await?,
+            status,
+            version_range,
+            cpe,
+            self.scores
+                .get(&vulnerability.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        )
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, ToSchema, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema, PartialEq)]
 pub struct PurlAdvisory {
     #[serde(flatten)]
     pub head: AdvisoryHead,
@@ -298,69 +638,104 @@ pub struct PurlAdvisory {
 }
 
 impl PurlAdvisory {
-    pub async fn from_entities<C: ConnectionTrait>(
-        purl_statuses: Vec<purl_status::Model>,
-        product_statuses: Vec<ProductStatusCatcher>,
+    async fn from_references<C: ConnectionTrait>(
+        purl_statuses: &[purl_status::Model],
+        product_statuses: &[ProductStatusCatcher],
+        references: &StatusReferences,
         tx: &C,
     ) -> Result<Vec<Self>, Error> {
-        let vulns = purl_statuses.load_one(vulnerability::Entity, tx).await?;
+        let advisory_of =
+            |status: &purl_status::Model| references.advisories.get(&status.advisory_id);
 
-        let advisories = purl_statuses.load_one(advisory::Entity, tx).await?;
+        // a user override takes precedence over all other statuses of its vulnerability
+        let overridden: HashSet<&str> = purl_statuses
+            .iter()
+            .filter(|status| {
+                advisory_of(status).is_some_and(|advisory| is_user_override(&advisory.labels))
+            })
+            .map(|status| status.vulnerability_id.as_str())
+            .collect();
 
         let mut results: Vec<PurlAdvisory> = Vec::new();
 
-        for ((vuln, advisory), status) in vulns
-            .into_iter()
-            .zip(advisories.iter())
-            .zip(purl_statuses.iter())
-        {
-            let vulnerability = vuln.unwrap_or(vulnerability::Model {
-                id: status.vulnerability_id.clone(),
-                title: None,
-                reserved: None,
-                published: None,
-                modified: None,
-                withdrawn: None,
-                cwes: None,
-                base_type: None,
-                base_severity: None,
-                base_score: None,
-                authoritative_advisory_id: None,
-                id_sort_key: None, // Fallback only; normally loaded from database
-            });
+        for status in purl_statuses {
+            let advisory = advisory_of(status);
 
-            if let Some(advisory) = advisory {
-                let qualified_package_status =
-                    PurlStatus::from_entity(&vulnerability, advisory, status, tx).await?;
-
-                if let Some(entry) = results.iter_mut().find(|e| e.head.uuid == advisory.id) {
-                    entry.status.push(qualified_package_status)
-                } else {
-                    let organization = advisory.find_related(organization::Entity).one(tx).await?;
-
-                    results.push(Self {
-                        head: AdvisoryHead::from_advisory(
-                            advisory,
-                            Memo::Provided(organization),
-                            tx,
-                        )
-                        .await?,
-                        status: vec![qualified_package_status],
-                    })
-                }
+            if overridden.contains(status.vulnerability_id.as_str())
+                && !advisory.is_some_and(|advisory| is_user_override(&advisory.labels))
+            {
+                continue;
+            }
+
+            let Some(advisory) = advisory else {
+                continue;
+            };
+
+            let vulnerability = references
+                .vulnerabilities
+                .get(&status.vulnerability_id)
+                .cloned()
+                .unwrap_or(vulnerability::Model {
+                    id: status.vulnerability_id.clone(),
+                    title: None,
+                    reserved: None,
+                    published: None,
+                    modified: None,
+                    withdrawn: None,
+                    cwes: None,
+                    base_type: None,
+                    base_severity: None,
+                    base_score: None,
+                    authoritative_advisory_id: None,
+                    id_sort_key: None, // Fallback only; normally loaded from database
+                });
+
+            let qualified_package_status = references
+                .purl_status(
+                    &vulnerability,
+                    advisory,
+                    references
+                        .statuses
+                        .get(&status.status_id)
+                        .cloned()
+                        .unwrap_or("unknown".into()),
+                    references
+                        .version_ranges
+                        .get(&status.version_range_id)
+                        .cloned()
+                        .map(VersionRange::from_entity)
+                        .transpose()?,
+                    status
+                        .context_cpe_id
+                        .and_then(|id| references.cpes.get(&id))
+                        .cloned(),
+                    tx,
+                )
+                .await?;
+
+            if let Some(entry) = results.iter_mut().find(|e| e.head.uuid == advisory.id) {
+                entry.status.push(qualified_package_status)
+            } else {
+                results.push(Self {
+                    head: references.advisory_head(advisory, tx).await?,
+                    status: vec![qualified_package_status],
+                })
             }
         }
 
         for product_status in product_statuses {
-            let purl_status = PurlStatus::new(
-                &product_status.vulnerability,
-                &product_status.advisory,
-                product_status.status.slug.clone(),
-                Some(VersionRange::from_entity(product_status.version_range)?),
-                Some(product_status.cpe.to_string()),
-                tx,
-            )
-            .await?;
+            let purl_status = references
+                .purl_status(
+                    &product_status.vulnerability,
+                    &product_status.advisory,
+                    product_status.status.slug.clone(),
+                    Some(VersionRange::from_entity(
+                        product_status.version_range.clone(),
+                    )?),
+                    Some(product_status.cpe.to_string()),
+                    tx,
+                )
+                .await?;
 
             if let Some(entry) = results
                 .iter_mut()
@@ -368,19 +743,10 @@ impl PurlAdvisory {
             {
                 entry.status.push(purl_status)
             } else {
-                let organization = product_status
-                    .advisory
-                    .find_related(organization::Entity)
-                    .one(tx)
-                    .await?;
-
                 results.push(Self {
-                    head: AdvisoryHead::from_advisory(
-                        &product_status.advisory,
-                        Memo::Provided(organization),
-                        tx,
-                    )
-                    .await?,
+                    head: references
+                        .advisory_head(&product_status.advisory, tx)
+                        .await?,
                     status: vec![purl_status],
                 })
             }
@@ -410,38 +776,6 @@ pub enum StatusContext {
 }
 
 impl PurlStatus {
-    pub async fn new<C: ConnectionTrait>(
-        vuln: &vulnerability::Model,
-        advisory: &advisory::Model,
-        status: String,
-        version_range: Option<VersionRange>,
-        cpe: Option<String>,
-        tx: &C,
-    ) -> Result<Self, Error> {
-        // Query scores from the new advisory_vulnerability_score table
-        let score_models = vuln
-            .find_related(advisory_vulnerability_score::Entity)
-            .all(tx)
-            .await?;
-
-        let all_scores = score_models.into_iter().map(ScoredVector::from).collect();
-        let issuer = Memo::Provided(advisory.find_related(organization::Entity).one(tx).await?);
-
-        Ok(Self {
-            vulnerability: VulnerabilityHead::from_vulnerability_entity(
-                vuln,
-                Memo::NotProvided,
-                tx,
-            )
-            .await?,
-            advisory: AdvisoryHead::from_advisory(advisory, issuer, tx).await?,
-            scores: all_scores,
-            status,
-            context: cpe.map(StatusContext::Cpe),
-            version_range,
-        })
-    }
-
     pub fn from_head(
         vulnerability: VulnerabilityHead,
         advisory: AdvisoryHead,
@@ -465,33 +799,6 @@ impl PurlStatus {
             version_range,
         })
     }
-
-    pub async fn from_entity<C: ConnectionTrait>(
-        vuln: &vulnerability::Model,
-        advisory: &advisory::Model,
-        package_status: &purl_status::Model,
-        tx: &C,
-    ) -> Result<Self, Error> {
-        let status = status::Entity::find_by_id(package_status.status_id)
-            .one(tx)
-            .await?
-            .map(|e| e.slug)
-            .unwrap_or("unknown".into());
-        let cpe = match package_status.context_cpe_id {
-            Some(context_cpe) => {
-                let cpe = cpe::Entity::find_by_id(context_cpe).one(tx).await?;
-                cpe.map(|v| v.to_string())
-            }
-            _ => None,
-        };
-        let version_range = version_range::Entity::find_by_id(package_status.version_range_id)
-            .one(tx)
-            .await?
-            .map(VersionRange::from_entity)
-            .transpose()?;
-
-        PurlStatus::new(vuln, advisory, status, version_range, cpe, tx).await
-    }
 }
 
 #[derive(Serialize, Clone, Deserialize, Debug, ToSchema, Default)]
@@ -555,8 +862,9 @@ impl FromQueryResultMultiModel for LicenseCatcher {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ProductStatusCatcher {
+    qualified_purl_id: Uuid,
     advisory: advisory::Model,
     vulnerability: vulnerability::Model,
     cpe: trustify_entity::cpe::Model,
@@ -567,6 +875,7 @@ pub struct ProductStatusCatcher {
 impl FromQueryResult for ProductStatusCatcher {
     fn from_query_result(res: &QueryResult, _pre: &str) -> Result<Self, DbErr> {
         Ok(Self {
+            qualified_purl_id: res.try_get("", "qualified_purl_id")?,
             advisory: Self::from_query_result_multi_model(res, "", advisory::Entity)?,
             vulnerability: Self::from_query_result_multi_model(res, "", vulnerability::Entity)?,
             cpe: Self::from_query_result_multi_model(res, "", trustify_entity::cpe::Entity)?,
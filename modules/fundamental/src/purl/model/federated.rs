@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use trustify_common::purl::Purl;
+use utoipa::ToSchema;
+
+/// Metadata of a package which is not known locally, looked up from its external registry
+///
+/// This information is ephemeral, it is not stored in the database.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct FederatedPurlInfo {
+    /// The PURL which was looked up
+    pub purl: Purl,
+    /// The registry the information was retrieved from
+    pub registry: String,
+    /// The name of the package, as reported by the registry
+    pub name: String,
+    /// The version of the package, either the requested or the latest one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// A short description of the package
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The license of the package, as reported by the registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// The homepage of the package
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// The source repository of the package
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
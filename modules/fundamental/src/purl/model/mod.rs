@@ -3,7 +3,8 @@ use std::collections::HashMap;
 use crate::{
     Error,
     purl::model::{
-        details::versioned_purl::VersionedPurlStatus, summary::remediation::RemediationSummary,
+        details::{purl::PurlDetails, versioned_purl::VersionedPurlStatus},
+        summary::remediation::RemediationSummary,
     },
 };
 use sea_orm::prelude::Uuid;
@@ -13,7 +14,10 @@ use trustify_entity::{base_purl, qualified_purl, versioned_purl};
 use utoipa::ToSchema;
 
 pub mod details;
+pub mod federated;
+pub mod stats;
 pub mod summary;
+pub mod version_constraint;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, Hash)]
 pub struct BasePurlHead {
@@ -139,6 +143,28 @@ pub struct RecommendResponse {
     pub recommendations: HashMap<String, Vec<RecommendEntry>>,
 }
 
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct PurlBatchRequest {
+    pub purls: Vec<Purl>,
+}
+
+#[derive(Serialize, Deserialize, Default, ToSchema)]
+pub struct PurlBatchResponse {
+    /// The details of each requested PURL, `null` if the PURL is not known
+    #[schema(value_type = HashMap<String, PurlDetails>)]
+    pub results: HashMap<Purl, Option<PurlDetails>>,
+}
+
+/// The outcome of garbage collecting unreferenced PURLs
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, ToSchema)]
+pub struct GcReport {
+    pub base_purls_deleted: u64,
+    pub versioned_purls_deleted: u64,
+    pub qualified_purls_deleted: u64,
+    /// Some of the deleted qualified PURLs
+    pub sample_deleted_purls: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Default, ToSchema)]
 pub struct RecommendEntry {
     pub package: String,
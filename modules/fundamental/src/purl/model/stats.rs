@@ -0,0 +1,36 @@
+use crate::purl::model::{
+    BasePurlHead,
+    summary::r#type::{TypeCounts, TypeSummary},
+};
+use sea_orm::{DbErr, FromQueryResult, QueryResult};
+use serde::{Deserialize, Serialize};
+use trustify_entity::base_purl;
+use utoipa::ToSchema;
+
+/// Statistics about the PURLs known to the system
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct PurlStats {
+    /// The counts over all types
+    pub totals: TypeCounts,
+    /// The counts per type
+    pub types: Vec<TypeSummary>,
+    /// The base PURLs referenced by the most SBOMs, in descending order
+    pub most_referenced: Vec<ReferencedBasePurl>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ReferencedBasePurl {
+    #[serde(flatten)]
+    pub head: BasePurlHead,
+    /// The number of SBOMs referencing any version of the base PURL
+    pub sboms: i64,
+}
+
+impl FromQueryResult for ReferencedBasePurl {
+    fn from_query_result(res: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+        Ok(Self {
+            head: BasePurlHead::from_entity(&base_purl::Model::from_query_result(res, pre)?),
+            sboms: res.try_get(pre, "sboms")?,
+        })
+    }
+}
@@ -1,10 +1,8 @@
 use crate::Error;
 use crate::purl::model::TypeHead;
-use sea_orm::{
-    ColumnTrait, ConnectionTrait, DeriveColumn, EntityTrait, EnumIter, QueryFilter, QuerySelect,
-};
+use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
 use serde::{Deserialize, Serialize};
-use trustify_entity::{base_purl, qualified_purl, versioned_purl};
+use std::ops::AddAssign;
 use utoipa::ToSchema;
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
@@ -14,63 +12,62 @@ pub struct TypeSummary {
     pub counts: TypeCounts,
 }
 
-#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
 pub struct TypeCounts {
+    /// The number of base PURLs
     pub base: i64,
+    /// The number of versioned PURLs
     pub version: i64,
+    /// The number of fully-qualified PURLs
     pub package: i64,
 }
 
+impl AddAssign for TypeCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.base += rhs.base;
+        self.version += rhs.version;
+        self.package += rhs.package;
+    }
+}
+
 impl TypeSummary {
-    pub async fn from_names<C: ConnectionTrait>(
-        names: &Vec<String>,
-        tx: &C,
-    ) -> Result<Vec<Self>, Error> {
-        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
-        enum QueryAs {
-            Count,
+    /// Count the PURLs of all types, ordered by type
+    pub async fn all<C: ConnectionTrait>(tx: &C) -> Result<Vec<Self>, Error> {
+        #[derive(FromQueryResult)]
+        struct Row {
+            name: String,
+            base: i64,
+            version: i64,
+            package: i64,
         }
 
-        let mut summaries = Vec::new();
-
-        for name in names {
-            let base: Option<i64> = base_purl::Entity::find()
-                .filter(base_purl::Column::Type.eq(name))
-                .select_only()
-                .column_as(base_purl::Column::Id.count(), "count")
-                .into_values::<_, QueryAs>()
-                .one(tx)
-                .await?;
-
-            let version: Option<i64> = versioned_purl::Entity::find()
-                .left_join(base_purl::Entity)
-                .filter(base_purl::Column::Type.eq(name))
-                .select_only()
-                .column_as(versioned_purl::Column::Id.count(), "count")
-                .into_values::<_, QueryAs>()
-                .one(tx)
-                .await?;
-
-            let package: Option<i64> = qualified_purl::Entity::find()
-                .left_join(versioned_purl::Entity)
-                .left_join(base_purl::Entity)
-                .filter(base_purl::Column::Type.eq(name))
-                .select_only()
-                .column_as(versioned_purl::Column::Id.count(), "count")
-                .into_values::<_, QueryAs>()
-                .one(tx)
-                .await?;
-
-            summaries.push(TypeSummary {
-                head: TypeHead { name: name.clone() },
-                counts: TypeCounts {
-                    base: base.unwrap_or_default(),
-                    version: version.unwrap_or_default(),
-                    package: package.unwrap_or_default(),
-                },
-            })
-        }
+        const SQL: &str = r#"
+SELECT
+    base_purl.type AS name,
+    count(DISTINCT base_purl.id) AS base,
+    count(DISTINCT versioned_purl.id) AS version,
+    count(qualified_purl.id) AS package
+FROM base_purl
+LEFT JOIN versioned_purl ON versioned_purl.base_purl_id = base_purl.id
+LEFT JOIN qualified_purl ON qualified_purl.versioned_purl_id = versioned_purl.id
+GROUP BY base_purl.type
+ORDER BY base_purl.type
+"#;
 
-        Ok(summaries)
+        Ok(
+            Row::find_by_statement(Statement::from_string(tx.get_database_backend(), SQL))
+                .all(tx)
+                .await?
+                .into_iter()
+                .map(|row| TypeSummary {
+                    head: TypeHead { name: row.name },
+                    counts: TypeCounts {
+                        base: row.base,
+                        version: row.version,
+                        package: row.package,
+                    },
+                })
+                .collect(),
+        )
     }
 }
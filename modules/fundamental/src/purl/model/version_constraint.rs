@@ -0,0 +1,153 @@
+use crate::Error;
+use sea_orm::Iterable;
+use std::str::FromStr;
+use trustify_entity::{version_range, version_scheme::VersionScheme};
+use trustify_module_ingestor::graph::advisory::version::Version;
+use uuid::Uuid;
+
+/// Parse the name of a version scheme, like `semver` or `maven`
+pub fn parse_version_scheme(scheme: &str) -> Result<VersionScheme, Error> {
+    VersionScheme::iter()
+        .find(|candidate| candidate.to_string().eq_ignore_ascii_case(scheme))
+        .ok_or_else(|| Error::BadRequest(format!("Unknown version scheme: {scheme}").into(), None))
+}
+
+/// A version constraint, like `>=2.14.0,<2.15.0`
+///
+/// The constraint is a comma separated list of comparisons (`>`, `>=`, `<`, `<=`, `=`), forming a
+/// single range of versions. A version without an operator is an exact match. How versions are
+/// compared is up to the version scheme the constraint is evaluated with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionConstraint {
+    pub low: Version,
+    pub high: Version,
+}
+
+impl VersionConstraint {
+    /// Turn the constraint into a version range of the given scheme
+    ///
+    /// The range is not meant to be stored, so it has no ID.
+    pub fn into_version_range(self, scheme: VersionScheme) -> version_range::Model {
+        fn bound(version: Version) -> (Option<String>, Option<bool>) {
+            match version {
+                Version::Inclusive(version) => (Some(version), Some(true)),
+                Version::Exclusive(version) => (Some(version), Some(false)),
+                Version::Unbounded => (None, Some(false)),
+            }
+        }
+
+        let (low_version, low_inclusive) = bound(self.low);
+        let (high_version, high_inclusive) = bound(self.high);
+
+        version_range::Model {
+            id: Uuid::nil(),
+            version_scheme_id: scheme,
+            low_version,
+            low_inclusive,
+            high_version,
+            high_inclusive,
+        }
+    }
+}
+
+impl FromStr for VersionConstraint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |msg: &str| {
+            Error::BadRequest(
+                format!("Invalid version constraint '{s}': {msg}").into(),
+                None,
+            )
+        };
+
+        let mut low = Version::Unbounded;
+        let mut high = Version::Unbounded;
+
+        for comparison in s.split(',').map(str::trim) {
+            let (op, version) = comparison.split_at(
+                comparison
+                    .find(|c: char| !"<>=".contains(c))
+                    .unwrap_or(comparison.len()),
+            );
+            let version = version.trim().to_string();
+            if version.is_empty() {
+                return Err(invalid("missing version"));
+            }
+
+            let (bound, value) = match op {
+                ">=" => (&mut low, Version::Inclusive(version)),
+                ">" => (&mut low, Version::Exclusive(version)),
+                "<=" => (&mut high, Version::Inclusive(version)),
+                "<" => (&mut high, Version::Exclusive(version)),
+                "" | "=" | "==" => {
+                    if low != Version::Unbounded || high != Version::Unbounded {
+                        return Err(invalid("an exact version can't be combined"));
+                    }
+                    low = Version::Inclusive(version.clone());
+                    high = Version::Inclusive(version);
+                    continue;
+                }
+                _ => return Err(invalid(&format!("unknown operator '{op}'"))),
+            };
+
+            if *bound != Version::Unbounded {
+                return Err(invalid("more than one lower or upper bound"));
+            }
+            *bound = value;
+        }
+
+        Ok(Self { low, high })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    fn inclusive(version: &str) -> Version {
+        Version::Inclusive(version.to_string())
+    }
+
+    fn exclusive(version: &str) -> Version {
+        Version::Exclusive(version.to_string())
+    }
+
+    #[rstest]
+    #[case(">=2.14.0,<2.15.0", inclusive("2.14.0"), exclusive("2.15.0"))]
+    #[case("< 2.15.0 , > 2.0", exclusive("2.0"), exclusive("2.15.0"))]
+    #[case("<=1.0", Version::Unbounded, inclusive("1.0"))]
+    #[case("1.2.3-1.el9", inclusive("1.2.3-1.el9"), inclusive("1.2.3-1.el9"))]
+    #[case("=1.2.3", inclusive("1.2.3"), inclusive("1.2.3"))]
+    fn parse(#[case] input: &str, #[case] low: Version, #[case] high: Version) {
+        assert_eq!(
+            VersionConstraint::from_str(input).expect("must parse"),
+            VersionConstraint { low, high }
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case(">=")]
+    #[case(">= ,<2.0")]
+    #[case(">=1.0,>2.0")]
+    #[case("1.0,<2.0")]
+    #[case("=>1.0")]
+    fn parse_invalid(#[case] input: &str) {
+        assert!(matches!(
+            VersionConstraint::from_str(input),
+            Err(Error::BadRequest(..))
+        ));
+    }
+
+    #[test]
+    fn scheme() {
+        assert_eq!(parse_version_scheme("rpm").ok(), Some(VersionScheme::Rpm));
+        assert_eq!(
+            parse_version_scheme("NuGet").ok(),
+            Some(VersionScheme::NuGet)
+        );
+        assert!(parse_version_scheme("foo").is_err());
+    }
+}
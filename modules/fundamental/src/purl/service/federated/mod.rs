@@ -0,0 +1,285 @@
+#[cfg(test)]
+mod test;
+
+use crate::{Error, purl::model::federated::FederatedPurlInfo};
+use moka::future::Cache;
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::instrument;
+use trustify_common::purl::Purl;
+
+/// The maximum number of external lookup results cached
+pub const DEFAULT_CAPACITY: u64 = 10_000;
+/// The time a registry has to respond
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The base URLs of the external registries
+#[derive(Clone, Debug)]
+pub struct Registries {
+    /// The crates.io API, e.g. `https://crates.io/api/v1`
+    pub crates_io: Url,
+    /// The PyPI JSON API, e.g. `https://pypi.org/pypi`
+    pub pypi: Url,
+    /// The Maven Central search API, e.g. `https://search.maven.org/solrsearch/select`
+    pub maven_central: Url,
+}
+
+/// The configuration of looking up PURLs from external registries
+#[derive(Clone, Debug)]
+pub struct FederatedConfig {
+    /// The registries to look up PURLs from
+    pub registries: Registries,
+    /// The time lookup results are cached for
+    pub ttl: Duration,
+}
+
+/// Looks up metadata of packages from their external registry, without persisting it.
+#[derive(Clone, Debug)]
+pub struct FederatedPurlService {
+    client: reqwest::Client,
+    registries: Registries,
+    cache: Cache<String, Option<FederatedPurlInfo>>,
+}
+
+impl FederatedPurlService {
+    pub fn new(config: FederatedConfig) -> Result<Self, reqwest::Error> {
+        let FederatedConfig { registries, ttl } = config;
+
+        let client = reqwest::Client::builder()
+            .user_agent("trustify-federated-lookup")
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            registries,
+            cache: Cache::builder()
+                .max_capacity(DEFAULT_CAPACITY)
+                .time_to_live(ttl)
+                .build(),
+        })
+    }
+
+    /// Look up a PURL from the registry of its type
+    ///
+    /// Returns `None` if the registry doesn't know the package, or if the type of the PURL has
+    /// no supported registry. Results, including misses, are cached.
+    #[instrument(skip(self), err(level=tracing::Level::INFO))]
+    pub async fn lookup(&self, purl: &Purl) -> Result<Option<FederatedPurlInfo>, Error> {
+        let key = purl.to_string();
+        if let Some(result) = self.cache.get(&key).await {
+            return Ok(result);
+        }
+
+        let result = match purl.ty.as_str() {
+            "cargo" => self.lookup_cargo(purl).await?,
+            "pypi" => self.lookup_pypi(purl).await?,
+            "maven" => self.lookup_maven(purl).await?,
+            _ => return Ok(None),
+        };
+
+        self.cache.insert(key, result.clone()).await;
+
+        Ok(result)
+    }
+
+    /// Fetch a JSON document, `None` if the registry responds with "not found"
+    async fn fetch<T: for<'de> Deserialize<'de>>(&self, url: Url) -> Result<Option<T>, Error> {
+        let response = self.client.get(url).send().await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(response.error_for_status()?.json().await?))
+    }
+
+    async fn lookup_cargo(&self, purl: &Purl) -> Result<Option<FederatedPurlInfo>, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(rename = "crate")]
+            krate: Crate,
+            #[serde(default)]
+            versions: Vec<Version>,
+        }
+
+        #[derive(Deserialize)]
+        struct Crate {
+            name: String,
+            description: Option<String>,
+            homepage: Option<String>,
+            repository: Option<String>,
+            max_version: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct Version {
+            num: String,
+            license: Option<String>,
+        }
+
+        let url = join(&self.registries.crates_io, &["crates", &purl.name])?;
+        let Some(Response { krate, versions }) = self.fetch::<Response>(url).await? else {
+            return Ok(None);
+        };
+
+        let version = purl.version.clone().or(krate.max_version);
+        let license = versions
+            .into_iter()
+            .find(|v| Some(&v.num) == version.as_ref())
+            .and_then(|v| v.license);
+
+        Ok(Some(FederatedPurlInfo {
+            purl: purl.clone(),
+            registry: "crates.io".to_string(),
+            name: krate.name,
+            version,
+            description: krate.description,
+            license,
+            homepage: krate.homepage,
+            repository: krate.repository,
+        }))
+    }
+
+    async fn lookup_pypi(&self, purl: &Purl) -> Result<Option<FederatedPurlInfo>, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            info: Info,
+        }
+
+        #[derive(Deserialize)]
+        struct Info {
+            name: String,
+            version: Option<String>,
+            summary: Option<String>,
+            license: Option<String>,
+            home_page: Option<String>,
+            #[serde(default)]
+            project_urls: Option<std::collections::HashMap<String, String>>,
+        }
+
+        let url = match &purl.version {
+            Some(version) => join(&self.registries.pypi, &[&purl.name, version, "json"])?,
+            None => join(&self.registries.pypi, &[&purl.name, "json"])?,
+        };
+        let Some(Response { info }) = self.fetch::<Response>(url).await? else {
+            return Ok(None);
+        };
+
+        let project_url = |keys: &[&str]| {
+            info.project_urls.as_ref().and_then(|urls| {
+                urls.iter()
+                    .find(|(key, _)| keys.iter().any(|k| key.eq_ignore_ascii_case(k)))
+                    .map(|(_, url)| url.clone())
+            })
+        };
+        let homepage = non_empty(info.home_page.clone()).or_else(|| project_url(&["homepage"]));
+        let repository = project_url(&["source", "repository", "source code"]);
+
+        Ok(Some(FederatedPurlInfo {
+            purl: purl.clone(),
+            registry: "pypi.org".to_string(),
+            name: info.name,
+            version: info.version,
+            description: non_empty(info.summary),
+            license: non_empty(info.license),
+            homepage,
+            repository,
+        }))
+    }
+
+    async fn lookup_maven(&self, purl: &Purl) -> Result<Option<FederatedPurlInfo>, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            response: Docs,
+        }
+
+        #[derive(Deserialize)]
+        struct Docs {
+            docs: Vec<Doc>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Doc {
+            g: String,
+            a: String,
+            v: Option<String>,
+            latest_version: Option<String>,
+        }
+
+        // without a group ID, there is no way to identify the artifact
+        let Some(group) = &purl.namespace else {
+            return Ok(None);
+        };
+
+        let group = escape_solr(group);
+        let name = escape_solr(&purl.name);
+
+        let mut url = self.registries.maven_central.clone();
+        {
+            let mut query = url.query_pairs_mut();
+            match &purl.version {
+                Some(version) => query
+                    .append_pair(
+                        "q",
+                        &format!(
+                            r#"g:"{group}" AND a:"{name}" AND v:"{}""#,
+                            escape_solr(version)
+                        ),
+                    )
+                    .append_pair("core", "gav"),
+                None => query.append_pair("q", &format!(r#"g:"{group}" AND a:"{name}""#)),
+            };
+            query.append_pair("rows", "1").append_pair("wt", "json");
+        }
+
+        let Some(Response { response }) = self.fetch::<Response>(url).await? else {
+            return Ok(None);
+        };
+        let Some(doc) = response.docs.into_iter().next() else {
+            return Ok(None);
+        };
+
+        Ok(Some(FederatedPurlInfo {
+            purl: purl.clone(),
+            registry: "Maven Central".to_string(),
+            name: format!("{}:{}", doc.g, doc.a),
+            version: doc.v.or(doc.latest_version),
+            description: None,
+            license: None,
+            homepage: None,
+            repository: None,
+        }))
+    }
+}
+
+/// Append path segments to a base URL
+fn join(base: &Url, segments: &[&str]) -> Result<Url, Error> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|()| Error::Internal(format!("Not a base URL: {base}")))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+/// Escape all characters having a special meaning in a Solr query
+fn escape_solr(value: &str) -> String {
+    const SPECIAL: &str = r#"\"+-&|!(){}[]^~*?:/"#;
+
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        if SPECIAL.contains(c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Registries tend to report missing values as empty strings
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
@@ -0,0 +1,261 @@
+use super::*;
+use serde_json::json;
+use std::str::FromStr;
+use test_log::test;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path, query_param},
+};
+
+fn service(server: &MockServer) -> Result<FederatedPurlService, anyhow::Error> {
+    let base = Url::parse(&server.uri())?;
+    Ok(FederatedPurlService::new(FederatedConfig {
+        registries: Registries {
+            crates_io: base.join("api/v1")?,
+            pypi: base.join("pypi")?,
+            maven_central: base.join("solrsearch/select")?,
+        },
+        ttl: Duration::from_secs(60),
+    })?)
+}
+
+#[test(tokio::test)]
+async fn cargo() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/crates/serde"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "crate": {
+                "name": "serde",
+                "description": "A serialization framework",
+                "homepage": "https://serde.rs",
+                "repository": "https://github.com/serde-rs/serde",
+                "max_version": "1.0.200",
+            },
+            "versions": [
+                { "num": "1.0.200", "license": "MIT OR Apache-2.0" },
+                { "num": "0.1.0", "license": "MIT" },
+            ],
+        })))
+        .mount(&server)
+        .await;
+
+    let service = service(&server)?;
+
+    let info = service
+        .lookup(&Purl::from_str("pkg:cargo/serde")?)
+        .await?
+        .expect("must be found");
+    assert_eq!(info.registry, "crates.io");
+    assert_eq!(info.name, "serde");
+    assert_eq!(info.version.as_deref(), Some("1.0.200"));
+    assert_eq!(info.license.as_deref(), Some("MIT OR Apache-2.0"));
+    assert_eq!(info.homepage.as_deref(), Some("https://serde.rs"));
+
+    let info = service
+        .lookup(&Purl::from_str("pkg:cargo/serde@0.1.0")?)
+        .await?
+        .expect("must be found");
+    assert_eq!(info.version.as_deref(), Some("0.1.0"));
+    assert_eq!(info.license.as_deref(), Some("MIT"));
+
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn pypi() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pypi/aiohttp/3.8.5/json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "info": {
+                "name": "aiohttp",
+                "version": "3.8.5",
+                "summary": "Async http client/server framework (asyncio)",
+                "license": "Apache 2",
+                "home_page": "",
+                "project_urls": {
+                    "Homepage": "https://github.com/aio-libs/aiohttp",
+                    "Source": "https://github.com/aio-libs/aiohttp",
+                },
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    let info = service(&server)?
+        .lookup(&Purl::from_str("pkg:pypi/aiohttp@3.8.5")?)
+        .await?
+        .expect("must be found");
+    assert_eq!(info.registry, "pypi.org");
+    assert_eq!(info.version.as_deref(), Some("3.8.5"));
+    assert_eq!(info.license.as_deref(), Some("Apache 2"));
+    assert_eq!(
+        info.homepage.as_deref(),
+        Some("https://github.com/aio-libs/aiohttp")
+    );
+    assert_eq!(
+        info.repository.as_deref(),
+        Some("https://github.com/aio-libs/aiohttp")
+    );
+
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn maven() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/solrsearch/select"))
+        .and(query_param(
+            "q",
+            r#"g:"org.apache.logging.log4j" AND a:"log4j-core""#,
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "response": {
+                "numFound": 1,
+                "docs": [{
+                    "g": "org.apache.logging.log4j",
+                    "a": "log4j-core",
+                    "latestVersion": "2.23.1",
+                }],
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    let service = service(&server)?;
+
+    let info = service
+        .lookup(&Purl::from_str(
+            "pkg:maven/org.apache.logging.log4j/log4j-core",
+        )?)
+        .await?
+        .expect("must be found");
+    assert_eq!(info.name, "org.apache.logging.log4j:log4j-core");
+    assert_eq!(info.version.as_deref(), Some("2.23.1"));
+
+    // no group ID, no lookup
+    assert!(
+        service
+            .lookup(&Purl::from_str("pkg:maven/log4j-core")?)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+/// Test that a PURL can't alter the query sent to Maven Central
+#[test(tokio::test)]
+async fn maven_escape() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/solrsearch/select"))
+        .and(query_param(
+            "q",
+            r#"g:"org.example" AND a:"a\" OR g\:\*" AND v:"1.0\-beta""#,
+        ))
+        .and(query_param("core", "gav"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "response": {
+                "numFound": 0,
+                "docs": [],
+            },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = service(&server)?;
+
+    assert!(
+        service
+            .lookup(&Purl::from_str(
+                "pkg:maven/org.example/a%22%20OR%20g%3A%2A@1.0-beta"
+            )?)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn escape() {
+    assert_eq!(escape_solr("log4j-core"), r"log4j\-core");
+    assert_eq!(escape_solr(r#"a" || b:(c)"#), r#"a\" \|\| b\:\(c\)"#);
+    assert_eq!(escape_solr("org.apache"), "org.apache");
+}
+
+/// Test a package unknown to the registry, and an unsupported PURL type
+#[test(tokio::test)]
+async fn not_found() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let service = service(&server)?;
+
+    assert!(
+        service
+            .lookup(&Purl::from_str("pkg:cargo/does-not-exist")?)
+            .await?
+            .is_none()
+    );
+    assert!(
+        service
+            .lookup(&Purl::from_str("pkg:rpm/redhat/openssl@3.0.7")?)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+/// Test registry errors are reported, and not cached
+#[test(tokio::test)]
+async fn registry_error() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let service = service(&server)?;
+    let purl = Purl::from_str("pkg:cargo/serde")?;
+
+    assert!(matches!(
+        service.lookup(&purl).await,
+        Err(Error::Registry(_))
+    ));
+    assert!(matches!(
+        service.lookup(&purl).await,
+        Err(Error::Registry(_))
+    ));
+
+    Ok(())
+}
+
+/// Test results, including misses, are cached
+#[test(tokio::test)]
+async fn cached() -> Result<(), anyhow::Error> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pypi/does-not-exist/json"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let service = service(&server)?;
+    let purl = Purl::from_str("pkg:pypi/does-not-exist")?;
+
+    assert!(service.lookup(&purl).await?.is_none());
+    assert!(service.lookup(&purl).await?.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+-- Merge qualified_purl rows sharing the same canonical PURL into the oldest one
+WITH
+    ranked AS (
+        SELECT
+            id,
+            first_value(id) OVER (
+                PARTITION BY purl
+                ORDER BY "timestamp" ASC NULLS LAST, id
+            ) AS canonical_id
+        FROM qualified_purl
+        WHERE purl IS NOT NULL
+    ),
+    duplicates AS (
+        SELECT id, canonical_id
+        FROM ranked
+        WHERE id <> canonical_id
+    ),
+    -- Drop references which already exist for the canonical PURL, they would collide otherwise
+    deleted_refs AS (
+        DELETE FROM sbom_node_purl_ref r
+        USING duplicates d
+        WHERE r.qualified_purl_id = d.id
+        AND EXISTS (
+            SELECT 1 FROM sbom_node_purl_ref c
+            WHERE c.sbom_id = r.sbom_id
+            AND c.node_id = r.node_id
+            AND c.qualified_purl_id = d.canonical_id
+        )
+        RETURNING r.qualified_purl_id
+    ),
+    -- Point all other references to the canonical PURL
+    updated_refs AS (
+        UPDATE sbom_node_purl_ref r
+        SET qualified_purl_id = d.canonical_id
+        FROM duplicates d
+        WHERE r.qualified_purl_id = d.id
+        AND NOT EXISTS (
+            SELECT 1 FROM sbom_node_purl_ref c
+            WHERE c.sbom_id = r.sbom_id
+            AND c.node_id = r.node_id
+            AND c.qualified_purl_id = d.canonical_id
+        )
+        RETURNING r.qualified_purl_id
+    )
+DELETE FROM qualified_purl
+WHERE id IN (SELECT id FROM duplicates)
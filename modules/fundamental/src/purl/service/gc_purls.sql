@@ -0,0 +1,69 @@
+-- Delete all PURLs which are neither referenced by an SBOM nor by an advisory
+WITH
+    -- Qualified PURLs not referenced by any SBOM
+    orphaned_qualified AS (
+        SELECT qp.id
+        FROM qualified_purl qp
+        JOIN versioned_purl vp ON vp.id = qp.versioned_purl_id
+        WHERE NOT EXISTS (
+            SELECT 1 FROM sbom_node_purl_ref r
+            WHERE r.qualified_purl_id = qp.id
+        )
+        AND NOT EXISTS (
+            -- Conservative: keeps ALL versions of a package if base_purl has purl_status
+            SELECT 1 FROM purl_status ps
+            WHERE ps.base_purl_id = vp.base_purl_id
+        )
+    ),
+    -- Versioned PURLs without any qualified PURL left after the qualified cleanup
+    orphaned_versioned AS (
+        SELECT vp.id
+        FROM versioned_purl vp
+        WHERE NOT EXISTS (
+            SELECT 1 FROM qualified_purl qp
+            WHERE qp.versioned_purl_id = vp.id
+            AND qp.id NOT IN (SELECT id FROM orphaned_qualified)
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM purl_status ps
+            WHERE ps.base_purl_id = vp.base_purl_id
+        )
+    ),
+    -- Base PURLs without any versioned PURL left after the versioned cleanup
+    orphaned_base AS (
+        SELECT bp.id
+        FROM base_purl bp
+        WHERE NOT EXISTS (
+            SELECT 1 FROM versioned_purl vp
+            WHERE vp.base_purl_id = bp.id
+            AND vp.id NOT IN (SELECT id FROM orphaned_versioned)
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM purl_status ps
+            WHERE ps.base_purl_id = bp.id
+        )
+    ),
+    -- DELETE operations: remove orphaned purls in correct dependency order
+    deleted_qualified AS (
+        DELETE FROM qualified_purl
+        WHERE id IN (SELECT id FROM orphaned_qualified)
+        RETURNING purl
+    ),
+    deleted_versioned AS (
+        DELETE FROM versioned_purl
+        WHERE id IN (SELECT id FROM orphaned_versioned)
+        RETURNING id
+    ),
+    deleted_base AS (
+        DELETE FROM base_purl
+        WHERE id IN (SELECT id FROM orphaned_base)
+        RETURNING id
+    )
+SELECT
+    (SELECT count(*) FROM deleted_base) AS base_purls_deleted,
+    (SELECT count(*) FROM deleted_versioned) AS versioned_purls_deleted,
+    (SELECT count(*) FROM deleted_qualified) AS qualified_purls_deleted,
+    (
+        SELECT coalesce(jsonb_agg(sample.purl), '[]'::jsonb)
+        FROM (SELECT purl FROM deleted_qualified LIMIT $1) sample
+    ) AS sample_deleted_purls
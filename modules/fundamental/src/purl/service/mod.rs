@@ -1,33 +1,42 @@
+pub mod federated;
+
 use std::collections::{HashMap, HashSet};
 
 use crate::{
     Error,
     common::license_filtering::LICENSE,
     purl::model::{
-        RecommendEntry, VexStatus, VulnerabilityStatus,
+        GcReport, RecommendEntry, VexStatus, VulnerabilityStatus,
         details::{
-            base_purl::BasePurlDetails, purl::PurlDetails, versioned_purl::VersionedPurlDetails,
+            base_purl::{BasePurlDetails, VersionSort},
+            purl::PurlDetails,
+            versioned_purl::VersionedPurlDetails,
         },
+        stats::{PurlStats, ReferencedBasePurl},
         summary::{
-            base_purl::BasePurlSummary, purl::PurlSummary, remediation::RemediationSummary,
-            r#type::TypeSummary,
+            base_purl::BasePurlSummary,
+            purl::PurlSummary,
+            remediation::RemediationSummary,
+            r#type::{TypeCounts, TypeSummary},
         },
+        version_constraint::VersionConstraint,
     },
+    sbom::{model::SbomSummary, service::SbomService},
 };
 use itertools::Itertools;
 use regex::Regex;
 use sea_orm::{
     ColumnTrait, Condition, ConnectionTrait, EntityTrait, FromQueryResult, LoaderTrait,
-    QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, prelude::Uuid,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Statement, prelude::Uuid,
 };
-use sea_query::{Asterisk, ColumnType, Expr, Func, JoinType, Order, SimpleExpr, UnionType};
+use sea_query::{Asterisk, ColumnType, Expr, Func, JoinType, SimpleExpr, UnionType};
 use tracing::{Instrument, info_span, instrument};
 use trustify_common::{
     db::{
         chunk::chunked_with,
         limiter::{LimitedResult, LimiterTrait},
         pagination_cache::PaginationCache,
-        query::{Columns, Filtering, IntoColumns, Query, q},
+        query::{Columns, Filtering, IntoColumns, Query},
     },
     model::{PaginatedResults, Pagination},
     purl::{Purl, PurlErr},
@@ -35,8 +44,10 @@ use trustify_common::{
 use trustify_entity::{
     advisory, base_purl, license, purl_status,
     qualified_purl::{self, CanonicalPurl},
-    remediation, remediation_purl_status, sbom_license_expanded, sbom_node, sbom_node_purl_ref,
-    sbom_package_license, status, version_range, versioned_purl, vulnerability,
+    remediation, remediation_purl_status, sbom, sbom_license_expanded, sbom_node,
+    sbom_node_purl_ref, sbom_package_license, status, version_range,
+    version_scheme::VersionScheme,
+    versioned_purl, vulnerability,
 };
 use trustify_module_ingestor::common::Deprecation;
 
@@ -134,25 +145,54 @@ impl PurlService {
         &self,
         connection: &C,
     ) -> Result<Vec<TypeSummary>, Error> {
-        #[derive(FromQueryResult)]
-        struct Ecosystem {
-            r#type: String,
+        TypeSummary::all(connection).await
+    }
+
+    /// Gather statistics about the PURLs: the counts per type, their totals, and the base PURLs
+    /// referenced by the most SBOMs
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn purl_stats<C: ConnectionTrait>(
+        &self,
+        top: u64,
+        connection: &C,
+    ) -> Result<PurlStats, Error> {
+        const SQL: &str = r#"
+SELECT
+    base_purl.id,
+    base_purl.type,
+    base_purl.namespace,
+    base_purl.name,
+    count(DISTINCT sbom_node_purl_ref.sbom_id) AS sboms
+FROM base_purl
+JOIN versioned_purl ON versioned_purl.base_purl_id = base_purl.id
+JOIN qualified_purl ON qualified_purl.versioned_purl_id = versioned_purl.id
+JOIN sbom_node_purl_ref ON sbom_node_purl_ref.qualified_purl_id = qualified_purl.id
+GROUP BY base_purl.id
+ORDER BY sboms DESC, base_purl.type, base_purl.namespace, base_purl.name
+LIMIT $1
+"#;
+
+        let types = TypeSummary::all(connection).await?;
+
+        let mut totals = TypeCounts::default();
+        for summary in &types {
+            totals += summary.counts;
         }
 
-        let ecosystems: Vec<_> = base_purl::Entity::find()
-            .select_only()
-            .column(base_purl::Column::Type)
-            .group_by(base_purl::Column::Type)
-            .distinct()
-            .order_by(base_purl::Column::Type, Order::Asc)
-            .into_model::<Ecosystem>()
+        let most_referenced =
+            ReferencedBasePurl::find_by_statement(Statement::from_sql_and_values(
+                connection.get_database_backend(),
+                SQL,
+                [(top as i64).into()],
+            ))
             .all(connection)
-            .await?
-            .into_iter()
-            .map(|e| e.r#type)
-            .collect();
+            .await?;
 
-        TypeSummary::from_names(&ecosystems, connection).await
+        Ok(PurlStats {
+            totals,
+            types,
+            most_referenced,
+        })
     }
 
     #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
@@ -174,6 +214,7 @@ impl PurlService {
         Ok(PaginatedResults {
             items: BasePurlSummary::from_entities(&items).await?,
             total,
+            next_cursor: None,
         })
     }
 
@@ -197,7 +238,7 @@ impl PurlService {
 
         if let Some(package) = query.one(connection).await? {
             Ok(Some(
-                BasePurlDetails::from_entity(&package, connection).await?,
+                BasePurlDetails::from_entity(&package, VersionSort::None, connection).await?,
             ))
         } else {
             Ok(None)
@@ -240,6 +281,7 @@ impl PurlService {
     pub async fn base_purl_by_uuid<C: ConnectionTrait>(
         &self,
         base_purl_uuid: &Uuid,
+        sort: VersionSort,
         connection: &C,
     ) -> Result<Option<BasePurlDetails>, Error> {
         if let Some(package) = base_purl::Entity::find_by_id(*base_purl_uuid)
@@ -247,7 +289,7 @@ impl PurlService {
             .await?
         {
             Ok(Some(
-                BasePurlDetails::from_entity(&package, connection).await?,
+                BasePurlDetails::from_entity(&package, sort, connection).await?,
             ))
         } else {
             Ok(None)
@@ -258,6 +300,7 @@ impl PurlService {
     pub async fn base_purl_by_purl<C: ConnectionTrait>(
         &self,
         purl: &Purl,
+        sort: VersionSort,
         connection: &C,
     ) -> Result<Option<BasePurlDetails>, Error> {
         let mut query = base_purl::Entity::find()
@@ -272,7 +315,7 @@ impl PurlService {
 
         if let Some(base_purl) = query.one(connection).await? {
             Ok(Some(
-                BasePurlDetails::from_entity(&base_purl, connection).await?,
+                BasePurlDetails::from_entity(&base_purl, sort, connection).await?,
             ))
         } else {
             Ok(None)
@@ -297,6 +340,61 @@ impl PurlService {
         }
     }
 
+    /// Find the versions of a base PURL matching a version constraint
+    ///
+    /// Versions are compared by the database, the same way as when matching advisories. If no
+    /// version scheme is provided, the scheme is derived from the type of the PURL. Returns `None`
+    /// if the base PURL is not known.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn versioned_purls_in_range<C: ConnectionTrait>(
+        &self,
+        base_purl_uuid: &Uuid,
+        constraint: VersionConstraint,
+        scheme: Option<VersionScheme>,
+        connection: &C,
+    ) -> Result<Option<Vec<VersionedPurlDetails>>, Error> {
+        let Some(package) = base_purl::Entity::find_by_id(*base_purl_uuid)
+            .one(connection)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let scheme = scheme.unwrap_or_else(|| VersionScheme::from(package.r#type.as_str()));
+        let range = constraint.into_version_range(scheme);
+
+        let package_versions = versioned_purl::Entity::find()
+            .filter(versioned_purl::Column::BasePurlId.eq(package.id))
+            .filter(Expr::cust_with_values(
+                "version_matches(version, ROW($1, $2, $3, $4, $5, $6)::version_range)",
+                [
+                    range.id.into(),
+                    range.version_scheme_id.to_string().into(),
+                    range.low_version.into(),
+                    range.low_inclusive.into(),
+                    range.high_version.into(),
+                    range.high_inclusive.into(),
+                ] as [sea_orm::Value; 6],
+            ))
+            .order_by_asc(versioned_purl::Column::Version)
+            .all(connection)
+            .await?;
+
+        let mut result = Vec::with_capacity(package_versions.len());
+        for package_version in &package_versions {
+            result.push(
+                VersionedPurlDetails::from_entity(
+                    Some(package.clone()),
+                    package_version,
+                    connection,
+                )
+                .await?,
+            );
+        }
+
+        Ok(Some(result))
+    }
+
     #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
     pub async fn versioned_purl_by_purl<C: ConnectionTrait>(
         &self,
@@ -370,6 +468,103 @@ impl PurlService {
         }
     }
 
+    /// List the SBOMs containing a fully-qualified PURL
+    ///
+    /// The inverse of listing the packages of an SBOM. An unknown PURL results in an empty list.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn sboms_for_purl<C: ConnectionTrait>(
+        &self,
+        purl_id: Uuid,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<SbomSummary>, Error> {
+        let select = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
+            .join(JoinType::Join, sbom::Relation::Node.def())
+            .join(JoinType::Join, sbom_node::Relation::Purl.def())
+            .filter(sbom_node_purl_ref::Column::QualifiedPurlId.eq(purl_id));
+
+        SbomService::new(self.cache.clone())
+            .related_sboms(select, paginated, Query::default(), connection)
+            .await
+    }
+
+    /// Resolve the details of a set of fully-qualified PURLs
+    ///
+    /// Unlike calling [`Self::purl_by_purl`] for each of them, the qualified PURLs, their
+    /// versioned and base PURLs, as well as their advisories and licenses, are fetched together.
+    /// So the number of queries doesn't grow with the number of requested PURLs. The result has
+    /// an entry for each requested PURL, which is `None` if the PURL is not known.
+    #[instrument(skip(self, purls, connection), fields(purls = purls.len()), err(level=tracing::Level::INFO))]
+    pub async fn purls_by_purls<C: ConnectionTrait>(
+        &self,
+        purls: &[Purl],
+        deprecation: Deprecation,
+        connection: &C,
+    ) -> Result<HashMap<Purl, Option<PurlDetails>>, Error> {
+        let canonicals: Vec<_> = purls
+            .iter()
+            .map(|purl| CanonicalPurl::from(purl.clone()))
+            .unique()
+            .collect();
+
+        let mut qualified = Vec::new();
+        for chunk in &chunked_with(1, canonicals.into_iter()) {
+            qualified.extend(
+                qualified_purl::Entity::find()
+                    .filter(qualified_purl::Column::Purl.is_in(chunk))
+                    .all(connection)
+                    .await?,
+            );
+        }
+
+        let versioned = qualified
+            .load_one(versioned_purl::Entity, connection)
+            .await?;
+        let versioned = qualified
+            .iter()
+            .zip(versioned)
+            .map(|(qualified, versioned)| {
+                versioned.ok_or_else(|| {
+                    Error::Data(format!(
+                        "underlying package-version missing for {}",
+                        qualified.id
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let base = versioned.load_one(base_purl::Entity, connection).await?;
+
+        let entities = qualified
+            .into_iter()
+            .zip(versioned)
+            .zip(base)
+            .map(|((qualified, versioned), base)| {
+                let base = base.ok_or(Error::Data("underlying package missing".to_string()))?;
+                Ok((base, versioned, qualified))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut details = HashMap::with_capacity(entities.len());
+        for chunk in &chunked_with(1, entities.into_iter()) {
+            let chunk: Vec<_> = chunk.collect();
+            let keys: Vec<_> = chunk
+                .iter()
+                .map(|(_, _, qualified)| qualified.purl.clone())
+                .collect();
+            let chunk = PurlDetails::from_entities(chunk, deprecation, connection).await?;
+            details.extend(keys.into_iter().zip(chunk));
+        }
+
+        Ok(purls
+            .iter()
+            .map(|purl| {
+                let found = details.get(&CanonicalPurl::from(purl.clone())).cloned();
+                (purl.clone(), found)
+            })
+            .collect())
+    }
+
     pub async fn base_purls<C: ConnectionTrait>(
         &self,
         query: Query,
@@ -388,6 +583,7 @@ impl PurlService {
         Ok(PaginatedResults {
             items: BasePurlSummary::from_entities(&items).await?,
             total,
+            next_cursor: None,
         })
     }
 
@@ -415,10 +611,7 @@ impl PurlService {
         )?;
 
         // Add license filtering if license query is present
-        if let Some(license_query) = query
-            .get_constraint_for_field(LICENSE)
-            .map(|constraint| q(&format!("{constraint}")))
-        {
+        if let Some(license_query) = query.get_query_for_field(LICENSE)? {
             let base = || {
                 sbom_node_purl_ref::Entity::find()
                     .select_only()
@@ -483,6 +676,7 @@ impl PurlService {
         Ok(PaginatedResults {
             items: PurlSummary::from_entities(&items),
             total,
+            next_cursor: None,
         })
     }
 
@@ -795,6 +989,88 @@ impl PurlService {
             .max_by(|(_, a), (_, b)| a.pre.cmp(&b.pre))
             .map(|(vp, _)| vp)
     }
+
+    /// Merge qualified PURLs sharing the same canonical PURL.
+    ///
+    /// The oldest of the duplicates is kept, all SBOM references are moved over to it, and the
+    /// others get deleted. Returns the number of deleted qualified PURLs.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn gc_duplicate_purls<C: ConnectionTrait>(
+        &self,
+        connection: &C,
+    ) -> Result<u64, Error> {
+        let stmt = Statement::from_string(
+            connection.get_database_backend(),
+            // it looks much more readable in an SQL file
+            include_str!("gc_duplicate_purls.sql"),
+        );
+
+        let result = connection
+            .execute(stmt)
+            .instrument(info_span!("gc_duplicate_purls"))
+            .await?;
+
+        log::info!(
+            "Removed {} duplicate qualified PURLs",
+            result.rows_affected()
+        );
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete all PURLs which are neither referenced by an SBOM nor by an advisory.
+    ///
+    /// Returns the number of deleted PURLs, and a sample of up to `sample_size` deleted
+    /// qualified PURLs.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn gc_purls<C: ConnectionTrait>(
+        &self,
+        sample_size: u64,
+        connection: &C,
+    ) -> Result<GcReport, Error> {
+        #[derive(FromQueryResult)]
+        struct Row {
+            base_purls_deleted: i64,
+            versioned_purls_deleted: i64,
+            qualified_purls_deleted: i64,
+            sample_deleted_purls: serde_json::Value,
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            connection.get_database_backend(),
+            // it looks much more readable in an SQL file
+            include_str!("gc_purls.sql"),
+            [(sample_size as i64).into()],
+        );
+
+        let row = Row::find_by_statement(stmt)
+            .one(connection)
+            .instrument(info_span!("gc_purls"))
+            .await?
+            .ok_or_else(|| Error::Data("garbage collection returned no result".to_string()))?;
+
+        let sample: Vec<CanonicalPurl> = serde_json::from_value(row.sample_deleted_purls)
+            .map_err(|err| Error::Data(format!("invalid deleted PURL: {err}")))?;
+
+        let report = GcReport {
+            base_purls_deleted: row.base_purls_deleted as u64,
+            versioned_purls_deleted: row.versioned_purls_deleted as u64,
+            qualified_purls_deleted: row.qualified_purls_deleted as u64,
+            sample_deleted_purls: sample
+                .into_iter()
+                .map(|purl| Purl::from(purl).to_string())
+                .collect(),
+        };
+
+        log::info!(
+            "Removed {} base, {} versioned, and {} qualified unreferenced PURLs",
+            report.base_purls_deleted,
+            report.versioned_purls_deleted,
+            report.qualified_purls_deleted
+        );
+
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
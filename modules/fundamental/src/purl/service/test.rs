@@ -1,5 +1,11 @@
 use crate::purl::{model::details::purl::StatusContext, service::PurlService};
-use std::str::FromStr;
+use std::{
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 use test_context::test_context;
 use test_log::test;
 use trustify_common::{
@@ -788,6 +794,69 @@ async fn qualified_packages_filter_by_license(ctx: &TrustifyContext) -> Result<(
     Ok(())
 }
 
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn qualified_packages_filter_by_license_or(
+    ctx: &TrustifyContext,
+) -> Result<(), anyhow::Error> {
+    let service = PurlService::new(PaginationCache::for_test());
+
+    let _mtv = ctx.ingest_document("spdx/mtv-2.6.json").await?;
+
+    // same as "license~GPLv3+ with exceptions|Apache"
+    let results = service
+        .purls(
+            q("license~GPLv3+ with exceptions|license~Apache"),
+            Limit(100),
+            &ctx.db,
+        )
+        .await?;
+
+    log::debug!("{results:#?}");
+    assert_eq!(57, results.items.len());
+
+    // OR-ing a license with another field can't be split from the license subquery
+    let result = service
+        .purls(
+            q("license~Apache|purl:name=libstdc++"),
+            Paginated::default(),
+            &ctx.db,
+        )
+        .await;
+
+    assert!(matches!(result, Err(crate::Error::Query(_))), "{result:?}");
+
+    // as AND binds tighter, the license can't be OR-ed within an AND with another field either
+    let result = service
+        .purls(
+            q("license~Apache|license~GPLv3+ with exceptions&purl:name=libstdc++"),
+            Paginated::default(),
+            &ctx.db,
+        )
+        .await;
+
+    assert!(matches!(result, Err(crate::Error::Query(_))), "{result:?}");
+
+    // but AND-ing alternative values of the license with other fields is fine
+    let results = service
+        .purls(
+            q("license~GPLv3+ with exceptions|Apache&purl:name=libstdc++|libgcc"),
+            Limit(100),
+            &ctx.db,
+        )
+        .await?;
+
+    log::debug!("{results:#?}");
+    assert!(
+        results
+            .items
+            .iter()
+            .all(|item| ["libstdc++", "libgcc"].contains(&item.head.purl.name.as_str()))
+    );
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn statuses(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -948,6 +1017,7 @@ async fn base_purl_by_purl(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     let results = service
         .base_purl_by_purl(
             &Purl::from_str("pkg:maven/org.apache/log4j@1.2.3")?,
+            Default::default(),
             &ctx.db,
         )
         .await?;
@@ -1154,3 +1224,75 @@ async fn product_status_version_filtering(ctx: &TrustifyContext) -> Result<(), a
 
     Ok(())
 }
+
+/// Test that resolving a batch of PURLs doesn't issue queries per PURL
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn purls_by_purls_bounded_queries(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let service = PurlService::new(PaginationCache::for_test());
+
+    ctx.ingest_document("csaf/rhsa-2024_3666.json").await?;
+
+    let known: Vec<_> = service
+        .purls(
+            Query::default(),
+            Paginated {
+                limit: 100,
+                ..Default::default()
+            },
+            &ctx.db,
+        )
+        .await?
+        .items
+        .into_iter()
+        .map(|purl| purl.head.purl)
+        .collect();
+    let tomcat_jsp = known
+        .iter()
+        .find(|purl| purl.to_string().contains("tomcat-jsp"))
+        .expect("must find tomcat-jsp")
+        .clone();
+
+    // fill up the batch with unknown PURLs
+    let mut purls = known;
+    let mut n = 0;
+    while purls.len() < 100 {
+        purls.push(Purl::from_str(&format!(
+            "pkg:maven/org.example/unknown@1.{n}"
+        ))?);
+        n += 1;
+    }
+
+    let queries = Arc::new(AtomicUsize::new(0));
+    let mut db = ctx.db.clone();
+    db.set_metric_callback({
+        let queries = queries.clone();
+        move |_| {
+            queries.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+
+    let single = service
+        .purls_by_purls(std::slice::from_ref(&tomcat_jsp), Default::default(), &db)
+        .await?;
+    let single_queries = queries.swap(0, Ordering::Relaxed);
+
+    let batch = service
+        .purls_by_purls(&purls, Default::default(), &db)
+        .await?;
+    let batch_queries = queries.load(Ordering::Relaxed);
+
+    // the number of queries doesn't depend on the number of PURLs
+    assert_eq!(batch.len(), 100);
+    assert_eq!(single_queries, batch_queries);
+    assert!(batch_queries <= 16, "too many queries: {batch_queries}");
+
+    // and a PURL resolves to the same details, no matter the size of the batch
+    let single = single[&tomcat_jsp].as_ref().expect("must find tomcat-jsp");
+    let batched = batch[&tomcat_jsp].as_ref().expect("must find tomcat-jsp");
+    assert_eq!(single.advisories, batched.advisories);
+    assert_eq!(1, batched.advisories.len());
+    assert_eq!(2, batched.advisories[0].status.len());
+
+    Ok(())
+}
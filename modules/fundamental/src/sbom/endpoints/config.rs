@@ -1,5 +1,11 @@
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+use crate::{license::model::policy::LicensePolicy, sbom::model::quality::QualityWeights};
+
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct Config {
     /// An upload limit in bytes. Zero meaning "unlimited".
     pub upload_limit: usize,
+    /// The weights used when scoring the quality of an SBOM
+    pub quality_weights: QualityWeights,
+    /// The license policy uploaded SBOMs must comply with, if any
+    pub license_policy: Option<LicensePolicy>,
 }
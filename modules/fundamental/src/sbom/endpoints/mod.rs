@@ -10,21 +10,35 @@ use uuid::Uuid;
 
 use crate::{
     Error,
-    common::LicenseRefMapping,
+    common::{LicenseRefMapping, service::delete_orphaned_doc},
     license::{
         get_sanitize_filename,
+        model::{
+            LicenseConflict, SbomLicenseObligations,
+            policy::{LicensePolicy, PolicyEvaluation},
+        },
         service::{LicenseService, license_export::LicenseExporter},
     },
     sbom::{
         model::{
-            SbomExternalPackageReference, SbomModel, SbomNodeReference, SbomPackage,
-            SbomPackageRelation, SbomSummary, Which, details::SbomAdvisory,
+            CpeEntry, PackageExternalReference, ReprocessResult, SbomAttestation,
+            SbomExternalPackageReference, SbomFormulation, SbomModel, SbomNodeReference,
+            SbomPackage, SbomPackageRelation, SbomSnippet, SbomSummary, Which,
+            compare::{SbomCompareRequest, SbomComparison},
+            details::SbomAdvisory,
+            quality::{QualityWeights, SbomQualityScore},
+            validate::ValidationReport,
+            vex::OpenVexDocument,
         },
-        service::{SbomService, sbom::FetchOptions},
+        service::{SbomService, sbom::FetchOptions, vex::VexExportService},
     },
     sbom_group::service::SbomGroupService,
 };
-use actix_web::{HttpResponse, Responder, delete, get, http::header, post, web};
+use actix_web::{
+    HttpResponse, Responder, delete, get,
+    http::header::{self, ETag, EntityTag, IfNoneMatch},
+    post, put, web,
+};
 use config::Config;
 use futures_util::TryStreamExt;
 use sea_orm::TransactionTrait;
@@ -39,7 +53,7 @@ use trustify_common::{
     db::{self, pagination_cache::PaginationCache, query::Query},
     decompress::decompress_async,
     id::Id,
-    model::{BinaryData, Paginated, PaginatedResults},
+    model::{BinaryData, CursorPaginated, Paginated, PaginatedResults},
 };
 use trustify_entity::{labels::Labels, relationship::Relationship};
 use trustify_module_ingestor::{
@@ -60,6 +74,8 @@ pub fn configure(
     db_rw: db::ReadWrite,
     db_ro: db::ReadOnly,
     upload_limit: usize,
+    quality_weights: QualityWeights,
+    license_policy: Option<LicensePolicy>,
     cache: PaginationCache,
 ) {
     let sbom_service = SbomService::new(cache);
@@ -68,7 +84,12 @@ pub fn configure(
         .app_data(web::Data::new(db_rw))
         .app_data(web::Data::new(db_ro))
         .app_data(web::Data::new(sbom_service))
-        .app_data(web::Data::new(Config { upload_limit }))
+        .app_data(web::Data::new(VexExportService::new()))
+        .app_data(web::Data::new(Config {
+            upload_limit,
+            quality_weights,
+            license_policy,
+        }))
         .service(v2::all)
         .service(v3::all)
         .service(all_related)
@@ -76,21 +97,40 @@ pub fn configure(
         .service(all_models)
         .service(get)
         .service(get_sbom_advisories)
+        .service(list_vulnerabilities)
+        .service(get_sbom_vex)
+        .service(get_sbom_quality)
+        .service(compare)
         .service(delete)
         .service(delete_many)
+        .service(restore)
+        .service(purge)
+        .service(reprocess)
         .service(packages)
+        .service(package_cpes)
+        .service(package_external_refs)
+        .service(external_refs)
         .service(models)
+        .service(formulations)
+        .service(snippets)
+        .service(attestations)
         .service(related)
         .service(upload)
+        .service(validate)
         .service(download)
+        .service(source)
         .service(label::set)
         .service(label::update)
         .service(label::all)
         .service(get_unique_licenses)
+        .service(get_license_conflicts)
+        .service(get_license_obligations)
         .service(get_license_export);
 }
 
 const CONTENT_TYPE_GZIP: &str = "application/gzip";
+const CONTENT_TYPE_SPDX: &str = "application/spdx+json";
+const CONTENT_TYPE_CYCLONEDX: &str = "application/vnd.cyclonedx+json";
 
 #[utoipa::path(
     tag = "sbom",
@@ -119,6 +159,65 @@ pub async fn get_unique_licenses(
     }
 }
 
+/// Find pairs of incompatible licenses, used together by packages of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listLicenseConflicts",
+    params(
+        ("id", Path, description = "ID of the SBOM to check"),
+    ),
+    responses(
+        (status = 200, description = "Pairs of incompatible licenses", body = Vec<LicenseConflict>),
+        (status = 404, description = "The SBOM could not be found"),
+    ),
+)]
+#[get("/v3/sbom/{id}/license-conflicts")]
+pub async fn get_license_conflicts(
+    fetcher: web::Data<LicenseService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let tx = db.begin().await?;
+    match fetcher.license_conflicts(id, &tx).await? {
+        Some(conflicts) => Ok(HttpResponse::Ok().json(conflicts)),
+        None => Ok(HttpResponse::NotFound().into()),
+    }
+}
+
+/// Get the obligations triggered by the licenses of the packages of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "getLicenseObligations",
+    params(
+        ("id" = Id, Path),
+    ),
+    responses(
+        (status = 200, description = "The license obligations of the SBOM", body = SbomLicenseObligations),
+        (status = 404, description = "The SBOM could not be found"),
+    ),
+)]
+#[get("/v3/sbom/{id}/license-obligations")]
+pub async fn get_license_obligations(
+    fetcher: web::Data<SbomService>,
+    license: web::Data<LicenseService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let tx = db.begin().await?;
+
+    let Some((sbom, _, _)) = fetcher.fetch_sbom(id, &tx).await? else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    let obligations = license.obligations_for_sbom(sbom.sbom_id, &tx).await?;
+
+    Ok(HttpResponse::Ok().json(SbomLicenseObligations::from(obligations)))
+}
+
 #[utoipa::path(
     tag = "sbom",
     operation_id = "getLicenseExport",
@@ -183,7 +282,7 @@ mod v2 {
         operation_id = "v2/listSboms",
         params(
             Query,
-            Paginated,
+            CursorPaginated,
             GroupFilterQuery,
         ),
         responses(
@@ -196,7 +295,7 @@ mod v2 {
         fetch: web::Data<SbomService>,
         db: web::Data<db::ReadOnly>,
         web::Query(search): web::Query<Query>,
-        web::Query(paginated): web::Query<Paginated>,
+        web::Query(paginated): web::Query<CursorPaginated>,
         QsQuery(group_filter): QsQuery<GroupFilterQuery>,
         authorizer: web::Data<Authorizer>,
         user: UserInformation,
@@ -234,7 +333,7 @@ mod v3 {
         operation_id = "listSboms",
         params(
             Query,
-            Paginated,
+            CursorPaginated,
             GroupFilterQuery,
             SbomListParams,
         ),
@@ -248,7 +347,7 @@ mod v3 {
         fetch: web::Data<SbomService>,
         db: web::Data<db::ReadOnly>,
         web::Query(search): web::Query<Query>,
-        web::Query(paginated): web::Query<Paginated>,
+        web::Query(paginated): web::Query<CursorPaginated>,
         web::Query(params): web::Query<SbomListParams>,
         QsQuery(group_filter): QsQuery<GroupFilterQuery>,
         authorizer: web::Data<Authorizer>,
@@ -367,12 +466,35 @@ pub async fn get(
     }
 }
 
+#[derive(Clone, Debug, Default, serde::Deserialize, IntoParams)]
+pub(crate) struct SbomAdvisoryParams {
+    /// Comma separated list of statuses to include, like `affected,under_investigation`.
+    ///
+    /// Defaults to `affected`. An empty value includes all statuses.
+    statuses: Option<String>,
+}
+
+impl SbomAdvisoryParams {
+    pub(crate) fn statuses(&self) -> Vec<String> {
+        match &self.statuses {
+            None => vec!["affected".to_string()],
+            Some(statuses) => statuses
+                .split(',')
+                .map(str::trim)
+                .filter(|status| !status.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
 /// Get advisories for an SBOM
 #[utoipa::path(
     tag = "sbom",
     operation_id = "getSbomAdvisories",
     params(
         ("id" = Id, Path),
+        SbomAdvisoryParams,
     ),
     responses(
         (status = 200, description = "Matching SBOM", body = Vec<SbomAdvisory>),
@@ -384,18 +506,147 @@ pub async fn get_sbom_advisories(
     fetcher: web::Data<SbomService>,
     db: web::Data<db::ReadOnly>,
     id: web::Path<String>,
+    web::Query(params): web::Query<SbomAdvisoryParams>,
     _: Require<GetSbomAdvisories>,
 ) -> Result<impl Responder, Error> {
     let id = Id::from_str(&id).map_err(Error::IdKey)?;
     let tx = db.begin().await?;
 
-    let statuses: Vec<String> = vec!["affected".to_string()];
-    match fetcher.fetch_sbom_details(id, statuses, &tx).await? {
+    match fetcher
+        .fetch_sbom_details(id, params.statuses(), &tx)
+        .await?
+    {
         Some(v) => Ok(HttpResponse::Ok().json(v.advisories)),
         None => Ok(HttpResponse::NotFound().finish()),
     }
 }
 
+/// List the advisories affecting an SBOM
+///
+/// Unlike getting the advisories of an SBOM, the result is paginated.
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listSbomVulnerabilities",
+    params(
+        ("id" = Id, Path),
+        SbomAdvisoryParams,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Advisories affecting the SBOM", body = PaginatedResults<SbomAdvisory>),
+        (status = 404, description = "The SBOM could not be found"),
+    ),
+)]
+#[get("/v3/sbom/{id}/vulnerabilities")]
+pub async fn list_vulnerabilities(
+    fetcher: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    web::Query(params): web::Query<SbomAdvisoryParams>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<GetSbomAdvisories>,
+) -> Result<impl Responder, Error> {
+    let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let tx = db.begin().await?;
+
+    match fetcher
+        .vulnerabilities_for_sbom(id, params.statuses(), paginated, &tx)
+        .await?
+    {
+        Some(v) => Ok(HttpResponse::Ok().json(v)),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+/// Export the package statuses of an SBOM as an OpenVEX document
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "getSbomVex",
+    params(
+        ("id" = Id, Path),
+    ),
+    responses(
+        (status = 200, description = "OpenVEX document of the SBOM", body = OpenVexDocument),
+        (status = 404, description = "The SBOM could not be found"),
+    ),
+)]
+#[get("/v3/sbom/{id}/vex")]
+pub async fn get_sbom_vex(
+    fetcher: web::Data<SbomService>,
+    exporter: web::Data<VexExportService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    _: Require<GetSbomAdvisories>,
+) -> Result<impl Responder, Error> {
+    let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let tx = db.begin().await?;
+
+    match fetcher.fetch_sbom(id, &tx).await? {
+        Some((sbom, _, _)) => {
+            Ok(HttpResponse::Ok().json(exporter.export_for_sbom(sbom.sbom_id, &tx).await?))
+        }
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+/// Score how complete the component information of an SBOM is
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "getSbomQuality",
+    params(
+        ("id" = Id, Path),
+    ),
+    responses(
+        (status = 200, description = "Quality score of the SBOM", body = SbomQualityScore),
+        (status = 404, description = "The SBOM could not be found"),
+    ),
+)]
+#[get("/v3/sbom/{id}/quality")]
+pub async fn get_sbom_quality(
+    fetcher: web::Data<SbomService>,
+    config: web::Data<Config>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let tx = db.begin().await?;
+
+    let Some((sbom, _, _)) = fetcher.fetch_sbom(id, &tx).await? else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    match fetcher
+        .score_sbom(sbom.sbom_id, &config.quality_weights, &tx)
+        .await?
+    {
+        Some(score) => Ok(HttpResponse::Ok().json(score)),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+/// Compare the packages of two SBOMs
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "compareSboms",
+    request_body = SbomCompareRequest,
+    responses(
+        (status = 200, description = "The differences between the SBOMs", body = SbomComparison),
+        (status = 404, description = "One of the SBOMs could not be found"),
+    ),
+)]
+#[post("/v3/sbom/compare")]
+pub async fn compare(
+    service: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    web::Json(request): web::Json<SbomCompareRequest>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let comparison = service.compare(request.old, request.new, &tx).await?;
+    Ok(HttpResponse::Ok().json(comparison))
+}
+
 all!(GetSbomAdvisories -> ReadSbom, ReadAdvisory);
 
 async fn delete_blobs<T: StorageBackend>(digests: &[String], storage: &T) {
@@ -413,6 +664,8 @@ async fn delete_blobs<T: StorageBackend>(digests: &[String], storage: &T) {
 }
 
 /// Delete an SBOM
+///
+/// The SBOM is hidden, but kept until it gets purged. Until then, it can be restored.
 #[utoipa::path(
     tag = "sbom",
     operation_id = "deleteSbom",
@@ -434,17 +687,87 @@ pub async fn delete(
     let tx = db.begin().await?;
 
     let id = Id::from_str(&id)?;
-    if let Some((v, _, _)) = service.fetch_sbom(id, &tx).await?
-        && let digests = service.delete_sboms(vec![v.sbom_id], &tx).await?
-        && !digests.is_empty()
-    {
+    if let Some((v, _, _)) = service.fetch_sbom(id, &tx).await? {
+        let deleted = service.soft_delete_sboms(vec![v.sbom_id], &tx).await?;
         tx.commit().await?;
+        for sbom_id in deleted {
+            i.clear_graph_cache(sbom_id);
+        }
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Restore a deleted SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "restoreSbom",
+    params(
+        ("id" = Id, Path),
+    ),
+    responses(
+        (status = 204, description = "The SBOM was restored"),
+        (status = 404, description = "No deleted SBOM could be found"),
+    ),
+)]
+#[put("/v3/sbom/{id}/restore")]
+pub async fn restore(
+    service: web::Data<SbomService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+
+    let id = Id::from_str(&id)?;
+    match service.restore_sbom(id, &tx).await? {
+        Some(()) => {
+            tx.commit().await?;
+            Ok(HttpResponse::NoContent().finish())
+        }
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
+/// Permanently delete an SBOM, which must have been deleted before
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "purgeSbom",
+    params(
+        ("id" = Id, Path),
+    ),
+    responses(
+        (status = 204, description = "The SBOM was purged or did not exist"),
+        (status = 409, description = "The SBOM was not deleted before"),
+    ),
+)]
+#[delete("/v3/sbom/{id}/purge")]
+pub async fn purge(
+    i: web::Data<IngestorService>,
+    service: web::Data<SbomService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+
+    let id = Id::from_str(&id)?;
+    if service.fetch_sbom(id.clone(), &tx).await?.is_some() {
+        return Err(Error::Conflict(
+            "The SBOM must be deleted before it can be purged".into(),
+        ));
+    }
+
+    let digests = service.purge_sbom(id, &tx).await?;
+    tx.commit().await?;
+    if !digests.is_empty() {
         delete_blobs(&digests, i.storage()).await;
     }
     Ok(HttpResponse::NoContent().finish())
 }
 
 /// Delete multiple SBOMs
+///
+/// Like deleting a single SBOM, the SBOMs are kept until they get purged.
 #[utoipa::path(
     tag = "sbom",
     operation_id = "deleteSboms",
@@ -472,16 +795,51 @@ pub async fn delete_many(
         .filter_map(|x| Uuid::try_parse(&x).ok())
         .collect();
 
-    let digests = service.delete_sboms(ids, &tx).await?;
+    let deleted = service.soft_delete_sboms(ids, &tx).await?;
 
-    if !digests.is_empty() {
+    if !deleted.is_empty() {
         tx.commit().await?;
-        delete_blobs(&digests, i.storage()).await;
+        for sbom_id in deleted {
+            i.clear_graph_cache(sbom_id);
+        }
     }
 
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Reprocess an SBOM from its source document, using the current ingestor
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "reprocessSbom",
+    params(
+        ("id", Path, description = "ID of the SBOM"),
+    ),
+    responses(
+        (status = 200, description = "The SBOM was reprocessed", body = ReprocessResult),
+        (status = 400, description = "The source document is not a supported SBOM"),
+        (status = 404, description = "The SBOM or its source document was not found"),
+    ),
+)]
+#[post("/v3/admin/sbom/{id}/reprocess")]
+pub async fn reprocess(
+    i: web::Data<IngestorService>,
+    service: web::Data<SbomService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<Uuid>,
+    _: Require<CreateSbom>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = service
+        .reprocess_from_source(id.into_inner(), i.storage(), &tx)
+        .await?;
+    tx.commit().await?;
+
+    i.clear_graph_cache(result.sbom_id);
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
 /// Search for packages of an SBOM
 #[utoipa::path(
     tag = "sbom",
@@ -519,6 +877,91 @@ pub async fn packages(
     Ok(HttpResponse::Ok().json(result))
 }
 
+/// List the CPEs of a package of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listPackageCpes",
+    params(
+        ("id", Path, description = "ID of the SBOM"),
+        ("node_id", Path, description = "ID of the package node within the SBOM"),
+    ),
+    responses(
+        (status = 200, description = "CPEs of the package", body = Vec<CpeEntry>),
+    ),
+)]
+#[get("/v3/sbom/{id}/package/{node_id}/cpes")]
+pub async fn package_cpes(
+    fetch: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    path: web::Path<(Uuid, String)>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let (id, node_id) = path.into_inner();
+    let tx = db.begin().await?;
+    let result = fetch.cpes_for_node(id, &node_id, &tx).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// List the external references of a package of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listPackageExternalRefs",
+    params(
+        ("id", Path, description = "ID of the SBOM"),
+        ("node_id", Path, description = "ID of the package node within the SBOM"),
+    ),
+    responses(
+        (status = 200, description = "External references of the package", body = Vec<PackageExternalReference>),
+    ),
+)]
+#[get("/v3/sbom/{id}/package/{node_id}/external-refs")]
+pub async fn package_external_refs(
+    fetch: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    path: web::Path<(Uuid, String)>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let (id, node_id) = path.into_inner();
+    let tx = db.begin().await?;
+    let result = fetch.external_refs_for_node(id, &node_id, &tx).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, IntoParams)]
+struct ExternalRefsQuery {
+    /// Only return references of this type, like `vcs` or `website`
+    r#type: Option<String>,
+}
+
+/// List the external references of all packages of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listExternalRefs",
+    params(
+        ("id", Path, description = "ID of the SBOM"),
+        ExternalRefsQuery,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "External references of the packages", body = PaginatedResults<PackageExternalReference>),
+    ),
+)]
+#[get("/v3/sbom/{id}/external-refs")]
+pub async fn external_refs(
+    fetch: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<Uuid>,
+    web::Query(ExternalRefsQuery { r#type }): web::Query<ExternalRefsQuery>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = fetch
+        .fetch_sbom_external_refs(id.into_inner(), r#type.as_deref(), paginated, &tx)
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
 /// Search for AI models associated with an SBOM
 #[utoipa::path(
     tag = "sbom",
@@ -550,6 +993,91 @@ pub async fn models(
     Ok(HttpResponse::Ok().json(result))
 }
 
+/// Search for the CycloneDX formulations of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listFormulations",
+    params(
+        ("id", Path, description = "ID of the SBOM to get formulations for"),
+        Query,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Formulations", body = PaginatedResults<SbomFormulation>),
+    ),
+)]
+#[get("/v3/sbom/{id}/formulations")]
+pub async fn formulations(
+    fetch: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<Uuid>,
+    web::Query(search): web::Query<Query>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = fetch
+        .fetch_sbom_formulations(id.into_inner(), search, paginated, &tx)
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// List the SPDX snippets of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listSnippets",
+    params(
+        ("id", Path, description = "ID of the SBOM to get snippets for"),
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Snippets", body = PaginatedResults<SbomSnippet>),
+    ),
+)]
+#[get("/v3/sbom/{id}/snippets")]
+pub async fn snippets(
+    fetch: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<Uuid>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = fetch
+        .fetch_sbom_snippets(id.into_inner(), paginated, &tx)
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// Search for the CycloneDX attestations of an SBOM
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "listAttestations",
+    params(
+        ("id", Path, description = "ID of the SBOM to get attestations for"),
+        Query,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Attestations", body = PaginatedResults<SbomAttestation>),
+    ),
+)]
+#[get("/v3/sbom/{id}/attestations")]
+pub async fn attestations(
+    fetch: web::Data<SbomService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<Uuid>,
+    web::Query(search): web::Query<Query>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = fetch
+        .fetch_sbom_attestations(id.into_inner(), search, paginated, &tx)
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
 /// Search for all AI models
 #[utoipa::path(
     tag = "sbom",
@@ -587,9 +1115,29 @@ struct RelatedQuery {
     #[serde(default)]
     #[param(inline)]
     pub which: Which,
-    /// Optional relationship filter
+    /// Optional relationship filter, a comma separated list of relationship types
     #[serde(default)]
-    pub relationship: Option<Relationship>,
+    pub relationship: Option<String>,
+}
+
+impl RelatedQuery {
+    /// The relationship types to filter for, empty for all
+    fn relationships(&self) -> Result<Vec<Relationship>, Error> {
+        let Some(relationship) = &self.relationship else {
+            return Ok(vec![]);
+        };
+
+        relationship
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                Relationship::from_str(s).map_err(|_| {
+                    Error::BadRequest(format!("Unknown relationship type: {s}").into(), None)
+                })
+            })
+            .collect()
+    }
 }
 
 /// Search for related packages in an SBOM
@@ -618,6 +1166,7 @@ pub async fn related(
     _: Require<ReadSbom>,
 ) -> Result<impl Responder, Error> {
     let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let relationships = related.relationships()?;
     let tx = db.begin().await?;
 
     let Some((sbom, _, _)) = fetch.fetch_sbom(id, &tx).await? else {
@@ -634,7 +1183,7 @@ pub async fn related(
                 None => SbomNodeReference::All,
                 Some(id) => SbomNodeReference::Package(id),
             },
-            related.relationship,
+            relationships,
             &tx,
         )
         .await?;
@@ -683,6 +1232,7 @@ const fn default_format() -> Format {
         (status = 201, description = "Upload an SBOM", body = IngestResult),
         (status = 400, description = "The file could not be parsed as an SBOM"),
         (status = 400, description = "One or more group IDs are invalid or do not exist"),
+        (status = 422, description = "The SBOM violates the license policy", body = PolicyEvaluation),
     )
 )]
 #[post("/v3/sbom")]
@@ -691,6 +1241,7 @@ const fn default_format() -> Format {
 pub async fn upload(
     ingestor: web::Data<IngestorService>,
     sbom_group: web::Data<SbomGroupService>,
+    license: web::Data<LicenseService>,
     config: web::Data<Config>,
     db: web::Data<db::ReadWrite>,
     QsQuery(UploadQuery {
@@ -712,6 +1263,30 @@ pub async fn upload(
         .await
         .map_err(Error::Ingestor)?;
 
+    if let Some(policy) = &config.license_policy {
+        let sbom_id = Uuid::from_str(&result.id).map_err(|err| Error::Any(err.into()))?;
+        let evaluation = license.evaluate_policy(sbom_id, policy, &tx).await?;
+
+        if evaluation.is_violated() {
+            tx.rollback().await?;
+            log::info!("Rejected SBOM violating the license policy: {sbom_id}");
+
+            // the document got stored before ingesting it, which got rolled back now
+            if let Err(e) = delete_orphaned_doc(&bytes, ingestor.storage(), db.get_ref()).await {
+                log::error!("Ignoring {e}");
+            }
+
+            return Ok(HttpResponse::UnprocessableEntity().json(evaluation));
+        }
+
+        for warning in &evaluation.warnings {
+            log::warn!(
+                "SBOM {sbom_id} uses flagged license: {}",
+                warning.license_id
+            );
+        }
+    }
+
     if !group.is_empty() {
         sbom_group
             .update_assignments(&result.id, None, group, &tx)
@@ -722,14 +1297,71 @@ pub async fn upload(
     // but with the `urn:uuid:` prefix. Which was used for "key" fields. Which accepted
     // for than the actual ID. The whole naming is flawed and confusing. But in order to
     // keep the API stable, we need to return the ID with the prefix.
-    result.id = format!("urn:uuid:{}", result.id);
-
     tx.commit().await?;
 
+    ingestor
+        .resolve_external_refs(&mut result, cache, db.get_ref())
+        .await
+        .map_err(Error::Ingestor)?;
+
+    result.id = format!("urn:uuid:{}", result.id);
+
     log::info!("Uploaded SBOM: {}", result.id);
     Ok(HttpResponse::Created().json(result))
 }
 
+#[derive(Clone, Debug, serde::Deserialize, utoipa::IntoParams)]
+struct ValidateQuery {
+    /// The format of the uploaded document.
+    #[serde(default = "default_format")]
+    #[param(inline)]
+    format: Format,
+}
+
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "validateSbom",
+    request_body = Vec <u8>,
+    params(
+        ValidateQuery,
+    ),
+    responses(
+        (status = 200, description = "The result of validating the SBOM", body = ValidationReport),
+    )
+)]
+#[post("/v3/sbom/validate")]
+/// Validate an SBOM, without ingesting it
+///
+/// The SBOM is processed the same way as when uploading it, but nothing gets stored.
+pub async fn validate(
+    ingestor: web::Data<IngestorService>,
+    config: web::Data<Config>,
+    db: web::Data<db::ReadWrite>,
+    web::Query(ValidateQuery { format }): web::Query<ValidateQuery>,
+    content_type: Option<web::Header<header::ContentType>>,
+    bytes: web::Bytes,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    if format != Format::SBOM && !format.matches_hint(Format::SBOM) {
+        return Err(Error::BadRequest(
+            format!("Format {format} is not an SBOM format").into(),
+            None,
+        ));
+    }
+
+    let bytes = decompress_async(bytes, content_type.map(|ct| ct.0), config.upload_limit).await??;
+
+    // dropping the transaction, without committing it, rolls back the ingestion
+    let tx = db.begin().await?;
+
+    let report = match ingestor.validate(&bytes, format, &tx).await {
+        Ok(outcome) => outcome.into(),
+        Err(err) => ValidationReport::invalid(err.try_into()?),
+    };
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
 /// Download an SBOM
 #[utoipa::path(
     tag = "sbom",
@@ -769,3 +1401,83 @@ pub async fn download(
         None => HttpResponse::NotFound().finish(),
     })
 }
+
+/// Get the original source document of an SBOM
+///
+/// The content type is derived from the format the SBOM was ingested as. The SHA-256 digest of
+/// the document is used as ETag.
+#[utoipa::path(
+    tag = "sbom",
+    operation_id = "getSbomSource",
+    params(
+        ("id" = Id, Path),
+        ("if-none-match" = Option<String>, Header, description = "The ETag of a cached copy of the document"),
+    ),
+    responses(
+        (
+            status = 200, description = "The source document of the SBOM",
+            body = inline(BinaryData),
+            headers(
+                ("etag" = String, description = "SHA-256 digest of the document")
+            )
+        ),
+        (status = 304, description = "The document matches the provided ETag"),
+        (status = 404, description = "The SBOM or its source document could not be found"),
+    )
+)]
+#[get("/v3/sbom/{id}/source")]
+pub async fn source(
+    ingestor: web::Data<IngestorService>,
+    db: web::Data<db::ReadOnly>,
+    service: web::Data<SbomService>,
+    id: web::Path<String>,
+    if_none_match: Option<web::Header<IfNoneMatch>>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let id = Id::from_str(&id).map_err(Error::IdKey)?;
+    let tx = db.begin().await?;
+
+    let Some((sbom, _, source_document)) = service.fetch_sbom(id, &tx).await? else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    let etag = EntityTag::new_strong(source_document.sha256.clone());
+    let matches = match if_none_match.map(web::Header::into_inner) {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(items)) => items.iter().any(|item| item.weak_eq(&etag)),
+        None => false,
+    };
+    if matches {
+        return Ok(HttpResponse::NotModified()
+            .append_header((header::ETAG, ETag(etag)))
+            .finish());
+    }
+
+    let stream = ingestor
+        .storage()
+        .retrieve(StorageKey::from_sha256(&source_document.sha256))
+        .await
+        .map_err(Error::Storage)?
+        .map(|stream| stream.map_err(Error::Storage));
+
+    let Some(stream) = stream else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    let content_type = match sbom.labels.0.get("type").map(String::as_str) {
+        Some("spdx") => CONTENT_TYPE_SPDX,
+        Some("cyclonedx") => CONTENT_TYPE_CYCLONEDX,
+        _ => "application/json",
+    };
+    let filename =
+        get_sanitize_filename(sbom.document_id.unwrap_or_else(|| sbom.sbom_id.to_string()));
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .append_header((header::ETAG, ETag(etag)))
+        .append_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}.json\""),
+        ))
+        .streaming(stream))
+}
@@ -3,9 +3,14 @@ use crate::{
         Group, GroupRef, UpdateAssignments, create_groups, locate_id, read_assignments,
         resolve_group_refs,
     },
+    endpoints::Config,
+    license::model::policy::{LicensePolicy, LicenseViolationReason, PolicyEvaluation},
     purl::model::summary::purl::PurlSummary,
-    sbom::model::{SbomPackage, SbomSummary},
-    test::{caller, label::Api},
+    sbom::{
+        model::{SbomPackage, SbomSummary, compare::SbomComparison, validate::ValidationReport},
+        service::SbomService,
+    },
+    test::{caller, caller_with, label::Api},
 };
 use actix_http::StatusCode;
 use actix_web::{
@@ -18,14 +23,14 @@ use futures::future::join_all;
 use rstest::rstest;
 use serde_json::{Value, json};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     io::Read,
     path::{Path, PathBuf},
     str::FromStr,
 };
 use test_context::test_context;
 use test_log::test;
-use trustify_common::{id::Id, model::PaginatedResults};
+use trustify_common::{db::pagination_cache::PaginationCache, id::Id, model::PaginatedResults};
 use trustify_module_ingestor::{model::IngestResult, service::Format};
 use trustify_module_storage::service::{StorageBackend, StorageKey};
 use trustify_test_context::{
@@ -588,6 +593,87 @@ async fn fetch_unique_licenses(ctx: &TrustifyContext) -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn fetch_license_conflicts(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let id = ctx
+        .ingest_document("cyclonedx/rh/latest_filters/TC-3278/container/datagrid-datagrid-8/latest/binary-2025-12-04-62F0D268C8094C2.json")
+        .await?
+        .id
+        .to_string();
+
+    // packages are licensed under Apache-2.0, as well as GPL-2.0-only
+    let uri = format!("/api/v3/sbom/urn:uuid:{id}/license-conflicts");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::debug!("{:#}", json!(response));
+
+    let conflicts = response.as_array().expect("must be an array");
+    assert!(conflicts.iter().any(|conflict| {
+        conflict["license_a"] == "Apache-2.0" && conflict["license_b"] == "GPL-2.0-only"
+    }));
+    assert!(
+        conflicts
+            .iter()
+            .all(|conflict| conflict["license_a"] != "MIT" && conflict["license_b"] != "MIT")
+    );
+
+    // an unknown SBOM
+    let uri = format!("/api/v3/sbom/urn:uuid:{}/license-conflicts", Uuid::now_v7());
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response = app.call_service(req).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn fetch_license_obligations(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let id = ctx
+        .ingest_document("cyclonedx/rh/latest_filters/TC-3278/container/datagrid-datagrid-8/latest/binary-2025-12-04-62F0D268C8094C2.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/urn:uuid:{id}/license-obligations");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::debug!("{:#}", json!(response));
+
+    // the strongest license is the GPL
+    assert_eq!(response["strongest_copyleft"], "strong");
+
+    let obligations = response["obligations"]
+        .as_array()
+        .expect("must be an array");
+    assert!(obligations.contains(&json!({
+        "license_id": "MIT",
+        "requires_attribution": true,
+        "requires_source_disclosure": false,
+        "patent_grant": false,
+        "copyleft": null,
+    })));
+    assert!(obligations.contains(&json!({
+        "license_id": "GPL-2.0-only",
+        "requires_attribution": true,
+        "requires_source_disclosure": true,
+        "patent_grant": false,
+        "copyleft": "strong",
+    })));
+
+    // each license is only listed once
+    let ids = obligations
+        .iter()
+        .map(|obligation| obligation["license_id"].as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids.len(), ids.iter().collect::<BTreeSet<_>>().len());
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn get_packages_sbom_by_query(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -1007,7 +1093,7 @@ async fn license_export(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
         .expect("Content-Type header missing");
     assert_eq!(content_type, "application/gzip");
 
-    let body = actix_web::test::read_body(response).await;
+    let body = read_body(response).await;
     let mut decoder = GzDecoder::new(&body[..]);
     let mut decompressed = String::new();
     decoder.read_to_string(&mut decompressed)?;
@@ -1018,6 +1104,30 @@ async fn license_export(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Only the SBOM list supports cursors, other lists must reject them
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::list("/api/v3/sbom?cursor=", StatusCode::OK)]
+#[case::v2_list("/api/v2/sbom?cursor=", StatusCode::OK)]
+#[case::related(
+    "/api/v3/sbom/by-package?purl=pkg%3Acargo%2Fhyper&cursor=",
+    StatusCode::BAD_REQUEST
+)]
+#[test(actix_web::test)]
+async fn list_cursor(
+    ctx: &TrustifyContext,
+    #[case] uri: &str,
+    #[case] expected_status: StatusCode,
+) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::get().uri(uri).to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), expected_status);
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn upload(ctx: &TrustifyContext) -> anyhow::Result<()> {
@@ -1037,6 +1147,123 @@ async fn upload(ctx: &TrustifyContext) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Validating an SBOM must report its issues, but not ingest it
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn validate(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom/validate")
+        .set_payload(document_bytes("quarkus-bom-2.13.8.Final-redhat-00004.json").await?)
+        .to_request();
+    let report: ValidationReport = app.call_and_read_body_json(request).await;
+    assert!(report.valid);
+    assert!(report.errors.is_empty());
+
+    // broken JSON is reported, with the line of the issue
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom/validate")
+        .set_payload("{\n  \"spdxVersion\": \"SPDX-2.3\",\n  \"name\": }")
+        .to_request();
+    let report: ValidationReport = app.call_and_read_body_json(request).await;
+    assert!(!report.valid);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].line, Some(3));
+
+    // advisories can't be validated as SBOMs
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom/validate?format=csaf")
+        .set_payload(document_bytes("csaf/CVE-2023-20862.json").await?)
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // nothing got ingested
+
+    let request = TestRequest::get().uri("/api/v3/sbom").to_request();
+    let response: PaginatedResults<SbomSummary> = app.call_and_read_body_json(request).await;
+    assert_eq!(response.total, 0);
+
+    Ok(())
+}
+
+/// Validating an already ingested SBOM must report the existing SBOM
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn validate_existing(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+    let result = ctx.ingest_document("spdx/simple.json").await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom/validate")
+        .set_payload(document_bytes("spdx/simple.json").await?)
+        .to_request();
+    let report: ValidationReport = app.call_and_read_body_json(request).await;
+    assert!(report.valid);
+    assert_eq!(report.existing_id, Some(result.id));
+
+    Ok(())
+}
+
+/// Uploading an SBOM using a denied license must be rejected, and not get ingested
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn upload_license_policy(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let config = Config {
+        sbom_license_policy: Some(LicensePolicy {
+            deny: vec!["MIT".into()],
+            warn: vec!["Apache-2.0".into()],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let app = caller_with(ctx, config, PaginationCache::for_test()).await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom")
+        .set_payload(document_bytes("quarkus-bom-2.13.8.Final-redhat-00004.json").await?)
+        .to_request();
+
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let result: PolicyEvaluation = actix_web::test::read_body_json(response).await;
+    assert!(result.violations.iter().all(|violation| {
+        violation.license_id.eq_ignore_ascii_case("MIT")
+            && violation.reason == LicenseViolationReason::Denied
+    }));
+    assert!(result.is_violated());
+    assert!(!result.warnings.is_empty());
+
+    let request = TestRequest::get().uri("/api/v3/sbom").to_request();
+    let response: PaginatedResults<SbomSummary> = app.call_and_read_body_json(request).await;
+    assert_eq!(response.total, 0);
+
+    // the stored document must not be left behind
+    let key = StorageKey::try_from(Id::from_str(
+        "sha256:488c5d97daed3613746f0c246f4a3d1b26ea52ce43d6bdd33f4219f881a00c07",
+    )?)?;
+    assert!(ctx.storage.retrieve(key.clone()).await?.is_none());
+
+    // but it must be kept, if it belongs to an SBOM ingested before
+    ctx.ingest_document("quarkus-bom-2.13.8.Final-redhat-00004.json")
+        .await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom")
+        .set_payload(document_bytes("quarkus-bom-2.13.8.Final-redhat-00004.json").await?)
+        .to_request();
+
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(ctx.storage.retrieve(key).await?.is_some());
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[rstest]
 #[case::single_group([GroupRef::ByName(&["Group 1"])], StatusCode::CREATED, 1)]
@@ -1260,7 +1487,8 @@ async fn delete_sbom(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
 
     log::debug!("Code: {}", response.status());
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
-    assert!(storage.retrieve(key).await?.is_none());
+    // - the document is kept until the SBOM gets purged
+    assert!(storage.retrieve(key.clone()).await?.is_some());
 
     // Deleting again should be idempotent (204, not 404).
     let response = app
@@ -1275,6 +1503,89 @@ async fn delete_sbom(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
     assert!(response.into_body().try_into_bytes().unwrap().is_empty());
 
+    // Purging should remove the document
+    let response = app
+        .call_service(
+            TestRequest::delete()
+                .uri(&format!(
+                    "/api/v3/sbom/urn:uuid:{}/purge",
+                    result.id.clone()
+                ))
+                .to_request(),
+        )
+        .await;
+
+    log::debug!("Code: {}", response.status());
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(storage.retrieve(key).await?.is_none());
+
+    Ok(())
+}
+
+/// Test restoring a deleted sbom
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn restore_sbom(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let result = ctx
+        .ingest_document("quarkus-bom-2.13.8.Final-redhat-00004.json")
+        .await?;
+    let uri = format!("/api/v3/sbom/urn:uuid:{}", result.id);
+
+    // restoring an SBOM which isn't deleted should fail
+    let response = app
+        .call_service(
+            TestRequest::put()
+                .uri(&format!("{uri}/restore"))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // purging an SBOM which isn't deleted should fail
+    let response = app
+        .call_service(
+            TestRequest::delete()
+                .uri(&format!("{uri}/purge"))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = app
+        .call_service(TestRequest::delete().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // a deleted SBOM is hidden
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let req = TestRequest::get().uri("/api/v3/sbom").to_request();
+    let sboms: PaginatedResults<SbomSummary> = app.call_and_read_body_json(req).await;
+    assert!(sboms.items.is_empty());
+
+    let response = app
+        .call_service(
+            TestRequest::put()
+                .uri(&format!("{uri}/restore"))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // a restored SBOM is visible again
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let req = TestRequest::get().uri("/api/v3/sbom").to_request();
+    let sboms: PaginatedResults<SbomSummary> = app.call_and_read_body_json(req).await;
+    assert_eq!(sboms.items.len(), 1);
+
     Ok(())
 }
 
@@ -1334,6 +1645,16 @@ async fn delete_sboms(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
         app.call_service(req).await
     }
 
+    async fn purge<const N: usize>(app: &impl CallService, ids: [&Uuid; N]) {
+        for id in ids {
+            let req = TestRequest::delete()
+                .uri(&format!("/api/v3/sbom/urn:uuid:{id}/purge"))
+                .to_request();
+            let response = app.call_service(req).await;
+            assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        }
+    }
+
     async fn delete_concurrently<const N: usize>(
         app: &impl CallService,
         ids: [&Uuid; N],
@@ -1370,6 +1691,10 @@ async fn delete_sboms(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     let response = delete(&app, [&id1]).await;
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
     assert!(response.into_body().try_into_bytes().unwrap().is_empty());
+    // - nothing is GC'ed before purging
+    assert_eq!(count_purls(&app).await, Some(2087));
+    assert!(storage.retrieve(key1.clone()).await?.is_some());
+    purge(&app, [&id1]).await;
     // - 4 purls should be GC'ed
     assert_eq!(count_purls(&app).await, Some(2083));
     // - check the storage
@@ -1384,6 +1709,7 @@ async fn delete_sboms(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
         assert_eq!(response.status(), StatusCode::NO_CONTENT);
         assert!(response.into_body().try_into_bytes().unwrap().is_empty());
     }
+    purge(&app, [&id1, &id3]).await;
     // - 610 purls should be GC'ed
     assert_eq!(count_purls(&app).await, Some(1473));
     // - check the storage
@@ -1395,6 +1721,7 @@ async fn delete_sboms(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     let response = delete(&app, [&id2, &id1, &id3]).await;
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
     assert!(response.into_body().try_into_bytes().unwrap().is_empty());
+    purge(&app, [&id2, &id1, &id3]).await;
     // - 1 purl should be GC'ed
     assert_eq!(count_purls(&app).await, Some(1472));
     // - check the storage
@@ -1445,6 +1772,112 @@ async fn download_sbom(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Test comparing two sboms
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn compare_sboms(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let [old, new] = ctx
+        .ingest_documents([
+            "spdx/quarkus-bom-3.2.11.Final-redhat-00001.json",
+            "spdx/quarkus-bom-3.2.12.Final-redhat-00002.json",
+        ])
+        .await?
+        .into_uuid();
+
+    async fn compare(app: &impl CallService, old: Uuid, new: Uuid) -> ServiceResponse {
+        let req = TestRequest::post()
+            .uri("/api/v3/sbom/compare")
+            .set_json(json!({"old": old, "new": new}))
+            .to_request();
+        app.call_service(req).await
+    }
+
+    // an SBOM doesn't differ from itself
+    let response = compare(&app, old, old).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let result: SbomComparison = actix_web::test::read_body_json(response).await;
+    assert_eq!(result, SbomComparison::default());
+
+    let response = compare(&app, old, new).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let result: SbomComparison = actix_web::test::read_body_json(response).await;
+    assert!(!result.version_changed.is_empty());
+    for change in &result.version_changed {
+        assert_ne!(change.old.version, change.new.version);
+    }
+
+    // unknown SBOMs can't be found
+    let response = compare(&app, old, Uuid::now_v7()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+/// Test fetching the source document of an sbom
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn sbom_source(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    const FILE: &str = "quarkus-bom-2.13.8.Final-redhat-00004.json";
+    let app = caller(ctx).await?;
+    let bytes = document_bytes(FILE).await?;
+    let result = ctx.ingest_document(FILE).await?;
+    let uri = format!("/api/v3/sbom/urn:uuid:{}/source", result.id);
+
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let headers = response.headers();
+    assert_eq!(headers["content-type"], "application/spdx+json");
+    assert_eq!(
+        headers["etag"],
+        "\"488c5d97daed3613746f0c246f4a3d1b26ea52ce43d6bdd33f4219f881a00c07\""
+    );
+    let disposition = headers["content-disposition"].to_str()?;
+    assert!(disposition.starts_with("attachment; filename=\"https___access.redhat.com_"));
+    assert!(disposition.ends_with(".json\""));
+
+    let etag = headers["etag"].clone();
+    let body = read_body(response).await;
+    assert_eq!(bytes, body);
+
+    // a matching ETag returns no content
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri(&uri)
+                .insert_header(("if-none-match", etag))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // a different ETag returns the document
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri(&uri)
+                .insert_header(("if-none-match", "\"other\""))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // unknown SBOMs can't be found
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri(&format!("/api/v3/sbom/urn:uuid:{}/source", Uuid::now_v7()))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
 #[test_context(TrustifyContext)]
 #[test(actix_web::test)]
 async fn get_advisories(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
@@ -1482,17 +1915,310 @@ async fn get_advisories(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
 }
 
 #[test_context(TrustifyContext)]
-#[test(actix_web::test)]
-async fn get_advisories_with_deprecated_filtering(
+#[rstest]
+#[case::default(None, &["GHSA-c25x-cm9x-qqgx"])]
+#[case::affected(Some("affected"), &["GHSA-c25x-cm9x-qqgx"])]
+#[case::fixed(Some("fixed"), &["GHSA-45c4-8wx5-qw6w"])]
+#[case::both(Some("fixed, affected"), &["GHSA-45c4-8wx5-qw6w", "GHSA-c25x-cm9x-qqgx"])]
+#[case::all(Some(""), &["GHSA-45c4-8wx5-qw6w", "GHSA-c25x-cm9x-qqgx"])]
+#[case::other(Some("not_affected"), &[])]
+#[test_log::test(actix_web::test)]
+async fn get_advisories_by_status(
     ctx: &TrustifyContext,
+    #[case] statuses: Option<&str>,
+    #[case] expected: &[&str],
 ) -> Result<(), anyhow::Error> {
+    // aiohttp is the fixed version of GHSA-45c4, Deno is affected by GHSA-c25x
+    let sbom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "serialNumber": "urn:uuid:4f0c5d4e-4a5b-4d36-9d8e-2f4c1f7f3c11",
+        "metadata": {
+            "component": { "type": "application", "name": "statuses", "version": "0.1.0" }
+        },
+        "components": [
+            {
+                "type": "library",
+                "name": "aiohttp",
+                "version": "3.8.5",
+                "purl": "pkg:pypi/aiohttp@3.8.5"
+            },
+            {
+                "type": "library",
+                "name": "Deno",
+                "version": "1.32.0",
+                "purl": "pkg:cargo/Deno@1.32.0"
+            }
+        ]
+    });
+
     let id = ctx
-        .ingest_documents([
-            "quarkus-bom-2.13.8.Final-redhat-00004.json",
-            "cve/CVE-2024-26308.json",
-            // "cve/CVE-2024-26308-updated.json",
-        ])
-        .await?[0]
+        .ingest_bytes_as(
+            &serde_json::to_vec(&sbom)?,
+            Format::CycloneDX,
+            ("source", "TrustifyContext"),
+        )
+        .await?
+        .id
+        .to_string();
+    ctx.ingest_documents([
+        "osv/GHSA-45c4-8wx5-qw6w.json",
+        "osv/GHSA-c25x-cm9x-qqgx.json",
+    ])
+    .await?;
+
+    let mut uri = format!("/api/v3/sbom/urn:uuid:{id}/advisory");
+    if let Some(statuses) = statuses {
+        uri.push_str(&format!("?statuses={}", encode(statuses)));
+    }
+
+    let app = caller(ctx).await?;
+    let v: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+
+    log::debug!("{v:#?}");
+
+    let mut actual = v
+        .as_array()
+        .expect("must be an array")
+        .iter()
+        .map(|advisory| advisory["identifier"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    actual.sort();
+
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn list_vulnerabilities(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    // aiohttp is the fixed version of GHSA-45c4, Deno is affected by GHSA-c25x
+    let sbom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "serialNumber": "urn:uuid:8d2e4b1a-3c5f-4e6d-9b7a-1f2e3d4c5b6a",
+        "metadata": {
+            "component": { "type": "application", "name": "vulnerabilities", "version": "0.1.0" }
+        },
+        "components": [
+            { "type": "library", "name": "aiohttp", "version": "3.8.5", "purl": "pkg:pypi/aiohttp@3.8.5" },
+            { "type": "library", "name": "Deno", "version": "1.32.0", "purl": "pkg:cargo/Deno@1.32.0" }
+        ]
+    });
+
+    let id = ctx
+        .ingest_bytes_as(
+            &serde_json::to_vec(&sbom)?,
+            Format::CycloneDX,
+            ("source", "TrustifyContext"),
+        )
+        .await?
+        .id
+        .to_string();
+    ctx.ingest_documents([
+        "osv/GHSA-45c4-8wx5-qw6w.json",
+        "osv/GHSA-c25x-cm9x-qqgx.json",
+    ])
+    .await?;
+
+    let app = caller(ctx).await?;
+
+    let identifiers = |v: &Value| {
+        v["items"]
+            .as_array()
+            .expect("must be an array")
+            .iter()
+            .map(|advisory| {
+                advisory["identifier"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // affected only, by default
+
+    let uri = format!("/api/v3/sbom/urn:uuid:{id}/vulnerabilities?total=true");
+    let v: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(identifiers(&v), ["GHSA-c25x-cm9x-qqgx"]);
+    assert_eq!(v["total"], 1);
+
+    // all statuses, a page at a time
+
+    let uri = format!(
+        "/api/v3/sbom/urn:uuid:{id}/vulnerabilities?statuses=fixed,affected&limit=1&total=true"
+    );
+    let v: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(identifiers(&v), ["GHSA-45c4-8wx5-qw6w"]);
+    assert_eq!(v["total"], 2);
+
+    let uri = format!(
+        "/api/v3/sbom/urn:uuid:{id}/vulnerabilities?statuses=fixed,affected&limit=1&offset=1"
+    );
+    let v: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(identifiers(&v), ["GHSA-c25x-cm9x-qqgx"]);
+
+    // unknown SBOM
+
+    let uri = format!("/api/v3/sbom/urn:uuid:{}/vulnerabilities", Uuid::new_v4());
+    let response = app
+        .call_service(TestRequest::get().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_vex(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "serialNumber": "urn:uuid:0b5a3c1e-6f43-4d5e-9a0c-7e1d2b3c4d5e",
+        "metadata": {
+            "component": { "type": "application", "name": "vex", "version": "0.1.0" }
+        },
+        "components": [
+            { "type": "library", "name": "aiohttp", "version": "3.8.5", "purl": "pkg:pypi/aiohttp@3.8.5" },
+            { "type": "library", "name": "Deno", "version": "1.32.0", "purl": "pkg:cargo/Deno@1.32.0" },
+            { "type": "library", "name": "hyper", "version": "0.14.9", "purl": "pkg:cargo/hyper@0.14.9" }
+        ]
+    });
+
+    let id = ctx
+        .ingest_bytes_as(
+            &serde_json::to_vec(&sbom)?,
+            Format::CycloneDX,
+            ("source", "TrustifyContext"),
+        )
+        .await?
+        .id
+        .to_string();
+    ctx.ingest_documents([
+        "osv/GHSA-45c4-8wx5-qw6w.json",
+        "osv/GHSA-c25x-cm9x-qqgx.json",
+        "osv/RUSTSEC-2021-0079.json",
+    ])
+    .await?;
+
+    let app = caller(ctx).await?;
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/override")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper@0.14.9",
+                    "status": "not_affected",
+                    "justification": "VulnerableCodeNotInExecutePath",
+                    "impact_statement": "Only HTTP/2 is used",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let v: Value = app
+        .call_and_read_body_json(
+            TestRequest::get()
+                .uri(&format!("/api/v3/sbom/urn:uuid:{id}/vex"))
+                .to_request(),
+        )
+        .await;
+
+    log::info!("{v:#}");
+
+    assert_eq!(v["@context"], "https://openvex.dev/ns/v0.0.1");
+    assert_eq!(v["@id"], format!("https://trustify.local/vex/{id}"));
+
+    let statements = v["statements"].as_array().expect("must be an array");
+    let find = |status: &str, purl: &str| {
+        statements
+            .iter()
+            .filter(|statement| statement["status"] == status)
+            .find(|statement| {
+                statement["products"]
+                    .as_array()
+                    .is_some_and(|products| products.iter().any(|product| product["@id"] == purl))
+            })
+    };
+
+    let fixed = find("fixed", "pkg:pypi/aiohttp@3.8.5").expect("must have a fixed statement");
+    assert_eq!(fixed["justification"], Value::Null);
+
+    let affected =
+        find("affected", "pkg:cargo/Deno@1.32.0").expect("must have an affected statement");
+    assert!(affected["action_statement"].is_string());
+
+    let not_affected =
+        find("not_affected", "pkg:cargo/hyper@0.14.9").expect("must have a not_affected statement");
+    assert_eq!(not_affected["vulnerability"]["name"], "CVE-2021-32714");
+    assert_eq!(
+        not_affected["justification"],
+        "vulnerable_code_not_in_execute_path"
+    );
+    assert_eq!(not_affected["impact_statement"], "Only HTTP/2 is used");
+
+    // the override replaces the status of the advisory, instead of contradicting it
+    assert!(find("affected", "pkg:cargo/hyper@0.14.9").is_none());
+
+    // the statements required by OpenVEX must be present
+    for statement in statements {
+        match statement["status"].as_str() {
+            Some("not_affected") => assert!(
+                statement["justification"].is_string() || statement["impact_statement"].is_string(),
+                "{statement}"
+            ),
+            Some("affected") => assert!(statement["action_statement"].is_string(), "{statement}"),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_vex_not_found(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri(&format!("/api/v3/sbom/urn:uuid:{}/vex", Uuid::now_v7()))
+                .to_request(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_advisories_with_deprecated_filtering(
+    ctx: &TrustifyContext,
+) -> Result<(), anyhow::Error> {
+    let id = ctx
+        .ingest_documents([
+            "quarkus-bom-2.13.8.Final-redhat-00004.json",
+            "cve/CVE-2024-26308.json",
+            // "cve/CVE-2024-26308-updated.json",
+        ])
+        .await?[0]
         .id
         .to_string();
 
@@ -2280,6 +3006,65 @@ async fn related_by_hash(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Test filtering related packages by relationship type
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::all(None, &["contains", "describes"])]
+#[case::contains(Some("contains"), &["contains"])]
+#[case::multiple(Some("contains,describes"), &["contains", "describes"])]
+#[case::none(Some("ancestor_of"), &[])]
+#[test_log::test(actix_web::test)]
+async fn related_by_relationship(
+    ctx: &TrustifyContext,
+    #[case] relationship: Option<&str>,
+    #[case] expected: &[&str],
+) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let id = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let mut uri = format!("/api/v3/sbom/urn:uuid:{id}/related");
+    if let Some(relationship) = relationship {
+        uri.push_str(&format!("?relationship={}", encode(relationship)));
+    }
+
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+    log::debug!("{response:#}");
+
+    let relationships: BTreeSet<_> = response["items"]
+        .as_array()
+        .expect("must be an array")
+        .iter()
+        .filter_map(|item| item["relationship"].as_str())
+        .collect();
+
+    assert_eq!(relationships, expected.iter().copied().collect());
+
+    Ok(())
+}
+
+/// Test an unknown relationship type is rejected
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn related_by_unknown_relationship(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let id = ctx.ingest_document("spdx/simple.json").await?.id;
+
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri(&format!(
+                    "/api/v3/sbom/urn:uuid:{id}/related?relationship=contains,foo"
+                ))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    Ok(())
+}
+
 fn ds6_docs() -> Result<Vec<PathBuf>, anyhow::Error> {
     let ds6_dir = trustify_test_context::absolute("../datasets/ds6")?;
     anyhow::ensure!(
@@ -2396,6 +3181,9 @@ async fn list_sboms_advisory_summary(
 ) -> Result<(), anyhow::Error> {
     let app = caller(ctx).await?;
     ctx.ingest_documents(docs).await?;
+    SbomService::new(PaginationCache::for_test())
+        .refresh_advisory_summary(&ctx.db)
+        .await?;
 
     let advisories_query = if advisories_param {
         "&advisories=true"
@@ -2423,3 +3211,415 @@ async fn list_sboms_advisory_summary(
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_formulations(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("cyclonedx/formulation_attestation_1dot6.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/formulations?total=true");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::info!("response:\n{:#}", json!(response));
+
+    assert_eq!(response["total"], 2);
+    assert_eq!(response["items"][0]["bom_ref"], "formula-1");
+    assert_eq!(
+        response["items"][0]["description"],
+        "Compile and package the application"
+    );
+    assert_eq!(response["items"][0]["components"][0]["name"], "gcc");
+    assert_eq!(response["items"][1]["bom_ref"], "formula-2");
+    assert_eq!(response["items"][1]["components"], json!([]));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_package_cpes(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("spdx/simple.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/package/SPDXRef-A/cpes");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::info!("response:\n{:#}", json!(response));
+
+    assert_eq!(
+        response,
+        json!([{
+            "cpe": "cpe:2.3:a:redhat:simple:1:*:el9:*:*:*:*:*",
+            "uri_format": "cpe:/a:redhat:simple:1::el9",
+        }])
+    );
+
+    // a node without any CPE
+    let uri = format!("/api/v3/sbom/{id}/package/SPDXRef-B/cpes");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    assert_eq!(response, json!([]));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_attestations(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("cyclonedx/formulation_attestation_1dot6.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/attestations?total=true");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::info!("response:\n{:#}", json!(response));
+
+    assert_eq!(response["total"], 1);
+    assert_eq!(
+        response["items"][0]["summary"],
+        "Build reproducibility attestation"
+    );
+    assert_eq!(response["items"][0]["assessor_ref"], "assessor-1");
+    assert_eq!(
+        response["items"][0]["evidence"],
+        json!([{
+            "requirement": "requirement-1",
+            "claims": ["claim-1"],
+            "conformance": {
+                "score": 0.8,
+                "rationale": "Most builds are reproducible"
+            }
+        }])
+    );
+
+    Ok(())
+}
+
+/// Documents prior to CycloneDX 1.6 don't carry any build evidence
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn formulations_empty_for_older_spec(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("zookeeper-3.9.2-cyclonedx.json")
+        .await?
+        .id
+        .to_string();
+
+    for path in ["formulations", "attestations"] {
+        let uri = format!("/api/v3/sbom/{id}/{path}?total=true");
+        let req = TestRequest::get().uri(&uri).to_request();
+        let response: Value = app.call_and_read_body_json(req).await;
+        assert_eq!(response["total"], 0);
+    }
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_package_external_refs(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("cyclonedx/external_references.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/package/a/external-refs");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::info!("response:\n{:#}", json!(response));
+
+    // the duplicate VCS reference is only stored once
+    assert_eq!(
+        response,
+        json!([
+            {
+                "node_id": "a",
+                "ref_type": "distribution",
+                "url": "https://example.com/a/a-1.tar.gz",
+                "hashes": {
+                    "SHA-256": "a591a6d40bf420404a011733cfb7b190d62c65bf0bcda32b57b277d9ad9f146e",
+                },
+            },
+            {
+                "node_id": "a",
+                "ref_type": "vcs",
+                "url": "https://github.com/example/a",
+                "hashes": {},
+            },
+            {
+                "node_id": "a",
+                "ref_type": "website",
+                "url": "https://example.com/a",
+                "hashes": {},
+            },
+        ])
+    );
+
+    // a package without any external references
+    let uri = format!("/api/v3/sbom/{id}/package/b/external-refs");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    assert_eq!(response, json!([]));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_external_refs_by_type(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("cyclonedx/external_references.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/external-refs?type=vcs&total=true");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::info!("response:\n{:#}", json!(response));
+
+    assert_eq!(response["total"], json!(2));
+    assert_eq!(
+        response["items"],
+        json!([
+            {
+                "node_id": "a",
+                "ref_type": "vcs",
+                "url": "https://github.com/example/a",
+                "hashes": {},
+            },
+            {
+                "node_id": "root",
+                "ref_type": "vcs",
+                "url": "https://github.com/example/root",
+                "hashes": {},
+            },
+        ])
+    );
+
+    // without a filter, all references are returned
+    let uri = format!("/api/v3/sbom/{id}/external-refs?total=true");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    assert_eq!(response["total"], json!(4));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_snippets(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let id = ctx
+        .ingest_document("spdx/issue-552.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/snippets?total=true");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    log::info!("response:\n{:#}", json!(response));
+
+    assert_eq!(
+        response,
+        json!({
+            "items": [{
+                "node_id": "SPDXRef-Snippet",
+                "name": "from linux kernel",
+                "file_node_id": "SPDXRef-DoapSource",
+                "byte_range_start": 310,
+                "byte_range_end": 420,
+                "line_range_start": 5,
+                "line_range_end": 23,
+                "license_concluded": "GPL-2.0-only",
+            }],
+            "total": 1,
+        })
+    );
+
+    // an SBOM without any snippets
+    let id = ctx
+        .ingest_document("spdx/simple.json")
+        .await?
+        .id
+        .to_string();
+
+    let uri = format!("/api/v3/sbom/{id}/snippets?total=true");
+    let req = TestRequest::get().uri(&uri).to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    assert_eq!(response, json!({"items": [], "total": 0}));
+
+    Ok(())
+}
+
+/// The total number of packages of an SBOM
+async fn packages(app: &impl CallService, id: Uuid) -> u64 {
+    let req = TestRequest::get()
+        .uri(&format!("/api/v3/sbom/urn:uuid:{id}/packages?total=true"))
+        .to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    response["total"].as_u64().expect("must be a number")
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::spdx("spdx/simple.json", "spdx")]
+#[case::cyclonedx("zookeeper-3.9.2-cyclonedx.json", "cyclonedx")]
+#[test_log::test(actix_web::test)]
+async fn reprocess(
+    ctx: &TrustifyContext,
+    #[case] document: &str,
+    #[case] format: &str,
+) -> Result<(), anyhow::Error> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use trustify_entity::sbom_package;
+
+    let app = caller(ctx).await?;
+    let id: Uuid = ctx.ingest_document(document).await?.id.parse()?;
+
+    let expected = packages(&app, id).await;
+    assert!(expected > 0);
+
+    // lose the packages, like an older ingestor not extracting them would have
+    sbom_package::Entity::delete_many()
+        .filter(sbom_package::Column::SbomId.eq(id))
+        .exec(&ctx.db)
+        .await?;
+    assert_eq!(packages(&app, id).await, 0);
+
+    let req = TestRequest::post()
+        .uri(&format!("/api/v3/admin/sbom/{id}/reprocess"))
+        .to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    assert_eq!(response["sbom_id"], json!(id));
+    assert_eq!(response["format"], format);
+    assert_eq!(response["packages_before"], 0);
+    assert_eq!(response["packages_after"], expected);
+
+    assert_eq!(packages(&app, id).await, expected);
+
+    // reprocessing again doesn't duplicate anything
+    let req = TestRequest::post()
+        .uri(&format!("/api/v3/admin/sbom/{id}/reprocess"))
+        .to_request();
+    let response: Value = app.call_and_read_body_json(req).await;
+    assert_eq!(response["packages_before"], expected);
+    assert_eq!(response["packages_after"], expected);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn reprocess_not_found(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri(&format!("/api/v3/admin/sbom/{}/reprocess", Uuid::now_v7()))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn get_quality(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "serialNumber": "urn:uuid:5e1f3a2b-8c4d-4e6f-9a1b-2c3d4e5f6a7b",
+        "metadata": {
+            "component": {
+                "type": "application", "name": "quality", "version": "0.1.0", "bom-ref": "app",
+                "purl": "pkg:generic/quality@0.1.0",
+                "licenses": [{ "license": { "id": "Apache-2.0" } }]
+            }
+        },
+        "components": [
+            {
+                "type": "library", "name": "aiohttp", "version": "3.8.5", "bom-ref": "a",
+                "purl": "pkg:pypi/aiohttp@3.8.5",
+                "cpe": "cpe:/a:aiohttp:aiohttp:3.8.5",
+                "licenses": [{ "license": { "id": "Apache-2.0" } }]
+            },
+            { "type": "library", "name": "hyper", "version": "0.14.9", "bom-ref": "b" },
+            { "type": "library", "name": "unknown", "bom-ref": "c" }
+        ],
+        "dependencies": [
+            { "ref": "app", "dependsOn": ["a", "b"] }
+        ]
+    });
+
+    let id = ctx
+        .ingest_bytes_as(
+            &serde_json::to_vec(&sbom)?,
+            Format::CycloneDX,
+            ("source", "TrustifyContext"),
+        )
+        .await?
+        .id
+        .to_string();
+
+    let app = caller(ctx).await?;
+    let v: Value = app
+        .call_and_read_body_json(
+            TestRequest::get()
+                .uri(&format!("/api/v3/sbom/urn:uuid:{id}/quality"))
+                .to_request(),
+        )
+        .await;
+
+    log::info!("{v:#}");
+
+    assert_eq!(v["total_components"], 4);
+    assert_eq!(v["components_with_purl"], 2);
+    assert_eq!(v["components_with_cpe"], 1);
+    assert_eq!(v["components_with_version"], 3);
+    assert_eq!(v["components_with_license"], 2);
+    assert_eq!(v["relationships_defined"], 2);
+
+    // 0.3 * 2/4 + 0.1 * 1/4 + 0.2 * 3/4 + 0.2 * 2/4 + 0.2 * 2/3
+    let score = v["overall_score"].as_f64().expect("must be a number");
+    assert!((score - 0.5583).abs() < 0.001, "unexpected score: {score}");
+
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri(&format!("/api/v3/sbom/urn:uuid:{}/quality", Uuid::now_v7()))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
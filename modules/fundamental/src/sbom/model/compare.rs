@@ -0,0 +1,287 @@
+use crate::sbom::model::{SbomPackage, SbomPackageRelation};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use trustify_entity::relationship::Relationship;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// The differences between the packages of two SBOMs
+///
+/// Packages are matched by their first PURL, ignoring version and qualifiers. Packages without
+/// a PURL are matched by their name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, Default)]
+pub struct SbomComparison {
+    /// Packages only present in the new SBOM
+    pub added: Vec<SbomPackage>,
+    /// Packages only present in the old SBOM
+    pub removed: Vec<SbomPackage>,
+    /// Packages present in both SBOMs, but with a different version
+    pub version_changed: Vec<SbomPackageChange>,
+    /// Relationships between the same packages, but of a different type
+    pub relationship_changed: Vec<SbomRelationshipChange>,
+}
+
+/// The SBOMs to compare
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct SbomCompareRequest {
+    /// The ID of the old SBOM
+    pub old: Uuid,
+    /// The ID of the new SBOM
+    pub new: Uuid,
+}
+
+/// A package, as found in the old and the new SBOM
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct SbomPackageChange {
+    pub old: SbomPackage,
+    pub new: SbomPackage,
+}
+
+/// A relationship of a package, as found in the old and the new SBOM
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct SbomRelationshipChange {
+    /// The package on the left side of the relationship, from the new SBOM
+    pub package: SbomPackage,
+    /// The relationship in the old SBOM, and the package on its right side
+    pub old: SbomPackageRelation<SbomPackage>,
+    /// The relationship in the new SBOM, and the package on its right side
+    pub new: SbomPackageRelation<SbomPackage>,
+}
+
+/// A relationship between two packages of an SBOM, by their node IDs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRelationship {
+    pub left: String,
+    pub relationship: Relationship,
+    pub right: String,
+}
+
+/// The packages and relationships of one side of the comparison
+#[derive(Debug, Clone, Default)]
+pub struct ComparisonSide {
+    pub packages: Vec<SbomPackage>,
+    pub relationships: Vec<NodeRelationship>,
+}
+
+impl ComparisonSide {
+    /// Group packages by their key, and map node IDs to keys
+    fn index(&self) -> (BTreeMap<String, Vec<&SbomPackage>>, HashMap<&str, String>) {
+        let mut packages = BTreeMap::<_, Vec<_>>::new();
+        let mut keys = HashMap::new();
+
+        for package in &self.packages {
+            let key = package.key();
+            keys.insert(package.id.as_str(), key.clone());
+            packages.entry(key).or_default().push(package);
+        }
+
+        (packages, keys)
+    }
+}
+
+/// The packages which don't have a package of the same version in `others`
+fn changed<'a>(packages: &[&'a SbomPackage], others: &[&SbomPackage]) -> Vec<&'a SbomPackage> {
+    packages
+        .iter()
+        .filter(|package| !others.iter().any(|other| other.version == package.version))
+        .copied()
+        .collect()
+}
+
+/// Group the relationships of a side by the keys of the packages on both sides
+fn relationships<'a>(
+    side: &'a ComparisonSide,
+    keys: &HashMap<&str, String>,
+) -> BTreeMap<(String, String), Vec<&'a NodeRelationship>> {
+    let mut result = BTreeMap::<_, Vec<_>>::new();
+
+    for rel in &side.relationships {
+        let (Some(left), Some(right)) = (keys.get(rel.left.as_str()), keys.get(rel.right.as_str()))
+        else {
+            continue;
+        };
+        let entry = result.entry((left.clone(), right.clone())).or_default();
+        if !entry
+            .iter()
+            .any(|r: &&NodeRelationship| r.relationship == rel.relationship)
+        {
+            entry.push(rel);
+        }
+    }
+
+    result
+}
+
+impl SbomComparison {
+    pub fn new(old: &ComparisonSide, new: &ComparisonSide) -> Self {
+        let mut result = Self::default();
+
+        let (old_packages, old_keys) = old.index();
+        let (new_packages, new_keys) = new.index();
+
+        for (key, old_packages) in &old_packages {
+            let Some(new_packages) = new_packages.get(key) else {
+                result.removed.extend(old_packages.iter().copied().cloned());
+                continue;
+            };
+
+            // packages with the same version are unchanged, pair up the rest
+            let old_changed = changed(old_packages, new_packages);
+            let new_changed = changed(new_packages, old_packages);
+
+            for (old, new) in old_changed.iter().zip(&new_changed) {
+                result.version_changed.push(SbomPackageChange {
+                    old: (*old).clone(),
+                    new: (*new).clone(),
+                });
+            }
+            result
+                .removed
+                .extend(old_changed.iter().skip(new_changed.len()).copied().cloned());
+            result
+                .added
+                .extend(new_changed.iter().skip(old_changed.len()).copied().cloned());
+        }
+
+        for (key, new_packages) in &new_packages {
+            if !old_packages.contains_key(key) {
+                result.added.extend(new_packages.iter().copied().cloned());
+            }
+        }
+
+        // relationships, by the keys of the packages on both sides
+
+        let old_relationships = relationships(old, &old_keys);
+        let new_relationships = relationships(new, &new_keys);
+        let old_by_id = old
+            .packages
+            .iter()
+            .map(|p| (p.id.as_str(), p))
+            .collect::<HashMap<_, _>>();
+        let new_by_id = new
+            .packages
+            .iter()
+            .map(|p| (p.id.as_str(), p))
+            .collect::<HashMap<_, _>>();
+
+        for (pair, old_rels) in &old_relationships {
+            let Some(new_rels) = new_relationships.get(pair) else {
+                continue;
+            };
+
+            let old_only = old_rels
+                .iter()
+                .filter(|o| !new_rels.iter().any(|n| n.relationship == o.relationship));
+            let new_only = new_rels
+                .iter()
+                .filter(|n| !old_rels.iter().any(|o| o.relationship == n.relationship));
+
+            for (old_rel, new_rel) in old_only.zip(new_only) {
+                let (Some(package), Some(old_right), Some(new_right)) = (
+                    new_by_id.get(new_rel.left.as_str()),
+                    old_by_id.get(old_rel.right.as_str()),
+                    new_by_id.get(new_rel.right.as_str()),
+                ) else {
+                    continue;
+                };
+
+                result.relationship_changed.push(SbomRelationshipChange {
+                    package: (*package).clone(),
+                    old: SbomPackageRelation {
+                        relationship: old_rel.relationship,
+                        package: (*old_right).clone(),
+                    },
+                    new: SbomPackageRelation {
+                        relationship: new_rel.relationship,
+                        package: (*new_right).clone(),
+                    },
+                });
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+    use trustify_common::purl::Purl;
+
+    fn package(id: &str, name: &str, version: &str, purl: Option<&str>) -> SbomPackage {
+        SbomPackage {
+            id: id.into(),
+            name: name.into(),
+            version: Some(version.into()),
+            purl: purl
+                .map(|purl| Purl::from_str(purl).expect("must parse").into())
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn relationship(left: &str, relationship: Relationship, right: &str) -> NodeRelationship {
+        NodeRelationship {
+            left: left.into(),
+            relationship,
+            right: right.into(),
+        }
+    }
+
+    #[test]
+    fn compare() {
+        let old = ComparisonSide {
+            packages: vec![
+                package("a", "a", "1", Some("pkg:maven/org/a@1?type=jar")),
+                package("b", "b", "1", Some("pkg:maven/org/b@1")),
+                package("c", "c", "1", None),
+                package("d", "d", "1", None),
+            ],
+            relationships: vec![
+                relationship("a", Relationship::Dependency, "b"),
+                relationship("a", Relationship::Dependency, "c"),
+            ],
+        };
+        let new = ComparisonSide {
+            packages: vec![
+                // different ID and qualifiers, but the same PURL
+                package("a2", "a", "1", Some("pkg:maven/org/a@1")),
+                package("b", "b", "2", Some("pkg:maven/org/b@2")),
+                package("c", "c", "1", None),
+                package("e", "e", "1", None),
+            ],
+            relationships: vec![
+                relationship("a2", Relationship::Dependency, "b"),
+                relationship("a2", Relationship::DevDependency, "c"),
+            ],
+        };
+
+        let result = SbomComparison::new(&old, &new);
+
+        assert_eq!(result.added, vec![new.packages[3].clone()]);
+        assert_eq!(result.removed, vec![old.packages[3].clone()]);
+        assert_eq!(
+            result.version_changed,
+            vec![SbomPackageChange {
+                old: old.packages[1].clone(),
+                new: new.packages[1].clone(),
+            }]
+        );
+        assert_eq!(
+            result.relationship_changed,
+            vec![SbomRelationshipChange {
+                package: new.packages[0].clone(),
+                old: SbomPackageRelation {
+                    relationship: Relationship::Dependency,
+                    package: old.packages[2].clone(),
+                },
+                new: SbomPackageRelation {
+                    relationship: Relationship::DevDependency,
+                    package: new.packages[2].clone(),
+                },
+            }]
+        );
+    }
+}
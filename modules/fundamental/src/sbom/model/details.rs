@@ -73,6 +73,9 @@ pub struct SbomDetails {
 
 impl SbomDetails {
     /// turn an (sbom, sbom_node) row into an [`SbomDetails`], if possible
+    ///
+    /// This is one of the slow queries, collecting the advisories of all packages. For SBOMs with
+    /// many packages, it may exceed a query timeout meant for regular queries.
     #[instrument(skip(service, tx), err(level=tracing::Level::INFO))]
     pub async fn from_entity<C>(
         (sbom, node, source_document): (sbom::Model, sbom_node::Model, source_document::Model),
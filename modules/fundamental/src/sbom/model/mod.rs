@@ -1,5 +1,9 @@
+pub mod compare;
 pub mod details;
+pub mod quality;
 pub mod raw_sql;
+pub mod validate;
+pub mod vex;
 
 use super::service::SbomService;
 use crate::{
@@ -18,7 +22,7 @@ use tracing::{info_span, instrument};
 use tracing_futures::Instrument;
 use trustify_common::{
     cpe::Cpe,
-    purl::Purl,
+    purl::{Purl, package_key},
     requested_field::{BoolRequestedField, RequestedField},
 };
 use trustify_entity::{
@@ -225,6 +229,142 @@ pub struct SbomModel {
     pub sbom_count: RequestedField<i64>,
 }
 
+/// A CycloneDX 1.6 formulation entry, describing how the content of an SBOM was formed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct SbomFormulation {
+    /// The `bom-ref` of the formula
+    pub bom_ref: String,
+    /// The description of the formula, taken from its workflows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The transient components used by the formula, as provided by the document
+    #[schema(value_type = Vec<Object>)]
+    pub components: serde_json::Value,
+}
+
+impl From<trustify_entity::sbom_formulation::Model> for SbomFormulation {
+    fn from(value: trustify_entity::sbom_formulation::Model) -> Self {
+        Self {
+            bom_ref: value.bom_ref,
+            description: value.description,
+            components: value.components,
+        }
+    }
+}
+
+/// A CycloneDX 1.6 attestation, asserted by an assessor
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct SbomAttestation {
+    /// The internal ID of the attestation
+    pub id: Uuid,
+    /// The short description explaining the main points of the attestation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// The `bom-ref` of the assessor asserting the attestation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assessor_ref: Option<String>,
+    /// The mapping of requirements to claims, as provided by the document
+    #[schema(value_type = Vec<Object>)]
+    pub evidence: serde_json::Value,
+}
+
+impl From<trustify_entity::sbom_attestation::Model> for SbomAttestation {
+    fn from(value: trustify_entity::sbom_attestation::Model) -> Self {
+        Self {
+            id: value.id,
+            summary: value.summary,
+            assessor_ref: value.assessor_ref,
+            evidence: value.evidence,
+        }
+    }
+}
+
+/// A CPE identifying a package of an SBOM, in both of its bindings
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[schema(example = json!({
+    "cpe": "cpe:2.3:a:redhat:enterprise_linux:9:*:*:*:*:*:*:*",
+    "uri_format": "cpe:/a:redhat:enterprise_linux:9"
+}))]
+pub struct CpeEntry {
+    /// The CPE 2.3 formatted string
+    pub cpe: String,
+    /// The CPE 2.2 URI binding
+    pub uri_format: String,
+}
+
+impl From<trustify_entity::cpe::Model> for CpeEntry {
+    fn from(value: trustify_entity::cpe::Model) -> Self {
+        let cpe = trustify_entity::cpe::CpeDto::from(value);
+        Self {
+            cpe: cpe.to_formatted_string(),
+            uri_format: cpe.to_uri_binding(),
+        }
+    }
+}
+
+/// An SPDX snippet, covering a range of a file of an SBOM
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, FromQueryResult)]
+#[schema(example = json!({
+    "node_id": "SPDXRef-Snippet",
+    "name": "from linux kernel",
+    "file_node_id": "SPDXRef-DoapSource",
+    "byte_range_start": 310,
+    "byte_range_end": 420,
+    "line_range_start": 5,
+    "line_range_end": 23,
+    "license_concluded": "GPL-2.0-only"
+}))]
+pub struct SbomSnippet {
+    /// The ID of the snippet node within the SBOM
+    pub node_id: String,
+    /// The name of the snippet, or its ID if it has none
+    pub name: String,
+    /// The ID of the file node the snippet is part of
+    pub file_node_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_range_start: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_range_end: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_range_start: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_range_end: Option<i32>,
+    /// The concluded license expression of the snippet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_concluded: Option<String>,
+}
+
+/// An external reference of a package, like its website or VCS
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[schema(example = json!({
+    "node_id": "pkg:maven/org.apache.zookeeper/zookeeper@3.9.2?type=jar",
+    "ref_type": "vcs",
+    "url": "https://gitbox.apache.org/repos/asf/zookeeper.git",
+    "hashes": {}
+}))]
+pub struct PackageExternalReference {
+    /// The ID of the package node within the SBOM
+    pub node_id: String,
+    /// The type of reference, like `vcs`, `website`, or `distribution`
+    pub ref_type: String,
+    /// The URL of the reference
+    pub url: String,
+    /// The hashes of the referenced content, by algorithm
+    #[schema(value_type = HashMap<String, String>)]
+    pub hashes: serde_json::Value,
+}
+
+impl From<trustify_entity::sbom_package_external_ref::Model> for PackageExternalReference {
+    fn from(value: trustify_entity::sbom_package_external_ref::Model) -> Self {
+        Self {
+            node_id: value.node_id,
+            ref_type: value.ref_type,
+            url: value.url,
+            hashes: value.hashes,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, Default)]
 pub struct SbomPackage {
     /// The SBOM internal ID of a package
@@ -250,6 +390,17 @@ pub struct SbomPackage {
     pub licenses_ref_mapping: Vec<LicenseRefMapping>,
 }
 
+impl SbomPackage {
+    /// The identity of the package across SBOMs, see [`package_key`]
+    pub fn key(&self) -> String {
+        package_key(
+            self.purl.iter().map(|purl| &purl.head.purl),
+            self.group.as_deref(),
+            &self.name,
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, Default)]
 pub struct SbomPackageSummary {
     /// The SBOM internal ID of a package
@@ -322,3 +473,18 @@ pub enum Which {
     /// Target side
     Right,
 }
+
+/// The outcome of reprocessing an SBOM from its source document
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct ReprocessResult {
+    /// The ID of the reprocessed SBOM
+    pub sbom_id: Uuid,
+    /// The format the source document was detected as
+    pub format: trustify_module_ingestor::service::Format,
+    /// Number of packages before reprocessing
+    pub packages_before: u64,
+    /// Number of packages after reprocessing
+    pub packages_after: u64,
+    /// Warnings of ingesting the source document
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+
+/// How complete the information of an SBOM's components is
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct SbomQualityScore {
+    /// The number of components (packages) in the SBOM
+    pub total_components: u64,
+    /// The number of components having at least one PURL
+    pub components_with_purl: u64,
+    /// The number of components having at least one CPE
+    pub components_with_cpe: u64,
+    /// The number of components having a version
+    pub components_with_version: u64,
+    /// The number of components having at least one license
+    pub components_with_license: u64,
+    /// The number of relationships between nodes, not counting "describes"
+    pub relationships_defined: u64,
+    /// The weighted average of the individual ratios, from 0.0 to 1.0
+    pub overall_score: f32,
+}
+
+impl SbomQualityScore {
+    /// Compute the overall score from the counts, using the provided weights
+    ///
+    /// Each count is turned into a ratio of the total number of components. For relationships,
+    /// that's the ratio of the `total_components - 1` relationships required to connect all
+    /// components, capped at 1.0.
+    pub fn new(
+        total_components: u64,
+        components_with_purl: u64,
+        components_with_cpe: u64,
+        components_with_version: u64,
+        components_with_license: u64,
+        relationships_defined: u64,
+        weights: &QualityWeights,
+    ) -> Self {
+        let overall_score = if total_components == 0 {
+            0.0
+        } else {
+            let ratio = |count: u64, total: u64| (count as f32 / total.max(1) as f32).min(1.0);
+            let total = total_components;
+
+            let weighted = [
+                (weights.purl, ratio(components_with_purl, total)),
+                (weights.cpe, ratio(components_with_cpe, total)),
+                (weights.version, ratio(components_with_version, total)),
+                (weights.license, ratio(components_with_license, total)),
+                (
+                    weights.relationships,
+                    if total == 1 {
+                        1.0
+                    } else {
+                        ratio(relationships_defined, total - 1)
+                    },
+                ),
+            ];
+
+            let sum: f32 = weighted.iter().map(|(weight, _)| weight).sum();
+            if sum > 0.0 {
+                weighted
+                    .iter()
+                    .map(|(weight, ratio)| weight * ratio)
+                    .sum::<f32>()
+                    / sum
+            } else {
+                0.0
+            }
+        };
+
+        Self {
+            total_components,
+            components_with_purl,
+            components_with_cpe,
+            components_with_version,
+            components_with_license,
+            relationships_defined,
+            overall_score,
+        }
+    }
+}
+
+/// The weights of the individual ratios, when computing the overall [`SbomQualityScore`]
+///
+/// Weights are relative to each other, they don't need to add up to 1.0. In text form, they are a
+/// comma separated list of `name=weight` pairs, like `purl=0.4,cpe=0`. Missing names keep their
+/// default weight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualityWeights {
+    pub purl: f32,
+    pub cpe: f32,
+    pub version: f32,
+    pub license: f32,
+    pub relationships: f32,
+}
+
+impl Default for QualityWeights {
+    fn default() -> Self {
+        Self {
+            purl: 0.3,
+            cpe: 0.1,
+            version: 0.2,
+            license: 0.2,
+            relationships: 0.2,
+        }
+    }
+}
+
+impl fmt::Display for QualityWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "purl={},cpe={},version={},license={},relationships={}",
+            self.purl, self.cpe, self.version, self.license, self.relationships
+        )
+    }
+}
+
+impl FromStr for QualityWeights {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (name, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Missing weight for '{pair}'"))?;
+            let weight: f32 = weight
+                .trim()
+                .parse()
+                .map_err(|err| format!("Invalid weight for '{name}': {err}"))?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!("Weight for '{name}' must not be negative"));
+            }
+
+            let target = match name.trim() {
+                "purl" => &mut result.purl,
+                "cpe" => &mut result.cpe,
+                "version" => &mut result.version,
+                "license" => &mut result.license,
+                "relationships" => &mut result.relationships,
+                name => return Err(format!("Unknown weight: {name}")),
+            };
+            *target = weight;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_weights() {
+        assert_eq!(
+            QualityWeights::from_str("purl=1, cpe=0").expect("must parse"),
+            QualityWeights {
+                purl: 1.0,
+                cpe: 0.0,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            QualityWeights::from_str(&QualityWeights::default().to_string()),
+            Ok(QualityWeights::default())
+        );
+        assert!(QualityWeights::from_str("purl").is_err());
+        assert!(QualityWeights::from_str("foo=1").is_err());
+        assert!(QualityWeights::from_str("purl=-1").is_err());
+    }
+
+    #[test]
+    fn score() {
+        let weights = QualityWeights::default();
+
+        let score = SbomQualityScore::new(4, 4, 4, 4, 4, 3, &weights);
+        assert!((score.overall_score - 1.0).abs() < f32::EPSILON);
+
+        let score = SbomQualityScore::new(4, 2, 0, 4, 0, 10, &weights);
+        assert!((score.overall_score - 0.55).abs() < 0.0001);
+
+        assert_eq!(
+            SbomQualityScore::new(0, 0, 0, 0, 0, 0, &weights).overall_score,
+            0.0
+        );
+    }
+}
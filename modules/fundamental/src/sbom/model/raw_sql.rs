@@ -34,200 +34,28 @@ pub const CONTEXT_CPE_FILTER_SQL: &str = r#"
 "#;
 
 /// Returns SQL that counts affected vulnerabilities grouped by severity for
-/// multiple SBOMs in a single query. Combines PURL-based matching (via
-/// `purl_status` + `version_matches()`), name-keyed CPE matching (via
-/// `product_status` + package name matching), and package-level CPE-identity
-/// matching (via `cpe_status` + `version_matches()`, mirroring
-/// [`cpe_advisory_info_sql`] so SBOM-list severity counts agree with the
-/// details endpoint). Takes `$1 = Uuid[]` and returns
+/// multiple SBOMs in a single query. Reads the pre-computed matches from the
+/// `mv_sbom_advisory_summary` materialized view, which combines PURL-based
+/// matching, name-keyed CPE matching, and package-level CPE-identity matching
+/// (mirroring [`cpe_advisory_info_sql`] so SBOM-list severity counts agree with
+/// the details endpoint). Takes `$1 = Uuid[]` and returns
 /// `(sbom_id, severity, count)` rows.
 ///
-/// Uses a shared `sbom_purl_info` CTE (referenced 3x) that PostgreSQL
-/// auto-materializes, acting as a barrier that prevents the planner from
-/// inlining the SBOM's package set and scanning the full `versioned_purl`
-/// table. The advisory filter is deferred to a separate CTE so the
-/// expensive `version_matches()` narrows the set before any advisory
-/// lookups.
+/// The highest severity is picked per (sbom, vulnerability), collapsing across
+/// advisories into one row per unique vulnerability. Unknown (no CVSS score) is
+/// treated as the lowest severity so that a real score from any advisory always
+/// wins.
 pub fn batch_severity_counts_sql() -> &'static str {
     r#"
-    WITH
-    -- Unnest the input array of SBOM IDs
-    input_sboms AS (
-        SELECT unnest($1::uuid[]) AS sbom_id
-    ),
-
-    -- Shared CTE: SBOM package info including version, base_purl_id,
-    -- and name/namespace. Referenced 3x so PostgreSQL auto-materializes
-    -- it, preventing the planner from inlining and scanning the full
-    -- versioned_purl table. Including base_purl here nudges the planner
-    -- to hash the small side (20k rows) instead of the large one (1.6M).
-    sbom_purl_info AS (
-        SELECT
-            spr.sbom_id,
-            vp.version,
-            vp.base_purl_id,
-            bp.name,
-            bp.namespace
-        FROM input_sboms i
-        JOIN sbom_node_purl_ref spr ON spr.sbom_id = i.sbom_id
-        JOIN qualified_purl qp ON spr.qualified_purl_id = qp.id
-        JOIN versioned_purl vp ON qp.versioned_purl_id = vp.id
-        JOIN base_purl bp ON vp.base_purl_id = bp.id
-    ),
-
-    -- CPE-based matching: per-SBOM allowed CPE IDs with generalized matching
-    sbom_cpes AS (
-        SELECT i.sbom_id, cpe.*
-        FROM input_sboms i
-        JOIN sbom_describing_cpe sdc ON sdc.sbom_id = i.sbom_id
-        JOIN cpe ON sdc.cpe_id = cpe.id
-    ),
-    sbom_generalized_cpes AS (
-        SELECT sc.sbom_id, c.*
-        FROM sbom_cpes sc
-        JOIN cpe c ON c.vendor = sc.vendor
-            AND c.product = sc.product
-            AND c.version = split_part(sc.version, '.', 1)
-    ),
-    sbom_allowed_cpes AS (
-        SELECT sbom_id, id AS cpe_id FROM sbom_cpes
-        UNION
-        SELECT sbom_id, id AS cpe_id FROM sbom_generalized_cpes
-    ),
-    sbom_has_cpes AS (
-        SELECT DISTINCT sbom_id FROM sbom_cpes
-    ),
-
-    -- PURL-based matching: version_matches called only for SBOM's packages,
-    -- advisory filter deferred to avoid unnecessary lookups.
-    purl_version_matches AS (
-        SELECT DISTINCT
-            sp.sbom_id,
-            pst.advisory_id,
-            pst.vulnerability_id
-        FROM sbom_purl_info sp
-        JOIN purl_status pst ON pst.base_purl_id = sp.base_purl_id
-        JOIN version_range vr ON pst.version_range_id = vr.id
-        JOIN status ON pst.status_id = status.id
-        WHERE status.slug = 'affected'
-          AND version_matches(sp.version, vr.*)
-          AND (
-              pst.context_cpe_id IS NULL
-              OR pst.context_cpe_id IN (SELECT cpe_id FROM sbom_allowed_cpes sac WHERE sac.sbom_id = sp.sbom_id)
-              OR sp.sbom_id NOT IN (SELECT sbom_id FROM sbom_has_cpes)
-          )
-    ),
-    purl_matches AS (
-        SELECT pm.sbom_id, pm.advisory_id, pm.vulnerability_id
-        FROM purl_version_matches pm
-        WHERE NOT EXISTS (
-            SELECT 1 FROM advisory a
-            WHERE a.id = pm.advisory_id AND a.deprecated
-        )
-    ),
-
-    -- CPE product_status matches by name
-    cpe_matches_name AS (
-        SELECT DISTINCT
-            sp.sbom_id,
-            ps.advisory_id,
-            ps.vulnerability_id
-        FROM product_status ps
-        JOIN sbom_purl_info sp ON ps.package = sp.name
-        JOIN status ON ps.status_id = status.id
-        JOIN advisory ON ps.advisory_id = advisory.id
-        WHERE status.slug = 'affected'
-          AND advisory.deprecated = false
-          AND (
-              ps.context_cpe_id IS NULL
-              OR ps.context_cpe_id IN (SELECT cpe_id FROM sbom_allowed_cpes sac WHERE sac.sbom_id = sp.sbom_id)
-              OR sp.sbom_id NOT IN (SELECT sbom_id FROM sbom_has_cpes)
-          )
-    ),
-
-    -- CPE product_status matches by namespace/name
-    cpe_matches_ns AS (
-        SELECT DISTINCT
-            sp.sbom_id,
-            ps.advisory_id,
-            ps.vulnerability_id
-        FROM product_status ps
-        JOIN sbom_purl_info sp ON ps.package = CONCAT(sp.namespace, '/', sp.name)
-        JOIN status ON ps.status_id = status.id
-        JOIN advisory ON ps.advisory_id = advisory.id
-        WHERE sp.namespace IS NOT NULL
-          AND status.slug = 'affected'
-          AND advisory.deprecated = false
-          AND (
-              ps.context_cpe_id IS NULL
-              OR ps.context_cpe_id IN (SELECT cpe_id FROM sbom_allowed_cpes sac WHERE sac.sbom_id = sp.sbom_id)
-              OR sp.sbom_id NOT IN (SELECT sbom_id FROM sbom_has_cpes)
-          )
-    ),
-
-    -- Package-level CPEs harvested from SBOMs (e.g. SPDX cpe23Type refs),
-    -- joined back to the owning package for its (fallback) version. Mirrors
-    -- sbom_cpe_pkgs in cpe_advisory_info_sql().
-    sbom_cpe_pkgs AS (
-        SELECT
-            scr.sbom_id,
-            c.vendor,
-            c.product,
-            c.part,
-            COALESCE(NULLIF(c.version, '*'), sp.version) AS version
-        FROM input_sboms i
-        JOIN sbom_node_cpe_ref scr ON scr.sbom_id = i.sbom_id
-        JOIN cpe c ON scr.cpe_id = c.id
-        JOIN sbom_package sp ON sp.sbom_id = scr.sbom_id AND sp.node_id = scr.node_id
-    ),
-
-    -- CPE-based matching via cpe_status (package-level CPE identity, as
-    -- opposed to cpe_matches_name/_ns above which key off product_status by
-    -- package name). Vendor+product identity ('a' part only) plus
-    -- version_matches() against the affected version range -- no
-    -- sbom_describing_cpe context filter, matching cpe_advisory_info_sql().
-    cpe_version_matches AS (
-        SELECT DISTINCT
-            p.sbom_id,
-            cs.advisory_id,
-            cs.vulnerability_id
-        FROM sbom_cpe_pkgs p
-        JOIN cpe sc ON sc.vendor = p.vendor AND sc.product = p.product AND sc.part = 'a'
-        JOIN cpe_status cs ON cs.cpe_id = sc.id
-        JOIN version_range vr ON cs.version_range_id = vr.id
-        JOIN status ON cs.status_id = status.id
-        JOIN advisory ON cs.advisory_id = advisory.id
-        WHERE p.part = 'a'
-          AND status.slug = 'affected'
-          AND advisory.deprecated = false
-          AND version_matches(p.version, vr.*)
-    ),
-
-    -- Union all matches
-    all_affected AS (
-        SELECT * FROM purl_matches
-        UNION
-        SELECT * FROM cpe_matches_name
-        UNION
-        SELECT * FROM cpe_matches_ns
-        UNION
-        SELECT * FROM cpe_version_matches
-    ),
-
-    -- Pick the highest severity per (sbom, vulnerability), collapsing
-    -- across advisories and CVSS versions into one row per unique vuln.
-    -- Unknown (no CVSS score) is treated as the lowest severity so that
-    -- a real score from any advisory always wins.
-    scored AS (
-        SELECT DISTINCT ON (a.sbom_id, a.vulnerability_id)
-            a.sbom_id,
-            COALESCE(avs.severity::text, 'unknown') AS severity
-        FROM all_affected a
-        LEFT JOIN advisory_vulnerability_score avs
-            ON avs.advisory_id = a.advisory_id
-            AND avs.vulnerability_id = a.vulnerability_id
-        ORDER BY a.sbom_id, a.vulnerability_id,
-            CASE avs.severity::text
+    WITH scored AS (
+        SELECT DISTINCT ON (sbom_id, vulnerability_id)
+            sbom_id,
+            severity
+        FROM mv_sbom_advisory_summary
+        WHERE sbom_id = ANY($1::uuid[])
+          AND status = 'affected'
+        ORDER BY sbom_id, vulnerability_id,
+            CASE severity
                 WHEN 'critical' THEN 5
                 WHEN 'high' THEN 4
                 WHEN 'medium' THEN 3
@@ -246,6 +74,11 @@ pub fn batch_severity_counts_sql() -> &'static str {
     "#
 }
 
+/// Refreshes the `mv_sbom_advisory_summary` materialized view, without blocking
+/// queries reading it.
+pub const REFRESH_ADVISORY_SUMMARY_SQL: &str =
+    "REFRESH MATERIALIZED VIEW CONCURRENTLY mv_sbom_advisory_summary";
+
 /// Returns SQL that finds advisory/vulnerability matches for a single SBOM
 /// through package-level CPE identity, keyed by `cpe_status` (populated by the
 /// CVE loader from `affected[].cpes`). Takes `$1 = sbom_id (uuid)`,
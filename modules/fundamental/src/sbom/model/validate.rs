@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use trustify_module_ingestor::{
+    graph::error::Error as GraphError, model::ValidationOutcome, service::Error,
+};
+use utoipa::ToSchema;
+
+/// The result of validating an SBOM, without ingesting it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema, Default)]
+pub struct ValidationReport {
+    /// If the SBOM could be ingested
+    pub valid: bool,
+    /// The ID of the SBOM, if the exact same document is already ingested
+    ///
+    /// Such a document is not validated again, it passed validation when ingesting it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub existing_id: Option<String>,
+    /// Issues preventing the SBOM from being ingested
+    pub errors: Vec<ValidationError>,
+    /// Issues found while ingesting the SBOM, which don't prevent it from being ingested
+    pub warnings: Vec<ValidationWarning>,
+}
+
+/// An issue preventing an SBOM from being ingested
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct ValidationError {
+    /// The field the issue was found in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    /// The line of the document the issue was found in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+}
+
+/// An issue found while ingesting an SBOM, which doesn't prevent it from being ingested
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct ValidationWarning {
+    /// The field the issue was found in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    /// The line of the document the issue was found in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+}
+
+impl ValidationReport {
+    /// The report of an SBOM which could be ingested, along with the warnings of ingesting it
+    pub fn valid(warnings: Vec<String>) -> Self {
+        Self {
+            valid: true,
+            existing_id: None,
+            errors: vec![],
+            warnings: warnings
+                .into_iter()
+                .map(|message| ValidationWarning {
+                    field: None,
+                    message,
+                    line: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// The report of an SBOM which is already ingested
+    pub fn existing(id: String) -> Self {
+        Self {
+            valid: true,
+            existing_id: Some(id),
+            errors: vec![],
+            warnings: vec![],
+        }
+    }
+
+    /// The report of an SBOM which could not be ingested
+    pub fn invalid(error: ValidationError) -> Self {
+        Self {
+            valid: false,
+            existing_id: None,
+            errors: vec![error],
+            warnings: vec![],
+        }
+    }
+}
+
+impl From<ValidationOutcome> for ValidationReport {
+    fn from(value: ValidationOutcome) -> Self {
+        match value {
+            ValidationOutcome::Loaded(warnings) => Self::valid(warnings),
+            ValidationOutcome::Existed(id) => Self::existing(id),
+        }
+    }
+}
+
+/// Convert an ingestion error caused by the content of the document.
+///
+/// Other errors, like database errors, are not a result of validating the document, and get
+/// returned as they are.
+impl TryFrom<Error> for ValidationError {
+    type Error = Error;
+
+    fn try_from(value: Error) -> Result<Self, Self::Error> {
+        let line = match &value {
+            Error::Json(err) | Error::Graph(GraphError::Json(err)) => Some(err.line() as u64),
+            Error::Yaml(_)
+            | Error::Xml(_)
+            | Error::Utf8(_)
+            | Error::HashKey(_)
+            | Error::InvalidContent(_)
+            | Error::UnsupportedFormat(_)
+            | Error::Graph(GraphError::Purl(_) | GraphError::Semver(_)) => None,
+            _ => return Err(value),
+        };
+
+        Ok(Self {
+            field: None,
+            message: value.to_string(),
+            line,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn content_errors() {
+        let err = serde_json::from_str::<serde_json::Value>("{\n\"a\": }").unwrap_err();
+        let error = ValidationError::try_from(Error::Json(err)).expect("must be a content error");
+        assert_eq!(error.line, Some(2));
+
+        let error = ValidationError::try_from(Error::UnsupportedFormat("foo".into()))
+            .expect("must be a content error");
+        assert_eq!(error.message, "invalid format: foo");
+        assert_eq!(error.line, None);
+
+        assert!(ValidationError::try_from(Error::Unavailable).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use trustify_module_ingestor::service::advisory::user_override::OverrideJustification;
+use utoipa::ToSchema;
+
+/// The JSON-LD context of OpenVEX documents
+pub const OPENVEX_CONTEXT: &str = "https://openvex.dev/ns/v0.0.1";
+
+/// An OpenVEX document, see <https://github.com/openvex/spec>
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct OpenVexDocument {
+    #[serde(rename = "@context")]
+    pub context: String,
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub author: String,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schema(value_type = String)]
+    pub timestamp: OffsetDateTime,
+    pub version: u32,
+    pub statements: Vec<OpenVexStatement>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct OpenVexStatement {
+    pub vulnerability: OpenVexVulnerability,
+    pub products: Vec<OpenVexProduct>,
+    pub status: OpenVexStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<OpenVexJustification>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact_statement: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_statement: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct OpenVexVulnerability {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct OpenVexProduct {
+    /// The PURL of the package
+    #[serde(rename = "@id")]
+    pub id: String,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenVexStatus {
+    NotAffected,
+    Affected,
+    Fixed,
+    UnderInvestigation,
+}
+
+impl OpenVexStatus {
+    /// The precedence of conflicting statuses of a package, from different advisories. The
+    /// highest one wins, erring on the side of caution.
+    pub fn precedence(self) -> u8 {
+        match self {
+            Self::NotAffected => 0,
+            Self::Fixed => 1,
+            Self::UnderInvestigation => 2,
+            Self::Affected => 3,
+        }
+    }
+
+    /// Map the slug of a status, `None` if OpenVEX has no such status
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "not_affected" => Some(Self::NotAffected),
+            "affected" => Some(Self::Affected),
+            "fixed" => Some(Self::Fixed),
+            "under_investigation" => Some(Self::UnderInvestigation),
+            _ => None,
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenVexJustification {
+    ComponentNotPresent,
+    VulnerableCodeNotPresent,
+    VulnerableCodeNotInExecutePath,
+    VulnerableCodeCannotBeControlledByAdversary,
+    InlineMitigationsAlreadyExist,
+}
+
+impl From<OverrideJustification> for OpenVexJustification {
+    fn from(value: OverrideJustification) -> Self {
+        match value {
+            OverrideJustification::ComponentNotPresent => Self::ComponentNotPresent,
+            OverrideJustification::VulnerableCodeNotPresent => Self::VulnerableCodeNotPresent,
+            OverrideJustification::VulnerableCodeNotInExecutePath => {
+                Self::VulnerableCodeNotInExecutePath
+            }
+            OverrideJustification::VulnerableCodeCannotBeControlledByAdversary => {
+                Self::VulnerableCodeCannotBeControlledByAdversary
+            }
+            OverrideJustification::InlineMitigationsAlreadyExist => {
+                Self::InlineMitigationsAlreadyExist
+            }
+        }
+    }
+}
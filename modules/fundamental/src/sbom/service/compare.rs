@@ -0,0 +1,66 @@
+use crate::{
+    Error,
+    sbom::{
+        model::compare::{ComparisonSide, NodeRelationship, SbomComparison},
+        service::SbomService,
+    },
+};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use tracing::instrument;
+use trustify_common::{db::query::Query, id::Id, model::Paginated};
+use trustify_entity::package_relates_to_package;
+use uuid::Uuid;
+
+impl SbomService {
+    /// Compare the packages of two SBOMs
+    ///
+    /// Fails with [`Error::NotFound`] if one of the SBOMs could not be found.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn compare<C: ConnectionTrait>(
+        &self,
+        old_id: Uuid,
+        new_id: Uuid,
+        connection: &C,
+    ) -> Result<SbomComparison, Error> {
+        let old = self.comparison_side(old_id, connection).await?;
+        let new = self.comparison_side(new_id, connection).await?;
+
+        Ok(SbomComparison::new(&old, &new))
+    }
+
+    async fn comparison_side<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        connection: &C,
+    ) -> Result<ComparisonSide, Error> {
+        if self
+            .fetch_sbom(Id::Uuid(sbom_id), connection)
+            .await?
+            .is_none()
+        {
+            return Err(Error::NotFound(format!("No SBOM {sbom_id}")));
+        }
+
+        let packages = self
+            .fetch_sbom_packages(sbom_id, Query::default(), Paginated::default(), connection)
+            .await?
+            .items;
+
+        let relationships = package_relates_to_package::Entity::find()
+            .filter(package_relates_to_package::Column::SbomId.eq(sbom_id))
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|rel| NodeRelationship {
+                left: rel.left_node_id,
+                relationship: rel.relationship,
+                right: rel.right_node_id,
+            })
+            .collect();
+
+        Ok(ComparisonSide {
+            packages,
+            relationships,
+        })
+    }
+}
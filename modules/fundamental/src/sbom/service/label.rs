@@ -1,7 +1,7 @@
 use crate::{Error, sbom::service::SbomService};
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ConnectionTrait, DatabaseBackend, EntityTrait,
-    IntoActiveModel, QueryTrait,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait,
+    IntoActiveModel, QueryFilter, QueryTrait,
 };
 use sea_query::Expr;
 use trustify_common::id::{Id, TrySelectForId};
@@ -20,6 +20,7 @@ impl SbomService {
         connection: &C,
     ) -> Result<Option<()>, Error> {
         let result = sbom::Entity::update_many()
+            .filter(sbom::Column::DeletedAt.is_null())
             .try_filter(id)?
             .col_expr(sbom::Column::Labels, Expr::value(labels.validate()?))
             .exec(connection)
@@ -42,6 +43,7 @@ impl SbomService {
         F: FnOnce(Labels) -> Labels,
     {
         let mut query = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
             .try_filter(id)?
             .build(DatabaseBackend::Postgres);
 
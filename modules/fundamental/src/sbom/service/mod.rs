@@ -1,6 +1,10 @@
 pub mod assertion;
+pub mod compare;
 pub mod label;
+pub mod quality;
+pub mod reprocess;
 pub mod sbom;
+pub mod vex;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,87 @@
+use crate::{
+    Error,
+    sbom::{
+        model::quality::{QualityWeights, SbomQualityScore},
+        service::SbomService,
+    },
+};
+use sea_orm::{ActiveEnum, ConnectionTrait, EntityTrait, FromQueryResult, Statement};
+use tracing::instrument;
+use trustify_entity::{relationship::Relationship, sbom};
+use uuid::Uuid;
+
+const SCORE_SQL: &str = r#"
+SELECT
+    count(*) AS total_components,
+    count(*) FILTER (WHERE EXISTS (
+        SELECT 1 FROM sbom_node_purl_ref r
+        WHERE r.sbom_id = p.sbom_id AND r.node_id = p.node_id
+    )) AS components_with_purl,
+    count(*) FILTER (WHERE EXISTS (
+        SELECT 1 FROM sbom_node_cpe_ref r
+        WHERE r.sbom_id = p.sbom_id AND r.node_id = p.node_id
+    )) AS components_with_cpe,
+    count(*) FILTER (WHERE coalesce(p.version, '') <> '') AS components_with_version,
+    count(*) FILTER (WHERE EXISTS (
+        SELECT 1 FROM sbom_package_license r
+        JOIN license ON license.id = r.license_id
+        WHERE r.sbom_id = p.sbom_id AND r.node_id = p.node_id
+            AND license.text NOT IN ('NOASSERTION', 'NONE')
+    )) AS components_with_license,
+    (
+        SELECT count(*) FROM package_relates_to_package r
+        WHERE r.sbom_id = $1 AND r.relationship <> $2
+    ) AS relationships_defined
+FROM sbom_package p
+WHERE p.sbom_id = $1
+"#;
+
+impl SbomService {
+    /// Score how complete the component information of an SBOM is
+    ///
+    /// Returns `Ok(None)` if the SBOM could not be found.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn score_sbom<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        weights: &QualityWeights,
+        connection: &C,
+    ) -> Result<Option<SbomQualityScore>, Error> {
+        #[derive(FromQueryResult)]
+        struct Row {
+            total_components: i64,
+            components_with_purl: i64,
+            components_with_cpe: i64,
+            components_with_version: i64,
+            components_with_license: i64,
+            relationships_defined: i64,
+        }
+
+        if sbom::Entity::find_by_id(sbom_id)
+            .one(connection)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let row = Row::find_by_statement(Statement::from_sql_and_values(
+            connection.get_database_backend(),
+            SCORE_SQL,
+            [sbom_id.into(), Relationship::Describes.to_value().into()],
+        ))
+        .one(connection)
+        .await?
+        .ok_or_else(|| Error::Data("Missing result of scoring query".into()))?;
+
+        Ok(Some(SbomQualityScore::new(
+            row.total_components as u64,
+            row.components_with_purl as u64,
+            row.components_with_cpe as u64,
+            row.components_with_version as u64,
+            row.components_with_license as u64,
+            row.relationships_defined as u64,
+            weights,
+        )))
+    }
+}
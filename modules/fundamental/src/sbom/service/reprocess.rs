@@ -0,0 +1,74 @@
+use crate::{
+    Error, common::service::retrieve_doc, sbom::model::ReprocessResult, sbom::service::SbomService,
+};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use tracing::instrument;
+use trustify_entity::{sbom_package, source_document};
+use trustify_module_ingestor::{
+    graph::Graph,
+    service::{self as ingestor, DocumentDetector, Format},
+};
+use trustify_module_storage::service::dispatch::DispatchBackend;
+use uuid::Uuid;
+
+impl SbomService {
+    /// Ingest an SBOM again from its stored source document, using the current ingestor.
+    ///
+    /// All content ingested from the document is replaced, while the ID, labels and group
+    /// assignments of the SBOM are kept.
+    #[instrument(skip(self, storage, connection), err(level=tracing::Level::INFO))]
+    pub async fn reprocess_from_source<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        storage: &DispatchBackend,
+        connection: &C,
+    ) -> Result<ReprocessResult, Error> {
+        let graph = Graph::new();
+        let Some(sbom) = graph
+            .get_sbom_by_id(sbom_id, connection)
+            .await
+            .map_err(ingestor::Error::from)?
+        else {
+            return Err(Error::NotFound(format!("No SBOM {sbom_id}")));
+        };
+
+        let Some(document) = source_document::Entity::find_by_id(sbom.sbom.source_document_id)
+            .one(connection)
+            .await?
+        else {
+            return Err(Error::NotFound(format!(
+                "No source document for SBOM {sbom_id}"
+            )));
+        };
+
+        let bytes = retrieve_doc(&document.sha256, storage).await?;
+        let detector = DocumentDetector::detect_as(&bytes, Format::SBOM)?;
+        let format = detector.format();
+
+        let packages_before = Self::count_packages(sbom_id, connection).await?;
+        let warnings = detector.reprocess(sbom, connection).await?;
+        let packages_after = Self::count_packages(sbom_id, connection).await?;
+
+        tracing::info!(
+            "Reprocessed SBOM {sbom_id} ({format}): {packages_before} -> {packages_after} packages"
+        );
+
+        Ok(ReprocessResult {
+            sbom_id,
+            format,
+            packages_before,
+            packages_after,
+            warnings,
+        })
+    }
+
+    async fn count_packages<C: ConnectionTrait>(
+        sbom_id: Uuid,
+        connection: &C,
+    ) -> Result<u64, Error> {
+        Ok(sbom_package::Entity::find()
+            .filter(sbom_package::Column::SbomId.eq(sbom_id))
+            .count(connection)
+            .await?)
+    }
+}
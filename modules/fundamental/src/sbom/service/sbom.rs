@@ -4,9 +4,12 @@ use crate::{
     common::license_filtering::{LICENSE, license_text_coalesce},
     purl::model::summary::purl::PurlSummary,
     sbom::model::{
-        AffectedSeverity, ModelCatcher, SbomAdvisorySummary, SbomExternalPackageReference,
-        SbomModel, SbomNodeReference, SbomPackage, SbomPackageRelation, SbomPackageSummary,
-        SbomSummary, Which, details::SbomDetails, raw_sql,
+        AffectedSeverity, CpeEntry, ModelCatcher, PackageExternalReference, SbomAdvisorySummary,
+        SbomAttestation, SbomExternalPackageReference, SbomFormulation, SbomModel,
+        SbomNodeReference, SbomPackage, SbomPackageRelation, SbomPackageSummary, SbomSnippet,
+        SbomSummary, Which,
+        details::{SbomAdvisory, SbomDetails},
+        raw_sql,
     },
 };
 use sea_orm::{
@@ -18,16 +21,18 @@ use sea_query::{ColumnType, Expr, JoinType, UnionType, extension::postgres::PgEx
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{collections::HashMap, fmt::Debug, sync::Arc, vec::Vec};
+use time::OffsetDateTime;
 use tracing::{Instrument, info_span, instrument};
 use trustify_common::{
     cpe::Cpe,
     db::{
-        limiter::{LimitedResult, LimiterTrait, limit_selector},
+        cursor::{CursorLimiterTrait, CursorPage, CursorQuery},
+        limiter::{LimitedResult, LimiterAsModelTrait, LimiterTrait, limit_selector},
         multi_model::{FromQueryResultMultiModel, SelectIntoMultiModel},
-        query::{Columns, Filtering, IntoColumns, Query, q},
+        query::{Columns, Filtering, IntoColumns, Query},
     },
     id::{Id, TrySelectForId},
-    model::{PaginatedResults, Pagination},
+    model::{Limit, PaginatedResults, Pagination},
     purl::Purl,
     requested_field::BoolRequestedField,
     service::{Mappable, Resulting},
@@ -38,8 +43,9 @@ use trustify_entity::{
     labels::Labels,
     license, organization, package_relates_to_package, qualified_purl,
     relationship::Relationship,
-    sbom, sbom_ai, sbom_group_assignment, sbom_license_expanded, sbom_node, sbom_node_cpe_ref,
-    sbom_node_purl_ref, sbom_package, sbom_package_license, source_document, status,
+    sbom, sbom_ai, sbom_attestation, sbom_formulation, sbom_group_assignment,
+    sbom_license_expanded, sbom_node, sbom_node_cpe_ref, sbom_node_purl_ref, sbom_package,
+    sbom_package_external_ref, sbom_package_license, sbom_snippet, source_document, status,
     versioned_purl, vulnerability,
 };
 
@@ -82,6 +88,7 @@ impl SbomService {
         connection: &C,
     ) -> Result<Option<(sbom::Model, sbom_node::Model, source_document::Model)>, Error> {
         let select = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
             .find_also_linked(sbom::SbomNodeLink)
             .find_also_related(source_document::Entity)
             .try_filter(id)?;
@@ -108,6 +115,32 @@ impl SbomService {
         })
     }
 
+    /// Fetch the advisories affecting an SBOM, one page at a time
+    ///
+    /// Advisories are ordered by their identifier. Returns `None` if the SBOM could not be found.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn vulnerabilities_for_sbom<C>(
+        &self,
+        id: Id,
+        statuses: Vec<String>,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<Option<PaginatedResults<SbomAdvisory>>, Error>
+    where
+        C: ConnectionTrait + StreamTrait,
+    {
+        let Some(details) = self.fetch_sbom_details(id, statuses, connection).await? else {
+            return Ok(None);
+        };
+
+        let mut advisories = details.advisories;
+        advisories.sort_by(|a, b| {
+            (&a.head.identifier, a.head.uuid).cmp(&(&b.head.identifier, b.head.uuid))
+        });
+
+        Ok(Some(paginated.paginate_array(&advisories)))
+    }
+
     /// fetch the summary of one sbom
     #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
     pub async fn fetch_sbom_summary<C: ConnectionTrait>(
@@ -198,6 +231,94 @@ impl SbomService {
         Ok(digests)
     }
 
+    /// Mark SBOMs as deleted
+    ///
+    /// Deleted SBOMs are hidden from all queries, but their data is kept until they get purged.
+    /// Returns the IDs of the SBOMs which got deleted, ignoring those which were already deleted
+    /// or don't exist.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn soft_delete_sboms<C: ConnectionTrait>(
+        &self,
+        ids: Vec<Uuid>,
+        connection: &C,
+    ) -> Result<Vec<Uuid>, Error> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(sbom::Entity::update_many()
+            .col_expr(sbom::Column::DeletedAt, Expr::current_timestamp().into())
+            .filter(sbom::Column::SbomId.is_in(ids))
+            .filter(sbom::Column::DeletedAt.is_null())
+            .exec_with_returning(connection)
+            .await?
+            .into_iter()
+            .map(|sbom| sbom.sbom_id)
+            .collect())
+    }
+
+    /// Restore a deleted SBOM
+    ///
+    /// Returns `Ok(Some(()))` if a deleted SBOM was found and restored. Otherwise, it will return
+    /// `Ok(None)`.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn restore_sbom<C: ConnectionTrait>(
+        &self,
+        id: Id,
+        connection: &C,
+    ) -> Result<Option<()>, Error> {
+        let result = sbom::Entity::update_many()
+            .col_expr(sbom::Column::DeletedAt, Expr::value(None::<OffsetDateTime>))
+            .filter(sbom::Column::DeletedAt.is_not_null())
+            .try_filter(id)?
+            .exec(connection)
+            .await?;
+
+        Ok((result.rows_affected > 0).then_some(()))
+    }
+
+    /// Permanently delete an SBOM, which must have been deleted before
+    ///
+    /// Returns the keys of blobs to be removed from the storage, like [`Self::delete_sboms`].
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn purge_sbom<C: ConnectionTrait>(
+        &self,
+        id: Id,
+        connection: &C,
+    ) -> Result<Vec<String>, Error> {
+        let ids = sbom::Entity::find()
+            .select_only()
+            .column(sbom::Column::SbomId)
+            .filter(sbom::Column::DeletedAt.is_not_null())
+            .try_filter(id)?
+            .into_tuple()
+            .all(connection)
+            .await?;
+
+        self.delete_sboms(ids, connection).await
+    }
+
+    /// Permanently delete all SBOMs which have been deleted for longer than the retention period
+    ///
+    /// Returns the keys of blobs to be removed from the storage, like [`Self::delete_sboms`].
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn purge_deleted_sboms<C: ConnectionTrait>(
+        &self,
+        retention: std::time::Duration,
+        connection: &C,
+    ) -> Result<Vec<String>, Error> {
+        let deleted_before = OffsetDateTime::now_utc() - retention;
+        let ids = sbom::Entity::find()
+            .select_only()
+            .column(sbom::Column::SbomId)
+            .filter(sbom::Column::DeletedAt.lt(deleted_before))
+            .into_tuple()
+            .all(connection)
+            .await?;
+
+        self.delete_sboms(ids, connection).await
+    }
+
     /// fetch all SBOMs
     #[instrument(
         skip(self, connection),
@@ -214,11 +335,10 @@ impl SbomService {
         C: ConnectionTrait,
         P: IntoPackage,
     {
-        let mut query = if options.labels.is_empty() {
-            sbom::Entity::find()
-        } else {
-            sbom::Entity::find().filter(Expr::col(sbom::Column::Labels).contains(options.labels))
-        };
+        let mut query = sbom::Entity::find().filter(sbom::Column::DeletedAt.is_null());
+        if !options.labels.is_empty() {
+            query = query.filter(Expr::col(sbom::Column::Labels).contains(options.labels));
+        }
 
         if let Some(group_ids) = options.groups {
             query = query.filter(
@@ -233,10 +353,7 @@ impl SbomService {
         }
 
         // Add license filtering if license query is present
-        if let Some(license_query) = search
-            .get_constraint_for_field(LICENSE)
-            .map(|constraint| q(&format!("{constraint}")))
-        {
+        if let Some(license_query) = search.get_query_for_field(LICENSE)? {
             // SPDX path: join through junction → dictionary
             let mut spdx_select = sbom_license_expanded::Entity::find()
                 .select_only()
@@ -280,7 +397,7 @@ impl SbomService {
             query = query.filter(sbom::Column::SbomId.in_subquery(spdx_select.into_query()));
         }
 
-        let limiter = query
+        let query = query
             .join(JoinType::InnerJoin, sbom::Relation::SbomNode.def())
             .select_also(sbom_node::Entity)
             .find_also_related(source_document::Entity)
@@ -298,14 +415,32 @@ impl SbomService {
                             _ => None,
                         },
                     }),
-            )?
-            .limiting(connection, paginated, &self.cache)?;
+            )?;
 
-        let LimitedResult {
-            items: sboms,
-            total,
-        } = limiter.fetch().await?;
-        let total = total.requested(paginated.total()).await?;
+        let (sboms, total, next_cursor) = match paginated.cursor() {
+            Some(cursor) => {
+                let CursorPage { items, next_cursor } = query
+                    .clone()
+                    .cursor_limiting(connection, paginated.limit(), &self.cache)?
+                    .next_page(Some(cursor))
+                    .await?;
+                // only count, without fetching any items
+                let LimitedResult { total, .. } = query
+                    .limiting(connection, Limit(0), &self.cache)?
+                    .fetch()
+                    .await?;
+                let total = total.requested(paginated.total()).await?;
+                (items, total, next_cursor)
+            }
+            None => {
+                let LimitedResult { items, total } = query
+                    .limiting(connection, paginated, &self.cache)?
+                    .fetch()
+                    .await?;
+                let total = total.requested(paginated.total()).await?;
+                (items, total, None)
+            }
+        };
 
         let filtered: Vec<_> = sboms
             .into_iter()
@@ -315,7 +450,11 @@ impl SbomService {
         let items =
             SbomSummary::from_entities(filtered, self, options.advisories, connection).await?;
 
-        Ok(PaginatedResults { total, items })
+        Ok(PaginatedResults {
+            total,
+            items,
+            next_cursor,
+        })
     }
 
     /// Fetch all packages from an SBOM.
@@ -349,10 +488,7 @@ impl SbomService {
         // The `filtering_with` translator cannot express OR across two different table columns,
         // so we pre-filter node_ids: any package whose SPDX-expanded text OR raw license text
         // matches the query is included.
-        if let Some(license_constraint) = search
-            .get_constraint_for_field(LICENSE)
-            .map(|constraint| q(&format!("{constraint}")))
-        {
+        if let Some(license_constraint) = search.get_query_for_field(LICENSE)? {
             // SPDX path: match via expanded_license dictionary
             let mut spdx_pkg_select = sbom_package_license::Entity::find()
                 .select_only()
@@ -425,7 +561,9 @@ impl SbomService {
             )?
             // default order
             .order_by_asc(sbom_node::Column::Name)
-            .order_by_asc(sbom_package::Column::Version);
+            .order_by_asc(sbom_package::Column::Version)
+            // stable order for paging through packages sharing name and version
+            .order_by_asc(sbom_package::Column::NodeId);
 
         // limit and execute
 
@@ -436,7 +574,11 @@ impl SbomService {
         let total = total.requested(paginated.total()).await?;
         let items = items.into_iter().map(SbomPackage::from_row).collect();
 
-        Ok(PaginatedResults { items, total })
+        Ok(PaginatedResults {
+            items,
+            total,
+            next_cursor: None,
+        })
     }
 
     /// Fetch AI models associated with an SBOM.
@@ -540,7 +682,162 @@ impl SbomService {
             })
             .collect();
 
-        Ok(PaginatedResults { items, total })
+        Ok(PaginatedResults {
+            items,
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Fetch the CycloneDX formulations of an SBOM.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn fetch_sbom_formulations<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        search: Query,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<SbomFormulation>, Error> {
+        let limiter = sbom_formulation::Entity::find()
+            .filter(sbom_formulation::Column::SbomId.eq(sbom_id))
+            .filtering(search)?
+            .order_by_asc(sbom_formulation::Column::BomRef)
+            .limiting(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items.into_iter().map(SbomFormulation::from).collect(),
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Fetch the CycloneDX attestations of an SBOM.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn fetch_sbom_attestations<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        search: Query,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<SbomAttestation>, Error> {
+        let limiter = sbom_attestation::Entity::find()
+            .filter(sbom_attestation::Column::SbomId.eq(sbom_id))
+            .filtering(search)?
+            .order_by_asc(sbom_attestation::Column::Id)
+            .limiting(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items.into_iter().map(SbomAttestation::from).collect(),
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Fetch the SPDX snippets of an SBOM.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn fetch_sbom_snippets<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<SbomSnippet>, Error> {
+        let limiter = sbom_snippet::Entity::find()
+            .join(JoinType::Join, sbom_snippet::Relation::Node.def())
+            .column_as(sbom_node::Column::Name, "name")
+            .filter(sbom_snippet::Column::SbomId.eq(sbom_id))
+            .order_by_asc(sbom_snippet::Column::NodeId)
+            .limiting_as::<SbomSnippet>(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items,
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Fetch the CPEs referenced by a single node of an SBOM.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn cpes_for_node<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        node_id: &str,
+        connection: &C,
+    ) -> Result<Vec<CpeEntry>, Error> {
+        let cpes = cpe::Entity::find()
+            .join(JoinType::Join, cpe::Relation::SbomNode.def())
+            .filter(sbom_node_cpe_ref::Column::SbomId.eq(sbom_id))
+            .filter(sbom_node_cpe_ref::Column::NodeId.eq(node_id))
+            .order_by_asc(cpe::Column::Id)
+            .all(connection)
+            .await?;
+
+        Ok(cpes.into_iter().map(CpeEntry::from).collect())
+    }
+
+    /// Fetch the external references of a single package of an SBOM.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn external_refs_for_node<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        node_id: &str,
+        connection: &C,
+    ) -> Result<Vec<PackageExternalReference>, Error> {
+        let refs = sbom_package_external_ref::Entity::find()
+            .filter(sbom_package_external_ref::Column::SbomId.eq(sbom_id))
+            .filter(sbom_package_external_ref::Column::NodeId.eq(node_id))
+            .order_by_asc(sbom_package_external_ref::Column::RefType)
+            .order_by_asc(sbom_package_external_ref::Column::Url)
+            .all(connection)
+            .await?;
+
+        Ok(refs
+            .into_iter()
+            .map(PackageExternalReference::from)
+            .collect())
+    }
+
+    /// Fetch the external references of all packages of an SBOM, optionally limited to a type.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn fetch_sbom_external_refs<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        ref_type: Option<&str>,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<PackageExternalReference>, Error> {
+        let mut query = sbom_package_external_ref::Entity::find()
+            .filter(sbom_package_external_ref::Column::SbomId.eq(sbom_id));
+
+        if let Some(ref_type) = ref_type {
+            query = query.filter(sbom_package_external_ref::Column::RefType.eq(ref_type));
+        }
+
+        let limiter = query
+            .order_by_asc(sbom_package_external_ref::Column::NodeId)
+            .order_by_asc(sbom_package_external_ref::Column::RefType)
+            .order_by_asc(sbom_package_external_ref::Column::Url)
+            .limiting(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items
+                .into_iter()
+                .map(PackageExternalReference::from)
+                .collect(),
+            total,
+            next_cursor: None,
+        })
     }
 
     /// Get all packages describing the SBOM.
@@ -562,7 +859,7 @@ impl SbomService {
             paginated,
             Which::Left,
             SbomNodeReference::All,
-            Some(Relationship::Describes),
+            vec![Relationship::Describes],
             db,
         )
         .await
@@ -656,6 +953,9 @@ impl SbomService {
 
     /// Count affected vulnerabilities grouped by severity for multiple SBOMs
     /// in a single batch query, combining both PURL and CPE matching paths.
+    ///
+    /// The counts are read from the pre-computed advisory summary, so they only reflect
+    /// documents ingested before the last call to [`Self::refresh_advisory_summary`].
     #[instrument(skip(self, db), err(level=tracing::Level::INFO))]
     pub async fn batch_advisory_severity_counts<C: ConnectionTrait>(
         &self,
@@ -698,6 +998,16 @@ impl SbomService {
         Ok(result)
     }
 
+    /// Re-compute the advisory summary, used by [`Self::batch_advisory_severity_counts`].
+    ///
+    /// Refreshing doesn't block reading the summary, but may take a while on large databases.
+    #[instrument(skip(self, db), err(level=tracing::Level::INFO))]
+    pub async fn refresh_advisory_summary<C: ConnectionTrait>(&self, db: &C) -> Result<(), Error> {
+        db.execute_unprepared(raw_sql::REFRESH_ADVISORY_SUMMARY_SQL)
+            .await?;
+        Ok(())
+    }
+
     #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
     pub async fn count_related_sboms<C: ConnectionTrait>(
         &self,
@@ -730,6 +1040,7 @@ impl SbomService {
 
         counts_map.extend(
             sbom::Entity::find()
+                .filter(sbom::Column::DeletedAt.is_null())
                 .join(JoinType::Join, sbom::Relation::Node.def())
                 .join(JoinType::Join, sbom_node::Relation::Cpe.def())
                 .filter(sbom_node_cpe_ref::Column::CpeId.is_in(cpes))
@@ -754,6 +1065,7 @@ impl SbomService {
 
         counts_map.extend(
             sbom::Entity::find()
+                .filter(sbom::Column::DeletedAt.is_null())
                 .join(JoinType::Join, sbom::Relation::Node.def())
                 .join(JoinType::Join, sbom_node::Relation::Purl.def())
                 .filter(sbom_node_purl_ref::Column::QualifiedPurlId.is_in(purls))
@@ -788,7 +1100,9 @@ impl SbomService {
         query: Query,
         connection: &C,
     ) -> Result<PaginatedResults<SbomSummary>, Error> {
-        let select = sbom::Entity::find().join(JoinType::Join, sbom::Relation::Node.def());
+        let select = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
+            .join(JoinType::Join, sbom::Relation::Node.def());
 
         let select = match package_ref {
             SbomExternalPackageReference::Purl(purl) => select
@@ -799,6 +1113,18 @@ impl SbomService {
                 .filter(sbom_node_cpe_ref::Column::CpeId.eq(cpe.uuid())),
         };
 
+        self.related_sboms(select, paginated, query, connection)
+            .await
+    }
+
+    /// Fetch a page of the summaries of the SBOMs selected by `select`.
+    pub(crate) async fn related_sboms<C: ConnectionTrait>(
+        &self,
+        select: Select<sbom::Entity>,
+        paginated: impl Pagination,
+        query: Query,
+        connection: &C,
+    ) -> Result<PaginatedResults<SbomSummary>, Error> {
         let query = select
             .find_also_linked(sbom::SbomNodeLink)
             .find_also_related(source_document::Entity)
@@ -826,10 +1152,17 @@ impl SbomService {
 
         let items = SbomSummary::from_entities(filtered, self, false, connection).await?;
 
-        Ok(PaginatedResults { items, total })
+        Ok(PaginatedResults {
+            items,
+            total,
+            next_cursor: None,
+        })
     }
 
     /// Fetch all related packages in the context of an SBOM.
+    ///
+    /// Only relationships of one of the provided types are considered, or all relationships if
+    /// `relationships` is empty.
     #[allow(clippy::too_many_arguments)]
     #[instrument(skip(self, db), err(level=tracing::Level::INFO))]
     pub async fn fetch_related_packages<C, R, P>(
@@ -839,7 +1172,7 @@ impl SbomService {
         options: R,
         which: Which,
         reference: impl Into<SbomNodeReference<'_>> + Debug,
-        relationship: Option<Relationship>,
+        relationships: Vec<Relationship>,
         db: &C,
     ) -> Result<R::Output<SbomPackageRelation<P>>, Error>
     where
@@ -900,8 +1233,9 @@ impl SbomService {
 
         // add relationship type filter
 
-        if let Some(relationship) = relationship {
-            query = query.filter(package_relates_to_package::Column::Relationship.eq(relationship));
+        if !relationships.is_empty() {
+            query =
+                query.filter(package_relates_to_package::Column::Relationship.is_in(relationships));
         }
 
         // execute
@@ -940,7 +1274,7 @@ impl SbomService {
                 (),
                 Which::Left,
                 pkg,
-                relationship.into(),
+                relationship.into().into_iter().collect(),
                 tx,
             )
             .await?;
@@ -1074,7 +1408,7 @@ impl IntoPackage for SbomPackage {
 /// built using [`package_from_row`].
 ///
 /// This will add the columns `purls` and `cpes` to the selected output.
-fn join_purls_and_cpes<E>(query: Select<E>) -> Select<E>
+pub(crate) fn join_purls_and_cpes<E>(query: Select<E>) -> Select<E>
 where
     E: EntityTrait,
 {
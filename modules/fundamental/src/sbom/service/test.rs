@@ -14,7 +14,7 @@ use trustify_common::{
         query::{Query, q},
     },
     id::Id,
-    model::{Limit, Paginated},
+    model::{CursorPaginated, Limit, Paginated},
     purl::Purl,
 };
 use trustify_entity::{labels::Labels, sbom_ancestor, sbom_describing_cpe};
@@ -998,3 +998,139 @@ async fn delete_ancestor_sbom_cleans_ancestor_link(
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn purge_deleted_sboms(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let result = ctx
+        .ingest_document("quarkus-bom-2.13.8.Final-redhat-00004.json")
+        .await?;
+    let sbom_uuid: Uuid = result.id.parse()?;
+
+    let service = SbomService::new(PaginationCache::for_test());
+
+    let tx = ctx.db.begin().await?;
+    assert_eq!(
+        service.soft_delete_sboms(vec![sbom_uuid], &tx).await?,
+        vec![sbom_uuid]
+    );
+    // deleting again doesn't report the SBOM
+    assert!(
+        service
+            .soft_delete_sboms(vec![sbom_uuid], &tx)
+            .await?
+            .is_empty()
+    );
+    tx.commit().await?;
+
+    assert!(
+        service
+            .fetch_sbom(Id::Uuid(sbom_uuid), &ctx.db)
+            .await?
+            .is_none()
+    );
+
+    // SBOMs within the retention period are kept
+    let tx = ctx.db.begin().await?;
+    assert!(
+        service
+            .purge_deleted_sboms(std::time::Duration::from_secs(60 * 60), &tx)
+            .await?
+            .is_empty()
+    );
+    tx.commit().await?;
+
+    // uploading the document again restores the SBOM
+    ctx.ingest_document("quarkus-bom-2.13.8.Final-redhat-00004.json")
+        .await?;
+    assert!(
+        service
+            .fetch_sbom(Id::Uuid(sbom_uuid), &ctx.db)
+            .await?
+            .is_some()
+    );
+
+    // only deleted SBOMs get purged
+    let tx = ctx.db.begin().await?;
+    assert!(
+        service
+            .purge_deleted_sboms(std::time::Duration::ZERO, &tx)
+            .await?
+            .is_empty()
+    );
+    service.soft_delete_sboms(vec![sbom_uuid], &tx).await?;
+    assert!(
+        !service
+            .purge_deleted_sboms(std::time::Duration::ZERO, &tx)
+            .await?
+            .is_empty()
+    );
+    tx.commit().await?;
+
+    assert!(
+        trustify_entity::sbom::Entity::find_by_id(sbom_uuid)
+            .one(&ctx.db)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn fetch_sboms_with_cursor(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_documents([
+        "spdx/quarkus-bom-3.2.11.Final-redhat-00001.json",
+        "spdx/quarkus-bom-3.2.12.Final-redhat-00002.json",
+        "zookeeper-3.9.2-cyclonedx.json",
+    ])
+    .await?;
+
+    let service = SbomService::new(PaginationCache::for_test());
+
+    let first = service
+        .fetch_sboms::<_, SbomPackage>(
+            Default::default(),
+            CursorPaginated {
+                limit: 2,
+                total: true,
+                cursor: Some(Default::default()),
+                ..Default::default()
+            },
+            Default::default(),
+            &ctx.db,
+        )
+        .await?;
+
+    assert_eq!(first.total, Some(3));
+    assert_eq!(first.items.len(), 2);
+    assert!(first.next_cursor.is_some());
+
+    let second = service
+        .fetch_sboms::<_, SbomPackage>(
+            Default::default(),
+            CursorPaginated {
+                limit: 2,
+                cursor: first.next_cursor,
+                ..Default::default()
+            },
+            Default::default(),
+            &ctx.db,
+        )
+        .await?;
+
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.next_cursor, None);
+
+    // pages don't overlap, and are ordered by ID
+    let ids: Vec<_> = first
+        .items
+        .iter()
+        .chain(&second.items)
+        .map(|sbom| sbom.head.id)
+        .collect();
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use crate::{
+    Error,
+    sbom::model::{
+        raw_sql::CONTEXT_CPE_FILTER_SQL,
+        vex::{
+            OPENVEX_CONTEXT, OpenVexDocument, OpenVexJustification, OpenVexProduct,
+            OpenVexStatement, OpenVexStatus, OpenVexVulnerability,
+        },
+    },
+};
+use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
+use std::collections::{BTreeMap, BTreeSet, btree_map::Entry};
+use time::OffsetDateTime;
+use tracing::instrument;
+use trustify_common::purl::Purl;
+use trustify_entity::{labels::Labels, qualified_purl::CanonicalPurl};
+use trustify_module_ingestor::service::advisory::user_override::{
+    JUSTIFICATION_LABEL, OverrideJustification, is_user_override,
+};
+use uuid::Uuid;
+
+/// The base of the `@id` of exported VEX documents
+const DOCUMENT_ID_BASE: &str = "https://trustify.local/vex";
+
+#[derive(FromQueryResult)]
+struct StatusRow {
+    purl: CanonicalPurl,
+    vulnerability_id: String,
+    status: String,
+    identifier: String,
+    labels: Labels,
+    description: Option<String>,
+    action: Option<String>,
+}
+
+/// The status of a package, for a single vulnerability
+struct PackageStatus {
+    status: OpenVexStatus,
+    user_override: bool,
+    identifier: String,
+    justification: Option<OpenVexJustification>,
+    impact_statement: Option<String>,
+    action_statement: Option<String>,
+}
+
+impl PackageStatus {
+    fn from_row(row: StatusRow) -> Option<Self> {
+        let status = OpenVexStatus::from_slug(&row.status)?;
+        let user_override = is_user_override(&row.labels);
+
+        // only user overrides carry a justification and an impact statement
+        let (justification, impact_statement) = if user_override {
+            let justification = row
+                .labels
+                .0
+                .get(JUSTIFICATION_LABEL)
+                .and_then(|value| {
+                    serde_json::from_value::<OverrideJustification>(value.as_str().into()).ok()
+                })
+                .map(OpenVexJustification::from);
+            (justification, row.description)
+        } else {
+            (None, None)
+        };
+
+        Some(Self {
+            status,
+            user_override,
+            identifier: row.identifier,
+            justification,
+            impact_statement,
+            action_statement: row.action,
+        })
+    }
+
+    /// Check if this status takes precedence over another one: a user override over all other
+    /// statuses, otherwise the status with the higher precedence.
+    fn supersedes(&self, other: &Self) -> bool {
+        (self.user_override, self.status.precedence())
+            > (other.user_override, other.status.precedence())
+    }
+
+    /// Fill in the statements OpenVEX requires for the status, if they are missing
+    fn complete(mut self) -> Self {
+        match self.status {
+            OpenVexStatus::NotAffected
+                if self.justification.is_none() && self.impact_statement.is_none() =>
+            {
+                self.impact_statement = Some(format!(
+                    "Not affected according to advisory {}",
+                    self.identifier
+                ));
+            }
+            OpenVexStatus::Affected if self.action_statement.is_none() => {
+                self.action_statement = Some(format!(
+                    "Update to a version which is not affected, see advisory {}",
+                    self.identifier
+                ));
+            }
+            _ => {}
+        }
+        self
+    }
+}
+
+/// The key statements are grouped by, all packages sharing it end up in the same statement
+type StatementKey = (
+    String,
+    OpenVexStatus,
+    Option<OpenVexJustification>,
+    Option<String>,
+    Option<String>,
+);
+
+pub struct VexExportService {}
+
+impl VexExportService {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Export the package statuses of all packages of an SBOM as an OpenVEX document
+    ///
+    /// Each package has a single status per vulnerability. A user override takes precedence over
+    /// the statuses of advisories, conflicting statuses of advisories are resolved by
+    /// [`OpenVexStatus::precedence`].
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn export_for_sbom<C: ConnectionTrait>(
+        &self,
+        sbom_id: Uuid,
+        connection: &C,
+    ) -> Result<OpenVexDocument, Error> {
+        let sql = format!(
+            r#"
+SELECT DISTINCT
+    qualified_purl.purl AS purl,
+    purl_status.vulnerability_id,
+    status.slug AS status,
+    advisory.identifier,
+    advisory.labels,
+    advisory_vulnerability.description,
+    (
+        SELECT string_agg(DISTINCT remediation.details, E'\n')
+        FROM remediation_purl_status
+        JOIN remediation ON remediation.id = remediation_purl_status.remediation_id
+        WHERE remediation_purl_status.purl_status_id = purl_status.id
+    ) AS action
+FROM sbom_package
+JOIN sbom_node_purl_ref
+    ON sbom_node_purl_ref.sbom_id = sbom_package.sbom_id
+    AND sbom_node_purl_ref.node_id = sbom_package.node_id
+JOIN qualified_purl ON qualified_purl.id = sbom_node_purl_ref.qualified_purl_id
+JOIN versioned_purl ON versioned_purl.id = qualified_purl.versioned_purl_id
+JOIN purl_status ON purl_status.base_purl_id = versioned_purl.base_purl_id
+JOIN version_range ON version_range.id = purl_status.version_range_id
+JOIN status ON status.id = purl_status.status_id
+JOIN advisory ON advisory.id = purl_status.advisory_id
+LEFT JOIN advisory_vulnerability
+    ON advisory_vulnerability.advisory_id = purl_status.advisory_id
+    AND advisory_vulnerability.vulnerability_id = purl_status.vulnerability_id
+WHERE sbom_package.sbom_id = $1
+    AND advisory.deprecated = false
+    AND version_matches(versioned_purl.version, version_range.*)
+    AND {CONTEXT_CPE_FILTER_SQL}
+ORDER BY advisory.identifier
+"#
+        );
+
+        let rows = StatusRow::find_by_statement(Statement::from_sql_and_values(
+            connection.get_database_backend(),
+            sql,
+            [sbom_id.into()],
+        ))
+        .all(connection)
+        .await?;
+
+        // collapse the statuses of each package and vulnerability into a single one
+
+        let mut packages = BTreeMap::<(String, String), PackageStatus>::new();
+
+        for row in rows {
+            let key = (
+                Purl::from(row.purl.clone()).to_string(),
+                row.vulnerability_id.clone(),
+            );
+            let Some(status) = PackageStatus::from_row(row) else {
+                continue;
+            };
+
+            match packages.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(status);
+                }
+                Entry::Occupied(mut entry) => {
+                    if status.supersedes(entry.get()) {
+                        entry.insert(status);
+                    }
+                }
+            }
+        }
+
+        let mut statements = BTreeMap::<StatementKey, BTreeSet<String>>::new();
+
+        for ((purl, vulnerability), status) in packages {
+            let status = status.complete();
+            statements
+                .entry((
+                    vulnerability,
+                    status.status,
+                    status.justification,
+                    status.impact_statement,
+                    status.action_statement,
+                ))
+                .or_default()
+                .insert(purl);
+        }
+
+        Ok(OpenVexDocument {
+            context: OPENVEX_CONTEXT.to_string(),
+            id: format!("{DOCUMENT_ID_BASE}/{sbom_id}"),
+            author: "Trustify".to_string(),
+            timestamp: OffsetDateTime::now_utc(),
+            version: 1,
+            statements: statements
+                .into_iter()
+                .map(
+                    |(
+                        (vulnerability, status, justification, impact_statement, action_statement),
+                        purls,
+                    )| OpenVexStatement {
+                        vulnerability: OpenVexVulnerability {
+                            name: vulnerability,
+                        },
+                        products: purls.into_iter().map(|id| OpenVexProduct { id }).collect(),
+                        status,
+                        justification,
+                        impact_statement,
+                        action_statement,
+                    },
+                )
+                .collect(),
+        })
+    }
+}
+
+impl Default for VexExportService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
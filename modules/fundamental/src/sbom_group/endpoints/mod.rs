@@ -3,7 +3,7 @@ mod test;
 
 use super::{
     model::*,
-    service::{ListOptions, SbomGroupService},
+    service::{CompareOptions, ListOptions, SbomGroupService, VulnerabilitySummaryOptions},
 };
 use crate::Error;
 use actix_web::{
@@ -40,9 +40,15 @@ pub fn configure(
         .app_data(web::Data::new(service))
         .service(list)
         .service(create)
+        .service(compare)
         .service(read)
+        .service(vulnerability_summary)
         .service(update)
         .service(delete)
+        .service(auto_assign)
+        .service(auto_unassign)
+        .service(add_members)
+        .service(remove_members)
         .service(read_assignments)
         .service(update_assignments)
         .service(bulk_update_assignments)
@@ -83,6 +89,45 @@ async fn list(
     Ok(HttpResponse::Ok().json(result))
 }
 
+#[utoipa::path(
+    tag = "sbomGroup",
+    operation_id = "compareSbomGroups",
+    params(
+        CompareOptions,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "The SBOMs of both groups were compared", body = GroupComparison),
+        (status = 400, description = "The request was not valid"),
+        (status = 401, description = "The user was not authenticated"),
+        (status = 403, description = "The user authenticated, but not authorized for this operation"),
+        (status = 404, description = "One of the groups was not found"),
+    )
+)]
+#[get("/v3/group/sbom/compare")]
+/// Compare the SBOMs assigned to two groups
+async fn compare(
+    service: web::Data<SbomGroupService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(options): web::Query<CompareOptions>,
+    web::Query(pagination): web::Query<Paginated>,
+    _: Require<ReadSbomGroup>,
+    _: Require<ReadSbom>,
+) -> actix_web::Result<impl Responder> {
+    let tx = db.begin().await?;
+    let result = service
+        .compare(
+            &options.left,
+            &options.right,
+            options.recursive,
+            pagination,
+            &tx,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
 #[derive(Serialize, ToSchema)]
 struct CreateResponse {
     /// The ID of the newly created group
@@ -201,6 +246,134 @@ async fn update(
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    tag = "sbomGroup",
+    operation_id = "autoAssignSbomGroup",
+    request_body = AutoAssignRequest,
+    params(
+        ("id", Path, description = "The ID of the group"),
+    ),
+    responses(
+        (status = 200, description = "The matching SBOMs were assigned to the group", body = AutoAssignResult),
+        (status = 400, description = "The request was not valid"),
+        (status = 401, description = "The user was not authenticated"),
+        (status = 403, description = "The user authenticated, but not authorized for this operation"),
+        (status = 404, description = "The group was not found"),
+    )
+)]
+#[post("/v3/group/sbom/{id}/auto-assign")]
+/// Assign all SBOMs carrying a label to a group
+async fn auto_assign(
+    service: web::Data<SbomGroupService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    web::Json(request): web::Json<AutoAssignRequest>,
+    _: Require<UpdateSbomGroup>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let affected = service
+        .auto_assign_by_label(&id, &request.label_key, &request.label_value, &tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(AutoAssignResult { affected }))
+}
+
+#[utoipa::path(
+    tag = "sbomGroup",
+    operation_id = "autoUnassignSbomGroup",
+    params(
+        ("id", Path, description = "The ID of the group"),
+        AutoAssignRequest,
+    ),
+    responses(
+        (status = 204, description = "The matching SBOMs were removed from the group"),
+        (status = 400, description = "The request was not valid"),
+        (status = 401, description = "The user was not authenticated"),
+        (status = 403, description = "The user authenticated, but not authorized for this operation"),
+        (status = 404, description = "The group was not found"),
+    )
+)]
+#[delete("/v3/group/sbom/{id}/auto-assign")]
+/// Remove all SBOMs carrying a label from a group
+async fn auto_unassign(
+    service: web::Data<SbomGroupService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    web::Query(request): web::Query<AutoAssignRequest>,
+    _: Require<UpdateSbomGroup>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    service
+        .auto_unassign_by_label(&id, &request.label_key, &request.label_value, &tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    tag = "sbomGroup",
+    operation_id = "addSbomGroupMembers",
+    request_body = GroupMembersRequest,
+    params(
+        ("id", Path, description = "The ID of the group"),
+    ),
+    responses(
+        (status = 204, description = "The SBOMs were assigned to the group"),
+        (status = 400, description = "The request was not valid"),
+        (status = 401, description = "The user was not authenticated"),
+        (status = 403, description = "The user authenticated, but not authorized for this operation"),
+        (status = 404, description = "The group, or one or more SBOMs, were not found"),
+    )
+)]
+#[post("/v3/group/sbom/{id}/members")]
+/// Assign SBOMs to a group
+async fn add_members(
+    service: web::Data<SbomGroupService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    web::Json(request): web::Json<GroupMembersRequest>,
+    _: Require<UpdateSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    service.assign_sboms(&id, request.sbom_ids, &tx).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    tag = "sbomGroup",
+    operation_id = "removeSbomGroupMembers",
+    request_body = GroupMembersRequest,
+    params(
+        ("id", Path, description = "The ID of the group"),
+    ),
+    responses(
+        (status = 204, description = "The SBOMs were removed from the group"),
+        (status = 400, description = "The request was not valid"),
+        (status = 401, description = "The user was not authenticated"),
+        (status = 403, description = "The user authenticated, but not authorized for this operation"),
+        (status = 404, description = "The group, or one or more SBOMs, were not found"),
+    )
+)]
+#[delete("/v3/group/sbom/{id}/members")]
+/// Remove SBOMs from a group
+async fn remove_members(
+    service: web::Data<SbomGroupService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    web::Json(request): web::Json<GroupMembersRequest>,
+    _: Require<UpdateSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    service.unassign_sboms(&id, request.sbom_ids, &tx).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[utoipa::path(
     tag = "sbomGroup",
     operation_id = "readSbomGroup",
@@ -239,6 +412,39 @@ async fn read(
     })
 }
 
+#[utoipa::path(
+    tag = "sbomGroup",
+    operation_id = "readSbomGroupVulnerabilitySummary",
+    params(
+        ("id", Path, description = "The ID of the group"),
+        VulnerabilitySummaryOptions,
+    ),
+    responses(
+        (status = 200, description = "The vulnerabilities of the group were summarized", body = GroupVulnerabilitySummary),
+        (status = 400, description = "The request was not valid"),
+        (status = 401, description = "The user was not authenticated"),
+        (status = 403, description = "The user authenticated, but not authorized for this operation"),
+        (status = 404, description = "The group was not found"),
+    )
+)]
+#[get("/v3/group/sbom/{id}/vulnerabilities")]
+/// Summarize the vulnerabilities affecting the SBOMs of a group
+async fn vulnerability_summary(
+    service: web::Data<SbomGroupService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    web::Query(options): web::Query<VulnerabilitySummaryOptions>,
+    _: Require<ReadSbomGroup>,
+    _: Require<ReadSbom>,
+) -> actix_web::Result<impl Responder> {
+    let tx = db.begin().await?;
+    let result = service
+        .vulnerability_summary(&id, options.recursive, &tx)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
 #[utoipa::path(
     tag = "sbomGroup",
     operation_id = "readSbomGroupAssignments",
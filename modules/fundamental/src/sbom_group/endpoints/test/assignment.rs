@@ -6,12 +6,14 @@ use crate::{
     sbom_group::model::BulkAssignmentRequest,
     test::caller,
 };
-use actix_http::StatusCode;
+use actix_http::{Method, StatusCode};
 use actix_web::test::TestRequest;
 use rstest::rstest;
 use serde_json::json;
 use test_context::test_context;
+use trustify_module_ingestor::service::Format;
 use trustify_test_context::{TrustifyContext, call::CallService};
+use urlencoding::encode;
 
 #[test_context(TrustifyContext)]
 #[test_log::test(actix_web::test)]
@@ -679,3 +681,128 @@ async fn patch_sbom_group_assignments_bulk(ctx: &TrustifyContext) -> anyhow::Res
 
     Ok(())
 }
+
+// --- auto-assign tests ---
+
+async fn call_auto_assign(
+    app: &impl CallService,
+    method: Method,
+    group_id: &str,
+    label_key: &str,
+    label_value: &str,
+) -> anyhow::Result<(StatusCode, serde_json::Value)> {
+    let uri = format!("/api/v3/group/sbom/{group_id}/auto-assign");
+    // removing takes the label from the query, as a DELETE has no body
+    let request = match method {
+        Method::DELETE => TestRequest::delete().uri(&format!(
+            "{uri}?label_key={}&label_value={}",
+            encode(label_key),
+            encode(label_value)
+        )),
+        method => TestRequest::default()
+            .method(method)
+            .uri(&uri)
+            .set_json(json!({"label_key": label_key, "label_value": label_value})),
+    };
+    let response = app.call_service(request.to_request()).await;
+
+    let status = response.status();
+    let body = actix_web::body::to_bytes(response.into_body())
+        .await
+        .map_err(|err| anyhow::anyhow!("{err}"))?;
+    let body = match body.is_empty() {
+        true => serde_json::Value::Null,
+        false => serde_json::from_slice(&body)?,
+    };
+
+    Ok((status, body))
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn auto_assign_by_label(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let group: GroupResponse = Create::new("Auto Group").execute(&app).await?;
+    let other: GroupResponse = Create::new("Other Group").execute(&app).await?;
+
+    let sbom1 = ctx
+        .ingest_document_as(
+            "zookeeper-3.9.2-cyclonedx.json",
+            Format::CycloneDX,
+            ("product", "rhel"),
+        )
+        .await?;
+    let sbom2 = ctx
+        .ingest_document_as("spdx/simple.json", Format::SPDX, ("product", "rhel"))
+        .await?;
+    let sbom3 = ctx
+        .ingest_document_as("spdx/1178.json", Format::SPDX, ("product", "quarkus"))
+        .await?;
+
+    let sbom1_id = sbom1.id.to_string();
+    let sbom2_id = sbom2.id.to_string();
+    let sbom3_id = sbom3.id.to_string();
+
+    // keep an existing assignment, which must not be counted again
+    PatchAssignments::new(vec![sbom1_id.clone()])
+        .add_groups(vec![group.id.clone(), other.id.clone()])
+        .execute(&app)
+        .await?;
+
+    let (status, body) = call_auto_assign(&app, Method::POST, &group.id, "product", "rhel").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({"affected": 1}));
+
+    assert_assigned_groups(&app, &sbom1_id, &[&group.id, &other.id]).await?;
+    assert_assigned_groups(&app, &sbom2_id, &[&group.id]).await?;
+    assert_assigned_groups(&app, &sbom3_id, &[]).await?;
+
+    // running again is a no-op
+    let (status, body) = call_auto_assign(&app, Method::POST, &group.id, "product", "rhel").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({"affected": 0}));
+
+    // remove by label, leaving other groups alone
+    let (status, _) = call_auto_assign(&app, Method::DELETE, &group.id, "product", "rhel").await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    assert_assigned_groups(&app, &sbom1_id, &[&other.id]).await?;
+    assert_assigned_groups(&app, &sbom2_id, &[]).await?;
+    assert_assigned_groups(&app, &sbom3_id, &[]).await?;
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::post(Method::POST)]
+#[case::delete(Method::DELETE)]
+#[test_log::test(actix_web::test)]
+async fn auto_assign_by_label_errors(
+    ctx: &TrustifyContext,
+    #[case] method: Method,
+) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let group: GroupResponse = Create::new("Auto Group").execute(&app).await?;
+
+    let (status, _) = call_auto_assign(
+        &app,
+        method.clone(),
+        "00000000-0000-0000-0000-000000000099",
+        "product",
+        "rhel",
+    )
+    .await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) =
+        call_auto_assign(&app, method.clone(), "not-a-uuid", "product", "rhel").await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = call_auto_assign(&app, method, &group.id, "", "rhel").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
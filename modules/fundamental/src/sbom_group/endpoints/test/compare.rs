@@ -0,0 +1,158 @@
+use crate::{
+    common::test::{Create, GroupResponse, PatchAssignments},
+    test::caller,
+};
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use rstest::rstest;
+use serde_json::Value;
+use test_context::test_context;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+fn ids(list: &Value) -> Vec<String> {
+    list["items"]
+        .as_array()
+        .expect("must be an array")
+        .iter()
+        .map(|item| item["id"].as_str().expect("must be a string").to_string())
+        .collect()
+}
+
+fn urn(id: &str) -> String {
+    format!("urn:uuid:{id}")
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::direct(false)]
+#[case::recursive(true)]
+#[test_log::test(actix_web::test)]
+async fn compare_groups(ctx: &TrustifyContext, #[case] recursive: bool) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let left: GroupResponse = Create::new("Left").execute(&app).await?;
+    let child: GroupResponse = Create::new("Child")
+        .parent(Some(&left.id))
+        .execute(&app)
+        .await?;
+    let right: GroupResponse = Create::new("Right").execute(&app).await?;
+
+    let shared = ctx
+        .ingest_document("zookeeper-3.9.2-cyclonedx.json")
+        .await?
+        .id
+        .to_string();
+    let nested = ctx
+        .ingest_document("spdx/simple.json")
+        .await?
+        .id
+        .to_string();
+    let right_only = ctx.ingest_document("spdx/1178.json").await?.id.to_string();
+
+    PatchAssignments::new(vec![shared.clone()])
+        .add_groups(vec![left.id.clone(), right.id.clone()])
+        .execute(&app)
+        .await?;
+    PatchAssignments::new(vec![nested.clone()])
+        .add_groups(vec![child.id.clone()])
+        .execute(&app)
+        .await?;
+    PatchAssignments::new(vec![right_only.clone()])
+        .add_groups(vec![right.id.clone()])
+        .execute(&app)
+        .await?;
+
+    let uri = format!(
+        "/api/v3/group/sbom/compare?left={}&right={}&recursive={recursive}&total=true",
+        left.id, right.id
+    );
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+
+    let expected_left = match recursive {
+        true => vec![urn(&nested)],
+        false => vec![],
+    };
+
+    assert_eq!(ids(&response["only_in_left"]), expected_left);
+    assert_eq!(response["only_in_left"]["total"], expected_left.len());
+    assert_eq!(ids(&response["only_in_right"]), [urn(&right_only)]);
+    assert_eq!(response["only_in_right"]["total"], 1);
+    assert_eq!(ids(&response["in_both"]), [urn(&shared)]);
+    assert_eq!(response["in_both"]["total"], 1);
+    assert_eq!(response["in_both"]["items"][0]["number_of_packages"], 41);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn compare_groups_paginated(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let left: GroupResponse = Create::new("Left").execute(&app).await?;
+    let right: GroupResponse = Create::new("Right").execute(&app).await?;
+
+    let mut sboms = vec![];
+    for path in [
+        "zookeeper-3.9.2-cyclonedx.json",
+        "spdx/simple.json",
+        "spdx/1178.json",
+    ] {
+        sboms.push(ctx.ingest_document(path).await?.id.to_string());
+    }
+
+    PatchAssignments::new(sboms.clone())
+        .add_groups(vec![left.id.clone()])
+        .execute(&app)
+        .await?;
+    PatchAssignments::new(vec![sboms[0].clone()])
+        .add_groups(vec![right.id.clone()])
+        .execute(&app)
+        .await?;
+
+    let uri = format!(
+        "/api/v3/group/sbom/compare?left={}&right={}&offset=1&limit=1&total=true",
+        left.id, right.id
+    );
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+
+    // each list is paginated on its own
+    assert_eq!(ids(&response["only_in_left"]).len(), 1);
+    assert_eq!(response["only_in_left"]["total"], 2);
+    assert_eq!(ids(&response["in_both"]).len(), 0);
+    assert_eq!(response["in_both"]["total"], 1);
+    assert_eq!(ids(&response["only_in_right"]).len(), 0);
+    assert_eq!(response["only_in_right"]["total"], 0);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn compare_groups_not_found(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let group: GroupResponse = Create::new("Group").execute(&app).await?;
+
+    for (left, right) in [
+        (group.id.as_str(), "00000000-0000-0000-0000-000000000099"),
+        ("00000000-0000-0000-0000-000000000099", group.id.as_str()),
+        (group.id.as_str(), "not-a-uuid"),
+    ] {
+        let uri = format!("/api/v3/group/sbom/compare?left={left}&right={right}");
+        let response = app
+            .call_service(TestRequest::get().uri(&uri).to_request())
+            .await;
+        assert_eq!(
+            response.status(),
+            StatusCode::NOT_FOUND,
+            "{left} vs {right}"
+        );
+    }
+
+    Ok(())
+}
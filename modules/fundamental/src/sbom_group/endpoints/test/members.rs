@@ -0,0 +1,122 @@
+use crate::{
+    common::test::{Create, GroupResponse, read_assignments},
+    test::caller,
+};
+use actix_http::{Method, StatusCode};
+use actix_web::test::TestRequest;
+use rstest::rstest;
+use serde_json::json;
+use test_context::test_context;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+async fn call_members(
+    app: &impl CallService,
+    method: Method,
+    group_id: &str,
+    sbom_ids: &[&str],
+) -> StatusCode {
+    app.call_service(
+        TestRequest::default()
+            .method(method)
+            .uri(&format!("/api/v3/group/sbom/{group_id}/members"))
+            .set_json(json!({ "sbom_ids": sbom_ids }))
+            .to_request(),
+    )
+    .await
+    .status()
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn add_and_remove_members(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let group: GroupResponse = Create::new("Group").execute(&app).await?;
+    let other: GroupResponse = Create::new("Other").execute(&app).await?;
+
+    let mut sboms = vec![];
+    for path in ["zookeeper-3.9.2-cyclonedx.json", "spdx/simple.json"] {
+        sboms.push(ctx.ingest_document(path).await?.id.to_string());
+    }
+    let sboms: Vec<&str> = sboms.iter().map(String::as_str).collect();
+
+    assert_eq!(
+        call_members(&app, Method::POST, &other.id, &sboms[..1]).await,
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(
+        call_members(&app, Method::POST, &group.id, &sboms).await,
+        StatusCode::NO_CONTENT
+    );
+    // adding them again doesn't change anything
+    assert_eq!(
+        call_members(&app, Method::POST, &group.id, &sboms).await,
+        StatusCode::NO_CONTENT
+    );
+
+    let mut groups = read_assignments(&app, sboms[0]).await?.group_ids;
+    groups.sort();
+    let mut expected = vec![group.id.clone(), other.id.clone()];
+    expected.sort();
+    assert_eq!(groups, expected);
+    assert_eq!(
+        read_assignments(&app, sboms[1]).await?.group_ids,
+        [group.id.clone()]
+    );
+
+    assert_eq!(
+        call_members(&app, Method::DELETE, &group.id, &sboms).await,
+        StatusCode::NO_CONTENT
+    );
+
+    // other assignments are kept
+    assert_eq!(
+        read_assignments(&app, sboms[0]).await?.group_ids,
+        [other.id.clone()]
+    );
+    assert!(read_assignments(&app, sboms[1]).await?.group_ids.is_empty());
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::add(Method::POST)]
+#[case::remove(Method::DELETE)]
+#[test_log::test(actix_web::test)]
+async fn members_not_found(ctx: &TrustifyContext, #[case] method: Method) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let group: GroupResponse = Create::new("Group").execute(&app).await?;
+    let sbom = ctx
+        .ingest_document("zookeeper-3.9.2-cyclonedx.json")
+        .await?
+        .id
+        .to_string();
+
+    assert_eq!(
+        call_members(
+            &app,
+            method.clone(),
+            "00000000-0000-0000-0000-000000000000",
+            &[&sbom]
+        )
+        .await,
+        StatusCode::NOT_FOUND
+    );
+
+    // a single missing SBOM fails the whole request
+    assert_eq!(
+        call_members(
+            &app,
+            method,
+            &group.id,
+            &[&sbom, "00000000-0000-0000-0000-000000000000"]
+        )
+        .await,
+        StatusCode::NOT_FOUND
+    );
+    assert!(read_assignments(&app, &sbom).await?.group_ids.is_empty());
+
+    Ok(())
+}
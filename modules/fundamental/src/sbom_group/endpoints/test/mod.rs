@@ -1,8 +1,11 @@
 mod assignment;
+mod compare;
 mod create;
 mod delete;
 mod list;
+mod members;
 mod update;
+mod vulnerabilities;
 
 use crate::common::test::{GroupResponse, IfMatchType, add_if_match};
 use actix_http::body::to_bytes;
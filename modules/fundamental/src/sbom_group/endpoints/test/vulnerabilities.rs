@@ -0,0 +1,178 @@
+use crate::{
+    common::test::{Create, GroupResponse, PatchAssignments},
+    sbom::service::SbomService,
+    test::caller,
+};
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use rstest::rstest;
+use sea_orm::TransactionTrait;
+use serde_json::{Value, json};
+use test_context::test_context;
+use trustify_common::db::pagination_cache::PaginationCache;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::direct(
+    false,
+    json!({
+        "total_sboms": 1,
+        "total_affected_sboms": 0,
+        "critical": 0,
+        "high": 0,
+        "medium": 0,
+        "low": 0,
+        "unknown": 0,
+        "top_cves": [],
+    })
+)]
+#[case::recursive(
+    true,
+    json!({
+        "total_sboms": 2,
+        "total_affected_sboms": 1,
+        "critical": 0,
+        "high": 0,
+        "medium": 1,
+        "low": 0,
+        "unknown": 0,
+        "top_cves": ["CVE-2023-0044"],
+    })
+)]
+#[test_log::test(actix_web::test)]
+async fn vulnerability_summary(
+    ctx: &TrustifyContext,
+    #[case] recursive: bool,
+    #[case] expected: Value,
+) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let parent: GroupResponse = Create::new("Parent").execute(&app).await?;
+    let child: GroupResponse = Create::new("Child")
+        .parent(Some(&parent.id))
+        .execute(&app)
+        .await?;
+
+    let unaffected = ctx
+        .ingest_document("zookeeper-3.9.2-cyclonedx.json")
+        .await?
+        .id
+        .to_string();
+    let affected = ctx
+        .ingest_document("quarkus-bom-2.13.8.Final-redhat-00004.json")
+        .await?
+        .id
+        .to_string();
+    ctx.ingest_document("csaf/cve-2023-0044.json").await?;
+
+    PatchAssignments::new(vec![unaffected])
+        .add_groups(vec![parent.id.clone()])
+        .execute(&app)
+        .await?;
+    PatchAssignments::new(vec![affected])
+        .add_groups(vec![child.id.clone()])
+        .execute(&app)
+        .await?;
+
+    SbomService::new(PaginationCache::for_test())
+        .refresh_advisory_summary(&ctx.db)
+        .await?;
+
+    let uri = format!(
+        "/api/v3/group/sbom/{}/vulnerabilities?recursive={recursive}",
+        parent.id
+    );
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+
+    assert_eq!(response, expected);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn vulnerability_summary_default_recursive(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let parent: GroupResponse = Create::new("Parent").execute(&app).await?;
+    let child: GroupResponse = Create::new("Child")
+        .parent(Some(&parent.id))
+        .execute(&app)
+        .await?;
+
+    let sbom = ctx
+        .ingest_document("zookeeper-3.9.2-cyclonedx.json")
+        .await?
+        .id
+        .to_string();
+    PatchAssignments::new(vec![sbom])
+        .add_groups(vec![child.id.clone()])
+        .execute(&app)
+        .await?;
+
+    let uri = format!("/api/v3/group/sbom/{}/vulnerabilities", parent.id);
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+
+    assert_eq!(response["total_sboms"], 1);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn vulnerability_summary_deleted_sbom(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let group: GroupResponse = Create::new("Group").execute(&app).await?;
+
+    let sbom = ctx
+        .ingest_document("quarkus-bom-2.13.8.Final-redhat-00004.json")
+        .await?
+        .id;
+    ctx.ingest_document("csaf/cve-2023-0044.json").await?;
+
+    PatchAssignments::new(vec![sbom.to_string()])
+        .add_groups(vec![group.id.clone()])
+        .execute(&app)
+        .await?;
+
+    let service = SbomService::new(PaginationCache::for_test());
+    let tx = ctx.db.begin().await?;
+    service.soft_delete_sboms(vec![sbom.parse()?], &tx).await?;
+    tx.commit().await?;
+    service.refresh_advisory_summary(&ctx.db).await?;
+
+    let uri = format!("/api/v3/group/sbom/{}/vulnerabilities", group.id);
+    let response: Value = app
+        .call_and_read_body_json(TestRequest::get().uri(&uri).to_request())
+        .await;
+
+    assert_eq!(response["total_sboms"], 0);
+    assert_eq!(response["total_affected_sboms"], 0);
+    assert_eq!(response["top_cves"], json!([]));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn vulnerability_summary_unknown_group(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller(ctx).await?;
+
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri("/api/v3/group/sbom/00000000-0000-0000-0000-000000000000/vulnerabilities")
+                .to_request(),
+        )
+        .await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
+use crate::sbom::model::SbomHead;
 use serde::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
 use trustify_common::model::PaginatedResults;
 use trustify_entity::{labels::Labels, sbom_group};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema, PartialEq, Eq)]
 pub struct Group {
@@ -114,6 +115,13 @@ pub struct BulkAssignmentRequest {
     pub group_ids: Vec<String>,
 }
 
+/// Request to assign SBOMs to, or remove them from, a group.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema, PartialEq, Eq)]
+pub struct GroupMembersRequest {
+    /// The IDs of the SBOMs to add or remove.
+    pub sbom_ids: Vec<String>,
+}
+
 /// Request to partially update SBOM group assignments (add and/or remove).
 ///
 /// Applies cartesian product semantics: each SBOM in `sbom_ids` gets all `add` groups
@@ -129,3 +137,53 @@ pub struct PatchAssignmentRequest {
     #[serde(default)]
     pub remove: Vec<String>,
 }
+
+/// Request to (un)assign all SBOMs carrying a label to a group.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema, IntoParams, PartialEq, Eq)]
+pub struct AutoAssignRequest {
+    /// The key of the label to match.
+    pub label_key: String,
+    /// The value the label must have.
+    pub label_value: String,
+}
+
+/// The result of assigning SBOMs by label.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema, PartialEq, Eq)]
+pub struct AutoAssignResult {
+    /// The number of SBOMs which were added to the group.
+    pub affected: u64,
+}
+
+/// The result of comparing the SBOMs assigned to two groups.
+///
+/// Each list is ordered by SBOM ID and paginated on its own.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct GroupComparison {
+    /// SBOMs only assigned to the left group.
+    pub only_in_left: PaginatedResults<SbomHead>,
+    /// SBOMs only assigned to the right group.
+    pub only_in_right: PaginatedResults<SbomHead>,
+    /// SBOMs assigned to both groups.
+    pub in_both: PaginatedResults<SbomHead>,
+}
+
+/// The vulnerabilities affecting the SBOMs of a group.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ToSchema, PartialEq, Eq)]
+pub struct GroupVulnerabilitySummary {
+    /// The number of SBOMs of the group.
+    pub total_sboms: u64,
+    /// The number of SBOMs affected by at least one vulnerability.
+    pub total_affected_sboms: u64,
+    /// The number of vulnerabilities with a critical severity.
+    pub critical: u64,
+    /// The number of vulnerabilities with a high severity.
+    pub high: u64,
+    /// The number of vulnerabilities with a medium severity.
+    pub medium: u64,
+    /// The number of vulnerabilities with a low severity.
+    pub low: u64,
+    /// The number of vulnerabilities without a known severity.
+    pub unknown: u64,
+    /// The IDs of the vulnerabilities affecting the most SBOMs, most prevalent first.
+    pub top_cves: Vec<String>,
+}
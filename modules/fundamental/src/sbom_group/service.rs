@@ -1,6 +1,10 @@
 use crate::{
     Error,
-    sbom_group::model::{Group, GroupDetails, GroupListResult, GroupRequest},
+    sbom::model::SbomHead,
+    sbom_group::model::{
+        Group, GroupComparison, GroupDetails, GroupListResult, GroupRequest,
+        GroupVulnerabilitySummary,
+    },
 };
 use isx::IsDefault;
 use itertools::izip;
@@ -11,7 +15,7 @@ use sea_orm::{
 use sea_query::{ArrayType, Expr, OnConflict, SimpleExpr, Value};
 use std::{
     borrow::Cow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     iter::repeat,
 };
 use trustify_common::{
@@ -73,6 +77,33 @@ impl ParentsMode {
     }
 }
 
+/// Options for comparing two groups
+#[derive(IntoParams, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CompareOptions {
+    /// The ID of the left group
+    pub left: String,
+    /// The ID of the right group
+    pub right: String,
+    /// include SBOMs of all descendant groups
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+/// Options for summarizing the vulnerabilities of a group
+#[derive(IntoParams, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VulnerabilitySummaryOptions {
+    /// include SBOMs of all descendant groups, defaults to true
+    #[serde(default = "default_recursive")]
+    pub recursive: bool,
+}
+
+const fn default_recursive() -> bool {
+    true
+}
+
+/// The number of most prevalent vulnerabilities reported by the vulnerability summary
+const TOP_CVES: usize = 10;
+
 pub struct SbomGroupService {
     max_group_name_length: usize,
     cache: PaginationCache,
@@ -164,7 +195,11 @@ impl SbomGroupService {
         };
 
         Ok(GroupListResult {
-            result: PaginatedResults { items, total },
+            result: PaginatedResults {
+                items,
+                total,
+                next_cursor: None,
+            },
             referenced,
         })
     }
@@ -537,6 +572,195 @@ WHERE parent IS NULL
         }))
     }
 
+    /// Compare the SBOMs assigned to two groups.
+    ///
+    /// The pagination is applied to each of the resulting lists independently.
+    pub async fn compare(
+        &self,
+        left: &str,
+        right: &str,
+        recursive: bool,
+        paginated: impl Pagination,
+        db: &impl ConnectionTrait,
+    ) -> Result<GroupComparison, Error> {
+        let left = self.group_sboms(left, recursive, db).await?;
+        let right = self.group_sboms(right, recursive, db).await?;
+
+        let only_in_left: Vec<_> = left.difference(&right).copied().collect();
+        let only_in_right: Vec<_> = right.difference(&left).copied().collect();
+        let in_both: Vec<_> = left.intersection(&right).copied().collect();
+
+        Ok(GroupComparison {
+            only_in_left: Self::sbom_heads(paginated.paginate_array(&only_in_left), db).await?,
+            only_in_right: Self::sbom_heads(paginated.paginate_array(&only_in_right), db).await?,
+            in_both: Self::sbom_heads(paginated.paginate_array(&in_both), db).await?,
+        })
+    }
+
+    /// Summarize the vulnerabilities affecting the SBOMs assigned to a group, and optionally its
+    /// descendants.
+    ///
+    /// Vulnerabilities are counted once, by the highest severity any advisory scores them with.
+    /// The matches are read from the pre-computed advisory summary, so they only reflect documents
+    /// ingested before it was last refreshed.
+    pub async fn vulnerability_summary(
+        &self,
+        id: &str,
+        recursive: bool,
+        db: &impl ConnectionTrait,
+    ) -> Result<GroupVulnerabilitySummary, Error> {
+        let sboms: Vec<Uuid> = self
+            .group_sboms(id, recursive, db)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut result = GroupVulnerabilitySummary {
+            total_sboms: sboms.len() as u64,
+            ..Default::default()
+        };
+
+        if sboms.is_empty() {
+            return Ok(result);
+        }
+
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                r#"
+SELECT COUNT(DISTINCT sbom_id) AS count
+FROM mv_sbom_advisory_summary
+WHERE sbom_id = ANY($1::uuid[]) AND status = 'affected'
+"#,
+                [sboms.clone().into()],
+            ))
+            .await?;
+        if let Some(row) = row {
+            result.total_affected_sboms = row.try_get::<i64>("", "count")? as u64;
+        }
+
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                r#"
+SELECT
+    vulnerability_id,
+    COUNT(DISTINCT sbom_id) AS sboms,
+    MAX(CASE severity
+        WHEN 'critical' THEN 4
+        WHEN 'high' THEN 3
+        WHEN 'medium' THEN 2
+        WHEN 'low' THEN 1
+        ELSE 0
+    END) AS severity
+FROM mv_sbom_advisory_summary
+WHERE sbom_id = ANY($1::uuid[]) AND status = 'affected'
+GROUP BY vulnerability_id
+ORDER BY sboms DESC, vulnerability_id
+"#,
+                [sboms.into()],
+            ))
+            .await?;
+
+        for row in rows {
+            let vulnerability_id: String = row.try_get("", "vulnerability_id")?;
+            match row.try_get::<i32>("", "severity")? {
+                4 => result.critical += 1,
+                3 => result.high += 1,
+                2 => result.medium += 1,
+                1 => result.low += 1,
+                _ => result.unknown += 1,
+            }
+            if result.top_cves.len() < TOP_CVES {
+                result.top_cves.push(vulnerability_id);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Collect the IDs of all SBOMs assigned to a group, and optionally its descendants.
+    async fn group_sboms(
+        &self,
+        id: &str,
+        recursive: bool,
+        db: &impl ConnectionTrait,
+    ) -> Result<BTreeSet<Uuid>, Error> {
+        let Some(group) = self.read(id, db).await? else {
+            return Err(Error::NotFound(id.to_string()));
+        };
+        let group_uuid =
+            Uuid::parse_str(&group.value.id).map_err(|_| Error::NotFound(id.to_string()))?;
+
+        let sql = if recursive {
+            r#"
+WITH RECURSIVE tree AS (
+    SELECT id, ARRAY[id] AS path FROM sbom_group WHERE id = $1
+    UNION ALL
+    SELECT g.id, t.path || g.id
+    FROM sbom_group g
+    JOIN tree t ON g.parent = t.id
+    WHERE NOT g.id = ANY(t.path)
+)
+SELECT DISTINCT a.sbom_id
+FROM sbom_group_assignment a
+JOIN tree t ON a.group_id = t.id
+JOIN sbom s ON s.sbom_id = a.sbom_id
+WHERE s.deleted_at IS NULL
+"#
+        } else {
+            r#"
+SELECT a.sbom_id
+FROM sbom_group_assignment a
+JOIN sbom s ON s.sbom_id = a.sbom_id
+WHERE a.group_id = $1 AND s.deleted_at IS NULL
+"#
+        };
+
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                sql,
+                [group_uuid.into()],
+            ))
+            .await?;
+
+        rows.into_iter()
+            .map(|row| Ok(row.try_get::<Uuid>("", "sbom_id")?))
+            .collect()
+    }
+
+    /// Resolve a page of SBOM IDs into their heads, keeping the order.
+    async fn sbom_heads(
+        ids: PaginatedResults<Uuid>,
+        db: &impl ConnectionTrait,
+    ) -> Result<PaginatedResults<SbomHead>, Error> {
+        let PaginatedResults { items, total, .. } = ids;
+
+        let mut rows: HashMap<_, _> = sbom::Entity::find()
+            .find_also_linked(sbom::SbomNodeLink)
+            .filter(sbom::Column::SbomId.is_in(items.clone()))
+            .filter(sbom::Column::DeletedAt.is_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|(sbom, node)| Some((sbom.sbom_id, (sbom, node?))))
+            .collect();
+
+        let mut heads = Vec::with_capacity(items.len());
+        for id in items {
+            if let Some((sbom, node)) = rows.remove(&id) {
+                heads.push(SbomHead::from_entity(&sbom, &node, db).await?);
+            }
+        }
+
+        Ok(PaginatedResults {
+            items: heads,
+            total,
+            next_cursor: None,
+        })
+    }
+
     /// Ensure a group name is valid
     ///
     /// This does not check uniqueness in the context of the parent.
@@ -598,6 +822,7 @@ WHERE parent IS NULL
 
         let sbom = sbom::Entity::find()
             .filter(sbom::Column::SbomId.eq(sbom_uuid))
+            .filter(sbom::Column::DeletedAt.is_null())
             .one(db)
             .await?;
 
@@ -796,6 +1021,126 @@ WHERE parent IS NULL
         Ok(())
     }
 
+    /// Assign SBOMs to a group, keeping their other assignments.
+    pub async fn assign_sboms(
+        &self,
+        group_id: &str,
+        sbom_ids: Vec<String>,
+        db: &impl ConnectionTrait,
+    ) -> Result<(), Error> {
+        let group_id = self.require_group(group_id, db).await?;
+        self.patch_assignments(sbom_ids, vec![group_id], vec![], db)
+            .await
+    }
+
+    /// Remove SBOMs from a group, keeping their other assignments.
+    pub async fn unassign_sboms(
+        &self,
+        group_id: &str,
+        sbom_ids: Vec<String>,
+        db: &impl ConnectionTrait,
+    ) -> Result<(), Error> {
+        let group_id = self.require_group(group_id, db).await?;
+        self.patch_assignments(sbom_ids, vec![], vec![group_id], db)
+            .await
+    }
+
+    /// Get the ID of an existing group, or fail with [`Error::NotFound`].
+    async fn require_group(&self, id: &str, db: &impl ConnectionTrait) -> Result<String, Error> {
+        match self.read(id, db).await? {
+            Some(group) => Ok(group.value.id),
+            None => Err(Error::NotFound(id.to_string())),
+        }
+    }
+
+    /// Assign all SBOMs carrying the label `label_key=label_value` to a group.
+    ///
+    /// Already existing assignments are kept. Returns the number of newly assigned SBOMs.
+    pub async fn auto_assign_by_label(
+        &self,
+        group_id: &str,
+        label_key: &str,
+        label_value: &str,
+        db: &impl ConnectionTrait,
+    ) -> Result<u64, Error> {
+        const SQL: &str = r#"
+WITH added AS (
+    INSERT INTO sbom_group_assignment (group_id, sbom_id)
+    SELECT $1, sbom_id FROM sbom WHERE labels @> $2
+    ON CONFLICT DO NOTHING
+    RETURNING sbom_id
+)
+UPDATE sbom SET revision = gen_random_uuid()
+FROM added
+WHERE sbom.sbom_id = added.sbom_id
+"#;
+
+        self.exec_by_label(SQL, group_id, label_key, label_value, db)
+            .await
+    }
+
+    /// Remove all SBOMs carrying the label `label_key=label_value` from a group.
+    ///
+    /// Returns the number of removed assignments.
+    pub async fn auto_unassign_by_label(
+        &self,
+        group_id: &str,
+        label_key: &str,
+        label_value: &str,
+        db: &impl ConnectionTrait,
+    ) -> Result<u64, Error> {
+        const SQL: &str = r#"
+WITH removed AS (
+    DELETE FROM sbom_group_assignment a
+    USING sbom s
+    WHERE a.sbom_id = s.sbom_id AND a.group_id = $1 AND s.labels @> $2
+    RETURNING a.sbom_id
+)
+UPDATE sbom SET revision = gen_random_uuid()
+FROM removed
+WHERE sbom.sbom_id = removed.sbom_id
+"#;
+
+        self.exec_by_label(SQL, group_id, label_key, label_value, db)
+            .await
+    }
+
+    /// Run a statement taking the group ID and a label filter, returning the number of
+    /// SBOMs it touched.
+    async fn exec_by_label(
+        &self,
+        sql: &str,
+        group_id: &str,
+        label_key: &str,
+        label_value: &str,
+        db: &impl ConnectionTrait,
+    ) -> Result<u64, Error> {
+        if label_key.is_empty() {
+            return Err(Error::BadRequest(
+                "Label key must not be empty".into(),
+                None,
+            ));
+        }
+
+        let Some(group) = self.read(group_id, db).await? else {
+            return Err(Error::NotFound(group_id.to_string()));
+        };
+        let group_uuid =
+            Uuid::parse_str(&group.value.id).map_err(|_| Error::NotFound(group_id.to_string()))?;
+
+        let labels = serde_json::json!({ label_key: label_value });
+
+        let result = db
+            .execute(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                sql,
+                [group_uuid.into(), labels.into()],
+            ))
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn bump_sbom_revision(
         sbom_uuid: Uuid,
         revision: Option<&str>,
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod test;
+
+use crate::{
+    Error,
+    source_document::{model::SourceDocumentEntry, service::SourceDocumentService},
+};
+use actix_web::{HttpResponse, Responder, delete, get, web};
+use sea_orm::TransactionTrait;
+use trustify_auth::{DeleteSbom, ReadSystemInformation, authorizer::Require};
+use trustify_common::{
+    db::{self, pagination_cache::PaginationCache},
+    model::{Paginated, PaginatedResults},
+};
+use trustify_module_ingestor::service::IngestorService;
+use trustify_module_storage::service::{StorageBackend, StorageKey};
+
+pub fn configure(
+    config: &mut utoipa_actix_web::service_config::ServiceConfig,
+    db_rw: db::ReadWrite,
+    db_ro: db::ReadOnly,
+    cache: PaginationCache,
+) {
+    let service = SourceDocumentService::new(cache);
+
+    config
+        .app_data(web::Data::new(db_rw))
+        .app_data(web::Data::new(db_ro))
+        .app_data(web::Data::new(service))
+        .service(list)
+        .service(delete_orphans);
+}
+
+#[utoipa::path(
+    tag = "sourceDocument",
+    operation_id = "listSourceDocuments",
+    params(
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "All stored source documents", body = PaginatedResults<SourceDocumentEntry>),
+    ),
+)]
+#[get("/v3/admin/source-documents")]
+/// List all stored source documents, along with the SBOM or advisory ingested from them
+pub async fn list(
+    service: web::Data<SourceDocumentService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSystemInformation>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(service.list(paginated, &tx).await?))
+}
+
+#[utoipa::path(
+    tag = "sourceDocument",
+    operation_id = "deleteOrphanedSourceDocuments",
+    responses(
+        (status = 204, description = "The orphaned source documents were removed"),
+    ),
+)]
+#[delete("/v3/admin/source-documents/orphans")]
+/// Remove all source documents which are neither used by an SBOM nor an advisory
+pub async fn delete_orphans(
+    i: web::Data<IngestorService>,
+    service: web::Data<SourceDocumentService>,
+    db: web::Data<db::ReadWrite>,
+    _: Require<DeleteSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let digests = service.delete_orphans(&tx).await?;
+    tx.commit().await?;
+
+    if !digests.is_empty()
+        && let Err(err) = i
+            .storage()
+            .delete_many(
+                &digests
+                    .iter()
+                    .map(|digest| StorageKey::from_sha256(digest))
+                    .collect::<Vec<_>>(),
+            )
+            .await
+    {
+        log::error!("Failed to remove orphaned source documents from the storage: {err:#?}");
+    }
+
+    log::info!("Removed {} orphaned source documents", digests.len());
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,62 @@
+use crate::test::caller;
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use sea_orm::EntityTrait;
+use serde_json::Value;
+use test_context::test_context;
+use test_log::test;
+use trustify_entity::sbom;
+use trustify_module_storage::service::{StorageBackend, StorageKey};
+use trustify_test_context::{TrustifyContext, call::CallService};
+use uuid::Uuid;
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn list_and_delete_orphans(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+    let result = ctx.ingest_document("spdx/simple.json").await?;
+    let sbom_id = Uuid::parse_str(&result.id)?;
+    ctx.ingest_document("osv/GHSA-2ccf-ffrj-m4qw.json").await?;
+
+    let request = TestRequest::get()
+        .uri("/api/v3/admin/source-documents?total=true")
+        .to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    assert_eq!(response["total"], 2);
+
+    let sha256 = response["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|item| item["sbom_id"] == sbom_id.to_string())
+        .and_then(|item| item["sha256"].as_str())
+        .and_then(|sha256| sha256.strip_prefix("sha256:"))
+        .expect("must have the SBOM document")
+        .to_string();
+
+    // orphan the SBOM document, without removing it
+    sbom::Entity::delete_by_id(sbom_id).exec(&ctx.db).await?;
+
+    let request = TestRequest::delete()
+        .uri("/api/v3/admin/source-documents/orphans")
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = TestRequest::get()
+        .uri("/api/v3/admin/source-documents?total=true")
+        .to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+    assert_eq!(response["total"], 1);
+    assert!(response["items"][0]["advisory_id"].is_string());
+
+    // the content is gone from the storage too
+    assert!(
+        ctx.storage
+            .retrieve(StorageKey::from_sha256(&sha256))
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
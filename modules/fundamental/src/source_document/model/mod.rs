@@ -5,6 +5,7 @@ use trustify_common::id::{Id, IdError};
 use trustify_entity::source_document;
 use trustify_module_storage::service::StorageKey;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SourceDocument {
@@ -41,6 +42,21 @@ impl SourceDocument {
     }
 }
 
+/// A stored source document, along with the SBOM or advisory using it
+///
+/// Documents having neither an SBOM nor an advisory are orphaned.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SourceDocumentEntry {
+    /// The ID of the source document
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub document: SourceDocument,
+    /// The ID of the SBOM ingested from this document, if any
+    pub sbom_id: Option<Uuid>,
+    /// The ID of the advisory ingested from this document, if any
+    pub advisory_id: Option<Uuid>,
+}
+
 impl TryInto<StorageKey> for &SourceDocument {
     type Error = IdError;
 
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod test;
+
+use crate::{
+    Error,
+    source_document::model::{SourceDocument, SourceDocumentEntry},
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, RelationTrait,
+};
+use sea_query::{JoinType, Order};
+use tracing::instrument;
+use trustify_common::{
+    db::{
+        limiter::{LimitedResult, LimiterAsModelTrait},
+        pagination_cache::PaginationCache,
+    },
+    model::{PaginatedResults, Pagination},
+};
+use trustify_entity::{advisory, sbom, source_document};
+use uuid::Uuid;
+
+#[derive(FromQueryResult)]
+struct SourceDocumentRow {
+    id: Uuid,
+    sha256: String,
+    sha384: String,
+    sha512: String,
+    size: i64,
+    ingested: time::OffsetDateTime,
+    sbom_id: Option<Uuid>,
+    advisory_id: Option<Uuid>,
+}
+
+impl From<SourceDocumentRow> for SourceDocumentEntry {
+    fn from(row: SourceDocumentRow) -> Self {
+        Self {
+            id: row.id,
+            document: SourceDocument::from_entity(&source_document::Model {
+                id: row.id,
+                sha256: row.sha256,
+                sha384: row.sha384,
+                sha512: row.sha512,
+                size: row.size,
+                ingested: row.ingested,
+            }),
+            sbom_id: row.sbom_id,
+            advisory_id: row.advisory_id,
+        }
+    }
+}
+
+pub struct SourceDocumentService {
+    cache: PaginationCache,
+}
+
+impl SourceDocumentService {
+    pub fn new(cache: PaginationCache) -> Self {
+        Self { cache }
+    }
+
+    /// List all source documents, along with the SBOM or advisory using them
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn list<C: ConnectionTrait>(
+        &self,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<SourceDocumentEntry>, Error> {
+        let limiter = source_document::Entity::find()
+            .join_rev(JoinType::LeftJoin, sbom::Relation::SourceDocument.def())
+            .join_rev(JoinType::LeftJoin, advisory::Relation::SourceDocument.def())
+            .column_as(sbom::Column::SbomId, "sbom_id")
+            .column_as(advisory::Column::Id, "advisory_id")
+            .order_by(source_document::Column::Ingested, Order::Desc)
+            .order_by(source_document::Column::Id, Order::Asc)
+            .limiting_as::<SourceDocumentRow>(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items.into_iter().map(SourceDocumentEntry::from).collect(),
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Delete all source documents which are neither used by an SBOM nor an advisory
+    ///
+    /// Returns the SHA-256 digests of the deleted documents, which should be removed from the
+    /// storage too.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn delete_orphans<C: ConnectionTrait>(
+        &self,
+        connection: &C,
+    ) -> Result<Vec<String>, Error> {
+        let deleted = source_document::Entity::delete_many()
+            .filter(
+                source_document::Column::Id.not_in_subquery(
+                    sbom::Entity::find()
+                        .select_only()
+                        .column(sbom::Column::SourceDocumentId)
+                        .into_query(),
+                ),
+            )
+            .filter(
+                source_document::Column::Id.not_in_subquery(
+                    advisory::Entity::find()
+                        .select_only()
+                        .column(advisory::Column::SourceDocumentId)
+                        .into_query(),
+                ),
+            )
+            .exec_with_returning(connection)
+            .await?;
+
+        log::info!("Deleted {} orphaned source documents", deleted.len());
+
+        Ok(deleted.into_iter().map(|doc| doc.sha256).collect())
+    }
+}
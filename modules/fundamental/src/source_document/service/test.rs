@@ -0,0 +1,91 @@
+use crate::source_document::service::SourceDocumentService;
+use sea_orm::EntityTrait;
+use test_context::test_context;
+use test_log::test;
+use trustify_common::{db::pagination_cache::PaginationCache, model::Paginated};
+use trustify_entity::{sbom, source_document};
+use trustify_test_context::TrustifyContext;
+use uuid::Uuid;
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn list(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom = ctx.ingest_document("spdx/simple.json").await?;
+    let advisory = ctx.ingest_document("osv/GHSA-2ccf-ffrj-m4qw.json").await?;
+
+    let service = SourceDocumentService::new(PaginationCache::for_test());
+    let result = service.list(Paginated::default(), &ctx.db).await?;
+
+    assert_eq!(result.items.len(), 2);
+
+    let sbom_entry = result
+        .items
+        .iter()
+        .find(|entry| entry.sbom_id.is_some())
+        .expect("must have an SBOM document");
+    assert_eq!(
+        sbom_entry.sbom_id,
+        Some(Uuid::parse_str(&sbom.id)?),
+        "{sbom_entry:?}"
+    );
+    assert_eq!(sbom_entry.advisory_id, None);
+    assert!(sbom_entry.document.sha256.starts_with("sha256:"));
+
+    let advisory_entry = result
+        .items
+        .iter()
+        .find(|entry| entry.advisory_id.is_some())
+        .expect("must have an advisory document");
+    assert_eq!(
+        advisory_entry.advisory_id,
+        Some(Uuid::parse_str(&advisory.id)?)
+    );
+    assert_eq!(advisory_entry.sbom_id, None);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn delete_orphans(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let result = ctx.ingest_document("spdx/simple.json").await?;
+    let sbom_id = Uuid::parse_str(&result.id)?;
+    ctx.ingest_document("osv/GHSA-2ccf-ffrj-m4qw.json").await?;
+
+    let service = SourceDocumentService::new(PaginationCache::for_test());
+
+    // nothing to do
+    assert!(service.delete_orphans(&ctx.db).await?.is_empty());
+
+    // orphan the document of the SBOM
+    let sbom = sbom::Entity::find_by_id(sbom_id)
+        .one(&ctx.db)
+        .await?
+        .expect("must exist");
+    let document = source_document::Entity::find_by_id(sbom.source_document_id)
+        .one(&ctx.db)
+        .await?
+        .expect("must exist");
+    sbom::Entity::delete_by_id(sbom_id).exec(&ctx.db).await?;
+
+    assert_eq!(
+        service.delete_orphans(&ctx.db).await?,
+        vec![document.sha256.clone()]
+    );
+    assert!(
+        source_document::Entity::find_by_id(document.id)
+            .one(&ctx.db)
+            .await?
+            .is_none()
+    );
+    assert_eq!(
+        service
+            .list(Paginated::default(), &ctx.db)
+            .await?
+            .items
+            .len(),
+        1
+    );
+
+    Ok(())
+}
@@ -5,24 +5,39 @@ use crate::common::model::Severity;
 use crate::{
     Error,
     endpoints::Deprecation,
+    sbom::{endpoints::SbomAdvisoryParams, model::SbomSummary},
     vulnerability::{
         model::{
-            AnalysisRequest, AnalysisResponseV3, VulnerabilityDetails, VulnerabilitySummary,
-            v2::AnalysisResponse,
+            AggregatedTtrMetrics, AnalysisRequest, AnalysisResponseV3, AnnotatedNode,
+            CvssBreakdown, EcosystemTrendEntry, NodeReference, SuppressVulnerability,
+            TrendInterval, TtrMetrics, VulnerabilityDetails, VulnerabilityOverrideEntry,
+            VulnerabilitySummary, VulnerabilitySuppression, v2::AnalysisResponse,
         },
         service::VulnerabilityService,
     },
 };
-use actix_web::{HttpResponse, Responder, get, post, web};
-use time::OffsetDateTime;
-use trustify_auth::{ReadAdvisory, authorizer::Require};
+use actix_web::{HttpResponse, Responder, delete, get, post, web};
+use sea_orm::TransactionTrait;
+use time::{
+    Date, OffsetDateTime,
+    format_description::well_known::{Iso8601, Rfc3339},
+};
+use trustify_auth::{
+    ReadAdvisory, ReadSbom, UpdateAdvisory, authenticator::user::UserInformation,
+    authorizer::Require,
+};
 use trustify_common::{
     db::{self, pagination_cache::PaginationCache, query::Query},
     model::{Paginated, PaginatedResults},
 };
+use trustify_module_ingestor::{
+    model::IngestResult,
+    service::{IngestorService, advisory::user_override::VulnerabilityOverride},
+};
 use trustify_query::TrustifyQuery;
 use trustify_query_derive::Query;
 use utoipa::IntoParams;
+use uuid::Uuid;
 
 #[derive(Clone, Debug, PartialEq, Eq, Default, serde::Deserialize, IntoParams)]
 pub struct VulnerabilityGetParams {
@@ -31,19 +46,59 @@ pub struct VulnerabilityGetParams {
     pub scores: bool,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Default, serde::Deserialize, IntoParams)]
+pub struct AverageTtrParams {
+    /// Limit the metrics to packages of this type (e.g. `maven`).
+    #[serde(default)]
+    pub r#type: Option<String>,
+}
+
+const fn default_min_count() -> u64 {
+    5
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, IntoParams)]
+pub struct EcosystemTrendParams {
+    /// The length of the periods vulnerabilities are counted for.
+    #[serde(default)]
+    #[param(inline)]
+    pub interval: TrendInterval,
+    /// Start of the time range, inclusive. Either an RFC 3339 timestamp or a date (e.g. `2024-01-01`).
+    pub start: String,
+    /// End of the time range, exclusive. Either an RFC 3339 timestamp or a date.
+    #[serde(default)]
+    pub end: Option<String>,
+    /// Leave out package types with fewer vulnerabilities over the whole time range.
+    #[serde(default = "default_min_count")]
+    pub min_count: u64,
+}
+
 pub fn configure(
     config: &mut utoipa_actix_web::service_config::ServiceConfig,
-    db: db::ReadOnly,
+    db_rw: db::ReadWrite,
+    db_ro: db::ReadOnly,
     cache: PaginationCache,
 ) {
     let service = VulnerabilityService::new(cache);
     config
         .app_data(web::Data::new(service))
-        .app_data(web::Data::new(db))
+        .app_data(web::Data::new(db_rw))
+        .app_data(web::Data::new(db_ro))
         .service(all)
+        .service(list_overrides) // Must be before `get` to avoid {id} matching "overrides"
+        .service(list_suppressions) // Must be before `get` to avoid {id} matching "suppressions"
         .service(analyze) // Must be before `get` to avoid {id} matching "analyze"
         .service(analyze_v3)
-        .service(get);
+        .service(annotate)
+        .service(average_ttr) // Must be before `get` to avoid {id} matching "avg-ttr"
+        .service(ecosystem_trend) // Must be before `get` to avoid {id} matching "ecosystem-trend"
+        .service(get)
+        .service(cvss)
+        .service(sboms)
+        .service(ttr)
+        .service(create_override)
+        .service(create_suppression)
+        .service(delete_suppression);
 }
 
 #[allow(dead_code)]
@@ -122,6 +177,333 @@ pub async fn get(
     }
 }
 
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "getVulnerabilityCvss",
+    params(
+        ("id", Path, description = "ID of the vulnerability"),
+    ),
+    responses(
+        (status = 200, description = "CVSS scores of the vulnerability, broken down into their metrics", body = CvssBreakdown),
+        (status = 404, description = "The vulnerability could not be found"),
+    ),
+)]
+#[get("/v3/vulnerability/{id}/cvss")]
+/// Retrieve the individual CVSS metrics of a vulnerability
+pub async fn cvss(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    if let Some(breakdown) = state.cvss_breakdown(&id, &tx).await? {
+        Ok(HttpResponse::Ok().json(breakdown))
+    } else {
+        Ok(HttpResponse::NotFound().finish())
+    }
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "listVulnerabilitySboms",
+    params(
+        ("id", Path, description = "ID of the vulnerability"),
+        SbomAdvisoryParams,
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "SBOMs containing packages with the requested statuses", body = PaginatedResults<SbomSummary>),
+    ),
+)]
+#[get("/v3/vulnerability/{id}/sboms")]
+/// List the SBOMs affected by a vulnerability
+pub async fn sboms(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    web::Query(params): web::Query<SbomAdvisoryParams>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadSbom>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(
+        state
+            .affected_sboms(&id, params.statuses(), paginated, &tx)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "getTimeToRemediation",
+    params(
+        ("id", Path, description = "ID of the vulnerability"),
+    ),
+    responses(
+        (status = 200, description = "Time to remediation of the vulnerability, null if no package was fixed", body = Option<TtrMetrics>),
+    ),
+)]
+#[get("/v3/vulnerability/{id}/ttr")]
+/// Retrieve the time it took to fix packages affected by a vulnerability
+pub async fn ttr(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    id: web::Path<String>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(state.time_to_remediation(&id, &tx).await?))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "getAverageTimeToRemediation",
+    params(
+        AverageTtrParams,
+    ),
+    responses(
+        (status = 200, description = "Time to remediation over all vulnerabilities, null if no package was fixed", body = Option<AggregatedTtrMetrics>),
+    ),
+)]
+#[get("/v3/vulnerability/avg-ttr")]
+/// Retrieve the time it took to fix packages, aggregated over all vulnerabilities
+pub async fn average_ttr(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(AverageTtrParams { r#type }): web::Query<AverageTtrParams>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(
+        state
+            .average_time_to_remediation(r#type.as_deref(), &tx)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "getEcosystemTrend",
+    params(
+        EcosystemTrendParams,
+    ),
+    responses(
+        (status = 200, description = "Number of vulnerabilities published per period and package type", body = Vec<EcosystemTrendEntry>),
+        (status = 400, description = "The start or end of the time range is invalid"),
+    ),
+)]
+#[get("/v3/vulnerability/ecosystem-trend")]
+/// Retrieve the number of newly published vulnerabilities over time, per package type
+pub async fn ecosystem_trend(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(EcosystemTrendParams {
+        interval,
+        start,
+        end,
+        min_count,
+    }): web::Query<EcosystemTrendParams>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    let start = parse_timestamp(&start)?;
+    let end = end.as_deref().map(parse_timestamp).transpose()?;
+
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(
+        state
+            .ecosystem_trend(interval, start, end, min_count, &tx)
+            .await?,
+    ))
+}
+
+/// Parse an RFC 3339 timestamp, or a date, which is taken as midnight UTC
+fn parse_timestamp(value: &str) -> Result<OffsetDateTime, Error> {
+    if let Ok(timestamp) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Ok(timestamp);
+    }
+
+    Date::parse(value, &Iso8601::DATE)
+        .map(|date| date.midnight().assume_utc())
+        .map_err(|err| {
+            Error::BadRequest(
+                format!("Invalid timestamp: {value}").into(),
+                Some(err.to_string().into()),
+            )
+        })
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "createVulnerabilityOverride",
+    params(
+        ("id", Path, description = "ID of the vulnerability"),
+    ),
+    request_body = VulnerabilityOverride,
+    responses(
+        (status = 201, description = "The override was created", body = IngestResult),
+        (status = 400, description = "The override was invalid"),
+        (status = 404, description = "The vulnerability could not be found"),
+    ),
+)]
+#[post("/v3/vulnerability/{id}/override")]
+/// Override the status of a package for a vulnerability
+///
+/// The override takes precedence over the statuses reported by all other advisories for the
+/// vulnerability, when retrieving details of the package.
+pub async fn create_override(
+    state: web::Data<VulnerabilityService>,
+    ingestor: web::Data<IngestorService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    web::Json(r#override): web::Json<VulnerabilityOverride>,
+    _: Require<UpdateAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+
+    if state
+        .fetch_vulnerability(&id, Default::default(), false, &tx)
+        .await?
+        .is_none()
+    {
+        return Ok(HttpResponse::NotFound().finish());
+    }
+
+    let result = ingestor.ingest_override(&id, r#override, &tx).await?;
+    log::info!("Created override of {id}: {}", result.id);
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "listVulnerabilityOverrides",
+    params(
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "User provided overrides of package statuses", body = PaginatedResults<VulnerabilityOverrideEntry>),
+    ),
+)]
+#[get("/v3/vulnerability/overrides")]
+/// List the user provided overrides of package statuses
+pub async fn list_overrides(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(state.fetch_overrides(paginated, &tx).await?))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "createVulnerabilitySuppression",
+    params(
+        ("id", Path, description = "ID of the vulnerability"),
+    ),
+    request_body = SuppressVulnerability,
+    responses(
+        (status = 201, description = "The suppression was created", body = VulnerabilitySuppression),
+        (status = 400, description = "The suppression was invalid"),
+        (status = 404, description = "The vulnerability could not be found"),
+    ),
+)]
+#[post("/v3/vulnerability/{id}/suppress")]
+/// Suppress a vulnerability for a package
+///
+/// Marks the vulnerability as suppressed for the package when analyzing it, until the
+/// suppression expires or gets revoked.
+pub async fn create_suppression(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<String>,
+    web::Json(request): web::Json<SuppressVulnerability>,
+    user: UserInformation,
+    _: Require<UpdateAdvisory>,
+) -> Result<impl Responder, Error> {
+    let created_by = user.id().unwrap_or("anonymous");
+
+    let tx = db.begin().await?;
+    let Some(result) = state
+        .create_suppression(&id, request, created_by, &tx)
+        .await?
+    else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+    tx.commit().await?;
+
+    log::info!(
+        "{created_by} suppressed {id} for {}: {}",
+        result.purl,
+        result.id
+    );
+
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "listVulnerabilitySuppressions",
+    params(
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Suppressions which didn't expire yet", body = PaginatedResults<VulnerabilitySuppression>),
+    ),
+)]
+#[get("/v3/vulnerability/suppressions")]
+/// List the active suppressions of vulnerabilities
+pub async fn list_suppressions(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(state.fetch_suppressions(paginated, &tx).await?))
+}
+
+#[utoipa::path(
+    tag = "vulnerability",
+    operation_id = "deleteVulnerabilitySuppression",
+    params(
+        ("id", Path, description = "ID of the suppression"),
+    ),
+    responses(
+        (status = 204, description = "The suppression was revoked, or did not exist"),
+    ),
+)]
+#[delete("/v3/vulnerability/suppression/{id}")]
+/// Revoke a suppression of a vulnerability
+pub async fn delete_suppression(
+    state: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<Uuid>,
+    user: UserInformation,
+    _: Require<UpdateAdvisory>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = state.delete_suppression(*id, &tx).await?;
+    tx.commit().await?;
+
+    if let Some(result) = result {
+        log::info!(
+            "{} revoked suppression {} of {} for {}, created by {}",
+            user.id().unwrap_or("anonymous"),
+            result.id,
+            result.vulnerability_id,
+            result.purl,
+            result.created_by
+        );
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[utoipa::path(
   operation_id = "v2/analyze",
   tag = "vulnerability",
@@ -165,3 +547,42 @@ pub async fn analyze_v3(
 
     Ok(HttpResponse::Ok().json(details))
 }
+
+/// The maximum number of nodes which can be annotated with a single request
+const MAX_ANNOTATE_NODES: usize = 200;
+
+#[utoipa::path(
+    operation_id = "annotateNodes",
+    tag = "analysis",
+    request_body = Vec<NodeReference>,
+    responses(
+        (status = 200, description = "The nodes, annotated with the vulnerabilities affecting them", body = Vec<AnnotatedNode>),
+        (status = 400, description = "More than 200 nodes were requested"),
+    ),
+)]
+#[post("/v3/analysis/annotate")]
+/// Annotate SBOM nodes with the vulnerabilities affecting their PURLs
+pub async fn annotate(
+    service: web::Data<VulnerabilityService>,
+    db: web::Data<db::ReadOnly>,
+    web::Json(nodes): web::Json<Vec<NodeReference>>,
+    _: Require<ReadSbom>,
+    _: Require<ReadAdvisory>,
+) -> Result<impl Responder, Error> {
+    if nodes.len() > MAX_ANNOTATE_NODES {
+        return Err(Error::BadRequest(
+            format!("At most {MAX_ANNOTATE_NODES} nodes can be annotated at once").into(),
+            None,
+        ));
+    }
+
+    let nodes: Vec<_> = nodes
+        .into_iter()
+        .map(|NodeReference { sbom_id, node_id }| (sbom_id, node_id))
+        .collect();
+
+    let tx = db.begin().await?;
+    let annotated = service.annotate_nodes(&nodes, &tx).await?;
+
+    Ok(HttpResponse::Ok().json(annotated))
+}
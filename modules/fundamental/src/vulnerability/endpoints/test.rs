@@ -1,18 +1,30 @@
 use crate::test::caller;
 use actix_web::test::TestRequest;
 use rstest::rstest;
+use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
 use serde_json::{Value, json};
+use std::str::FromStr;
 use test_context::test_context;
-use time::{OffsetDateTime, macros::datetime};
-use trustify_common::hashing::Digests;
-use trustify_entity::advisory_vulnerability_score::{ScoreType, Severity};
-use trustify_module_ingestor::graph::{
-    Outcome,
-    advisory::{AdvisoryContext, AdvisoryInformation},
-    cvss::{ScoreCreator, ScoreInformation},
-    vulnerability::{BaseScore as VulnBaseScore, VulnerabilityInformation},
+use time::{
+    OffsetDateTime,
+    macros::{date, datetime},
+};
+use trustify_common::{hashing::Digests, purl::Purl};
+use trustify_entity::{
+    advisory_vulnerability_score::{ScoreType, Severity},
+    epss_score, sbom_node, vulnerability_suppression,
+};
+use trustify_module_ingestor::{
+    graph::{
+        Outcome,
+        advisory::{AdvisoryContext, AdvisoryInformation},
+        cvss::{ScoreCreator, ScoreInformation},
+        vulnerability::{BaseScore as VulnBaseScore, VulnerabilityInformation},
+    },
+    service::Format,
 };
 use trustify_test_context::{TrustifyContext, call::CallService};
+use uuid::Uuid;
 
 /// Perform a GET request and return the parsed JSON response.
 async fn get_vulnerability(ctx: &TrustifyContext, uri: &str) -> Result<Value, anyhow::Error> {
@@ -371,3 +383,563 @@ async fn base_score_after_ingestion(
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn vulnerability_epss_score(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    // given: a vulnerability, with an EPSS score
+    ctx.ingest_document("cve/CVE-2024-26308.json").await?;
+
+    let vuln = get_vulnerability(ctx, "/api/v3/vulnerability/CVE-2024-26308").await?;
+    assert!(vuln.get("epss_score").is_none());
+    assert!(vuln.get("epss_percentile").is_none());
+
+    epss_score::Entity::insert(epss_score::ActiveModel {
+        vulnerability_id: Set("CVE-2024-26308".into()),
+        score: Set(0.00043),
+        percentile: Set(0.11801),
+        scored_date: Set(date!(2025 - 06 - 01)),
+    })
+    .exec_without_returning(&ctx.db)
+    .await?;
+
+    // when: the vulnerability is fetched via the API
+    let vuln = get_vulnerability(ctx, "/api/v3/vulnerability/CVE-2024-26308").await?;
+
+    // then: the EPSS score is included
+    assert_eq!(vuln["epss_score"], json!(0.00043));
+    assert_eq!(vuln["epss_percentile"], json!(0.11801));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn time_to_remediation(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_documents(["osv/RUSTSEC-2021-0079.json", "cve/CVE-2021-32714.json"])
+        .await?;
+
+    let ttr = get_vulnerability(ctx, "/api/v3/vulnerability/CVE-2021-32714/ttr").await?;
+    assert_eq!(ttr["cve_id"], "CVE-2021-32714");
+    assert_eq!(ttr["sample_size"], 1);
+
+    let ttr = get_vulnerability(ctx, "/api/v3/vulnerability/avg-ttr?type=cargo").await?;
+    assert_eq!(ttr["type"], "cargo");
+    assert_eq!(ttr["sample_size"], 1);
+
+    // no fixed packages, no metrics
+    let ttr = get_vulnerability(ctx, "/api/v3/vulnerability/avg-ttr?type=maven").await?;
+    assert_eq!(ttr, json!(null));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::monthly("start=2021-01-01&min_count=1", json!([{ "period": "2021-07", "ecosystem": "cargo", "new_vulns": 1 }]))]
+#[case::yearly("interval=year&start=2021-01-01T00:00:00Z&min_count=1", json!([{ "period": "2021", "ecosystem": "cargo", "new_vulns": 1 }]))]
+#[case::weekly("interval=week&start=2021-01-01&min_count=1", json!([{ "period": "2021-W27", "ecosystem": "cargo", "new_vulns": 1 }]))]
+#[case::below_min_count("start=2021-01-01", json!([]))]
+#[case::after_start("start=2021-08-01&min_count=1", json!([]))]
+#[case::before_end("start=2021-01-01&end=2021-07-01&min_count=1", json!([]))]
+#[test_log::test(actix_web::test)]
+async fn ecosystem_trend(
+    ctx: &TrustifyContext,
+    #[case] query: &str,
+    #[case] expected: Value,
+) -> Result<(), anyhow::Error> {
+    ctx.ingest_documents(["osv/RUSTSEC-2021-0079.json", "cve/CVE-2021-32714.json"])
+        .await?;
+
+    let trend = get_vulnerability(
+        ctx,
+        &format!("/api/v3/vulnerability/ecosystem-trend?{query}"),
+    )
+    .await?;
+    assert_eq!(trend, expected);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn ecosystem_trend_invalid_start(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let app = caller(ctx).await?;
+
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri("/api/v3/vulnerability/ecosystem-trend?start=last-year")
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn cvss_breakdown(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_documents(["cve/CVE-2021-32714.json", "osv/RUSTSEC-2021-0079.json"])
+        .await?;
+
+    let cvss = get_vulnerability(ctx, "/api/v3/vulnerability/CVE-2021-32714/cvss").await?;
+
+    // the CVE record is the authoritative source, the OSV record scores higher
+    assert_eq!(
+        cvss,
+        json!({
+            "v3_1": {
+                "vector": "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H",
+                "score": 5.9,
+                "severity": "medium",
+                "metrics": {
+                    "AV": "Network",
+                    "AC": "High",
+                    "PR": "None",
+                    "UI": "None",
+                    "S": "Unchanged",
+                    "C": "None",
+                    "I": "None",
+                    "A": "High",
+                },
+            },
+            "v3_0": null,
+            "v2": null,
+        })
+    );
+
+    let app = caller(ctx).await?;
+    let response = app
+        .call_service(
+            TestRequest::get()
+                .uri("/api/v3/vulnerability/CVE-0000-0000/cvss")
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+/// Collect the statuses of a package for a vulnerability, along with the labels of their
+/// advisories.
+fn package_statuses(details: &Value, vulnerability_id: &str) -> Vec<(String, Value)> {
+    details["advisories"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|advisory| {
+            advisory["status"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|status| status["vulnerability"]["identifier"] == vulnerability_id)
+                .map(|status| {
+                    (
+                        status["status"].as_str().unwrap_or_default().to_string(),
+                        advisory["labels"].clone(),
+                    )
+                })
+        })
+        .collect()
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn create_override(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_document("osv/RUSTSEC-2021-0079.json").await?;
+    let hyper = ctx
+        .graph
+        .ingest_qualified_package(&Purl::from_str("pkg:cargo/hyper@0.14.9")?, &ctx.db)
+        .await?;
+    let uri = format!("/api/v3/purl/{}", hyper.qualified_package.id);
+
+    let details = get_vulnerability(ctx, &uri).await?;
+    let statuses = package_statuses(&details, "CVE-2021-32714");
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].0, "affected");
+
+    let app = caller(ctx).await?;
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/override")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper@0.14.9",
+                    "status": "not_affected",
+                    "justification": "VulnerableCodeNotInExecutePath",
+                    "impact_statement": "Only HTTP/2 is used",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::CREATED);
+
+    // the override takes precedence over the status of the OSV advisory
+    let details = get_vulnerability(ctx, &uri).await?;
+    let statuses = package_statuses(&details, "CVE-2021-32714");
+    assert_eq!(statuses.len(), 1, "{statuses:?}");
+    assert_eq!(statuses[0].0, "not_affected");
+    assert_eq!(statuses[0].1["origin"], "user-override");
+
+    let overrides = get_vulnerability(ctx, "/api/v3/vulnerability/overrides?total=true").await?;
+    assert_eq!(overrides["total"], 1);
+    let entry = &overrides["items"][0];
+    assert_eq!(entry["vulnerability_id"], "CVE-2021-32714");
+    assert_eq!(entry["purl"], "pkg:cargo/hyper@0.14.9");
+    assert_eq!(entry["status"], "not_affected");
+    assert_eq!(entry["justification"], "VulnerableCodeNotInExecutePath");
+    assert_eq!(entry["impact_statement"], "Only HTTP/2 is used");
+
+    // a newer override of the same package replaces the previous one
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/override")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper@0.14.9",
+                    "status": "affected",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::CREATED);
+
+    let overrides = get_vulnerability(ctx, "/api/v3/vulnerability/overrides?total=true").await?;
+    assert_eq!(overrides["total"], 1);
+    assert_eq!(overrides["items"][0]["status"], "affected");
+    assert_eq!(overrides["items"][0]["justification"], Value::Null);
+
+    let details = get_vulnerability(ctx, &uri).await?;
+    let statuses = package_statuses(&details, "CVE-2021-32714");
+    assert_eq!(statuses.len(), 1, "{statuses:?}");
+    assert_eq!(statuses[0].0, "affected");
+    assert_eq!(statuses[0].1["origin"], "user-override");
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn create_override_invalid(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_document("osv/RUSTSEC-2021-0079.json").await?;
+    let app = caller(ctx).await?;
+
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-0000-0000/override")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper@0.14.9",
+                    "status": "not_affected",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/override")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper@0.14.9",
+                    "status": "fine",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/override")
+                .set_json(json!({
+                    "purl": "hyper",
+                    "status": "not_affected",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// Check if the vulnerability is suppressed for the package, when analyzing it
+async fn is_suppressed(
+    app: &impl CallService,
+    purl: &str,
+    vulnerability: &str,
+) -> Result<bool, anyhow::Error> {
+    let response: Value = app
+        .call_and_read_body_json(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/analyze")
+                .set_json(json!({ "purls": [purl] }))
+                .to_request(),
+        )
+        .await;
+
+    let details = response[purl]["details"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|details| details["identifier"] == vulnerability)
+        .ok_or_else(|| anyhow::anyhow!("{vulnerability} not found: {response:#?}"))?;
+
+    Ok(details["suppressed"] == true)
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn suppress_vulnerability(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_document("osv/RUSTSEC-2021-0079.json").await?;
+    let app = caller(ctx).await?;
+
+    assert!(!is_suppressed(&app, "pkg:cargo/hyper@0.14.9", "CVE-2021-32714").await?);
+
+    // without a version, the suppression applies to all versions of the package
+    let suppression: Value = app
+        .call_and_read_body_json(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/suppress")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper",
+                    "justification": "vulnerable_code_not_present",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(suppression["vulnerability_id"], "CVE-2021-32714");
+    assert_eq!(suppression["purl"], "pkg:cargo/hyper");
+    assert_eq!(suppression["expires_at"], Value::Null);
+
+    assert!(is_suppressed(&app, "pkg:cargo/hyper@0.14.9", "CVE-2021-32714").await?);
+
+    // expired suppressions are neither listed, nor applied
+    vulnerability_suppression::Entity::insert(vulnerability_suppression::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        vulnerability_id: Set("CVE-2021-32714".into()),
+        purl: Set("pkg:cargo/hyper@0.14.9".into()),
+        justification: Set("inline_mitigations_already_exist".into()),
+        expires_at: Set(Some(datetime!(2020-01-01 0:00 UTC))),
+        created_by: Set("someone".into()),
+        created_at: Set(datetime!(2019-01-01 0:00 UTC)),
+    })
+    .exec(&ctx.db)
+    .await?;
+
+    let suppressions =
+        get_vulnerability(ctx, "/api/v3/vulnerability/suppressions?total=true").await?;
+    assert_eq!(suppressions["total"], 1);
+    assert_eq!(suppressions["items"][0]["id"], suppression["id"]);
+
+    // revoke the suppression
+    let uri = format!(
+        "/api/v3/vulnerability/suppression/{}",
+        suppression["id"].as_str().unwrap_or_default()
+    );
+    let response = app
+        .call_service(TestRequest::delete().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::NO_CONTENT);
+    // revoking again is fine
+    let response = app
+        .call_service(TestRequest::delete().uri(&uri).to_request())
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::NO_CONTENT);
+
+    assert!(!is_suppressed(&app, "pkg:cargo/hyper@0.14.9", "CVE-2021-32714").await?);
+
+    let suppressions =
+        get_vulnerability(ctx, "/api/v3/vulnerability/suppressions?total=true").await?;
+    assert_eq!(suppressions["total"], 0);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn suppress_vulnerability_invalid(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_document("osv/RUSTSEC-2021-0079.json").await?;
+    let app = caller(ctx).await?;
+
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-0000-0000/suppress")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper",
+                    "justification": "vulnerable_code_not_present",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/suppress")
+                .set_json(json!({
+                    "purl": "hyper",
+                    "justification": "vulnerable_code_not_present",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/vulnerability/CVE-2021-32714/suppress")
+                .set_json(json!({
+                    "purl": "pkg:cargo/hyper",
+                    "justification": "vulnerable_code_not_present",
+                    "expires_at": "2020-01-01T00:00:00Z",
+                }))
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test_log::test(actix_web::test)]
+async fn annotate_nodes(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let sbom = ctx.ingest_document("cyclonedx/ghsa_test.json").await?;
+    ctx.ingest_documents([
+        "osv/GHSA-45c4-8wx5-qw6w.json",
+        "osv/GHSA-c25x-cm9x-qqgx.json",
+    ])
+    .await?;
+
+    let sbom_id = Uuid::parse_str(&sbom.id)?;
+    let node_id = |name: &'static str| async move {
+        sbom_node::Entity::find()
+            .filter(sbom_node::Column::SbomId.eq(sbom_id))
+            .filter(sbom_node::Column::Name.eq(name))
+            .one(&ctx.db)
+            .await
+            .map(|node| node.expect("node must exist").node_id)
+    };
+    let aiohttp = node_id("aiohttp").await?;
+    let deno = node_id("Deno").await?;
+
+    let app = caller(ctx).await?;
+    let response: Value = app
+        .call_and_read_body_json(
+            TestRequest::post()
+                .uri("/api/v3/analysis/annotate")
+                .set_json(json!([
+                    { "sbom_id": sbom_id, "node_id": aiohttp },
+                    { "sbom_id": sbom_id, "node_id": deno },
+                    { "sbom_id": sbom_id, "node_id": "unknown" },
+                ]))
+                .to_request(),
+        )
+        .await;
+
+    assert_eq!(
+        response,
+        json!([
+            {
+                "sbom_id": sbom_id,
+                "node_id": aiohttp,
+                "purls": ["pkg:pypi/aiohttp@1.0.5"],
+                "vulnerability_summary": { "medium": 1 },
+            },
+            {
+                "sbom_id": sbom_id,
+                "node_id": deno,
+                "purls": ["pkg:cargo/Deno@1.32.0"],
+                "vulnerability_summary": { "critical": 1 },
+            },
+            {
+                "sbom_id": sbom_id,
+                "node_id": "unknown",
+                "purls": [],
+                "vulnerability_summary": {},
+            },
+        ])
+    );
+
+    // too many nodes
+    let nodes = vec![json!({ "sbom_id": sbom_id, "node_id": aiohttp }); 201];
+    let response = app
+        .call_service(
+            TestRequest::post()
+                .uri("/api/v3/analysis/annotate")
+                .set_json(nodes)
+                .to_request(),
+        )
+        .await;
+    assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::default("", &["CVE-2023-28445"], &["affected"])]
+#[case::not_affected("", &["CVE-2023-37276"], &[])]
+#[case::fixed("?statuses=fixed", &["CVE-2023-37276"], &["affected"])]
+#[case::all("?statuses=", &["CVE-2023-28445", "CVE-2023-37276"], &["affected"])]
+#[case::other("?statuses=not_affected", &["CVE-2023-28445", "CVE-2023-37276"], &[])]
+#[test_log::test(actix_web::test)]
+async fn affected_sboms(
+    ctx: &TrustifyContext,
+    #[case] params: &str,
+    #[case] vulnerabilities: &[&str],
+    #[case] expected: &[&str],
+) -> Result<(), anyhow::Error> {
+    // aiohttp is the fixed version of CVE-2023-37276, Deno is affected by CVE-2023-28445
+    let sbom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "serialNumber": "urn:uuid:2b7c9e4f-6a1d-4c3b-8e5f-7d9a0b1c2e3f",
+        "metadata": {
+            "component": { "type": "application", "name": "affected", "version": "0.1.0" }
+        },
+        "components": [
+            { "type": "library", "name": "aiohttp", "version": "3.8.5", "purl": "pkg:pypi/aiohttp@3.8.5" },
+            { "type": "library", "name": "Deno", "version": "1.32.0", "purl": "pkg:cargo/Deno@1.32.0" }
+        ]
+    });
+
+    ctx.ingest_bytes_as(
+        &serde_json::to_vec(&sbom)?,
+        Format::CycloneDX,
+        ("source", "TrustifyContext"),
+    )
+    .await?;
+    ctx.ingest_documents([
+        "osv/GHSA-45c4-8wx5-qw6w.json",
+        "osv/GHSA-c25x-cm9x-qqgx.json",
+    ])
+    .await?;
+
+    for vulnerability in vulnerabilities {
+        let uri = format!("/api/v3/vulnerability/{vulnerability}/sboms{params}");
+        let v = get_vulnerability(ctx, &uri).await?;
+        log::debug!("{v:#?}");
+
+        let names = v["items"]
+            .as_array()
+            .expect("must be an array")
+            .iter()
+            .map(|sbom| sbom["name"].as_str().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected, "{vulnerability}");
+    }
+
+    Ok(())
+}
@@ -1,10 +1,15 @@
 use crate::{
     purl::model::{details::purl::PurlStatus, summary::remediation::RemediationSummary},
+    sbom::model::AffectedSeverity,
     vulnerability::model::VulnerabilityHead,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, ops::Deref};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct AnalysisRequest {
@@ -30,6 +35,9 @@ pub struct AnalysisDetailsV3 {
     pub head: VulnerabilityHead,
     /// List of purl statuses & remediations
     pub purl_statuses: Vec<AnalysisPurlStatus>,
+    /// If the vulnerability is suppressed for the package, by an active suppression
+    #[serde(default)]
+    pub suppressed: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
@@ -42,3 +50,21 @@ impl Deref for AnalysisResponseV3 {
         &self.0
     }
 }
+
+/// A node of an SBOM, to annotate with the vulnerabilities affecting it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct NodeReference {
+    pub sbom_id: Uuid,
+    pub node_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+/// A node of an SBOM, annotated with the vulnerabilities affecting it
+pub struct AnnotatedNode {
+    pub sbom_id: Uuid,
+    pub node_id: String,
+    /// The PURLs of the node
+    pub purls: Vec<String>,
+    /// The number of vulnerabilities affecting the node, by their highest severity
+    pub vulnerability_summary: HashMap<AffectedSeverity, u64>,
+}
@@ -0,0 +1,263 @@
+use crate::common::model::{Score, Severity};
+use cvss::{v2_0::CvssV2, v3::CvssV3};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr};
+use trustify_entity::advisory_vulnerability_score::{self, ScoreType};
+use utoipa::ToSchema;
+
+/// The CVSS scores of a vulnerability, broken down into their individual metrics.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+#[schema(example = json!({
+    "v3_1": {
+        "vector": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+        "score": 9.8,
+        "severity": "critical",
+        "metrics": {
+            "AV": "Network",
+            "AC": "Low",
+            "PR": "None",
+            "UI": "None",
+            "S": "Unchanged",
+            "C": "High",
+            "I": "High",
+            "A": "High"
+        }
+    },
+    "v3_0": null,
+    "v2": null
+}))]
+pub struct CvssBreakdown {
+    /// The CVSS v3.1 score, if any
+    pub v3_1: Option<CvssMetrics>,
+    /// The CVSS v3.0 score, if any
+    pub v3_0: Option<CvssMetrics>,
+    /// The CVSS v2.0 score, if any
+    pub v2: Option<CvssMetrics>,
+}
+
+/// A single CVSS score, with the values of its base metrics.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct CvssMetrics {
+    /// The raw CVSS vector string
+    pub vector: String,
+    /// The numeric score, rounded to one decimal place
+    pub score: f64,
+    /// The severity band derived from the score value
+    pub severity: Severity,
+    /// The named values of the base metrics, keyed by their abbreviation (e.g. `AV`)
+    pub metrics: BTreeMap<String, String>,
+}
+
+impl CvssMetrics {
+    /// Break down a stored score.
+    ///
+    /// Returns `None` if the type of the score isn't supported, or its vector cannot be parsed.
+    pub fn from_score(score: &advisory_vulnerability_score::Model) -> Option<Self> {
+        let metrics = match score.r#type {
+            ScoreType::V2_0 => v2_metrics(&CvssV2::from_str(&score.vector).ok()?),
+            ScoreType::V3_0 | ScoreType::V3_1 => v3_metrics(&CvssV3::from_str(&score.vector).ok()?),
+            ScoreType::V4_0 => return None,
+        };
+
+        let Score {
+            value, severity, ..
+        } = Score::from(score.clone());
+
+        Some(Self {
+            vector: score.vector.clone(),
+            score: value,
+            severity,
+            metrics,
+        })
+    }
+}
+
+fn collect_metrics<'a>(
+    metrics: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> BTreeMap<String, String> {
+    metrics
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.to_string(), value?.to_string())))
+        .collect()
+}
+
+fn v3_metrics(cvss: &CvssV3) -> BTreeMap<String, String> {
+    use cvss::v3::{
+        AttackComplexity, AttackVector, Impact, PrivilegesRequired, Scope, UserInteraction,
+    };
+
+    let impact = |impact: &Impact| match impact {
+        Impact::High => "High",
+        Impact::Low => "Low",
+        Impact::None => "None",
+        Impact::NotDefined => "Not Defined",
+    };
+
+    collect_metrics([
+        (
+            "AV",
+            cvss.attack_vector.as_ref().map(|v| match v {
+                AttackVector::Network => "Network",
+                AttackVector::AdjacentNetwork => "Adjacent Network",
+                AttackVector::Local => "Local",
+                AttackVector::Physical => "Physical",
+                AttackVector::NotDefined => "Not Defined",
+            }),
+        ),
+        (
+            "AC",
+            cvss.attack_complexity.as_ref().map(|v| match v {
+                AttackComplexity::Low => "Low",
+                AttackComplexity::High => "High",
+                AttackComplexity::NotDefined => "Not Defined",
+            }),
+        ),
+        (
+            "PR",
+            cvss.privileges_required.as_ref().map(|v| match v {
+                PrivilegesRequired::None => "None",
+                PrivilegesRequired::Low => "Low",
+                PrivilegesRequired::High => "High",
+                PrivilegesRequired::NotDefined => "Not Defined",
+            }),
+        ),
+        (
+            "UI",
+            cvss.user_interaction.as_ref().map(|v| match v {
+                UserInteraction::None => "None",
+                UserInteraction::Required => "Required",
+                UserInteraction::NotDefined => "Not Defined",
+            }),
+        ),
+        (
+            "S",
+            cvss.scope.as_ref().map(|v| match v {
+                Scope::Unchanged => "Unchanged",
+                Scope::Changed => "Changed",
+                Scope::NotDefined => "Not Defined",
+            }),
+        ),
+        ("C", cvss.confidentiality_impact.as_ref().map(impact)),
+        ("I", cvss.integrity_impact.as_ref().map(impact)),
+        ("A", cvss.availability_impact.as_ref().map(impact)),
+    ])
+}
+
+fn v2_metrics(cvss: &CvssV2) -> BTreeMap<String, String> {
+    use cvss::v2_0::{AccessComplexity, AccessVector, Authentication, Impact};
+
+    let impact = |impact: &Impact| match impact {
+        Impact::None => "None",
+        Impact::Partial => "Partial",
+        Impact::Complete => "Complete",
+    };
+
+    collect_metrics([
+        (
+            "AV",
+            cvss.access_vector.as_ref().map(|v| match v {
+                AccessVector::Network => "Network",
+                AccessVector::AdjacentNetwork => "Adjacent Network",
+                AccessVector::Local => "Local",
+            }),
+        ),
+        (
+            "AC",
+            cvss.access_complexity.as_ref().map(|v| match v {
+                AccessComplexity::High => "High",
+                AccessComplexity::Medium => "Medium",
+                AccessComplexity::Low => "Low",
+            }),
+        ),
+        (
+            "Au",
+            cvss.authentication.as_ref().map(|v| match v {
+                Authentication::Multiple => "Multiple",
+                Authentication::Single => "Single",
+                Authentication::None => "None",
+            }),
+        ),
+        ("C", cvss.confidentiality_impact.as_ref().map(impact)),
+        ("I", cvss.integrity_impact.as_ref().map(impact)),
+        ("A", cvss.availability_impact.as_ref().map(impact)),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sea_orm::prelude::Uuid;
+    use serde_json::json;
+    use trustify_entity::advisory_vulnerability_score::Severity as EntitySeverity;
+
+    fn score(r#type: ScoreType, vector: &str, score: f32) -> advisory_vulnerability_score::Model {
+        advisory_vulnerability_score::Model {
+            id: Uuid::nil(),
+            advisory_id: Uuid::nil(),
+            vulnerability_id: "CVE-0000-0000".into(),
+            r#type,
+            vector: vector.into(),
+            score,
+            severity: EntitySeverity::from((score, r#type)),
+        }
+    }
+
+    #[test]
+    fn v3_1_metrics() {
+        let metrics = CvssMetrics::from_score(&score(
+            ScoreType::V3_1,
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+            9.8,
+        ))
+        .expect("must parse");
+
+        assert_eq!(metrics.score, 9.8);
+        assert_eq!(metrics.severity, Severity::Critical);
+        assert_eq!(
+            json!(metrics.metrics),
+            json!({
+                "AV": "Network",
+                "AC": "Low",
+                "PR": "None",
+                "UI": "None",
+                "S": "Unchanged",
+                "C": "High",
+                "I": "High",
+                "A": "High",
+            })
+        );
+    }
+
+    #[test]
+    fn v2_metrics() {
+        let metrics =
+            CvssMetrics::from_score(&score(ScoreType::V2_0, "AV:N/AC:M/Au:N/C:P/I:N/A:N", 4.3))
+                .expect("must parse");
+
+        assert_eq!(metrics.severity, Severity::Medium);
+        assert_eq!(
+            json!(metrics.metrics),
+            json!({
+                "AV": "Network",
+                "AC": "Medium",
+                "Au": "None",
+                "C": "Partial",
+                "I": "None",
+                "A": "None",
+            })
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(CvssMetrics::from_score(&score(ScoreType::V3_1, "not-a-vector", 1.0)).is_none());
+        assert!(
+            CvssMetrics::from_score(&score(
+                ScoreType::V4_0,
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                9.3,
+            ))
+            .is_none()
+        );
+    }
+}
@@ -12,7 +12,9 @@ use trustify_common::{
     memo::Memo,
     requested_field::{BoolRequestedField, RequestedField},
 };
-use trustify_entity::{advisory_vulnerability, advisory_vulnerability_score, vulnerability};
+use trustify_entity::{
+    advisory_vulnerability, advisory_vulnerability_score, epss_score, vulnerability,
+};
 use trustify_module_ingestor::common::{Deprecation, DeprecationForExt};
 use utoipa::ToSchema;
 
@@ -28,6 +30,15 @@ pub struct VulnerabilityDetails {
     /// Only present when the `scores` query parameter is set to `true`.
     #[serde(default, skip_serializing_if = "IsDefault::is_default")]
     pub scores: RequestedField<Vec<ScoredVector>>,
+
+    /// The EPSS score, the probability of the vulnerability being exploited within the next 30
+    /// days, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epss_score: Option<f64>,
+
+    /// The percentile of the EPSS score, relative to all scored vulnerabilities, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epss_percentile: Option<f64>,
 }
 
 impl VulnerabilityDetails {
@@ -69,6 +80,11 @@ impl VulnerabilityDetails {
             })
         });
 
+        let epss = epss_score::Entity::find_by_id(&vulnerability.id)
+            .one(tx)
+            .instrument(info_span!("find epss score"))
+            .await?;
+
         let advisories = VulnerabilityAdvisorySummary::from_entities(
             vulnerability,
             &advisory_vulnerabilities,
@@ -86,6 +102,8 @@ impl VulnerabilityDetails {
             .await?,
             advisories,
             scores: authoritative_scores,
+            epss_score: epss.as_ref().map(|epss| epss.score),
+            epss_percentile: epss.as_ref().map(|epss| epss.percentile),
         })
     }
 }
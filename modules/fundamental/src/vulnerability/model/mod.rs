@@ -1,11 +1,21 @@
 mod analyze;
+mod cvss;
 mod details;
 mod summary;
+mod suppression;
+mod trend;
+mod ttr;
+mod user_override;
 pub mod v2;
 
 pub use analyze::*;
+pub use cvss::*;
 pub use details::*;
 pub use summary::*;
+pub use suppression::*;
+pub use trend::*;
+pub use ttr::*;
+pub use user_override::*;
 
 use crate::{
     Error,
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use trustify_entity::vulnerability_suppression;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A request to suppress a vulnerability for a package
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct SuppressVulnerability {
+    /// The package the vulnerability doesn't apply to. Without a version, it applies to all
+    /// versions of the package.
+    pub purl: String,
+    /// The reason for accepting the risk, e.g. `vulnerable_code_not_present`
+    pub justification: String,
+    /// When the suppression stops applying. Without it, the suppression applies until revoked.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+/// An accepted risk of a vulnerability, for a package
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct VulnerabilitySuppression {
+    /// The ID of the suppression
+    pub id: Uuid,
+    /// The ID of the vulnerability
+    pub vulnerability_id: String,
+    /// The package the suppression applies to
+    pub purl: String,
+    /// The reason for accepting the risk
+    pub justification: String,
+    /// When the suppression stops applying, if ever
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    /// The user who created the suppression
+    pub created_by: String,
+    /// When the suppression was created
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<vulnerability_suppression::Model> for VulnerabilitySuppression {
+    fn from(value: vulnerability_suppression::Model) -> Self {
+        Self {
+            id: value.id,
+            vulnerability_id: value.vulnerability_id,
+            purl: value.purl,
+            justification: value.justification,
+            expires_at: value.expires_at,
+            created_by: value.created_by,
+            created_at: value.created_at,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The length of the periods trends are bucketed by
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendInterval {
+    Day,
+    Week,
+    #[default]
+    Month,
+    Year,
+}
+
+impl TrendInterval {
+    /// The unit of `date_trunc` truncating a timestamp to the start of its period
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+
+    /// The `to_char` format of the label of a period
+    pub fn format(&self) -> &'static str {
+        match self {
+            Self::Day => "YYYY-MM-DD",
+            Self::Week => r#"IYYY-"W"IW"#,
+            Self::Month => "YYYY-MM",
+            Self::Year => "YYYY",
+        }
+    }
+}
+
+/// Number of vulnerabilities published in a period, affecting packages of an ecosystem.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct EcosystemTrendEntry {
+    /// The period, e.g. `2024-01` for a monthly interval, or `2024-W01` for a weekly one
+    pub period: String,
+    /// The package type, e.g. `maven`
+    pub ecosystem: String,
+    /// Number of vulnerabilities published in the period
+    pub new_vulns: u64,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Time to remediation of a single vulnerability.
+///
+/// Each package which had a fix reported makes up one sample, measured in days from publishing
+/// the vulnerability until the first advisory reported the package as fixed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct TtrMetrics {
+    /// The ID of the vulnerability
+    pub cve_id: String,
+    /// Median number of days until a fix was available
+    pub median_days: f64,
+    /// 90th percentile of days until a fix was available
+    pub p90_days: f64,
+    /// Number of fixed packages the metrics are based on
+    pub sample_size: u64,
+}
+
+/// Time to remediation, aggregated over all vulnerabilities.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct AggregatedTtrMetrics {
+    /// The package type the metrics are limited to, if any
+    pub r#type: Option<String>,
+    /// Average number of days until a fix was available
+    pub average_days: f64,
+    /// Median number of days until a fix was available
+    pub median_days: f64,
+    /// 90th percentile of days until a fix was available
+    pub p90_days: f64,
+    /// Number of fixed packages the metrics are based on
+    pub sample_size: u64,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A user provided override of the status of a package for a vulnerability
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct VulnerabilityOverrideEntry {
+    /// The ID of the advisory created for the override
+    pub advisory_id: Uuid,
+    /// The ID of the vulnerability
+    pub vulnerability_id: String,
+    /// The package the override applies to
+    pub purl: String,
+    /// The status assigned to the package
+    pub status: String,
+    /// The justification for the status, if provided
+    pub justification: Option<String>,
+    /// The statement explaining the decision, if provided
+    pub impact_statement: Option<String>,
+    /// When the override was created
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created: Option<OffsetDateTime>,
+}
@@ -0,0 +1,38 @@
+-- Number of vulnerabilities published per period, for each package type they affect.
+--
+-- A vulnerability counts once for each type of package an advisory reported a status for.
+--
+-- $1: unit the publishing date is truncated to, e.g. 'month'
+-- $2: format of the period label, as used by to_char
+-- $3: start of the time range, inclusive
+-- $4: end of the time range, exclusive, or NULL for no end
+-- $5: minimum number of vulnerabilities of a package type, over the whole time range
+WITH buckets AS (
+    SELECT
+        date_trunc($1, vulnerability.published AT TIME ZONE 'UTC') AS bucket,
+        base_purl.type AS ecosystem,
+        COUNT(DISTINCT vulnerability.id) AS new_vulns
+    FROM purl_status
+        JOIN advisory ON advisory.id = purl_status.advisory_id
+        JOIN vulnerability ON vulnerability.id = purl_status.vulnerability_id
+        JOIN base_purl ON base_purl.id = purl_status.base_purl_id
+    WHERE advisory.deprecated = FALSE
+        AND vulnerability.published >= $3
+        AND ($4::timestamptz IS NULL OR vulnerability.published < $4)
+    GROUP BY 1, 2
+),
+totals AS (
+    SELECT
+        bucket,
+        ecosystem,
+        new_vulns,
+        SUM(new_vulns) OVER (PARTITION BY ecosystem) AS total
+    FROM buckets
+)
+SELECT
+    to_char(bucket, $2) AS period,
+    ecosystem,
+    new_vulns
+FROM totals
+WHERE total >= $5
+ORDER BY bucket, ecosystem
@@ -9,22 +9,33 @@ use crate::{
         details::{purl::PurlStatus, version_range::VersionRange},
         summary::remediation::RemediationSummary,
     },
+    sbom::{
+        model::{AffectedSeverity, SbomSummary},
+        service::SbomService,
+    },
     vulnerability::model::{
-        AnalysisDetailsV3, AnalysisPurlStatus, AnalysisResponseV3, AnalysisResultV3,
-        VulnerabilityDetails, VulnerabilityHead, VulnerabilitySummary,
+        AggregatedTtrMetrics, AnalysisDetailsV3, AnalysisPurlStatus, AnalysisResponseV3,
+        AnalysisResultV3, AnnotatedNode, CvssBreakdown, CvssMetrics, EcosystemTrendEntry,
+        SuppressVulnerability, TrendInterval, TtrMetrics, VulnerabilityDetails, VulnerabilityHead,
+        VulnerabilityOverrideEntry, VulnerabilitySummary, VulnerabilitySuppression,
         v2::{AnalysisAdvisory, AnalysisDetails, AnalysisResponse, AnalysisResult},
     },
 };
-use sea_orm::{EntityTrait, FromQueryResult, Statement, prelude::*};
-use sea_query::{Expr, PgFunc};
+use sea_orm::{
+    Condition, EntityTrait, FromQueryResult, QueryOrder, QuerySelect, QueryTrait, RelationTrait,
+    Set, Statement, prelude::*,
+};
+use sea_query::{Asterisk, Expr, Func, JoinType, PgFunc, SimpleExpr, extension::postgres::PgExpr};
 use std::{
-    collections::{BTreeMap, HashMap, btree_map::Entry},
+    collections::{BTreeMap, BTreeSet, HashMap, btree_map::Entry},
     str::FromStr,
 };
+use time::OffsetDateTime;
 use tracing::instrument;
 use trustify_common::{
     db::{
-        limiter::{LimitedResult, LimiterTrait},
+        VersionMatches,
+        limiter::{LimitedResult, LimiterAsModelTrait, LimiterTrait},
         pagination_cache::PaginationCache,
         query::{Columns, Filtering, Query},
     },
@@ -33,16 +44,32 @@ use trustify_common::{
     purl::Purl,
 };
 use trustify_entity::{
-    advisory, advisory_vulnerability_score, cpe, organization, remediation::RemediationCategory,
-    vulnerability, vulnerability_description,
+    advisory, advisory_vulnerability,
+    advisory_vulnerability_score::{self, ScoreType},
+    base_purl, cpe,
+    labels::Labels,
+    organization, purl_status, qualified_purl,
+    remediation::RemediationCategory,
+    sbom, sbom_node_purl_ref, status, version_range, versioned_purl, vulnerability,
+    vulnerability_description, vulnerability_suppression,
+};
+use trustify_module_ingestor::{
+    common::Deprecation,
+    service::advisory::user_override::{JUSTIFICATION_LABEL, ORIGIN_LABEL, ORIGIN_USER_OVERRIDE},
 };
-use trustify_module_ingestor::common::Deprecation;
 
 struct AdvisoryData {
     advisory: advisory::Model,
     issuer: Memo<organization::Model>,
 }
 
+struct TtrStatistics {
+    average_days: f64,
+    median_days: f64,
+    p90_days: f64,
+    sample_size: u64,
+}
+
 struct AnalysisData {
     purls_with_vulnerabilities: Vec<QueryResult>,
     warnings: HashMap<String, Vec<String>>,
@@ -50,6 +77,43 @@ struct AnalysisData {
     scores: Vec<advisory_vulnerability_score::Model>,
     advisories_map: HashMap<Uuid, AdvisoryData>,
     cpe_map: HashMap<Uuid, cpe::Model>,
+    suppressions: Vec<vulnerability_suppression::Model>,
+}
+
+#[derive(FromQueryResult)]
+struct OverrideRow {
+    advisory_id: Uuid,
+    vulnerability_id: String,
+    status: String,
+    ty: String,
+    namespace: Option<String>,
+    name: String,
+    version: Option<String>,
+    labels: Labels,
+    created: Option<OffsetDateTime>,
+    impact_statement: Option<String>,
+}
+
+impl From<OverrideRow> for VulnerabilityOverrideEntry {
+    fn from(row: OverrideRow) -> Self {
+        let purl = Purl {
+            ty: row.ty,
+            namespace: row.namespace,
+            name: row.name,
+            version: row.version,
+            qualifiers: Default::default(),
+        };
+
+        Self {
+            advisory_id: row.advisory_id,
+            vulnerability_id: row.vulnerability_id,
+            purl: purl.to_string(),
+            status: row.status,
+            justification: row.labels.0.get(JUSTIFICATION_LABEL).cloned(),
+            impact_statement: row.impact_statement,
+            created: row.created,
+        }
+    }
 }
 
 pub struct VulnerabilityService {
@@ -97,6 +161,7 @@ impl VulnerabilityService {
         Ok(PaginatedResults {
             total,
             items: VulnerabilitySummary::from_entities(&vulnerabilities, connection).await?,
+            next_cursor: None,
         })
     }
 
@@ -129,6 +194,58 @@ impl VulnerabilityService {
         }
     }
 
+    /// List the SBOMs containing a package with one of the `statuses` for a vulnerability.
+    ///
+    /// Packages are matched by their PURL, against the version ranges of non-deprecated
+    /// advisories. An empty list of statuses includes all statuses.
+    #[instrument(
+        skip(self, paginated, connection),
+        err(level=tracing::Level::INFO)
+    )]
+    pub async fn affected_sboms<C: ConnectionTrait + Sync + Send>(
+        &self,
+        vulnerability_id: &str,
+        statuses: Vec<String>,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<SbomSummary>, Error> {
+        let mut affected = sbom_node_purl_ref::Entity::find()
+            .select_only()
+            .column(sbom_node_purl_ref::Column::SbomId)
+            .join(JoinType::Join, sbom_node_purl_ref::Relation::Purl.def())
+            .join(
+                JoinType::Join,
+                qualified_purl::Relation::VersionedPurl.def(),
+            )
+            .join(JoinType::Join, versioned_purl::Relation::BasePurl.def())
+            .join(JoinType::Join, base_purl::Relation::PurlStatus.def())
+            .filter(purl_status::Column::VulnerabilityId.eq(vulnerability_id))
+            .join(JoinType::Join, purl_status::Relation::Status.def())
+            .join(JoinType::Join, purl_status::Relation::VersionRange.def())
+            .filter(SimpleExpr::FunctionCall(
+                Func::cust(VersionMatches)
+                    .arg(Expr::col((
+                        versioned_purl::Entity,
+                        versioned_purl::Column::Version,
+                    )))
+                    .arg(Expr::col((version_range::Entity, Asterisk))),
+            ))
+            .join(JoinType::Join, purl_status::Relation::Advisory.def())
+            .filter(advisory::Column::Deprecated.eq(false));
+
+        if !statuses.is_empty() {
+            affected = affected.filter(status::Column::Slug.is_in(statuses));
+        }
+
+        let select = sbom::Entity::find()
+            .filter(sbom::Column::DeletedAt.is_null())
+            .filter(sbom::Column::SbomId.in_subquery(affected.into_query()));
+
+        SbomService::new(self.cache.clone())
+            .related_sboms(select, paginated, Query::default(), connection)
+            .await
+    }
+
     #[instrument(
         skip(self, connection),
         err(level=tracing::Level::INFO),
@@ -145,6 +262,339 @@ impl VulnerabilityService {
         Ok(result.rows_affected)
     }
 
+    /// List the user provided overrides of package statuses, which are not deprecated by a
+    /// newer override.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn fetch_overrides<C: ConnectionTrait + Sync + Send>(
+        &self,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<VulnerabilityOverrideEntry>, Error> {
+        let limiter = purl_status::Entity::find()
+            .select_only()
+            .column_as(purl_status::Column::AdvisoryId, "advisory_id")
+            .column_as(purl_status::Column::VulnerabilityId, "vulnerability_id")
+            .column_as(status::Column::Slug, "status")
+            .column_as(base_purl::Column::Type, "ty")
+            .column_as(base_purl::Column::Namespace, "namespace")
+            .column_as(base_purl::Column::Name, "name")
+            .column_as(version_range::Column::LowVersion, "version")
+            .column_as(advisory::Column::Labels, "labels")
+            .column_as(advisory::Column::Published, "created")
+            .column_as(
+                advisory_vulnerability::Column::Description,
+                "impact_statement",
+            )
+            .join(JoinType::Join, purl_status::Relation::Advisory.def())
+            .join(JoinType::Join, purl_status::Relation::Status.def())
+            .join(JoinType::Join, purl_status::Relation::BasePurl.def())
+            .join(JoinType::Join, purl_status::Relation::VersionRange.def())
+            .join(
+                JoinType::LeftJoin,
+                purl_status::Relation::AdvisoryVulnerability.def(),
+            )
+            .filter(
+                Expr::col(advisory::Column::Labels)
+                    .contains(Labels::new().add(ORIGIN_LABEL, ORIGIN_USER_OVERRIDE)),
+            )
+            .filter(advisory::Column::Deprecated.eq(false))
+            .order_by_desc(advisory::Column::Published)
+            .order_by_asc(purl_status::Column::Id)
+            .limiting_as::<OverrideRow>(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items
+                .into_iter()
+                .map(VulnerabilityOverrideEntry::from)
+                .collect(),
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Suppress a vulnerability for a package, accepting the risk.
+    ///
+    /// Returns `None` if the vulnerability doesn't exist.
+    #[instrument(
+        skip(self, request, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn create_suppression<C: ConnectionTrait + Sync + Send>(
+        &self,
+        vulnerability_id: &str,
+        request: SuppressVulnerability,
+        created_by: &str,
+        connection: &C,
+    ) -> Result<Option<VulnerabilitySuppression>, Error> {
+        let purl = Purl::from_str(&request.purl)?;
+
+        if let Some(expires_at) = request.expires_at
+            && expires_at <= OffsetDateTime::now_utc()
+        {
+            return Err(Error::bad_request(
+                "Expiration must be in the future",
+                Some(format!("expires_at: {expires_at}")),
+            ));
+        }
+
+        if vulnerability::Entity::find_by_id(vulnerability_id)
+            .one(connection)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let suppression = vulnerability_suppression::ActiveModel {
+            id: Set(Uuid::now_v7()),
+            vulnerability_id: Set(vulnerability_id.to_string()),
+            purl: Set(purl.to_string()),
+            justification: Set(request.justification),
+            expires_at: Set(request.expires_at),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(OffsetDateTime::now_utc()),
+        }
+        .insert(connection)
+        .await?;
+
+        Ok(Some(suppression.into()))
+    }
+
+    /// List the suppressions which didn't expire yet, the most recent first.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn fetch_suppressions<C: ConnectionTrait + Sync + Send>(
+        &self,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<VulnerabilitySuppression>, Error> {
+        let limiter = vulnerability_suppression::Entity::find()
+            .filter(active_suppression(OffsetDateTime::now_utc()))
+            .order_by_desc(vulnerability_suppression::Column::CreatedAt)
+            .order_by_asc(vulnerability_suppression::Column::Id)
+            .limiting(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items
+                .into_iter()
+                .map(VulnerabilitySuppression::from)
+                .collect(),
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Revoke a suppression, returning the revoked suppression if it existed.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn delete_suppression<C: ConnectionTrait + Sync + Send>(
+        &self,
+        id: Uuid,
+        connection: &C,
+    ) -> Result<Option<VulnerabilitySuppression>, Error> {
+        let Some(suppression) = vulnerability_suppression::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        vulnerability_suppression::Entity::delete_by_id(id)
+            .exec(connection)
+            .await?;
+
+        Ok(Some(suppression.into()))
+    }
+
+    /// Break down the CVSS scores of a vulnerability into their individual metrics.
+    ///
+    /// For each CVSS version, a score of the authoritative advisory is preferred. Otherwise,
+    /// the highest score is used. Returns `None` if the vulnerability doesn't exist.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn cvss_breakdown<C: ConnectionTrait + Sync + Send>(
+        &self,
+        vulnerability_id: &str,
+        connection: &C,
+    ) -> Result<Option<CvssBreakdown>, Error> {
+        let Some(vulnerability) = vulnerability::Entity::find_by_id(vulnerability_id)
+            .one(connection)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut scores = advisory_vulnerability_score::Entity::find()
+            .filter(advisory_vulnerability_score::Column::VulnerabilityId.eq(vulnerability_id))
+            .order_by_desc(advisory_vulnerability_score::Column::Score)
+            .all(connection)
+            .await?;
+
+        // stable sort, keeping the order of scores otherwise
+        scores.sort_by_key(|score| {
+            Some(score.advisory_id) != vulnerability.authoritative_advisory_id
+        });
+
+        let breakdown = |r#type| {
+            scores
+                .iter()
+                .filter(|score| score.r#type == r#type)
+                .find_map(CvssMetrics::from_score)
+        };
+
+        Ok(Some(CvssBreakdown {
+            v3_1: breakdown(ScoreType::V3_1),
+            v3_0: breakdown(ScoreType::V3_0),
+            v2: breakdown(ScoreType::V2_0),
+        }))
+    }
+
+    /// Time it took to get a fix for packages affected by a vulnerability.
+    ///
+    /// Returns `None` if no package was reported as fixed.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn time_to_remediation<C: ConnectionTrait + Sync + Send>(
+        &self,
+        vulnerability_id: &str,
+        connection: &C,
+    ) -> Result<Option<TtrMetrics>, Error> {
+        let metrics = Self::ttr_statistics(Some(vulnerability_id), None, connection).await?;
+
+        Ok(metrics.map(|metrics| TtrMetrics {
+            cve_id: vulnerability_id.to_string(),
+            median_days: metrics.median_days,
+            p90_days: metrics.p90_days,
+            sample_size: metrics.sample_size,
+        }))
+    }
+
+    /// Time it took to get a fix for packages affected by any vulnerability, optionally limited
+    /// to a package type.
+    ///
+    /// Returns `None` if no package was reported as fixed.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn average_time_to_remediation<C: ConnectionTrait + Sync + Send>(
+        &self,
+        r#type: Option<&str>,
+        connection: &C,
+    ) -> Result<Option<AggregatedTtrMetrics>, Error> {
+        let metrics = Self::ttr_statistics(None, r#type, connection).await?;
+
+        Ok(metrics.map(|metrics| AggregatedTtrMetrics {
+            r#type: r#type.map(ToString::to_string),
+            average_days: metrics.average_days,
+            median_days: metrics.median_days,
+            p90_days: metrics.p90_days,
+            sample_size: metrics.sample_size,
+        }))
+    }
+
+    async fn ttr_statistics<C: ConnectionTrait>(
+        vulnerability_id: Option<&str>,
+        r#type: Option<&str>,
+        connection: &C,
+    ) -> Result<Option<TtrStatistics>, Error> {
+        #[derive(FromQueryResult)]
+        struct Row {
+            average_days: Option<f64>,
+            median_days: Option<f64>,
+            p90_days: Option<f64>,
+            sample_size: i64,
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            connection.get_database_backend(),
+            include_str!("time_to_remediation.sql"),
+            [vulnerability_id.into(), r#type.into()],
+        );
+
+        let Some(row) = Row::find_by_statement(stmt).one(connection).await? else {
+            return Ok(None);
+        };
+
+        let (Some(average_days), Some(median_days), Some(p90_days)) =
+            (row.average_days, row.median_days, row.p90_days)
+        else {
+            // no samples at all
+            return Ok(None);
+        };
+
+        Ok(Some(TtrStatistics {
+            average_days,
+            median_days,
+            p90_days,
+            sample_size: row.sample_size as u64,
+        }))
+    }
+
+    /// Number of vulnerabilities published per period and package type, starting at `start`.
+    ///
+    /// Package types with fewer than `min_count` vulnerabilities over the whole time range are
+    /// left out.
+    #[instrument(
+        skip(self, connection),
+        err(level=tracing::Level::INFO),
+    )]
+    pub async fn ecosystem_trend<C: ConnectionTrait + Sync + Send>(
+        &self,
+        interval: TrendInterval,
+        start: OffsetDateTime,
+        end: Option<OffsetDateTime>,
+        min_count: u64,
+        connection: &C,
+    ) -> Result<Vec<EcosystemTrendEntry>, Error> {
+        #[derive(FromQueryResult)]
+        struct Row {
+            period: String,
+            ecosystem: String,
+            new_vulns: i64,
+        }
+
+        let stmt = Statement::from_sql_and_values(
+            connection.get_database_backend(),
+            include_str!("ecosystem_trend.sql"),
+            [
+                interval.unit().into(),
+                interval.format().into(),
+                start.into(),
+                end.into(),
+                (min_count as i64).into(),
+            ],
+        );
+
+        Ok(Row::find_by_statement(stmt)
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|row| EcosystemTrendEntry {
+                period: row.period,
+                ecosystem: row.ecosystem,
+                new_vulns: row.new_vulns as u64,
+            })
+            .collect())
+    }
+
     /// Analyze PURLs and return vulnerability data in the v3 response format.
     ///
     /// V3 response uses `AnalysisResponseV3` / `AnalysisResultV3` / `AnalysisDetailsV3`.
@@ -166,6 +616,103 @@ impl VulnerabilityService {
         self.format_response(data, connection).await
     }
 
+    /// Annotate SBOM nodes, given as pairs of SBOM and node IDs, with the vulnerabilities
+    /// affecting their PURLs.
+    ///
+    /// The PURLs of all nodes are analyzed at once. Nodes which don't exist, or don't have any
+    /// PURL, are returned without PURLs and vulnerabilities.
+    #[instrument(
+        skip_all,
+        err(level=tracing::Level::INFO),
+        fields(count=nodes.len()),
+    )]
+    pub async fn annotate_nodes<C>(
+        &self,
+        nodes: &[(Uuid, String)],
+        connection: &C,
+    ) -> Result<Vec<AnnotatedNode>, Error>
+    where
+        C: ConnectionTrait,
+    {
+        if nodes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let sbom_ids: Vec<Uuid> = nodes.iter().map(|(sbom_id, _)| *sbom_id).collect();
+        let node_ids: Vec<String> = nodes.iter().map(|(_, node_id)| node_id.clone()).collect();
+
+        // this may select nodes which were not requested, they are never looked up below
+        let refs = sbom_node_purl_ref::Entity::find()
+            .filter(Expr::col(sbom_node_purl_ref::Column::SbomId).eq(PgFunc::any(sbom_ids)))
+            .filter(Expr::col(sbom_node_purl_ref::Column::NodeId).eq(PgFunc::any(node_ids)))
+            .find_also_related(qualified_purl::Entity)
+            .all(connection)
+            .await?;
+
+        let mut node_purls: HashMap<(Uuid, String), BTreeSet<String>> = HashMap::new();
+        for (node, purl) in refs {
+            if let Some(purl) = purl {
+                node_purls
+                    .entry((node.sbom_id, node.node_id))
+                    .or_default()
+                    .insert(Purl::from(purl.purl).to_string());
+            }
+        }
+
+        let all_purls: BTreeSet<&String> = node_purls.values().flatten().collect();
+        let analysis = self.analyze_purls_v3(all_purls, connection).await?;
+
+        Ok(nodes
+            .iter()
+            .map(|(sbom_id, node_id)| {
+                let purls: Vec<String> = node_purls
+                    .get(&(*sbom_id, node_id.clone()))
+                    .map(|purls| purls.iter().cloned().collect())
+                    .unwrap_or_default();
+
+                // the highest severity of each vulnerability, across all PURLs and advisories
+                let mut severities: HashMap<&str, AffectedSeverity> = HashMap::new();
+                for details in purls
+                    .iter()
+                    .filter_map(|purl| analysis.get(purl))
+                    .flat_map(|result| &result.details)
+                    .filter(|details| !details.suppressed)
+                {
+                    for status in details
+                        .purl_statuses
+                        .iter()
+                        .filter(|status| status.purl_status.status == "affected")
+                    {
+                        let severity = AffectedSeverity::from(
+                            status
+                                .purl_status
+                                .scores
+                                .iter()
+                                .map(|score| score.score.severity)
+                                .max(),
+                        );
+                        severities
+                            .entry(&details.head.identifier)
+                            .and_modify(|current| *current = (*current).max(severity))
+                            .or_insert(severity);
+                    }
+                }
+
+                let mut vulnerability_summary = HashMap::new();
+                for severity in severities.into_values() {
+                    *vulnerability_summary.entry(severity).or_default() += 1;
+                }
+
+                AnnotatedNode {
+                    sbom_id: *sbom_id,
+                    node_id: node_id.clone(),
+                    purls,
+                    vulnerability_summary,
+                }
+            })
+            .collect())
+    }
+
     /// Analyze PURLs and return vulnerability data in the legacy v2 response format.
     ///
     /// V2 response uses `AnalysisResponse` / `AnalysisResult` / `AnalysisDetails` / `AnalysisAdvisory`.
@@ -274,7 +821,7 @@ impl VulnerabilityService {
                 )
                 .filter(
                     Expr::col(advisory_vulnerability_score::Column::VulnerabilityId)
-                        .eq(PgFunc::any(vulnerability_ids)),
+                        .eq(PgFunc::any(vulnerability_ids.clone())),
                 )
                 .all(connection)
                 .await?
@@ -326,6 +873,21 @@ impl VulnerabilityService {
         };
         log::debug!("Pre-fetched {} CPEs", cpe_map.len());
 
+        // Pre-fetch the active suppressions of the found vulnerabilities
+        let suppressions = if !vulnerability_ids.is_empty() {
+            vulnerability_suppression::Entity::find()
+                .filter(
+                    Expr::col(vulnerability_suppression::Column::VulnerabilityId)
+                        .eq(PgFunc::any(vulnerability_ids)),
+                )
+                .filter(active_suppression(OffsetDateTime::now_utc()))
+                .all(connection)
+                .await?
+        } else {
+            vec![]
+        };
+        log::debug!("Pre-fetched {} suppressions", suppressions.len());
+
         Ok(AnalysisData {
             purls_with_vulnerabilities,
             warnings,
@@ -333,6 +895,7 @@ impl VulnerabilityService {
             scores,
             advisories_map: advisories,
             cpe_map,
+            suppressions,
         })
     }
 
@@ -352,6 +915,7 @@ impl VulnerabilityService {
             scores,
             advisories_map,
             cpe_map,
+            suppressions,
         } = data;
 
         // Build a map of (advisory_id, vulnerability_id) -> Vec<Model> for score calculation
@@ -367,7 +931,7 @@ impl VulnerabilityService {
         let mut result = BTreeMap::new();
 
         for row in purls_with_vulnerabilities {
-            let (requested_purl, head) = Self::row_to_vuln_v3(
+            let (requested_purl, mut head) = Self::row_to_vuln_v3(
                 row,
                 connection,
                 &descriptions_map,
@@ -377,6 +941,11 @@ impl VulnerabilityService {
             )
             .await?;
 
+            head.suppressed = suppressions.iter().any(|suppression| {
+                suppression.vulnerability_id == head.head.identifier
+                    && suppresses(&suppression.purl, &requested_purl)
+            });
+
             match result.entry(requested_purl.clone()) {
                 Entry::Vacant(entry) => {
                     entry.insert(AnalysisResultV3 {
@@ -414,6 +983,7 @@ impl VulnerabilityService {
             scores,
             advisories_map,
             cpe_map: _,
+            suppressions: _,
         } = data;
 
         let mut scores_map: HashMap<(Uuid, String), Vec<Score>> = HashMap::new();
@@ -471,7 +1041,7 @@ impl VulnerabilityService {
         format!(
             r#"
 SELECT
-  $1 as requested_purl,
+  input.requested_purl,
   vulnerability.id,
   vulnerability.title,
   vulnerability.reserved,
@@ -521,18 +1091,21 @@ GROUP BY
   vulnerability.withdrawn,
   vulnerability.cwes,
   vulnerability.id_sort_key,
-  requested_purl
+  input.requested_purl
 "#
         )
     }
 
     /// Build the query for finding matching vulnerabilities
+    ///
+    /// All PURLs are provided to the query as a single `VALUES` list, which both parts of the
+    /// query, matching PURL statuses and product statuses, join against.
     fn build_query(
         purls: impl IntoIterator<Item = impl AsRef<str>>,
         connection: &impl ConnectionTrait,
         warnings: &mut HashMap<String, Vec<String>>,
     ) -> Result<String, Error> {
-        let query = purls
+        let input = purls
             .into_iter()
             .map(|p| {
                 let p = p.as_ref();
@@ -546,120 +1119,99 @@ GROUP BY
                     return Ok(None);
                 };
 
-                let ns_condition = match &purl.namespace {
-                    Some(namespace) => {
-                        let sql = "base_purl.namespace = $1";
-                        Statement::from_sql_and_values(
-                            connection.get_database_backend(),
-                            sql,
-                            [namespace.into()],
-                        )
-                        .to_string()
-                    }
-                    None => "base_purl.namespace IS NULL".to_string(),
-                };
-
-                let purl_status_sql = Self::build_vulnerabilities_query_string(
-                    r#"'advisory_id', purl_status.advisory_id, 'context_cpe', purl_status.context_cpe_id"#,
-                    r#" remediation_purl_status rps
-                        JOIN remediation r ON r.id = rps.remediation_id
-                        WHERE rps.purl_status_id = purl_status.id
-                    "#,
-                    r#" base_purl
-                        LEFT JOIN purl_status ON base_purl.id = purl_status.base_purl_id
-                        INNER JOIN version_range ON purl_status.version_range_id = version_range.id
-                        LEFT JOIN vulnerability ON purl_status.vulnerability_id = vulnerability.id
-                        INNER JOIN status ON purl_status.status_id = status.id
-                    "#,
-                    format!(r#" {ns_condition}
-                        AND base_purl.name = $2
-                        AND base_purl.type = $3
-                        AND version_matches($4, version_range.*) = TRUE
-                    "#).as_str(),
-                    "r.data",
-                );
-
-                let purl_status_query = Statement::from_sql_and_values(
-                    connection.get_database_backend(),
-                    &purl_status_sql,
-                    [
-                        p.into(),
-                        purl.name.clone().into(),
-                        purl.ty.clone().into(),
-                        version.clone().into(),
-                    ],
-                );
-
-                let package_condition = match &purl.namespace {
-                    Some(namespace) => {
-                        let full_name = format!("{}/{}", namespace, purl.name);
-                        let sql = "(product_status.package = $1 OR product_status.package = $2)";
-                        Statement::from_sql_and_values(
-                            connection.get_database_backend(),
-                            sql,
-                            [full_name.into(), purl.name.clone().into()],
-                        )
-                        .to_string()
-                    }
-                    None => {
-                        let sql = "product_status.package = $1";
-                        Statement::from_sql_and_values(
-                            connection.get_database_backend(),
-                            sql,
-                            [purl.name.clone().into()],
-                        )
-                        .to_string()
-                    }
-                };
-
-                let product_status_sql = Self::build_vulnerabilities_query_string(
-                    r#" 'advisory_id', product_status.advisory_id,
-                        'context_cpe', cpe.id
-                    "#,
-                    r#" remediation_product_status rps
-                        JOIN remediation r ON r.id = rps.remediation_id
-                        WHERE rps.product_status_id = product_status.id
-                    "#,
-                    r#" product_status
-                        JOIN status ON product_status.status_id = status.id
-                        JOIN vulnerability ON product_status.vulnerability_id = vulnerability.id
-                        JOIN product_version_range ON product_status.product_version_range_id = product_version_range.id
-                        JOIN version_range ON product_version_range.version_range_id = version_range.id
-                        LEFT JOIN cpe ON product_status.context_cpe_id = cpe.id
-                    "#,
-                    format!(r#" {package_condition}
-                        AND product_status.package IS NOT NULL
-                    "#).as_str(),
-                    r#" jsonb_set(r.data, '{product_ids}',
-                        COALESCE(
-                            (SELECT jsonb_agg(pid)
-                             FROM jsonb_array_elements_text(r.data->'product_ids') pid
-                             WHERE pid IN (
-                                SELECT unnest(ps2.csaf_product_ids)
-                                FROM product_status ps2
-                                JOIN remediation_product_status rps2 ON rps2.product_status_id = ps2.id
-                                WHERE rps2.remediation_id = r.id
-                                  AND ps2.id = product_status.id
-                             )
-                            ), '[]'::jsonb)
-                    ) "#,
-                );
-                let product_status_query = Statement::from_sql_and_values(
-                    connection.get_database_backend(),
-                    &product_status_sql,
-                    [p.into()],
-                );
-
-                Ok(Some(format!(
-                    "{} UNION ALL {}",
-                    purl_status_query, product_status_query
-                )))
+                let full_name = purl
+                    .namespace
+                    .as_ref()
+                    .map(|namespace| format!("{}/{}", namespace, purl.name));
+
+                Ok(Some(
+                    Statement::from_sql_and_values(
+                        connection.get_database_backend(),
+                        "($1, $2, $3, $4, $5, $6)",
+                        [
+                            p.into(),
+                            purl.namespace.into(),
+                            purl.name.into(),
+                            purl.ty.into(),
+                            version.into(),
+                            full_name.into(),
+                        ],
+                    )
+                    .to_string(),
+                ))
             })
             .filter_map(Result::transpose)
-            .collect::<Result<Vec<String>, Error>>()?
-            .join(" UNION ALL ");
+            .collect::<Result<Vec<String>, Error>>()?;
+
+        if input.is_empty() {
+            return Ok(String::new());
+        }
+
+        let purl_status_sql = Self::build_vulnerabilities_query_string(
+            r#"'advisory_id', purl_status.advisory_id, 'context_cpe', purl_status.context_cpe_id"#,
+            r#" remediation_purl_status rps
+                JOIN remediation r ON r.id = rps.remediation_id
+                WHERE rps.purl_status_id = purl_status.id
+            "#,
+            r#" input
+                JOIN base_purl ON base_purl.name = input.name
+                    AND base_purl.type = input.type
+                    AND base_purl.namespace IS NOT DISTINCT FROM input.namespace
+                LEFT JOIN purl_status ON base_purl.id = purl_status.base_purl_id
+                INNER JOIN version_range ON purl_status.version_range_id = version_range.id
+                LEFT JOIN vulnerability ON purl_status.vulnerability_id = vulnerability.id
+                INNER JOIN status ON purl_status.status_id = status.id
+            "#,
+            r#" version_matches(input.version, version_range.*) = TRUE
+            "#,
+            "r.data",
+        );
+
+        let product_status_sql = Self::build_vulnerabilities_query_string(
+            r#" 'advisory_id', product_status.advisory_id,
+                'context_cpe', cpe.id
+            "#,
+            r#" remediation_product_status rps
+                JOIN remediation r ON r.id = rps.remediation_id
+                WHERE rps.product_status_id = product_status.id
+            "#,
+            r#" input
+                JOIN product_status ON product_status.package = input.full_name
+                    OR product_status.package = input.name
+                JOIN status ON product_status.status_id = status.id
+                JOIN vulnerability ON product_status.vulnerability_id = vulnerability.id
+                JOIN product_version_range ON product_status.product_version_range_id = product_version_range.id
+                JOIN version_range ON product_version_range.version_range_id = version_range.id
+                LEFT JOIN cpe ON product_status.context_cpe_id = cpe.id
+            "#,
+            r#" product_status.package IS NOT NULL
+            "#,
+            r#" jsonb_set(r.data, '{product_ids}',
+                COALESCE(
+                    (SELECT jsonb_agg(pid)
+                     FROM jsonb_array_elements_text(r.data->'product_ids') pid
+                     WHERE pid IN (
+                        SELECT unnest(ps2.csaf_product_ids)
+                        FROM product_status ps2
+                        JOIN remediation_product_status rps2 ON rps2.product_status_id = ps2.id
+                        WHERE rps2.remediation_id = r.id
+                          AND ps2.id = product_status.id
+                     )
+                    ), '[]'::jsonb)
+            ) "#,
+        );
 
-        Ok(query)
+        Ok(format!(
+            r#"
+WITH input (requested_purl, namespace, name, type, version, full_name) AS (
+  VALUES {}
+)
+{purl_status_sql}
+UNION ALL
+{product_status_sql}
+"#,
+            input.join(", ")
+        ))
     }
 
     /// Take a row from [`Self::build_query`] and turn it into a result entry
@@ -797,6 +1349,7 @@ GROUP BY
             AnalysisDetailsV3 {
                 head,
                 purl_statuses,
+                suppressed: false,
             },
         ))
     }
@@ -899,3 +1452,25 @@ GROUP BY
         ))
     }
 }
+
+/// The condition for a suppression to be active at the point in time `now`
+fn active_suppression(now: OffsetDateTime) -> Condition {
+    Condition::any()
+        .add(vulnerability_suppression::Column::ExpiresAt.is_null())
+        .add(vulnerability_suppression::Column::ExpiresAt.gt(now))
+}
+
+/// Check if a suppression for the PURL `suppression` applies to the requested `purl`
+///
+/// A suppression without a version applies to all versions of the package. Qualifiers are
+/// ignored.
+fn suppresses(suppression: &str, purl: &str) -> bool {
+    let (Ok(suppression), Ok(purl)) = (Purl::from_str(suppression), Purl::from_str(purl)) else {
+        return false;
+    };
+
+    suppression.ty == purl.ty
+        && suppression.namespace == purl.namespace
+        && suppression.name == purl.name
+        && (suppression.version.is_none() || suppression.version == purl.version)
+}
@@ -1,7 +1,10 @@
 use crate::{
     purl::{model::summary::remediation::RemediationSummary, service::PurlService},
     sbom::service::SbomService,
-    vulnerability::{model::BaseScore, service::VulnerabilityService},
+    vulnerability::{
+        model::{BaseScore, TtrMetrics},
+        service::VulnerabilityService,
+    },
 };
 use rstest::rstest;
 use serde_json::json;
@@ -1126,3 +1129,72 @@ async fn analyze_purls_product_status_0044(ctx: &TrustifyContext) -> Result<(),
 
     Ok(())
 }
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn time_to_remediation(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    use sea_orm::ConnectionTrait;
+
+    let service = VulnerabilityService::new(PaginationCache::for_test());
+
+    ctx.ingest_documents(["osv/RUSTSEC-2021-0079.json", "cve/CVE-2021-32714.json"])
+        .await?;
+
+    // the RUSTSEC advisory reporting the fix got published 30 days later
+    ctx.db
+        .execute_unprepared(
+            "UPDATE vulnerability SET published = '2021-06-07T12:00:00Z' WHERE id = 'CVE-2021-32714'",
+        )
+        .await?;
+
+    let ttr = service
+        .time_to_remediation("CVE-2021-32714", &ctx.db)
+        .await?
+        .expect("must have metrics");
+
+    assert_eq!(
+        ttr,
+        TtrMetrics {
+            cve_id: "CVE-2021-32714".into(),
+            median_days: 30.0,
+            p90_days: 30.0,
+            sample_size: 1,
+        }
+    );
+
+    let ttr = service
+        .average_time_to_remediation(Some("cargo"), &ctx.db)
+        .await?
+        .expect("must have metrics");
+
+    assert_eq!(ttr.average_days, 30.0);
+    assert_eq!(ttr.sample_size, 1);
+
+    // nothing got fixed for those
+    assert!(
+        service
+            .time_to_remediation("CVE-0000-0000", &ctx.db)
+            .await?
+            .is_none()
+    );
+    assert!(
+        service
+            .average_time_to_remediation(Some("maven"), &ctx.db)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[case::same("pkg:cargo/hyper@0.14.9", "pkg:cargo/hyper@0.14.9", true)]
+#[case::any_version("pkg:cargo/hyper", "pkg:cargo/hyper@0.14.9", true)]
+#[case::qualifiers("pkg:cargo/hyper@0.14.9", "pkg:cargo/hyper@0.14.9?foo=bar", true)]
+#[case::other_version("pkg:cargo/hyper@0.14.10", "pkg:cargo/hyper@0.14.9", false)]
+#[case::other_name("pkg:cargo/hyper", "pkg:cargo/tokio@1.0.0", false)]
+#[case::other_namespace("pkg:maven/org.foo/bar", "pkg:maven/org.baz/bar@1.0", false)]
+#[case::invalid("hyper", "pkg:cargo/hyper@0.14.9", false)]
+fn suppresses(#[case] suppression: &str, #[case] purl: &str, #[case] expected: bool) {
+    assert_eq!(super::suppresses(suppression, purl), expected);
+}
@@ -0,0 +1,34 @@
+-- Days from publishing a vulnerability until the first advisory reported a package as fixed.
+--
+-- Each package (base PURL) fixed for a vulnerability makes up one sample.
+--
+-- $1: ID of the vulnerability, or NULL for all vulnerabilities
+-- $2: type of the package, or NULL for all types
+WITH samples AS (
+    SELECT
+        GREATEST(
+            EXTRACT(EPOCH FROM (MIN(advisory.published) - vulnerability.published)) / 86400,
+            0
+        )::float8 AS days
+    FROM purl_status
+        JOIN status ON status.id = purl_status.status_id
+        JOIN advisory ON advisory.id = purl_status.advisory_id
+        JOIN vulnerability ON vulnerability.id = purl_status.vulnerability_id
+        JOIN base_purl ON base_purl.id = purl_status.base_purl_id
+    WHERE status.slug = 'fixed'
+        AND advisory.deprecated = FALSE
+        AND advisory.published IS NOT NULL
+        AND vulnerability.published IS NOT NULL
+        AND ($1::text IS NULL OR purl_status.vulnerability_id = $1)
+        AND ($2::text IS NULL OR base_purl.type = $2)
+    GROUP BY
+        purl_status.vulnerability_id,
+        vulnerability.published,
+        purl_status.base_purl_id
+)
+SELECT
+    AVG(days) AS average_days,
+    percentile_cont(0.5) WITHIN GROUP (ORDER BY days) AS median_days,
+    percentile_cont(0.9) WITHIN GROUP (ORDER BY days) AS p90_days,
+    COUNT(*) AS sample_size
+FROM samples
@@ -42,6 +42,7 @@ impl WeaknessService {
         Ok(PaginatedResults {
             items: WeaknessSummary::from_entities(&items).await?,
             total,
+            next_cursor: None,
         })
     }
 
@@ -1,8 +1,25 @@
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
 use sea_orm::{AccessMode, ConnectionTrait, DbBackend, Statement, TransactionTrait};
+use serde_json::json;
+use std::time::Duration;
 use test_context::test_context;
 use test_log::test;
-use trustify_common::db::{DbError, ReadOnly, ReadWrite};
-use trustify_test_context::TrustifyContext;
+use trustify_common::{
+    config,
+    db::{
+        Database, DatabaseErrors, DbError, QueryTimeout, ReadOnly, ReadWrite,
+        pagination_cache::PaginationCache,
+    },
+    model::PaginatedResults,
+};
+use trustify_module_analysis::{config::AnalysisConfig, service::AnalysisService};
+use trustify_module_fundamental::{Config, configure, sbom::model::SbomSummary};
+use trustify_test_context::{
+    TrustifyContext,
+    call::{self, CallService},
+    document_bytes,
+};
 
 /// ReadOnly::begin() opens a transaction that PostgreSQL enforces as read-only.
 #[test_context(TrustifyContext)]
@@ -84,3 +101,135 @@ async fn read_only_allows_reads(ctx: &TrustifyContext) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// ReadOnly cancels statements exceeding the configured query timeout.
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn read_only_query_timeout(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let ro = ReadOnly::new(ctx.db.clone())
+        .with_query_timeout(QueryTimeout::new(Duration::from_millis(10)));
+    let tx = ro.begin().await?;
+
+    let result = tx
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT pg_sleep(1)".to_string(),
+        ))
+        .await;
+
+    let err = result.expect_err("statement must be cancelled");
+    assert!(err.is_query_canceled(), "unexpected error: {err}");
+    assert!(matches!(DbError::from(err), DbError::Timeout));
+
+    // a longer timeout overrides the configured one
+
+    let tx = ro
+        .begin_with_timeout(QueryTimeout::new(Duration::from_secs(10)))
+        .await?;
+
+    tx.query_one(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT pg_sleep(0.1)".to_string(),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Writes never go to a read replica, even while read-only transactions do.
+///
+/// The replica connects to the same database as a role which is only allowed to read, so any
+/// write ending up on the replica would fail.
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn read_only_replicas_see_no_writes(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let role = format!("{}_replica", ctx.db.name());
+
+    // SAFETY: the names are derived from the name of the test database, not from user input
+    ctx.db
+        .execute_unprepared(&format!(
+            r#"
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT FROM pg_roles WHERE rolname = '{role}') THEN
+        CREATE ROLE "{role}" LOGIN PASSWORD 'replica';
+    END IF;
+END
+$$;
+GRANT CONNECT ON DATABASE "{name}" TO "{role}";
+GRANT USAGE ON SCHEMA public TO "{role}";
+GRANT SELECT ON ALL TABLES IN SCHEMA public TO "{role}";
+"#,
+            name = ctx.db.name(),
+        ))
+        .await?;
+
+    let mut replica = config::Database::from_env()?;
+    replica.username = role.clone();
+    replica.password = "replica".into();
+    replica.host = "localhost".into();
+    replica.name = ctx.db.name().into();
+    replica.port = ctx.port;
+    let replica = Database::new(&replica).await?;
+
+    let db_rw = ReadWrite::new(ctx.db.clone());
+    let db_ro = ReadOnly::new(ctx.db.clone()).with_replicas(vec![replica]);
+
+    // read-only transactions are served by the replica
+
+    let tx = db_ro.begin().await?;
+    let user: String = tx
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT current_user::text AS name".to_string(),
+        ))
+        .await?
+        .expect("must return a row")
+        .try_get("", "name")?;
+    assert_eq!(user, role);
+    tx.rollback().await?;
+
+    let analysis = AnalysisService::new(AnalysisConfig::default(), db_ro.clone());
+    let app = call::caller(|svc| {
+        configure(
+            svc,
+            Config {
+                sbom_upload_limit: 10 * 1024 * 1024,
+                advisory_upload_limit: 10 * 1024 * 1024,
+                max_group_name_length: 255,
+                ..Default::default()
+            },
+            db_rw,
+            db_ro.clone(),
+            ctx.storage.clone(),
+            analysis.clone(),
+            PaginationCache::for_test(),
+        );
+        trustify_module_analysis::endpoints::configure(svc, db_ro, analysis);
+    })
+    .await?;
+
+    // writes succeed, so they didn't use the replica
+
+    let request = TestRequest::post()
+        .uri("/api/v3/sbom")
+        .set_payload(document_bytes("spdx/simple.json").await?)
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let request = TestRequest::post()
+        .uri("/api/v3/group/sbom")
+        .set_json(json!({"name": "replicated"}))
+        .to_request();
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // and reading them back goes through the replica
+
+    let request = TestRequest::get().uri("/api/v3/sbom").to_request();
+    let response: PaginatedResults<SbomSummary> = app.call_and_read_body_json(request).await;
+    assert_eq!(response.total, 1);
+
+    Ok(())
+}
@@ -16,6 +16,8 @@ async fn upload_bomb_sbom(ctx: &TrustifyContext) -> anyhow::Result<()> {
             sbom_upload_limit: 1024 * 1024,
             advisory_upload_limit: 1024 * 1024,
             max_group_name_length: 32,
+            sbom_external_ref_resolver: None,
+            ..Default::default()
         },
         PaginationCache::for_test(),
     )
@@ -41,6 +43,8 @@ async fn upload_bomb_advisory(ctx: &TrustifyContext) -> anyhow::Result<()> {
             sbom_upload_limit: 1024 * 1024,
             advisory_upload_limit: 1024 * 1024,
             max_group_name_length: 32,
+            sbom_external_ref_resolver: None,
+            ..Default::default()
         },
         PaginationCache::for_test(),
     )
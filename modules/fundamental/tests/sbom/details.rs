@@ -315,6 +315,7 @@ async fn sbom_severity_counts_cpe_matching(ctx: &TrustifyContext) -> Result<(),
     ctx.ingest_document("cve/CVE-2099-0001.json").await?;
     ctx.ingest_document("cve/CVE-2099-0002.json").await?;
     ctx.ingest_document("cve/CVE-2099-0003.json").await?;
+    sbom.refresh_advisory_summary(&ctx.db).await?;
 
     let sbom_id = Id::parse_uuid(result.id)?;
     let Id::Uuid(sbom_uuid) = sbom_id else {
@@ -116,7 +116,7 @@ async fn test_parse_spdx(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
                     },
                     Which::Left,
                     first,
-                    Some(Relationship::Contains),
+                    vec![Relationship::Contains],
                     &ctx.db,
                 )
                 .await?
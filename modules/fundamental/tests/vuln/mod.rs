@@ -6,7 +6,9 @@ use test_context::test_context;
 use test_log::test;
 use trustify_common::db::pagination_cache::PaginationCache;
 use trustify_common::id::Id;
-use trustify_module_fundamental::vulnerability::service::VulnerabilityService;
+use trustify_module_fundamental::vulnerability::{
+    model::AnalysisResponseV3, service::VulnerabilityService,
+};
 use trustify_module_ingestor::service::Format;
 use trustify_test_context::{Dataset, TrustifyContext, subset::ContainsSubset};
 
@@ -214,3 +216,48 @@ async fn version_filtering(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+/// Analyzing several PURLs at once must give the same result as analyzing each of them alone.
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn analyze_batch(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    ctx.ingest_dataset(Dataset::DS3).await?;
+
+    let service = VulnerabilityService::new(PaginationCache::for_test());
+
+    let purls = [
+        "pkg:rpm/redhat/gnutls@3.7.6-23.el9?arch=aarch64",
+        "pkg:rpm/redhat/gnutls@3.8.3-5.el9?arch=aarch64",
+        "pkg:rpm/redhat/openssl@3.0.7-25.el9?arch=x86_64",
+        "pkg:rpm/redhat/gnutls",
+    ];
+
+    let batch = service.analyze_purls_v3(purls, &ctx.db).await?;
+
+    // the PURL without a version only carries a warning
+    assert!(!batch["pkg:rpm/redhat/gnutls"].warnings.is_empty());
+
+    for purl in &purls[..3] {
+        let single = service.analyze_purls_v3([purl], &ctx.db).await?;
+        assert_eq!(statuses(&batch, purl), statuses(&single, purl), "{purl}");
+    }
+
+    Ok(())
+}
+
+/// The vulnerabilities and statuses of a PURL in an analysis result, sorted
+fn statuses(result: &AnalysisResponseV3, purl: &str) -> Vec<(String, String)> {
+    result
+        .get(purl)
+        .into_iter()
+        .flat_map(|entry| &entry.details)
+        .flat_map(|details| &details.purl_statuses)
+        .map(|status| {
+            (
+                status.purl_status.vulnerability.identifier.clone(),
+                status.purl_status.status.clone(),
+            )
+        })
+        .sorted()
+        .collect()
+}
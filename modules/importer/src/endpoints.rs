@@ -1,5 +1,5 @@
 use super::service::{Error, ImporterService, PatchError};
-use crate::model::{Importer, ImporterConfiguration, ImporterReport};
+use crate::model::{Importer, ImporterConfiguration, ImporterReport, ImporterStats};
 use actix_web::{
     HttpResponse, Responder, delete, get,
     guard::{self, Guard, GuardContext},
@@ -30,6 +30,7 @@ pub fn configure(
         .service(patch_json_merge)
         .service(delete)
         .service(get_reports)
+        .service(get_stats)
         .service(set_enabled)
         .service(force);
 }
@@ -59,6 +60,7 @@ async fn list(
     ),
     responses(
         (status = 201, description = "Created a new importer configuration"),
+        (status = 400, description = "The importer configuration is invalid"),
         (status = 409, description = "An importer with that name already exists")
     )
 )]
@@ -117,6 +119,7 @@ async fn read(
     ),
     responses(
         (status = 201, description = "Updated the importer configuration"),
+        (status = 400, description = "The importer configuration is invalid"),
         (status = 409, description = "The importer could not be found"),
         (status = 412, description = "The provided if-match header did not match the stored revision"),
     )
@@ -155,6 +158,7 @@ async fn update(
     ),
     responses(
         (status = 201, description = "Created a new importer configuration"),
+        (status = 400, description = "The importer configuration is invalid"),
         (status = 409, description = "The importer could not be found"),
         (status = 412, description = "The provided if-match header did not match the stored revision"),
     )
@@ -306,6 +310,27 @@ async fn get_reports(
     ))
 }
 
+#[utoipa::path(
+    tag = "importer",
+    operation_id = "getImporterStats",
+    params(
+        ("name", Path, description = "The name of the importer"),
+    ),
+    responses(
+        (status = 200, description = "Retrieved importer statistics", body = ImporterStats),
+        (status = 404, description = "The importer could not be found")
+    )
+)]
+#[get("/v3/importer/{name}/stats")]
+/// Get statistics over the runs of an importer
+async fn get_stats(
+    service: web::Data<ImporterService>,
+    name: web::Path<String>,
+    _: Require<ReadImporter>,
+) -> Result<impl Responder, Error> {
+    Ok(web::Json(service.stats(&name).await?))
+}
+
 mod guards {
     use super::*;
 
@@ -0,0 +1,43 @@
+use super::*;
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    ToSchema,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct EpssImporter {
+    #[serde(flatten)]
+    pub common: CommonImporter,
+
+    /// The base URL of the daily EPSS score files
+    #[serde(default = "default::source")]
+    pub source: String,
+}
+
+pub const DEFAULT_SOURCE_EPSS: &str = "https://epss.cyentia.com";
+
+mod default {
+    pub fn source() -> String {
+        super::DEFAULT_SOURCE_EPSS.into()
+    }
+}
+
+impl Deref for EpssImporter {
+    type Target = CommonImporter;
+
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
+
+impl DerefMut for EpssImporter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.common
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    ToSchema,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GhsaImporter {
+    #[serde(flatten)]
+    pub common: CommonImporter,
+
+    /// The base URL of the GitHub REST API
+    #[serde(default = "default::source")]
+    pub source: String,
+
+    /// The token authorizing access to the GitHub API
+    ///
+    /// Without a token, the rate limit of the API is much lower.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// The GitHub ecosystems to import advisories for, like `maven` or `npm`. Imports all
+    /// ecosystems when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ecosystems: Vec<String>,
+
+    /// Only import advisories updated since this point in time. Imports all advisories otherwise.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<String>")]
+    pub since: Option<OffsetDateTime>,
+}
+
+pub const DEFAULT_SOURCE_GHSA: &str = "https://api.github.com";
+
+mod default {
+    pub fn source() -> String {
+        super::DEFAULT_SOURCE_GHSA.into()
+    }
+}
+
+impl Deref for GhsaImporter {
+    type Target = CommonImporter;
+
+    fn deref(&self) -> &Self::Target {
+        &self.common
+    }
+}
+
+impl DerefMut for GhsaImporter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.common
+    }
+}
@@ -4,6 +4,8 @@ mod clearly_defined;
 mod csaf;
 mod cve;
 mod cwe;
+mod epss;
+mod ghsa;
 mod nvd;
 mod osv;
 mod quay;
@@ -15,6 +17,8 @@ pub use clearly_defined_curation::*;
 pub use csaf::*;
 pub use cve::*;
 pub use cwe::*;
+pub use epss::*;
+pub use ghsa::*;
 pub use nvd::*;
 pub use osv::*;
 pub use quay::*;
@@ -26,7 +30,7 @@ use std::{
     time::Duration,
 };
 use time::OffsetDateTime;
-use trustify_common::model::Revisioned;
+use trustify_common::{model::Revisioned, serde::is_default};
 use trustify_entity::{
     importer::{self, Model},
     importer_report,
@@ -186,6 +190,8 @@ pub enum ImporterConfiguration {
     ClearlyDefinedCuration(ClearlyDefinedCurationImporter),
     Cwe(CweImporter),
     Quay(QuayImporter),
+    Ghsa(GhsaImporter),
+    Epss(EpssImporter),
 }
 
 impl Deref for ImporterConfiguration {
@@ -202,6 +208,8 @@ impl Deref for ImporterConfiguration {
             Self::ClearlyDefinedCuration(importer) => &importer.common,
             Self::Cwe(importer) => &importer.common,
             Self::Quay(importer) => &importer.common,
+            Self::Ghsa(importer) => &importer.common,
+            Self::Epss(importer) => &importer.common,
         }
     }
 }
@@ -218,14 +226,22 @@ impl DerefMut for ImporterConfiguration {
             Self::ClearlyDefinedCuration(importer) => &mut importer.common,
             Self::Cwe(importer) => &mut importer.common,
             Self::Quay(importer) => &mut importer.common,
+            Self::Ghsa(importer) => &mut importer.common,
+            Self::Epss(importer) => &mut importer.common,
         }
     }
 }
 
+impl ImporterConfiguration {
+    /// Check if the importer supports [`CommonImporter::dry_run`]
+    pub fn supports_dry_run(&self) -> bool {
+        matches!(self, Self::Sbom(_) | Self::Csaf(_) | Self::Osv(_))
+    }
+}
+
 #[derive(
     Clone,
     Debug,
-    Default,
     PartialEq,
     Eq,
     serde::Serialize,
@@ -251,6 +267,63 @@ pub struct CommonImporter {
     /// Labels which will be applied to the ingested documents.
     #[serde(default, skip_serializing_if = "Labels::is_empty")]
     pub labels: Labels,
+
+    /// How often a run failing with a transient error is retried, before waiting for the next
+    /// period.
+    #[serde(
+        default = "default::max_retries",
+        skip_serializing_if = "default::is_max_retries"
+    )]
+    pub max_retries: u32,
+
+    /// The delay before the first retry, in seconds. It doubles with every further retry, up to
+    /// five minutes.
+    #[serde(
+        default = "default::retry_delay_secs",
+        skip_serializing_if = "default::is_retry_delay_secs"
+    )]
+    pub retry_delay_secs: u64,
+
+    /// Only parse and validate documents, and report what would have been ingested, without
+    /// ingesting anything. Only supported by the SBOM, CSAF and OSV importers, all other importers
+    /// reject it.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub dry_run: bool,
+}
+
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+pub const DEFAULT_RETRY_DELAY_SECS: u64 = 10;
+
+mod default {
+    pub fn max_retries() -> u32 {
+        super::DEFAULT_MAX_RETRIES
+    }
+
+    pub fn is_max_retries(value: &u32) -> bool {
+        *value == super::DEFAULT_MAX_RETRIES
+    }
+
+    pub fn retry_delay_secs() -> u64 {
+        super::DEFAULT_RETRY_DELAY_SECS
+    }
+
+    pub fn is_retry_delay_secs(value: &u64) -> bool {
+        *value == super::DEFAULT_RETRY_DELAY_SECS
+    }
+}
+
+impl Default for CommonImporter {
+    fn default() -> Self {
+        Self {
+            disabled: false,
+            period: Duration::default(),
+            description: None,
+            labels: Default::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay_secs: DEFAULT_RETRY_DELAY_SECS,
+            dry_run: false,
+        }
+    }
 }
 
 // Just here to create a schema for humantime_serde.
@@ -338,6 +411,30 @@ impl From<importer_report::Model> for ImporterReport {
     }
 }
 
+/// Statistics over the recorded runs of an importer
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImporterStats {
+    /// The number of recorded runs
+    pub total_runs: u64,
+    /// The number of runs which finished without an error
+    pub successful_runs: u64,
+    /// The number of runs which finished with an error
+    pub failed_runs: u64,
+    /// The number of documents processed, summed up over all runs
+    pub total_documents_processed: u64,
+    /// The average duration of a run, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_run_duration_secs: Option<f64>,
+    /// The start of the most recent run
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    pub last_run: Option<OffsetDateTime>,
+}
+
 /// Create the progress from the progress state
 fn into_progress(
     start: OffsetDateTime,
@@ -408,6 +505,7 @@ mod test {
                         period: Duration::from_secs(30),
                         description: None,
                         labels: Default::default(),
+                        ..Default::default()
                     },
                     source: "test".into(),
                     keys: vec![],
@@ -32,14 +32,55 @@ pub struct NvdImporter {
     /// first year with NVD data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start_year: Option<u16>,
+
+    /// Fetch CVEs from the NVD CVE API, instead of the per-year feed. When set,
+    /// `source`, `years` and `start_year` are ignored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<NvdApi>,
+}
+
+/// Access to the NVD CVE API
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    ToSchema,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdApi {
+    /// The URL of the CVE API
+    #[serde(default = "default::api_source")]
+    pub source: String,
+
+    /// The API key, raising the rate limit from 5 to 50 requests per 30 seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Only import CVEs modified since this point in time. Imports all CVEs otherwise.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<String>")]
+    pub modified_since: Option<OffsetDateTime>,
 }
 
 pub const DEFAULT_SOURCE_NVD: &str = "https://github.com/fkie-cad/nvd-json-data-feeds";
+pub const DEFAULT_SOURCE_NVD_API: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
 
 mod default {
     pub fn source() -> String {
         super::DEFAULT_SOURCE_NVD.into()
     }
+
+    pub fn api_source() -> String {
+        super::DEFAULT_SOURCE_NVD_API.into()
+    }
 }
 
 impl Deref for NvdImporter {
@@ -74,6 +74,7 @@ mod test {
                     period: Duration::from_secs(30),
                     description: None,
                     labels: Default::default(),
+                    ..Default::default()
                 },
                 source: "https://redhat.com".to_string(),
                 keys: vec![],
@@ -51,7 +51,9 @@ impl super::ImportRunner {
             ignore_missing,
         } = importer;
 
-        let report = Arc::new(Mutex::new(ReportBuilder::new()));
+        let report = Arc::new(Mutex::new(
+            ReportBuilder::new().with_dry_run(common.dry_run),
+        ));
 
         let fetcher =
             Fetcher::new(FetcherOptions::new().retries(fetch_retries.unwrap_or_default())).await?;
@@ -70,6 +72,7 @@ impl super::ImportRunner {
             context,
             ingestor,
             db: self.db.clone(),
+            dry_run: common.dry_run,
             labels: common.labels,
             report: report.clone(),
         };
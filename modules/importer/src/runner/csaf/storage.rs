@@ -14,6 +14,8 @@ pub struct StorageVisitor<C: RunContext> {
     pub context: C,
     pub ingestor: IngestorService,
     pub db: ReadWrite,
+    /// only check documents, don't ingest them
+    pub dry_run: bool,
     /// the report to report our messages to
     pub report: Arc<Mutex<ReportBuilder>>,
     pub labels: Labels,
@@ -36,6 +38,17 @@ impl<C: RunContext, S: Source> ValidatedVisitor<S> for StorageVisitor<C> {
         let location = doc.context.url().to_string();
         let file = doc.possibly_relative_url();
 
+        if self.dry_run {
+            let outcome = self
+                .ingestor
+                .dry_run(&doc.data, Format::CSAF, &self.db)
+                .await
+                .map_err(StorageError::Storage)?;
+            self.report.lock().add_dry_run(outcome);
+
+            return self.context.check_canceled(|| StorageError::Canceled).await;
+        }
+
         self.db
             .transaction(async |tx| {
                 self.ingestor
@@ -0,0 +1,313 @@
+//! Import EPSS scores from FIRST's daily CSV feed.
+
+use crate::{
+    model::EpssImporter,
+    runner::{
+        RunOutput,
+        context::RunContext,
+        progress::Progress,
+        report::{Phase, ReportBuilder, ScannerError},
+    },
+};
+use bytes::Bytes;
+use reqwest::StatusCode;
+use sea_orm::{ActiveValue::Set, EntityTrait};
+use sea_query::OnConflict;
+use std::{io::Read, time::Duration};
+use time::{Date, OffsetDateTime, macros::format_description};
+use tracing::instrument;
+use trustify_common::db::chunk::EntityChunkedIter;
+use trustify_entity::epss_score;
+
+/// The number of days to go back when looking for the most recent score file.
+///
+/// The file of the current day is only published some time after midnight (UTC).
+const MAX_DAYS_BACK: usize = 2;
+
+/// The time downloading a single score file may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+time::serde::format_description!(iso_date, Date, "[year]-[month]-[day]");
+
+/// The state of the EPSS import, stored as continuation
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpssState {
+    /// The date of the most recently imported scores
+    #[serde(with = "iso_date")]
+    pub scored_date: Date,
+}
+
+/// A row of the score file
+#[derive(Debug, serde::Deserialize)]
+struct Record {
+    cve: String,
+    epss: f64,
+    percentile: f64,
+}
+
+impl super::ImportRunner {
+    #[instrument(skip(self, context), err(level=tracing::Level::INFO))]
+    pub async fn run_once_epss(
+        &self,
+        context: impl RunContext + 'static,
+        epss: EpssImporter,
+        continuation: serde_json::Value,
+    ) -> Result<RunOutput, ScannerError> {
+        let mut report = ReportBuilder::new();
+
+        let state = serde_json::from_value::<Option<EpssState>>(continuation)
+            .ok()
+            .flatten();
+
+        let client = reqwest::Client::builder()
+            .user_agent("trustify-epss-importer")
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .map_err(|err| ScannerError::Critical(err.into()))?;
+
+        let progress = context.progress(format!("Import EPSS: {}", epss.source));
+
+        let Some((scored_date, data)) =
+            fetch_latest(&client, &epss.source)
+                .await
+                .map_err(|err| ScannerError::Normal {
+                    err,
+                    output: RunOutput {
+                        report: report.clone().build(),
+                        continuation: state.as_ref().and_then(|s| serde_json::to_value(s).ok()),
+                    },
+                })?
+        else {
+            return Err(ScannerError::Normal {
+                err: anyhow::anyhow!("no recent EPSS score file found at {}", epss.source),
+                output: RunOutput {
+                    report: report.build(),
+                    continuation: state.and_then(|s| serde_json::to_value(s).ok()),
+                },
+            });
+        };
+
+        if let Some(state) = &state
+            && state.scored_date >= scored_date
+        {
+            tracing::info!("EPSS scores of {scored_date} are already imported");
+            return Ok(RunOutput {
+                report: report.build(),
+                continuation: serde_json::to_value(state).ok(),
+            });
+        }
+
+        let records = tokio::task::spawn_blocking(move || parse(&*data))
+            .await
+            .map_err(|err| ScannerError::Critical(err.into()))?
+            .map_err(|err| ScannerError::Normal {
+                err,
+                output: RunOutput {
+                    report: report.clone().build(),
+                    continuation: state.as_ref().and_then(|s| serde_json::to_value(s).ok()),
+                },
+            })?;
+
+        progress
+            .message(format!("EPSS: {} scores of {scored_date}", records.len()))
+            .await;
+
+        let models = records.into_iter().map(|record| epss_score::ActiveModel {
+            vulnerability_id: Set(record.cve),
+            score: Set(record.epss),
+            percentile: Set(record.percentile),
+            scored_date: Set(scored_date),
+        });
+
+        let result = self
+            .db
+            .transaction(async |tx| {
+                let mut count = 0;
+
+                for batch in &models.chunked() {
+                    let batch = batch.collect::<Vec<_>>();
+                    count += batch.len();
+
+                    epss_score::Entity::insert_many(batch)
+                        .on_conflict(
+                            OnConflict::column(epss_score::Column::VulnerabilityId)
+                                .update_columns([
+                                    epss_score::Column::Score,
+                                    epss_score::Column::Percentile,
+                                    epss_score::Column::ScoredDate,
+                                ])
+                                .to_owned(),
+                        )
+                        .exec_without_returning(tx)
+                        .await?;
+                }
+
+                Ok::<_, sea_orm::DbErr>(count)
+            })
+            .await;
+
+        match result {
+            Ok(count) => {
+                report.add_items(count);
+                Ok(RunOutput {
+                    report: report.build(),
+                    continuation: serde_json::to_value(EpssState { scored_date }).ok(),
+                })
+            }
+            Err(err) => {
+                report.add_error(Phase::Upload, scored_date.to_string(), err.to_string());
+                Err(ScannerError::Normal {
+                    err: err.into(),
+                    output: RunOutput {
+                        report: report.build(),
+                        continuation: state.and_then(|s| serde_json::to_value(s).ok()),
+                    },
+                })
+            }
+        }
+    }
+}
+
+/// Fetch the most recent score file, starting with the one of the current day
+async fn fetch_latest(
+    client: &reqwest::Client,
+    source: &str,
+) -> anyhow::Result<Option<(Date, Bytes)>> {
+    let mut date = OffsetDateTime::now_utc().date();
+
+    for _ in 0..=MAX_DAYS_BACK {
+        let url = file_url(source, date)?;
+        let response = client.get(&url).send().await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            tracing::debug!("No EPSS scores at {url}");
+        } else {
+            return Ok(Some((date, response.error_for_status()?.bytes().await?)));
+        }
+
+        let Some(previous) = date.previous_day() else {
+            break;
+        };
+        date = previous;
+    }
+
+    Ok(None)
+}
+
+/// The URL of the score file of a day
+fn file_url(source: &str, date: Date) -> anyhow::Result<String> {
+    let date = date.format(format_description!("[year]-[month]-[day]"))?;
+    Ok(format!(
+        "{}/epss_scores-{date}.csv.gz",
+        source.trim_end_matches('/')
+    ))
+}
+
+/// Decompress and parse a score file
+///
+/// The file starts with a comment line, carrying the model version and score date, followed by
+/// the `cve,epss,percentile` header. The records are read from the decompressed stream one by
+/// one, without holding the decompressed file in memory.
+fn parse(data: impl Read) -> anyhow::Result<Vec<Record>> {
+    Ok(csv::ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .from_reader(flate2::read::GzDecoder::new(data))
+        .deserialize()
+        .collect::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::ImportRunner;
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+    use test_context::test_context;
+    use test_log::test;
+    use time::macros::date;
+    use trustify_common::db::ReadWrite;
+    use trustify_test_context::TrustifyContext;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path_regex},
+    };
+
+    const SCORES: &str = r#"#model_version:v2025.03.14,score_date:2025-06-01T12:55:00Z
+cve,epss,percentile
+CVE-2021-44228,0.94358,0.99961
+CVE-2099-1000,0.00043,0.11801
+"#;
+
+    fn gzip(data: &str) -> anyhow::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data.as_bytes())?;
+        Ok(encoder.finish()?)
+    }
+
+    #[test]
+    fn url() -> anyhow::Result<()> {
+        assert_eq!(
+            file_url("https://epss.cyentia.com/", date!(2025 - 06 - 01))?,
+            "https://epss.cyentia.com/epss_scores-2025-06-01.csv.gz"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_scores() -> anyhow::Result<()> {
+        let records = parse(&*gzip(SCORES)?)?;
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].cve, "CVE-2021-44228");
+        assert_eq!(records[0].epss, 0.94358);
+        assert_eq!(records[0].percentile, 0.99961);
+
+        Ok(())
+    }
+
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn run_mock_epss(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let epss = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/epss_scores-\d{4}-\d{2}-\d{2}\.csv\.gz$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(gzip(SCORES)?))
+            .mount(&epss)
+            .await;
+
+        let runner = ImportRunner {
+            db: ReadWrite::new(ctx.db.clone()),
+            storage: ctx.storage.clone().into(),
+            working_dir: None,
+            analysis: None,
+        };
+        let importer = EpssImporter {
+            common: Default::default(),
+            source: epss.uri(),
+        };
+
+        let output = runner
+            .run_once_epss((), importer.clone(), serde_json::Value::Null)
+            .await?;
+
+        assert_eq!(output.report.number_of_items, 2);
+
+        let score = epss_score::Entity::find_by_id("CVE-2021-44228")
+            .one(&ctx.db)
+            .await?
+            .expect("must be imported");
+        assert_eq!(score.score, 0.94358);
+        assert_eq!(score.percentile, 0.99961);
+
+        // the same day won't be imported twice
+        let output = runner
+            .run_once_epss((), importer, output.continuation.expect("continuation"))
+            .await?;
+
+        assert_eq!(output.report.number_of_items, 0);
+
+        Ok(())
+    }
+}
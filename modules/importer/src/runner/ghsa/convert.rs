@@ -0,0 +1,318 @@
+//! Convert GitHub security advisories into OSV documents.
+
+use osv::schema::Vulnerability;
+use serde_json::{Value, json};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+/// An advisory, as returned by the GitHub REST API
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaAdvisory {
+    pub ghsa_id: String,
+    #[serde(default)]
+    pub cve_id: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub identifiers: Option<Vec<GhsaIdentifier>>,
+    #[serde(default)]
+    pub references: Option<Vec<String>>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub published_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub withdrawn_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub vulnerabilities: Option<Vec<GhsaVulnerability>>,
+    #[serde(default)]
+    pub cvss: Option<GhsaCvss>,
+    #[serde(default)]
+    pub cvss_severities: Option<GhsaCvssSeverities>,
+    #[serde(default)]
+    pub cwes: Option<Vec<GhsaCwe>>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaIdentifier {
+    pub r#type: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaVulnerability {
+    pub package: Option<GhsaPackage>,
+    #[serde(default)]
+    pub vulnerable_version_range: Option<String>,
+    #[serde(default)]
+    pub first_patched_version: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaPackage {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaCvss {
+    #[serde(default)]
+    pub vector_string: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaCvssSeverities {
+    #[serde(default)]
+    pub cvss_v3: Option<GhsaCvss>,
+    #[serde(default)]
+    pub cvss_v4: Option<GhsaCvss>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct GhsaCwe {
+    pub cwe_id: String,
+}
+
+/// Map a GitHub ecosystem to its OSV counterpart
+///
+/// Returns `None` for ecosystems OSV has no equivalent for, like `other`.
+pub fn ecosystem(ecosystem: &str) -> Option<&'static str> {
+    Some(match ecosystem {
+        "actions" => "GitHub Actions",
+        "composer" => "Packagist",
+        "erlang" => "Hex",
+        "go" => "Go",
+        "maven" => "Maven",
+        "npm" => "npm",
+        "nuget" => "NuGet",
+        "pip" => "PyPI",
+        "pub" => "Pub",
+        "rubygems" => "RubyGems",
+        "rust" => "crates.io",
+        "swift" => "SwiftURL",
+        _ => return None,
+    })
+}
+
+/// Translate a GitHub version range, like `>= 1.0, < 1.2`, into OSV events
+///
+/// A range without lower bound starts at `0`. A range without upper bound uses the first patched
+/// version, if there is one.
+fn events(range: Option<&str>, first_patched: Option<&str>) -> Vec<Value> {
+    let mut introduced = None;
+    let mut upper = None;
+
+    for constraint in range.into_iter().flat_map(|range| range.split(',')) {
+        let constraint = constraint.trim();
+        // longer operators first, so that `>=` isn't taken for `>`
+        let Some((op, version)) = [">=", "<=", ">", "<", "="]
+            .into_iter()
+            .find_map(|op| Some((op, constraint.strip_prefix(op)?.trim())))
+        else {
+            continue;
+        };
+
+        match op {
+            // OSV has no exclusive lower bound, the closest is starting with that version
+            ">=" | ">" => introduced = Some(version),
+            "<" => upper = Some(json!({"fixed": version})),
+            "<=" => upper = Some(json!({"last_affected": version})),
+            _ => {
+                introduced = Some(version);
+                upper = Some(json!({"last_affected": version}));
+            }
+        }
+    }
+
+    let upper = upper.or_else(|| first_patched.map(|version| json!({"fixed": version})));
+
+    let mut result = vec![json!({"introduced": introduced.unwrap_or("0")})];
+    result.extend(upper);
+    result
+}
+
+fn timestamp(value: OffsetDateTime) -> anyhow::Result<String> {
+    Ok(value.format(&Rfc3339)?)
+}
+
+impl GhsaAdvisory {
+    /// Convert into an OSV document
+    pub fn into_osv(self) -> anyhow::Result<Vulnerability> {
+        let mut aliases = Vec::new();
+        for alias in self.cve_id.iter().cloned().chain(
+            self.identifiers
+                .iter()
+                .flatten()
+                .filter(|id| id.r#type == "CVE")
+                .map(|id| id.value.clone()),
+        ) {
+            if alias != self.ghsa_id && !aliases.contains(&alias) {
+                aliases.push(alias);
+            }
+        }
+
+        let affected = self
+            .vulnerabilities
+            .iter()
+            .flatten()
+            .filter_map(|vuln| {
+                let package = vuln.package.as_ref()?;
+                let ecosystem = ecosystem(&package.ecosystem)?;
+                Some(json!({
+                    "package": {
+                        "ecosystem": ecosystem,
+                        "name": package.name,
+                    },
+                    "ranges": [{
+                        "type": "ECOSYSTEM",
+                        "events": events(
+                            vuln.vulnerable_version_range.as_deref(),
+                            vuln.first_patched_version.as_deref(),
+                        ),
+                    }],
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        let mut severity = Vec::new();
+        let severities = self.cvss_severities.as_ref();
+        if let Some(vector) = severities
+            .and_then(|s| s.cvss_v3.as_ref())
+            .or(self.cvss.as_ref())
+            .and_then(|cvss| cvss.vector_string.as_ref())
+        {
+            severity.push(json!({"type": "CVSS_V3", "score": vector}));
+        }
+        if let Some(vector) = severities
+            .and_then(|s| s.cvss_v4.as_ref())
+            .and_then(|cvss| cvss.vector_string.as_ref())
+        {
+            severity.push(json!({"type": "CVSS_V4", "score": vector}));
+        }
+
+        let mut references = Vec::new();
+        if let Some(url) = &self.html_url {
+            references.push(json!({"type": "ADVISORY", "url": url}));
+        }
+        for url in self.references.iter().flatten() {
+            if Some(url) != self.html_url.as_ref() {
+                references.push(json!({"type": "WEB", "url": url}));
+            }
+        }
+
+        let mut osv = json!({
+            "id": self.ghsa_id,
+            "modified": timestamp(self.updated_at)?,
+            "aliases": aliases,
+            "affected": affected,
+            "severity": severity,
+            "references": references,
+            "database_specific": {
+                "severity": self.severity.map(|s| s.to_uppercase()),
+                "cwe_ids": self.cwes.iter().flatten().map(|cwe| &cwe.cwe_id).collect::<Vec<_>>(),
+                "github_reviewed": true,
+            },
+        });
+
+        let fields = [
+            ("published", self.published_at.map(timestamp).transpose()?),
+            ("withdrawn", self.withdrawn_at.map(timestamp).transpose()?),
+            ("summary", self.summary),
+            ("details", self.description),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                osv[name] = value.into();
+            }
+        }
+
+        Ok(serde_json::from_value(osv)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn version_ranges() {
+        assert_eq!(
+            events(Some(">= 1.0, < 1.2"), Some("1.2")),
+            vec![json!({"introduced": "1.0"}), json!({"fixed": "1.2"})]
+        );
+        assert_eq!(
+            events(Some("<= 2.3.4"), None),
+            vec![
+                json!({"introduced": "0"}),
+                json!({"last_affected": "2.3.4"})
+            ]
+        );
+        assert_eq!(
+            events(Some("= 0.9"), None),
+            vec![
+                json!({"introduced": "0.9"}),
+                json!({"last_affected": "0.9"})
+            ]
+        );
+        assert_eq!(
+            events(Some(">= 3"), Some("3.1")),
+            vec![json!({"introduced": "3"}), json!({"fixed": "3.1"})]
+        );
+        assert_eq!(events(None, None), vec![json!({"introduced": "0"})]);
+    }
+
+    #[test]
+    fn convert() -> anyhow::Result<()> {
+        let advisory: GhsaAdvisory = serde_json::from_value(json!({
+            "ghsa_id": "GHSA-aaaa-bbbb-cccc",
+            "cve_id": "CVE-2099-0001",
+            "html_url": "https://github.com/advisories/GHSA-aaaa-bbbb-cccc",
+            "summary": "Something is wrong",
+            "description": "Something is really wrong",
+            "severity": "high",
+            "identifiers": [
+                {"type": "GHSA", "value": "GHSA-aaaa-bbbb-cccc"},
+                {"type": "CVE", "value": "CVE-2099-0001"}
+            ],
+            "references": ["https://example.com/fix"],
+            "published_at": "2099-01-01T00:00:00Z",
+            "updated_at": "2099-01-02T00:00:00Z",
+            "withdrawn_at": null,
+            "vulnerabilities": [
+                {
+                    "package": {"ecosystem": "maven", "name": "org.example:foo"},
+                    "vulnerable_version_range": ">= 1.0, < 1.2",
+                    "first_patched_version": "1.2"
+                },
+                {
+                    "package": {"ecosystem": "other", "name": "foo"},
+                    "vulnerable_version_range": "< 1",
+                    "first_patched_version": null
+                }
+            ],
+            "cvss_severities": {
+                "cvss_v3": {
+                    "vector_string": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                    "score": 9.8
+                },
+                "cvss_v4": {"vector_string": null, "score": null}
+            },
+            "cwes": [{"cwe_id": "CWE-79", "name": "XSS"}]
+        }))?;
+
+        let osv = advisory.into_osv()?;
+
+        assert_eq!(osv.id, "GHSA-aaaa-bbbb-cccc");
+        assert_eq!(osv.aliases, Some(vec!["CVE-2099-0001".to_string()]));
+        assert_eq!(osv.affected.map(|a| a.len()), Some(1));
+        assert_eq!(osv.severity.map(|s| s.len()), Some(1));
+        assert_eq!(osv.references.map(|r| r.len()), Some(2));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+mod convert;
+
+use crate::{
+    model::GhsaImporter,
+    runner::{
+        RunOutput,
+        context::RunContext,
+        progress::{Progress, ProgressInstance},
+        report::{Phase, ReportBuilder, ScannerError},
+    },
+};
+use convert::GhsaAdvisory;
+use reqwest::{
+    StatusCode,
+    header::{self, HeaderMap},
+};
+use std::{collections::BTreeMap, time::Duration};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::instrument;
+use trustify_entity::labels::Labels;
+use trustify_module_ingestor::{
+    graph::Graph,
+    service::{Cache, Format, IngestorService},
+};
+
+/// The number of advisories requested per page, the maximum the API allows.
+const PAGE_SIZE: &str = "100";
+
+/// How often a request is retried after hitting the rate limit.
+const MAX_RATE_LIMIT_RETRIES: usize = 3;
+
+/// The longest time to wait for the rate limit to reset. GitHub resets it every hour.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// The key of the continuation cursor when importing all ecosystems.
+const ALL_ECOSYSTEMS: &str = "*";
+
+impl super::ImportRunner {
+    #[instrument(skip(self, context), err(level=tracing::Level::INFO))]
+    pub async fn run_once_ghsa(
+        &self,
+        context: impl RunContext + 'static,
+        ghsa: GhsaImporter,
+        continuation: serde_json::Value,
+    ) -> Result<RunOutput, ScannerError> {
+        let ingestor =
+            IngestorService::new(Graph::new(), self.storage.clone(), self.analysis.clone());
+
+        let mut report = ReportBuilder::new();
+
+        // Per-ecosystem `updated_at` of the most recently updated advisory ingested. The next run
+        // continues from there.
+        let mut state: BTreeMap<String, String> =
+            serde_json::from_value(continuation).unwrap_or_default();
+
+        let client = reqwest::Client::builder()
+            .user_agent("trustify-ghsa-importer")
+            .build()
+            .map_err(|err| ScannerError::Critical(err.into()))?;
+
+        let ecosystems = match ghsa.ecosystems.is_empty() {
+            true => vec![None],
+            false => ghsa.ecosystems.iter().map(|e| Some(e.as_str())).collect(),
+        };
+
+        let progress = context.progress(format!("Import GHSA: {}", ghsa.source));
+        let mut instance = progress.start(ecosystems.len());
+
+        for ecosystem in ecosystems {
+            if context.is_canceled().await {
+                break;
+            }
+
+            let key = ecosystem.unwrap_or(ALL_ECOSYSTEMS).to_string();
+            progress.message(format!("GHSA {key}")).await;
+
+            if let Err(err) = self
+                .ingest_ecosystem(
+                    &context,
+                    &client,
+                    &ingestor,
+                    &ghsa,
+                    ecosystem,
+                    &mut state,
+                    &mut report,
+                )
+                .await
+            {
+                // Record and continue: the cursor only covers what was ingested, so the next run
+                // picks up from there.
+                tracing::warn!("Failed to process GHSA ecosystem {key}: {err}");
+                report.add_error(Phase::Retrieval, key, err.to_string());
+            }
+
+            instance.tick().await;
+        }
+
+        instance.finish().await;
+
+        Ok(RunOutput {
+            report: report.build(),
+            continuation: serde_json::to_value(state).ok(),
+        })
+    }
+
+    /// Page through all advisories of an ecosystem, updated since the last run
+    #[allow(clippy::too_many_arguments)]
+    async fn ingest_ecosystem(
+        &self,
+        context: &impl RunContext,
+        client: &reqwest::Client,
+        ingestor: &IngestorService,
+        ghsa: &GhsaImporter,
+        ecosystem: Option<&str>,
+        state: &mut BTreeMap<String, String>,
+        report: &mut ReportBuilder,
+    ) -> anyhow::Result<()> {
+        let key = ecosystem.unwrap_or(ALL_ECOSYSTEMS).to_string();
+
+        let since = match state.get(&key) {
+            Some(cursor) => Some(OffsetDateTime::parse(cursor, &Rfc3339)?),
+            None => ghsa.since,
+        };
+
+        let mut query = vec![
+            ("type", "reviewed".to_string()),
+            ("sort", "updated".to_string()),
+            ("direction", "asc".to_string()),
+            ("per_page", PAGE_SIZE.to_string()),
+        ];
+        if let Some(ecosystem) = ecosystem {
+            query.push(("ecosystem", ecosystem.to_string()));
+        }
+        if let Some(since) = since {
+            query.push(("updated", format!(">={}", since.format(&Rfc3339)?)));
+        }
+
+        let mut url = reqwest::Url::parse_with_params(
+            &format!("{}/advisories", ghsa.source.trim_end_matches('/')),
+            &query,
+        )?;
+
+        loop {
+            if context.is_canceled().await {
+                break;
+            }
+
+            let (advisories, next) = fetch_page(client, url, ghsa.token.as_deref()).await?;
+
+            let mut cursor = None;
+            for advisory in advisories {
+                let updated = advisory.updated_at;
+                let id = advisory.ghsa_id.clone();
+
+                match self
+                    .ingest_advisory(context, ingestor, ghsa, advisory)
+                    .await
+                {
+                    Ok(()) => report.tick(),
+                    Err(err) => report.add_error(Phase::Upload, id, err.to_string()),
+                }
+
+                cursor = cursor.max(Some(updated));
+            }
+
+            if let Some(cursor) = cursor {
+                state.insert(key.clone(), cursor.format(&Rfc3339)?);
+            }
+
+            match next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn ingest_advisory(
+        &self,
+        context: &impl RunContext,
+        ingestor: &IngestorService,
+        ghsa: &GhsaImporter,
+        advisory: GhsaAdvisory,
+    ) -> anyhow::Result<()> {
+        let osv = advisory.into_osv()?;
+        let data = serde_json::to_vec(&osv)?;
+        let labels = Labels::new()
+            .add("source", &ghsa.source)
+            .add("importer", context.name())
+            .add("file", &osv.id)
+            .extend(ghsa.labels.0.clone());
+
+        self.db
+            .transaction(async |tx| {
+                ingestor
+                    .ingest(&data, Format::OSV, labels.clone(), None, Cache::Skip, tx)
+                    .await
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Fetch a page of advisories, returning the URL of the next page, if there is one
+///
+/// Waits for the rate limit to reset when it got exhausted.
+async fn fetch_page(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+    token: Option<&str>,
+) -> anyhow::Result<(Vec<GhsaAdvisory>, Option<reqwest::Url>)> {
+    let mut retries = 0;
+
+    loop {
+        let mut request = client
+            .get(url.clone())
+            .header(header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        let wait = rate_limit_wait(response.headers(), OffsetDateTime::now_utc());
+
+        if matches!(
+            response.status(),
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+        ) && let Some(wait) = wait
+            && retries < MAX_RATE_LIMIT_RETRIES
+        {
+            retries += 1;
+            tracing::info!("GHSA rate limit exceeded, waiting {}s", wait.as_secs());
+            tokio::time::sleep(wait).await;
+            continue;
+        }
+
+        let response = response.error_for_status()?;
+        let next = next_link(response.headers());
+        let advisories = response.json().await?;
+
+        // don't run into the limit with the next request
+        if let Some(wait) = wait
+            && next.is_some()
+        {
+            tracing::info!("GHSA rate limit exhausted, waiting {}s", wait.as_secs());
+            tokio::time::sleep(wait).await;
+        }
+
+        return Ok((advisories, next));
+    }
+}
+
+/// The time to wait before sending the next request, if the rate limit is exhausted
+fn rate_limit_wait(headers: &HeaderMap, now: OffsetDateTime) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<i64>().ok())
+    };
+
+    let wait = if let Some(seconds) = header("retry-after") {
+        seconds
+    } else if header("x-ratelimit-remaining") == Some(0) {
+        header("x-ratelimit-reset")? - now.unix_timestamp()
+    } else {
+        return None;
+    };
+
+    // wait at least a second, as the reset time is rounded down
+    Some(Duration::from_secs(wait.max(1) as u64).min(MAX_RATE_LIMIT_WAIT))
+}
+
+/// The URL of the next page, from the `Link` header
+fn next_link(headers: &HeaderMap) -> Option<reqwest::Url> {
+    headers
+        .get_all(header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let (url, params) = link.trim().split_once(';')?;
+            params
+                .split(';')
+                .any(|param| param.trim() == r#"rel="next""#)
+                .then(|| url.trim().trim_start_matches('<').trim_end_matches('>'))
+        })
+        .and_then(|url| reqwest::Url::parse(url).ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::ImportRunner;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+    use test_context::test_context;
+    use test_log::test;
+    use trustify_common::db::ReadWrite;
+    use trustify_test_context::TrustifyContext;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path, query_param},
+    };
+
+    fn importer(source: &str) -> GhsaImporter {
+        GhsaImporter {
+            common: Default::default(),
+            source: source.to_string(),
+            token: Some("secret".into()),
+            ecosystems: vec!["maven".into()],
+            since: None,
+        }
+    }
+
+    #[test]
+    fn parse_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::LINK,
+            HeaderValue::from_static(
+                r#"<https://api.github.com/advisories?after=abc>; rel="next", <https://api.github.com/advisories?before=def>; rel="prev""#,
+            ),
+        );
+
+        assert_eq!(
+            next_link(&headers).map(|url| url.to_string()),
+            Some("https://api.github.com/advisories?after=abc".to_string())
+        );
+        assert_eq!(next_link(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let now = OffsetDateTime::from_unix_timestamp(1_000).expect("valid timestamp");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("10"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1060"));
+        assert_eq!(rate_limit_wait(&headers, now), None);
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        assert_eq!(
+            rate_limit_wait(&headers, now),
+            Some(Duration::from_secs(60))
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("5"));
+        assert_eq!(rate_limit_wait(&headers, now), Some(Duration::from_secs(5)));
+    }
+
+    fn advisory(id: &str, updated: &str) -> serde_json::Value {
+        json!({
+            "ghsa_id": id,
+            "cve_id": null,
+            "html_url": format!("https://github.com/advisories/{id}"),
+            "summary": "An issue",
+            "description": "An issue in a package",
+            "severity": "medium",
+            "published_at": "2099-01-01T00:00:00Z",
+            "updated_at": updated,
+            "vulnerabilities": [{
+                "package": {"ecosystem": "maven", "name": "org.example:foo"},
+                "vulnerable_version_range": "< 1.2",
+                "first_patched_version": "1.2"
+            }]
+        })
+    }
+
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn run_mock_ghsa(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let github = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/advisories"))
+            .and(query_param("ecosystem", "maven"))
+            .and(query_param("after", "page2"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([advisory(
+                "GHSA-2222-2222-2222",
+                "2099-01-03T00:00:00Z"
+            )])))
+            .mount(&github)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/advisories"))
+            .and(query_param("ecosystem", "maven"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        format!(
+                            r#"<{}/advisories?ecosystem=maven&after=page2>; rel="next""#,
+                            github.uri()
+                        )
+                        .as_str(),
+                    )
+                    .set_body_json(json!([advisory(
+                        "GHSA-1111-1111-1111",
+                        "2099-01-02T00:00:00Z"
+                    )])),
+            )
+            .up_to_n_times(1)
+            .mount(&github)
+            .await;
+
+        let runner = ImportRunner {
+            db: ReadWrite::new(ctx.db.clone()),
+            storage: ctx.storage.clone().into(),
+            working_dir: None,
+            analysis: None,
+        };
+
+        let output = runner
+            .run_once_ghsa((), importer(&github.uri()), serde_json::Value::Null)
+            .await?;
+
+        assert_eq!(output.report.number_of_items, 2);
+        assert!(
+            output.report.messages.is_empty(),
+            "{:?}",
+            output.report.messages
+        );
+
+        let state: BTreeMap<String, String> =
+            serde_json::from_value(output.continuation.expect("continuation state"))?;
+        assert_eq!(
+            state.get("maven").map(String::as_str),
+            Some("2099-01-03T00:00:00Z")
+        );
+
+        Ok(())
+    }
+}
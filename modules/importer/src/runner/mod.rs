@@ -6,6 +6,8 @@ pub mod context;
 pub mod csaf;
 pub mod cve;
 pub mod cwe;
+pub mod epss;
+pub mod ghsa;
 pub mod nvd;
 pub mod osv;
 pub mod progress;
@@ -18,6 +20,7 @@ use crate::{
     runner::{context::RunContext, report::ScannerError},
     server::RunOutput,
 };
+use anyhow::anyhow;
 use std::path::PathBuf;
 use time::OffsetDateTime;
 use tracing::instrument;
@@ -42,6 +45,13 @@ impl ImportRunner {
         last_success: Option<OffsetDateTime>,
         continuation: serde_json::Value,
     ) -> Result<RunOutput, ScannerError> {
+        // importers stored before rejecting this would otherwise ingest for real
+        if configuration.dry_run && !configuration.supports_dry_run() {
+            return Err(ScannerError::Critical(anyhow!(
+                "dry runs are not supported by this importer"
+            )));
+        }
+
         let last_success = last_success.map(|t| t.into());
 
         match configuration {
@@ -68,6 +78,12 @@ impl ImportRunner {
             ImporterConfiguration::Quay(quay) => {
                 self.run_once_quay(context, quay, continuation).await
             }
+            ImporterConfiguration::Ghsa(ghsa) => {
+                self.run_once_ghsa(context, ghsa, continuation).await
+            }
+            ImporterConfiguration::Epss(epss) => {
+                self.run_once_epss(context, epss, continuation).await
+            }
         }
     }
 
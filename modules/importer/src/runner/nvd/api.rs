@@ -0,0 +1,384 @@
+//! Import CVEs from the NVD CVE API.
+
+use crate::{
+    model::{NvdApi, NvdImporter},
+    runner::{
+        RunOutput,
+        context::RunContext,
+        progress::Progress,
+        report::{Phase, ReportBuilder, ScannerError},
+    },
+};
+use serde_json::value::RawValue;
+use std::{collections::VecDeque, time::Duration};
+use time::{OffsetDateTime, UtcOffset, macros::format_description};
+use tokio::time::Instant;
+use trustify_entity::labels::Labels;
+use trustify_module_ingestor::service::{Cache, Format, IngestorService};
+
+/// The number of CVEs requested per page, the maximum the API allows.
+const RESULTS_PER_PAGE: usize = 2000;
+
+/// The longest modification date range the API accepts in a single query.
+const MAX_WINDOW: time::Duration = time::Duration::days(120);
+
+/// The window the API rate limit applies to.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(30);
+/// The number of requests per window, without an API key.
+const RATE_LIMIT: usize = 5;
+/// The number of requests per window, with an API key.
+const RATE_LIMIT_API_KEY: usize = 50;
+
+/// The progress of an API import, stored as continuation
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiState {
+    /// Start of the modification window being imported. `None` for importing all CVEs.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub since: Option<OffsetDateTime>,
+    /// Index of the next result to fetch
+    pub start_index: usize,
+    /// Number of results, as reported by the last page
+    pub total_results: usize,
+}
+
+/// A page of results of the CVE API
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPage {
+    start_index: usize,
+    total_results: usize,
+    #[serde(default)]
+    vulnerabilities: Vec<ApiItem>,
+}
+
+/// A single result, wrapping the bare NVD `cve` object, which is kept raw and handed straight
+/// to the ingestor
+#[derive(Debug, serde::Deserialize)]
+struct ApiItem {
+    cve: Box<RawValue>,
+}
+
+#[derive(serde::Deserialize)]
+struct CveId {
+    id: String,
+}
+
+/// Limits the number of requests sent within a time window
+pub struct RateLimiter {
+    limit: usize,
+    window: Duration,
+    requests: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            requests: VecDeque::with_capacity(limit),
+        }
+    }
+
+    /// Wait until another request may be sent
+    pub async fn acquire(&mut self) {
+        let now = Instant::now();
+        while let Some(first) = self.requests.front()
+            && now.duration_since(*first) >= self.window
+        {
+            self.requests.pop_front();
+        }
+
+        if self.requests.len() >= self.limit
+            && let Some(first) = self.requests.pop_front()
+        {
+            tokio::time::sleep_until(first + self.window).await;
+        }
+
+        self.requests.push_back(Instant::now());
+    }
+}
+
+impl crate::runner::ImportRunner {
+    pub(super) async fn run_once_nvd_api(
+        &self,
+        context: impl RunContext + 'static,
+        nvd: NvdImporter,
+        api: NvdApi,
+        ingestor: IngestorService,
+        continuation: serde_json::Value,
+    ) -> Result<RunOutput, ScannerError> {
+        // the end of all windows, and the start of the next run
+        let start = OffsetDateTime::now_utc();
+
+        let mut report = ReportBuilder::new();
+
+        // Resume an interrupted run, or start with the configured point in time. The state of
+        // the feed import doesn't parse, and starts over as well.
+        let mut state = serde_json::from_value::<Option<ApiState>>(continuation)
+            .ok()
+            .flatten()
+            .unwrap_or(ApiState {
+                since: api.modified_since,
+                start_index: 0,
+                total_results: 0,
+            });
+
+        let client = reqwest::Client::builder()
+            .user_agent("trustify-nvd-importer")
+            .build()
+            .map_err(|err| ScannerError::Critical(err.into()))?;
+
+        let mut limiter = RateLimiter::new(
+            match api.api_key {
+                Some(_) => RATE_LIMIT_API_KEY,
+                None => RATE_LIMIT,
+            },
+            RATE_LIMIT_WINDOW,
+        );
+
+        let labels = Labels::new()
+            .add("source", "nvd")
+            .add("importer", context.name())
+            .extend(nvd.common.labels.0.clone());
+
+        let progress = context.progress(format!("Import NVD API: {}", api.source));
+
+        loop {
+            if context.is_canceled().await {
+                break;
+            }
+
+            let end = state.since.map(|since| (since + MAX_WINDOW).min(start));
+
+            limiter.acquire().await;
+            let page = match fetch_page(&client, &api, &state, end).await {
+                Ok(page) => page,
+                Err(err) => {
+                    // keep the state, so that the next run continues from here
+                    return Err(ScannerError::Normal {
+                        err,
+                        output: RunOutput {
+                            report: report.build(),
+                            continuation: serde_json::to_value(&state).ok(),
+                        },
+                    });
+                }
+            };
+
+            for item in &page.vulnerabilities {
+                let data = item.cve.get().as_bytes();
+                let file = serde_json::from_slice::<CveId>(data)
+                    .map(|cve| cve.id)
+                    .unwrap_or_default();
+
+                let result = self
+                    .db
+                    .transaction(async |tx| {
+                        ingestor
+                            .ingest(
+                                data,
+                                Format::NVD,
+                                labels.clone().add("file", &file),
+                                None,
+                                Cache::Skip,
+                                tx,
+                            )
+                            .await
+                    })
+                    .await;
+
+                match result {
+                    Ok(_) => report.tick(),
+                    Err(err) => report.add_error(Phase::Upload, file, err.to_string()),
+                }
+            }
+
+            state.start_index = page.start_index + page.vulnerabilities.len();
+            state.total_results = page.total_results;
+
+            progress
+                .message(format!(
+                    "NVD API: {} of {}",
+                    state.start_index, state.total_results
+                ))
+                .await;
+
+            if !page.vulnerabilities.is_empty() && state.start_index < state.total_results {
+                continue;
+            }
+
+            // the window is complete, move on to the next one, or finish
+
+            state.start_index = 0;
+            state.total_results = 0;
+
+            match end {
+                Some(end) if end < start => state.since = Some(end),
+                _ => {
+                    state.since = Some(start);
+                    break;
+                }
+            }
+        }
+
+        Ok(RunOutput {
+            report: report.build(),
+            continuation: serde_json::to_value(state).ok(),
+        })
+    }
+}
+
+/// Fetch a page of CVEs, modified between the `since` of the state and `end`
+async fn fetch_page(
+    client: &reqwest::Client,
+    api: &NvdApi,
+    state: &ApiState,
+    end: Option<OffsetDateTime>,
+) -> anyhow::Result<ApiPage> {
+    let mut request = client.get(&api.source).query(&[
+        ("startIndex", state.start_index.to_string()),
+        ("resultsPerPage", RESULTS_PER_PAGE.to_string()),
+    ]);
+
+    if let (Some(since), Some(end)) = (state.since, end) {
+        request = request.query(&[
+            ("lastModStartDate", format_date(since)?),
+            ("lastModEndDate", format_date(end)?),
+        ]);
+    }
+
+    if let Some(api_key) = &api.api_key {
+        request = request.header("apiKey", api_key);
+    }
+
+    Ok(request.send().await?.error_for_status()?.json().await?)
+}
+
+/// Format a date the way the API expects it, as extended ISO-8601 in UTC
+fn format_date(value: OffsetDateTime) -> anyhow::Result<String> {
+    let format =
+        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z");
+    Ok(value.to_offset(UtcOffset::UTC).format(format)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::ImportRunner;
+    use serde_json::json;
+    use test_context::test_context;
+    use test_log::test;
+    use time::macros::datetime;
+    use trustify_common::db::ReadWrite;
+    use trustify_module_ingestor::graph::Graph;
+    use trustify_test_context::TrustifyContext;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path, query_param},
+    };
+
+    #[test]
+    fn date_format() -> anyhow::Result<()> {
+        assert_eq!(
+            format_date(datetime!(2024-03-01 12:30:05.123456 +02:00))?,
+            "2024-03-01T10:30:05.123Z"
+        );
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn rate_limit() {
+        let window = Duration::from_millis(200);
+        let mut limiter = RateLimiter::new(2, window);
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < window);
+
+        limiter.acquire().await;
+        assert!(start.elapsed() >= window);
+    }
+
+    fn page(start_index: usize, total_results: usize, cves: &[&str]) -> serde_json::Value {
+        let cve: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../../../etc/test-data/nvd/CVE-2099-1000.json"
+        ))
+        .expect("must parse");
+
+        json!({
+            "resultsPerPage": cves.len(),
+            "startIndex": start_index,
+            "totalResults": total_results,
+            "format": "NVD_CVE",
+            "version": "2.0",
+            "vulnerabilities": cves.iter().map(|id| {
+                let mut cve = cve.clone();
+                cve["id"] = json!(id);
+                json!({ "cve": cve })
+            }).collect::<Vec<_>>(),
+        })
+    }
+
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn run_mock_nvd_api(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let nvd = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/cves"))
+            .and(query_param("startIndex", "0"))
+            .and(header("apiKey", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page(0, 2, &["CVE-2099-1000"])))
+            .mount(&nvd)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cves"))
+            .and(query_param("startIndex", "1"))
+            .and(header("apiKey", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page(1, 2, &["CVE-2099-1001"])))
+            .mount(&nvd)
+            .await;
+
+        let runner = ImportRunner {
+            db: ReadWrite::new(ctx.db.clone()),
+            storage: ctx.storage.clone().into(),
+            working_dir: None,
+            analysis: None,
+        };
+        let ingestor = IngestorService::new(Graph::new(), ctx.storage.clone(), None);
+
+        let api = NvdApi {
+            source: format!("{}/cves", nvd.uri()),
+            api_key: Some("secret".into()),
+            modified_since: None,
+        };
+        let importer = NvdImporter {
+            common: Default::default(),
+            source: Default::default(),
+            years: Default::default(),
+            start_year: None,
+            api: Some(api.clone()),
+        };
+
+        let output = runner
+            .run_once_nvd_api((), importer, api, ingestor, serde_json::Value::Null)
+            .await?;
+
+        assert_eq!(output.report.number_of_items, 2);
+        assert!(
+            output.report.messages.is_empty(),
+            "{:?}",
+            output.report.messages
+        );
+
+        // the next run only fetches what got modified since this one
+        let state: ApiState =
+            serde_json::from_value(output.continuation.expect("continuation state"))?;
+        assert!(state.since.is_some());
+        assert_eq!(state.start_index, 0);
+
+        Ok(())
+    }
+}
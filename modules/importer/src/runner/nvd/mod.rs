@@ -1,3 +1,5 @@
+mod api;
+
 use crate::{
     model::NvdImporter,
     runner::{
@@ -36,6 +38,12 @@ impl super::ImportRunner {
         let ingestor =
             IngestorService::new(Graph::new(), self.storage.clone(), self.analysis.clone());
 
+        if let Some(api) = nvd.api.clone() {
+            return self
+                .run_once_nvd_api(context, nvd, api, ingestor, continuation)
+                .await;
+        }
+
         let mut report = ReportBuilder::new();
 
         // Per-year sha256 of the last successfully ingested feed asset. A year
@@ -278,6 +286,7 @@ mod test {
             source: source.to_string(),
             years: years.iter().copied().collect(),
             start_year,
+            api: None,
         }
     }
 
@@ -33,6 +33,7 @@ struct Context<C: RunContext + 'static> {
     report: Arc<Mutex<ReportBuilder>>,
     ingestor: IngestorService,
     db: ReadWrite,
+    dry_run: bool,
 }
 
 impl<C: RunContext> Context<C> {
@@ -62,6 +63,13 @@ impl<C: RunContext> Context<C> {
             }
         }
 
+        if self.dry_run {
+            let outcome =
+                Handle::current().block_on(self.ingestor.dry_run(&data, Format::OSV, &self.db))?;
+            self.report.lock().add_dry_run(outcome);
+            return Ok(());
+        }
+
         Handle::current().block_on(async {
             self.db
                 .transaction(async |tx| {
@@ -120,7 +128,7 @@ impl super::ImportRunner {
         let ingestor =
             IngestorService::new(Graph::new(), self.storage.clone(), self.analysis.clone());
 
-        let report = Arc::new(Mutex::new(ReportBuilder::new()));
+        let report = Arc::new(Mutex::new(ReportBuilder::new().with_dry_run(osv.dry_run)));
         let continuation = serde_json::from_value(continuation).unwrap_or_default();
 
         // working dir
@@ -144,6 +152,7 @@ impl super::ImportRunner {
                 report: report.clone(),
                 ingestor,
                 db: self.db.clone(),
+                dry_run: osv.common.dry_run,
             }),
         )
         .continuation(continuation)
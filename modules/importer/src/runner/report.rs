@@ -3,6 +3,7 @@ use parking_lot::Mutex;
 use schemars::JsonSchema;
 use std::{collections::BTreeMap, iter, sync::Arc};
 use time::OffsetDateTime;
+use trustify_module_ingestor::model::DryRunOutcome;
 
 /// The phase of processing
 #[derive(
@@ -62,6 +63,23 @@ pub struct Report {
     /// Messages emitted during processing
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub messages: BTreeMap<Phase, BTreeMap<String, Vec<Message>>>,
+    /// What would have been ingested, present only if this was a dry run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<DryRunSummary>,
+}
+
+/// The outcome of a dry run
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunSummary {
+    /// Number of documents which would have been inserted
+    pub inserted: usize,
+    /// Number of documents which would have been ingested as a new version of an existing one
+    pub updated: usize,
+    /// Number of documents which are already ingested
+    pub skipped: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
@@ -101,14 +119,36 @@ impl ReportBuilder {
                 end_date: OffsetDateTime::now_utc(),
                 number_of_items: 0,
                 messages: Default::default(),
+                dry_run: None,
             },
         }
     }
 
+    /// Create a builder for a dry run, if `dry_run` is set
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.report.dry_run = dry_run.then(Default::default);
+        self
+    }
+
     pub fn tick(&mut self) {
         self.report.number_of_items += 1;
     }
 
+    /// Count a number of items, processed at once
+    pub fn add_items(&mut self, items: usize) {
+        self.report.number_of_items += items;
+    }
+
+    /// Record the outcome of checking a document in a dry run
+    pub fn add_dry_run(&mut self, outcome: DryRunOutcome) {
+        let summary = self.report.dry_run.get_or_insert_default();
+        match outcome {
+            DryRunOutcome::Insert => summary.inserted += 1,
+            DryRunOutcome::Update => summary.updated += 1,
+            DryRunOutcome::Skip => summary.skipped += 1,
+        }
+    }
+
     /// Add a single message
     pub fn add_message(
         &mut self,
@@ -41,7 +41,9 @@ impl super::ImportRunner {
 
         // report
 
-        let report = Arc::new(Mutex::new(ReportBuilder::new()));
+        let report = Arc::new(Mutex::new(
+            ReportBuilder::new().with_dry_run(importer.dry_run),
+        ));
 
         let SbomImporter {
             common,
@@ -73,6 +75,7 @@ impl super::ImportRunner {
             labels: common.labels,
             ingestor,
             db: self.db.clone(),
+            dry_run: common.dry_run,
             report: report.clone(),
             max_size: size_limit.map(|size| size.as_u64()),
         };
@@ -23,6 +23,8 @@ pub struct StorageVisitor<C: RunContext> {
     pub labels: Labels,
     pub ingestor: IngestorService,
     pub db: ReadWrite,
+    /// only check documents, don't ingest them
+    pub dry_run: bool,
     /// the report to report our messages to
     pub report: Arc<Mutex<ReportBuilder>>,
 }
@@ -69,6 +71,17 @@ impl<C: RunContext> ValidatedVisitor<HttpSource> for StorageVisitor<C> {
             None => (doc.data.clone(), false),
         };
 
+        if self.dry_run {
+            let outcome = self
+                .ingestor
+                .dry_run(&data, Format::SBOM, &self.db)
+                .await
+                .map_err(StorageError::Storage)?;
+            self.report.lock().add_dry_run(outcome);
+
+            return self.context.check_canceled(|| StorageError::Canceled).await;
+        }
+
         let result = self
             .db
             .transaction(async |tx| {
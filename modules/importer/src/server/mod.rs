@@ -2,7 +2,7 @@ pub mod context;
 pub(crate) mod progress;
 
 use crate::{
-    model::{Importer, State},
+    model::{Importer, ImporterConfiguration, State},
     runner::{
         ImportRunner,
         common::heartbeat::Heart,
@@ -12,6 +12,7 @@ use crate::{
     service::{Error, ImporterService},
 };
 use opentelemetry::global;
+use serde_json::json;
 use std::{path::PathBuf, time::Duration};
 use time::OffsetDateTime;
 use tokio::{task::LocalSet, time::MissedTickBehavior};
@@ -20,6 +21,10 @@ use tracing::instrument;
 use trustify_common::db::{ReadWrite, pagination_cache::PaginationCache};
 use trustify_module_analysis::service::AnalysisService;
 use trustify_module_storage::service::dispatch::DispatchBackend;
+use trustify_module_webhook::{model::WebhookEvent, service::notify};
+
+/// The longest delay between two retries of a failed run.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
 
 /// Run the importer loop.
 ///
@@ -152,16 +157,18 @@ async fn import(
 
     log::info!("Starting run: {}", importer.name);
 
-    let context = ServiceRunContext::new(service.clone(), importer.name.clone(), cancel);
+    let dry_run = importer.data.configuration.dry_run;
 
-    let (last_error, report, continuation) = match runner
-        .run_once(
-            context,
-            importer.data.configuration,
-            importer.data.last_success,
-            importer.data.continuation,
-        )
-        .await
+    let (last_error, report, continuation) = match run_once_with_retry(
+        &runner,
+        &service,
+        &importer.name,
+        &cancel,
+        importer.data.configuration,
+        importer.data.last_success,
+        importer.data.continuation,
+    )
+    .await
     {
         Ok(RunOutput {
             report,
@@ -179,6 +186,19 @@ async fn import(
 
     log::info!("Import run complete: {last_error:?}");
 
+    let report = report.and_then(|report| serde_json::to_value(report).ok());
+
+    let (event, data) = match &last_error {
+        None => (
+            WebhookEvent::ImporterComplete,
+            json!({ "importer": importer.name, "dry_run": dry_run, "report": report }),
+        ),
+        Some(err) => (
+            WebhookEvent::ImporterError,
+            json!({ "importer": importer.name, "dry_run": dry_run, "report": report, "error": err }),
+        ),
+    };
+
     service
         .update_finish(
             &importer.name,
@@ -186,13 +206,97 @@ async fn import(
             last_run,
             last_error,
             continuation,
-            report.and_then(|report| serde_json::to_value(report).ok()),
+            report,
+            dry_run,
         )
         .await?;
 
+    // the run is complete, failing to notify about it doesn't change that
+    if let Err(err) = notify(event, data, &runner.db).await {
+        log::warn!("Failed to notify about the run of {}: {err}", importer.name);
+    }
+
     Ok(())
 }
 
+/// Run an importer, retrying runs failing with a transient error
+///
+/// Retries up to `max_retries` times, waiting `retry_delay_secs` before the first retry and
+/// doubling the delay for each further one, up to [`MAX_RETRY_DELAY`].
+async fn run_once_with_retry(
+    runner: &ImportRunner,
+    service: &ImporterService,
+    name: &str,
+    cancel: &CancellationToken,
+    configuration: ImporterConfiguration,
+    last_success: Option<OffsetDateTime>,
+    continuation: serde_json::Value,
+) -> Result<RunOutput, ScannerError> {
+    let max_retries = configuration.max_retries;
+    let retry_delay = Duration::from_secs(configuration.retry_delay_secs);
+
+    let mut attempt = 0;
+
+    loop {
+        let context = ServiceRunContext::new(service.clone(), name.to_string(), cancel.clone());
+
+        let result = runner
+            .run_once(
+                context,
+                configuration.clone(),
+                last_success,
+                continuation.clone(),
+            )
+            .await;
+
+        let err = match &result {
+            Err(ScannerError::Normal { err, .. }) if attempt < max_retries && is_transient(err) => {
+                err
+            }
+            _ => return result,
+        };
+
+        let delay = backoff(retry_delay, attempt);
+        attempt += 1;
+
+        log::warn!(
+            "Import run of {name} failed, retrying ({attempt}/{max_retries}) in {}s: {err}",
+            delay.as_secs()
+        );
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = cancel.cancelled() => return result,
+        }
+    }
+}
+
+/// The delay before the retry following `attempt`
+fn backoff(delay: Duration, attempt: u32) -> Duration {
+    delay
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// Check if an error is worth retrying, like a network issue or a server error
+///
+/// Client errors, like an HTTP 4xx response or a document failing to parse, are not.
+fn is_transient(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if cause.is::<std::io::Error>() {
+            return true;
+        }
+
+        match cause.downcast_ref::<reqwest::Error>() {
+            Some(err) => match err.status() {
+                Some(status) => status.is_server_error(),
+                None => err.is_timeout() || err.is_connect() || err.is_request(),
+            },
+            None => false,
+        }
+    })
+}
+
 async fn reap(importers: &[Importer], service: &ImporterService) -> anyhow::Result<()> {
     for importer in importers
         .iter()
@@ -211,8 +315,38 @@ async fn reap(importers: &[Importer], service: &ImporterService) -> anyhow::Resu
                 Some("Import aborted".into()),
                 None,
                 None,
+                false,
             )
             .await?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backoff_delay() {
+        let delay = Duration::from_secs(10);
+
+        assert_eq!(backoff(delay, 0), Duration::from_secs(10));
+        assert_eq!(backoff(delay, 1), Duration::from_secs(20));
+        assert_eq!(backoff(delay, 4), Duration::from_secs(160));
+        assert_eq!(backoff(delay, 5), MAX_RETRY_DELAY);
+        assert_eq!(backoff(delay, 100), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn transient_errors() {
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        assert!(is_transient(&anyhow::Error::new(io).context("fetching")));
+
+        assert!(!is_transient(&anyhow::anyhow!("failed to parse document")));
+        assert!(!is_transient(
+            &serde_json::from_str::<serde_json::Value>("{")
+                .unwrap_err()
+                .into()
+        ));
+    }
+}
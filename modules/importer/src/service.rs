@@ -1,11 +1,15 @@
-use crate::model::{Importer, ImporterConfiguration, ImporterReport};
+use crate::model::{Importer, ImporterConfiguration, ImporterReport, ImporterStats};
 use actix_web::{HttpResponse, ResponseError, body::BoxBody};
+use moka::future::Cache;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, TransactionTrait,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, FromQueryResult,
+    PaginatorTrait, QueryFilter, QueryOrder, Statement, TransactionTrait,
 };
 use sea_query::{Alias, Expr, Nullable, SimpleExpr};
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    time::Duration,
+};
 use time::OffsetDateTime;
 use tracing::instrument;
 use trustify_common::{
@@ -25,6 +29,8 @@ use uuid::Uuid;
 pub enum Error {
     #[error("importer already exists")]
     AlreadyExists,
+    #[error("invalid importer: {0}")]
+    BadRequest(String),
     #[error("importer '{0}' not found")]
     NotFound(String),
     #[error("mid air collision")]
@@ -83,6 +89,11 @@ impl ResponseError for Error {
                 message: self.to_string(),
                 details: None,
             }),
+            Self::BadRequest(_) => HttpResponse::BadRequest().json(ErrorInformation {
+                error: "BadRequest".into(),
+                message: self.to_string(),
+                details: None,
+            }),
             Self::NotFound(_) => HttpResponse::NotFound().json(ErrorInformation {
                 error: "NotFound".into(),
                 message: self.to_string(),
@@ -124,16 +135,37 @@ where
     }
 }
 
+/// Validate a configuration, before storing it
+fn validate(configuration: &mut ImporterConfiguration) -> Result<(), Error> {
+    configuration.labels.validate_mut()?;
+
+    if configuration.dry_run && !configuration.supports_dry_run() {
+        return Err(Error::BadRequest(
+            "dry runs are only supported by the SBOM, CSAF and OSV importers".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// The time the statistics of an importer are cached for
+const STATS_TTL: Duration = Duration::from_secs(60);
+
 #[derive(Clone, Debug)]
 pub struct ImporterService {
     db: ReadWrite,
     cache: PaginationCache,
+    stats: Cache<String, ImporterStats>,
 }
 
 impl ImporterService {
     /// Creates a new importer service backed by the given read-write connection.
     pub fn new(db: ReadWrite, cache: PaginationCache) -> Self {
-        Self { db, cache }
+        Self {
+            db,
+            cache,
+            stats: Cache::builder().time_to_live(STATS_TTL).build(),
+        }
     }
 
     pub async fn list(&self) -> Result<Vec<Importer>, Error> {
@@ -152,7 +184,7 @@ impl ImporterService {
         name: String,
         mut configuration: ImporterConfiguration,
     ) -> Result<(), Error> {
-        configuration.labels.validate_mut()?;
+        validate(&mut configuration)?;
 
         let entity = importer::ActiveModel {
             name: Set(name.clone()),
@@ -223,10 +255,7 @@ impl ImporterService {
 
         // validate
 
-        configuration
-            .labels
-            .validate_mut()
-            .map_err(|err| PatchError::Common(err.into()))?;
+        validate(&mut configuration)?;
 
         // store
 
@@ -252,7 +281,7 @@ impl ImporterService {
         expected_revision: Option<&str>,
         mut configuration: ImporterConfiguration,
     ) -> Result<(), Error> {
-        configuration.labels.validate_mut()?;
+        validate(&mut configuration)?;
 
         self.update(
             &self.db,
@@ -300,6 +329,7 @@ impl ImporterService {
         last_error: Option<String>,
         continuation: Option<serde_json::Value>,
         report: Option<serde_json::Value>,
+        dry_run: bool,
     ) -> Result<(), Error> {
         let tx = self.db.begin().await?;
 
@@ -319,9 +349,12 @@ impl ImporterService {
                 Expr::value(String::null()),
             ),
             (importer::Column::LastChange, Expr::value(now)),
-            (importer::Column::Continuation, Expr::value(continuation)),
         ];
-        if successful {
+        // a dry run didn't ingest anything, so the next run must not continue from it
+        if !dry_run {
+            updates.push((importer::Column::Continuation, Expr::value(continuation)));
+        }
+        if successful && !dry_run {
             // we use the `start` marker, so that `last_success` can be used as the next `since`
             updates.push((importer::Column::LastSuccess, Expr::value(start)));
         }
@@ -493,6 +526,75 @@ impl ImporterService {
         Ok(PaginatedResults {
             total,
             items: items.into_iter().map(ImporterReport::from).collect(),
+            next_cursor: None,
         })
     }
+
+    /// Aggregate statistics over the reports of an importer
+    ///
+    /// Results are cached for a short time, so they may lag behind the most recent runs.
+    #[instrument(skip(self), err(level=tracing::Level::INFO))]
+    pub async fn stats(&self, name: &str) -> Result<ImporterStats, Error> {
+        if let Some(stats) = self.stats.get(name).await {
+            return Ok(stats);
+        }
+
+        if importer::Entity::find_by_id(name)
+            .one(&self.db)
+            .await?
+            .is_none()
+        {
+            return Err(Error::NotFound(name.to_string()));
+        }
+
+        #[derive(FromQueryResult)]
+        struct Row {
+            total_runs: i64,
+            successful_runs: i64,
+            failed_runs: i64,
+            total_documents_processed: i64,
+            avg_run_duration_secs: Option<f64>,
+            last_run: Option<OffsetDateTime>,
+        }
+
+        let row = Row::find_by_statement(Statement::from_sql_and_values(
+            self.db.get_database_backend(),
+            r#"
+SELECT
+    COUNT(*) AS total_runs,
+    COUNT(*) FILTER (WHERE error IS NULL) AS successful_runs,
+    COUNT(*) FILTER (WHERE error IS NOT NULL) AS failed_runs,
+    COALESCE(SUM(COALESCE(
+        (report->>'numberOfItems')::bigint,
+        (report->>'number_of_items')::bigint,
+        0
+    )), 0)::bigint AS total_documents_processed,
+    AVG(EXTRACT(EPOCH FROM
+        (report->>'endDate')::timestamptz - (report->>'startDate')::timestamptz
+    ))::float8 AS avg_run_duration_secs,
+    MAX((report->>'startDate')::timestamptz) AS last_run
+FROM importer_report
+WHERE importer = $1
+"#,
+            [name.into()],
+        ))
+        .one(&self.db)
+        .await?;
+
+        // an aggregate without grouping always returns a row
+        let stats = row
+            .map(|row| ImporterStats {
+                total_runs: row.total_runs as _,
+                successful_runs: row.successful_runs as _,
+                failed_runs: row.failed_runs as _,
+                total_documents_processed: row.total_documents_processed as _,
+                avg_run_duration_secs: row.avg_run_duration_secs,
+                last_run: row.last_run,
+            })
+            .unwrap_or_default();
+
+        self.stats.insert(name.to_string(), stats.clone()).await;
+
+        Ok(stats)
+    }
 }
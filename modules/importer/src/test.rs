@@ -1,7 +1,11 @@
 #![cfg(test)]
 
-use super::model::{
-    CommonImporter, Importer, ImporterConfiguration, ImporterData, SbomImporter, State,
+use super::{
+    model::{
+        CommonImporter, Importer, ImporterConfiguration, ImporterData, ImporterStats, SbomImporter,
+        State,
+    },
+    service::ImporterService,
 };
 use actix_http::{Request, body::BoxBody};
 use actix_web::{
@@ -14,6 +18,7 @@ use serde_json::json;
 use std::time::Duration;
 use test_context::test_context;
 use test_log::test;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 use trustify_common::db::{self, pagination_cache::PaginationCache};
 use trustify_test_context::{ReadOnly, TrustifyContext, app::TestApp};
 use utoipa_actix_web::AppExt;
@@ -25,6 +30,7 @@ fn mock_configuration(source: impl Into<String>) -> ImporterConfiguration {
             period: Duration::from_secs(30),
             description: None,
             labels: Default::default(),
+            ..Default::default()
         },
         source: source.into(),
         keys: vec![],
@@ -381,6 +387,155 @@ async fn patch(ctx: TrustifyContext) {
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
 }
 
+#[test_context(TrustifyContext, skip_teardown)]
+#[test(actix_web::test)]
+async fn dry_run_unsupported(ctx: TrustifyContext) {
+    let app = app(&ctx).await;
+
+    // supported by the SBOM importer
+
+    let mut configuration = mock_configuration("bar");
+    configuration.dry_run = true;
+
+    let req = actix::TestRequest::post()
+        .uri("/api/v3/importer/foo")
+        .set_json(&configuration)
+        .to_request();
+
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // but not by the CVE importer
+
+    let cve = json!({
+        "cve": {
+            "period": "1d",
+            "dryRun": true,
+        },
+    });
+
+    let req = actix::TestRequest::post()
+        .uri("/api/v3/importer/cve")
+        .set_json(&cve)
+        .to_request();
+
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = actix::TestRequest::put()
+        .uri("/api/v3/importer/foo")
+        .set_json(&cve)
+        .to_request();
+
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test_context(TrustifyContext, skip_teardown)]
+#[test(actix_web::test)]
+async fn stats(ctx: TrustifyContext) -> anyhow::Result<()> {
+    let app = app(&ctx).await;
+
+    // unknown importer
+
+    let req = actix::TestRequest::get()
+        .uri("/api/v3/importer/foo/stats")
+        .to_request();
+
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // create one, and record some runs
+
+    let service = ImporterService::new(
+        db::ReadWrite::new(ctx.db.clone()),
+        PaginationCache::for_test(),
+    );
+    service
+        .create("foo".into(), mock_configuration("bar"))
+        .await?;
+
+    let runs = [
+        ("2024-01-01T00:00:00Z", "2024-01-01T00:00:10Z", 5, None),
+        (
+            "2024-01-02T00:00:00Z",
+            "2024-01-02T00:00:30Z",
+            0,
+            Some("failed"),
+        ),
+        ("2024-01-03T00:00:00Z", "2024-01-03T00:00:20Z", 7, None),
+    ];
+    for (start, end, items, error) in runs {
+        service
+            .update_finish(
+                "foo",
+                None,
+                OffsetDateTime::parse(start, &Rfc3339)?,
+                error.map(ToString::to_string),
+                None,
+                Some(json!({
+                    "startDate": start,
+                    "endDate": end,
+                    "numberOfItems": items,
+                    "messages": {},
+                })),
+                false,
+            )
+            .await?;
+    }
+
+    // get the stats
+
+    let req = actix::TestRequest::get()
+        .uri("/api/v3/importer/foo/stats")
+        .to_request();
+
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result: ImporterStats = actix::read_body_json(resp).await;
+    assert_eq!(
+        result,
+        ImporterStats {
+            total_runs: 3,
+            successful_runs: 2,
+            failed_runs: 1,
+            total_documents_processed: 12,
+            avg_run_duration_secs: Some(20.0),
+            last_run: Some(OffsetDateTime::parse("2024-01-03T00:00:00Z", &Rfc3339)?),
+        }
+    );
+
+    // further runs are not reflected, until the cache expires
+
+    service
+        .update_finish(
+            "foo",
+            None,
+            OffsetDateTime::now_utc(),
+            None,
+            None,
+            Some(json!({
+                "startDate": "2024-01-04T00:00:00Z",
+                "endDate": "2024-01-04T00:00:10Z",
+                "numberOfItems": 1,
+                "messages": {},
+            })),
+            false,
+        )
+        .await?;
+
+    let req = actix::TestRequest::get()
+        .uri("/api/v3/importer/foo/stats")
+        .to_request();
+
+    let resp = actix::call_service(&app, req).await;
+    let cached: ImporterStats = actix::read_body_json(resp).await;
+    assert_eq!(cached, result);
+
+    Ok(())
+}
+
 #[test_context(ReadOnly<TrustifyContext>)]
 #[test(actix_web::test)]
 async fn read_only(ctx: &mut ReadOnly<TrustifyContext>) {
@@ -1,14 +1,19 @@
 use crate::{
     graph::Graph,
-    service::{Error, IngestorService},
+    service::{
+        Error, IngestorService,
+        archive::{ArchiveIngestResult, ArchiveLimits},
+    },
 };
-use actix_web::{HttpResponse, Responder, post, web};
+use actix_web::{HttpResponse, Responder, get, post, web};
 use sea_orm::TransactionTrait;
-use trustify_auth::{UploadDataset, authorizer::Require};
+use trustify_auth::{
+    CreateAdvisory, CreateSbom, ReadSystemInformation, UploadDataset, authorizer::Require,
+};
 use trustify_common::{db, model::BinaryData};
 use trustify_entity::labels::Labels;
 use trustify_module_analysis::service::AnalysisService;
-use trustify_module_storage::service::dispatch::DispatchBackend;
+use trustify_module_storage::service::{dispatch::DispatchBackend, health::HealthStatus};
 use utoipa::IntoParams;
 
 /// mount the "ingestor" module
@@ -24,13 +29,19 @@ pub fn configure(
     svc.app_data(web::Data::new(ingestor_service))
         .app_data(web::Data::new(config))
         .app_data(web::Data::new(db))
-        .service(upload_dataset);
+        .service(upload_dataset)
+        .service(upload_archive)
+        .service(storage_status);
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct Config {
-    /// Limit of a single content entry (after decompression).
+    /// Limit of a single content entry (after decompression), of datasets and archives.
     pub dataset_entry_limit: usize,
+    /// Maximum number of entries of an archive.
+    pub archive_entries_limit: usize,
+    /// Limit of all entries of an archive together (after decompression).
+    pub archive_total_limit: usize,
 }
 
 #[derive(
@@ -72,3 +83,63 @@ pub async fn upload_dataset(
 
     Ok(HttpResponse::Created().json(result))
 }
+
+#[utoipa::path(
+    tag = "archive",
+    operation_id = "uploadArchive",
+    request_body = inline(BinaryData),
+    params(UploadParams),
+    responses(
+        (status = 200, description = "The result of ingesting the files of the archive", body = ArchiveIngestResult),
+        (status = 400, description = "The file could not be read as a ZIP archive, or contains the same file twice"),
+        (status = 413, description = "The archive declares to exceed the limits of its entries"),
+    )
+)]
+#[post("/v3/ingest/archive")]
+/// Upload a ZIP archive of SBOMs and advisories
+///
+/// Each file of the archive gets ingested on its own. Failing to read or ingest a file doesn't
+/// affect the other files.
+pub async fn upload_archive(
+    service: web::Data<IngestorService>,
+    config: web::Data<Config>,
+    db: web::Data<db::ReadWrite>,
+    web::Query(UploadParams { labels }): web::Query<UploadParams>,
+    bytes: web::Bytes,
+    _: Require<CreateSbom>,
+    _: Require<CreateAdvisory>,
+) -> Result<impl Responder, Error> {
+    let result = service
+        .ingest_archive(
+            &bytes,
+            labels,
+            ArchiveLimits {
+                entry: config.dataset_entry_limit,
+                entries: config.archive_entries_limit,
+                total: config.archive_total_limit,
+            },
+            db.get_ref(),
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[utoipa::path(
+    tag = "storage",
+    operation_id = "storageStatus",
+    responses(
+        (status = 200, description = "The health of the storage backends", body = Vec<HealthStatus>),
+    )
+)]
+#[get("/v3/status/storage")]
+/// Check the health of the storage backends
+///
+/// Writes, reads back, and deletes a probe blob for each configured backend. The response is
+/// successful even if a backend is unhealthy, check the status of each entry instead.
+pub async fn storage_status(
+    service: web::Data<IngestorService>,
+    _: Require<ReadSystemInformation>,
+) -> impl Responder {
+    HttpResponse::Ok().json(vec![service.storage().health_check().await])
+}
@@ -333,6 +333,7 @@ impl<'g> PackageContext<'g> {
                 .into_iter()
                 .map(|each| PackageVersionContext::new(self, each))
                 .collect(),
+            next_cursor: None,
         })
     }
 }
@@ -0,0 +1,58 @@
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait, Set};
+use sea_query::OnConflict;
+use serde_cyclonedx::cyclonedx::v_1_6::CycloneDxDeclarationsItemAttestations;
+use serde_json::Value;
+use trustify_common::db::chunk::EntityChunkedIter;
+use trustify_entity::sbom_attestation;
+use uuid::Uuid;
+
+/// Creator of CycloneDX 1.6 attestations, taken from the document's declarations
+pub struct AttestationCreator {
+    sbom_id: Uuid,
+    attestations: Vec<sbom_attestation::ActiveModel>,
+}
+
+impl AttestationCreator {
+    pub fn new(sbom_id: Uuid) -> Self {
+        Self {
+            sbom_id,
+            attestations: Vec::new(),
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        attestation: &CycloneDxDeclarationsItemAttestations,
+    ) -> Result<(), serde_json::Error> {
+        let evidence = match &attestation.map {
+            Some(map) => serde_json::to_value(map)?,
+            None => Value::Array(vec![]),
+        };
+
+        self.attestations.push(sbom_attestation::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            sbom_id: Set(self.sbom_id),
+            summary: Set(attestation.summary.clone()),
+            assessor_ref: Set(attestation.assessor.clone()),
+            evidence: Set(evidence),
+        });
+
+        Ok(())
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<(), DbErr> {
+        for batch in &self.attestations.into_iter().chunked() {
+            sbom_attestation::Entity::insert_many(batch)
+                .on_conflict(
+                    OnConflict::column(sbom_attestation::Column::Id)
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .do_nothing()
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
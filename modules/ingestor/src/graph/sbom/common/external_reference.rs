@@ -0,0 +1,77 @@
+use super::Checksum;
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait, Set};
+use sea_query::OnConflict;
+use serde_cyclonedx::cyclonedx::v_1_6::ExternalReference;
+use serde_json::{Map, Value};
+use trustify_common::db::chunk::EntityChunkedIter;
+use trustify_entity::sbom_package_external_ref;
+use uuid::Uuid;
+
+/// Creator of external references of packages, like website or VCS
+pub struct ExternalReferenceCreator {
+    sbom_id: Uuid,
+    refs: Vec<sbom_package_external_ref::ActiveModel>,
+}
+
+impl ExternalReferenceCreator {
+    pub fn new(sbom_id: Uuid) -> Self {
+        Self {
+            sbom_id,
+            refs: Vec::new(),
+        }
+    }
+
+    pub fn add<'a>(
+        &mut self,
+        node_id: &str,
+        refs: impl IntoIterator<Item = &'a ExternalReference>,
+    ) {
+        for r#ref in refs {
+            let url = match &r#ref.url {
+                Value::String(url) => url.clone(),
+                url => url.to_string(),
+            };
+
+            // hashes are stored as an object of algorithm to value
+            let hashes = r#ref
+                .hashes
+                .iter()
+                .flatten()
+                .cloned()
+                .map(|hash| {
+                    let Checksum { r#type, value } = hash.into();
+                    (r#type.into_owned(), Value::String(value))
+                })
+                .collect::<Map<_, _>>();
+
+            self.refs.push(sbom_package_external_ref::ActiveModel {
+                sbom_id: Set(self.sbom_id),
+                node_id: Set(node_id.to_string()),
+                ref_type: Set(r#ref.type_.clone()),
+                url: Set(url),
+                hashes: Set(Value::Object(hashes)),
+            });
+        }
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<(), DbErr> {
+        for batch in &self.refs.into_iter().chunked() {
+            sbom_package_external_ref::Entity::insert_many(batch)
+                .on_conflict(
+                    OnConflict::columns([
+                        sbom_package_external_ref::Column::SbomId,
+                        sbom_package_external_ref::Column::NodeId,
+                        sbom_package_external_ref::Column::RefType,
+                        sbom_package_external_ref::Column::Url,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait, Set};
+use sea_query::OnConflict;
+use serde_cyclonedx::cyclonedx::v_1_6::Formula;
+use serde_json::Value;
+use trustify_common::db::chunk::EntityChunkedIter;
+use trustify_entity::sbom_formulation;
+use uuid::Uuid;
+
+/// Creator of CycloneDX 1.6 formulation entries
+pub struct FormulationCreator {
+    sbom_id: Uuid,
+    formulations: Vec<sbom_formulation::ActiveModel>,
+}
+
+impl FormulationCreator {
+    pub fn new(sbom_id: Uuid) -> Self {
+        Self {
+            sbom_id,
+            formulations: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, formula: &Formula) -> Result<(), serde_json::Error> {
+        // a formula without a bom-ref can't be referenced, so we create one
+        let bom_ref = formula
+            .bom_ref
+            .clone()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        // the formula itself has no description, we use the one of its first workflow
+        let description = formula
+            .workflows
+            .iter()
+            .flatten()
+            .find_map(|workflow| workflow.description.clone().or(workflow.name.clone()));
+
+        let components = match &formula.components {
+            Some(components) => serde_json::to_value(components)?,
+            None => Value::Array(vec![]),
+        };
+
+        self.formulations.push(sbom_formulation::ActiveModel {
+            sbom_id: Set(self.sbom_id),
+            bom_ref: Set(bom_ref),
+            description: Set(description),
+            components: Set(components),
+        });
+
+        Ok(())
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<(), DbErr> {
+        for batch in &self.formulations.into_iter().chunked() {
+            sbom_formulation::Entity::insert_many(batch)
+                .on_conflict(
+                    OnConflict::columns([
+                        sbom_formulation::Column::SbomId,
+                        sbom_formulation::Column::BomRef,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
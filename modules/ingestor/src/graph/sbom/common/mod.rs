@@ -1,8 +1,11 @@
+mod attestation;
 mod checksum;
 mod cryptographic_asset;
 mod expanded_license;
 mod external;
+mod external_reference;
 mod file;
+mod formulation;
 mod license;
 mod licensing_info;
 mod machine_learning_model;
@@ -10,15 +13,20 @@ mod node;
 mod package;
 mod reference;
 mod relationship;
+mod snippet;
 
+pub use attestation::*;
 pub use checksum::*;
 pub use cryptographic_asset::*;
 pub use expanded_license::*;
 pub use external::*;
+pub use external_reference::*;
 pub use file::*;
+pub use formulation::*;
 pub use license::*;
 pub use licensing_info::*;
 pub use machine_learning_model::*;
 pub use package::*;
 pub use reference::*;
 pub use relationship::*;
+pub use snippet::*;
@@ -0,0 +1,105 @@
+use crate::graph::sbom::{Checksum, ReferenceSource, common::node::NodeCreator};
+use sea_orm::{ActiveValue::Set, ConnectionTrait, DbErr, EntityTrait};
+use sea_query::OnConflict;
+use spdx_rs::models::{Pointer, Snippet};
+use tracing::instrument;
+use trustify_common::db::chunk::EntityChunkedIter;
+use trustify_entity::sbom_snippet;
+use uuid::Uuid;
+
+/// Creator of SPDX snippets
+pub struct SnippetCreator {
+    sbom_id: Uuid,
+    nodes: NodeCreator,
+    snippets: Vec<sbom_snippet::ActiveModel>,
+}
+
+impl SnippetCreator {
+    pub fn with_capacity(sbom_id: Uuid, capacity: usize) -> Self {
+        Self {
+            sbom_id,
+            nodes: NodeCreator::with_capacity(sbom_id, capacity),
+            snippets: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn add(&mut self, snippet: Snippet) {
+        // a snippet may have one byte range and one line range, we take the first of each
+        let mut bytes = None;
+        let mut lines = None;
+        for range in &snippet.ranges {
+            match (&range.start_pointer, &range.end_pointer) {
+                (Pointer::Byte { offset: start, .. }, Pointer::Byte { offset: end, .. }) => {
+                    bytes.get_or_insert((*start as i64, *end as i64));
+                }
+                (
+                    Pointer::Line {
+                        line_number: start, ..
+                    },
+                    Pointer::Line {
+                        line_number: end, ..
+                    },
+                ) => {
+                    lines.get_or_insert((*start, *end));
+                }
+                _ => {
+                    log::info!(
+                        "Skipping mismatching range pointers of snippet: {}",
+                        snippet.snippet_spdx_identifier
+                    );
+                }
+            }
+        }
+
+        let name = snippet
+            .snippet_name
+            .unwrap_or_else(|| snippet.snippet_spdx_identifier.clone());
+
+        self.nodes.add(
+            snippet.snippet_spdx_identifier.clone(),
+            name,
+            Checksum::NONE,
+        );
+
+        self.snippets.push(sbom_snippet::ActiveModel {
+            sbom_id: Set(self.sbom_id),
+            node_id: Set(snippet.snippet_spdx_identifier),
+            file_node_id: Set(snippet.snippet_from_file_spdx_identifier),
+            byte_range_start: Set(bytes.map(|(start, _)| start)),
+            byte_range_end: Set(bytes.map(|(_, end)| end)),
+            line_range_start: Set(lines.map(|(start, _)| start)),
+            line_range_end: Set(lines.map(|(_, end)| end)),
+            license_concluded: Set(snippet
+                .snippet_concluded_license
+                .map(|license| license.to_string())),
+        });
+    }
+
+    #[instrument(skip_all, fields(num=self.snippets.len()), err(level=tracing::Level::INFO))]
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<(), DbErr> {
+        self.nodes.create(db).await?;
+
+        for batch in &self.snippets.into_iter().chunked() {
+            sbom_snippet::Entity::insert_many(batch)
+                .on_conflict(
+                    OnConflict::columns([
+                        sbom_snippet::Column::SbomId,
+                        sbom_snippet::Column::NodeId,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .do_nothing()
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> ReferenceSource<'a> for SnippetCreator {
+    fn references(&'a self) -> impl IntoIterator<Item = &'a str> {
+        self.nodes.references()
+    }
+}
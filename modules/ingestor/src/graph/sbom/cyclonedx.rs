@@ -4,10 +4,11 @@ use crate::{
         product::ProductInformation,
         purl::creator::PurlCreator,
         sbom::{
-            CryptographicAssetCreator, CycloneDx as CycloneDxProcessor, LicenseCreator,
-            LicenseInfo, MachineLearningModelCreator, NodeInfoParam, PackageCreator,
-            PackageLicensenInfo, PackageReference, References, RelationshipCreator, SbomContext,
-            SbomInformation, populate_expanded_license,
+            AttestationCreator, Checksum, CryptographicAssetCreator,
+            CycloneDx as CycloneDxProcessor, ExternalReferenceCreator, FormulationCreator,
+            LicenseCreator, LicenseInfo, MachineLearningModelCreator, NodeInfoParam,
+            PackageCreator, PackageLicensenInfo, PackageReference, References, RelationshipCreator,
+            SbomContext, SbomInformation, populate_expanded_license,
             processor::{
                 InitContext, PostContext, Processor, RedHatProductComponentRelationships,
                 RunProcessors,
@@ -24,6 +25,7 @@ use sbom_walker::{
 use sea_orm::ConnectionTrait;
 use serde_cyclonedx::cyclonedx::v_1_6::{
     Component, ComponentEvidenceIdentity, CycloneDx, LicenseChoiceUrl, OrganizationalContact,
+    Service,
 };
 use std::{borrow::Cow, collections::HashMap, str::FromStr};
 use time::{OffsetDateTime, format_description::well_known::Iso8601};
@@ -217,6 +219,7 @@ impl SbomContext {
         // record components
 
         creator.add_all(&sbom.components);
+        creator.add_services(&sbom.services);
 
         // create relationships
 
@@ -239,6 +242,13 @@ impl SbomContext {
 
         creator.create(connection, &mut processors).await?;
 
+        // formulations and attestations only exist starting with CycloneDX 1.6
+
+        if sbom.spec_version == "1.6" {
+            self.ingest_cyclonedx_build_evidence(&sbom, connection)
+                .await?;
+        }
+
         self.populate_describing_cpes(connection).await?;
         self.populate_ancestors(connection).await?;
 
@@ -246,6 +256,32 @@ impl SbomContext {
 
         Ok(())
     }
+
+    /// Store the formulations and attestations of a CycloneDX 1.6 document
+    async fn ingest_cyclonedx_build_evidence(
+        &self,
+        sbom: &CycloneDx,
+        connection: &impl ConnectionTrait,
+    ) -> Result<(), Error> {
+        let mut formulations = FormulationCreator::new(self.sbom.sbom_id);
+        for formula in sbom.formulation.iter().flatten() {
+            formulations.add(formula)?;
+        }
+
+        let mut attestations = AttestationCreator::new(self.sbom.sbom_id);
+        for attestation in sbom
+            .declarations
+            .iter()
+            .flat_map(|declarations| declarations.attestations.iter().flatten())
+        {
+            attestations.add(attestation)?;
+        }
+
+        formulations.create(connection).await?;
+        attestations.create(connection).await?;
+
+        Ok(())
+    }
 }
 
 /// Creator of CycloneDX components and dependencies
@@ -253,6 +289,7 @@ impl SbomContext {
 struct Creator<'a> {
     sbom_id: Uuid,
     components: Vec<&'a Component>,
+    services: Vec<&'a Service>,
     relations: Vec<(String, Relationship, String)>,
 }
 
@@ -261,6 +298,7 @@ impl<'a> Creator<'a> {
         Self {
             sbom_id,
             components: Default::default(),
+            services: Default::default(),
             relations: Default::default(),
         }
     }
@@ -283,6 +321,13 @@ impl<'a> Creator<'a> {
         }
     }
 
+    pub fn add_services(&mut self, services: &'a Option<Vec<Service>>) {
+        for service in services.iter().flatten() {
+            self.services.push(service);
+            self.add_services(&service.services);
+        }
+    }
+
     pub fn relate(&mut self, left: String, rel: Relationship, right: String) {
         self.relations.push((left, rel, right));
     }
@@ -293,12 +338,17 @@ impl<'a> Creator<'a> {
         db: &impl ConnectionTrait,
         processors: &mut [Box<dyn Processor>],
     ) -> Result<(), Error> {
-        let mut creator = ComponentCreator::new(self.sbom_id, self.components.len());
+        let mut creator =
+            ComponentCreator::new(self.sbom_id, self.components.len() + self.services.len());
 
         for comp in self.components {
             creator.add_component(comp)?;
         }
 
+        for service in self.services {
+            creator.add_service(service);
+        }
+
         for (left, rel, right) in self.relations {
             creator.add_relation(left, rel, right);
         }
@@ -327,6 +377,7 @@ struct ComponentCreator {
     files: FileCreator,
     models: MachineLearningModelCreator,
     crypto: CryptographicAssetCreator,
+    external_refs: ExternalReferenceCreator,
     relationships: RelationshipCreator<CycloneDxProcessor>,
     // Map each node to a collection of references
     refs: HashMap<String, Vec<PackageReference>>,
@@ -343,6 +394,7 @@ impl ComponentCreator {
             files: FileCreator::new(sbom_id),
             models: MachineLearningModelCreator::new(sbom_id),
             crypto: CryptographicAssetCreator::new(sbom_id),
+            external_refs: ExternalReferenceCreator::new(sbom_id),
             relationships: RelationshipCreator::new(sbom_id, CycloneDxProcessor),
             refs: Default::default(),
         }
@@ -418,7 +470,8 @@ impl ComponentCreator {
                 const EMPTY: Vec<PackageReference> = vec![];
                 match ty {
                     // We treat all these types as "packages"
-                    Application | Framework | Library | Container | OperatingSystem => {
+                    Application | Framework | Library | Container | Platform | OperatingSystem
+                    | Device | DeviceDriver | Firmware | Data => {
                         self.packages.add(
                             NodeInfoParam {
                                 node_id: node_id.clone(),
@@ -429,7 +482,9 @@ impl ComponentCreator {
                             },
                             self.refs.get(&node_id).unwrap_or(&EMPTY).iter(),
                             comp.hashes.clone().into_iter().flatten(),
-                        )
+                        );
+                        self.external_refs
+                            .add(&node_id, comp.external_references.iter().flatten());
                     }
                     File => {
                         self.files.add(
@@ -455,7 +510,6 @@ impl ComponentCreator {
                             comp.try_into()?,
                         );
                     }
-                    _ => log::error!("Unsupported component type: '{ty}'"),
                 }
             }
             Err(e) => {
@@ -498,6 +552,28 @@ impl ComponentCreator {
         Ok(())
     }
 
+    /// Add a service, which we treat as a package, so that dependencies on it can be recorded
+    pub fn add_service(&mut self, service: &Service) {
+        let node_id = service
+            .bom_ref
+            .clone()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        self.packages.add(
+            NodeInfoParam {
+                node_id: node_id.clone(),
+                name: service.name.to_string(),
+                group: service.group.as_ref().map(|v| v.to_string()),
+                version: service.version.as_ref().map(|v| v.to_string()),
+                package_license_info: vec![],
+            },
+            std::iter::empty(),
+            Checksum::NONE,
+        );
+        self.external_refs
+            .add(&node_id, service.external_references.iter().flatten());
+    }
+
     fn add_relation(&mut self, left: String, rel: Relationship, right: String) {
         self.relationships.relate(left, rel, right);
     }
@@ -588,6 +664,7 @@ impl ComponentCreator {
         self.files.create(db).await?;
         self.models.create(db).await?;
         self.crypto.create(db).await?;
+        self.external_refs.create(db).await?;
         self.relationships.create(db).await?;
 
         // Populate expanded license tables
@@ -4,6 +4,7 @@ pub mod clearly_defined;
 pub mod cyclonedx;
 pub mod processor;
 pub mod spdx;
+pub mod spdx3;
 
 mod common;
 pub use common::*;
@@ -21,8 +22,8 @@ use crate::{
 use cpe::uri::OwnedUri;
 use entity::{product, product_version};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, ModelTrait,
-    QueryFilter, QuerySelect, RelationTrait, Select, Set, Statement, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, IntoActiveModel,
+    ModelTrait, QueryFilter, QuerySelect, RelationTrait, Select, Set, Statement, TransactionTrait,
     prelude::Uuid,
 };
 use sea_query::{Condition, Expr, Func, JoinType, Query, SimpleExpr, extension::postgres::PgExpr};
@@ -34,8 +35,12 @@ use time::OffsetDateTime;
 use tracing::instrument;
 use trustify_common::{cpe::Cpe, hashing::Digests, purl::Purl, sbom::SbomLocator};
 use trustify_entity::{
-    self as entity, labels::Labels, license, relationship::Relationship, sbom, sbom_node,
-    sbom_node_cpe_ref, sbom_node_purl_ref, sbom_package_license, source_document,
+    self as entity, graph_cache, labels::Labels, license, licensing_infos,
+    package_relates_to_package, relationship::Relationship, sbom, sbom_ai, sbom_ancestor,
+    sbom_attestation, sbom_crypto, sbom_describing_cpe, sbom_external_node, sbom_file,
+    sbom_formulation, sbom_license_expanded, sbom_node, sbom_node_checksum, sbom_node_cpe_ref,
+    sbom_node_purl_ref, sbom_package, sbom_package_external_ref, sbom_package_license,
+    sbom_snippet, source_document,
 };
 
 #[derive(Clone, Default)]
@@ -61,6 +66,20 @@ impl From<()> for SbomInformation {
 
 type SelectEntity<E> = Select<E>;
 
+/// Delete the rows of an entity which belong to an SBOM, by the column referencing the SBOM
+async fn delete_by_sbom<C: ConnectionTrait, T: ColumnTrait>(
+    connection: &C,
+    sbom_id: Uuid,
+    column: T,
+) -> Result<(), Error> {
+    <T::EntityName as EntityTrait>::delete_many()
+        .filter(column.eq(sbom_id))
+        .exec(connection)
+        .await?;
+
+    Ok(())
+}
+
 impl Graph {
     pub async fn get_sbom_by_id<C: ConnectionTrait>(
         &self,
@@ -120,7 +139,15 @@ impl Graph {
             })
             .await?
         {
-            CreateOutcome::Exists(sbom) => return Ok(Outcome::Existed(sbom)),
+            CreateOutcome::Exists(mut sbom) => {
+                // uploading a deleted document again restores it
+                if sbom.sbom.deleted_at.is_some() {
+                    let mut model = sbom.sbom.into_active_model();
+                    model.deleted_at = Set(None);
+                    sbom.sbom = model.update(connection).await?;
+                }
+                return Ok(Outcome::Existed(sbom));
+            }
             CreateOutcome::Created(new_id) => new_id,
         };
 
@@ -142,6 +169,7 @@ impl Graph {
 
             properties: Set(properties),
             revision: Set(Uuid::now_v7()),
+            deleted_at: Set(None),
         };
 
         let node_model = sbom_node::ActiveModel {
@@ -609,6 +637,91 @@ impl SbomContext {
         Ok(())
     }
 
+    /// Remove all content which was ingested from the document of this SBOM, so that the
+    /// document can be ingested again.
+    ///
+    /// The SBOM itself is kept, including its ID, labels and source document, but updated with
+    /// the provided information and a new revision.
+    #[instrument(skip(self, info, connection), err(level=tracing::Level::INFO))]
+    pub async fn reset<C: ConnectionTrait>(
+        self,
+        info: impl Into<SbomInformation>,
+        connection: &C,
+    ) -> Result<Self, Error> {
+        let SbomInformation {
+            node_id,
+            name,
+            published,
+            authors,
+            suppliers,
+            data_licenses,
+            properties,
+        } = info.into();
+        let Self { graph, sbom } = self;
+        let sbom_id = sbom.sbom_id;
+
+        // all content ingested from the document, referencing rows come first
+        delete_by_sbom(connection, sbom_id, graph_cache::Column::SbomId).await?;
+        delete_by_sbom(
+            connection,
+            sbom_id,
+            package_relates_to_package::Column::SbomId,
+        )
+        .await?;
+        delete_by_sbom(connection, sbom_id, sbom_node_checksum::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_node_cpe_ref::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_node_purl_ref::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_package_license::Column::SbomId).await?;
+        delete_by_sbom(
+            connection,
+            sbom_id,
+            sbom_package_external_ref::Column::SbomId,
+        )
+        .await?;
+        delete_by_sbom(connection, sbom_id, sbom_license_expanded::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, licensing_infos::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_describing_cpe::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_external_node::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_ai::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_crypto::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_formulation::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_snippet::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_attestation::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_package::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_file::Column::SbomId).await?;
+        delete_by_sbom(connection, sbom_id, sbom_node::Column::SbomId).await?;
+
+        // ancestor links get populated from both sides
+        sbom_ancestor::Entity::delete_many()
+            .filter(
+                Condition::any()
+                    .add(sbom_ancestor::Column::SbomId.eq(sbom_id))
+                    .add(sbom_ancestor::Column::AncestorSbomId.eq(sbom_id)),
+            )
+            .exec(connection)
+            .await?;
+
+        let mut model = sbom::ActiveModel::from(sbom);
+        model.node_id = Set(node_id.clone());
+        model.published = Set(published);
+        model.authors = Set(authors);
+        model.suppliers = Set(suppliers);
+        model.data_licenses = Set(data_licenses);
+        model.properties = Set(properties);
+        model.revision = Set(Uuid::now_v7());
+        let result = model.update(connection).await?;
+
+        sbom_node::ActiveModel {
+            sbom_id: Set(sbom_id),
+            node_id: Set(node_id),
+            name: Set(name),
+        }
+        .insert(connection)
+        .await?;
+
+        Ok(Self::new(&graph, result))
+    }
+
     /// Materializes unidirectional cross-SBOM ancestor links scoped to actual
     /// external node references. The SBOM holding the `sbom_external_node`
     /// entry is the ancestor (product); the checksum-matched SBOM is the
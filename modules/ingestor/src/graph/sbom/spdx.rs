@@ -6,7 +6,8 @@ use crate::{
         sbom::{
             FileCreator, LicenseCreator, LicenseInfo, LicensingInfo, LicensingInfoCreator,
             NodeInfoParam, PackageCreator, PackageLicensenInfo, PackageReference, References,
-            RelationshipCreator, SbomContext, SbomInformation, Spdx, populate_expanded_license,
+            RelationshipCreator, SbomContext, SbomInformation, SnippetCreator, Spdx,
+            populate_expanded_license,
             processor::{
                 InitContext, PostContext, Processor, RedHatProductComponentRelationships,
                 RunProcessors,
@@ -320,6 +321,15 @@ impl SbomContext {
             );
         }
 
+        // prepare snippets
+
+        let mut snippets =
+            SnippetCreator::with_capacity(self.sbom.sbom_id, sbom_data.snippet_information.len());
+
+        for snippet in sbom_data.snippet_information {
+            snippets.add(snippet);
+        }
+
         // run post-processor
 
         PostContext {
@@ -347,15 +357,17 @@ impl SbomContext {
         let sources = References::new()
             .add_source(&doc_id)
             .add_source(&packages)
-            .add_source(&files);
+            .add_source(&files)
+            .add_source(&snippets);
         relationships
             .validate(sources)
             .map_err(Error::InvalidContent)?;
 
-        // create packages, files, and relationships
+        // create packages, files, snippets, and relationships
 
         packages.create(db).await?;
         files.create(db).await?;
+        snippets.create(db).await?;
         relationships.create(db).await?;
 
         // Populate expanded license tables
@@ -0,0 +1,602 @@
+//! Ingestion of SPDX 3.0 documents, serialized as JSON-LD.
+
+use crate::{
+    graph::{
+        cpe::CpeCreator,
+        product::ProductInformation,
+        purl::creator::PurlCreator,
+        sbom::{
+            Checksum, FileCreator, NodeInfoParam, PackageCreator, PackageReference, References,
+            RelationshipCreator, SbomContext, SbomInformation,
+        },
+    },
+    service::Error,
+};
+use sbom_walker::report::ReportSink;
+use sea_orm::ConnectionTrait;
+use serde::Deserialize;
+use std::{collections::HashSet, str::FromStr};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::instrument;
+use trustify_common::{cpe::Cpe, purl::Purl};
+use trustify_entity::relationship::Relationship;
+
+/// The prefix of the IDs of the SPDX license list entries
+const SPDX_LICENSES: &str = "https://spdx.org/licenses/";
+
+/// An SPDX 3.0 document, serialized as JSON-LD.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Spdx3 {
+    /// All elements of the document
+    #[serde(rename = "@graph", default)]
+    pub graph: Vec<Element>,
+}
+
+/// An element of the graph, only the elements required for ingestion are parsed.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum Element {
+    CreationInfo(CreationInfo),
+    SpdxDocument(SpdxDocument),
+    #[serde(
+        rename = "Agent",
+        alias = "Person",
+        alias = "Organization",
+        alias = "SoftwareAgent",
+        alias = "Tool"
+    )]
+    Agent(Agent),
+    #[serde(rename = "software_Package")]
+    Package(Package),
+    #[serde(rename = "software_File")]
+    File(File),
+    #[serde(alias = "LifecycleScopedRelationship")]
+    Relationship(Spdx3Relationship),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationInfo {
+    /// The blank node ID, other elements use to reference the creation info
+    #[serde(rename = "@id", default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub created: Option<String>,
+    /// The IDs of the agents which created the document
+    #[serde(default)]
+    pub created_by: Vec<String>,
+    #[serde(default)]
+    pub spec_version: Option<String>,
+}
+
+/// The creation info of an element, either a reference to a [`CreationInfo`] or the info itself
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CreationInfoRef {
+    Id(String),
+    Inline(CreationInfo),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxDocument {
+    pub spdx_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub creation_info: Option<CreationInfoRef>,
+    #[serde(default)]
+    pub data_license: Option<String>,
+    /// The elements the document describes
+    #[serde(default)]
+    pub root_element: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Agent {
+    pub spdx_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Package {
+    pub spdx_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(rename = "software_packageVersion", default)]
+    pub version: Option<String>,
+    #[serde(rename = "software_packageUrl", default)]
+    pub purl: Option<String>,
+    /// The ID of the supplying agent
+    #[serde(default)]
+    pub supplied_by: Option<String>,
+    #[serde(default)]
+    pub external_identifier: Vec<ExternalIdentifier>,
+    #[serde(default)]
+    pub verified_using: Vec<IntegrityMethod>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct File {
+    pub spdx_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub verified_using: Vec<IntegrityMethod>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalIdentifier {
+    pub external_identifier_type: String,
+    pub identifier: String,
+}
+
+/// A method verifying the integrity of an element, only hashes are being used
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityMethod {
+    #[serde(default)]
+    pub algorithm: Option<String>,
+    #[serde(default)]
+    pub hash_value: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spdx3Relationship {
+    pub from: String,
+    #[serde(default)]
+    pub to: Vec<String>,
+    pub relationship_type: String,
+    /// The lifecycle scope of a `LifecycleScopedRelationship`
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl Spdx3 {
+    /// The `SpdxDocument` element of the graph
+    pub fn document(&self) -> Option<&SpdxDocument> {
+        self.graph.iter().find_map(|element| match element {
+            Element::SpdxDocument(document) => Some(document),
+            _ => None,
+        })
+    }
+
+    /// The creation info of the document
+    pub fn creation_info(&self) -> Option<&CreationInfo> {
+        match self.document()?.creation_info.as_ref()? {
+            CreationInfoRef::Inline(info) => Some(info),
+            CreationInfoRef::Id(id) => self.graph.iter().find_map(|element| match element {
+                Element::CreationInfo(info) if info.id.as_deref() == Some(id) => Some(info),
+                _ => None,
+            }),
+        }
+    }
+
+    /// The name of an agent, falling back to its ID
+    fn agent_name(&self, id: &str) -> String {
+        self.graph
+            .iter()
+            .find_map(|element| match element {
+                Element::Agent(agent) if agent.spdx_id == id => agent.name.clone(),
+                _ => None,
+            })
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn packages(&self) -> impl Iterator<Item = &Package> {
+        self.graph.iter().filter_map(|element| match element {
+            Element::Package(package) => Some(package),
+            _ => None,
+        })
+    }
+
+    fn files(&self) -> impl Iterator<Item = &File> {
+        self.graph.iter().filter_map(|element| match element {
+            Element::File(file) => Some(file),
+            _ => None,
+        })
+    }
+
+    fn relationships(&self) -> impl Iterator<Item = &Spdx3Relationship> {
+        self.graph.iter().filter_map(|element| match element {
+            Element::Relationship(relationship) => Some(relationship),
+            _ => None,
+        })
+    }
+
+    /// The IDs of the elements describing the document
+    fn describing(&self) -> HashSet<&str> {
+        let mut result = HashSet::new();
+
+        if let Some(document) = self.document() {
+            result.extend(document.root_element.iter().map(String::as_str));
+
+            for rel in self.relationships() {
+                if rel.relationship_type == "describes" && rel.from == document.spdx_id {
+                    result.extend(rel.to.iter().map(String::as_str));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Convert a hash to a checksum, ignoring other integrity methods and unknown algorithms
+fn checksum(method: &IntegrityMethod) -> Option<Checksum> {
+    let r#type = match method.algorithm.as_deref()? {
+        "md2" => "MD2",
+        "md4" => "MD4",
+        "md5" => "MD5",
+        "md6" => "MD6",
+        "sha1" => "SHA-1",
+        "sha224" => "SHA-224",
+        "sha256" => "SHA-256",
+        "sha384" => "SHA-384",
+        "sha512" => "SHA-512",
+        "sha3_224" => "SHA3-224",
+        "sha3_256" => "SHA3-256",
+        "sha3_384" => "SHA3-384",
+        "sha3_512" => "SHA3-512",
+        "blake2b256" => "BLAKE2b-256",
+        "blake2b384" => "BLAKE2b-384",
+        "blake2b512" => "BLAKE2b-512",
+        "blake3" => "BLAKE3",
+        "adler32" => "ADLER32",
+        _ => return None,
+    };
+
+    Some(Checksum {
+        r#type: r#type.into(),
+        value: method.hash_value.clone()?,
+    })
+}
+
+fn checksums(methods: &[IntegrityMethod]) -> Vec<Checksum> {
+    methods.iter().filter_map(checksum).collect()
+}
+
+pub struct Information<'a>(pub &'a Spdx3);
+
+impl<'a> From<Information<'a>> for SbomInformation {
+    fn from(value: Information<'a>) -> Self {
+        let sbom = value.0;
+        let document = sbom.document();
+        let creation_info = sbom.creation_info();
+
+        let published = creation_info
+            .and_then(|info| info.created.as_deref())
+            .and_then(|created| OffsetDateTime::parse(created, &Rfc3339).ok());
+
+        let authors = creation_info
+            .into_iter()
+            .flat_map(|info| &info.created_by)
+            .map(|id| sbom.agent_name(id))
+            .collect();
+
+        // suppliers of the describing packages
+
+        let describing = sbom.describing();
+        let suppliers = sbom
+            .packages()
+            .filter(|package| describing.contains(package.spdx_id.as_str()))
+            .filter_map(|package| package.supplied_by.as_deref())
+            .map(|id| sbom.agent_name(id))
+            .collect::<HashSet<_>>();
+
+        let data_licenses = document
+            .and_then(|document| document.data_license.as_deref())
+            .map(|license| license.trim_start_matches(SPDX_LICENSES).to_string())
+            .into_iter()
+            .collect();
+
+        Self {
+            node_id: document
+                .map(|document| document.spdx_id.clone())
+                .unwrap_or_default(),
+            name: document
+                .and_then(|document| document.name.clone())
+                .unwrap_or_default(),
+            published,
+            authors,
+            suppliers: suppliers.into_iter().collect(),
+            data_licenses,
+            properties: Default::default(),
+        }
+    }
+}
+
+impl SbomContext {
+    #[instrument(skip(db, sbom_data, warnings), ret(level=tracing::Level::DEBUG))]
+    pub async fn ingest_spdx3(
+        &self,
+        sbom_data: Spdx3,
+        warnings: &dyn ReportSink,
+        db: &impl ConnectionTrait,
+    ) -> Result<(), Error> {
+        let document_id = sbom_data
+            .document()
+            .map(|document| document.spdx_id.clone())
+            .unwrap_or_default();
+        let describing = sbom_data.describing();
+
+        // prepare packages
+
+        let mut purls = PurlCreator::new();
+        let mut cpes = CpeCreator::new();
+        let mut packages = PackageCreator::new(self.sbom.sbom_id);
+        let mut node_ids = HashSet::from([document_id.as_str()]);
+
+        for package in sbom_data.packages() {
+            let mut refs = Vec::new();
+            let mut product_cpe = None;
+
+            // the PURL may be provided as property, as well as external identifier
+            let purl_refs = package
+                .purl
+                .iter()
+                .chain(
+                    package
+                        .external_identifier
+                        .iter()
+                        .filter(|r| r.external_identifier_type == "packageUrl")
+                        .map(|r| &r.identifier),
+                )
+                .collect::<HashSet<_>>();
+            for purl in purl_refs {
+                match Purl::from_str(purl) {
+                    Ok(purl) => {
+                        refs.push(PackageReference::Purl(purl.clone()));
+                        purls.add(purl);
+                    }
+                    Err(err) => {
+                        tracing::info!("Failed to parse PURL ({purl}): {err}");
+                    }
+                }
+            }
+
+            for r in &package.external_identifier {
+                if !matches!(&*r.external_identifier_type, "cpe22" | "cpe23") {
+                    continue;
+                }
+                match Cpe::from_str(&r.identifier) {
+                    Ok(cpe) => {
+                        refs.push(PackageReference::Cpe(cpe.uuid()));
+                        cpes.add(cpe.clone());
+                        product_cpe = product_cpe.or(Some(cpe));
+                    }
+                    Err(err) => {
+                        tracing::info!("Failed to parse CPE ({}): {err}", r.identifier);
+                    }
+                }
+            }
+
+            let name = package.name.clone().unwrap_or_default();
+
+            if describing.contains(package.spdx_id.as_str()) {
+                let pr = self
+                    .graph
+                    .ingest_product(
+                        name.clone(),
+                        ProductInformation {
+                            vendor: package
+                                .supplied_by
+                                .as_deref()
+                                .map(|id| sbom_data.agent_name(id)),
+                            cpe: product_cpe,
+                        },
+                        db,
+                    )
+                    .await?;
+
+                if let Some(ver) = package.version.clone() {
+                    pr.ingest_product_version(ver, Some(self.sbom.sbom_id), db)
+                        .await?;
+                }
+            }
+
+            node_ids.insert(&package.spdx_id);
+            packages.add(
+                NodeInfoParam {
+                    node_id: package.spdx_id.clone(),
+                    name,
+                    group: None,
+                    version: package.version.clone(),
+                    package_license_info: vec![],
+                },
+                refs.iter(),
+                checksums(&package.verified_using),
+            );
+        }
+
+        // prepare files
+
+        let mut files = FileCreator::new(self.sbom.sbom_id);
+
+        for file in sbom_data.files() {
+            node_ids.insert(&file.spdx_id);
+            files.add(
+                file.spdx_id.clone(),
+                file.name.clone().unwrap_or_default(),
+                checksums(&file.verified_using),
+            );
+        }
+
+        // prepare relationships
+
+        let mut relationships = RelationshipCreator::new(self.sbom.sbom_id, ());
+
+        let described = describing
+            .iter()
+            .map(|root| (document_id.as_str(), Relationship::Describes, *root));
+        let related = sbom_data
+            .relationships()
+            .flat_map(|rel| rel.to.iter().filter_map(|to| map_relationship(rel, to)));
+
+        for (left, rel, right) in described.chain(related) {
+            // relationships may point to elements we don't ingest, like licenses
+            if !node_ids.contains(left) || !node_ids.contains(right) {
+                warnings.error(format!(
+                    "Skipping relationship of unknown elements: {left} {rel} {right}"
+                ));
+                continue;
+            }
+
+            relationships.relate(left.to_string(), rel, right.to_string());
+        }
+
+        // create all purls and CPEs
+
+        purls.create(db).await?;
+        cpes.create(db).await?;
+
+        // validate relationships before inserting
+
+        let doc_id = [document_id.as_str()];
+        let sources = References::new()
+            .add_source(&doc_id)
+            .add_source(&packages)
+            .add_source(&files);
+        relationships
+            .validate(sources)
+            .map_err(Error::InvalidContent)?;
+
+        // create packages, files, and relationships
+
+        packages.create(db).await?;
+        files.create(db).await?;
+        relationships.create(db).await?;
+
+        self.populate_describing_cpes(db).await?;
+        self.populate_ancestors(db).await?;
+
+        Ok(())
+    }
+}
+
+/// Map an SPDX 3.0 relationship to the relationship of the graph, in the direction the graph
+/// expects it.
+fn map_relationship<'a>(
+    rel: &'a Spdx3Relationship,
+    to: &'a str,
+) -> Option<(&'a str, Relationship, &'a str)> {
+    let from = rel.from.as_str();
+    let scope = rel.scope.as_deref();
+
+    Some(match rel.relationship_type.as_str() {
+        "ancestorOf" => (from, Relationship::AncestorOf, to),
+        "contains" => (from, Relationship::Contains, to),
+        "dependsOn" => match scope {
+            Some("development") => (from, Relationship::DevDependency, to),
+            Some("test") => (from, Relationship::TestDependency, to),
+            Some("runtime") => (from, Relationship::RuntimeDependency, to),
+            _ => (from, Relationship::Dependency, to),
+        },
+        "descendantOf" => (to, Relationship::AncestorOf, from),
+        "describes" => (from, Relationship::Describes, to),
+        "generates" => (from, Relationship::Generates, to),
+        "hasExample" => (from, Relationship::Example, to),
+        "hasOptionalDependency" => (from, Relationship::OptionalDependency, to),
+        "hasProvidedDependency" => (from, Relationship::ProvidedDependency, to),
+        "hasVariant" => (from, Relationship::Variant, to),
+        "usesTool" => match scope {
+            Some("development") => (from, Relationship::DevTool, to),
+            _ => (from, Relationship::BuildTool, to),
+        },
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_graph() -> Result<(), serde_json::Error> {
+        let sbom: Spdx3 = serde_json::from_value(json!({
+            "@context": "https://spdx.org/rdf/3.0.1/spdx-context.jsonld",
+            "@graph": [
+                {
+                    "type": "CreationInfo",
+                    "@id": "_:creationinfo",
+                    "created": "2024-03-06T00:00:00Z",
+                    "createdBy": ["urn:example:person"],
+                    "specVersion": "3.0.1"
+                },
+                {
+                    "type": "Person",
+                    "spdxId": "urn:example:person",
+                    "name": "Jane Doe",
+                    "creationInfo": "_:creationinfo"
+                },
+                {
+                    "type": "SpdxDocument",
+                    "spdxId": "urn:example:document",
+                    "name": "example",
+                    "creationInfo": "_:creationinfo",
+                    "dataLicense": "https://spdx.org/licenses/CC0-1.0",
+                    "rootElement": ["urn:example:package"]
+                },
+                {
+                    "type": "software_Package",
+                    "spdxId": "urn:example:package",
+                    "name": "example",
+                    "software_packageVersion": "1.0.0",
+                    "creationInfo": "_:creationinfo"
+                },
+                {
+                    "type": "simplelicensing_LicenseExpression",
+                    "spdxId": "urn:example:license",
+                    "creationInfo": "_:creationinfo"
+                }
+            ]
+        }))?;
+
+        assert!(matches!(sbom.graph[4], Element::Other));
+
+        let info = SbomInformation::from(Information(&sbom));
+        assert_eq!(info.node_id, "urn:example:document");
+        assert_eq!(info.name, "example");
+        assert_eq!(info.authors, vec!["Jane Doe".to_string()]);
+        assert_eq!(info.data_licenses, vec!["CC0-1.0".to_string()]);
+        assert!(info.published.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn relationship_direction() {
+        let rel = |relationship_type: &str, scope: Option<&str>| Spdx3Relationship {
+            from: "a".into(),
+            to: vec!["b".into()],
+            relationship_type: relationship_type.into(),
+            scope: scope.map(Into::into),
+        };
+
+        assert_eq!(
+            map_relationship(&rel("dependsOn", None), "b"),
+            Some(("a", Relationship::Dependency, "b"))
+        );
+        assert_eq!(
+            map_relationship(&rel("dependsOn", Some("test")), "b"),
+            Some(("a", Relationship::TestDependency, "b"))
+        );
+        assert_eq!(
+            map_relationship(&rel("descendantOf", None), "b"),
+            Some(("b", Relationship::AncestorOf, "a"))
+        );
+        assert_eq!(
+            map_relationship(&rel("hasDeclaredLicense", None), "b"),
+            None
+        );
+    }
+}
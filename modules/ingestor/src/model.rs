@@ -12,3 +12,26 @@ pub struct IngestResult {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
 }
+
+/// What ingesting a document would do, as determined by a dry run
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum DryRunOutcome {
+    /// The document is new
+    Insert,
+    /// The document is a new version of an already ingested document
+    Update,
+    /// The exact same document is already ingested
+    Skip,
+}
+
+/// The result of validating a document, without ingesting it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationOutcome {
+    /// The document was loaded, with the warnings of loading it
+    Loaded(Vec<String>),
+    /// The exact same document is already ingested, as the SBOM with this ID
+    Existed(String),
+}
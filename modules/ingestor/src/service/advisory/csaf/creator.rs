@@ -16,7 +16,7 @@ use crate::{
         advisory::csaf::{product_status::ProductStatus, util::ResolveProductIdCache},
     },
 };
-use csaf::{Csaf, definitions::ProductIdT, vulnerability::Remediation};
+use csaf::{definitions::ProductIdT, vulnerability::Remediation};
 use sea_orm::{ActiveValue::Set, ConnectionTrait, EntityTrait};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
@@ -32,15 +32,92 @@ use trustify_entity::{
 };
 use uuid::Uuid;
 
+/// Resolve the product, and its components, a product status refers to
+fn process_product_id(
+    cache: &ResolveProductIdCache,
+    product_id: &str,
+    status: &'static str,
+) -> ProductStatus {
+    let mut product = ProductStatus {
+        status,
+        ..Default::default()
+    };
+    let mut product_ids = vec![];
+    match cache.get_relationship(product_id) {
+        Some(rel) => {
+            let inner_id: &ProductIdT = &rel.product_reference;
+            let context = &rel.relates_to_product_reference;
+
+            // Find all products
+            product_ids.push(context.0.as_str());
+            // Find all components/packages within
+            product_ids.push(inner_id.0.as_str());
+        }
+        None => {
+            // If there's no relationship, find only products
+            product_ids.push(product_id);
+        }
+    };
+    for product_id in product_ids {
+        product = cache
+            .trace_product(product_id)
+            .iter()
+            .fold(product, |mut product, branch| {
+                product.update_from_branch(branch);
+                product
+            });
+    }
+
+    product
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ProductIdStatusMapping {
     pub purl_status_ids: Vec<Uuid>,
     pub product_status_ids: Vec<Uuid>,
 }
 
+/// Resolve the products of a list of product IDs, keyed by their product ID
+fn process_product_ids(
+    cache: &ResolveProductIdCache,
+    ps: &[ProductIdT],
+    status: &'static str,
+) -> Vec<(String, ProductStatus)> {
+    // resolving products only reads from the cache, so it can be done in parallel
+    #[cfg(feature = "csaf-parallel-processing")]
+    {
+        use rayon::prelude::*;
+        ps.par_iter()
+            .map(|r| (r.0.clone(), process_product_id(cache, &r.0, status)))
+            .collect()
+    }
+    #[cfg(not(feature = "csaf-parallel-processing"))]
+    {
+        ps.iter()
+            .map(|r| (r.0.clone(), process_product_id(cache, &r.0, status)))
+            .collect()
+    }
+}
+
+/// Resolve the products of all product statuses of a vulnerability
+///
+/// This is CPU bound and blocking, it must not be called from the async runtime directly.
+pub fn resolve_product_statuses(
+    cache: &ResolveProductIdCache,
+    product_status: &csaf::vulnerability::ProductStatus,
+) -> Vec<(String, ProductStatus)> {
+    [
+        (&product_status.fixed, "fixed"),
+        (&product_status.known_not_affected, "not_affected"),
+        (&product_status.known_affected, "affected"),
+    ]
+    .into_iter()
+    .flat_map(|(ps, status)| process_product_ids(cache, ps.as_deref().unwrap_or_default(), status))
+    .collect()
+}
+
 #[derive(Debug)]
-pub struct StatusCreator<'a> {
-    cache: ResolveProductIdCache<'a>,
+pub struct StatusCreator {
     advisory_id: Uuid,
     vulnerability_id: String,
     entries: HashSet<PurlStatus>,
@@ -49,11 +126,9 @@ pub struct StatusCreator<'a> {
     product_to_purl_statuses: HashMap<ProductStatus, Vec<PurlStatus>>,
 }
 
-impl<'a> StatusCreator<'a> {
-    pub fn new(csaf: &'a Csaf, advisory_id: Uuid, vulnerability_identifier: String) -> Self {
-        let cache = ResolveProductIdCache::new(csaf);
+impl StatusCreator {
+    pub fn new(advisory_id: Uuid, vulnerability_identifier: String) -> Self {
         Self {
-            cache,
             advisory_id,
             vulnerability_id: vulnerability_identifier,
             entries: HashSet::new(),
@@ -63,40 +138,11 @@ impl<'a> StatusCreator<'a> {
         }
     }
 
-    pub fn add_all(&mut self, ps: &Option<Vec<ProductIdT>>, status: &'static str) {
-        for r in ps.iter().flatten() {
-            let mut product = ProductStatus {
-                status,
-                ..Default::default()
-            };
-            let mut product_ids = vec![];
-            match self.cache.get_relationship(&r.0) {
-                Some(rel) => {
-                    let inner_id: &ProductIdT = &rel.product_reference;
-                    let context = &rel.relates_to_product_reference;
-
-                    // Find all products
-                    product_ids.push(&context.0);
-                    // Find all components/packages within
-                    product_ids.push(&inner_id.0);
-                }
-                None => {
-                    // If there's no relationship, find only products
-                    product_ids.push(&r.0);
-                }
-            };
-            for product_id in product_ids {
-                product = self.cache.trace_product(product_id).iter().fold(
-                    product,
-                    |mut product, branch| {
-                        product.update_from_branch(branch);
-                        product
-                    },
-                );
-            }
-
+    /// Add products, as resolved by [`resolve_product_statuses`]
+    pub fn add_all(&mut self, products: Vec<(String, ProductStatus)>) {
+        for (product_id, product) in products {
             self.product_id_to_product
-                .insert(r.0.clone(), product.clone());
+                .insert(product_id, product.clone());
             self.products.insert(product);
         }
     }
@@ -11,12 +11,17 @@ use crate::{
     model::IngestResult,
     service::{
         Error, Warnings,
-        advisory::csaf::{RemediationCreator, StatusCreator, extract_scores, util::gen_identifier},
+        advisory::csaf::{
+            RemediationCreator, StatusCreator, extract_scores,
+            product_status::ProductStatus,
+            resolve_product_statuses,
+            util::{ResolveProductIdCache, gen_identifier},
+        },
     },
 };
 use csaf::{
     Csaf,
-    vulnerability::{ProductStatus, Remediation, Vulnerability},
+    vulnerability::{Remediation, Vulnerability},
 };
 use hex::ToHex;
 use sea_orm::{ConnectionTrait, TransactionTrait};
@@ -119,9 +124,29 @@ impl<'g> CsafLoader<'g> {
         }
         vuln_creator.create(tx).await?;
 
+        // Resolving the products of the product statuses is CPU bound, keep it off the runtime
+        let (csaf, products) = tokio::task::spawn_blocking(move || {
+            let products: Vec<_> = {
+                let cache = ResolveProductIdCache::new(&csaf);
+                csaf.vulnerabilities
+                    .iter()
+                    .flatten()
+                    .map(|vuln| match (&vuln.cve, &vuln.product_status) {
+                        // we only ingest the product statuses of CVEs
+                        (Some(_), Some(product_status)) => {
+                            resolve_product_statuses(&cache, product_status)
+                        }
+                        _ => vec![],
+                    })
+                    .collect()
+            };
+            (csaf, products)
+        })
+        .await?;
+
         // Then process each vulnerability for linking and product status
-        for vuln in csaf.vulnerabilities.iter().flatten() {
-            self.ingest_vulnerability(&csaf, &advisory, vuln, tx)
+        for (vuln, products) in csaf.vulnerabilities.iter().flatten().zip(products) {
+            self.ingest_vulnerability(&csaf, &advisory, vuln, products, tx)
                 .await?;
         }
 
@@ -147,6 +172,7 @@ impl<'g> CsafLoader<'g> {
         csaf: &Csaf,
         advisory: &AdvisoryContext<'_>,
         vulnerability: &Vulnerability,
+        products: Vec<(String, ProductStatus)>,
         connection: &C,
     ) -> Result<(), Error> {
         let Some(cve_id) = &vulnerability.cve else {
@@ -174,11 +200,10 @@ impl<'g> CsafLoader<'g> {
             )
             .await?;
 
-        if let Some(product_status) = &vulnerability.product_status {
+        if vulnerability.product_status.is_some() {
             self.ingest_product_statuses(
-                csaf,
                 &advisory_vulnerability,
-                product_status,
+                products,
                 &vulnerability.remediations,
                 connection,
             )
@@ -191,14 +216,12 @@ impl<'g> CsafLoader<'g> {
     #[instrument(skip_all, err(level=tracing::Level::INFO))]
     async fn ingest_product_statuses<C: ConnectionTrait>(
         &self,
-        csaf: &Csaf,
         advisory_vulnerability: &AdvisoryVulnerabilityContext<'_>,
-        product_status: &ProductStatus,
+        products: Vec<(String, ProductStatus)>,
         remediations: &Option<Vec<Remediation>>,
         connection: &C,
     ) -> Result<(), Error> {
         let mut creator = StatusCreator::new(
-            csaf,
             advisory_vulnerability.advisory_vulnerability.advisory_id,
             advisory_vulnerability
                 .advisory_vulnerability
@@ -206,9 +229,7 @@ impl<'g> CsafLoader<'g> {
                 .clone(),
         );
 
-        creator.add_all(&product_status.fixed, "fixed");
-        creator.add_all(&product_status.known_not_affected, "not_affected");
-        creator.add_all(&product_status.known_affected, "affected");
+        creator.add_all(products);
 
         let product_id_mapping = creator.create(self.graph, connection).await?;
 
@@ -2,6 +2,7 @@ pub mod csaf;
 pub mod cve;
 pub mod nvd;
 pub mod osv;
+pub mod user_override;
 
 #[cfg(test)]
 pub mod test;
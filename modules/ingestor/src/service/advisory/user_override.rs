@@ -0,0 +1,197 @@
+use crate::{
+    graph::{
+        Graph,
+        advisory::{
+            AdvisoryInformation, AdvisoryVulnerabilityInformation,
+            version::{Version, VersionInfo, VersionSpec},
+        },
+    },
+    model::IngestResult,
+    service::Error,
+};
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use time::OffsetDateTime;
+use tracing::instrument;
+use trustify_common::{hashing::Digests, purl::Purl};
+use trustify_entity::{labels::Labels, version_scheme::VersionScheme};
+use utoipa::ToSchema;
+
+/// The label key marking advisories created from user overrides
+pub const ORIGIN_LABEL: &str = "origin";
+/// The label value marking advisories created from user overrides
+pub const ORIGIN_USER_OVERRIDE: &str = "user-override";
+/// The label key holding the justification of a user override
+pub const JUSTIFICATION_LABEL: &str = "justification";
+
+/// Check if the labels of an advisory mark it as created from a user override
+pub fn is_user_override(labels: &Labels) -> bool {
+    labels.0.get(ORIGIN_LABEL).map(String::as_str) == Some(ORIGIN_USER_OVERRIDE)
+}
+
+/// The status a user override can assign to a package
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideStatus {
+    Affected,
+    Fixed,
+    NotAffected,
+    UnderInvestigation,
+}
+
+impl OverrideStatus {
+    /// The slug of the matching entry in the status table
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::Affected => "affected",
+            Self::Fixed => "fixed",
+            Self::NotAffected => "not_affected",
+            Self::UnderInvestigation => "under_investigation",
+        }
+    }
+}
+
+/// The justification of a user override, following the VEX justifications
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema, strum::Display)]
+pub enum OverrideJustification {
+    ComponentNotPresent,
+    VulnerableCodeNotPresent,
+    VulnerableCodeNotInExecutePath,
+    VulnerableCodeCannotBeControlledByAdversary,
+    InlineMitigationsAlreadyExist,
+}
+
+/// A user provided override of the status of a package for a vulnerability
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({
+    "purl": "pkg:maven/org.apache.logging.log4j/log4j-core@2.13.3",
+    "status": "not_affected",
+    "justification": "VulnerableCodeNotPresent",
+    "impact_statement": "The JNDI lookup class is removed from the deployed artifact"
+}))]
+pub struct VulnerabilityOverride {
+    /// The package the override applies to. If it has a version, only that version is affected
+    /// by the override.
+    pub purl: String,
+    pub status: OverrideStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<OverrideJustification>,
+    /// A statement explaining the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact_statement: Option<String>,
+}
+
+/// The document stored for a user override
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserOverrideDocument {
+    pub vulnerability_id: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created: OffsetDateTime,
+    #[serde(flatten)]
+    pub r#override: VulnerabilityOverride,
+}
+
+impl UserOverrideDocument {
+    /// The identifier of the advisory created for the override.
+    ///
+    /// Overrides of the same package and vulnerability share the identifier, so that a newer
+    /// override deprecates the older ones.
+    pub fn identifier(&self) -> String {
+        format!(
+            "user-override:{}:{}",
+            self.vulnerability_id, self.r#override.purl
+        )
+    }
+}
+
+/// Loader creating an advisory, and a package status, for a user override
+pub struct UserOverrideLoader<'g> {
+    graph: &'g Graph,
+}
+
+impl<'g> UserOverrideLoader<'g> {
+    pub fn new(graph: &'g Graph) -> Self {
+        Self { graph }
+    }
+
+    #[instrument(skip(self, document, tx), err(level=tracing::Level::INFO))]
+    pub async fn load(
+        &self,
+        document: UserOverrideDocument,
+        digests: &Digests,
+        tx: &(impl ConnectionTrait + TransactionTrait),
+    ) -> Result<IngestResult, Error> {
+        let purl = Purl::from_str(&document.r#override.purl)
+            .map_err(|err| Error::InvalidContent(err.into()))?;
+
+        let identifier = document.identifier();
+        let labels = Labels::new()
+            .add(ORIGIN_LABEL, ORIGIN_USER_OVERRIDE)
+            .extend(
+                document
+                    .r#override
+                    .justification
+                    .map(|justification| (JUSTIFICATION_LABEL, justification.to_string())),
+            );
+
+        let information = AdvisoryInformation {
+            id: identifier.clone(),
+            title: Some(format!(
+                "Override of {} for {}",
+                document.vulnerability_id, document.r#override.purl
+            )),
+            issuer: None,
+            published: Some(document.created),
+            // required for deprecating previous overrides
+            modified: Some(document.created),
+            withdrawn: None,
+            version: None,
+        };
+
+        let advisory = self
+            .graph
+            .ingest_advisory(&identifier, labels, digests, information, tx)
+            .await?;
+
+        let advisory_vuln = advisory
+            .link_to_vulnerability(
+                &document.vulnerability_id,
+                Some(AdvisoryVulnerabilityInformation {
+                    title: None,
+                    summary: None,
+                    description: document.r#override.impact_statement.clone(),
+                    reserved_date: None,
+                    discovery_date: None,
+                    release_date: None,
+                    cwes: None,
+                }),
+                tx,
+            )
+            .await?;
+
+        let spec = match &purl.version {
+            Some(version) => VersionSpec::Exact(version.clone()),
+            None => VersionSpec::Range(Version::Unbounded, Version::Unbounded),
+        };
+
+        advisory_vuln
+            .ingest_package_status(
+                None,
+                &purl,
+                document.r#override.status.slug(),
+                VersionInfo {
+                    scheme: VersionScheme::from(purl.ty.as_str()),
+                    spec,
+                },
+                tx,
+            )
+            .await?;
+
+        Ok(IngestResult {
+            id: advisory.advisory.id.to_string(),
+            document_id: Some(identifier),
+            warnings: vec![],
+        })
+    }
+}
@@ -0,0 +1,173 @@
+//! Reading archives of documents, like the ones produced by build systems
+
+use crate::{model::IngestResult, service::Error};
+use anyhow::anyhow;
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{Cursor, Read},
+    path::{Component, Path},
+};
+
+/// A file of an archive
+pub struct ArchiveEntry {
+    /// The full name of the file, inside the archive
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// A file of an archive which could not be read
+#[derive(Debug)]
+pub struct ArchiveEntryError {
+    /// The full name of the file, inside the archive
+    pub name: String,
+    pub error: Error,
+}
+
+/// Limits of reading an archive, after decompression
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArchiveLimits {
+    /// The size limit of a single file
+    pub entry: usize,
+    /// The maximum number of entries, including directories
+    pub entries: usize,
+    /// The size limit of all files together
+    pub total: usize,
+}
+
+/// Reads the files of a ZIP archive, one at a time.
+///
+/// Directories and `.DS_Store` files are skipped. An archive declaring to exceed the
+/// [`ArchiveLimits`] is rejected up front with [`Error::PayloadTooLarge`], an archive containing
+/// the same file twice with [`Error::InvalidContent`].
+///
+/// Files which can't be read, or which turn out to exceed the limit of a single file, are
+/// reported as failed entries, without affecting the other files. Once the files turn out to
+/// exceed the limit of all files, the remaining files are reported as failed, without reading them.
+pub struct ZipReader<'a> {
+    zip: zip::ZipArchive<Cursor<&'a [u8]>>,
+    limits: ArchiveLimits,
+    /// The names of the files to read, by their index. `None` for entries to skip.
+    names: Vec<Option<String>>,
+    next: usize,
+    total: usize,
+    exceeded: bool,
+}
+
+impl<'a> ZipReader<'a> {
+    /// Open an archive, rejecting it up front if it declares exceeding the limits
+    pub fn new(buffer: &'a [u8], limits: ArchiveLimits) -> Result<Self, Error> {
+        let mut zip = zip::ZipArchive::new(Cursor::new(buffer))?;
+
+        if zip.len() > limits.entries {
+            return Err(Error::PayloadTooLarge);
+        }
+
+        let mut names = Vec::with_capacity(zip.len());
+        let mut seen = HashSet::new();
+        let mut declared = 0u64;
+        for i in 0..zip.len() {
+            let file = zip.by_index_raw(i)?;
+            declared = declared.saturating_add(file.size());
+
+            let name = match file.enclosed_name() {
+                Some(name) if file.is_file() && !is_ds_store(file.name()) => Some(normalize(&name)),
+                _ => None,
+            };
+
+            if let Some(name) = &name {
+                // the result is keyed by name, so a second file must not replace the first one
+                if !seen.insert(name.clone()) {
+                    return Err(Error::InvalidContent(anyhow!(
+                        "duplicate file in archive: {name}"
+                    )));
+                }
+            }
+
+            names.push(name);
+        }
+        if declared > limits.total as u64 {
+            return Err(Error::PayloadTooLarge);
+        }
+
+        Ok(Self {
+            zip,
+            limits,
+            names,
+            next: 0,
+            total: 0,
+            exceeded: false,
+        })
+    }
+
+    /// Read the next file, returns `None` once all files have been processed
+    pub fn next_entry(&mut self) -> Option<Result<ArchiveEntry, ArchiveEntryError>> {
+        while self.next < self.names.len() {
+            let index = self.next;
+            self.next += 1;
+
+            let Some(name) = self.names[index].take() else {
+                continue;
+            };
+
+            tracing::debug!("archive entry: {name}");
+
+            if self.exceeded {
+                return Some(Err(ArchiveEntryError {
+                    name,
+                    error: Error::PayloadTooLarge,
+                }));
+            }
+
+            return Some(match self.read(index) {
+                Ok(data) => Ok(ArchiveEntry { name, data }),
+                Err(error) => Err(ArchiveEntryError { name, error }),
+            });
+        }
+
+        None
+    }
+
+    fn read(&mut self, index: usize) -> Result<Vec<u8>, Error> {
+        let file = self.zip.by_index(index)?;
+
+        // don't trust the sizes declared by the archive
+        let remaining = self.limits.total - self.total;
+        let limit = self.limits.entry.min(remaining);
+        let mut data = Vec::new();
+        file.take(limit as u64 + 1).read_to_end(&mut data)?;
+        if data.len() > limit {
+            self.exceeded = data.len() > remaining;
+            return Err(Error::PayloadTooLarge);
+        }
+        self.total += data.len();
+
+        Ok(data)
+    }
+}
+
+fn is_ds_store(name: &str) -> bool {
+    name == ".DS_Store" || name.ends_with("/.DS_Store")
+}
+
+/// Normalize an enclosed path, so that different spellings of it result in the same name
+fn normalize(path: &Path) -> String {
+    let mut segments = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_string_lossy()),
+            Component::ParentDir => {
+                segments.pop();
+            }
+            _ => {}
+        }
+    }
+    segments.join("/")
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct ArchiveIngestResult {
+    /// Files which got ingested, by their name
+    pub files: BTreeMap<String, IngestResult>,
+    /// Files which could not be ingested, by their name, along with the reason
+    pub failures: BTreeMap<String, String>,
+}
@@ -1,16 +1,22 @@
 use crate::{
-    graph::{Graph, sbom::clearly_defined::Curation},
+    graph::{
+        Graph,
+        sbom::{SbomContext, clearly_defined::Curation},
+    },
     model::IngestResult,
     service::{
         Error, JsonSource,
         advisory::{
-            csaf::loader::CsafLoader, cve::loader::CveLoader, nvd::loader::NvdLoader,
-            nvd::schema::NvdCve, osv::loader::OsvLoader,
+            csaf::{loader::CsafLoader, util::gen_identifier},
+            cve::loader::CveLoader,
+            nvd::loader::NvdLoader,
+            nvd::schema::NvdCve,
+            osv::loader::OsvLoader,
         },
         sbom::{
             clearly_defined::ClearlyDefinedLoader,
             clearly_defined_curation::ClearlyDefinedCurationLoader, cyclonedx::CyclonedxLoader,
-            spdx::SpdxLoader,
+            spdx::SpdxLoader, spdx3::Spdx3Loader,
         },
         weakness::CweCatalogLoader,
     },
@@ -52,6 +58,8 @@ pub enum DetectedDocument {
     Osv(Box<Vulnerability>),
     /// SPDX keeps the raw Value because the loader applies license fixups before ingestion.
     Spdx(serde_json::Value),
+    /// SPDX 3.0, serialized as JSON-LD. Kept as raw Value, as the loader extracts the `@graph`.
+    Spdx3(serde_json::Value),
     CycloneDx(Box<serde_cyclonedx::cyclonedx::v_1_6::CycloneDx>),
     ClearlyDefined(serde_json::Value),
     ClearlyDefinedCuration(Box<Curation>),
@@ -59,6 +67,42 @@ pub enum DetectedDocument {
     CweCatalog(Vec<u8>),
 }
 
+/// The identifier of a document, shared by all versions of the document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DocumentIdentifier {
+    /// The document ID of an SBOM
+    Sbom(String),
+    /// The identifier of an advisory
+    Advisory(String),
+}
+
+impl DetectedDocument {
+    /// The identifier the document gets ingested with, if it has one.
+    pub fn identifier(&self) -> Option<DocumentIdentifier> {
+        match self {
+            Self::Csaf(csaf) => Some(DocumentIdentifier::Advisory(gen_identifier(csaf))),
+            Self::Cve(cve) => Some(DocumentIdentifier::Advisory(cve.id().to_string())),
+            Self::Nvd(cve) => Some(DocumentIdentifier::Advisory(cve.id.clone())),
+            Self::Osv(osv) => Some(DocumentIdentifier::Advisory(osv.id.clone())),
+            Self::Spdx(value) => value["documentNamespace"]
+                .as_str()
+                .map(|id| DocumentIdentifier::Sbom(id.to_string())),
+            Self::Spdx3(value) => value["@graph"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|element| element["type"] == "SpdxDocument")
+                .and_then(|document| document["spdxId"].as_str())
+                .map(|id| DocumentIdentifier::Sbom(id.to_string())),
+            Self::CycloneDx(cdx) => cdx
+                .serial_number
+                .as_ref()
+                .map(|sn| DocumentIdentifier::Sbom(format!("{}/{}", sn, cdx.version.unwrap_or(0)))),
+            Self::ClearlyDefined(_) | Self::ClearlyDefinedCuration(_) | Self::CweCatalog(_) => None,
+        }
+    }
+}
+
 /// Stateful document format detector that parses raw bytes through
 /// wire format detection, intermediate representation, content type
 /// detection, and domain type parsing, reusing work at each stage.
@@ -179,6 +223,11 @@ impl DocumentDetector {
                     .load(labels, value, digests, tx)
                     .await
             }
+            DetectedDocument::Spdx3(value) => {
+                Spdx3Loader::new(graph)
+                    .load(labels, value, digests, tx)
+                    .await
+            }
             DetectedDocument::CycloneDx(cdx) => {
                 CyclonedxLoader::new(graph)
                     .ingest(labels, cdx, digests, tx)
@@ -201,6 +250,34 @@ impl DocumentDetector {
             }
         }
     }
+
+    /// Consumes self and ingests the parsed document again, into an already ingested SBOM.
+    ///
+    /// All content previously ingested for the SBOM is replaced. Only SBOM formats are
+    /// supported. Returns the warnings of ingesting the document.
+    #[instrument(skip_all, err(level = tracing::Level::INFO))]
+    pub async fn reprocess(
+        self,
+        sbom: SbomContext,
+        tx: &impl ConnectionTrait,
+    ) -> Result<Vec<String>, Error> {
+        let graph = sbom.graph.clone();
+        match self.document {
+            DetectedDocument::Spdx(value) => {
+                SpdxLoader::new(&graph).reprocess(sbom, value, tx).await
+            }
+            DetectedDocument::Spdx3(value) => {
+                Spdx3Loader::new(&graph).reprocess(sbom, value, tx).await
+            }
+            DetectedDocument::CycloneDx(cdx) => {
+                CyclonedxLoader::new(&graph).reprocess(sbom, cdx, tx).await
+            }
+            _ => Err(Error::UnsupportedFormat(format!(
+                "Only SBOMs can be reprocessed, not {}",
+                self.format
+            ))),
+        }
+    }
 }
 
 /// Determine the wire format from the first non-whitespace byte.
@@ -253,13 +330,17 @@ fn detect_format(value: &serde_json::Value, hint: Format) -> Result<Format, Erro
         return Ok(Format::CVE);
     }
 
+    if Format::SPDX.matches_hint(hint) && is_spdx3(value) {
+        return Ok(Format::SPDX);
+    }
+
     if Format::SPDX.matches_hint(hint)
         && let Some(ver) = value.get("spdxVersion").and_then(|v| v.as_str())
     {
         return match ver {
             "SPDX-2.2" | "SPDX-2.3" => Ok(Format::SPDX),
             other => Err(Error::UnsupportedFormat(format!(
-                "SPDX version {other} is unsupported; try 2.2 or 2.3"
+                "SPDX version {other} is unsupported; try 2.2, 2.3 or 3.0"
             ))),
         };
     }
@@ -293,6 +374,22 @@ fn detect_format(value: &serde_json::Value, hint: Format) -> Result<Format, Erro
     )))
 }
 
+/// Check if a document is an SPDX 3.0 document.
+///
+/// SPDX 3.0 JSON-LD documents don't need to carry a version, besides the one of their context.
+fn is_spdx3(value: &serde_json::Value) -> bool {
+    let version = value
+        .get("spdxVersion")
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.starts_with("SPDX-3."));
+    let context = value
+        .get("@context")
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.contains("spdx.org/rdf/3."));
+
+    version || context
+}
+
 /// Parse a JSON-compatible source into a `DetectedDocument` for a known format.
 ///
 /// Accepts both `&[u8]` (for JSON with known format — direct parse, no Value
@@ -314,9 +411,13 @@ fn parse_format(source: impl JsonSource, format: Format) -> Result<DetectedDocum
         Format::OSV => Ok(DetectedDocument::Osv(Box::new(
             source.parse_json().map_err(map_err)?,
         ))),
-        Format::SPDX => Ok(DetectedDocument::Spdx(
-            source.parse_json().map_err(map_err)?,
-        )),
+        Format::SPDX => {
+            let value: serde_json::Value = source.parse_json().map_err(map_err)?;
+            match is_spdx3(&value) {
+                true => Ok(DetectedDocument::Spdx3(value)),
+                false => Ok(DetectedDocument::Spdx(value)),
+            }
+        }
         Format::CycloneDX => Ok(DetectedDocument::CycloneDx(
             source.parse_json().map_err(map_err)?,
         )),
@@ -440,6 +541,18 @@ mod test {
         Ok(())
     }
 
+    #[test(tokio::test)]
+    async fn detect_spdx3() -> Result<(), anyhow::Error> {
+        let bytes = document_bytes("spdx/simple-3.0.json").await?;
+        let detector = DocumentDetector::detect_as(&bytes, Format::SBOM)?;
+        assert_eq!(detector.format(), Format::SPDX);
+        assert!(matches!(
+            detector.into_document(),
+            DetectedDocument::Spdx3(_)
+        ));
+        Ok(())
+    }
+
     #[test(tokio::test)]
     async fn detect_cwe_catalog() -> Result<(), anyhow::Error> {
         let cwe = document_read("cwec_latest.xml.zip")?;
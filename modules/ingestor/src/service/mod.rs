@@ -1,4 +1,5 @@
 pub mod advisory;
+pub mod archive;
 pub mod dataset;
 mod detect;
 pub mod sbom;
@@ -6,28 +7,48 @@ pub mod weakness;
 
 mod format;
 mod json;
-pub use detect::{DetectedDocument, DocumentDetector, WireFormat};
+pub use detect::{DetectedDocument, DocumentDetector, DocumentIdentifier, WireFormat};
 pub use format::Format;
 pub use json::JsonSource;
 
 use crate::graph::Graph;
 use crate::{
-    model::IngestResult,
-    service::dataset::{DatasetIngestResult, DatasetLoader},
+    model::{DryRunOutcome, IngestResult, ValidationOutcome},
+    service::{
+        advisory::user_override::{
+            UserOverrideDocument, UserOverrideLoader, VulnerabilityOverride,
+        },
+        archive::{ArchiveIngestResult, ArchiveLimits, ZipReader},
+        dataset::{DatasetIngestResult, DatasetLoader},
+        sbom::spdx::SbomExternalRefResolver,
+    },
 };
 use actix_web::{HttpResponse, ResponseError, body::BoxBody};
 use anyhow::anyhow;
+use hex::ToHex;
+use opentelemetry::{
+    KeyValue, global,
+    metrics::{Counter, Histogram},
+};
 use parking_lot::Mutex;
 use sbom_walker::report::ReportSink;
 use sea_orm::error::DbErr;
-use sea_orm::{ConnectionTrait, TransactionTrait};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, TransactionTrait,
+};
 use std::{fmt::Debug, sync::Arc, time::Instant};
+use time::OffsetDateTime;
 use tokio::task::JoinError;
 use tracing::instrument;
-use trustify_common::{db::DatabaseErrors, error::ErrorInformation, id::IdError};
-use trustify_entity::labels::Labels;
+use trustify_common::{
+    db::DatabaseErrors, error::ErrorInformation, hashing::Digests, id::IdError,
+    metrics::duration_histogram,
+};
+use trustify_entity::{advisory, labels::Labels, sbom, source_document};
 use trustify_module_analysis::service::AnalysisService;
 use trustify_module_storage::service::{StorageBackend, dispatch::DispatchBackend};
+use trustify_module_webhook::{model::WebhookEvent, service::notify};
+use uuid::Uuid;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -196,6 +217,9 @@ pub struct IngestorService {
     graph: Graph,
     storage: DispatchBackend,
     analysis: Option<AnalysisService>,
+    external_refs: Option<SbomExternalRefResolver>,
+    ingested: Counter<u64>,
+    ingest_duration: Histogram<f64>,
 }
 
 impl IngestorService {
@@ -204,13 +228,29 @@ impl IngestorService {
         storage: impl Into<DispatchBackend>,
         analysis: Option<AnalysisService>,
     ) -> Self {
+        let meter = global::meter("IngestorService");
+
         Self {
             graph,
             storage: storage.into(),
             analysis,
+            external_refs: None,
+            ingested: meter.u64_counter("ingested_documents").build(),
+            ingest_duration: duration_histogram(
+                &meter,
+                "ingest_duration",
+                "Duration of ingesting a document",
+            ),
         }
     }
 
+    /// Fetch and ingest the external documents referenced by ingested SPDX SBOMs, when calling
+    /// [`Self::resolve_external_refs`].
+    pub fn with_external_ref_resolver(mut self, resolver: SbomExternalRefResolver) -> Self {
+        self.external_refs = Some(resolver);
+        self
+    }
+
     pub fn storage(&self) -> &DispatchBackend {
         &self.storage
     }
@@ -252,9 +292,208 @@ impl IngestorService {
             humantime::Duration::from(duration),
         );
 
+        let attributes = [
+            KeyValue::new("kind", kind(fmt)),
+            KeyValue::new("format", <&'static str>::from(fmt)),
+        ];
+        self.ingested.add(1, &attributes);
+        self.ingest_duration
+            .record(duration.as_secs_f64(), &attributes);
+
+        if let Some(event) = event(fmt) {
+            let data = serde_json::json!({
+                "id": result.id,
+                "document_id": result.document_id,
+                "format": <&'static str>::from(fmt),
+            });
+            notify(event, data, tx).await?;
+        }
+
         Ok(result)
     }
 
+    /// Check what ingesting a document would do, without storing or ingesting it.
+    ///
+    /// The document is parsed and validated the same way as when ingesting it. A document
+    /// counts as an update if a document with the same identifier was ingested before.
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub async fn dry_run(
+        &self,
+        bytes: &[u8],
+        format: Format,
+        connection: &impl ConnectionTrait,
+    ) -> Result<DryRunOutcome, Error> {
+        let detector = DocumentDetector::detect_as(bytes, format)?;
+
+        let sha256: String = Digests::digest(bytes).sha256.encode_hex();
+        let existing = source_document::Entity::find()
+            .filter(source_document::Column::Sha256.eq(sha256))
+            .count(connection)
+            .await?;
+        if existing > 0 {
+            return Ok(DryRunOutcome::Skip);
+        }
+
+        let versions = match detector.into_document().identifier() {
+            Some(DocumentIdentifier::Sbom(id)) => {
+                sbom::Entity::find()
+                    .filter(sbom::Column::DocumentId.eq(id))
+                    .filter(sbom::Column::DeletedAt.is_null())
+                    .count(connection)
+                    .await?
+            }
+            Some(DocumentIdentifier::Advisory(id)) => {
+                advisory::Entity::find()
+                    .filter(advisory::Column::Identifier.eq(id))
+                    .count(connection)
+                    .await?
+            }
+            None => 0,
+        };
+
+        Ok(match versions {
+            0 => DryRunOutcome::Insert,
+            _ => DryRunOutcome::Update,
+        })
+    }
+
+    /// Validate a document by loading it, without storing it or notifying anyone about it.
+    ///
+    /// The document gets loaded into the database using the provided transaction, which the
+    /// caller must roll back. Loading skips documents which are already ingested, so instead of
+    /// reporting such a document as valid without looking at it, the ID of the existing SBOM
+    /// gets returned.
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub async fn validate(
+        &self,
+        bytes: &[u8],
+        format: Format,
+        tx: &(impl ConnectionTrait + TransactionTrait),
+    ) -> Result<ValidationOutcome, Error> {
+        let detector = DocumentDetector::detect_as(bytes, format)?;
+        let digests = Digests::digest(bytes);
+
+        let sha256: String = digests.sha256.encode_hex();
+        if let Some(existing) = self.graph.get_sbom_by_digest(&sha256, tx).await? {
+            return Ok(ValidationOutcome::Existed(
+                existing.sbom.sbom_id.to_string(),
+            ));
+        }
+
+        let result = detector
+            .load(&self.graph, Labels::new(), None, &digests, tx)
+            .await?;
+
+        Ok(ValidationOutcome::Loaded(result.warnings))
+    }
+
+    /// Ingest a user provided override of the status of a package for a vulnerability.
+    ///
+    /// The override is stored as a document of its own, and ingested as an advisory, labeled
+    /// with `origin=user-override`. A newer override for the same package and vulnerability
+    /// deprecates the previous one.
+    #[instrument(skip(self, tx), err(level=tracing::Level::INFO))]
+    pub async fn ingest_override(
+        &self,
+        vulnerability_id: &str,
+        r#override: VulnerabilityOverride,
+        tx: &(impl ConnectionTrait + TransactionTrait),
+    ) -> Result<IngestResult, Error> {
+        let document = UserOverrideDocument {
+            vulnerability_id: vulnerability_id.to_string(),
+            created: OffsetDateTime::now_utc(),
+            r#override,
+        };
+        let bytes = serde_json::to_vec(&document)?;
+
+        let result = self
+            .storage
+            .store(&bytes[..])
+            .await
+            .map_err(|err| Error::Storage(anyhow!("{err}")))?;
+
+        UserOverrideLoader::new(&self.graph)
+            .load(document, &result.digests, tx)
+            .await
+    }
+
+    /// Fetch and ingest the external documents referenced by an ingested SPDX SBOM, which are
+    /// not ingested yet, along with the documents they reference in turn.
+    ///
+    /// This must be called after the transaction ingesting the SBOM was committed. Documents are
+    /// fetched outside of any transaction, and each of them gets ingested in a transaction of its
+    /// own. Failing to resolve a document is reported as a warning of the result.
+    #[instrument(skip_all, fields(id = result.id), err(level=tracing::Level::INFO))]
+    pub async fn resolve_external_refs(
+        &self,
+        result: &mut IngestResult,
+        cache: Cache,
+        db: &impl TransactionTrait,
+    ) -> Result<(), Error> {
+        let Some(resolver) = &self.external_refs else {
+            return Ok(());
+        };
+
+        let sbom_id = Uuid::parse_str(&result.id).map_err(|err| Error::Generic(err.into()))?;
+        let mut pending = vec![(sbom_id, 0)];
+
+        while let Some((sbom_id, depth)) = pending.pop() {
+            if depth >= resolver.max_depth() {
+                continue;
+            }
+
+            let documents = {
+                let tx = db.begin().await?;
+                resolver.unresolved(&self.graph, sbom_id, &tx).await?
+            };
+
+            for document in documents {
+                let data = match resolver.fetch(&document).await {
+                    Ok(data) => data,
+                    Err(err) => {
+                        result.warnings.push(format!(
+                            "Failed to fetch external document {}: {err}",
+                            document.uri
+                        ));
+                        continue;
+                    }
+                };
+
+                log::info!("Ingesting external document: {}", document.uri);
+
+                let tx = db.begin().await?;
+                let ingested = self
+                    .ingest(
+                        &data,
+                        Format::SPDX,
+                        Labels::new().add("source", &document.uri),
+                        None,
+                        cache,
+                        &tx,
+                    )
+                    .await;
+
+                match ingested {
+                    Ok(ingested) => {
+                        tx.commit().await?;
+                        result.warnings.extend(ingested.warnings);
+                        pending.push((
+                            Uuid::parse_str(&ingested.id)
+                                .map_err(|err| Error::Generic(err.into()))?,
+                            depth + 1,
+                        ));
+                    }
+                    Err(err) => result.warnings.push(format!(
+                        "Failed to ingest external document {}: {err}",
+                        document.uri
+                    )),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Ingest a dataset archive
     #[instrument(skip(self, bytes, tx), err(level=tracing::Level::INFO))]
     pub async fn ingest_dataset(
@@ -268,6 +507,76 @@ impl IngestorService {
         loader.load(labels.into(), bytes, tx).await
     }
 
+    /// Ingest all SBOMs and advisories of a ZIP archive
+    ///
+    /// Each file gets read and ingested in a transaction of its own. Files which fail to be read or
+    /// ingested, or which are neither an SBOM nor an advisory, are reported as failures of the
+    /// result, without affecting the other files. Once the files exceed the total size limit, the
+    /// remaining files are reported as failures too.
+    #[instrument(skip(self, bytes, db), err(level=tracing::Level::INFO))]
+    pub async fn ingest_archive(
+        &self,
+        bytes: &[u8],
+        labels: impl Into<Labels> + Debug,
+        limits: ArchiveLimits,
+        db: &impl TransactionTrait,
+    ) -> Result<ArchiveIngestResult, Error> {
+        let labels = labels.into();
+        let mut result = ArchiveIngestResult::default();
+
+        let mut reader = ZipReader::new(bytes, limits)?;
+        while let Some(entry) = reader.next_entry() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    result.failures.insert(err.name, err.error.to_string());
+                    continue;
+                }
+            };
+
+            let format = match DocumentDetector::detect(&entry.data) {
+                Ok(detector) => detector.format(),
+                Err(err) => {
+                    result.failures.insert(entry.name, err.to_string());
+                    continue;
+                }
+            };
+
+            if !format.matches_hint(Format::SBOM) && !format.matches_hint(Format::Advisory) {
+                result.failures.insert(
+                    entry.name,
+                    format!("neither an SBOM nor an advisory, but: {format}"),
+                );
+                continue;
+            }
+
+            let labels = labels.clone().add("archiveFile", &entry.name);
+
+            let tx = db.begin().await?;
+            match self
+                .ingest(&entry.data, format, labels, None, Cache::Skip, &tx)
+                .await
+            {
+                Ok(ingested) => {
+                    tx.commit().await?;
+                    result.files.insert(entry.name, ingested);
+                }
+                Err(err) => {
+                    result.failures.insert(entry.name, err.to_string());
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Drop the graph of an SBOM from the analysis cache, after its content changed
+    pub fn clear_graph_cache(&self, sbom_id: Uuid) {
+        if let Some(analysis) = &self.analysis {
+            analysis.clear_graph(sbom_id);
+        }
+    }
+
     /// If appropriate, load result into analysis graph cache
     #[instrument(skip(self))]
     async fn load_graph_cache(&self, fmt: Format, result: &IngestResult, wait: bool) {
@@ -299,6 +608,28 @@ impl IngestorService {
     }
 }
 
+/// The kind of document a format carries, used for distinguishing metrics
+fn kind(format: Format) -> &'static str {
+    if format.matches_hint(Format::SBOM) {
+        "sbom"
+    } else if format.matches_hint(Format::Advisory) {
+        "advisory"
+    } else {
+        "other"
+    }
+}
+
+/// The webhook event of ingesting a document of a format
+fn event(format: Format) -> Option<WebhookEvent> {
+    if format.matches_hint(Format::SBOM) {
+        Some(WebhookEvent::SbomIngested)
+    } else if format.matches_hint(Format::Advisory) {
+        Some(WebhookEvent::AdvisoryIngested)
+    } else {
+        None
+    }
+}
+
 /// Capture warnings from the import process
 #[derive(Default)]
 pub(crate) struct Warnings(Arc<Mutex<Vec<String>>>);
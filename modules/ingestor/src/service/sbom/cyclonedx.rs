@@ -1,5 +1,8 @@
 use crate::{
-    graph::{Graph, Outcome, sbom::cyclonedx},
+    graph::{
+        Graph, Outcome,
+        sbom::{SbomContext, cyclonedx},
+    },
     model::IngestResult,
     service::{Error, JsonSource, Warnings},
 };
@@ -86,6 +89,24 @@ impl<'g> CyclonedxLoader<'g> {
             warnings: warnings.into(),
         })
     }
+
+    /// Ingest the document of an already ingested SBOM again, replacing its content.
+    ///
+    /// Returns the warnings of ingesting the document.
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub(crate) async fn reprocess(
+        &self,
+        sbom: SbomContext,
+        cdx: Box<serde_cyclonedx::cyclonedx::v_1_6::CycloneDx>,
+        tx: &impl ConnectionTrait,
+    ) -> Result<Vec<String>, Error> {
+        let warnings = Warnings::default();
+
+        let sbom = sbom.reset(cyclonedx::Information(&cdx), tx).await?;
+        sbom.ingest_cyclonedx(cdx, &warnings, tx).await?;
+
+        Ok(warnings.into())
+    }
 }
 
 enum Kind {
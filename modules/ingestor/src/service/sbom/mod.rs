@@ -2,3 +2,4 @@ pub mod clearly_defined;
 pub mod clearly_defined_curation;
 pub mod cyclonedx;
 pub mod spdx;
+pub mod spdx3;
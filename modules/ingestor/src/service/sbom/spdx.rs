@@ -1,16 +1,34 @@
 use crate::{
     graph::{
         Graph, Outcome,
-        sbom::spdx::{self},
+        sbom::{
+            SbomContext,
+            spdx::{self},
+        },
     },
     model::IngestResult,
     service::{Error, Warnings},
 };
-use sea_orm::{ConnectionTrait, TransactionTrait};
+use anyhow::bail;
+use bytes::{Bytes, BytesMut};
+use hex::ToHex;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect,
+    TransactionTrait,
+};
 use serde_json::Value;
+use std::time::Duration;
 use tracing::instrument;
-use trustify_common::{hashing::Digests, sbom::spdx::parse_spdx};
-use trustify_entity::labels::Labels;
+use trustify_common::{
+    hashing::Digests,
+    sbom::{SbomLocator, spdx::parse_spdx},
+};
+use trustify_entity::{
+    labels::Labels,
+    sbom, sbom_external_node,
+    sbom_external_node::{DiscriminatorType, ExternalType},
+};
+use uuid::Uuid;
 
 pub struct SpdxLoader<'g> {
     graph: &'g Graph,
@@ -69,15 +87,358 @@ impl<'g> SpdxLoader<'g> {
             warnings: warnings.into(),
         })
     }
+
+    /// Ingest the document of an already ingested SBOM again, replacing its content.
+    ///
+    /// Returns the warnings of ingesting the document.
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub async fn reprocess(
+        &self,
+        sbom: SbomContext,
+        json: Value,
+        tx: &impl ConnectionTrait,
+    ) -> Result<Vec<String>, Error> {
+        let warnings = Warnings::default();
+
+        let (spdx, _) = parse_spdx(&warnings, json)?;
+
+        let sbom = sbom.reset(spdx::Information(&spdx), tx).await?;
+        sbom.ingest_spdx(spdx, &warnings, tx).await?;
+
+        Ok(warnings.into())
+    }
+}
+
+/// An external SPDX document, referenced by an ingested SBOM through its `externalDocumentRefs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalDocument {
+    /// The URI of the referenced document
+    pub uri: String,
+    /// The SHA-256 digest of the referenced document, if the reference provides one
+    pub sha256: Option<String>,
+}
+
+/// The time fetching a single external document may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The maximum size of a fetched external document
+const FETCH_MAX_SIZE: usize = 128 * 1024 * 1024;
+
+/// Resolves the external documents referenced by SPDX SBOMs, fetching the ones not ingested yet.
+#[derive(Clone, Debug)]
+pub struct SbomExternalRefResolver {
+    client: reqwest::Client,
+    max_depth: usize,
+    max_size: usize,
+}
+
+impl SbomExternalRefResolver {
+    /// Create a new resolver, following references up to `max_depth` documents deep.
+    pub fn new(max_depth: usize) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .user_agent("trustify-sbom-resolver")
+            .timeout(FETCH_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            max_depth,
+            max_size: FETCH_MAX_SIZE,
+        })
+    }
+
+    /// Set the maximum size of a fetched document, in bytes.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// The maximum depth of references which get resolved.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Find the external documents referenced by an SBOM which are not in the database yet,
+    /// neither by their document ID nor by their SHA-256 digest.
+    #[instrument(skip(self, graph, connection), err(level=tracing::Level::INFO))]
+    pub async fn unresolved<C: ConnectionTrait>(
+        &self,
+        graph: &Graph,
+        sbom_id: Uuid,
+        connection: &C,
+    ) -> Result<Vec<ExternalDocument>, Error> {
+        let references = sbom_external_node::Entity::find()
+            .select_only()
+            .distinct()
+            .column(sbom_external_node::Column::ExternalDocRef)
+            .column(sbom_external_node::Column::DiscriminatorType)
+            .column(sbom_external_node::Column::DiscriminatorValue)
+            .filter(sbom_external_node::Column::SbomId.eq(sbom_id))
+            .filter(sbom_external_node::Column::ExternalType.eq(ExternalType::SPDX))
+            .into_tuple::<(String, Option<DiscriminatorType>, Option<String>)>()
+            .all(connection)
+            .await?;
+
+        let mut result: Vec<ExternalDocument> = vec![];
+
+        for (uri, discriminator_type, discriminator_value) in references {
+            if result.iter().any(|document| document.uri == uri) {
+                continue;
+            }
+
+            let sha256 = match (discriminator_type, discriminator_value) {
+                (Some(DiscriminatorType::Sha256), Some(value)) if !value.is_empty() => Some(value),
+                _ => None,
+            };
+
+            let known_by_id = sbom::Entity::find()
+                .filter(sbom::Column::DocumentId.eq(&uri))
+                .count(connection)
+                .await?
+                > 0;
+            if known_by_id {
+                continue;
+            }
+
+            if let Some(sha256) = &sha256
+                && graph
+                    .locate_sbom(SbomLocator::Sha256(sha256.clone()), connection)
+                    .await?
+                    .is_some()
+            {
+                continue;
+            }
+
+            result.push(ExternalDocument { uri, sha256 });
+        }
+
+        Ok(result)
+    }
+
+    /// Fetch an external document, verifying its digest if the reference provided one.
+    #[instrument(skip(self), err(level=tracing::Level::INFO))]
+    pub async fn fetch(&self, document: &ExternalDocument) -> anyhow::Result<Bytes> {
+        let url = reqwest::Url::parse(&document.uri)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("unsupported URL scheme: {}", url.scheme());
+        }
+
+        let mut response = self.client.get(url).send().await?.error_for_status()?;
+
+        if let Some(length) = response.content_length()
+            && length > self.max_size as u64
+        {
+            bail!(
+                "document exceeds the maximum size of {} bytes",
+                self.max_size
+            );
+        }
+
+        // don't trust the announced length
+        let mut data = BytesMut::new();
+        while let Some(chunk) = response.chunk().await? {
+            if data.len() + chunk.len() > self.max_size {
+                bail!(
+                    "document exceeds the maximum size of {} bytes",
+                    self.max_size
+                );
+            }
+            data.extend_from_slice(&chunk);
+        }
+        let data = data.freeze();
+
+        if let Some(expected) = &document.sha256 {
+            let actual: String = Digests::digest(&data).sha256.encode_hex();
+            if !actual.eq_ignore_ascii_case(expected) {
+                bail!("SHA-256 digest mismatch, expected: {expected}, actual: {actual}");
+            }
+        }
+
+        Ok(data)
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use super::SbomExternalRefResolver;
     use crate::service::{Cache, IngestorService};
     use crate::{graph::Graph, service::Format};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use serde_json::{Value, json};
     use test_context::test_context;
     use test_log::test;
+    use trustify_entity::sbom;
     use trustify_test_context::{TrustifyContext, document_bytes};
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    /// Build `simple-ext-a.json`, with its external reference pointing to the mock server.
+    async fn ext_a(server: &MockServer) -> Result<Vec<u8>, anyhow::Error> {
+        let mut json: Value =
+            serde_json::from_slice(&document_bytes("spdx/simple-ext-a.json").await?)?;
+        json["externalDocumentRefs"][0]["spdxDocument"] =
+            json!(format!("{}/simple-ext-b.json", server.uri()));
+        Ok(serde_json::to_vec(&json)?)
+    }
+
+    async fn mock_ext_b(server: &MockServer, expected: u64) -> Result<(), anyhow::Error> {
+        Mock::given(method("GET"))
+            .and(path("/simple-ext-b.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_bytes(document_bytes("spdx/simple-ext-b.json").await?.to_vec()),
+            )
+            .expect(expected)
+            .mount(server)
+            .await;
+        Ok(())
+    }
+
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn resolve_external_refs(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let server = MockServer::start().await;
+        mock_ext_b(&server, 1).await?;
+        let data = ext_a(&server).await?;
+
+        let ingestor = IngestorService::new(Graph::new(), ctx.storage.clone(), None)
+            .with_external_ref_resolver(SbomExternalRefResolver::new(1)?);
+
+        let mut result = ctx
+            .db
+            .transaction(async |tx| {
+                ingestor
+                    .ingest(
+                        &data,
+                        Format::SPDX,
+                        ("source", "test"),
+                        None,
+                        Cache::Skip,
+                        tx,
+                    )
+                    .await
+            })
+            .await?;
+        ingestor
+            .resolve_external_refs(&mut result, Cache::Skip, &ctx.db)
+            .await?;
+
+        assert!(
+            !result
+                .warnings
+                .iter()
+                .any(|warning| warning.contains("external document")),
+            "unexpected warnings: {:?}",
+            result.warnings
+        );
+
+        // the referenced document got ingested too
+        let ext_b = sbom::Entity::find()
+            .filter(sbom::Column::DocumentId.eq("uri:simple-ext-b"))
+            .one(&ctx.db)
+            .await?;
+        assert!(ext_b.is_some());
+
+        Ok(())
+    }
+
+    /// A referenced document which is already known by its digest doesn't get fetched.
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn skip_known_external_refs(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let server = MockServer::start().await;
+        mock_ext_b(&server, 0).await?;
+        let data = ext_a(&server).await?;
+
+        ctx.ingest_document("spdx/simple-ext-b.json").await?;
+
+        let ingestor = IngestorService::new(Graph::new(), ctx.storage.clone(), None)
+            .with_external_ref_resolver(SbomExternalRefResolver::new(1)?);
+
+        let mut result = ctx
+            .db
+            .transaction(async |tx| {
+                ingestor
+                    .ingest(
+                        &data,
+                        Format::SPDX,
+                        ("source", "test"),
+                        None,
+                        Cache::Skip,
+                        tx,
+                    )
+                    .await
+            })
+            .await?;
+        ingestor
+            .resolve_external_refs(&mut result, Cache::Skip, &ctx.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Failing to fetch or ingest a referenced document doesn't affect the referencing SBOM.
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn failing_external_refs(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/simple-ext-b.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{ \"spdxVersion\": 1 }"))
+            .mount(&server)
+            .await;
+        let data = ext_a(&server).await?;
+
+        for (resolver, expected) in [
+            (SbomExternalRefResolver::new(1)?, "Failed to ingest"),
+            (
+                SbomExternalRefResolver::new(1)?.with_max_size(8),
+                "exceeds the maximum size",
+            ),
+        ] {
+            let ingestor = IngestorService::new(Graph::new(), ctx.storage.clone(), None)
+                .with_external_ref_resolver(resolver);
+
+            let mut result = ctx
+                .db
+                .transaction(async |tx| {
+                    ingestor
+                        .ingest(
+                            &data,
+                            Format::SPDX,
+                            ("source", "test"),
+                            None,
+                            Cache::Skip,
+                            tx,
+                        )
+                        .await
+                })
+                .await?;
+            ingestor
+                .resolve_external_refs(&mut result, Cache::Skip, &ctx.db)
+                .await?;
+
+            assert!(
+                result
+                    .warnings
+                    .iter()
+                    .any(|warning| warning.contains(expected)),
+                "missing warning {expected:?}: {:?}",
+                result.warnings
+            );
+        }
+
+        // the referencing SBOM is still there
+        let ext_a = sbom::Entity::find()
+            .filter(sbom::Column::DocumentId.eq("uri:simple-ext-a"))
+            .one(&ctx.db)
+            .await?;
+        assert!(ext_a.is_some());
+
+        Ok(())
+    }
 
     #[test_context(TrustifyContext)]
     #[test(tokio::test)]
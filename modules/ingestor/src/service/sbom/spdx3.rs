@@ -0,0 +1,160 @@
+use crate::{
+    graph::{
+        Graph, Outcome,
+        sbom::{
+            SbomContext,
+            spdx3::{self, Spdx3},
+        },
+    },
+    model::IngestResult,
+    service::{Error, Warnings},
+};
+use anyhow::anyhow;
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use serde_json::Value;
+use tracing::instrument;
+use trustify_common::hashing::Digests;
+use trustify_entity::labels::Labels;
+
+/// Loads SPDX 3.0 SBOMs, serialized as JSON-LD.
+pub struct Spdx3Loader<'g> {
+    graph: &'g Graph,
+}
+
+impl<'g> Spdx3Loader<'g> {
+    pub fn new(graph: &'g Graph) -> Self {
+        Self { graph }
+    }
+
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub async fn load(
+        &self,
+        labels: Labels,
+        json: Value,
+        digests: &Digests,
+        tx: &(impl ConnectionTrait + TransactionTrait),
+    ) -> Result<IngestResult, Error> {
+        let warnings = Warnings::default();
+
+        let (spdx, document_id) = parse(json)?;
+
+        log::info!("Storing: {document_id}");
+
+        let labels = labels.add("type", "spdx");
+
+        let sbom = match self
+            .graph
+            .ingest_sbom(
+                labels,
+                digests,
+                Some(document_id.clone()),
+                spdx3::Information(&spdx),
+                tx,
+            )
+            .await?
+        {
+            Outcome::Existed(sbom) => sbom,
+            Outcome::Added(sbom) => {
+                sbom.ingest_spdx3(spdx, &warnings, tx).await?;
+                sbom
+            }
+        };
+
+        Ok(IngestResult {
+            id: sbom.sbom.sbom_id.to_string(),
+            document_id: Some(document_id),
+            warnings: warnings.into(),
+        })
+    }
+
+    /// Ingest the document of an already ingested SBOM again, replacing its content.
+    ///
+    /// Returns the warnings of ingesting the document.
+    #[instrument(skip_all, err(level=tracing::Level::INFO))]
+    pub async fn reprocess(
+        &self,
+        sbom: SbomContext,
+        json: Value,
+        tx: &impl ConnectionTrait,
+    ) -> Result<Vec<String>, Error> {
+        let warnings = Warnings::default();
+
+        let (spdx, _) = parse(json)?;
+
+        let sbom = sbom.reset(spdx3::Information(&spdx), tx).await?;
+        sbom.ingest_spdx3(spdx, &warnings, tx).await?;
+
+        Ok(warnings.into())
+    }
+}
+
+/// Parse the `@graph` of a document, along with the ID of its `SpdxDocument` element.
+fn parse(json: Value) -> Result<(Spdx3, String), Error> {
+    let spdx: Spdx3 = serde_json::from_value(json)?;
+
+    let document_id = spdx
+        .document()
+        .map(|document| document.spdx_id.clone())
+        .ok_or_else(|| Error::InvalidContent(anyhow!("missing SpdxDocument element")))?;
+
+    Ok((spdx, document_id))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph::Graph,
+        service::{Cache, Format, IngestorService},
+    };
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use test_context::test_context;
+    use test_log::test;
+    use trustify_entity::{package_relates_to_package, relationship::Relationship, sbom_package};
+    use trustify_test_context::{TrustifyContext, document_bytes};
+
+    #[test_context(TrustifyContext)]
+    #[test(tokio::test)]
+    async fn ingest_spdx3(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+        let data = document_bytes("spdx/simple-3.0.json").await?;
+
+        let ingestor = IngestorService::new(Graph::new(), ctx.storage.clone(), None);
+
+        let result = ctx
+            .db
+            .transaction(async |tx| {
+                ingestor
+                    .ingest(
+                        &data,
+                        Format::SPDX,
+                        ("source", "test"),
+                        None,
+                        Cache::Skip,
+                        tx,
+                    )
+                    .await
+            })
+            .await?;
+
+        assert_eq!(
+            result.document_id.as_deref(),
+            Some("https://example.com/spdx3/simple/document")
+        );
+
+        let sbom_id = result.id.parse::<uuid::Uuid>()?;
+
+        let packages = sbom_package::Entity::find()
+            .filter(sbom_package::Column::SbomId.eq(sbom_id))
+            .all(&ctx.db)
+            .await?;
+        assert_eq!(packages.len(), 3);
+
+        let dependencies = package_relates_to_package::Entity::find()
+            .filter(package_relates_to_package::Column::SbomId.eq(sbom_id))
+            .filter(package_relates_to_package::Column::Relationship.eq(Relationship::Dependency))
+            .all(&ctx.db)
+            .await?;
+        assert_eq!(dependencies.len(), 2);
+
+        Ok(())
+    }
+}
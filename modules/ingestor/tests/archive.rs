@@ -0,0 +1,243 @@
+#[path = "common.rs"]
+mod common;
+
+use actix_http::StatusCode;
+use actix_web::test::TestRequest;
+use common::caller_with;
+use rstest::rstest;
+use std::io::{Cursor, Write};
+use test_context::test_context;
+use test_log::test;
+use trustify_module_ingestor::{endpoints::Config, service::archive::ArchiveIngestResult};
+use trustify_test_context::{TrustifyContext, call::CallService, document_bytes};
+use zip::{CompressionMethod, write::FileOptions};
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn upload_archive(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller_with(
+        ctx,
+        Config {
+            dataset_entry_limit: 10 * 1024 * 1024,
+            archive_entries_limit: 10,
+            archive_total_limit: 10 * 1024 * 1024,
+        },
+    )
+    .await?;
+
+    let mut data = vec![];
+    let mut archive = zip::write::ZipWriter::new(Cursor::new(&mut data));
+    archive.start_file("sbom.spdx.json", FileOptions::<()>::default())?;
+    archive.write_all(&document_bytes("spdx/simple.json").await?)?;
+    archive.add_directory("advisories", FileOptions::<()>::default())?;
+    archive.start_file(
+        "advisories/GHSA-2ccf-ffrj-m4qw.json",
+        FileOptions::<()>::default(),
+    )?;
+    archive.write_all(&document_bytes("osv/GHSA-2ccf-ffrj-m4qw.json").await?)?;
+    archive.start_file("sbom.spdx.json.intoto.jsonl", FileOptions::<()>::default())?;
+    archive.write_all(b"not a document")?;
+    archive.finish()?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/ingest/archive?labels.source=test")
+        .set_payload(data)
+        .to_request();
+
+    let result: ArchiveIngestResult = app.call_and_read_body_json(request).await;
+
+    // the attestation must not prevent the documents from being ingested
+
+    assert_eq!(
+        result.files.keys().collect::<Vec<_>>(),
+        ["advisories/GHSA-2ccf-ffrj-m4qw.json", "sbom.spdx.json"]
+    );
+    assert_eq!(
+        result.failures.keys().collect::<Vec<_>>(),
+        ["sbom.spdx.json.intoto.jsonl"]
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn upload_invalid_archive(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller_with(ctx, Config::default()).await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/ingest/archive")
+        .set_payload("not an archive")
+        .to_request();
+
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[rstest]
+#[case::entries(2, 10 * 1024 * 1024)]
+#[case::total(10, 1024)]
+#[test_log::test(actix_web::test)]
+async fn upload_archive_exceeding_limits(
+    ctx: &TrustifyContext,
+    #[case] archive_entries_limit: usize,
+    #[case] archive_total_limit: usize,
+) -> anyhow::Result<()> {
+    let app = caller_with(
+        ctx,
+        Config {
+            dataset_entry_limit: 10 * 1024 * 1024,
+            archive_entries_limit,
+            archive_total_limit,
+        },
+    )
+    .await?;
+
+    let mut data = vec![];
+    let mut archive = zip::write::ZipWriter::new(Cursor::new(&mut data));
+    for name in ["a.spdx.json", "b.spdx.json", "c.spdx.json"] {
+        archive.start_file(name, FileOptions::<()>::default())?;
+        archive.write_all(&document_bytes("spdx/simple.json").await?)?;
+    }
+    archive.finish()?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/ingest/archive")
+        .set_payload(data)
+        .to_request();
+
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    Ok(())
+}
+
+/// Write the same SBOM under each of the names, without compressing it
+async fn stored_archive(names: &[&str]) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+    let document = document_bytes("spdx/simple.json").await?.to_vec();
+
+    let mut data = vec![];
+    let mut archive = zip::write::ZipWriter::new(Cursor::new(&mut data));
+    for name in names {
+        archive.start_file(
+            *name,
+            FileOptions::<()>::default().compression_method(CompressionMethod::Stored),
+        )?;
+        archive.write_all(&document)?;
+    }
+    archive.finish()?;
+
+    Ok((data, document))
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn upload_archive_with_corrupt_entry(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller_with(
+        ctx,
+        Config {
+            dataset_entry_limit: 10 * 1024 * 1024,
+            archive_entries_limit: 10,
+            archive_total_limit: 10 * 1024 * 1024,
+        },
+    )
+    .await?;
+
+    let (mut data, document) = stored_archive(&["a.spdx.json", "b.spdx.json"]).await?;
+
+    // flip a byte of the first file, failing its checksum
+    let start = data
+        .windows(document.len())
+        .position(|window| window == document)
+        .expect("must contain the document");
+    data[start + 10] ^= 0xFF;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/ingest/archive")
+        .set_payload(data)
+        .to_request();
+
+    let result: ArchiveIngestResult = app.call_and_read_body_json(request).await;
+
+    assert_eq!(result.files.keys().collect::<Vec<_>>(), ["b.spdx.json"]);
+    assert_eq!(result.failures.keys().collect::<Vec<_>>(), ["a.spdx.json"]);
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn upload_archive_exceeding_total_limit_while_reading(
+    ctx: &TrustifyContext,
+) -> anyhow::Result<()> {
+    let (mut data, document) =
+        stored_archive(&["a.spdx.json", "b.spdx.json", "c.spdx.json"]).await?;
+
+    // claim the files to be empty, passing the check of the declared sizes
+    for (signature, offset) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+        let headers = data
+            .windows(4)
+            .enumerate()
+            .filter(|(_, window)| window == signature)
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        for header in headers {
+            data[header + offset..header + offset + 4].fill(0);
+        }
+    }
+
+    let app = caller_with(
+        ctx,
+        Config {
+            dataset_entry_limit: 10 * 1024 * 1024,
+            archive_entries_limit: 10,
+            archive_total_limit: document.len() * 3 / 2,
+        },
+    )
+    .await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/ingest/archive")
+        .set_payload(data)
+        .to_request();
+
+    let result: ArchiveIngestResult = app.call_and_read_body_json(request).await;
+
+    // the first file got ingested, the rest was skipped
+    assert_eq!(result.files.keys().collect::<Vec<_>>(), ["a.spdx.json"]);
+    assert_eq!(
+        result.failures.keys().collect::<Vec<_>>(),
+        ["b.spdx.json", "c.spdx.json"]
+    );
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn upload_archive_with_duplicate_names(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller_with(
+        ctx,
+        Config {
+            dataset_entry_limit: 10 * 1024 * 1024,
+            archive_entries_limit: 10,
+            archive_total_limit: 10 * 1024 * 1024,
+        },
+    )
+    .await?;
+
+    let (data, _) = stored_archive(&["a.spdx.json", "./a.spdx.json"]).await?;
+
+    let request = TestRequest::post()
+        .uri("/api/v3/ingest/archive")
+        .set_payload(data)
+        .to_request();
+
+    let response = app.call_service(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#![recursion_limit = "512"]
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use test_context::test_context;
+use test_log::test;
+use trustify_entity::{
+    package_relates_to_package, relationship::Relationship, sbom_ai, sbom_file, sbom_package,
+};
+use trustify_module_ingestor::service::Format;
+use trustify_test_context::TrustifyContext;
+use uuid::Uuid;
+
+/// Ingest a CycloneDX 1.6 SBOM, using the component types added with 1.6, and services
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn component_types_1dot6(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let result = ctx
+        .ingest_document_as(
+            "cyclonedx/component_types_1dot6.json",
+            Format::SBOM,
+            ("source", "test"),
+        )
+        .await?;
+    let sbom_id: Uuid = result.id.parse()?;
+
+    // device, firmware, device driver, platform, data, and the service
+    let packages = sbom_package::Entity::find()
+        .filter(sbom_package::Column::SbomId.eq(sbom_id))
+        .all(&ctx.db)
+        .await?;
+    assert_eq!(packages.len(), 6);
+
+    let models = sbom_ai::Entity::find()
+        .filter(sbom_ai::Column::SbomId.eq(sbom_id))
+        .all(&ctx.db)
+        .await?;
+    assert_eq!(models.len(), 1);
+
+    let files = sbom_file::Entity::find()
+        .filter(sbom_file::Column::SbomId.eq(sbom_id))
+        .all(&ctx.db)
+        .await?;
+    assert_eq!(files.len(), 1);
+
+    // all dependencies could be resolved, including those on the new component types
+    let dependencies = package_relates_to_package::Entity::find()
+        .filter(package_relates_to_package::Column::SbomId.eq(sbom_id))
+        .filter(package_relates_to_package::Column::Relationship.eq(Relationship::Dependency))
+        .all(&ctx.db)
+        .await?;
+    assert_eq!(dependencies.len(), 6);
+
+    Ok(())
+}
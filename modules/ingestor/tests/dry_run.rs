@@ -0,0 +1,48 @@
+use test_context::test_context;
+use test_log::test;
+use trustify_module_ingestor::{model::DryRunOutcome, service::Format};
+use trustify_test_context::{TrustifyContext, document_bytes};
+
+#[test_context(TrustifyContext)]
+#[test(tokio::test)]
+async fn dry_run_osv(ctx: &TrustifyContext) -> Result<(), anyhow::Error> {
+    let bytes = document_bytes("osv/RUSTSEC-2021-0079.json").await?;
+
+    // nothing ingested yet
+
+    let outcome = ctx.ingestor.dry_run(&bytes, Format::OSV, &ctx.db).await?;
+    assert_eq!(outcome, DryRunOutcome::Insert);
+
+    // a dry run must not ingest anything
+
+    let outcome = ctx.ingestor.dry_run(&bytes, Format::OSV, &ctx.db).await?;
+    assert_eq!(outcome, DryRunOutcome::Insert);
+
+    // once ingested, the same document is skipped
+
+    ctx.ingest_bytes_as(&bytes, Format::OSV, ("source", "test"))
+        .await?;
+
+    let outcome = ctx.ingestor.dry_run(&bytes, Format::OSV, &ctx.db).await?;
+    assert_eq!(outcome, DryRunOutcome::Skip);
+
+    // a changed document with the same ID is an update
+
+    let mut osv: serde_json::Value = serde_json::from_slice(&bytes)?;
+    osv["modified"] = "2099-01-01T00:00:00Z".into();
+    let changed = serde_json::to_vec(&osv)?;
+
+    let outcome = ctx.ingestor.dry_run(&changed, Format::OSV, &ctx.db).await?;
+    assert_eq!(outcome, DryRunOutcome::Update);
+
+    // an invalid document fails
+
+    assert!(
+        ctx.ingestor
+            .dry_run(b"{}", Format::OSV, &ctx.db)
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
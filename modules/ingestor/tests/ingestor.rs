@@ -1,4 +1,5 @@
 #![recursion_limit = "512"]
+mod dry_run;
 mod performance;
 mod reingest;
 mod version;
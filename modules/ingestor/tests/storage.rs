@@ -0,0 +1,33 @@
+#[path = "common.rs"]
+mod common;
+
+use actix_web::test::TestRequest;
+use common::caller_with;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_module_ingestor::endpoints::Config;
+use trustify_test_context::{TrustifyContext, call::CallService};
+
+#[test_context(TrustifyContext)]
+#[test(actix_web::test)]
+async fn storage_status(ctx: &TrustifyContext) -> anyhow::Result<()> {
+    let app = caller_with(ctx, Config::default()).await?;
+
+    let request = TestRequest::get()
+        .uri("/api/v3/status/storage")
+        .to_request();
+    let response: Value = app.call_and_read_body_json(request).await;
+
+    let [status] = response.as_array().expect("must be an array").as_slice() else {
+        panic!("must report exactly one backend: {response:#?}");
+    };
+    assert_eq!(status["backend"], json!("fs"));
+    assert_eq!(status["status"], json!("ok"));
+    assert!(status["write_ms"].is_u64());
+    assert!(status["read_ms"].is_u64());
+    assert!(status["delete_ms"].is_u64());
+    assert_eq!(status["error"], Value::Null);
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use super::{StorageBackend, StorageKey, dispatch::DispatchBackend};
+use bytes::BytesMut;
+use futures::TryStreamExt;
+use std::time::Instant;
+
+/// The state of a storage backend
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    Ok,
+    Error,
+}
+
+/// The result of probing a storage backend
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+pub struct HealthStatus {
+    /// The type of backend, like `fs` or `s3`
+    pub backend: String,
+    /// The outcome of the probe
+    pub status: HealthState,
+    /// Time it took to write the probe, in milliseconds
+    pub write_ms: Option<u64>,
+    /// Time it took to read back the probe, in milliseconds
+    pub read_ms: Option<u64>,
+    /// Time it took to delete the probe, in milliseconds
+    pub delete_ms: Option<u64>,
+    /// The first error encountered, if any
+    pub error: Option<String>,
+}
+
+impl DispatchBackend {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Filesystem(_) => "fs",
+            Self::S3(_) => "s3",
+        }
+    }
+
+    /// Check the backend by writing, reading back, and deleting a probe blob.
+    ///
+    /// Failures are reported as part of the status, not as an error. Once the probe was written,
+    /// deleting it is attempted even if reading it back failed.
+    pub async fn health_check(&self) -> HealthStatus {
+        let mut status = HealthStatus {
+            backend: self.name().to_string(),
+            status: HealthState::Ok,
+            write_ms: None,
+            read_ms: None,
+            delete_ms: None,
+            error: None,
+        };
+
+        // the storage is content addressed, so the content must be unique to get a unique key
+        let content = format!("healthcheck {}", uuid::Uuid::new_v4());
+
+        let start = Instant::now();
+        let key = match self.store(content.as_bytes()).await {
+            Ok(result) => result.key(),
+            Err(err) => {
+                status.fail(format!("failed to write probe: {err}"));
+                return status;
+            }
+        };
+        status.write_ms = Some(elapsed_ms(start));
+
+        let start = Instant::now();
+        match self.read(key.clone()).await {
+            Ok(Some(data)) if data == content.as_bytes() => {
+                status.read_ms = Some(elapsed_ms(start));
+            }
+            Ok(Some(_)) => status.fail("probe content mismatch".into()),
+            Ok(None) => status.fail("probe not found after writing".into()),
+            Err(err) => status.fail(format!("failed to read probe: {err}")),
+        }
+
+        let start = Instant::now();
+        match self.delete(key).await {
+            Ok(()) => status.delete_ms = Some(elapsed_ms(start)),
+            Err(err) => status.fail(format!("failed to delete probe: {err}")),
+        }
+
+        status
+    }
+
+    async fn read(&self, key: StorageKey) -> Result<Option<BytesMut>, anyhow::Error> {
+        let Some(stream) = self.retrieve(key).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(stream.try_collect().await?))
+    }
+}
+
+impl HealthStatus {
+    /// Mark as failed, keeping the first error
+    fn fail(&mut self, error: String) {
+        self.status = HealthState::Error;
+        self.error.get_or_insert(error);
+    }
+}
+
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::fs::FileSystemBackend;
+    use test_log::test;
+
+    #[test(tokio::test)]
+    async fn health_check_fs() {
+        let (backend, dir) = FileSystemBackend::for_test().await.unwrap();
+        let backend = DispatchBackend::from(backend);
+
+        let status = backend.health_check().await;
+
+        assert_eq!(status.backend, "fs");
+        assert_eq!(status.status, HealthState::Ok, "{:?}", status.error);
+        assert!(status.write_ms.is_some());
+        assert!(status.read_ms.is_some());
+        assert!(status.delete_ms.is_some());
+        assert_eq!(status.error, None);
+
+        // the probe must not be left behind
+        let remaining = count_files(&dir.path().join("content"));
+        assert_eq!(remaining, 0);
+    }
+
+    #[test(tokio::test)]
+    async fn health_check_fs_failing() {
+        let (backend, dir) = FileSystemBackend::for_test().await.unwrap();
+        let backend = DispatchBackend::from(backend);
+        // replace the content directory with a file, so that writing must fail
+        let content = dir.path().join("content");
+        std::fs::remove_dir_all(&content).unwrap();
+        std::fs::write(&content, b"").unwrap();
+
+        let status = backend.health_check().await;
+
+        assert_eq!(status.status, HealthState::Error);
+        assert_eq!(status.write_ms, None);
+        assert!(status.error.is_some());
+    }
+
+    fn count_files(path: &std::path::Path) -> usize {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return 0;
+        };
+
+        entries
+            .flatten()
+            .map(|entry| match entry.file_type() {
+                Ok(t) if t.is_dir() => count_files(&entry.path()),
+                _ => 1,
+            })
+            .sum()
+    }
+}
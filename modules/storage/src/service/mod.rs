@@ -1,5 +1,6 @@
 pub mod dispatch;
 pub mod fs;
+pub mod health;
 pub mod s3;
 
 mod test;
@@ -0,0 +1,101 @@
+use crate::{
+    model::{Webhook, WebhookRequest},
+    service::{Error, WebhookService},
+};
+use actix_web::{HttpResponse, Responder, delete, get, post, web};
+use sea_orm::TransactionTrait;
+use trustify_auth::{CreateImporter, DeleteImporter, ReadImporter, authorizer::Require};
+use trustify_common::{
+    db::{self, pagination_cache::PaginationCache},
+    model::{Paginated, PaginatedResults},
+};
+use uuid::Uuid;
+
+/// Mount the "webhook" module.
+pub fn configure(
+    svc: &mut utoipa_actix_web::service_config::ServiceConfig,
+    db: db::ReadWrite,
+    cache: PaginationCache,
+) {
+    svc.app_data(web::Data::new(WebhookService::new(cache)))
+        .app_data(web::Data::new(db))
+        .service(list)
+        .service(create)
+        .service(delete);
+}
+
+#[utoipa::path(
+    tag = "webhook",
+    operation_id = "listWebhooks",
+    params(
+        Paginated,
+    ),
+    responses(
+        (status = 200, description = "Subscriptions to events", body = PaginatedResults<Webhook>),
+    )
+)]
+#[get("/v3/webhook")]
+/// List the webhooks subscribed to events
+async fn list(
+    service: web::Data<WebhookService>,
+    db: web::Data<db::ReadWrite>,
+    web::Query(paginated): web::Query<Paginated>,
+    _: Require<ReadImporter>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    Ok(HttpResponse::Ok().json(service.list(paginated, &tx).await?))
+}
+
+#[utoipa::path(
+    tag = "webhook",
+    operation_id = "createWebhook",
+    request_body = WebhookRequest,
+    responses(
+        (status = 201, description = "The webhook was subscribed to the events", body = Webhook),
+        (status = 400, description = "The webhook was invalid"),
+    )
+)]
+#[post("/v3/webhook")]
+/// Subscribe a webhook to events
+///
+/// Events are posted to the URL of the webhook, signed with its secret. Failed deliveries are
+/// retried with an increasing delay.
+///
+/// The host of the URL must resolve to public addresses. Redirects are not followed.
+async fn create(
+    service: web::Data<WebhookService>,
+    db: web::Data<db::ReadWrite>,
+    web::Json(request): web::Json<WebhookRequest>,
+    _: Require<CreateImporter>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    let result = service.create(request, &tx).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[utoipa::path(
+    tag = "webhook",
+    operation_id = "deleteWebhook",
+    params(
+        ("id", Path, description = "The ID of the webhook"),
+    ),
+    responses(
+        (status = 204, description = "The webhook was removed, or did not exist"),
+    )
+)]
+#[delete("/v3/webhook/{id}")]
+/// Remove a webhook, along with its pending deliveries
+async fn delete(
+    service: web::Data<WebhookService>,
+    db: web::Data<db::ReadWrite>,
+    id: web::Path<Uuid>,
+    _: Require<DeleteImporter>,
+) -> Result<impl Responder, Error> {
+    let tx = db.begin().await?;
+    service.delete(*id, &tx).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
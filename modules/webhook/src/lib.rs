@@ -0,0 +1,5 @@
+pub mod endpoints;
+pub mod model;
+pub mod server;
+pub mod service;
+pub mod test;
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use trustify_entity::webhook_config;
+use url::Url;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// An event a webhook can subscribe to
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    strum::AsRefStr,
+    strum::Display,
+    strum::EnumString,
+)]
+pub enum WebhookEvent {
+    /// An importer run completed successfully
+    #[serde(rename = "importer.complete")]
+    #[strum(serialize = "importer.complete")]
+    ImporterComplete,
+    /// An importer run failed
+    #[serde(rename = "importer.error")]
+    #[strum(serialize = "importer.error")]
+    ImporterError,
+    /// An SBOM was ingested
+    #[serde(rename = "sbom.ingested")]
+    #[strum(serialize = "sbom.ingested")]
+    SbomIngested,
+    /// An advisory was ingested
+    #[serde(rename = "advisory.ingested")]
+    #[strum(serialize = "advisory.ingested")]
+    AdvisoryIngested,
+}
+
+/// A request to subscribe to events
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookRequest {
+    /// The URL events get posted to
+    pub url: Url,
+    /// The secret used to sign the payload, sent in the `X-Trustify-Signature-256` header
+    pub secret: String,
+    /// The events to subscribe to
+    pub events: Vec<WebhookEvent>,
+}
+
+/// A subscription to events
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Webhook {
+    pub id: Uuid,
+    /// The URL events get posted to
+    pub url: String,
+    /// The events subscribed to
+    pub events: Vec<WebhookEvent>,
+}
+
+impl From<webhook_config::Model> for Webhook {
+    fn from(value: webhook_config::Model) -> Self {
+        Self {
+            id: value.id,
+            url: value.url,
+            events: value
+                .events
+                .iter()
+                // ignore events which are no longer known
+                .filter_map(|event| WebhookEvent::from_str(event).ok())
+                .collect(),
+        }
+    }
+}
+
+/// The body of the request sent to a webhook
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookPayload {
+    /// The ID of the delivery, which stays the same when retrying
+    pub id: Uuid,
+    pub event: WebhookEvent,
+    /// When the event occurred
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: time::OffsetDateTime,
+    /// Details of the event, depending on the type of event
+    pub data: serde_json::Value,
+}
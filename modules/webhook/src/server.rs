@@ -0,0 +1,240 @@
+use crate::model::{WebhookEvent, WebhookPayload};
+use hmac::{Hmac, KeyInit, Mac};
+use sea_orm::{ColumnTrait, DbBackend, EntityTrait, FromQueryResult, QueryFilter, Statement};
+use sea_query::Expr;
+use sha2::Sha256;
+use std::{str::FromStr, time::Duration};
+use time::OffsetDateTime;
+use tokio::time::MissedTickBehavior;
+use trustify_common::db::ReadWrite;
+use trustify_entity::webhook_delivery_log::{self, State};
+use uuid::Uuid;
+
+/// The header carrying the signature of the payload
+pub const SIGNATURE_HEADER: &str = "X-Trustify-Signature-256";
+/// The header carrying the event of the payload
+pub const EVENT_HEADER: &str = "X-Trustify-Event";
+/// The header carrying the ID of the delivery
+pub const DELIVERY_HEADER: &str = "X-Trustify-Delivery";
+
+/// The number of times a failed delivery is retried.
+const MAX_RETRIES: i32 = 5;
+/// The delay before the first retry, doubled for each further one.
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+/// The number of deliveries attempted at once.
+const BATCH_SIZE: i64 = 50;
+/// The time a delivery is reserved for the current attempt, keeping other replicas from
+/// attempting it as well.
+const LEASE: Duration = Duration::from_secs(5 * 60);
+/// The time a webhook has to respond.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Periodically deliver the queued events to their webhooks
+pub async fn deliver_webhooks(db: ReadWrite, period: Duration) -> anyhow::Result<()> {
+    // don't follow redirects, which could lead to targets not checked when creating the webhook
+    let client = reqwest::Client::builder()
+        .user_agent("trustify-webhook")
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        loop {
+            match deliver_due(&db, &client).await {
+                // continue with the next batch, there might be more
+                Ok(count) if count >= BATCH_SIZE as usize => continue,
+                Ok(_) => break,
+                Err(err) => {
+                    tracing::warn!("Failed to deliver webhooks: {err}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// A delivery which is due, along with the webhook to deliver to
+#[derive(Debug, FromQueryResult)]
+struct Due {
+    id: Uuid,
+    event: String,
+    payload: serde_json::Value,
+    created: OffsetDateTime,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+/// Attempt all deliveries which are due, returning the number of attempted deliveries
+pub(crate) async fn deliver_due(db: &ReadWrite, client: &reqwest::Client) -> anyhow::Result<usize> {
+    let due = Due::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+UPDATE webhook_delivery_log AS d
+SET next_attempt = now() + make_interval(secs => $1)
+FROM webhook_config AS w
+WHERE d.webhook_id = w.id AND d.id IN (
+    SELECT id FROM webhook_delivery_log
+    WHERE state = $2 AND next_attempt <= now()
+    ORDER BY next_attempt
+    LIMIT $3
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING d.id, d.event, d.payload, d.created, d.attempts, w.url, w.secret
+"#,
+        [
+            (LEASE.as_secs() as f64).into(),
+            (State::Pending as i32).into(),
+            BATCH_SIZE.into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    let count = due.len();
+
+    for due in due {
+        let id = due.id;
+        let attempts = due.attempts + 1;
+        let result = attempt(client, due).await;
+        if let Err(err) = &result {
+            tracing::info!("Failed to deliver {id} (attempt {attempts}): {err}");
+        }
+        record(db, id, attempts, result).await?;
+    }
+
+    Ok(count)
+}
+
+#[derive(Debug, thiserror::Error)]
+enum DeliveryError {
+    #[error("unknown event: {0}")]
+    Event(String),
+    #[error("webhook responded with {0}")]
+    Status(reqwest::StatusCode),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Post the payload to the webhook, returning the status code of the response
+async fn attempt(client: &reqwest::Client, due: Due) -> Result<u16, DeliveryError> {
+    let event = WebhookEvent::from_str(&due.event).map_err(|_| DeliveryError::Event(due.event))?;
+
+    let body = serde_json::to_vec(&WebhookPayload {
+        id: due.id,
+        event,
+        timestamp: due.created,
+        data: due.payload,
+    })?;
+
+    let response = client
+        .post(&due.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, sign(&due.secret, &body))
+        .header(EVENT_HEADER, event.to_string())
+        .header(DELIVERY_HEADER, due.id.to_string())
+        .body(body)
+        .send()
+        .await?;
+
+    match response.status() {
+        status if status.is_success() => Ok(status.as_u16()),
+        status => Err(DeliveryError::Status(status)),
+    }
+}
+
+/// Store the result of an attempt, and schedule the next one if it failed
+async fn record(
+    db: &ReadWrite,
+    id: Uuid,
+    attempts: i32,
+    result: Result<u16, DeliveryError>,
+) -> Result<(), sea_orm::DbErr> {
+    let now = OffsetDateTime::now_utc();
+
+    let (state, next_attempt, status, error) = match result {
+        Ok(status) => (State::Delivered, now, Some(status), None),
+        Err(err) => {
+            let status = match &err {
+                DeliveryError::Status(status) => Some(status.as_u16()),
+                _ => None,
+            };
+            let state = match err {
+                // unknown events will never succeed
+                DeliveryError::Event(_) => State::Failed,
+                _ if attempts > MAX_RETRIES => State::Failed,
+                _ => State::Pending,
+            };
+            (
+                state,
+                now + backoff(attempts),
+                status,
+                Some(err.to_string()),
+            )
+        }
+    };
+
+    webhook_delivery_log::Entity::update_many()
+        .col_expr(webhook_delivery_log::Column::State, Expr::value(state))
+        .col_expr(
+            webhook_delivery_log::Column::Attempts,
+            Expr::value(attempts),
+        )
+        .col_expr(
+            webhook_delivery_log::Column::NextAttempt,
+            Expr::value(next_attempt),
+        )
+        .col_expr(webhook_delivery_log::Column::LastAttempt, Expr::value(now))
+        .col_expr(
+            webhook_delivery_log::Column::LastStatus,
+            Expr::value(status.map(i32::from)),
+        )
+        .col_expr(webhook_delivery_log::Column::LastError, Expr::value(error))
+        .filter(webhook_delivery_log::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// The delay before the next attempt, after a number of failed attempts
+fn backoff(attempts: i32) -> Duration {
+    RETRY_DELAY.saturating_mul(2u32.saturating_pow(attempts.max(1) as u32 - 1))
+}
+
+/// Sign the payload, GitHub style: the hex encoded HMAC-SHA256, prefixed with `sha256=`
+pub fn sign(secret: &str, payload: &[u8]) -> String {
+    #[allow(clippy::expect_used)]
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signature() {
+        // the example of the GitHub documentation
+        assert_eq!(
+            sign("It's a Secret to Everybody", b"Hello, World!"),
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+    }
+
+    #[test]
+    fn backoff_delay() {
+        assert_eq!(backoff(1), Duration::from_secs(30));
+        assert_eq!(backoff(2), Duration::from_secs(60));
+        assert_eq!(backoff(5), Duration::from_secs(480));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::model::{Webhook, WebhookEvent, WebhookRequest};
+use actix_web::{HttpResponse, ResponseError, body::BoxBody};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ConnectionTrait, DbBackend, DbErr, EntityTrait, QueryOrder,
+    Statement,
+};
+use std::net::IpAddr;
+use tokio::net::lookup_host;
+use tracing::instrument;
+use trustify_common::{
+    db::{
+        DatabaseErrors,
+        limiter::{LimitedResult, LimiterTrait},
+        pagination_cache::{LimitError, PaginationCache},
+    },
+    error::ErrorInformation,
+    model::{PaginatedResults, Pagination},
+};
+use trustify_entity::{webhook_config, webhook_delivery_log};
+use url::{Host, Url};
+use uuid::Uuid;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid webhook: {0}")]
+    BadRequest(String),
+    #[error("unavailable")]
+    Unavailable,
+    #[error("database error: {0}")]
+    Database(#[source] DbErr),
+    #[error(transparent)]
+    Limit(#[from] LimitError),
+}
+
+impl From<DbErr> for Error {
+    fn from(value: DbErr) -> Self {
+        if value.is_read_only() {
+            Error::Unavailable
+        } else {
+            Error::Database(value)
+        }
+    }
+}
+
+impl ResponseError for Error {
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        match self {
+            Self::BadRequest(_) => HttpResponse::BadRequest().json(ErrorInformation {
+                error: "BadRequest".into(),
+                message: self.to_string(),
+                details: None,
+            }),
+            Self::Unavailable => HttpResponse::ServiceUnavailable().json(ErrorInformation {
+                error: "Unavailable".into(),
+                message: self.to_string(),
+                details: None,
+            }),
+            Self::Limit(err) => err.error_response(),
+            _ => HttpResponse::InternalServerError().json(ErrorInformation {
+                error: "Internal".into(),
+                message: self.to_string(),
+                details: None,
+            }),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    cache: PaginationCache,
+    private_targets: bool,
+}
+
+impl WebhookService {
+    pub fn new(cache: PaginationCache) -> Self {
+        Self {
+            cache,
+            private_targets: false,
+        }
+    }
+
+    /// Allow webhooks targeting loopback, link-local and private addresses, which are rejected by
+    /// default.
+    pub fn with_private_targets(mut self, private_targets: bool) -> Self {
+        self.private_targets = private_targets;
+        self
+    }
+
+    /// Subscribe to events
+    #[instrument(skip(self, request, connection), fields(url = %request.url), err(level=tracing::Level::INFO))]
+    pub async fn create<C: ConnectionTrait>(
+        &self,
+        request: WebhookRequest,
+        connection: &C,
+    ) -> Result<Webhook, Error> {
+        let WebhookRequest {
+            url,
+            secret,
+            mut events,
+        } = request;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(Error::BadRequest(format!(
+                "unsupported URL scheme: {}",
+                url.scheme()
+            )));
+        }
+        if secret.is_empty() {
+            return Err(Error::BadRequest("the secret must not be empty".into()));
+        }
+        if !self.private_targets {
+            check_public_target(&url).await?;
+        }
+
+        events.sort_by_key(|event| event.to_string());
+        events.dedup();
+        if events.is_empty() {
+            return Err(Error::BadRequest(
+                "at least one event must be subscribed to".into(),
+            ));
+        }
+
+        let result = webhook_config::ActiveModel {
+            id: Set(Uuid::now_v7()),
+            url: Set(url.to_string()),
+            secret: Set(secret),
+            events: Set(events.iter().map(ToString::to_string).collect()),
+        }
+        .insert(connection)
+        .await?;
+
+        Ok(result.into())
+    }
+
+    /// List all subscriptions
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn list<C: ConnectionTrait + Sync + Send>(
+        &self,
+        paginated: impl Pagination,
+        connection: &C,
+    ) -> Result<PaginatedResults<Webhook>, Error> {
+        let limiter = webhook_config::Entity::find()
+            .order_by_asc(webhook_config::Column::Id)
+            .limiting(connection, paginated, &self.cache)?;
+
+        let LimitedResult { items, total } = limiter.fetch().await?;
+        let total = total.requested(paginated.total()).await?;
+
+        Ok(PaginatedResults {
+            items: items.into_iter().map(Webhook::from).collect(),
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Remove a subscription, along with its deliveries. Returns `false` if it didn't exist.
+    #[instrument(skip(self, connection), err(level=tracing::Level::INFO))]
+    pub async fn delete<C: ConnectionTrait>(
+        &self,
+        id: Uuid,
+        connection: &C,
+    ) -> Result<bool, Error> {
+        let result = webhook_config::Entity::delete_by_id(id)
+            .exec(connection)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
+
+/// Ensure the host of a URL only resolves to public addresses, so that webhooks can't be used to
+/// reach services of the internal network
+async fn check_public_target(url: &Url) -> Result<(), Error> {
+    let port = url.port_or_known_default().unwrap_or_default();
+    let addresses: Vec<IpAddr> = match url.host() {
+        Some(Host::Ipv4(ip)) => vec![ip.into()],
+        Some(Host::Ipv6(ip)) => vec![ip.into()],
+        Some(Host::Domain(domain)) => lookup_host((domain, port))
+            .await
+            .map_err(|err| Error::BadRequest(format!("unable to resolve {domain}: {err}")))?
+            .map(|addr| addr.ip())
+            .collect(),
+        None => return Err(Error::BadRequest("the URL has no host".into())),
+    };
+
+    if addresses.is_empty() {
+        return Err(Error::BadRequest("the host has no addresses".into()));
+    }
+
+    match addresses.into_iter().find(|ip| !is_public(*ip)) {
+        Some(ip) => Err(Error::BadRequest(format!(
+            "the host resolves to a non-public address: {ip}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Check if an address is public, and not loopback, link-local, private or otherwise special
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // shared address space, 100.64.0.0/10
+                || (a == 100 && (b & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(ip.into()),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Queue the delivery of an event to all webhooks subscribed to it
+///
+/// The deliveries are part of the transaction of the connection, so that nothing gets delivered
+/// if the transaction is rolled back. Returns the number of queued deliveries.
+#[instrument(skip(data, connection), err(level=tracing::Level::INFO))]
+pub async fn notify<C: ConnectionTrait>(
+    event: WebhookEvent,
+    data: serde_json::Value,
+    connection: &C,
+) -> Result<u64, DbErr> {
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+INSERT INTO webhook_delivery_log (webhook_id, event, payload, state)
+SELECT id, $1, $2, $3
+FROM webhook_config
+WHERE $1 = ANY(events)
+"#,
+        [
+            event.to_string().into(),
+            data.into(),
+            (webhook_delivery_log::State::Pending as i32).into(),
+        ],
+    );
+
+    Ok(connection.execute(stmt).await?.rows_affected())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn public_addresses() -> anyhow::Result<()> {
+        for ip in ["93.184.215.14", "2606:2800:21f:cb07:6820:80da:af6b:8b2c"] {
+            assert!(is_public(ip.parse()?), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse()?), "{ip}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+#![cfg(test)]
+
+use super::{
+    model::{WebhookEvent, WebhookRequest},
+    server::{SIGNATURE_HEADER, deliver_due, sign},
+    service::{WebhookService, notify},
+};
+use actix_http::{Request, body::BoxBody};
+use actix_web::{
+    App,
+    dev::{Service, ServiceResponse},
+    http::StatusCode,
+    test as actix,
+};
+use sea_orm::EntityTrait;
+use serde_json::{Value, json};
+use test_context::test_context;
+use test_log::test;
+use trustify_common::db::{self, pagination_cache::PaginationCache};
+use trustify_entity::webhook_delivery_log::{self, State};
+use trustify_test_context::{TrustifyContext, app::TestApp};
+use utoipa_actix_web::AppExt;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{header, header_exists, method, path},
+};
+
+async fn app(
+    ctx: &TrustifyContext,
+) -> impl Service<Request, Response = ServiceResponse<BoxBody>, Error = actix_web::Error> {
+    let db = db::ReadWrite::new(ctx.db.clone());
+    actix::init_service(
+        App::new()
+            .into_utoipa_app()
+            .add_test_authorizer()
+            .service(
+                utoipa_actix_web::scope("/api").configure(|svc| {
+                    super::endpoints::configure(svc, db, PaginationCache::for_test())
+                }),
+            )
+            .into_app(),
+    )
+    .await
+}
+
+#[test_context(TrustifyContext, skip_teardown)]
+#[test(actix_web::test)]
+async fn create_and_delete(ctx: TrustifyContext) {
+    let app = app(&ctx).await;
+
+    let req = actix::TestRequest::post()
+        .uri("/api/v3/webhook")
+        .set_json(json!({
+            "url": "https://example.com/hook",
+            "secret": "s3cr3t",
+            "events": ["importer.complete", "sbom.ingested", "importer.complete"],
+        }))
+        .to_request();
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let webhook: Value = actix::read_body_json(resp).await;
+    assert_eq!(webhook["url"], "https://example.com/hook");
+    assert_eq!(
+        webhook["events"],
+        json!(["importer.complete", "sbom.ingested"])
+    );
+    // the secret is never returned
+    assert_eq!(webhook.get("secret"), None);
+
+    let req = actix::TestRequest::get()
+        .uri("/api/v3/webhook?total=true")
+        .to_request();
+    let result: Value = actix::call_and_read_body_json(&app, req).await;
+    assert_eq!(result["total"], 1);
+    assert_eq!(result["items"][0]["id"], webhook["id"]);
+
+    let uri = format!(
+        "/api/v3/webhook/{}",
+        webhook["id"].as_str().unwrap_or_default()
+    );
+    let req = actix::TestRequest::delete().uri(&uri).to_request();
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // deleting again is fine
+    let req = actix::TestRequest::delete().uri(&uri).to_request();
+    let resp = actix::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+}
+
+#[test_context(TrustifyContext, skip_teardown)]
+#[test(actix_web::test)]
+async fn create_invalid(ctx: TrustifyContext) {
+    let app = app(&ctx).await;
+
+    for body in [
+        json!({ "url": "ftp://example.com", "secret": "s3cr3t", "events": ["sbom.ingested"] }),
+        json!({ "url": "https://example.com", "secret": "", "events": ["sbom.ingested"] }),
+        json!({ "url": "https://example.com", "secret": "s3cr3t", "events": [] }),
+        json!({ "url": "https://example.com", "secret": "s3cr3t", "events": ["sbom.deleted"] }),
+        // non-public targets
+        json!({ "url": "http://127.0.0.1/hook", "secret": "s3cr3t", "events": ["sbom.ingested"] }),
+        json!({ "url": "http://localhost/hook", "secret": "s3cr3t", "events": ["sbom.ingested"] }),
+        json!({ "url": "http://169.254.169.254/", "secret": "s3cr3t", "events": ["sbom.ingested"] }),
+        json!({ "url": "http://[fd00::1]/hook", "secret": "s3cr3t", "events": ["sbom.ingested"] }),
+    ] {
+        let req = actix::TestRequest::post()
+            .uri("/api/v3/webhook")
+            .set_json(&body)
+            .to_request();
+        let resp = actix::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{body}");
+    }
+}
+
+async fn subscribe(
+    ctx: &TrustifyContext,
+    server: &MockServer,
+    events: Vec<WebhookEvent>,
+) -> anyhow::Result<()> {
+    // the mock server runs on the loopback interface
+    WebhookService::new(PaginationCache::for_test())
+        .with_private_targets(true)
+        .create(
+            WebhookRequest {
+                url: format!("{}/hook", server.uri()).parse()?,
+                secret: "s3cr3t".into(),
+                events,
+            },
+            &ctx.db,
+        )
+        .await?;
+    Ok(())
+}
+
+#[test_context(TrustifyContext, skip_teardown)]
+#[test(tokio::test)]
+async fn deliver(ctx: TrustifyContext) -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .and(header("content-type", "application/json"))
+        .and(header("x-trustify-event", "sbom.ingested"))
+        .and(header_exists("x-trustify-delivery"))
+        .and(header_exists("x-trustify-signature-256"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    subscribe(&ctx, &server, vec![WebhookEvent::SbomIngested]).await?;
+
+    assert_eq!(
+        notify(WebhookEvent::SbomIngested, json!({ "id": "sbom" }), &ctx.db).await?,
+        1
+    );
+    // not subscribed to
+    assert_eq!(
+        notify(WebhookEvent::ImporterComplete, json!({}), &ctx.db).await?,
+        0
+    );
+
+    let db = db::ReadWrite::new(ctx.db.clone());
+    let client = reqwest::Client::new();
+    assert_eq!(deliver_due(&db, &client).await?, 1);
+    // nothing left to deliver
+    assert_eq!(deliver_due(&db, &client).await?, 0);
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(
+        request
+            .headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok()),
+        Some(sign("s3cr3t", &request.body).as_str())
+    );
+    let body: Value = serde_json::from_slice(&request.body)?;
+    assert_eq!(body["event"], "sbom.ingested");
+    assert_eq!(body["data"], json!({ "id": "sbom" }));
+
+    let deliveries = webhook_delivery_log::Entity::find().all(&ctx.db).await?;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].state, State::Delivered);
+    assert_eq!(deliveries[0].attempts, 1);
+    assert_eq!(deliveries[0].last_status, Some(200));
+    assert_eq!(body["id"], json!(deliveries[0].id));
+
+    Ok(())
+}
+
+#[test_context(TrustifyContext, skip_teardown)]
+#[test(tokio::test)]
+async fn retry(ctx: TrustifyContext) -> anyhow::Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    subscribe(&ctx, &server, vec![WebhookEvent::ImporterError]).await?;
+    notify(WebhookEvent::ImporterError, json!({}), &ctx.db).await?;
+
+    let db = db::ReadWrite::new(ctx.db.clone());
+    let client = reqwest::Client::new();
+    assert_eq!(deliver_due(&db, &client).await?, 1);
+    // the retry is not due yet
+    assert_eq!(deliver_due(&db, &client).await?, 0);
+
+    let deliveries = webhook_delivery_log::Entity::find().all(&ctx.db).await?;
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].state, State::Pending);
+    assert_eq!(deliveries[0].attempts, 1);
+    assert_eq!(deliveries[0].last_status, Some(503));
+    assert!(deliveries[0].last_error.is_some());
+    assert!(deliveries[0].next_attempt > deliveries[0].created);
+
+    Ok(())
+}
@@ -5,7 +5,8 @@ use crate::{endpoints, profile::spawn_db_check, sample_data};
 use actix_web::web;
 use bytesize::ByteSize;
 use futures::FutureExt;
-use std::{env, process::ExitCode, sync::Arc};
+use std::{env, process::ExitCode, sync::Arc, time::Duration};
+use tokio::time::MissedTickBehavior;
 use trustify_auth::{
     auth::AuthConfigArguments,
     authenticator::Authenticator,
@@ -32,9 +33,19 @@ use trustify_infrastructure::{
     otel::{Metrics as OtelMetrics, Tracing},
 };
 use trustify_module_analysis::{config::AnalysisConfig, service::AnalysisService};
-use trustify_module_ingestor::graph::Graph;
-use trustify_module_storage::{config::StorageConfig, service::dispatch::DispatchBackend};
+use trustify_module_fundamental::{
+    license::model::policy::LicensePolicy,
+    purl::service::federated::{FederatedConfig, FederatedPurlService, Registries},
+    sbom::{model::quality::QualityWeights, service::SbomService},
+};
+use trustify_module_ingestor::{graph::Graph, service::sbom::spdx::SbomExternalRefResolver};
+use trustify_module_storage::{
+    config::StorageConfig,
+    service::{StorageBackend, StorageKey, dispatch::DispatchBackend},
+};
 use trustify_module_ui::{UI, endpoints::UiResources};
+use trustify_module_webhook::server::deliver_webhooks;
+use url::Url;
 use utoipa::openapi::{Info, License};
 
 /// Run the API server
@@ -72,10 +83,47 @@ pub struct Run {
     )]
     pub advisory_upload_limit: BinaryByteSize,
 
+    /// Fetch and ingest the external documents referenced by uploaded SPDX SBOMs, if they are
+    /// not ingested yet.
+    #[arg(long, env = "TRUSTD_SBOM_RESOLVE_EXTERNAL_REFS")]
+    pub sbom_resolve_external_refs: bool,
+
+    /// The maximum depth of external document references to follow, when resolving them.
+    #[arg(
+        long,
+        env = "TRUSTD_SBOM_RESOLVE_EXTERNAL_REFS_MAX_DEPTH",
+        default_value_t = 3
+    )]
+    pub sbom_resolve_external_refs_max_depth: usize,
+
     /// The maximum group name length
     #[arg(long, env = "TRUSTD_MAX_GROUP_NAME_LENGTH", default_value_t = 255)]
     pub max_group_name_length: usize,
 
+    /// The weights used when scoring the quality of an SBOM, as a comma separated list of
+    /// `name=weight` pairs. Names are: purl, cpe, version, license, relationships.
+    #[arg(
+        long,
+        env = "TRUSTD_SBOM_QUALITY_WEIGHTS",
+        default_value_t = QualityWeights::default()
+    )]
+    pub sbom_quality_weights: QualityWeights,
+
+    /// SPDX licenses uploaded SBOMs may use, comma separated. If set, uploading an SBOM using any
+    /// other license, which is not on the warn list, is rejected.
+    #[arg(long, env = "TRUSTD_SBOM_LICENSE_ALLOW", value_delimiter = ',')]
+    pub sbom_license_allow: Vec<String>,
+
+    /// SPDX licenses uploaded SBOMs must not use, comma separated. Uploading an SBOM using any of
+    /// them is rejected.
+    #[arg(long, env = "TRUSTD_SBOM_LICENSE_DENY", value_delimiter = ',')]
+    pub sbom_license_deny: Vec<String>,
+
+    /// SPDX licenses which are permitted, but logged as a warning when an uploaded SBOM uses them,
+    /// comma separated.
+    #[arg(long, env = "TRUSTD_SBOM_LICENSE_WARN", value_delimiter = ',')]
+    pub sbom_license_warn: Vec<String>,
+
     /// The size limit of documents in a dataset, uncompressed.
     #[arg(
         long,
@@ -84,6 +132,22 @@ pub struct Run {
     )]
     pub dataset_entry_limit: BinaryByteSize,
 
+    /// The maximum number of entries of an uploaded archive.
+    #[arg(
+        long,
+        env = "TRUSTD_ARCHIVE_ENTRIES_LIMIT",
+        default_value_t = default::archive_entries_limit()
+    )]
+    pub archive_entries_limit: usize,
+
+    /// The size limit of all documents of an uploaded archive together, uncompressed.
+    #[arg(
+        long,
+        env = "TRUSTD_ARCHIVE_TOTAL_LIMIT",
+        default_value_t = default::archive_total_limit()
+    )]
+    pub archive_total_limit: BinaryByteSize,
+
     /// The size limit of documents for a scan, uncompressed.
     #[arg(
         long,
@@ -92,6 +156,63 @@ pub struct Run {
     )]
     pub scan_limit: BinaryByteSize,
 
+    /// The interval of refreshing the advisory summary of SBOMs (humantime, e.g. "5m", 0 disables
+    /// refreshing). Refreshing is also disabled in read-only mode.
+    #[arg(
+        long,
+        env = "TRUSTD_ADVISORY_SUMMARY_REFRESH_INTERVAL",
+        default_value = "5m"
+    )]
+    pub advisory_summary_refresh_interval: humantime::Duration,
+
+    /// The interval of purging deleted SBOMs (humantime, e.g. "1h", 0 disables purging). Purging
+    /// is also disabled in read-only mode.
+    #[arg(long, env = "TRUSTD_DELETED_SBOM_PURGE_INTERVAL", default_value = "1h")]
+    pub deleted_sbom_purge_interval: humantime::Duration,
+
+    /// The time deleted SBOMs are kept, before they get purged (humantime, e.g. "30d").
+    #[arg(long, env = "TRUSTD_DELETED_SBOM_RETENTION", default_value = "30d")]
+    pub deleted_sbom_retention: humantime::Duration,
+
+    /// The interval of delivering events to webhooks (humantime, e.g. "10s", 0 disables
+    /// delivering). Delivering is also disabled in read-only mode.
+    #[arg(long, env = "TRUSTD_WEBHOOK_DELIVERY_INTERVAL", default_value = "10s")]
+    pub webhook_delivery_interval: humantime::Duration,
+
+    /// The time a single statement of a read-only request may take, before it gets cancelled
+    /// (humantime, e.g. "30s", 0 disables the timeout). Cancelled requests fail with a 504 status.
+    #[arg(long, env = "TRUSTD_QUERY_TIMEOUT", default_value = "0")]
+    pub query_timeout: humantime::Duration,
+
+    /// The crates.io API used to look up cargo PURLs which are not known locally.
+    #[arg(
+        long,
+        env = "TRUSTD_FEDERATED_CRATES_IO_URL",
+        default_value = "https://crates.io/api/v1"
+    )]
+    pub federated_crates_io_url: Url,
+
+    /// The PyPI JSON API used to look up pypi PURLs which are not known locally.
+    #[arg(
+        long,
+        env = "TRUSTD_FEDERATED_PYPI_URL",
+        default_value = "https://pypi.org/pypi"
+    )]
+    pub federated_pypi_url: Url,
+
+    /// The Maven Central search API used to look up maven PURLs which are not known locally.
+    #[arg(
+        long,
+        env = "TRUSTD_FEDERATED_MAVEN_CENTRAL_URL",
+        default_value = "https://search.maven.org/solrsearch/select"
+    )]
+    pub federated_maven_central_url: Url,
+
+    /// The time results of looking up PURLs from external registries are cached (humantime,
+    /// e.g. "5m").
+    #[arg(long, env = "TRUSTD_FEDERATED_CACHE_TTL", default_value = "5m")]
+    pub federated_cache_ttl: humantime::Duration,
+
     // flattened commands must go last
     //
     /// Analysis configuration
@@ -145,6 +266,14 @@ mod default {
         BinaryByteSize(ByteSize::gib(1))
     }
 
+    pub const fn archive_entries_limit() -> usize {
+        10_000
+    }
+
+    pub const fn archive_total_limit() -> BinaryByteSize {
+        BinaryByteSize(ByteSize::gib(4))
+    }
+
     pub const fn scan_limit() -> BinaryByteSize {
         BinaryByteSize(ByteSize::gib(1))
     }
@@ -194,6 +323,12 @@ struct InitData {
     config: ModuleConfig,
     analysis: AnalysisService,
     read_only: bool,
+    /// The database and the interval for refreshing the advisory summary, if enabled
+    advisory_summary_refresh: Option<(db::Database, Duration)>,
+    /// The interval and retention period for purging deleted SBOMs, if enabled
+    deleted_sbom_purge: Option<(Duration, Duration)>,
+    /// The interval for delivering events to webhooks, if enabled
+    webhook_delivery: Option<Duration>,
 }
 
 /// Groups all module configurations.
@@ -258,8 +393,15 @@ impl InitData {
         }
 
         let ro_config = run.database_ro.to_database_config(&run.database);
-        let db_ro = db::ReadOnly::new(db::Database::new(&ro_config).await?);
+        let mut replicas = vec![];
+        for config in run.database_ro.to_replica_configs(&run.database) {
+            replicas.push(db::Database::new(&config).await?);
+        }
+        let db_ro = db::ReadOnly::new(db::Database::new(&ro_config).await?)
+            .with_replicas(replicas)
+            .with_query_timeout(db::QueryTimeout::new(*run.query_timeout));
         let db_rw = db::ReadWrite::new(db.clone());
+        let persist_graphs = run.analysis.persist_graphs;
 
         let cache = run.pagination.into_cache();
 
@@ -275,6 +417,21 @@ impl InitData {
 
         let storage = run.storage.into_storage(run.devmode).await?;
 
+        let advisory_summary_refresh = (!run.read_only
+            && !run.advisory_summary_refresh_interval.is_zero())
+        .then(|| (db.clone(), *run.advisory_summary_refresh_interval));
+
+        let deleted_sbom_purge = (!run.read_only && !run.deleted_sbom_purge_interval.is_zero())
+            .then(|| {
+                (
+                    *run.deleted_sbom_purge_interval,
+                    *run.deleted_sbom_retention,
+                )
+            });
+
+        let webhook_delivery = (!run.read_only && !run.webhook_delivery_interval.is_zero())
+            .then_some(*run.webhook_delivery_interval);
+
         let ui = UI {
             version: env!("CARGO_PKG_VERSION").to_string(),
             auth_required: authenticator.is_some().to_string(),
@@ -284,14 +441,41 @@ impl InitData {
             oidc_load_user: run.ui.load_user.to_string(),
         };
 
+        let sbom_external_ref_resolver = run
+            .sbom_resolve_external_refs
+            .then(|| SbomExternalRefResolver::new(run.sbom_resolve_external_refs_max_depth))
+            .transpose()?;
+
+        let federated_purl_service = FederatedPurlService::new(FederatedConfig {
+            registries: Registries {
+                crates_io: run.federated_crates_io_url,
+                pypi: run.federated_pypi_url,
+                maven_central: run.federated_maven_central_url,
+            },
+            ttl: *run.federated_cache_ttl,
+        })?;
+
+        let sbom_license_policy = LicensePolicy {
+            allow: run.sbom_license_allow,
+            deny: run.sbom_license_deny,
+            warn: run.sbom_license_warn,
+        };
+
         let config = ModuleConfig {
             fundamental: trustify_module_fundamental::endpoints::Config {
                 sbom_upload_limit: run.sbom_upload_limit.into(),
                 advisory_upload_limit: run.advisory_upload_limit.into(),
                 max_group_name_length: run.max_group_name_length,
+                sbom_quality_weights: run.sbom_quality_weights,
+                sbom_license_policy: (!sbom_license_policy.is_empty())
+                    .then_some(sbom_license_policy),
+                sbom_external_ref_resolver,
+                federated_purl_service: Some(federated_purl_service),
             },
             ingestor: trustify_module_ingestor::endpoints::Config {
                 dataset_entry_limit: run.dataset_entry_limit.into(),
+                archive_entries_limit: run.archive_entries_limit,
+                archive_total_limit: run.archive_total_limit.into(),
             },
             ui: trustify_module_ui::endpoints::Config {
                 scan_limit: run.scan_limit.into(),
@@ -299,7 +483,11 @@ impl InitData {
         };
 
         Ok(InitData {
-            analysis: AnalysisService::new(run.analysis, db_ro.clone()),
+            analysis: AnalysisService::new_with_store(
+                run.analysis,
+                db_ro.clone(),
+                persist_graphs.then(|| db_rw.clone()),
+            ),
             authenticator,
             authorizer,
             db_rw,
@@ -315,10 +503,12 @@ impl InitData {
             embedded_oidc,
             ui,
             read_only: run.read_only,
+            advisory_summary_refresh,
+            deleted_sbom_purge,
+            webhook_delivery,
         })
     }
 
-    #[allow(unused_mut)]
     async fn run(mut self) -> anyhow::Result<()> {
         let ui = Arc::new(UiResources::new(&self.ui)?);
 
@@ -348,9 +538,23 @@ impl InitData {
         };
         let http = async { http.run().await }.boxed_local();
 
-        #[allow(unused_mut)]
         let mut tasks = vec![http];
 
+        if let Some((db, period)) = self.advisory_summary_refresh.take() {
+            tasks.push(refresh_advisory_summary(db, period).boxed_local());
+        }
+
+        if let Some((period, retention)) = self.deleted_sbom_purge.take() {
+            tasks.push(
+                purge_deleted_sboms(self.db_rw.clone(), self.storage.clone(), period, retention)
+                    .boxed_local(),
+            );
+        }
+
+        if let Some(period) = self.webhook_delivery.take() {
+            tasks.push(deliver_webhooks(self.db_rw.clone(), period).boxed_local());
+        }
+
         // track the embedded OIDC server task
         #[cfg(feature = "garage-door")]
         if let Some(embedded_oidc) = self.embedded_oidc.take() {
@@ -371,6 +575,69 @@ impl InitData {
     }
 }
 
+/// Periodically refresh the advisory summary of SBOMs, so that it picks up newly ingested
+/// documents.
+async fn refresh_advisory_summary(db: db::Database, period: Duration) -> anyhow::Result<()> {
+    // refreshing never lists anything, so there are no totals to cache
+    let service = SbomService::new(PaginationCache::new(Duration::ZERO, 0));
+
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        if let Err(err) = service.refresh_advisory_summary(&db).await {
+            log::warn!("Failed to refresh the advisory summary: {err}");
+        }
+    }
+}
+
+/// Periodically purge SBOMs which have been deleted for longer than the retention period.
+async fn purge_deleted_sboms(
+    db: db::ReadWrite,
+    storage: DispatchBackend,
+    period: Duration,
+    retention: Duration,
+) -> anyhow::Result<()> {
+    // purging never lists anything, so there are no totals to cache
+    let service = SbomService::new(PaginationCache::new(Duration::ZERO, 0));
+
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let digests = match db
+            .transaction(async |tx| service.purge_deleted_sboms(retention, tx).await)
+            .await
+        {
+            Ok(digests) => digests,
+            Err(err) => {
+                log::warn!("Failed to purge deleted SBOMs: {err}");
+                continue;
+            }
+        };
+
+        if digests.is_empty() {
+            continue;
+        }
+
+        log::info!(
+            "Purged deleted SBOMs, removing {} document(s)",
+            digests.len()
+        );
+
+        let keys = digests
+            .iter()
+            .map(|digest| StorageKey::from_sha256(digest))
+            .collect::<Vec<_>>();
+        if let Err(err) = storage.delete_many(&keys).await {
+            log::error!("Failed to remove purged SBOMs from the storage: {err:#?}");
+        }
+    }
+}
+
 pub fn default_openapi_info() -> Info {
     let mut info = Info::new("Trustify", env!("CARGO_PKG_VERSION"));
     info.description = Some("Software Supply-Chain Security API".into());
@@ -440,10 +707,11 @@ pub(crate) fn configure(svc: &mut utoipa_actix_web::service_config::ServiceConfi
                     db_ro.clone(),
                     storage,
                     analysis.clone(),
-                    cache,
+                    cache.clone(),
                 );
                 trustify_module_analysis::endpoints::configure(svc, db_ro.clone(), analysis);
                 trustify_module_user::endpoints::configure(svc);
+                trustify_module_webhook::endpoints::configure(svc, db_rw.clone(), cache);
                 trustify_module_ui::endpoints::configure(svc, ui)
             }),
     );
@@ -47,6 +47,7 @@ async fn add_osv(
                 period: Duration::from_secs(300),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: source.to_string(),
             branch: branch.map(ToString::to_string),
@@ -73,6 +74,7 @@ async fn add_cve(
                 period: Duration::from_secs(300),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: DEFAULT_SOURCE_CVEPROJECT.into(),
             years: HashSet::default(),
@@ -97,10 +99,12 @@ async fn add_nvd(
                 period: Duration::from_secs(300),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: DEFAULT_SOURCE_NVD.into(),
             years: HashSet::default(),
             start_year,
+            api: None,
         }),
     )
     .await
@@ -121,6 +125,7 @@ async fn add_clearly_defined_curations(
                 period: Duration::from_secs(60 * 60),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: DEFAULT_SOURCE_CLEARLY_DEFINED_CURATION.into(),
             types: ClearlyDefinedPackageType::all(),
@@ -144,6 +149,7 @@ async fn add_clearly_defined(
                 period: Duration::from_secs(60 * 60),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: DEFAULT_SOURCE_CLEARLY_DEFINED.into(),
             types: ClearlyDefinedPackageType::all(),
@@ -163,6 +169,7 @@ async fn add_cwe(importer: &ImporterService, name: &str, description: &str) -> a
                 period: Duration::from_secs(60 * 60 * 24),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: DEFAULT_SOURCE_CWE_CATALOG.into(),
         }),
@@ -185,6 +192,7 @@ async fn add_quay(
                 period: Duration::from_secs(60 * 10),
                 description: Some(description.into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: DEFAULT_SOURCE_QUAY.into(),
             namespace: Some(namespace.into()),
@@ -208,6 +216,7 @@ pub async fn sample_data(
             period: Duration::from_secs(300),
             description: Some("All Red Hat SBOMs".into()),
             labels: Default::default(),
+            ..Default::default()
         },
         source: "https://security.access.redhat.com/data/sbom/v1/".to_string(),
         keys: vec![
@@ -229,6 +238,7 @@ pub async fn sample_data(
                 period: Duration::from_secs(300),
                 description: Some("All Red Hat CSAF data".into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: "redhat.com".to_string(),
             v3_signatures: true,
@@ -248,6 +258,7 @@ pub async fn sample_data(
                 period: Duration::from_secs(300),
                 description: Some("Red Hat VEX files from 2024".into()),
                 labels: Default::default(),
+                ..Default::default()
             },
             source: "redhat.com".to_string(),
             v3_signatures: true,
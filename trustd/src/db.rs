@@ -1,7 +1,16 @@
 use migration::data::{self, Direction, Options, Runner};
 use postgresql_embedded::{PostgreSQL, VersionReq};
-use std::{collections::HashMap, env, fs::create_dir_all, process::ExitCode, time::Duration};
-use trustify_common::{config::Database, db};
+use std::{
+    collections::HashMap,
+    env,
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+    pin::Pin,
+    process::ExitCode,
+    time::Duration,
+};
+use tokio::io::AsyncRead;
+use trustify_common::{config::Database, db, decompress::decompress_async_read};
 use trustify_infrastructure::otel::{Tracing, init_tracing};
 use trustify_module_storage::config::StorageConfig;
 
@@ -29,6 +38,21 @@ pub enum Command {
     Refresh,
     /// Run specific data migrations
     Data(Data),
+    /// Export the database as a gzip compressed SQL dump
+    Export {
+        /// The file to write the dump to, or `-` for stdout
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Replace the database with the content of an SQL dump (DANGER)
+    ///
+    /// Dumps created by `db export` are accepted, as long as their file name ends with `.gz`.
+    Import {
+        /// The dump to import, or `-` for stdin. Files ending with `.gz` or `.xz` are
+        /// decompressed.
+        #[arg(long)]
+        input: PathBuf,
+    },
 }
 
 impl Run {
@@ -40,7 +64,34 @@ impl Run {
             Command::Migrate { up_to } => Run::migrate(database, up_to).await,
             Command::Refresh => Run::refresh(database).await,
             Command::Data(data) => data.run(Direction::Up, database).await,
+            Command::Export { output } => Run::export(database, output).await,
+            Command::Import { input } => Run::import(database, input).await,
+        }
+    }
+
+    /// Write a dump of the database to a file, or stdout.
+    async fn export(database: Database, output: PathBuf) -> anyhow::Result<ExitCode> {
+        let output = (output != Path::new("-")).then_some(output);
+        trustify_db::Database::export(&database, output.as_deref()).await?;
+
+        if let Some(output) = output {
+            log::info!("Exported database to: {}", output.display());
         }
+
+        Ok(ExitCode::SUCCESS)
+    }
+
+    /// Replace the database with the content of a dump, read from a file, or stdin.
+    async fn import(database: Database, input: PathBuf) -> anyhow::Result<ExitCode> {
+        let source: Pin<Box<dyn AsyncRead + Send>> = if input == Path::new("-") {
+            Box::pin(tokio::io::stdin())
+        } else {
+            decompress_async_read(&input).await?
+        };
+
+        trustify_db::Database::import(&database, source).await?;
+
+        Ok(ExitCode::SUCCESS)
     }
 
     async fn create(database: Database) -> anyhow::Result<ExitCode> {
@@ -0,0 +1,239 @@
+use serde::Serialize;
+use std::{convert::Infallible, process::ExitCode, time::Duration};
+use tabled::{Table, Tabled};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use trustify_common::{
+    config::Database,
+    db::{self, ReadWrite, pagination_cache::PaginationCache, query::Query},
+    model::Paginated,
+};
+use trustify_infrastructure::otel::{Tracing, init_tracing};
+use trustify_module_importer::{
+    model::{Importer, ImporterReport},
+    service::{Error, ImporterService},
+};
+
+/// Run the importer server, unless one of the management commands is used
+#[derive(clap::Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+// the flattened server arguments already use the group name "Run"
+#[group(id = "importer")]
+pub struct Run {
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+
+    #[command(flatten)]
+    pub(crate) server: trustify_server::profile::importer::Run,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// List all importers
+    List {
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Enable an importer
+    Enable {
+        /// The name of the importer
+        name: String,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Disable an importer
+    Disable {
+        /// The name of the importer
+        name: String,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Run an importer as soon as possible, ignoring its period
+    RunNow {
+        /// The name of the importer
+        name: String,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Show the latest reports of an importer
+    Logs {
+        /// The name of the importer
+        name: String,
+        /// The number of reports to show
+        #[arg(long, default_value_t = 10)]
+        last_n: u64,
+        #[command(flatten)]
+        options: Options,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Options {
+    /// Print the result as JSON, instead of a table
+    #[arg(long)]
+    pub json: bool,
+
+    // flattened commands must go last
+    //
+    /// Database configuration
+    #[command(flatten)]
+    pub database: Database,
+}
+
+impl Run {
+    pub async fn run(self) -> anyhow::Result<ExitCode> {
+        match self.command {
+            Some(command) => command.run().await,
+            None => self.server.run().await,
+        }
+    }
+}
+
+impl Command {
+    async fn run(self) -> anyhow::Result<ExitCode> {
+        init_tracing("importer-manage", Tracing::Disabled);
+
+        match self {
+            Self::List { options } => {
+                let importers = options.service().await?.list().await?;
+                options.print(&importers, importers.iter().map(ImporterRow::from))?;
+            }
+            Self::Enable { name, options } => set_enabled(&name, true, options).await?,
+            Self::Disable { name, options } => set_enabled(&name, false, options).await?,
+            Self::RunNow { name, options } => {
+                let service = options.service().await?;
+                // clearing the last run makes the importer due on its next check
+                service.reset(&name, None).await?;
+                print_importer(&service, &name, &options).await?;
+            }
+            Self::Logs {
+                name,
+                last_n,
+                options,
+            } => {
+                let service = options.service().await?;
+                if service.read(&name).await?.is_none() {
+                    return Err(Error::NotFound(name).into());
+                }
+
+                let reports = service
+                    .get_reports(
+                        &name,
+                        Query::default(),
+                        Paginated {
+                            offset: 0,
+                            limit: last_n,
+                            total: false,
+                        },
+                    )
+                    .await?
+                    .items;
+                options.print(&reports, reports.iter().map(ReportRow::from))?;
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+async fn set_enabled(name: &str, enabled: bool, options: Options) -> anyhow::Result<()> {
+    let service = options.service().await?;
+    service
+        .patch_configuration(name, None, |mut configuration| {
+            configuration.disabled = !enabled;
+            Ok::<_, Infallible>(configuration)
+        })
+        .await?;
+
+    print_importer(&service, name, &options).await
+}
+
+/// Print the current state of an importer, after it got modified
+async fn print_importer(
+    service: &ImporterService,
+    name: &str,
+    options: &Options,
+) -> anyhow::Result<()> {
+    let importer = service
+        .read(name)
+        .await?
+        .ok_or_else(|| Error::NotFound(name.to_string()))?
+        .value;
+
+    options.print(&importer, [ImporterRow::from(&importer)])
+}
+
+impl Options {
+    async fn service(&self) -> anyhow::Result<ImporterService> {
+        let db = db::Database::new(&self.database).await?;
+        // management commands don't compute totals, so there is nothing worth caching
+        let cache = PaginationCache::new(Duration::ZERO, 0);
+        Ok(ImporterService::new(ReadWrite::new(db), cache))
+    }
+
+    fn print<T, R>(&self, value: &T, rows: impl IntoIterator<Item = R>) -> anyhow::Result<()>
+    where
+        T: Serialize + ?Sized,
+        R: Tabled,
+    {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value)?);
+        } else {
+            println!("{}", Table::new(rows));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Tabled)]
+struct ImporterRow {
+    name: String,
+    enabled: bool,
+    state: String,
+    #[tabled(rename = "last run")]
+    last_run: String,
+    #[tabled(rename = "last success")]
+    last_success: String,
+    #[tabled(rename = "last error")]
+    last_error: String,
+}
+
+impl From<&Importer> for ImporterRow {
+    fn from(importer: &Importer) -> Self {
+        let data = &importer.data;
+        Self {
+            name: importer.name.clone(),
+            enabled: !data.configuration.disabled,
+            state: format!("{:?}", data.state),
+            last_run: format_time(data.last_run),
+            last_success: format_time(data.last_success),
+            last_error: data.last_error.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct ReportRow {
+    created: String,
+    items: String,
+    error: String,
+}
+
+impl From<&ImporterReport> for ReportRow {
+    fn from(report: &ImporterReport) -> Self {
+        Self {
+            created: format_time(Some(report.creation)),
+            items: report
+                .report
+                .as_ref()
+                .map(|report| report.number_of_items.to_string())
+                .unwrap_or_default(),
+            error: report.error.clone().unwrap_or_default(),
+        }
+    }
+}
+
+fn format_time(time: Option<OffsetDateTime>) -> String {
+    time.and_then(|time| time.format(&Rfc3339).ok())
+        .unwrap_or_default()
+}
@@ -11,19 +11,29 @@ use tokio::{
 };
 
 mod db;
+mod importer;
 mod openapi;
+mod purl;
+mod sbom;
+mod seed;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(clap::Subcommand, Debug)]
 pub enum Command {
     /// Run the API server
     Api(trustify_server::profile::api::Run),
-    /// Run the importer server
-    Importer(trustify_server::profile::importer::Run),
+    /// Run the importer server, or manage importers
+    Importer(importer::Run),
     /// Manage the database
     Db(db::Run),
     /// Access OpenAPI related information of the API server
     Openapi(openapi::Run),
+    /// Look up PURLs, without running the server
+    Purl(purl::Run),
+    /// Work with SBOMs, without running the server
+    Sbom(sbom::Run),
+    /// Ingest fixtures, described by a manifest, e.g. for demo environments
+    Seed(seed::Run),
 }
 
 #[derive(clap::Parser, Debug)]
@@ -45,6 +55,9 @@ impl Trustd {
             Some(Command::Importer(run)) => run.run().await,
             Some(Command::Db(run)) => run.run().await,
             Some(Command::Openapi(run)) => run.run().await,
+            Some(Command::Purl(run)) => run.run().await,
+            Some(Command::Sbom(run)) => run.run().await,
+            Some(Command::Seed(run)) => run.run().await,
             None => pm_mode().await,
         }
     }
@@ -121,7 +134,7 @@ mod tests {
 
     #[cfg(test)]
     mod test {
-        use crate::{Command, Trustd};
+        use crate::{Command, Trustd, importer};
         use clap::Parser;
         use temp_env::with_vars;
 
@@ -144,5 +157,37 @@ mod tests {
 
             assert_eq!(run.auth.clients.client_ids, vec!["frontend", "walker"])
         }
+
+        /// running the importer server must still work, next to the management commands
+        #[test]
+        fn test_importer_server_or_command() {
+            let Ok(Trustd {
+                command: Some(Command::Importer(run)),
+                ..
+            }) = Trustd::try_parse_from(["trustd", "importer", "--working-dir", "wd"])
+            else {
+                panic!("must parse into the importer command");
+            };
+            assert!(run.command.is_none());
+            assert_eq!(run.server.working_dir, Some("wd".into()));
+
+            let Ok(Trustd {
+                command: Some(Command::Importer(run)),
+                ..
+            }) = Trustd::try_parse_from(["trustd", "importer", "logs", "osv", "--last-n", "3"])
+            else {
+                panic!("must parse into the importer command");
+            };
+            assert!(matches!(
+                run.command,
+                Some(importer::Command::Logs { name, last_n: 3, .. }) if name == "osv"
+            ));
+
+            // server arguments don't mix with management commands
+            assert!(
+                Trustd::try_parse_from(["trustd", "importer", "--working-dir", "wd", "list"])
+                    .is_err()
+            );
+        }
     }
 }
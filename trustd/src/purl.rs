@@ -0,0 +1,253 @@
+use anyhow::anyhow;
+use serde::Serialize;
+use std::{process::ExitCode, str::FromStr, time::Duration};
+use tabled::{Table, Tabled};
+use trustify_common::{
+    config::Database,
+    db::{self, pagination_cache::PaginationCache, query::q},
+    model::Paginated,
+    purl::Purl,
+};
+use trustify_infrastructure::otel::{Tracing, init_tracing};
+use trustify_module_fundamental::purl::{
+    model::{
+        BasePurlHead, PurlHead, VersionedPurlHead,
+        details::purl::{PurlAdvisory, PurlDetails},
+        summary::purl::PurlSummary,
+    },
+    service::PurlService,
+};
+use trustify_module_ingestor::common::Deprecation;
+use uuid::Uuid;
+
+#[derive(clap::Args, Debug)]
+pub struct Run {
+    #[command(subcommand)]
+    pub(crate) command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Show the details of a PURL
+    Get {
+        /// The PURL, or the ID of the qualified PURL
+        purl: String,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// List PURLs, optionally filtered
+    List {
+        /// The type of the PURLs, like `maven`
+        #[arg(long = "type")]
+        r#type: Option<String>,
+        /// The namespace of the PURLs
+        #[arg(long)]
+        namespace: Option<String>,
+        /// The name of the PURLs
+        #[arg(long)]
+        name: Option<String>,
+        /// An additional query, using the same syntax as the `q` parameter of the API
+        #[arg(long)]
+        query: Option<String>,
+        /// The maximum number of PURLs to list
+        #[arg(long, default_value_t = 25)]
+        limit: u64,
+        #[command(flatten)]
+        options: Options,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Options {
+    /// The output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    // flattened commands must go last
+    //
+    /// Database configuration
+    #[command(flatten)]
+    pub database: Database,
+}
+
+impl Run {
+    pub async fn run(self) -> anyhow::Result<ExitCode> {
+        init_tracing("purl-run", Tracing::Disabled);
+
+        match self.command {
+            Command::Get { purl, options } => {
+                let (db, service) = options.connect().await?;
+
+                let details = match Uuid::parse_str(&purl) {
+                    Ok(uuid) => {
+                        service
+                            .purl_by_uuid(&uuid, Deprecation::Ignore, &db)
+                            .await?
+                    }
+                    Err(_) => {
+                        service
+                            .purl_by_purl(&Purl::from_str(&purl)?, Deprecation::Ignore, &db)
+                            .await?
+                    }
+                }
+                .ok_or_else(|| anyhow!("PURL not found: {purl}"))?;
+
+                match options.format {
+                    OutputFormat::Json => print_json(&details)?,
+                    OutputFormat::Table => print_details(&details),
+                }
+            }
+            Command::List {
+                r#type,
+                namespace,
+                name,
+                query,
+                limit,
+                options,
+            } => {
+                let (db, service) = options.connect().await?;
+
+                let query = [
+                    r#type.map(|value| format!("purl:type={value}")),
+                    namespace.map(|value| format!("purl:namespace={value}")),
+                    name.map(|value| format!("purl:name={value}")),
+                    query,
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("&");
+
+                let purls = service
+                    .purls(
+                        q(&query),
+                        Paginated {
+                            offset: 0,
+                            limit,
+                            total: false,
+                        },
+                        &db,
+                    )
+                    .await?
+                    .items;
+
+                match options.format {
+                    OutputFormat::Json => print_json(&purls)?,
+                    OutputFormat::Table => {
+                        println!("{}", Table::new(purls.iter().map(SummaryRow::from)))
+                    }
+                }
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+impl Options {
+    async fn connect(&self) -> anyhow::Result<(db::Database, PurlService)> {
+        let db = db::Database::new(&self.database).await?;
+        // a single request is being made, so there is nothing worth caching
+        let cache = PaginationCache::new(Duration::ZERO, 0);
+        Ok((db, PurlService::new(cache)))
+    }
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_details(details: &PurlDetails) {
+    println!(
+        "{}",
+        Table::new([
+            HeadRow::from(&details.base),
+            HeadRow::from(&details.version),
+            HeadRow::from(&details.head),
+        ])
+    );
+
+    if !details.advisories.is_empty() {
+        println!(
+            "{}",
+            Table::new(details.advisories.iter().flat_map(StatusRow::from_advisory))
+        );
+    }
+}
+
+#[derive(Tabled)]
+struct HeadRow {
+    kind: &'static str,
+    uuid: Uuid,
+    purl: String,
+}
+
+impl From<&BasePurlHead> for HeadRow {
+    fn from(head: &BasePurlHead) -> Self {
+        Self {
+            kind: "base",
+            uuid: head.uuid,
+            purl: head.purl.to_string(),
+        }
+    }
+}
+
+impl From<&VersionedPurlHead> for HeadRow {
+    fn from(head: &VersionedPurlHead) -> Self {
+        Self {
+            kind: "versioned",
+            uuid: head.uuid,
+            purl: head.purl.to_string(),
+        }
+    }
+}
+
+impl From<&PurlHead> for HeadRow {
+    fn from(head: &PurlHead) -> Self {
+        Self {
+            kind: "qualified",
+            uuid: head.uuid,
+            purl: head.purl.to_string(),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct StatusRow {
+    advisory: String,
+    vulnerability: String,
+    status: String,
+}
+
+impl StatusRow {
+    fn from_advisory(advisory: &PurlAdvisory) -> impl Iterator<Item = Self> + '_ {
+        advisory.status.iter().map(|status| Self {
+            advisory: advisory.head.identifier.clone(),
+            vulnerability: status.vulnerability.identifier.clone(),
+            status: status.status.clone(),
+        })
+    }
+}
+
+#[derive(Tabled)]
+struct SummaryRow {
+    uuid: Uuid,
+    purl: String,
+}
+
+impl From<&PurlSummary> for SummaryRow {
+    fn from(summary: &PurlSummary) -> Self {
+        Self {
+            uuid: summary.head.uuid,
+            purl: summary.head.purl.to_string(),
+        }
+    }
+}
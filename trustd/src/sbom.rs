@@ -0,0 +1,180 @@
+use anyhow::Context;
+use indicatif::{ProgressBar, ProgressStyle};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, TransactionTrait};
+use serde::Serialize;
+use std::{process::ExitCode, time::Duration};
+use trustify_common::{config::Database, db};
+use trustify_entity::{labels::Labels, sbom_package};
+use trustify_module_ingestor::{
+    graph::Graph,
+    service::{Cache, Format, IngestorService},
+};
+use trustify_module_storage::config::StorageConfig;
+use url::Url;
+use uuid::Uuid;
+
+#[derive(clap::Args, Debug)]
+pub struct Run {
+    #[command(subcommand)]
+    pub(crate) command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Ingest an SBOM from a local file or a URL
+    Ingest(Ingest),
+}
+
+impl Run {
+    pub async fn run(self) -> anyhow::Result<ExitCode> {
+        match self.command {
+            Command::Ingest(ingest) => ingest.run().await,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SbomFormat {
+    /// Detect the format from the content
+    #[default]
+    Auto,
+    Spdx,
+    Cyclonedx,
+}
+
+impl From<SbomFormat> for Format {
+    fn from(value: SbomFormat) -> Self {
+        match value {
+            SbomFormat::Auto => Format::SBOM,
+            SbomFormat::Spdx => Format::SPDX,
+            SbomFormat::Cyclonedx => Format::CycloneDX,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct Ingest {
+    /// The path of the SBOM file, or an HTTP(S) URL to download it from
+    pub source: String,
+
+    /// The format of the SBOM
+    #[arg(long, value_enum, default_value_t)]
+    pub format: SbomFormat,
+
+    /// Labels to add to the SBOM, in the form of `key=value`
+    #[arg(long, value_parser = parse_label)]
+    pub labels: Vec<(String, String)>,
+
+    // flattened commands must go last
+    //
+    /// Database configuration
+    #[command(flatten)]
+    pub database: Database,
+
+    /// Location of the storage
+    #[command(flatten)]
+    pub storage: StorageConfig,
+}
+
+/// The outcome of ingesting an SBOM
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct IngestedSbom {
+    pub sbom_id: Uuid,
+    pub document_id: Option<String>,
+    pub package_count: u64,
+}
+
+fn parse_label(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.into(), value.into())),
+        _ => Err(format!("invalid label '{value}', must be 'key=value'")),
+    }
+}
+
+impl Ingest {
+    pub async fn run(self) -> anyhow::Result<ExitCode> {
+        // no tracing output, stdout is reserved for the result
+
+        let progress = ProgressBar::new_spinner();
+        progress.set_style(ProgressStyle::default_spinner().template("{spinner:.green} {msg}")?);
+        progress.enable_steady_tick(Duration::from_millis(100));
+
+        let result = self.ingest(&progress).await;
+        progress.finish_and_clear();
+
+        println!("{}", serde_json::to_string_pretty(&result?)?);
+
+        Ok(ExitCode::SUCCESS)
+    }
+
+    async fn ingest(self, progress: &ProgressBar) -> anyhow::Result<IngestedSbom> {
+        progress.set_message(format!("Reading {}", self.source));
+        let data = read_source(&self.source).await?;
+
+        let db = db::Database::new(&self.database).await?;
+        let storage = self.storage.into_storage(false).await?;
+        let ingestor = IngestorService::new(Graph::new(), storage, None);
+
+        progress.set_message(format!("Ingesting {}", self.source));
+
+        let tx = db.begin().await?;
+        let result = ingestor
+            .ingest(
+                &data,
+                self.format.into(),
+                Labels::new().extend(self.labels),
+                None,
+                Cache::Skip,
+                &tx,
+            )
+            .await?;
+
+        let sbom_id = Uuid::parse_str(&result.id)?;
+        let package_count = sbom_package::Entity::find()
+            .filter(sbom_package::Column::SbomId.eq(sbom_id))
+            .count(&tx)
+            .await?;
+
+        tx.commit().await?;
+
+        for warning in &result.warnings {
+            progress.suspend(|| eprintln!("Warning: {warning}"));
+        }
+
+        Ok(IngestedSbom {
+            sbom_id,
+            document_id: result.document_id,
+            package_count,
+        })
+    }
+}
+
+/// Read the content from a local file, or download it when the source is an HTTP(S) URL.
+async fn read_source(source: &str) -> anyhow::Result<Vec<u8>> {
+    match Url::parse(source) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(reqwest::get(url)
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("failed to download {source}"))?
+            .bytes()
+            .await?
+            .to_vec()),
+        _ => tokio::fs::read(source)
+            .await
+            .with_context(|| format!("failed to read {source}")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn labels() {
+        assert_eq!(parse_label("a=b"), Ok(("a".into(), "b".into())));
+        assert_eq!(parse_label("a=b=c"), Ok(("a".into(), "b=c".into())));
+        assert_eq!(parse_label("a="), Ok(("a".into(), "".into())));
+        assert!(parse_label("=b").is_err());
+        assert!(parse_label("a").is_err());
+    }
+}
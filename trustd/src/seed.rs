@@ -0,0 +1,319 @@
+use anyhow::{Context, anyhow};
+use sea_orm::TransactionTrait;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+use trustify_common::{config::Database, db, db::pagination_cache::PaginationCache};
+use trustify_entity::labels::Labels;
+use trustify_infrastructure::otel::{Tracing, init_tracing};
+use trustify_module_fundamental::sbom_group::{model::GroupRequest, service::SbomGroupService};
+use trustify_module_ingestor::{
+    graph::Graph,
+    model::IngestResult,
+    service::{Cache, Format, IngestorService},
+};
+use trustify_module_storage::config::StorageConfig;
+
+/// Ingest a set of fixtures, described by a manifest file
+#[derive(clap::Args, Debug)]
+pub struct Run {
+    /// Directory containing the manifest and the documents it references
+    #[arg(long, env = "TRUSTD_SEED_FIXTURES_DIR")]
+    pub fixtures_dir: PathBuf,
+
+    /// Name of the manifest file, relative to the fixtures directory
+    #[arg(long, env = "TRUSTD_SEED_MANIFEST", default_value = "manifest.yaml")]
+    pub manifest: PathBuf,
+
+    /// File to write the JSON summary to, instead of printing it
+    #[arg(long, env = "TRUSTD_SEED_OUTPUT")]
+    pub output: Option<PathBuf>,
+
+    /// The maximum group name length
+    #[arg(long, env = "TRUSTD_MAX_GROUP_NAME_LENGTH", default_value_t = 255)]
+    pub max_group_name_length: usize,
+
+    // flattened commands must go last
+    //
+    /// Database configuration
+    #[command(flatten)]
+    pub database: Database,
+
+    /// Location of the storage
+    #[command(flatten)]
+    pub storage: StorageConfig,
+}
+
+/// The fixtures to ingest
+///
+/// Documents are referenced by their path, relative to the fixtures directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub sboms: Vec<SbomFixture>,
+    #[serde(default)]
+    pub advisories: Vec<AdvisoryFixture>,
+    #[serde(default)]
+    pub groups: Vec<GroupFixture>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SbomFixture {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub labels: Labels,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct AdvisoryFixture {
+    pub path: PathBuf,
+    /// The format of the advisory, detected from its content if missing
+    #[serde(default = "default_advisory_format")]
+    pub format: Format,
+    #[serde(default)]
+    pub labels: Labels,
+}
+
+fn default_advisory_format() -> Format {
+    Format::Advisory
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct GroupFixture {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Paths of the SBOMs to assign, which must be listed in the `sboms` section
+    #[serde(default)]
+    pub sboms: Vec<PathBuf>,
+}
+
+/// What was created by seeding
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Summary {
+    pub sboms: Vec<IngestedDocument>,
+    pub advisories: Vec<IngestedDocument>,
+    pub groups: Vec<CreatedGroup>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IngestedDocument {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub result: IngestResult,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CreatedGroup {
+    pub name: String,
+    pub id: String,
+    /// The IDs of the SBOMs assigned to the group
+    pub sboms: Vec<String>,
+}
+
+impl Manifest {
+    pub fn parse(data: &str) -> anyhow::Result<Self> {
+        Ok(serde_yml::from_str(data)?)
+    }
+}
+
+impl Run {
+    pub async fn run(self) -> anyhow::Result<ExitCode> {
+        init_tracing("seed-run", Tracing::Disabled);
+
+        let manifest_path = self.fixtures_dir.join(&self.manifest);
+        let manifest = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read manifest {}", manifest_path.display()))?;
+        let manifest = Manifest::parse(&manifest)
+            .with_context(|| format!("failed to parse manifest {}", manifest_path.display()))?;
+
+        let db = db::Database::new(&self.database).await?;
+        let storage = self.storage.into_storage(false).await?;
+        let ingestor = IngestorService::new(Graph::new(), storage, None);
+        // seeding never lists groups, so there are no totals to cache
+        let cache = PaginationCache::new(Duration::ZERO, 0);
+        let groups = SbomGroupService::new(self.max_group_name_length, cache);
+
+        let summary = Seeder {
+            fixtures_dir: &self.fixtures_dir,
+            ingestor: &ingestor,
+            groups: &groups,
+        }
+        .seed(manifest, &db)
+        .await?;
+
+        let summary = serde_json::to_string_pretty(&summary)?;
+        match self.output {
+            Some(output) => fs::write(output, summary)?,
+            None => println!("{summary}"),
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+struct Seeder<'a> {
+    fixtures_dir: &'a Path,
+    ingestor: &'a IngestorService,
+    groups: &'a SbomGroupService,
+}
+
+impl Seeder<'_> {
+    /// Ingest all documents of the manifest, in order, and create its groups.
+    ///
+    /// Everything is done in a single transaction, so that a failing fixture doesn't leave a
+    /// partially seeded database behind.
+    async fn seed(
+        &self,
+        manifest: Manifest,
+        db: &impl TransactionTrait,
+    ) -> anyhow::Result<Summary> {
+        let tx = db.begin().await?;
+        let mut summary = Summary::default();
+
+        for sbom in manifest.sboms {
+            let result = self
+                .ingest(&sbom.path, Format::SBOM, sbom.labels, &tx)
+                .await?;
+            summary.sboms.push(IngestedDocument {
+                path: sbom.path,
+                result,
+            });
+        }
+
+        for advisory in manifest.advisories {
+            let result = self
+                .ingest(&advisory.path, advisory.format, advisory.labels, &tx)
+                .await?;
+            summary.advisories.push(IngestedDocument {
+                path: advisory.path,
+                result,
+            });
+        }
+
+        let sbom_ids: HashMap<_, _> = summary
+            .sboms
+            .iter()
+            .map(|sbom| (sbom.path.as_path(), sbom.result.id.clone()))
+            .collect();
+
+        // an SBOM may be part of multiple groups, but assigning replaces all groups of an SBOM
+        let mut assignments = BTreeMap::<String, Vec<String>>::new();
+
+        for group in manifest.groups {
+            let sboms = group
+                .sboms
+                .iter()
+                .map(|path| {
+                    sbom_ids.get(path.as_path()).cloned().ok_or_else(|| {
+                        anyhow!(
+                            "group '{}' references SBOM {}, which is not part of the manifest",
+                            group.name,
+                            path.display()
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let id = self
+                .groups
+                .create(
+                    GroupRequest {
+                        name: group.name.clone(),
+                        parent: None,
+                        description: group.description,
+                        labels: Default::default(),
+                    },
+                    &tx,
+                )
+                .await
+                .with_context(|| format!("failed to create group '{}'", group.name))?
+                .value;
+
+            for sbom in &sboms {
+                assignments
+                    .entry(sbom.clone())
+                    .or_default()
+                    .push(id.clone());
+            }
+
+            summary.groups.push(CreatedGroup {
+                name: group.name,
+                id,
+                sboms,
+            });
+        }
+
+        for (sbom, groups) in assignments {
+            self.groups
+                .update_assignments(&sbom, None, groups, &tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(summary)
+    }
+
+    async fn ingest(
+        &self,
+        path: &Path,
+        format: Format,
+        labels: Labels,
+        tx: &(impl sea_orm::ConnectionTrait + TransactionTrait),
+    ) -> anyhow::Result<IngestResult> {
+        let full_path = self.fixtures_dir.join(path);
+        let data = fs::read(&full_path)
+            .with_context(|| format!("failed to read {}", full_path.display()))?;
+
+        self.ingestor
+            .ingest(&data, format, labels, None, Cache::Skip, tx)
+            .await
+            .with_context(|| format!("failed to ingest {}", full_path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_manifest() {
+        let manifest = Manifest::parse(
+            r#"
+sboms:
+  - path: spdx/quarkus-bom-2.13.8.Final-redhat-00004.json
+    labels:
+      type: spdx
+  - path: cyclonedx/zookeeper-3.9.2-cyclonedx.json
+advisories:
+  - path: csaf/cve-2023-33201.json
+    format: csaf
+  - path: osv/RUSTSEC-2021-0079.json
+groups:
+  - name: demo
+    sboms:
+      - spdx/quarkus-bom-2.13.8.Final-redhat-00004.json
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.sboms.len(), 2);
+        assert_eq!(manifest.sboms[0].labels, Labels::new().add("type", "spdx"));
+        assert!(manifest.sboms[1].labels.is_empty());
+        assert_eq!(manifest.advisories[0].format, Format::CSAF);
+        assert_eq!(manifest.advisories[1].format, Format::Advisory);
+        assert_eq!(manifest.groups[0].name, "demo");
+        assert_eq!(manifest.groups[0].sboms.len(), 1);
+    }
+
+    #[test]
+    fn parse_empty_manifest() {
+        assert_eq!(Manifest::parse("{}").unwrap(), Manifest::default());
+    }
+}
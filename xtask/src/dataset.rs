@@ -279,6 +279,7 @@ fn default_common(description: impl Into<String>) -> CommonImporter {
         period: Default::default(),
         description: Some(description.into()),
         labels: Default::default(),
+        ..Default::default()
     }
 }
 